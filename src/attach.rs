@@ -1,10 +1,14 @@
 use std::io;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 pub const ATTACH_PIPE_STDIN: u8 = 1;
 pub const ATTACH_PIPE_STDOUT: u8 = 2;
 pub const ATTACH_PIPE_STDERR: u8 = 3;
+/// shim 回放的历史输出：负载为 `原始 pipe(1) + 记录时间纳秒(8) + 数据`。
+pub const ATTACH_PIPE_SCROLLBACK: u8 = 4;
 
 const ATTACH_FRAME_HEADER_LEN: usize = 5;
+const ATTACH_SCROLLBACK_HEADER_LEN: usize = 9;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AttachOutputFrame {
@@ -20,12 +24,37 @@ pub fn encode_attach_output_frame(pipe: u8, payload: &[u8]) -> Vec<u8> {
     frame
 }
 
+pub fn encode_attach_scrollback_frame(
+    pipe: u8,
+    recorded_at: SystemTime,
+    payload: &[u8],
+) -> Vec<u8> {
+    let nanos = recorded_at
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos() as u64)
+        .unwrap_or_default();
+    let mut inner = Vec::with_capacity(ATTACH_SCROLLBACK_HEADER_LEN + payload.len());
+    inner.push(pipe);
+    inner.extend_from_slice(&nanos.to_be_bytes());
+    inner.extend_from_slice(payload);
+    encode_attach_output_frame(ATTACH_PIPE_SCROLLBACK, &inner)
+}
+
 #[derive(Debug, Default)]
 pub struct AttachOutputDecoder {
     buffer: Vec<u8>,
+    /// 只保留不早于该时间的回放输出；`None` 表示全部回放。
+    scrollback_since: Option<SystemTime>,
 }
 
 impl AttachOutputDecoder {
+    pub fn with_scrollback_since(scrollback_since: Option<SystemTime>) -> Self {
+        Self {
+            buffer: Vec::new(),
+            scrollback_since,
+        }
+    }
+
     pub fn push(&mut self, chunk: &[u8]) -> io::Result<Vec<AttachOutputFrame>> {
         self.buffer.extend_from_slice(chunk);
         let mut offset = 0usize;
@@ -48,17 +77,16 @@ impl AttachOutputDecoder {
                 break;
             }
 
-            if pipe != ATTACH_PIPE_STDOUT && pipe != ATTACH_PIPE_STDERR {
-                return Err(io::Error::new(
-                    io::ErrorKind::InvalidData,
-                    format!("unknown attach output pipe id {}", pipe),
-                ));
-            }
-
-            frames.push(AttachOutputFrame {
-                pipe,
-                payload: self.buffer[offset + ATTACH_FRAME_HEADER_LEN..frame_end].to_vec(),
-            });
+            let body = &self.buffer[offset + ATTACH_FRAME_HEADER_LEN..frame_end];
+            let frame = if pipe == ATTACH_PIPE_SCROLLBACK {
+                self.decode_scrollback(body)?
+            } else {
+                Some(AttachOutputFrame {
+                    pipe: validate_output_pipe(pipe)?,
+                    payload: body.to_vec(),
+                })
+            };
+            frames.extend(frame);
             offset = frame_end;
         }
 
@@ -69,6 +97,29 @@ impl AttachOutputDecoder {
         Ok(frames)
     }
 
+    fn decode_scrollback(&self, body: &[u8]) -> io::Result<Option<AttachOutputFrame>> {
+        if body.len() < ATTACH_SCROLLBACK_HEADER_LEN {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "truncated attach scrollback frame",
+            ));
+        }
+        let pipe = validate_output_pipe(body[0])?;
+        let mut nanos = [0u8; 8];
+        nanos.copy_from_slice(&body[1..ATTACH_SCROLLBACK_HEADER_LEN]);
+        let recorded_at = UNIX_EPOCH + Duration::from_nanos(u64::from_be_bytes(nanos));
+        if self
+            .scrollback_since
+            .is_some_and(|since| recorded_at < since)
+        {
+            return Ok(None);
+        }
+        Ok(Some(AttachOutputFrame {
+            pipe,
+            payload: body[ATTACH_SCROLLBACK_HEADER_LEN..].to_vec(),
+        }))
+    }
+
     pub fn finish(&self) -> io::Result<()> {
         if self.buffer.is_empty() {
             Ok(())
@@ -81,6 +132,16 @@ impl AttachOutputDecoder {
     }
 }
 
+fn validate_output_pipe(pipe: u8) -> io::Result<u8> {
+    if pipe != ATTACH_PIPE_STDOUT && pipe != ATTACH_PIPE_STDERR {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unknown attach output pipe id {}", pipe),
+        ));
+    }
+    Ok(pipe)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -112,4 +173,46 @@ mod tests {
         );
         decoder.finish().unwrap();
     }
+
+    #[test]
+    fn test_decoder_filters_scrollback_frames_by_since() {
+        let now = SystemTime::now();
+        let mut chunks = encode_attach_scrollback_frame(
+            ATTACH_PIPE_STDOUT,
+            now - Duration::from_secs(60),
+            b"old",
+        );
+        chunks.extend(encode_attach_scrollback_frame(
+            ATTACH_PIPE_STDERR,
+            now,
+            b"recent",
+        ));
+        chunks.extend(encode_attach_output_frame(ATTACH_PIPE_STDOUT, b"live"));
+
+        let mut all = AttachOutputDecoder::default();
+        let payloads = all
+            .push(&chunks)
+            .unwrap()
+            .into_iter()
+            .map(|frame| (frame.pipe, frame.payload))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            payloads,
+            vec![
+                (ATTACH_PIPE_STDOUT, b"old".to_vec()),
+                (ATTACH_PIPE_STDERR, b"recent".to_vec()),
+                (ATTACH_PIPE_STDOUT, b"live".to_vec()),
+            ]
+        );
+
+        let mut recent =
+            AttachOutputDecoder::with_scrollback_since(Some(now - Duration::from_secs(10)));
+        let payloads = recent
+            .push(&chunks)
+            .unwrap()
+            .into_iter()
+            .map(|frame| frame.payload)
+            .collect::<Vec<_>>();
+        assert_eq!(payloads, vec![b"recent".to_vec(), b"live".to_vec()]);
+    }
 }
@@ -16,6 +16,8 @@ const MIN_CONTAINER_STOP_TIMEOUT_SECS: u32 = 30;
 const DEFAULT_CONTAINER_CREATE_TIMEOUT_SECS: u32 = 240;
 const MIN_CONTAINER_CREATE_TIMEOUT_SECS: u32 = 30;
 const DEFAULT_GRPC_MAX_MESSAGE_SIZE_BYTES: u32 = 80 * 1024 * 1024;
+const DEFAULT_EVENT_LOG_CAPACITY: u64 = 10_000;
+const DEFAULT_CONTAINER_EVENTS_QUEUE_SIZE: usize = 1024;
 const DEFAULT_PERSISTENT_ROOT_DIR: &str = "/var/lib/crius";
 const DEFAULT_RUNTIME_STATE_DIR: &str = "/run/crius";
 const DEFAULT_CRI_SOCKET_URI: &str = "unix:///run/crius/crius.sock";
@@ -63,6 +65,9 @@ pub struct Config {
     /// tracing 导出配置。
     pub tracing: TracingConfig,
 
+    /// 事件外发配置。
+    pub events: EventsConfig,
+
     /// NRI 配置。
     pub nri: NriConfig,
 
@@ -87,6 +92,12 @@ pub struct ApiConfig {
     pub grpc_max_recv_msg_size: u32,
     /// 是否在 GetEvents 流中额外发送 pod-level lifecycle events。
     pub enable_pod_events: bool,
+    /// 持久化事件日志保留的事件条数，超出后淘汰最旧的记录；0 表示停用事件日志和 `WatchEvents`。
+    pub event_log_capacity: u64,
+    /// GetContainerEvents 每个订阅者待发送事件的队列上限。
+    pub container_events_queue_size: usize,
+    /// 订阅者队列溢出时的处理方式。
+    pub container_events_overflow: ContainerEventsOverflow,
     /// ListPodSandboxMetrics 中包含哪些 pod-level metrics；支持 `all`、`cpu`、`memory`、`network`、`process`、`disk`。
     pub included_pod_metrics: Vec<String>,
     /// Pod/container stats 的缓存周期（秒）；0 表示按请求即时采集。
@@ -115,6 +126,30 @@ pub struct LoggingConfig {
     pub dir: String,
     /// CRI 单条日志记录切分阈值（字节）。
     pub max_container_log_line_size: usize,
+    /// attach 回放缓冲中每个输出流保留的字节数；0 表示关闭。
+    pub attach_scrollback_bytes: usize,
+    /// daemon 内建的容器日志轮转；只作用于 daemon 分配日志路径的容器。
+    pub container_log_rotation: ContainerLogRotationConfig,
+}
+
+/// 无 kubelet 场景下的容器日志轮转配置。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct ContainerLogRotationConfig {
+    /// 是否启用内建轮转。
+    pub enabled: bool,
+    /// 当前日志达到该大小（字节）后轮转。
+    pub max_size: u64,
+    /// 保留的历史日志文件个数。
+    pub max_files: usize,
+    /// 是否 gzip 压缩历史日志。
+    pub compress: bool,
+    /// 日志大小检查周期。
+    #[serde(
+        deserialize_with = "crate::streaming::deserialize_duration",
+        serialize_with = "crate::streaming::serialize_duration"
+    )]
+    pub check_interval: std::time::Duration,
 }
 
 /// 守护进程安全配置。
@@ -168,15 +203,138 @@ pub struct MetricsConfig {
 }
 
 /// tracing 导出配置。
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct TracingConfig {
     /// 是否启用 tracing 导出。
     pub enable: bool,
-    /// tracing 导出 HTTP endpoint。
+    /// OTLP/HTTP collector endpoint；只给出根地址时自动补 `/v1/traces`。
     pub endpoint: String,
-    /// 采样率，按百万分比表示。
+    /// 采样率，按百万分比表示；只作用于根 span，带 `traceparent` 的请求沿用上游采样决定。
     pub sampling_rate_per_million: u32,
+    /// OTLP/HTTP 负载编码。
+    pub protocol: TracingProtocol,
+    /// 上报的 `service.name` resource 属性。
+    pub service_name: String,
+    /// 等待导出的 span 队列上限，队列满时新 span 被丢弃。
+    pub max_queue_size: usize,
+    /// 单次导出的 span 数上限。
+    pub max_export_batch_size: usize,
+    /// 未凑满一批时的导出周期（毫秒）。
+    pub export_interval_ms: u64,
+    /// 单次导出请求超时（毫秒）。
+    pub export_timeout_ms: u64,
+}
+
+/// OTLP/HTTP 负载编码。
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum TracingProtocol {
+    #[default]
+    #[serde(rename = "http/protobuf")]
+    HttpProtobuf,
+    #[serde(rename = "http/json")]
+    HttpJson,
+}
+
+/// GetContainerEvents 订阅者队列溢出时的处理方式。
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ContainerEventsOverflow {
+    /// 以 `RESOURCE_EXHAUSTED` 结束事件流，kubelet 随后重新 list 并订阅。
+    #[default]
+    Disconnect,
+    /// 丢弃积压事件，按当前容器状态补发一轮事件后继续推送。
+    Resync,
+}
+
+impl ContainerEventsOverflow {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Disconnect => "disconnect",
+            Self::Resync => "resync",
+        }
+    }
+}
+
+/// 事件外发配置。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EventsConfig {
+    /// 跟随持久化事件日志外发事件的 sink；需要 `api.event_log_capacity` 大于 0。
+    pub sinks: Vec<EventSinkConfig>,
+}
+
+/// 单个事件 sink；按 `type` 只读取对应的字段。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EventSinkConfig {
+    /// sink 名称，在健康状态中展示，必须唯一。
+    pub name: String,
+    /// sink 类型。
+    #[serde(rename = "type")]
+    pub sink_type: EventSinkType,
+    /// 外发的最低事件级别：`debug`、`info`、`warning` 或 `error`。
+    pub min_severity: String,
+    /// 外发的事件类型；末尾为 `*` 或 `.` 时按前缀匹配，为空表示全部。
+    pub kinds: Vec<String>,
+    /// 待投递事件队列上限，队列满时丢弃新事件。
+    pub queue_size: usize,
+    /// webhook 地址。
+    pub url: String,
+    /// webhook 请求附带的 HTTP 头。
+    pub headers: std::collections::BTreeMap<String, String>,
+    /// 单次 webhook 请求超时（毫秒）。
+    pub timeout_ms: u64,
+    /// webhook 失败后的最大重试次数。
+    pub max_retries: u32,
+    /// 首次重试前的退避时间（毫秒），之后每次翻倍，最长 30 秒。
+    pub retry_backoff_ms: u64,
+    /// syslog 地址：`udp://host:port`、`tcp://host:port` 或 `unix:///dev/log`。
+    pub address: String,
+    /// syslog facility 名称。
+    pub facility: String,
+    /// syslog APP-NAME。
+    pub app_name: String,
+    /// JSON 行文件路径。
+    pub path: String,
+    /// 文件超过该大小后轮转（字节）。
+    pub max_size_bytes: u64,
+    /// 保留的文件数，含当前文件。
+    pub max_files: usize,
+}
+
+/// 事件 sink 类型。
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum EventSinkType {
+    #[default]
+    Webhook,
+    Syslog,
+    File,
+}
+
+impl EventSinkType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Webhook => "webhook",
+            Self::Syslog => "syslog",
+            Self::File => "file",
+        }
+    }
+}
+
+/// RFC 5424 facility 名称对应的编码。
+pub fn syslog_facility_code(name: &str) -> Option<u8> {
+    const FACILITIES: &[&str] = &[
+        "kern", "user", "mail", "daemon", "auth", "syslog", "lpr", "news", "uucp", "cron",
+        "authpriv", "ftp",
+    ];
+    let name = name.trim().to_ascii_lowercase();
+    if let Some(index) = FACILITIES.iter().position(|facility| *facility == name) {
+        return Some(index as u8);
+    }
+    let local = name.strip_prefix("local")?.parse::<u8>().ok()?;
+    (local <= 7).then_some(16 + local)
 }
 
 /// 守护进程 cgroup driver 配置。
@@ -364,6 +522,8 @@ pub struct RuntimeHandlerConfig {
     pub monitor_env: Option<Vec<String>>,
     /// 是否允许该 handler 的 exec/attach/port-forward 使用 websocket 协议；未设置时继承默认值。
     pub stream_websockets: Option<bool>,
+    /// 是否允许该 handler 的 exec/attach/port-forward 使用 SPDY/3.1；未设置时继承默认值。
+    pub stream_spdy: Option<bool>,
     /// 该 handler 额外允许处理的 annotation 前缀。
     pub allowed_annotations: Vec<String>,
     /// 该 handler 默认注入到 OCI annotations 的键值对；显式请求优先。
@@ -376,6 +536,8 @@ pub struct RuntimeHandlerConfig {
     pub container_create_timeout: Option<u32>,
     /// 该 handler 的 rootfs snapshotter；为空时使用默认 `internal-overlay-untar`。
     pub snapshotter: String,
+    /// 该 handler 的容器日志驱动，格式为 `name[:key=value,...]`；为空时只写 CRI 日志文件。
+    pub log_drivers: Vec<String>,
     /// 该 handler 专属的 CNI 配置目录；为空时继承全局 network.config_dirs。
     pub cni_conf_dir: String,
     /// 该 handler 专属的 CNI 配置文件最大加载数量；未设置时继承全局 network.max_conf_num。
@@ -449,12 +611,14 @@ pub struct ResolvedRuntimeHandlerConfig {
     pub monitor_cgroup: String,
     pub monitor_env: Vec<String>,
     pub stream_websockets: bool,
+    pub stream_spdy: bool,
     pub allowed_annotations: Vec<String>,
     pub default_annotations: HashMap<String, String>,
     pub privileged_without_host_devices: bool,
     pub privileged_without_host_devices_all_devices_allowed: bool,
     pub container_create_timeout: u32,
     pub snapshotter: String,
+    pub log_drivers: Vec<String>,
 }
 
 impl Default for ResolvedRuntimeHandlerConfig {
@@ -470,12 +634,14 @@ impl Default for ResolvedRuntimeHandlerConfig {
             monitor_cgroup: String::new(),
             monitor_env: Vec::new(),
             stream_websockets: true,
+            stream_spdy: true,
             allowed_annotations: Vec::new(),
             default_annotations: HashMap::new(),
             privileged_without_host_devices: false,
             privileged_without_host_devices_all_devices_allowed: false,
             container_create_timeout: DEFAULT_CONTAINER_CREATE_TIMEOUT_SECS,
             snapshotter: "internal-overlay-untar".to_string(),
+            log_drivers: Vec::new(),
         }
     }
 }
@@ -588,6 +754,10 @@ pub struct NetworkConfig {
     /// 是否将 netns 挂载统一放到 runtime state dir 下。
     #[serde(alias = "netns_mounts_under_state_dir")]
     pub netns_mounts_under_state_dir: bool,
+    /// 预热 netns 池容量；0 表示关闭，每个 Pod 沙箱同步创建 netns。
+    pub netns_pool_size: usize,
+    /// CNI watcher 激活新配置集前是否先在临时 netns 中执行一次 ADD/DEL。
+    pub cni_reload_dry_run: bool,
     /// `crs` 本地 Pod 使用的网络域。
     pub local: NetworkDomainConfig,
     /// kubelet / CRI 调用使用的网络域。
@@ -730,6 +900,9 @@ impl Default for ApiConfig {
             grpc_max_send_msg_size: DEFAULT_GRPC_MAX_MESSAGE_SIZE_BYTES,
             grpc_max_recv_msg_size: DEFAULT_GRPC_MAX_MESSAGE_SIZE_BYTES,
             enable_pod_events: true,
+            event_log_capacity: DEFAULT_EVENT_LOG_CAPACITY,
+            container_events_queue_size: DEFAULT_CONTAINER_EVENTS_QUEUE_SIZE,
+            container_events_overflow: ContainerEventsOverflow::default(),
             included_pod_metrics: vec!["all".to_string()],
             stats_collection_period: 0,
             pod_sandbox_metrics_collection_period: 0,
@@ -746,6 +919,20 @@ impl Default for LoggingConfig {
             file: None,
             dir: "/var/log/crius".to_string(),
             max_container_log_line_size: 4096,
+            attach_scrollback_bytes: 64 * 1024,
+            container_log_rotation: ContainerLogRotationConfig::default(),
+        }
+    }
+}
+
+impl Default for ContainerLogRotationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_size: 10 * 1024 * 1024,
+            max_files: 5,
+            compress: true,
+            check_interval: std::time::Duration::from_secs(10),
         }
     }
 }
@@ -765,6 +952,45 @@ impl Default for SecurityConfig {
     }
 }
 
+impl Default for TracingConfig {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            endpoint: String::new(),
+            sampling_rate_per_million: 0,
+            protocol: TracingProtocol::default(),
+            service_name: "crius".to_string(),
+            max_queue_size: 2048,
+            max_export_batch_size: 512,
+            export_interval_ms: 5000,
+            export_timeout_ms: 10000,
+        }
+    }
+}
+
+impl Default for EventSinkConfig {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            sink_type: EventSinkType::default(),
+            min_severity: "info".to_string(),
+            kinds: Vec::new(),
+            queue_size: 1024,
+            url: String::new(),
+            headers: std::collections::BTreeMap::new(),
+            timeout_ms: 5000,
+            max_retries: 3,
+            retry_backoff_ms: 500,
+            address: String::new(),
+            facility: "daemon".to_string(),
+            app_name: "crius".to_string(),
+            path: String::new(),
+            max_size_bytes: 100 * 1024 * 1024,
+            max_files: 5,
+        }
+    }
+}
+
 impl Default for MetricsConfig {
     fn default() -> Self {
         Self {
@@ -782,6 +1008,7 @@ impl Default for MetricsConfig {
                 "runtime".to_string(),
                 "resources".to_string(),
                 "images".to_string(),
+                "operations".to_string(),
             ],
         }
     }
@@ -922,6 +1149,8 @@ impl Default for NetworkConfig {
             default_network_name: cri.default_network_name.clone(),
             disable_hostport_mapping: cri.disable_hostport_mapping,
             netns_mounts_under_state_dir: cri.netns_mounts_under_state_dir,
+            netns_pool_size: 0,
+            cni_reload_dry_run: false,
             local,
             cri,
         }
@@ -1025,6 +1254,7 @@ impl Default for Config {
             security: SecurityConfig::default(),
             metrics: MetricsConfig::default(),
             tracing: TracingConfig::default(),
+            events: EventsConfig::default(),
             nri: NriConfig::default(),
             rootless: crate::rootless::RootlessConfig::default(),
         };
@@ -1080,12 +1310,14 @@ impl RuntimeConfig {
             )?,
             monitor_env: self.monitor_env.clone(),
             stream_websockets: true,
+            stream_spdy: true,
             allowed_annotations: Vec::new(),
             default_annotations: HashMap::new(),
             privileged_without_host_devices: false,
             privileged_without_host_devices_all_devices_allowed: false,
             container_create_timeout: DEFAULT_CONTAINER_CREATE_TIMEOUT_SECS,
             snapshotter: "internal-overlay-untar".to_string(),
+            log_drivers: Vec::new(),
         };
         let mut resolved = HashMap::from([(default_handler.to_string(), default_runtime.clone())]);
 
@@ -1135,6 +1367,9 @@ impl RuntimeConfig {
                 if let Some(stream_websockets) = config.stream_websockets {
                     inherited.stream_websockets = stream_websockets;
                 }
+                if let Some(stream_spdy) = config.stream_spdy {
+                    inherited.stream_spdy = stream_spdy;
+                }
                 inherited.allowed_annotations = config.allowed_annotations.clone();
                 inherited.default_annotations = config.default_annotations.clone();
                 inherited.privileged_without_host_devices = config.privileged_without_host_devices;
@@ -1147,6 +1382,9 @@ impl RuntimeConfig {
                 if !config.snapshotter.trim().is_empty() {
                     inherited.snapshotter = config.snapshotter.trim().to_string();
                 }
+                if !config.log_drivers.is_empty() {
+                    inherited.log_drivers = config.log_drivers.clone();
+                }
                 resolved.insert(handler, inherited);
                 continue;
             }
@@ -1200,6 +1438,7 @@ impl RuntimeConfig {
                     stream_websockets: config
                         .stream_websockets
                         .unwrap_or(default_runtime.stream_websockets),
+                    stream_spdy: config.stream_spdy.unwrap_or(default_runtime.stream_spdy),
                     allowed_annotations: config.allowed_annotations.clone(),
                     default_annotations: config.default_annotations.clone(),
                     privileged_without_host_devices: config.privileged_without_host_devices,
@@ -1214,6 +1453,7 @@ impl RuntimeConfig {
                     } else {
                         config.snapshotter.trim().to_string()
                     },
+                    log_drivers: config.log_drivers.clone(),
                 },
             );
         }
@@ -1314,11 +1554,16 @@ impl RuntimeConfig {
 
 impl NetworkConfig {
     pub fn cni_config(&self) -> CniConfig {
-        self.cri_domain().cni_config()
+        let mut cni = self.cri_domain().cni_config();
+        cni.set_netns_pool_size(self.netns_pool_size);
+        cni.set_reload_dry_run(self.cni_reload_dry_run);
+        cni
     }
 
     pub fn local_cni_config(&self) -> CniConfig {
-        self.local.cni_config()
+        let mut cni = self.local.cni_config();
+        cni.set_netns_pool_size(self.netns_pool_size);
+        cni
     }
 
     pub fn cri_domain(&self) -> NetworkDomainConfig {
@@ -1437,6 +1682,16 @@ impl Config {
             &mut self.api.grpc_max_recv_msg_size,
         )?;
         apply_bool_override("CRIUS_ENABLE_POD_EVENTS", &mut self.api.enable_pod_events)?;
+        apply_u64_override("CRIUS_EVENT_LOG_CAPACITY", &mut self.api.event_log_capacity)?;
+        apply_usize_override(
+            "CRIUS_CONTAINER_EVENTS_QUEUE_SIZE",
+            &mut self.api.container_events_queue_size,
+        )?;
+        if let Some(policy) = std::env::var_os("CRIUS_CONTAINER_EVENTS_OVERFLOW") {
+            self.api.container_events_overflow =
+                parse_container_events_overflow(&policy.to_string_lossy())
+                    .map_err(Error::Config)?;
+        }
         apply_csv_override(
             "CRIUS_INCLUDED_POD_METRICS",
             &mut self.api.included_pod_metrics,
@@ -1526,6 +1781,35 @@ impl Config {
             "CRIUS_STREAM_IDLE_TIMEOUT",
             &mut self.api.streaming.port_forward_idle_timeout,
         )?;
+        apply_usize_override(
+            "CRIUS_STREAM_MAX_SESSIONS",
+            &mut self.api.streaming.max_sessions,
+        )?;
+        apply_usize_override(
+            "CRIUS_STREAM_MAX_SESSIONS_PER_CONTAINER",
+            &mut self.api.streaming.max_sessions_per_container,
+        )?;
+        apply_bool_override("CRIUS_STREAM_AUDIT", &mut self.api.streaming.audit.enabled)?;
+        apply_csv_override(
+            "CRIUS_STREAM_AUDIT_RUNTIME_HANDLERS",
+            &mut self.api.streaming.audit.runtime_handlers,
+        );
+        apply_csv_override(
+            "CRIUS_STREAM_AUDIT_NAMESPACES",
+            &mut self.api.streaming.audit.namespaces,
+        );
+        apply_bool_override(
+            "CRIUS_STREAM_AUDIT_TRANSCRIPTS",
+            &mut self.api.streaming.audit.record_transcripts,
+        )?;
+        apply_string_override(
+            "CRIUS_STREAM_AUDIT_DIR",
+            &mut self.api.streaming.audit.transcript_dir,
+        );
+        apply_u64_override(
+            "CRIUS_STREAM_AUDIT_MAX_TRANSCRIPT_BYTES",
+            &mut self.api.streaming.audit.max_transcript_bytes,
+        )?;
 
         apply_string_override("CRIUS_RUNTIME_TYPE", &mut self.runtime.runtime_type);
         apply_string_override("CRIUS_RUNTIME_PATH", &mut self.runtime.runtime_path);
@@ -1802,6 +2086,11 @@ impl Config {
             "CRIUS_NETNS_MOUNTS_UNDER_STATE_DIR",
             &mut self.network.netns_mounts_under_state_dir,
         )?;
+        apply_usize_override("CRIUS_NETNS_POOL_SIZE", &mut self.network.netns_pool_size)?;
+        apply_bool_override(
+            "CRIUS_CNI_RELOAD_DRY_RUN",
+            &mut self.network.cni_reload_dry_run,
+        )?;
         self.network.cri = self.network.cri_domain();
 
         apply_string_override("CRIUS_LOG_LEVEL", &mut self.logging.level);
@@ -1811,6 +2100,26 @@ impl Config {
             "CRIUS_MAX_CONTAINER_LOG_LINE_SIZE",
             &mut self.logging.max_container_log_line_size,
         )?;
+        apply_usize_override(
+            "CRIUS_ATTACH_SCROLLBACK_BYTES",
+            &mut self.logging.attach_scrollback_bytes,
+        )?;
+        apply_bool_override(
+            "CRIUS_CONTAINER_LOG_ROTATION",
+            &mut self.logging.container_log_rotation.enabled,
+        )?;
+        apply_u64_override(
+            "CRIUS_CONTAINER_LOG_MAX_SIZE",
+            &mut self.logging.container_log_rotation.max_size,
+        )?;
+        apply_usize_override(
+            "CRIUS_CONTAINER_LOG_MAX_FILES",
+            &mut self.logging.container_log_rotation.max_files,
+        )?;
+        apply_bool_override(
+            "CRIUS_CONTAINER_LOG_COMPRESS",
+            &mut self.logging.container_log_rotation.compress,
+        )?;
 
         apply_bool_override("CRIUS_ENABLE_METRICS", &mut self.metrics.enable)?;
         apply_string_override("CRIUS_METRICS_HOST", &mut self.metrics.host);
@@ -1836,6 +2145,11 @@ impl Config {
             "CRIUS_TRACING_SAMPLING_RATE_PER_MILLION",
             &mut self.tracing.sampling_rate_per_million,
         )?;
+        if let Some(protocol) = std::env::var_os("CRIUS_TRACING_PROTOCOL") {
+            self.tracing.protocol =
+                parse_tracing_protocol(&protocol.to_string_lossy()).map_err(Error::Config)?;
+        }
+        apply_string_override("CRIUS_TRACING_SERVICE_NAME", &mut self.tracing.service_name);
 
         apply_bool_override("CRIUS_ENABLE_NRI", &mut self.nri.enable)?;
         apply_bool_override("CRIUS_ENABLE_CDI", &mut self.nri.enable_cdi)?;
@@ -1946,6 +2260,11 @@ impl Config {
                 "api.grpc_max_recv_msg_size must be greater than zero".to_string(),
             ));
         }
+        if self.api.container_events_queue_size == 0 {
+            return Err(Error::Config(
+                "api.container_events_queue_size must be greater than zero".to_string(),
+            ));
+        }
         self.api
             .streaming
             .validate()
@@ -2319,12 +2638,34 @@ impl Config {
 
         if self.tracing.enable {
             ensure_non_empty("tracing.endpoint", &self.tracing.endpoint)?;
+            ensure_non_empty("tracing.service_name", &self.tracing.service_name)?;
+            if self.tracing.max_queue_size == 0
+                || self.tracing.max_export_batch_size == 0
+                || self.tracing.max_export_batch_size > self.tracing.max_queue_size
+            {
+                return Err(Error::Config(
+                    "tracing.max_export_batch_size must be between 1 and tracing.max_queue_size"
+                        .to_string(),
+                ));
+            }
+            if self.tracing.export_interval_ms == 0 || self.tracing.export_timeout_ms == 0 {
+                return Err(Error::Config(
+                    "tracing.export_interval_ms and tracing.export_timeout_ms must be greater than zero"
+                        .to_string(),
+                ));
+            }
         }
         if self.tracing.sampling_rate_per_million > 1_000_000 {
             return Err(Error::Config(
                 "tracing.sampling_rate_per_million must be between 0 and 1000000".to_string(),
             ));
         }
+        if !self.events.sinks.is_empty() && self.api.event_log_capacity == 0 {
+            return Err(Error::Config(
+                "events.sinks requires api.event_log_capacity to be greater than zero".to_string(),
+            ));
+        }
+        validate_event_sinks(&self.events.sinks)?;
 
         ensure_non_empty("network.plugin", &self.network.plugin)?;
         if self.network.plugin != "cni" {
@@ -2365,6 +2706,10 @@ impl Config {
                 &handler_config.snapshotter,
                 &self.image.external_snapshotters,
             )?;
+            validate_log_drivers(
+                &format!("runtime.runtimes.{handler}.log_drivers"),
+                &handler_config.log_drivers,
+            )?;
             if !handler_config.monitor_path.trim().is_empty() {
                 ensure_non_empty(
                     &format!("runtime.runtimes.{handler}.monitor_path"),
@@ -2390,6 +2735,13 @@ impl Config {
                 &format!("runtime.runtimes.{handler}.default_annotations"),
                 &handler_config.default_annotations,
             )?;
+            if handler_config.stream_websockets == Some(false)
+                && handler_config.stream_spdy == Some(false)
+            {
+                return Err(Error::Config(format!(
+                    "runtime.runtimes.{handler} must not disable both stream_websockets and stream_spdy"
+                )));
+            }
         }
         self.runtime.resolved_runtimes()?;
         self.runtime.validate_workloads()?;
@@ -2405,6 +2757,20 @@ impl Config {
             ));
         }
         validate_log_filter(&self.logging.level)?;
+        let rotation = &self.logging.container_log_rotation;
+        if rotation.enabled {
+            if rotation.max_size == 0 || rotation.max_files == 0 {
+                return Err(Error::Config(
+                    "logging.container_log_rotation max_size and max_files must be greater than zero"
+                        .to_string(),
+                ));
+            }
+            if rotation.check_interval < std::time::Duration::from_secs(1) {
+                return Err(Error::Config(
+                    "logging.container_log_rotation.check_interval must be at least 1s".to_string(),
+                ));
+            }
+        }
         if let Some(file) = &self.logging.file {
             ensure_non_empty("logging.file", file)?;
         }
@@ -35,6 +35,7 @@ fn resolved_runtimes_merge_default_and_handler_specific_entries() {
                     monitor_cgroup: None,
                     monitor_env: Some(vec!["RUST_LOG=debug".to_string()]),
                     stream_websockets: None,
+                    stream_spdy: None,
                     allowed_annotations: vec!["io.example.runtime/".to_string()],
                     default_annotations: HashMap::from([(
                         "io.example.runtime/default".to_string(),
@@ -46,6 +47,7 @@ fn resolved_runtimes_merge_default_and_handler_specific_entries() {
                     snapshotter: "internal-cached-rootfs".to_string(),
                     cni_conf_dir: String::new(),
                     cni_max_conf_num: None,
+                    log_drivers: Vec::new(),
                     inherit_default_runtime: false,
                 },
             ),
@@ -76,12 +78,14 @@ fn resolved_runtimes_merge_default_and_handler_specific_entries() {
             monitor_cgroup: expected_monitor_cgroup.clone(),
             monitor_env: vec!["PATH=/usr/bin".to_string()],
             stream_websockets: true,
+            stream_spdy: true,
             allowed_annotations: Vec::new(),
             default_annotations: HashMap::new(),
             privileged_without_host_devices: false,
             privileged_without_host_devices_all_devices_allowed: false,
             container_create_timeout: DEFAULT_CONTAINER_CREATE_TIMEOUT_SECS,
             snapshotter: "internal-overlay-untar".to_string(),
+            log_drivers: Vec::new(),
         })
     );
     assert_eq!(
@@ -97,6 +101,7 @@ fn resolved_runtimes_merge_default_and_handler_specific_entries() {
             monitor_cgroup: expected_monitor_cgroup.clone(),
             monitor_env: vec!["RUST_LOG=debug".to_string()],
             stream_websockets: true,
+            stream_spdy: true,
             allowed_annotations: vec!["io.example.runtime/".to_string()],
             default_annotations: HashMap::from([(
                 "io.example.runtime/default".to_string(),
@@ -106,6 +111,7 @@ fn resolved_runtimes_merge_default_and_handler_specific_entries() {
             privileged_without_host_devices_all_devices_allowed: false,
             container_create_timeout: MIN_CONTAINER_CREATE_TIMEOUT_SECS,
             snapshotter: "internal-cached-rootfs".to_string(),
+            log_drivers: Vec::new(),
         })
     );
     assert_eq!(
@@ -121,12 +127,14 @@ fn resolved_runtimes_merge_default_and_handler_specific_entries() {
             monitor_cgroup: expected_monitor_cgroup,
             monitor_env: vec!["PATH=/usr/bin".to_string()],
             stream_websockets: true,
+            stream_spdy: true,
             allowed_annotations: Vec::new(),
             default_annotations: HashMap::new(),
             privileged_without_host_devices: false,
             privileged_without_host_devices_all_devices_allowed: false,
             container_create_timeout: DEFAULT_CONTAINER_CREATE_TIMEOUT_SECS,
             snapshotter: "internal-overlay-untar".to_string(),
+            log_drivers: Vec::new(),
         })
     );
 }
@@ -305,6 +313,43 @@ fn network_config_accepts_netns_mounts_under_state_dir() {
     assert!(config.network.cni_config().netns_mounts_under_state_dir());
 }
 
+#[test]
+fn network_config_applies_netns_pool_size_to_both_domains() {
+    let config: Config = toml::from_str(
+        r#"
+            root = "/var/lib/crius"
+
+            [network]
+            plugin = "cni"
+            netns_pool_size = 8
+            "#,
+    )
+    .expect("netns_pool_size should deserialize");
+
+    assert_eq!(config.network.netns_pool_size, 8);
+    assert_eq!(config.network.cni_config().netns_pool_size(), 8);
+    assert_eq!(config.network.local_cni_config().netns_pool_size(), 8);
+    assert_eq!(Config::default().network.cni_config().netns_pool_size(), 0);
+}
+
+#[test]
+fn network_config_applies_cni_reload_dry_run_to_cri_domain() {
+    let config: Config = toml::from_str(
+        r#"
+            root = "/var/lib/crius"
+
+            [network]
+            plugin = "cni"
+            cni_reload_dry_run = true
+            "#,
+    )
+    .expect("cni_reload_dry_run should deserialize");
+
+    assert!(config.network.cni_reload_dry_run);
+    assert!(config.network.cni_config().reload_dry_run());
+    assert!(!Config::default().network.cni_config().reload_dry_run());
+}
+
 #[test]
 fn network_config_accepts_teardown_timeout() {
     let config: Config = toml::from_str(
@@ -570,6 +615,79 @@ fn validate_rejects_unknown_wasm_direct_backend_option() {
     ));
 }
 
+#[test]
+fn runtime_handler_log_drivers_resolve_and_validate() {
+    let mut config = Config::default();
+    config.runtime.handlers = vec!["runc".to_string(), "kata".to_string()];
+    config.runtime.runtimes.insert(
+        "kata".to_string(),
+        RuntimeHandlerConfig {
+            runtime_path: "/usr/bin/kata-runtime".to_string(),
+            runtime_root: "/run/crius/kata".to_string(),
+            log_drivers: vec![
+                "cri-file".to_string(),
+                "syslog:address=udp://127.0.0.1:514".to_string(),
+            ],
+            ..Default::default()
+        },
+    );
+
+    config.validate().expect("log drivers should validate");
+    let resolved = config.runtime.resolved_runtimes().unwrap();
+    assert_eq!(
+        resolved["kata"].log_drivers,
+        vec![
+            "cri-file".to_string(),
+            "syslog:address=udp://127.0.0.1:514".to_string(),
+        ]
+    );
+    assert!(resolved["runc"].log_drivers.is_empty());
+
+    config.runtime.runtimes.get_mut("kata").unwrap().log_drivers = vec!["forward".to_string()];
+    let err = config
+        .validate()
+        .expect_err("forward driver without address must fail validation");
+    assert!(err
+        .to_string()
+        .contains("runtime.runtimes.kata.log_drivers entry \"forward\" requires address"));
+}
+
+#[test]
+fn runtime_handler_stream_spdy_resolves_and_rejects_disabling_every_transport() {
+    let mut config = Config::default();
+    config.runtime.handlers = vec!["runc".to_string(), "kata".to_string()];
+    config.runtime.runtimes.insert(
+        "kata".to_string(),
+        RuntimeHandlerConfig {
+            runtime_path: "/usr/bin/kata-runtime".to_string(),
+            runtime_root: "/run/crius/kata".to_string(),
+            stream_spdy: Some(false),
+            ..Default::default()
+        },
+    );
+
+    config
+        .validate()
+        .expect("websocket-only handler should validate");
+    let resolved = config.runtime.resolved_runtimes().unwrap();
+    assert!(!resolved["kata"].stream_spdy);
+    assert!(resolved["kata"].stream_websockets);
+    assert!(resolved["runc"].stream_spdy);
+
+    config
+        .runtime
+        .runtimes
+        .get_mut("kata")
+        .unwrap()
+        .stream_websockets = Some(false);
+    let err = config
+        .validate()
+        .expect_err("handler without any streaming transport must fail validation");
+    assert!(err
+        .to_string()
+        .contains("runtime.runtimes.kata must not disable both stream_websockets and stream_spdy"));
+}
+
 #[test]
 fn runtime_handler_config_accepts_configured_external_snapshotter() {
     let dir = tempdir().unwrap();
@@ -1989,6 +2107,81 @@ fn streaming_config_accepts_duration_strings() {
     );
 }
 
+#[test]
+fn streaming_config_rejects_per_container_session_limit_above_node_limit() {
+    let config: Config = toml::from_str(
+        r#"
+            root = "/var/lib/crius"
+
+            [api.streaming]
+            max_sessions = 16
+            max_sessions_per_container = 4
+            "#,
+    )
+    .expect("streaming session limits should deserialize");
+    assert_eq!(config.api.streaming.max_sessions, 16);
+    assert_eq!(config.api.streaming.max_sessions_per_container, 4);
+    config
+        .api
+        .streaming
+        .validate()
+        .expect("per-container limit below node limit is valid");
+
+    let mut streaming = config.api.streaming.clone();
+    streaming.max_sessions_per_container = 32;
+    let err = streaming
+        .validate()
+        .expect_err("per-container limit above node limit must fail");
+    assert!(err
+        .to_string()
+        .contains("api.streaming.max_sessions_per_container must not exceed"));
+}
+
+#[test]
+fn streaming_audit_config_scopes_sessions_and_validates_transcripts() {
+    let config: Config = toml::from_str(
+        r#"
+            root = "/var/lib/crius"
+
+            [api.streaming.audit]
+            enabled = true
+            runtime_handlers = ["kata"]
+            namespaces = ["prod"]
+            record_transcripts = true
+            max_transcript_bytes = 1048576
+            "#,
+    )
+    .expect("streaming audit config should deserialize");
+    let audit = &config.api.streaming.audit;
+    assert_eq!(audit.transcript_dir, "/var/log/crius/streaming-audit");
+    assert_eq!(audit.max_transcript_bytes, 1_048_576);
+    assert!(audit.applies_to("kata", "default"));
+    assert!(audit.applies_to("runc", "prod"));
+    assert!(!audit.applies_to("runc", "default"));
+    config
+        .api
+        .streaming
+        .validate()
+        .expect("audit config should be valid");
+
+    let mut streaming = config.api.streaming.clone();
+    streaming.audit.transcript_dir = "relative/audit".to_string();
+    let err = streaming
+        .validate()
+        .expect_err("relative transcript dir must fail");
+    assert!(err
+        .to_string()
+        .contains("api.streaming.audit.transcript_dir must be an absolute path"));
+
+    let mut audit = crate::streaming::StreamingAuditConfig {
+        enabled: true,
+        ..Default::default()
+    };
+    assert!(audit.applies_to("runc", "default"));
+    audit.enabled = false;
+    assert!(!audit.applies_to("runc", "default"));
+}
+
 #[test]
 fn streaming_config_accepts_tls_fields() {
     let config: Config = toml::from_str(
@@ -2097,6 +2290,11 @@ fn env_overrides_take_precedence() {
             dir.path().join("crius.log").to_str().unwrap(),
         ),
         ("CRIUS_MAX_CONTAINER_LOG_LINE_SIZE", "8192"),
+        ("CRIUS_ATTACH_SCROLLBACK_BYTES", "0"),
+        ("CRIUS_CONTAINER_LOG_ROTATION", "true"),
+        ("CRIUS_CONTAINER_LOG_MAX_SIZE", "1048576"),
+        ("CRIUS_CONTAINER_LOG_MAX_FILES", "3"),
+        ("CRIUS_CONTAINER_LOG_COMPRESS", "false"),
         ("CRIUS_LOG_TO_JOURNALD", "true"),
         ("CRIUS_NO_SYNC_LOG", "true"),
         ("CRIUS_RESTRICT_OOM_SCORE_ADJ", "true"),
@@ -2349,6 +2547,17 @@ fn env_overrides_take_precedence() {
     assert_eq!(config.logging.level, "debug");
     assert!(config.logging.file.is_some());
     assert_eq!(config.logging.max_container_log_line_size, 8192);
+    assert_eq!(config.logging.attach_scrollback_bytes, 0);
+    assert_eq!(
+        config.logging.container_log_rotation,
+        ContainerLogRotationConfig {
+            enabled: true,
+            max_size: 1024 * 1024,
+            max_files: 3,
+            compress: false,
+            check_interval: std::time::Duration::from_secs(10),
+        }
+    );
 }
 
 #[test]
@@ -2468,3 +2677,115 @@ impl Drop for EnvGuard {
         }
     }
 }
+
+#[test]
+fn event_sinks_config_deserializes_and_validates() {
+    let config: Config = toml::from_str(
+        r#"
+            root = "/var/lib/crius"
+
+            [[events.sinks]]
+            name = "siem"
+            type = "webhook"
+            url = "https://siem.example.com/ingest"
+            min_severity = "warning"
+            kinds = ["container.*", "exec."]
+            headers = { Authorization = "Bearer secret" }
+
+            [[events.sinks]]
+            name = "local-syslog"
+            type = "syslog"
+            address = "unix:///dev/log"
+            facility = "local3"
+
+            [[events.sinks]]
+            name = "audit-file"
+            type = "file"
+            path = "/var/log/crius/events.jsonl"
+            "#,
+    )
+    .expect("event sinks config should deserialize");
+    let sinks = &config.events.sinks;
+    assert_eq!(sinks.len(), 3);
+    assert_eq!(sinks[0].sink_type, EventSinkType::Webhook);
+    assert_eq!(sinks[0].max_retries, 3);
+    assert_eq!(sinks[1].sink_type, EventSinkType::Syslog);
+    assert_eq!(syslog_facility_code(&sinks[1].facility), Some(19));
+    assert_eq!(sinks[2].max_files, 5);
+    validate_event_sinks(sinks).expect("event sinks should validate");
+
+    let mut invalid = sinks.clone();
+    invalid[1].name = "siem".to_string();
+    assert!(validate_event_sinks(&invalid)
+        .expect_err("duplicate names must fail")
+        .to_string()
+        .contains("duplicate event sink name"));
+
+    let mut invalid = sinks.clone();
+    invalid[1].address = "graylog:514".to_string();
+    assert!(validate_event_sinks(&invalid)
+        .expect_err("schemeless syslog address must fail")
+        .to_string()
+        .contains("events.sinks[1].address must start with udp://, tcp:// or unix://"));
+
+    let mut invalid = sinks.clone();
+    invalid[2].path = "events.jsonl".to_string();
+    assert!(validate_event_sinks(&invalid)
+        .expect_err("relative file path must fail")
+        .to_string()
+        .contains("events.sinks[2].path must be an absolute path"));
+
+    let mut config = Config::default();
+    config.api.event_log_capacity = 0;
+    config.events.sinks = sinks.clone();
+    assert!(config
+        .validate()
+        .expect_err("sinks without event log must fail")
+        .to_string()
+        .contains("events.sinks requires api.event_log_capacity"));
+}
+
+#[test]
+fn container_events_overflow_policy_parses_from_file_and_env() {
+    let config: Config = toml::from_str(
+        r#"
+            [api]
+            container_events_queue_size = 64
+            container_events_overflow = "resync"
+            "#,
+    )
+    .expect("container events settings should deserialize");
+    assert_eq!(config.api.container_events_queue_size, 64);
+    assert_eq!(
+        config.api.container_events_overflow,
+        ContainerEventsOverflow::Resync
+    );
+    assert_eq!(
+        Config::default().api.container_events_overflow,
+        ContainerEventsOverflow::Disconnect
+    );
+
+    let _lock = env_lock().lock().unwrap();
+    let _guard = EnvGuard::set_many(&[
+        ("CRIUS_CONTAINER_EVENTS_QUEUE_SIZE", "0"),
+        ("CRIUS_CONTAINER_EVENTS_OVERFLOW", "Resync"),
+    ]);
+    let mut config = Config::default();
+    let err = config
+        .apply_env_overrides()
+        .expect_err("zero queue size must fail");
+    assert_eq!(
+        config.api.container_events_overflow,
+        ContainerEventsOverflow::Resync
+    );
+    assert!(err
+        .to_string()
+        .contains("api.container_events_queue_size must be greater than zero"));
+
+    let _guard = EnvGuard::set_many(&[("CRIUS_CONTAINER_EVENTS_OVERFLOW", "buffer")]);
+    assert!(Config::default()
+        .apply_env_overrides()
+        .expect_err("unknown policy must fail")
+        .to_string()
+        .contains("expected disconnect or resync"));
+}
@@ -5,7 +5,10 @@ use crate::error::Error;
 use crate::network::MainIpPreference;
 use crate::prelude::*;
 
-use super::{CgroupDriverConfig, ExternalSnapshotterConfig};
+use super::{
+    CgroupDriverConfig, ContainerEventsOverflow, EventSinkConfig, EventSinkType,
+    ExternalSnapshotterConfig, TracingProtocol,
+};
 
 pub(super) fn rewrite_default_string(
     target: &mut String,
@@ -232,6 +235,28 @@ pub(super) fn parse_cgroup_driver(raw: &str) -> std::result::Result<CgroupDriver
     }
 }
 
+pub(super) fn parse_tracing_protocol(raw: &str) -> std::result::Result<TracingProtocol, String> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "http/protobuf" => Ok(TracingProtocol::HttpProtobuf),
+        "http/json" => Ok(TracingProtocol::HttpJson),
+        other => Err(format!(
+            "invalid tracing protocol {other}; expected http/protobuf or http/json"
+        )),
+    }
+}
+
+pub(super) fn parse_container_events_overflow(
+    raw: &str,
+) -> std::result::Result<ContainerEventsOverflow, String> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "disconnect" => Ok(ContainerEventsOverflow::Disconnect),
+        "resync" => Ok(ContainerEventsOverflow::Resync),
+        other => Err(format!(
+            "invalid container events overflow policy {other}; expected disconnect or resync"
+        )),
+    }
+}
+
 pub(super) fn parse_main_ip_preference(raw: &str) -> std::result::Result<MainIpPreference, String> {
     match raw.trim().to_ascii_lowercase().as_str() {
         "ipv4" => Ok(MainIpPreference::Ipv4),
@@ -337,7 +362,7 @@ pub(super) fn validate_included_pod_metrics(values: &[String]) -> Result<()> {
 }
 
 pub(super) fn validate_metrics_collectors(values: &[String]) -> Result<()> {
-    const ALLOWED: &[&str] = &["runtime", "resources", "images"];
+    const ALLOWED: &[&str] = &["runtime", "resources", "images", "operations", "containers"];
     if values.is_empty() {
         return Err(Error::Config(
             "metrics.collectors must contain at least one collector".to_string(),
@@ -355,6 +380,98 @@ pub(super) fn validate_metrics_collectors(values: &[String]) -> Result<()> {
     Ok(())
 }
 
+pub(super) fn validate_event_sinks(sinks: &[EventSinkConfig]) -> Result<()> {
+    let mut names = HashSet::new();
+    for (index, sink) in sinks.iter().enumerate() {
+        let field = |name: &str| format!("events.sinks[{index}].{name}");
+        ensure_non_empty(&field("name"), &sink.name)?;
+        if !names.insert(sink.name.trim()) {
+            return Err(Error::Config(format!(
+                "duplicate event sink name {:?}",
+                sink.name
+            )));
+        }
+        if !matches!(
+            sink.min_severity.trim(),
+            "debug" | "info" | "warning" | "error"
+        ) {
+            return Err(Error::Config(format!(
+                "{} must be one of debug, info, warning or error",
+                field("min_severity")
+            )));
+        }
+        if sink.kinds.iter().any(|kind| kind.trim().is_empty()) {
+            return Err(Error::Config(format!(
+                "{} must not contain empty entries",
+                field("kinds")
+            )));
+        }
+        if sink.queue_size == 0 {
+            return Err(Error::Config(format!(
+                "{} must be greater than zero",
+                field("queue_size")
+            )));
+        }
+        match sink.sink_type {
+            EventSinkType::Webhook => {
+                ensure_non_empty(&field("url"), &sink.url)?;
+                let scheme_ok = reqwest::Url::parse(sink.url.trim())
+                    .is_ok_and(|url| matches!(url.scheme(), "http" | "https"));
+                if !scheme_ok {
+                    return Err(Error::Config(format!(
+                        "{} must be an http:// or https:// URL",
+                        field("url")
+                    )));
+                }
+                if sink.timeout_ms == 0 {
+                    return Err(Error::Config(format!(
+                        "{} must be greater than zero",
+                        field("timeout_ms")
+                    )));
+                }
+            }
+            EventSinkType::Syslog => {
+                ensure_non_empty(&field("address"), &sink.address)?;
+                let address = sink.address.trim();
+                let valid = ["udp://", "tcp://", "unix://"]
+                    .iter()
+                    .any(|scheme| address.len() > scheme.len() && address.starts_with(scheme))
+                    || address.starts_with('/');
+                if !valid {
+                    return Err(Error::Config(format!(
+                        "{} must start with udp://, tcp:// or unix://",
+                        field("address")
+                    )));
+                }
+                if super::syslog_facility_code(&sink.facility).is_none() {
+                    return Err(Error::Config(format!(
+                        "{} {:?} is not a syslog facility",
+                        field("facility"),
+                        sink.facility
+                    )));
+                }
+            }
+            EventSinkType::File => {
+                ensure_non_empty(&field("path"), &sink.path)?;
+                if !Path::new(sink.path.trim()).is_absolute() {
+                    return Err(Error::Config(format!(
+                        "{} must be an absolute path",
+                        field("path")
+                    )));
+                }
+                if sink.max_size_bytes == 0 || sink.max_files == 0 {
+                    return Err(Error::Config(format!(
+                        "{} and {} must be greater than zero",
+                        field("max_size_bytes"),
+                        field("max_files")
+                    )));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
 pub(super) fn validate_configured_runtime_snapshotter(
     name: &str,
     value: &str,
@@ -372,6 +489,44 @@ pub(super) fn validate_configured_runtime_snapshotter(
     )))
 }
 
+pub(super) fn validate_log_drivers(name: &str, values: &[String]) -> Result<()> {
+    const AVAILABLE: &[&str] = &["cri-file", "journald", "json-file", "forward", "syslog"];
+    for value in values {
+        let trimmed = value.trim();
+        let (driver, options) = trimmed.split_once(':').unwrap_or((trimmed, ""));
+        if !AVAILABLE.contains(&driver) {
+            return Err(Error::Config(format!(
+                "{name} contains unknown log driver {driver:?}; available drivers: {:?}",
+                AVAILABLE
+            )));
+        }
+        let mut has_address = false;
+        for option in options
+            .split(',')
+            .filter(|option| !option.trim().is_empty())
+        {
+            let Some((key, value)) = option.split_once('=') else {
+                return Err(Error::Config(format!(
+                    "{name} entry {trimmed:?} has invalid option {option:?}; expected key=value"
+                )));
+            };
+            if value.trim().is_empty() {
+                return Err(Error::Config(format!(
+                    "{name} entry {trimmed:?} has empty option {}",
+                    key.trim()
+                )));
+            }
+            has_address |= key.trim() == "address";
+        }
+        if driver == "forward" && !has_address {
+            return Err(Error::Config(format!(
+                "{name} entry {trimmed:?} requires address=unix:///path"
+            )));
+        }
+    }
+    Ok(())
+}
+
 pub(super) fn validate_external_snapshotters(
     snapshotters: &HashMap<String, ExternalSnapshotterConfig>,
 ) -> Result<()> {
@@ -75,6 +75,7 @@ pub enum Command {
     Metrics(MetricsArgs),
     Recovery(RecoveryArgs),
     Gc(GcArgs),
+    Streaming(StreamingArgs),
     Debug(DebugArgs),
     Completion(CompletionArgs),
 }
@@ -170,22 +171,52 @@ pub struct ContainerLogsArgs {
     pub follow: bool,
     #[arg(short = 'n', long)]
     pub tail: Option<i64>,
-    #[arg(long)]
+    #[arg(long, conflicts_with = "since_time")]
     pub since: Option<String>,
+    #[arg(long, value_name = "RFC3339")]
+    pub since_time: Option<String>,
     #[arg(short = 't', long)]
     pub timestamps: bool,
+    #[arg(short = 'p', long)]
+    pub previous: bool,
+    #[arg(long)]
+    pub limit_bytes: Option<i64>,
     pub container: String,
 }
 
+#[derive(Debug, ClapArgs)]
+pub struct ContainerCpArgs {
+    #[arg(value_name = "SRC")]
+    pub source: String,
+    #[arg(value_name = "DST")]
+    pub destination: String,
+}
+
 #[derive(Debug, ClapArgs)]
 pub struct ExecArgs {
     #[command(flatten)]
     pub stream: StreamOptions,
+    #[command(flatten)]
+    pub process: ExecProcessArgs,
     pub container: String,
     #[arg(last = true, required = true)]
     pub command: Vec<String>,
 }
 
+#[derive(Clone, Debug, Default, ClapArgs)]
+pub struct ExecProcessArgs {
+    #[arg(short = 'e', long = "env")]
+    pub env: Vec<String>,
+    #[arg(short = 'w', long)]
+    pub workdir: Option<String>,
+    #[arg(short = 'u', long)]
+    pub user: Option<String>,
+    #[arg(long)]
+    pub privileged: bool,
+    #[arg(long = "cap-add")]
+    pub cap_add: Vec<String>,
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
 pub enum StopObjectType {
     Container,
@@ -475,6 +506,9 @@ pub enum ContainerCommand {
         id: String,
         #[command(flatten)]
         stream: StreamOptions,
+        /// Only replay buffered output from this recent window (e.g. 30s, 0s for none)
+        #[arg(long, value_name = "DURATION", value_parser = parse_duration)]
+        since: Option<Duration>,
     },
     Stats(ContainerStatsArgs),
     Checkpoint {
@@ -495,6 +529,11 @@ pub enum ContainerCommand {
         id: String,
     },
     Logs(ContainerLogsArgs),
+    #[command(
+        about = "Copy files between a container and the local filesystem",
+        long_about = "Copy files between a container and the local filesystem. crius reads and writes the container root filesystem itself, so the image does not need tar. Use CONTAINER:PATH for the container side, and - to read a tar archive from stdin or write one to stdout."
+    )]
+    Cp(ContainerCpArgs),
 }
 
 #[derive(Debug, Default, ClapArgs)]
@@ -747,10 +786,24 @@ pub struct RunContainerCreateOptions {
 }
 
 #[derive(Debug, Default, ClapArgs)]
-pub struct EventsArgs {}
+pub struct EventsArgs {
+    /// Replay the durable event log after this sequence number (0 for all retained events)
+    #[arg(long, value_name = "SEQ")]
+    pub since_seq: Option<i64>,
+    /// Keep streaming new events from the durable event log
+    #[arg(short = 'f', long)]
+    pub follow: bool,
+    /// Filter durable events: kind=, subject=[KIND/]ID, pod=, namespace=, severity= (minimum)
+    #[arg(long = "filter", value_name = "KEY=VALUE")]
+    pub filters: Vec<String>,
+}
 
 #[derive(Debug, Default, ClapArgs)]
-pub struct StatsArgs {}
+pub struct StatsArgs {
+    /// Show crius' own daemon, shim, pull cgroup and CNI plugin usage instead of containers
+    #[arg(long)]
+    pub runtime: bool,
+}
 
 #[derive(Debug, ClapArgs)]
 pub struct MetricsArgs {
@@ -798,6 +851,37 @@ pub struct ExecuteModeArgs {
     pub execute: bool,
 }
 
+#[derive(Debug, ClapArgs)]
+pub struct StreamingArgs {
+    #[command(subcommand)]
+    pub command: StreamingCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum StreamingCommand {
+    Sessions(StreamingSessionsArgs),
+    Kill { session: String },
+    Recordings(StreamingSessionsArgs),
+    Replay(StreamingReplayArgs),
+}
+
+#[derive(Debug, ClapArgs)]
+pub struct StreamingReplayArgs {
+    pub session: String,
+    #[arg(long, default_value_t = 1.0)]
+    pub speed: f64,
+    #[arg(long, default_value_t = 2.0)]
+    pub max_idle: f64,
+    #[arg(long)]
+    pub raw: bool,
+}
+
+#[derive(Debug, Default, ClapArgs)]
+pub struct StreamingSessionsArgs {
+    #[arg(long)]
+    pub container: Option<String>,
+}
+
 #[derive(Debug, ClapArgs)]
 pub struct DebugArgs {
     #[command(subcommand)]
@@ -812,10 +896,46 @@ pub enum DebugCommand {
     Nri,
     Security,
     Cgroups,
-    Streaming,
+    Streaming(DebugStreamingArgs),
     Metrics,
     Tracing,
     Rootless,
+    Container(DebugContainerArgs),
+}
+
+#[derive(Debug, Default, ClapArgs)]
+pub struct DebugStreamingArgs {
+    #[command(subcommand)]
+    pub command: Option<DebugStreamingCommand>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum DebugStreamingCommand {
+    Sessions(StreamingSessionsArgs),
+}
+
+#[derive(Debug, ClapArgs)]
+pub struct DebugContainerArgs {
+    #[arg(long)]
+    pub image: String,
+    #[arg(long)]
+    pub name: Option<String>,
+    #[arg(long, value_enum, default_value_t = PullPolicyArg::Missing)]
+    pub pull: PullPolicyArg,
+    #[arg(long = "target-rootfs")]
+    pub target_rootfs: bool,
+    #[arg(short = 'e', long = "env")]
+    pub env: Vec<String>,
+    #[arg(long)]
+    pub privileged: bool,
+    #[arg(long = "cap-add")]
+    pub cap_add: Vec<String>,
+    #[arg(short = 't', long)]
+    pub tty: bool,
+    #[arg(long, value_enum, default_value_t = StreamProtocolArg::Websocket)]
+    pub protocol: StreamProtocolArg,
+    pub container: String,
+    pub command: Vec<String>,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
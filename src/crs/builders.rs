@@ -6,7 +6,8 @@ use crate::{
     crs::{
         args::{
             ContainerCreateArgs, ContainerCreateOptions, ContainerResourceArgs,
-            ContainerSecurityArgs, ImageAuthArgs, PodCreateArgs, SandboxSecurityArgs,
+            ContainerSecurityArgs, ExecProcessArgs, ImageAuthArgs, PodCreateArgs,
+            SandboxSecurityArgs,
         },
         parsers::{
             parse_auth_json, parse_byte_size, parse_device, parse_env_file, parse_hugepage,
@@ -15,6 +16,7 @@ use crate::{
             KeyValuePair, ParsedUser, ResourceFragment,
         },
     },
+    proto::local::v1::{ExecOptions, ExecUser},
     proto::runtime::v1::{
         AuthConfig, Capability, CdiDevice, ContainerConfig, ContainerMetadata, DnsConfig,
         ImageSpec, Int64Value, KeyValue, LinuxContainerConfig, LinuxContainerResources,
@@ -66,6 +68,41 @@ pub(crate) fn build_container_config(
     build_container_config_from_parts(&args.image, &args.command, &args.options)
 }
 
+/// 没有任何覆盖项时返回 `None`，调用方继续走 CRI exec。
+pub(crate) fn build_exec_options(args: &ExecProcessArgs) -> Result<Option<ExecOptions>, String> {
+    for value in &args.env {
+        parse_key_value("--env", value)?;
+    }
+    if let Some(workdir) = args.workdir.as_deref() {
+        if !workdir.starts_with('/') {
+            return Err(format!(
+                "invalid --workdir \"{workdir}\": expected an absolute path"
+            ));
+        }
+    }
+    let user = match args.user.as_deref().map(parse_user).transpose()? {
+        Some(ParsedUser::Id { uid, gid }) => Some(ExecUser {
+            uid,
+            gid: gid.map(|value| Int64Value { value }),
+        }),
+        Some(ParsedUser::Name(name)) => {
+            return Err(format!(
+                "invalid --user \"{name}\": exec requires a numeric UID or UID:GID"
+            ));
+        }
+        None => None,
+    };
+
+    let options = ExecOptions {
+        env: args.env.clone(),
+        workdir: args.workdir.clone().unwrap_or_default(),
+        user,
+        privileged: args.privileged,
+        add_capabilities: args.cap_add.clone(),
+    };
+    Ok((options != ExecOptions::default()).then_some(options))
+}
+
 pub(crate) fn build_auth_config(args: &ImageAuthArgs) -> Result<Option<AuthConfig>, String> {
     let sources = [
         args.auth_json.is_some(),
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use crate::crs::{
     args::StreamOptions, client::CrsClient, context::CliContext, error::CliError,
     error::CommandResult, streaming,
@@ -9,6 +11,7 @@ pub(crate) async fn handle(
     client: &CrsClient,
     id: String,
     stream: StreamOptions,
+    since: Option<Duration>,
 ) -> Result<CommandResult, CliError> {
     let mut options = streaming::AttachStreamOptions::from_args(id, stream)?;
     let mut runtime = client.runtime()?;
@@ -32,6 +35,15 @@ pub(crate) async fn handle(
         })
         .await?
         .into_inner();
-    options.stream_url = Some(response.url);
+    options.stream_url = Some(with_scrollback_since(response.url, since));
     streaming::attach(options).await
 }
+
+/// shim 默认回放全部缓冲输出；`--since` 通过 streaming URL 告知服务端只回放最近的部分。
+fn with_scrollback_since(url: String, since: Option<Duration>) -> String {
+    let Some(since) = since else {
+        return url;
+    };
+    let separator = if url.contains('?') { '&' } else { '?' };
+    format!("{url}{separator}sinceSeconds={}", since.as_secs())
+}
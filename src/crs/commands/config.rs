@@ -68,6 +68,8 @@ async fn handle_reload_status(
                 "lastReload": view.last_reload,
                 "lastError": view.last_error,
                 "cniWatcher": view.cni_watcher,
+                "cniActive": view.cni_active,
+                "cniLastKnownGood": reload.get("cniLastKnownGood"),
             }))
             .with_warnings(warnings),
     )
@@ -111,6 +113,8 @@ fn reload_has_status_fields(reload: &serde_json::Value) -> bool {
         "lastCniWatchAtUnixMillis",
         "lastCniWatchError",
         "cniWatchDirs",
+        "cniLastKnownGood",
+        "lastCniRejectedError",
     ]
     .iter()
     .any(|key| reload.get(*key).is_some())
@@ -284,6 +288,7 @@ fn reload_status_view(
                 "lastReloadError",
                 "watcherLastError",
                 "lastCniWatchError",
+                "lastCniRejectedError",
                 "error",
             ],
             "last error",
@@ -300,9 +305,36 @@ fn reload_status_view(
             "CNI watcher",
             warnings,
         ),
+        cni_active: cni_active_display(reload),
     }
 }
 
+/// 当前生效的 CNI 配置快照（last-known-good）；尚未激活时显示 none。
+fn cni_active_display(reload: &serde_json::Value) -> String {
+    let Some(snapshot) = reload
+        .get("cniLastKnownGood")
+        .filter(|value| value.is_object())
+    else {
+        return "none".to_string();
+    };
+    let generation = snapshot
+        .get("generation")
+        .and_then(serde_json::Value::as_u64)
+        .unwrap_or_default();
+    let networks = snapshot
+        .get("loadedNetworks")
+        .and_then(serde_json::Value::as_array)
+        .map(|networks| {
+            networks
+                .iter()
+                .filter_map(serde_json::Value::as_str)
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+        .unwrap_or_default();
+    format!("gen {generation}: {networks}")
+}
+
 pub(crate) fn field_string(
     value: &serde_json::Value,
     keys: &[&str],
@@ -53,6 +53,7 @@ pub(crate) async fn handle(
         } => handle_update(ctx, client, id, resources, annotations).await,
         ContainerCommand::ReopenLog { id } => handle_reopen_log(ctx, client, id).await,
         ContainerCommand::Logs(_) => Err(CliError::not_implemented("crs container logs")),
+        ContainerCommand::Cp(_) => Err(CliError::not_implemented("crs container cp")),
     }
 }
 
@@ -136,27 +137,38 @@ pub(crate) async fn exec_sync_with_command(
     args: crate::crs::args::ExecArgs,
     command_name: &'static str,
 ) -> Result<CommandResult, CliError> {
+    let exec_options = crate::crs::builders::build_exec_options(&args.process)
+        .map_err(|message| CliError::invalid_input(message).with_command(command_name))?;
     let options = crate::crs::streaming::ExecStreamOptions::from_args(
         args.container,
         args.command,
         args.stream,
     )?;
-    let mut runtime = client.runtime()?;
+    let request = ExecSyncRequest {
+        container_id: options.container_id.clone(),
+        cmd: options.command.clone(),
+        timeout: 0,
+    };
     let response = client
         .with_rpc_timeout(async {
-            runtime
-                .exec_sync(ExecSyncRequest {
-                    container_id: options.container_id.clone(),
-                    cmd: options.command.clone(),
-                    timeout: 0,
-                })
-                .await
-                .map_err(|status| {
-                    CliError::from_tonic_status(status)
-                        .with_command(command_name)
-                        .with_endpoint(client.endpoint())
-                        .with_object(format!("container {}", options.container_id))
-                })
+            let result = match exec_options {
+                Some(exec_options) => {
+                    client
+                        .local()?
+                        .exec_sync(crate::proto::local::v1::LocalExecSyncRequest {
+                            request: Some(request),
+                            options: Some(exec_options),
+                        })
+                        .await
+                }
+                None => client.runtime()?.exec_sync(request).await,
+            };
+            result.map_err(|status| {
+                CliError::from_tonic_status(status)
+                    .with_command(command_name)
+                    .with_endpoint(client.endpoint())
+                    .with_object(format!("container {}", options.container_id))
+            })
         })
         .await?
         .into_inner();
@@ -708,16 +720,16 @@ fn container_status_error(
         .with_object(format!("container {id}"))
 }
 
-struct ContainerOperationRender {
-    kind: &'static str,
-    pod_id: String,
-    container_id: String,
-    image: String,
-    action: &'static str,
-    summary: serde_json::Value,
+pub(crate) struct ContainerOperationRender {
+    pub(crate) kind: &'static str,
+    pub(crate) pod_id: String,
+    pub(crate) container_id: String,
+    pub(crate) image: String,
+    pub(crate) action: &'static str,
+    pub(crate) summary: serde_json::Value,
 }
 
-fn render_container_operation(
+pub(crate) fn render_container_operation(
     ctx: &CliContext,
     client: &CrsClient,
     operation: ContainerOperationRender,
@@ -0,0 +1,310 @@
+use std::ffi::OsStr;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use futures::StreamExt;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+
+use crate::crs::{
+    args::ContainerCpArgs,
+    client::CrsClient,
+    commands::container::{render_container_operation, ContainerOperationRender},
+    context::CliContext,
+    error::{CliError, CommandResult},
+};
+use crate::proto::local::v1::{CopyFromContainerRequest, CopyToContainerRequest};
+use crate::runtime::container_copy::{
+    ChunkReader, ChunkWriter, ContainerCopyError, CopyRoot, CopySummary,
+};
+
+const COMMAND: &str = "crs container cp";
+const COPY_CHUNK_BYTES: usize = 64 * 1024;
+const COPY_CHANNEL_CAPACITY: usize = 16;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CopyEndpoint {
+    Container { id: String, path: String },
+    Local(PathBuf),
+    Stdio,
+}
+
+pub(crate) async fn handle(
+    ctx: &CliContext,
+    client: &CrsClient,
+    args: ContainerCpArgs,
+) -> Result<CommandResult, CliError> {
+    let source = parse_copy_endpoint(&args.source);
+    let destination = parse_copy_endpoint(&args.destination);
+    let (container_id, summary) = match (source, destination) {
+        (CopyEndpoint::Container { .. }, CopyEndpoint::Container { .. }) => {
+            return Err(
+                CliError::invalid_input("copying between two containers is not supported")
+                    .with_command(COMMAND),
+            );
+        }
+        (CopyEndpoint::Container { id, path }, destination) => {
+            ensure_copy_path(&id, &path)?;
+            let Some(summary) =
+                copy_from_container(client, &id, &path, destination, &args.source).await?
+            else {
+                // 归档已写到标准输出，不能再混入渲染结果。
+                return Ok(CommandResult::success());
+            };
+            (id, summary)
+        }
+        (source, CopyEndpoint::Container { id, path }) => {
+            ensure_copy_path(&id, &path)?;
+            let summary = copy_to_container(client, &id, &path, source, &args.source).await?;
+            (id, summary)
+        }
+        _ => {
+            return Err(CliError::invalid_input(
+                "one of SRC and DST must be CONTAINER:PATH; prefix local paths containing ':' with ./",
+            )
+            .with_command(COMMAND));
+        }
+    };
+
+    render_container_operation(
+        ctx,
+        client,
+        ContainerOperationRender {
+            kind: "ContainerCopy",
+            container_id: container_id.clone(),
+            pod_id: String::new(),
+            image: String::new(),
+            action: "copied",
+            summary: serde_json::json!({
+                "containerId": container_id,
+                "source": args.source,
+                "destination": args.destination,
+                "entries": summary.entries,
+                "bytes": summary.bytes,
+            }),
+        },
+    )
+}
+
+async fn copy_from_container(
+    client: &CrsClient,
+    container_id: &str,
+    path: &str,
+    destination: CopyEndpoint,
+    source_display: &str,
+) -> Result<Option<CopySummary>, CliError> {
+    let extract_target = match &destination {
+        CopyEndpoint::Local(local_path) => Some(local_destination(local_path)?),
+        _ => None,
+    };
+    let mut local = client.local()?;
+    let mut stream = client
+        .with_rpc_timeout(async {
+            local
+                .copy_from_container(CopyFromContainerRequest {
+                    container_id: container_id.to_string(),
+                    path: path.to_string(),
+                })
+                .await
+                .map_err(|status| copy_status_error(status, client, source_display))
+        })
+        .await?
+        .into_inner();
+
+    let Some((root, target)) = extract_target else {
+        let mut stdout = std::io::stdout().lock();
+        while let Some(chunk) = stream.next().await {
+            let chunk =
+                chunk.map_err(|status| copy_status_error(status, client, source_display))?;
+            stdout
+                .write_all(&chunk.data)
+                .map_err(|err| CliError::internal(format!("failed to write archive: {err}")))?;
+        }
+        stdout
+            .flush()
+            .map_err(|err| CliError::internal(format!("failed to write archive: {err}")))?;
+        return Ok(None);
+    };
+
+    let (tx, mut rx) = mpsc::channel::<std::io::Result<Vec<u8>>>(COPY_CHANNEL_CAPACITY);
+    let extract = tokio::task::spawn_blocking(move || {
+        root.extract(&target, ChunkReader::new(move || rx.blocking_recv()))
+    });
+    let mut stream_error = None;
+    while let Some(chunk) = stream.next().await {
+        match chunk {
+            Ok(chunk) => {
+                if tx.send(Ok(chunk.data)).await.is_err() {
+                    break;
+                }
+            }
+            Err(status) => {
+                let _ = tx
+                    .send(Err(std::io::Error::other(status.message().to_string())))
+                    .await;
+                stream_error = Some(status);
+                break;
+            }
+        }
+    }
+    drop(tx);
+
+    let extracted = extract
+        .await
+        .map_err(|err| CliError::internal(format!("copy task failed: {err}")))?;
+    if let Some(status) = stream_error {
+        return Err(copy_status_error(status, client, source_display));
+    }
+    extracted.map(Some).map_err(local_copy_error)
+}
+
+async fn copy_to_container(
+    client: &CrsClient,
+    container_id: &str,
+    path: &str,
+    source: CopyEndpoint,
+    source_display: &str,
+) -> Result<CopySummary, CliError> {
+    let archive_source = match &source {
+        CopyEndpoint::Local(local_path) => Some(local_source(local_path, source_display)?),
+        _ => None,
+    };
+    let (tx, rx) = mpsc::channel::<Vec<u8>>(COPY_CHANNEL_CAPACITY);
+    let producer = tokio::task::spawn_blocking(move || match archive_source {
+        Some((root, name)) => root
+            .archive(
+                &name,
+                ChunkWriter::new(COPY_CHUNK_BYTES, move |data| {
+                    tx.blocking_send(data)
+                        .map_err(|_| std::io::Error::from(std::io::ErrorKind::BrokenPipe))
+                }),
+            )
+            .map(|_| ()),
+        None => {
+            let mut stdin = std::io::stdin().lock();
+            loop {
+                let mut chunk = vec![0u8; COPY_CHUNK_BYTES];
+                let read = stdin.read(&mut chunk)?;
+                if read == 0 {
+                    return Ok(());
+                }
+                chunk.truncate(read);
+                if tx.blocking_send(chunk).is_err() {
+                    return Ok(());
+                }
+            }
+        }
+    });
+
+    let header = CopyToContainerRequest {
+        container_id: container_id.to_string(),
+        path: path.to_string(),
+        data: Vec::new(),
+    };
+    let requests =
+        futures::stream::once(async move { header }).chain(ReceiverStream::new(rx).map(|data| {
+            CopyToContainerRequest {
+                data,
+                ..Default::default()
+            }
+        }));
+    let mut local = client.local()?;
+    let response = local.copy_to_container(requests).await;
+    let produced = producer
+        .await
+        .map_err(|err| CliError::internal(format!("copy task failed: {err}")))?;
+    // 本地打包失败时服务端只会看到被截断的归档，优先报告本地原因。
+    produced.map_err(local_copy_error)?;
+    let response = response
+        .map_err(|status| copy_status_error(status, client, source_display))?
+        .into_inner();
+    Ok(CopySummary {
+        entries: response.entries,
+        bytes: response.bytes,
+    })
+}
+
+/// 与 docker cp 一致：冒号前不含 `/` 时视为容器；本地路径含冒号时可写成 `./a:b`。
+fn parse_copy_endpoint(raw: &str) -> CopyEndpoint {
+    if raw == "-" {
+        return CopyEndpoint::Stdio;
+    }
+    match raw.split_once(':') {
+        Some((id, path)) if !id.is_empty() && !id.contains('/') => CopyEndpoint::Container {
+            id: id.to_string(),
+            path: path.to_string(),
+        },
+        _ => CopyEndpoint::Local(PathBuf::from(raw)),
+    }
+}
+
+fn ensure_copy_path(container_id: &str, path: &str) -> Result<(), CliError> {
+    if path.is_empty() {
+        return Err(CliError::invalid_input("container path must not be empty")
+            .with_command(COMMAND)
+            .with_object(format!("container {container_id}")));
+    }
+    Ok(())
+}
+
+/// 本地目标是已有目录时解包到其中，否则在父目录中以目标名落地。
+fn local_destination(path: &Path) -> Result<(CopyRoot, String), CliError> {
+    if path.is_dir() {
+        return Ok((
+            CopyRoot::for_directory(path).map_err(local_copy_error)?,
+            ".".to_string(),
+        ));
+    }
+    let name = path.file_name().and_then(OsStr::to_str).ok_or_else(|| {
+        CliError::invalid_input(format!("invalid destination {}", path.display()))
+            .with_command(COMMAND)
+    })?;
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    Ok((
+        CopyRoot::for_directory(parent).map_err(local_copy_error)?,
+        format!("/{name}"),
+    ))
+}
+
+/// 本地源以其父目录为根打包；`dir/.` 只打包目录内容。
+fn local_source(path: &Path, raw: &str) -> Result<(CopyRoot, String), CliError> {
+    std::fs::symlink_metadata(path).map_err(|err| {
+        CliError::invalid_input(format!("cannot read {}: {err}", path.display()))
+            .with_command(COMMAND)
+    })?;
+    let name = path.file_name().and_then(OsStr::to_str);
+    if raw == "." || raw.ends_with("/.") || name.is_none() {
+        return Ok((
+            CopyRoot::for_directory(path).map_err(local_copy_error)?,
+            "/.".to_string(),
+        ));
+    }
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let suffix = if raw.ends_with('/') { "/" } else { "" };
+    Ok((
+        CopyRoot::for_directory(parent).map_err(local_copy_error)?,
+        format!("/{}{suffix}", name.unwrap_or_default()),
+    ))
+}
+
+fn local_copy_error(err: ContainerCopyError) -> CliError {
+    match err {
+        ContainerCopyError::NotFound(message) | ContainerCopyError::InvalidArgument(message) => {
+            CliError::invalid_input(message).with_command(COMMAND)
+        }
+        other => CliError::internal(other.to_string()).with_command(COMMAND),
+    }
+}
+
+fn copy_status_error(status: tonic::Status, client: &CrsClient, object: &str) -> CliError {
+    CliError::from_tonic_status(status)
+        .with_command(COMMAND)
+        .with_endpoint(client.endpoint())
+        .with_object(object.to_string())
+}
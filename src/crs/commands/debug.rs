@@ -1,10 +1,11 @@
 use crate::crs::{
-    args::{DebugArgs, DebugCommand},
+    args::{DebugArgs, DebugCommand, DebugStreamingCommand},
     client::CrsClient,
     commands::{
         config::{load_effective_config, value_to_display},
-        runtime,
+        debug_container, runtime,
         status::{parse_info_map, render_and_print},
+        streaming,
     },
     context::CliContext,
     error::{CliError, CommandResult},
@@ -12,7 +13,8 @@ use crate::crs::{
 };
 use crate::proto::{
     diagnostics::v1::{
-        NriStatusRequest, SecurityStatusRequest, ServerInfoRequest, ShimStatusRequest,
+        EventSinkStatusRequest, NriStatusRequest, SecurityStatusRequest, ServerInfoRequest,
+        ShimStatusRequest,
     },
     runtime::v1::{RuntimeConfigRequest, StatusRequest, VersionRequest},
 };
@@ -29,10 +31,16 @@ pub(crate) async fn handle(
         DebugCommand::Nri => debug_nri(ctx, client).await,
         DebugCommand::Security => debug_security(ctx, client).await,
         DebugCommand::Cgroups => debug_cgroups(ctx, client).await,
-        DebugCommand::Streaming => debug_streaming(ctx, client).await,
+        DebugCommand::Streaming(args) => match args.command {
+            Some(DebugStreamingCommand::Sessions(args)) => {
+                streaming::handle_sessions(ctx, client, args, "crs debug streaming sessions").await
+            }
+            None => debug_streaming(ctx, client).await,
+        },
         DebugCommand::Metrics => debug_metrics(ctx, client).await,
         DebugCommand::Tracing => debug_tracing(ctx, client).await,
         DebugCommand::Rootless => debug_rootless(ctx, client).await,
+        DebugCommand::Container(args) => debug_container::handle(ctx, client, args).await,
     }
 }
 
@@ -321,13 +329,17 @@ async fn debug_streaming(ctx: &CliContext, client: &CrsClient) -> Result<Command
 async fn debug_metrics(ctx: &CliContext, client: &CrsClient) -> Result<CommandResult, CliError> {
     let mut warnings = Vec::new();
     let (config, _) = required_config(client, "crs debug metrics", &mut warnings).await?;
+    let event_sinks = event_sink_status(client, &mut warnings).await;
+    let sinks_healthy = event_sinks
+        .iter()
+        .all(|sink| sink["healthy"].as_bool().unwrap_or(false));
     render_debug(
         ctx,
         client,
         DebugRender {
             kind: "DebugMetrics",
             check: "metrics".to_string(),
-            status: "ok".to_string(),
+            status: status_from_bool(sinks_healthy).to_string(),
             message: "metrics diagnostics summary".to_string(),
             details: serde_json::json!({
                 "enabled": lookup_display(&config, &["/metrics/enabled"]).unwrap_or_else(|| "unknown".to_string()),
@@ -335,12 +347,74 @@ async fn debug_metrics(ctx: &CliContext, client: &CrsClient) -> Result<CommandRe
                 "tls": lookup_display(&config, &["/metrics/tls/enabled", "/metrics/tls"]),
                 "collector": lookup_display(&config, &["/metrics/collector"]),
                 "podMetrics": lookup_display(&config, &["/metrics/podMetrics"]),
+                "eventSinks": event_sinks,
             }),
             warnings,
         },
     )
 }
 
+/// 事件 sink 健康状态；旧 daemon 没有该 RPC 时只记录警告。
+async fn event_sink_status(
+    client: &CrsClient,
+    warnings: &mut Vec<String>,
+) -> Vec<serde_json::Value> {
+    let response = match client.diagnostics() {
+        Ok(mut diagnostics) => {
+            client
+                .with_rpc_timeout(async {
+                    diagnostics
+                        .event_sink_status(EventSinkStatusRequest {})
+                        .await
+                        .map_err(|status| {
+                            CliError::from_diagnostics_status(status, client.endpoint())
+                        })
+                })
+                .await
+        }
+        Err(error) => Err(error),
+    };
+    let response = match response {
+        Ok(response) => response.into_inner(),
+        Err(error) => {
+            warnings.push(format!("failed to load event sink status: {error}"));
+            return Vec::new();
+        }
+    };
+    warnings.extend(response.warnings);
+    let timestamp = |nanos: i64| {
+        (nanos > 0).then(|| {
+            chrono::DateTime::from_timestamp_nanos(nanos)
+                .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+        })
+    };
+    response
+        .sinks
+        .into_iter()
+        .map(|sink| {
+            if !sink.healthy {
+                warnings.push(format!(
+                    "event sink {} is failing: {}",
+                    sink.name, sink.last_error
+                ));
+            }
+            serde_json::json!({
+                "name": sink.name,
+                "type": sink.sink_type,
+                "target": sink.target,
+                "healthy": sink.healthy,
+                "queued": sink.queued,
+                "delivered": sink.delivered,
+                "failed": sink.failed,
+                "dropped": sink.dropped,
+                "lastError": (!sink.last_error.is_empty()).then_some(sink.last_error),
+                "lastDeliveredAt": timestamp(sink.last_delivered_unix_nanos),
+                "lastFailedAt": timestamp(sink.last_failed_unix_nanos),
+            })
+        })
+        .collect()
+}
+
 async fn debug_tracing(ctx: &CliContext, client: &CrsClient) -> Result<CommandResult, CliError> {
     let mut warnings = Vec::new();
     let (config, _) = required_config(client, "crs debug tracing", &mut warnings).await?;
@@ -366,6 +440,16 @@ async fn debug_tracing(ctx: &CliContext, client: &CrsClient) -> Result<CommandRe
 
 async fn debug_rootless(ctx: &CliContext, client: &CrsClient) -> Result<CommandResult, CliError> {
     let response = security_status(client, "crs debug rootless").await?;
+    let mut warnings = response.warnings.clone();
+    let port_forwards = if response.rootless_port_forwards_json.trim().is_empty() {
+        serde_json::json!([])
+    } else {
+        parse_json_or_raw(
+            &response.rootless_port_forwards_json,
+            "portForwardsRaw",
+            &mut warnings,
+        )
+    };
     render_debug(
         ctx,
         client,
@@ -386,10 +470,12 @@ async fn debug_rootless(ctx: &CliContext, client: &CrsClient) -> Result<CommandR
             .to_string(),
             details: serde_json::json!({
                 "enabled": response.rootless_enabled,
+                "networkMode": response.rootless_network_mode,
+                "portForwards": port_forwards,
                 "seccompAvailable": response.seccomp_available,
                 "apparmorAvailable": response.apparmor_available,
             }),
-            warnings: response.warnings,
+            warnings,
         },
     )
 }
@@ -0,0 +1,249 @@
+use tokio::signal::unix::{signal, SignalKind};
+use tokio::sync::oneshot;
+
+use crate::crs::{
+    args::{
+        ContainerCreateArgs, ContainerCreateOptions, ContainerSecurityArgs, DebugContainerArgs,
+        StreamOptions,
+    },
+    builders::build_container_config,
+    client::CrsClient,
+    commands::run::{ensure_image, start_container},
+    context::CliContext,
+    error::{CliError, CommandResult},
+    streaming,
+};
+use crate::proto::local::v1::CreateDebugContainerRequest;
+use crate::proto::runtime::v1::{AttachRequest, RemoveContainerRequest, StopContainerRequest};
+
+const COMMAND: &str = "crs debug container";
+
+pub(crate) async fn handle(
+    ctx: &CliContext,
+    client: &CrsClient,
+    args: DebugContainerArgs,
+) -> Result<CommandResult, CliError> {
+    if args.container.trim().is_empty() {
+        return Err(
+            CliError::invalid_input("target container id must not be empty").with_command(COMMAND),
+        );
+    }
+    if args.image.trim().is_empty() {
+        return Err(CliError::invalid_input("image must not be empty").with_command(COMMAND));
+    }
+
+    // 整个命令期间接管 SIGINT/SIGTERM：创建完成前被中断时由守护进程的启动租约清理，
+    // 创建之后则先停止并删除调试容器再退出。
+    let terminated = termination_signal();
+    tokio::pin!(terminated);
+    let container_id = tokio::select! {
+        result = async {
+            ensure_image(client, &args.image, args.pull, COMMAND).await?;
+            create_debug_container(client, &args).await
+        } => result?,
+        result = &mut terminated => {
+            result?;
+            return Err(CliError::interrupted().with_command(COMMAND));
+        }
+    };
+    let outcome = tokio::select! {
+        outcome = attach_debug_container(client, &args, &container_id) => outcome,
+        result = &mut terminated => result.map(|()| {
+            CommandResult::failure(crate::crs::error::ExitStatus::Interrupted)
+        }),
+    };
+
+    let warnings = remove_debug_container(client, &container_id).await;
+    if !matches!(ctx.output(), crate::crs::args::OutputArg::Json) {
+        for warning in &warnings {
+            eprintln!("warning: {warning}");
+        }
+    }
+
+    outcome
+}
+
+/// 等待 SIGINT 或 SIGTERM。
+async fn termination_signal() -> Result<(), CliError> {
+    let listen_failed = |source: std::io::Error| {
+        CliError::internal(format!("failed to listen for signals: {source}"))
+    };
+    let mut terminate = signal(SignalKind::terminate()).map_err(listen_failed)?;
+    tokio::select! {
+        result = tokio::signal::ctrl_c() => result.map_err(listen_failed),
+        _ = terminate.recv() => Ok(()),
+    }
+}
+
+async fn create_debug_container(
+    client: &CrsClient,
+    args: &DebugContainerArgs,
+) -> Result<String, CliError> {
+    let create_args = ContainerCreateArgs {
+        options: ContainerCreateOptions {
+            name: args.name.clone(),
+            env: args.env.clone(),
+            stdin: true,
+            tty: args.tty,
+            security: ContainerSecurityArgs {
+                privileged: args.privileged,
+                cap_add: args.cap_add.clone(),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        pod: String::new(),
+        image: args.image.clone(),
+        command: args.command.clone(),
+    };
+    let config = build_container_config(&create_args)
+        .map_err(|error| CliError::invalid_input(error).with_command(COMMAND))?;
+    let mut local = client.local()?;
+    let response = client
+        .with_rpc_timeout(async {
+            local
+                .create_debug_container(CreateDebugContainerRequest {
+                    target_container_id: args.container.clone(),
+                    config: Some(config),
+                    mount_target_rootfs: args.target_rootfs,
+                })
+                .await
+                .map_err(|status| {
+                    CliError::from_tonic_status(status)
+                        .with_command(COMMAND)
+                        .with_endpoint(client.endpoint())
+                        .with_object(format!("container {}", args.container))
+                })
+        })
+        .await?
+        .into_inner();
+
+    Ok(response.container_id)
+}
+
+/// 先建立 attach 流再启动容器，避免调试 shell 的首屏输出在附着前丢失。
+async fn attach_debug_container(
+    client: &CrsClient,
+    args: &DebugContainerArgs,
+    container_id: &str,
+) -> Result<CommandResult, CliError> {
+    let mut options = streaming::AttachStreamOptions::from_args(
+        container_id.to_string(),
+        StreamOptions {
+            stdin: true,
+            tty: args.tty,
+            stdout: true,
+            stderr: true,
+            resize: None,
+            protocol: args.protocol,
+        },
+    )?;
+    let mut runtime = client.runtime()?;
+    let response = client
+        .with_rpc_timeout(async {
+            runtime
+                .attach(AttachRequest {
+                    container_id: options.container_id.clone(),
+                    stdin: options.stdin,
+                    stdout: options.stdout,
+                    stderr: options.stderr,
+                    tty: options.tty,
+                })
+                .await
+                .map_err(|status| {
+                    CliError::from_tonic_status(status)
+                        .with_command(COMMAND)
+                        .with_endpoint(client.endpoint())
+                        .with_object(format!("container {}", options.container_id))
+                })
+        })
+        .await?
+        .into_inner();
+    options.stream_url = Some(response.url);
+
+    let (ready_tx, ready_rx) = oneshot::channel();
+    // 被信号打断时本函数的 future 会被丢弃，守卫负责中止 attach 任务并恢复终端模式。
+    let mut attach_task = AbortOnDrop(tokio::spawn(streaming::attach_ready(options, ready_tx)));
+    match ready_rx.await {
+        Ok(Ok(())) => {}
+        Ok(Err(error)) => return Err(error),
+        Err(_) => {
+            return match (&mut attach_task.0).await {
+                Ok(result) => result,
+                Err(error) => Err(CliError::internal(format!(
+                    "attach stream task failed before container start: {error}"
+                ))),
+            };
+        }
+    }
+
+    start_container(client, container_id, COMMAND).await?;
+
+    (&mut attach_task.0)
+        .await
+        .map_err(|source| CliError::internal(format!("attach stream task failed: {source}")))?
+}
+
+struct AbortOnDrop<T>(tokio::task::JoinHandle<T>);
+
+impl<T> Drop for AbortOnDrop<T> {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// 会话结束后停止并删除调试容器；失败时只给出警告，由守护进程兜底清理。
+/// 守护进程可能已经因会话结束先删除了容器，此时的 NotFound 不算失败。
+async fn remove_debug_container(client: &CrsClient, container_id: &str) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let Ok(mut runtime) = client.runtime() else {
+        warnings.push(format!(
+            "failed to remove debug container {container_id}: runtime client unavailable; run `crs container remove {container_id}`"
+        ));
+        return warnings;
+    };
+
+    if let Err(error) = client
+        .with_rpc_timeout(async {
+            runtime
+                .stop_container(StopContainerRequest {
+                    container_id: container_id.to_string(),
+                    timeout: 0,
+                })
+                .await
+                .map(|_| ())
+                .or_else(ignore_not_found)
+        })
+        .await
+    {
+        warnings.push(format!(
+            "failed to stop debug container {container_id}: {error}"
+        ));
+    }
+    if let Err(error) = client
+        .with_rpc_timeout(async {
+            runtime
+                .remove_container(RemoveContainerRequest {
+                    container_id: container_id.to_string(),
+                })
+                .await
+                .map(|_| ())
+                .or_else(ignore_not_found)
+        })
+        .await
+    {
+        warnings.push(format!(
+            "failed to remove debug container {container_id}: {error}; run `crs container remove {container_id}`"
+        ));
+    }
+
+    warnings
+}
+
+fn ignore_not_found(status: tonic::Status) -> Result<(), CliError> {
+    if status.code() == tonic::Code::NotFound {
+        Ok(())
+    } else {
+        Err(CliError::from_tonic_status(status).with_command(COMMAND))
+    }
+}
@@ -1,12 +1,16 @@
 use std::time::{Duration, UNIX_EPOCH};
 
+use serde::Serialize;
+
 use crate::crs::{
     args::{EventsArgs, OutputArg},
     client::CrsClient,
     context::CliContext,
     error::{CliError, CommandResult},
-    format::{print_table, ContainerEventView, TableRow},
+    format::{print_table, ContainerEventView, EventLogView, TableRow},
+    parsers::parse_event_filter,
 };
+use crate::proto::diagnostics::v1::{EventRecord, WatchEventsRequest};
 use crate::proto::runtime::v1::{
     ContainerEventResponse, ContainerEventType, GetEventsRequest, PodSandboxStatus,
 };
@@ -14,8 +18,12 @@ use crate::proto::runtime::v1::{
 pub(crate) async fn handle(
     ctx: &CliContext,
     client: &CrsClient,
-    _args: EventsArgs,
+    args: EventsArgs,
 ) -> Result<CommandResult, CliError> {
+    if args.since_seq.is_some() || args.follow || !args.filters.is_empty() {
+        return watch_event_log(ctx, client, args).await;
+    }
+
     let mut runtime = client.runtime()?;
     let response = client
         .with_rpc_timeout(async {
@@ -63,17 +71,71 @@ pub(crate) async fn handle(
     }
 }
 
+/// 通过诊断接口读取持久化事件日志，支持断点续读、跟随和过滤。
+async fn watch_event_log(
+    ctx: &CliContext,
+    client: &CrsClient,
+    args: EventsArgs,
+) -> Result<CommandResult, CliError> {
+    if args.since_seq.is_some_and(|seq| seq < 0) {
+        return Err(
+            CliError::invalid_input("--since-seq must not be negative").with_command("crs events")
+        );
+    }
+    let mut request = WatchEventsRequest {
+        since_seq: args.since_seq.unwrap_or_default(),
+        // 只给 --follow 时从当前位置开始，只看新事件。
+        from_latest: args.follow && args.since_seq.is_none(),
+        follow: args.follow,
+        ..Default::default()
+    };
+    for filter in &args.filters {
+        parse_event_filter(filter, &mut request)
+            .map_err(|err| CliError::invalid_input(err).with_command("crs events"))?;
+    }
+
+    let mut diagnostics = client.diagnostics()?;
+    let mut stream = client
+        .with_rpc_timeout(async {
+            diagnostics.watch_events(request).await.map_err(|status| {
+                CliError::from_diagnostics_status(status, client.endpoint())
+                    .with_command("crs events")
+            })
+        })
+        .await?
+        .into_inner();
+    let mut printed_header = false;
+
+    loop {
+        let next_record = stream.message();
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {
+                return Err(CliError::interrupted().with_command("crs events"));
+            }
+            record = next_record => {
+                let record = record.map_err(|status| {
+                    CliError::from_diagnostics_status(status, client.endpoint())
+                        .with_command("crs events")
+                })?;
+                let Some(record) = record else {
+                    return Ok(CommandResult::success());
+                };
+                print_event(ctx, &event_log_view(record), &mut printed_header)?;
+            }
+        }
+    }
+}
+
 async fn sleep_until(deadline: Option<tokio::time::Instant>) {
     if let Some(deadline) = deadline {
         tokio::time::sleep_until(deadline).await;
     }
 }
 
-fn print_event(
-    ctx: &CliContext,
-    view: &ContainerEventView,
-    printed_header: &mut bool,
-) -> Result<(), CliError> {
+fn print_event<T>(ctx: &CliContext, view: &T, printed_header: &mut bool) -> Result<(), CliError>
+where
+    T: TableRow + Serialize,
+{
     match ctx.output() {
         OutputArg::Json => {
             let line = serde_json::to_string(view).map_err(|source| {
@@ -118,6 +180,22 @@ fn event_view(event: ContainerEventResponse) -> ContainerEventView {
     }
 }
 
+fn event_log_view(record: EventRecord) -> EventLogView {
+    EventLogView {
+        seq: record.seq,
+        time: format_event_time(record.timestamp_unix_nanos),
+        source: record.source,
+        severity: record.severity,
+        kind: record.kind,
+        subject_kind: record.subject_kind,
+        subject_id: record.subject_id,
+        pod_id: record.pod_id,
+        namespace: record.namespace,
+        timestamp_unix_nanos: record.timestamp_unix_nanos,
+        details: serde_json::from_str(&record.details_json).ok(),
+    }
+}
+
 fn event_type_name(event_type: i32) -> &'static str {
     match ContainerEventType::try_from(event_type) {
         Ok(ContainerEventType::ContainerCreatedEvent) => "created",
@@ -1,7 +1,8 @@
 use crate::crs::{
-    args::ExecArgs, client::CrsClient, context::CliContext, error::CliError, error::CommandResult,
-    streaming,
+    args::ExecArgs, builders::build_exec_options, client::CrsClient, context::CliContext,
+    error::CliError, error::CommandResult, streaming,
 };
+use crate::proto::local::v1::LocalExecRequest;
 use crate::proto::runtime::v1::ExecRequest;
 
 pub(crate) async fn handle(
@@ -13,27 +14,38 @@ pub(crate) async fn handle(
         return super::container::exec_sync_with_command(ctx, client, args, "crs exec").await;
     }
 
+    let exec_options = build_exec_options(&args.process)
+        .map_err(|message| CliError::invalid_input(message).with_command("crs container exec"))?;
     let mut options =
         streaming::ExecStreamOptions::from_args(args.container, args.command, args.stream)?;
-    let mut runtime = client.runtime()?;
+    let request = ExecRequest {
+        container_id: options.container_id.clone(),
+        cmd: options.command.clone(),
+        stdin: options.stdin,
+        stdout: options.stdout,
+        stderr: options.stderr,
+        tty: options.tty,
+    };
     let response = client
         .with_rpc_timeout(async {
-            runtime
-                .exec(ExecRequest {
-                    container_id: options.container_id.clone(),
-                    cmd: options.command.clone(),
-                    stdin: options.stdin,
-                    stdout: options.stdout,
-                    stderr: options.stderr,
-                    tty: options.tty,
-                })
-                .await
-                .map_err(|status| {
-                    CliError::from_tonic_status(status)
-                        .with_command("crs container exec")
-                        .with_endpoint(client.endpoint())
-                        .with_object(format!("container {}", options.container_id))
-                })
+            let result = match exec_options {
+                Some(exec_options) => {
+                    client
+                        .local()?
+                        .exec(LocalExecRequest {
+                            request: Some(request),
+                            options: Some(exec_options),
+                        })
+                        .await
+                }
+                None => client.runtime()?.exec(request).await,
+            };
+            result.map_err(|status| {
+                CliError::from_tonic_status(status)
+                    .with_command("crs container exec")
+                    .with_endpoint(client.endpoint())
+                    .with_object(format!("container {}", options.container_id))
+            })
         })
         .await?
         .into_inner();
@@ -22,18 +22,25 @@ pub(crate) async fn handle(
         );
     }
 
-    let since_unix_nanos = args
-        .since
-        .as_deref()
-        .map(parse_since)
-        .transpose()
-        .map_err(CliError::invalid_input)?;
+    let since_unix_nanos = match (args.since.as_deref(), args.since_time.as_deref()) {
+        (_, Some(since_time)) => Some(parse_since_time(since_time)?),
+        (Some(since), None) => Some(parse_since(since).map_err(CliError::invalid_input)?),
+        (None, None) => None,
+    };
+    if args.limit_bytes.is_some_and(|limit| limit <= 0) {
+        return Err(
+            CliError::invalid_input("--limit-bytes must be greater than 0")
+                .with_command("crs logs"),
+        );
+    }
     let request = ContainerLogRequest {
         container_id: args.container.clone(),
         follow: args.follow,
         tail_lines: args.tail.unwrap_or(-1),
         since_unix_nanos: since_unix_nanos.unwrap_or_default(),
         timestamps: args.timestamps,
+        previous: args.previous,
+        limit_bytes: args.limit_bytes.unwrap_or_default(),
     };
 
     let mut diagnostics = client.diagnostics()?;
@@ -56,6 +63,19 @@ pub(crate) async fn handle(
     Ok(CommandResult::success())
 }
 
+/// `--since-time` 只接受 RFC3339 时间戳，与 kubectl 一致。
+fn parse_since_time(value: &str) -> Result<i64, CliError> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .ok()
+        .and_then(|timestamp| timestamp.timestamp_nanos_opt())
+        .ok_or_else(|| {
+            CliError::invalid_input(format!(
+                "invalid since-time \"{value}\": expected RFC3339 timestamp"
+            ))
+            .with_command("crs logs")
+        })
+}
+
 async fn write_text_stream(
     stream: &mut tonic::Streaming<ContainerLogChunk>,
 ) -> Result<(), CliError> {
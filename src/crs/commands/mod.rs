@@ -2,7 +2,9 @@ pub(crate) mod attach;
 pub(crate) mod completion;
 pub(crate) mod config;
 pub(crate) mod container;
+pub(crate) mod cp;
 pub(crate) mod debug;
+pub(crate) mod debug_container;
 pub(crate) mod doctor;
 pub(crate) mod events;
 pub(crate) mod exec;
@@ -18,6 +20,7 @@ pub(crate) mod runtime;
 pub(crate) mod shortcuts;
 pub(crate) mod stats;
 pub(crate) mod status;
+pub(crate) mod streaming;
 pub(crate) mod version;
 
 use crate::crs::{
@@ -61,11 +64,12 @@ pub(crate) async fn dispatch(
             command => pod::handle(ctx, client, command).await,
         },
         Command::Container(args) => match args.command {
-            ContainerCommand::Attach { id, stream } => {
-                attach::handle(ctx, client, id, stream).await
+            ContainerCommand::Attach { id, stream, since } => {
+                attach::handle(ctx, client, id, stream, since).await
             }
             ContainerCommand::Exec(args) => exec::handle(ctx, client, args).await,
             ContainerCommand::Logs(args) => logs::handle(ctx, client, args).await,
+            ContainerCommand::Cp(args) => cp::handle(ctx, client, args).await,
             command => container::handle(ctx, client, command).await,
         },
         Command::Run(args) => run::handle(ctx, client, *args).await,
@@ -74,6 +78,7 @@ pub(crate) async fn dispatch(
         Command::Metrics(args) => metrics::handle(ctx, client, args).await,
         Command::Recovery(args) => recovery::handle(ctx, client, args).await,
         Command::Gc(args) => gc::handle(ctx, client, args).await,
+        Command::Streaming(args) => streaming::handle(ctx, client, args).await,
         Command::Debug(args) => debug::handle(ctx, client, args).await,
         Command::Completion(args) => completion::handle(ctx, client, args).await,
     }
@@ -108,10 +108,10 @@ pub(crate) async fn handle(
     args: RunArgs,
 ) -> Result<CommandResult, CliError> {
     let plan = RunPlan::from_args(args)?;
-    ensure_image(client, &plan).await?;
+    ensure_image(client, &plan.image, plan.pull, "crs run").await?;
     let (container_id, pod_id) = create_run_container(client, &plan).await?;
     let outcome = if plan.detach {
-        if let Err(error) = start_container(client, &container_id).await {
+        if let Err(error) = start_container(client, &container_id, "crs run").await {
             emit_leftover_warnings(&leftover_warnings(Some(&container_id)));
             return Err(error);
         }
@@ -124,7 +124,7 @@ pub(crate) async fn handle(
             }
         }
     } else {
-        if let Err(error) = start_container(client, &container_id).await {
+        if let Err(error) = start_container(client, &container_id, "crs run").await {
             emit_leftover_warnings(&leftover_warnings(Some(&container_id)));
             return Err(error);
         }
@@ -216,26 +216,35 @@ fn run_container_options(
     }
 }
 
-async fn ensure_image(client: &CrsClient, plan: &RunPlan) -> Result<(), CliError> {
-    match plan.pull {
-        PullPolicyArg::Always => pull_image(client, &plan.image).await,
+pub(super) async fn ensure_image(
+    client: &CrsClient,
+    image: &str,
+    pull: PullPolicyArg,
+    command: &'static str,
+) -> Result<(), CliError> {
+    match pull {
+        PullPolicyArg::Always => pull_image(client, image, command).await,
         PullPolicyArg::Never => {
-            image_status(client, &plan.image).await?;
+            image_status(client, image, command).await?;
             Ok(())
         }
-        PullPolicyArg::Missing => match image_status(client, &plan.image).await {
+        PullPolicyArg::Missing => match image_status(client, image, command).await {
             Ok(()) => Ok(()),
             Err(error)
                 if matches!(error.exit_status(), crate::crs::error::ExitStatus::NotFound) =>
             {
-                pull_image(client, &plan.image).await
+                pull_image(client, image, command).await
             }
             Err(error) => Err(error),
         },
     }
 }
 
-async fn image_status(client: &CrsClient, image: &str) -> Result<(), CliError> {
+async fn image_status(
+    client: &CrsClient,
+    image: &str,
+    command: &'static str,
+) -> Result<(), CliError> {
     let mut image_client = client.image()?;
     client
         .with_rpc_timeout(async {
@@ -251,7 +260,7 @@ async fn image_status(client: &CrsClient, image: &str) -> Result<(), CliError> {
                 .await
                 .map_err(|status| {
                     CliError::from_tonic_status(status)
-                        .with_command("crs run")
+                        .with_command(command)
                         .with_endpoint(client.endpoint())
                         .with_object(format!("image {image}"))
                 })
@@ -260,7 +269,11 @@ async fn image_status(client: &CrsClient, image: &str) -> Result<(), CliError> {
     Ok(())
 }
 
-async fn pull_image(client: &CrsClient, image: &str) -> Result<(), CliError> {
+async fn pull_image(
+    client: &CrsClient,
+    image: &str,
+    command: &'static str,
+) -> Result<(), CliError> {
     let mut image_client = client.image()?;
     client
         .with_rpc_timeout(async {
@@ -277,7 +290,7 @@ async fn pull_image(client: &CrsClient, image: &str) -> Result<(), CliError> {
                 .await
                 .map_err(|status| {
                     CliError::from_tonic_status(status)
-                        .with_command("crs run")
+                        .with_command(command)
                         .with_endpoint(client.endpoint())
                         .with_object(format!("image {image}"))
                 })
@@ -414,7 +427,11 @@ async fn create_container(
     Ok(response.container_id)
 }
 
-async fn start_container(client: &CrsClient, container_id: &str) -> Result<(), CliError> {
+pub(super) async fn start_container(
+    client: &CrsClient,
+    container_id: &str,
+    command: &'static str,
+) -> Result<(), CliError> {
     let mut runtime = client.runtime()?;
     client
         .with_rpc_timeout(async {
@@ -425,7 +442,7 @@ async fn start_container(client: &CrsClient, container_id: &str) -> Result<(), C
                 .await
                 .map_err(|status| {
                     CliError::from_tonic_status(status)
-                        .with_command("crs run")
+                        .with_command(command)
                         .with_endpoint(client.endpoint())
                         .with_object(format!("container {container_id}"))
                 })
@@ -460,7 +477,7 @@ async fn run_foreground_attach(
         }
     }
 
-    if let Err(error) = start_container(client, container_id).await {
+    if let Err(error) = start_container(client, container_id, "crs run").await {
         attach_task.abort();
         emit_leftover_warnings(&leftover_warnings(Some(container_id)));
         return Err(error);
@@ -633,6 +650,7 @@ async fn read_run_log(client: &CrsClient, container_id: &str) -> Result<Vec<u8>,
                     tail_lines: -1,
                     since_unix_nanos: 0,
                     timestamps: false,
+                    ..Default::default()
                 })
                 .await
                 .map_err(|status| {
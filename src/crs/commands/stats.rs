@@ -1,15 +1,81 @@
 use crate::crs::{
     args::StatsArgs,
     client::CrsClient,
-    commands::container,
+    commands::{container, status::render_and_print},
     context::CliContext,
     error::{CliError, CommandResult},
+    format::{CommandOutput, RuntimeFootprintView},
 };
+use crate::proto::diagnostics::v1::RuntimeFootprintRequest;
 
 pub(crate) async fn handle(
     ctx: &CliContext,
     client: &CrsClient,
-    _args: StatsArgs,
+    args: StatsArgs,
 ) -> Result<CommandResult, CliError> {
+    if args.runtime {
+        return handle_runtime(ctx, client).await;
+    }
     container::handle_stats_list(ctx, client, None, Vec::new(), "ContainerStats").await
 }
+
+async fn handle_runtime(ctx: &CliContext, client: &CrsClient) -> Result<CommandResult, CliError> {
+    let mut diagnostics = client.diagnostics()?;
+    let response = client
+        .with_rpc_timeout(async {
+            diagnostics
+                .runtime_footprint(RuntimeFootprintRequest {})
+                .await
+                .map_err(|status| {
+                    CliError::from_diagnostics_status(status, client.endpoint())
+                        .with_command("crs stats --runtime")
+                })
+        })
+        .await?
+        .into_inner();
+    let views = response
+        .components
+        .into_iter()
+        .map(|usage| RuntimeFootprintView {
+            component: usage.component,
+            processes: usage.processes,
+            cpu_seconds: usage.cpu_seconds,
+            memory_bytes: usage.memory_bytes,
+            open_fds: usage.open_fds,
+            threads: usage.threads,
+        })
+        .collect::<Vec<_>>();
+    // 拉取期间 daemon 进程位于拉取 cgroup 内，合计时只累加进程类组件以免重复计算。
+    let process_views = views
+        .iter()
+        .filter(|view| view.component != "pull_cgroup")
+        .collect::<Vec<_>>();
+    let cni_plugins = response
+        .cni_plugins
+        .iter()
+        .map(|plugin| {
+            serde_json::json!({
+                "plugin": plugin.plugin,
+                "invocations": plugin.invocations,
+                "failures": plugin.failures,
+                "durationSeconds": plugin.duration_seconds,
+                "inFlight": plugin.in_flight,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    render_and_print(
+        ctx,
+        CommandOutput::new("RuntimeFootprint", client.endpoint(), views.clone())
+            .with_summary(serde_json::json!({
+                "count": views.len(),
+                "processes": process_views.iter().map(|view| view.processes).sum::<u64>(),
+                "memoryBytes": process_views.iter().map(|view| view.memory_bytes).sum::<u64>(),
+                "openFds": process_views.iter().map(|view| view.open_fds).sum::<u64>(),
+                "cniPlugins": cni_plugins,
+                "collectedAtUnixNanos": response.collected_at_unix_nanos,
+            }))
+            .with_warnings(response.warnings),
+    )?;
+    Ok(CommandResult::success())
+}
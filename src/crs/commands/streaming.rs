@@ -0,0 +1,254 @@
+use std::io::Write;
+use std::time::Duration;
+
+use futures::StreamExt;
+
+use crate::crs::{
+    args::{
+        OutputArg, StreamingArgs, StreamingCommand, StreamingReplayArgs, StreamingSessionsArgs,
+    },
+    client::CrsClient,
+    commands::status::render_and_print,
+    context::CliContext,
+    error::{CliError, CommandResult},
+    format::{CommandOutput, StreamingRecordingView, StreamingSessionView},
+};
+use crate::proto::diagnostics::v1::{
+    KillStreamingSessionRequest, StreamingAuditSession, StreamingAuditSessionsRequest,
+    StreamingAuditTranscriptRequest, StreamingSession, StreamingSessionsRequest,
+};
+
+pub(crate) async fn handle(
+    ctx: &CliContext,
+    client: &CrsClient,
+    args: StreamingArgs,
+) -> Result<CommandResult, CliError> {
+    match args.command {
+        StreamingCommand::Sessions(args) => {
+            handle_sessions(ctx, client, args, "crs streaming sessions").await
+        }
+        StreamingCommand::Kill { session } => handle_kill(ctx, client, session).await,
+        StreamingCommand::Recordings(args) => handle_recordings(ctx, client, args).await,
+        StreamingCommand::Replay(args) => handle_replay(ctx, client, args).await,
+    }
+}
+
+pub(crate) async fn handle_sessions(
+    ctx: &CliContext,
+    client: &CrsClient,
+    args: StreamingSessionsArgs,
+    command_name: &'static str,
+) -> Result<CommandResult, CliError> {
+    let mut diagnostics = client.diagnostics()?;
+    let response = client
+        .with_rpc_timeout(async {
+            diagnostics
+                .streaming_sessions(StreamingSessionsRequest {
+                    container_id: args.container.unwrap_or_default(),
+                })
+                .await
+                .map_err(|status| {
+                    CliError::from_diagnostics_status(status, client.endpoint())
+                        .with_command(command_name)
+                })
+        })
+        .await?
+        .into_inner();
+    let views = response
+        .sessions
+        .into_iter()
+        .map(session_view)
+        .collect::<Vec<_>>();
+
+    render_and_print(
+        ctx,
+        CommandOutput::new("StreamingSessions", client.endpoint(), views.clone())
+            .with_summary(serde_json::json!({
+                "count": views.len(),
+                "maxSessions": response.max_sessions,
+                "maxSessionsPerContainer": response.max_sessions_per_container,
+            }))
+            .with_warnings(response.warnings),
+    )?;
+    Ok(CommandResult::success())
+}
+
+async fn handle_kill(
+    ctx: &CliContext,
+    client: &CrsClient,
+    session_id: String,
+) -> Result<CommandResult, CliError> {
+    let mut diagnostics = client.diagnostics()?;
+    let response = client
+        .with_rpc_timeout(async {
+            diagnostics
+                .kill_streaming_session(KillStreamingSessionRequest { session_id })
+                .await
+                .map_err(|status| {
+                    CliError::from_diagnostics_status(status, client.endpoint())
+                        .with_command("crs streaming kill")
+                })
+        })
+        .await?
+        .into_inner();
+    let views = response.session.into_iter().map(session_view).collect();
+
+    render_and_print(
+        ctx,
+        CommandOutput::new("StreamingSessionKill", client.endpoint(), views),
+    )?;
+    Ok(CommandResult::success())
+}
+
+async fn handle_recordings(
+    ctx: &CliContext,
+    client: &CrsClient,
+    args: StreamingSessionsArgs,
+) -> Result<CommandResult, CliError> {
+    let mut diagnostics = client.diagnostics()?;
+    let response = client
+        .with_rpc_timeout(async {
+            diagnostics
+                .streaming_audit_sessions(StreamingAuditSessionsRequest {
+                    container_id: args.container.unwrap_or_default(),
+                })
+                .await
+                .map_err(|status| {
+                    CliError::from_diagnostics_status(status, client.endpoint())
+                        .with_command("crs streaming recordings")
+                })
+        })
+        .await?
+        .into_inner();
+    let views = response
+        .sessions
+        .into_iter()
+        .map(recording_view)
+        .collect::<Vec<_>>();
+
+    render_and_print(
+        ctx,
+        CommandOutput::new("StreamingRecordings", client.endpoint(), views.clone())
+            .with_summary(serde_json::json!({
+                "count": views.len(),
+                "auditEnabled": response.audit_enabled,
+                "recordTranscripts": response.record_transcripts,
+            }))
+            .with_warnings(response.warnings),
+    )?;
+    Ok(CommandResult::success())
+}
+
+async fn handle_replay(
+    ctx: &CliContext,
+    client: &CrsClient,
+    args: StreamingReplayArgs,
+) -> Result<CommandResult, CliError> {
+    if !args.speed.is_finite() || args.speed <= 0.0 {
+        return Err(CliError::invalid_input("--speed must be greater than zero")
+            .with_command("crs streaming replay"));
+    }
+    if !args.max_idle.is_finite() || args.max_idle < 0.0 {
+        return Err(CliError::invalid_input("--max-idle must not be negative")
+            .with_command("crs streaming replay"));
+    }
+
+    let mut diagnostics = client.diagnostics()?;
+    let mut stream = client
+        .with_rpc_timeout(async {
+            diagnostics
+                .streaming_audit_transcript(StreamingAuditTranscriptRequest {
+                    session_id: args.session.clone(),
+                })
+                .await
+                .map_err(|status| {
+                    CliError::from_diagnostics_status(status, client.endpoint())
+                        .with_command("crs streaming replay")
+                        .with_object(format!("streaming session {}", args.session))
+                })
+        })
+        .await?
+        .into_inner();
+    let mut transcript = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|status| {
+            CliError::from_tonic_status(status).with_command("crs streaming replay")
+        })?;
+        transcript.extend_from_slice(&chunk.data);
+    }
+
+    // asciicast 本身就是 JSON lines，JSON 输出与 --raw 都原样输出。
+    if args.raw || ctx.output() == OutputArg::Json {
+        write_stdout(&transcript)?;
+        return Ok(CommandResult::success());
+    }
+
+    let transcript = String::from_utf8_lossy(&transcript);
+    let mut previous = 0.0f64;
+    // 第一行是 asciicast 头部。
+    for line in transcript.lines().skip(1) {
+        let Ok((time, code, data)) = serde_json::from_str::<(f64, String, String)>(line) else {
+            continue;
+        };
+        let mut delay = (time - previous).max(0.0) / args.speed;
+        if args.max_idle > 0.0 {
+            delay = delay.min(args.max_idle);
+        }
+        previous = time;
+        match code.as_str() {
+            "o" => {
+                tokio::time::sleep(Duration::from_secs_f64(delay)).await;
+                write_stdout(data.as_bytes())?;
+            }
+            "m" => eprintln!("-- {data} --"),
+            _ => {}
+        }
+    }
+    Ok(CommandResult::success())
+}
+
+fn write_stdout(data: &[u8]) -> Result<(), CliError> {
+    let mut stdout = std::io::stdout();
+    stdout
+        .write_all(data)
+        .and_then(|()| stdout.flush())
+        .map_err(|source| CliError::internal(format!("failed to write transcript: {source}")))
+}
+
+fn recording_view(session: StreamingAuditSession) -> StreamingRecordingView {
+    StreamingRecordingView {
+        session_id: session.session_id,
+        token: session.token,
+        kind: session.kind,
+        container_id: session.container_id,
+        pod_sandbox_id: session.pod_sandbox_id,
+        pod_namespace: session.pod_namespace,
+        pod_name: session.pod_name,
+        runtime_handler: session.runtime_handler,
+        client_address: session.client_address,
+        protocol: session.protocol,
+        command: session.command,
+        tty: session.tty,
+        started_at_unix_millis: session.started_at_unix_millis,
+        ended_at_unix_millis: (session.ended_at_unix_millis != 0)
+            .then_some(session.ended_at_unix_millis),
+        exit_code: session.exit_code_known.then_some(session.exit_code),
+        bytes_received: session.bytes_received,
+        bytes_sent: session.bytes_sent,
+        transcript_path: (!session.transcript_path.is_empty()).then_some(session.transcript_path),
+        transcript_truncated: session.transcript_truncated,
+    }
+}
+
+fn session_view(session: StreamingSession) -> StreamingSessionView {
+    StreamingSessionView {
+        id: session.id,
+        container_id: session.container_id,
+        kind: session.kind,
+        protocol: session.protocol,
+        client_address: session.client_address,
+        started_at_unix_millis: session.started_at_unix_millis,
+        bytes_received: session.bytes_received,
+        bytes_sent: session.bytes_sent,
+    }
+}
@@ -423,11 +423,18 @@ pub(crate) struct ConfigReloadStatusView {
     pub last_reload: String,
     pub last_error: String,
     pub cni_watcher: String,
+    pub cni_active: String,
 }
 
 impl TableRow for ConfigReloadStatusView {
     fn headers() -> &'static [&'static str] {
-        &["WATCHER", "LAST RELOAD", "LAST ERROR", "CNI WATCHER"]
+        &[
+            "WATCHER",
+            "LAST RELOAD",
+            "LAST ERROR",
+            "CNI WATCHER",
+            "CNI ACTIVE",
+        ]
     }
 
     fn cells(&self) -> Vec<String> {
@@ -436,6 +443,7 @@ impl TableRow for ConfigReloadStatusView {
             self.last_reload.clone(),
             self.last_error.clone(),
             self.cni_watcher.clone(),
+            self.cni_active.clone(),
         ]
     }
 }
@@ -604,6 +612,134 @@ impl TableRow for GcCandidateView {
     }
 }
 
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StreamingSessionView {
+    pub id: String,
+    pub container_id: String,
+    pub kind: String,
+    pub protocol: String,
+    pub client_address: String,
+    pub started_at_unix_millis: i64,
+    pub bytes_received: u64,
+    pub bytes_sent: u64,
+}
+
+impl TableRow for StreamingSessionView {
+    fn headers() -> &'static [&'static str] {
+        &[
+            "SESSION",
+            "CONTAINER",
+            "KIND",
+            "PROTOCOL",
+            "CLIENT",
+            "STARTED",
+            "RECEIVED",
+            "SENT",
+        ]
+    }
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.id.clone(),
+            self.container_id.clone(),
+            self.kind.clone(),
+            self.protocol.clone(),
+            self.client_address.clone(),
+            format_unix_nanos(
+                self.started_at_unix_millis.saturating_mul(1_000_000),
+                SystemTime::now(),
+            ),
+            format_bytes(self.bytes_received),
+            format_bytes(self.bytes_sent),
+        ]
+    }
+
+    fn quiet_cell(&self) -> String {
+        self.id.clone()
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StreamingRecordingView {
+    pub session_id: String,
+    pub token: String,
+    pub kind: String,
+    pub container_id: String,
+    pub pod_sandbox_id: String,
+    pub pod_namespace: String,
+    pub pod_name: String,
+    pub runtime_handler: String,
+    pub client_address: String,
+    pub protocol: String,
+    pub command: Vec<String>,
+    pub tty: bool,
+    pub started_at_unix_millis: i64,
+    pub ended_at_unix_millis: Option<i64>,
+    pub exit_code: Option<i32>,
+    pub bytes_received: u64,
+    pub bytes_sent: u64,
+    pub transcript_path: Option<String>,
+    pub transcript_truncated: bool,
+}
+
+impl TableRow for StreamingRecordingView {
+    fn headers() -> &'static [&'static str] {
+        &[
+            "SESSION",
+            "CONTAINER",
+            "POD",
+            "KIND",
+            "COMMAND",
+            "STARTED",
+            "DURATION",
+            "EXIT",
+            "TRANSCRIPT",
+        ]
+    }
+
+    fn cells(&self) -> Vec<String> {
+        let pod = if self.pod_name.is_empty() {
+            String::new()
+        } else {
+            format!("{}/{}", self.pod_namespace, self.pod_name)
+        };
+        let duration = match self.ended_at_unix_millis {
+            Some(ended) => format!(
+                "{}s",
+                ended.saturating_sub(self.started_at_unix_millis).max(0) / 1000
+            ),
+            None => "running".to_string(),
+        };
+        let transcript = match (&self.transcript_path, self.transcript_truncated) {
+            (Some(_), true) => "truncated",
+            (Some(_), false) => "yes",
+            (None, _) => "no",
+        };
+        vec![
+            self.session_id.clone(),
+            self.container_id.clone(),
+            pod,
+            self.kind.clone(),
+            self.command.join(" "),
+            format_unix_nanos(
+                self.started_at_unix_millis.saturating_mul(1_000_000),
+                SystemTime::now(),
+            ),
+            duration,
+            self.exit_code
+                .map(|code| code.to_string())
+                .unwrap_or_default(),
+            transcript.to_string(),
+        ]
+    }
+
+    fn quiet_cell(&self) -> String {
+        self.session_id.clone()
+    }
+}
+
 #[derive(Clone, Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct DebugView {
@@ -1007,6 +1143,53 @@ impl TableRow for ContainerEventView {
     }
 }
 
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct EventLogView {
+    pub seq: i64,
+    pub time: String,
+    pub source: String,
+    pub severity: String,
+    pub kind: String,
+    pub subject_kind: String,
+    pub subject_id: String,
+    pub pod_id: String,
+    pub namespace: String,
+    pub timestamp_unix_nanos: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
+}
+
+impl TableRow for EventLogView {
+    fn headers() -> &'static [&'static str] {
+        &[
+            "SEQ",
+            "TIME",
+            "SEVERITY",
+            "KIND",
+            "SUBJECT",
+            "POD",
+            "NAMESPACE",
+        ]
+    }
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.seq.to_string(),
+            self.time.clone(),
+            self.severity.clone(),
+            self.kind.clone(),
+            format!("{}/{}", self.subject_kind, self.subject_id),
+            self.pod_id.clone(),
+            self.namespace.clone(),
+        ]
+    }
+
+    fn quiet_cell(&self) -> String {
+        self.seq.to_string()
+    }
+}
+
 #[derive(Clone, Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct ResourceUsageView {
@@ -1075,6 +1258,41 @@ impl TableRow for PodStatsView {
     }
 }
 
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RuntimeFootprintView {
+    pub component: String,
+    pub processes: u64,
+    pub cpu_seconds: f64,
+    pub memory_bytes: u64,
+    pub open_fds: u64,
+    pub threads: u64,
+}
+
+impl TableRow for RuntimeFootprintView {
+    fn headers() -> &'static [&'static str] {
+        &[
+            "COMPONENT",
+            "PROCESSES",
+            "CPU TIME",
+            "MEMORY",
+            "FDS",
+            "THREADS",
+        ]
+    }
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.component.clone(),
+            self.processes.to_string(),
+            format!("{:.2}s", self.cpu_seconds),
+            format_bytes(self.memory_bytes),
+            self.open_fds.to_string(),
+            self.threads.to_string(),
+        ]
+    }
+}
+
 #[derive(Clone, Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct PodMetricsView {
@@ -2,6 +2,7 @@ use std::{fs, net::IpAddr, path::Path, time::Duration};
 
 use base64::Engine;
 
+use crate::proto::diagnostics::v1::WatchEventsRequest;
 use crate::proto::runtime::v1::{
     AuthConfig, Device, HugepageLimit, IdMapping, ImageSpec, Mount, MountPropagation, PortMapping,
     Protocol, SeLinuxOption, SecurityProfile,
@@ -911,6 +912,53 @@ pub(crate) fn parse_since_at(
     timestamp_to_unix_nanos(since, value)
 }
 
+/// 把 `crs events --filter KEY=VALUE` 合并进 `WatchEvents` 请求；`kind` 可重复。
+pub(crate) fn parse_event_filter(
+    value: &str,
+    request: &mut WatchEventsRequest,
+) -> Result<(), String> {
+    let Some((key, filter_value)) = value.split_once('=') else {
+        return Err(format!(
+            "invalid event filter \"{value}\": expected KEY=VALUE"
+        ));
+    };
+    let filter_value = filter_value.trim();
+    if filter_value.is_empty() {
+        return Err(format!(
+            "invalid event filter \"{value}\": value must not be empty"
+        ));
+    }
+    match key.trim() {
+        "kind" => request.kinds.push(filter_value.to_string()),
+        "subject" => match filter_value.split_once('/') {
+            Some((subject_kind, subject_id)) => {
+                request.subject_kind = subject_kind.to_string();
+                request.subject_id = subject_id.to_string();
+            }
+            None => request.subject_id = filter_value.to_string(),
+        },
+        "subject-kind" => request.subject_kind = filter_value.to_string(),
+        "pod" => request.pod_id = filter_value.to_string(),
+        "namespace" => request.namespace = filter_value.to_string(),
+        "severity" => match filter_value.to_ascii_lowercase().as_str() {
+            severity @ ("debug" | "info" | "warning" | "error") => {
+                request.min_severity = severity.to_string();
+            }
+            _ => {
+                return Err(format!(
+                    "invalid event filter \"{value}\": severity must be debug, info, warning, or error"
+                ))
+            }
+        },
+        _ => {
+            return Err(format!(
+                "invalid event filter \"{value}\": expected kind, subject, subject-kind, pod, namespace, or severity"
+            ))
+        }
+    }
+    Ok(())
+}
+
 fn timestamp_to_unix_nanos(
     timestamp: chrono::DateTime<chrono::Utc>,
     source: &str,
@@ -1515,6 +1563,10 @@ mod tests {
             ("user", parse_user("1000:").unwrap_err()),
             ("ID mapping", parse_id_mapping("1:2:0").unwrap_err()),
             ("since", parse_since("not-time").unwrap_err()),
+            (
+                "event filter",
+                parse_event_filter("color=red", &mut WatchEventsRequest::default()).unwrap_err(),
+            ),
         ];
 
         for (parser, error) in cases {
@@ -1532,4 +1584,31 @@ mod tests {
             parse_auth_json("inline", r#"{"auths":{"r":{"auth":"secret text" }}}"#).unwrap_err();
         assert!(!secret_error.contains("secret text"), "{secret_error}");
     }
+
+    #[test]
+    fn parses_event_filters_into_watch_request() {
+        let mut request = WatchEventsRequest::default();
+        for filter in [
+            "kind=container.*",
+            "kind=cri.container_started",
+            "subject=container/abc",
+            "pod=pod-1",
+            "namespace=default",
+            "severity=Warning",
+        ] {
+            parse_event_filter(filter, &mut request).unwrap();
+        }
+
+        assert_eq!(request.kinds, vec!["container.*", "cri.container_started"]);
+        assert_eq!(request.subject_kind, "container");
+        assert_eq!(request.subject_id, "abc");
+        assert_eq!(request.pod_id, "pod-1");
+        assert_eq!(request.namespace, "default");
+        assert_eq!(request.min_severity, "warning");
+
+        parse_event_filter("subject=def", &mut request).unwrap();
+        assert_eq!(request.subject_id, "def");
+        assert!(parse_event_filter("severity=fatal", &mut request).is_err());
+        assert!(parse_event_filter("pod=", &mut request).is_err());
+    }
 }
@@ -21,8 +21,10 @@ use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use tokio::sync::{Mutex, Notify};
 use tonic::{Request, Response, Status};
+use tracing::Instrument;
 
 use crate::error::Error;
+use crate::metrics::operations::{OperationPhase, PhaseTimer};
 use crate::proto::runtime::v1::{
     image_service_server::ImageService, AuthConfig, FilesystemIdentifier, FilesystemUsage, Image,
     ImageFsInfoRequest, ImageFsInfoResponse, ImageSpec, ImageStatusRequest, ImageStatusResponse,
@@ -2642,6 +2644,7 @@ impl ImageServiceImpl {
             "Using registry API pull flow for {} via {}",
             reference, endpoint.base_url
         );
+        let manifest_stage = PhaseTimer::start(OperationPhase::ImagePull, "resolve_manifest");
         let mut http_builder = reqwest::Client::builder();
         if !self.pull_progress_timeout.is_zero() {
             http_builder = http_builder.timeout(self.pull_progress_timeout);
@@ -2913,6 +2916,7 @@ impl ImageServiceImpl {
             .and_then(|v| v.as_array())
             .ok_or_else(|| Status::internal("manifest missing layers"))?;
 
+        manifest_stage.succeed();
         info!("Start downloading {} layers", layers.len());
         let layer_jobs: Vec<(usize, String, String)> = layers
             .iter()
@@ -2962,6 +2966,7 @@ impl ImageServiceImpl {
                     .ok_or_else(|| Status::internal("layer missing digest"))
             })
             .collect::<Result<_, _>>()?;
+        let download_stage = PhaseTimer::start(OperationPhase::ImagePull, "download_layers");
         let downloaded_results =
             Self::collect_with_concurrency_limit(self.max_concurrent_downloads, layer_jobs, {
                 let http = http.clone();
@@ -2994,6 +2999,7 @@ impl ImageServiceImpl {
                 }
             })
             .await?;
+        download_stage.succeed();
         let total_size: u64 = downloaded_results
             .iter()
             .map(|(_, bytes, len, _)| (*len).max(bytes.len() as u64))
@@ -3329,17 +3335,26 @@ impl ImageService for ImageServiceImpl {
             let (image_id, image_size, layers_to_persist, pulled_metadata) = self
                 .pull_via_registry_api(&reference, &auth, supplied_bearer_token.as_deref())
                 .await?;
-            self.persist_pulled_image(PersistedPullImage {
-                requested_ref: requested_ref.clone(),
-                canonical_ref: canonical_ref.clone(),
-                reference,
-                image_id,
-                image_size,
-                layers_to_persist,
-                pulled_metadata,
-            })
-            .await
+            let persist_stage = PhaseTimer::start(OperationPhase::ImagePull, "persist");
+            let response = self
+                .persist_pulled_image(PersistedPullImage {
+                    requested_ref: requested_ref.clone(),
+                    canonical_ref: canonical_ref.clone(),
+                    reference,
+                    image_id,
+                    image_size,
+                    layers_to_persist,
+                    pulled_metadata,
+                })
+                .await?;
+            persist_stage.succeed();
+            Ok(response)
         })
+        .instrument(crate::trace_export::phase_span(
+            "image",
+            "pull",
+            &canonical_ref,
+        ))
         .await;
 
         if let Some(notify) = self.in_progress_pulls.lock().await.remove(&pull_key) {
@@ -26,6 +26,7 @@ use tokio_stream::wrappers::UnixListenerStream;
 use tonic::transport::Server;
 use tonic_reflection::server::Builder as ReflectionBuilder;
 use tracing::{debug, info, warn};
+use tracing_subscriber::filter::{filter_fn, FilterExt};
 use tracing_subscriber::fmt::writer::MakeWriter;
 use tracing_subscriber::prelude::*;
 use tracing_subscriber::{fmt, EnvFilter};
@@ -265,6 +266,10 @@ async fn main() -> Result<(), Error> {
         image_oci_artifact_mount_support: config.image.oci_artifact_mount_support,
         workloads: config.runtime.workloads.clone(),
         enable_pod_events: config.api.enable_pod_events,
+        event_log_capacity: config.api.event_log_capacity,
+        event_sinks: config.events.sinks.clone(),
+        container_events_queue_size: config.api.container_events_queue_size,
+        container_events_overflow: config.api.container_events_overflow,
         included_pod_metrics: config.api.included_pod_metrics.clone(),
         stats_collection_period: config.api.stats_collection_period,
         pod_sandbox_metrics_collection_period: config.api.pod_sandbox_metrics_collection_period,
@@ -372,6 +377,7 @@ async fn main() -> Result<(), Error> {
         exec_sync_io_drain_timeout: config.api.exec_sync_io_drain_timeout,
         max_container_log_line_size: config.logging.max_container_log_line_size,
         log_to_journald: config.runtime.log_to_journald,
+        container_log_rotation: config.logging.container_log_rotation.clone(),
         no_sync_log: config.runtime.no_sync_log,
         restrict_oom_score_adj: config.runtime.restrict_oom_score_adj,
         enable_unprivileged_ports: config.runtime.enable_unprivileged_ports,
@@ -389,6 +395,7 @@ async fn main() -> Result<(), Error> {
             monitor_env: config.runtime.monitor_env.clone(),
             debug: config.runtime.shim_debug,
             log_to_journald: config.runtime.log_to_journald,
+            log_drivers: Vec::new(),
             no_sync_log: config.runtime.no_sync_log,
             no_pivot: config.runtime.no_pivot,
             no_new_keyring: config.runtime.no_new_keyring,
@@ -398,6 +405,7 @@ async fn main() -> Result<(), Error> {
             ),
             runtime_path: PathBuf::from(&config.runtime.runtime_path),
             max_container_log_line_size: config.logging.max_container_log_line_size,
+            attach_scrollback_bytes: config.logging.attach_scrollback_bytes,
             state_db_path: PathBuf::from(&config.root).join("crius.db"),
         },
         streaming: config.api.streaming.clone(),
@@ -419,6 +427,7 @@ async fn main() -> Result<(), Error> {
 
     prepare_runtime_service(&runtime_service).await;
     let shutdown_nri = runtime_service.nri_handle();
+    let shutdown_netns_pool = runtime_service.netns_pool();
     let image_service = runtime_service.image_service();
     let reflection_service = ReflectionBuilder::configure()
         .register_encoded_file_descriptor_set(include_bytes!(concat!(
@@ -481,7 +490,10 @@ async fn main() -> Result<(), Error> {
             .max_encoding_message_size(runtime_config.grpc_max_send_msg_size as usize)
             .max_decoding_message_size(runtime_config.grpc_max_recv_msg_size as usize);
 
+    // 先添加的 layer 在外层：RPC 耗时在请求 span 内记录，直方图才能带上 trace exemplar。
     let server = Server::builder()
+        .layer(crius::trace_export::GrpcTraceLayer)
+        .layer(crius::metrics::operations::RpcMetricsLayer)
         .add_service(runtime_service_server)
         .add_service(image_service_server)
         .add_service(diagnostics_service_server)
@@ -514,13 +526,23 @@ async fn main() -> Result<(), Error> {
             .serve_with_incoming_shutdown(uds_stream, shutdown_signal())
             .await;
         shutdown_watchdog.abort();
-        shutdown_runtime_service(&runtime_config.clean_shutdown_file, shutdown_nri).await;
+        shutdown_runtime_service(
+            &runtime_config.clean_shutdown_file,
+            shutdown_nri,
+            shutdown_netns_pool,
+        )
+        .await;
         serve_result?;
     } else {
         let addr: SocketAddr = listen.parse()?;
         let serve_result = server.serve_with_shutdown(addr, shutdown_signal()).await;
         shutdown_watchdog.abort();
-        shutdown_runtime_service(&runtime_config.clean_shutdown_file, shutdown_nri).await;
+        shutdown_runtime_service(
+            &runtime_config.clean_shutdown_file,
+            shutdown_nri,
+            shutdown_netns_pool,
+        )
+        .await;
         serve_result?;
     }
 
@@ -639,10 +661,15 @@ async fn prepare_runtime_service(runtime_service: &RuntimeServiceImpl) {
     }
 }
 
-async fn shutdown_runtime_service(clean_shutdown_file: &Path, nri: Arc<dyn crius::nri::NriApi>) {
+async fn shutdown_runtime_service(
+    clean_shutdown_file: &Path,
+    nri: Arc<dyn crius::nri::NriApi>,
+    netns_pool: crius::network::NetnsPool,
+) {
     if let Err(err) = write_clean_shutdown_marker(clean_shutdown_file).await {
         log::error!("Failed to persist clean shutdown marker: {}", err);
     }
+    netns_pool.drain();
     if let Err(err) = nri.shutdown().await {
         log::error!("Failed to shutdown NRI: {}", err);
     }
@@ -993,23 +1020,32 @@ fn apply_cli_overrides(args: &Args, config: &mut Config) {
     }
 }
 
-fn init_logging(config: &Config) -> Result<(), Error> {
-    let base_filter = EnvFilter::try_from_default_env().or_else(|_| {
+fn logging_filter(config: &Config) -> Result<EnvFilter, Error> {
+    Ok(EnvFilter::try_from_default_env().or_else(|_| {
         EnvFilter::try_new(format!(
             "crius={},tower_http=info",
             config.logging.level.trim()
         ))
-    })?;
+    })?)
+}
+
+fn init_logging(config: &Config) -> Result<(), Error> {
     let writer = LogOutput::from_config(config)?;
+    // span 只用于导出 trace，不进入日志行，避免日志格式随 span 上下文变化。
     let fmt_layer = fmt::layer()
         .with_timer(LocalLogTimer)
         .with_file(true)
         .with_line_number(true)
-        .with_writer(writer);
-    let trace_layer = crius::trace_export::build_layer(&config.tracing)?;
+        .with_writer(writer)
+        .with_filter(logging_filter(config)?.and(filter_fn(|metadata| metadata.is_event())));
+    // 导出层不受日志级别影响地接收 crius span，span 内的事件仍按日志级别过滤。
+    let trace_filter =
+        filter_fn(|metadata| metadata.is_span() && metadata.target().starts_with("crius"))
+            .or(logging_filter(config)?);
+    let trace_layer = crius::trace_export::build_layer(&config.tracing)?
+        .map(|layer| layer.with_filter(trace_filter));
 
     tracing_subscriber::registry()
-        .with(base_filter)
         .with(fmt_layer)
         .with(trace_layer)
         .try_init()?;
@@ -1149,12 +1185,14 @@ mod tests {
                     monitor_cgroup: String::new(),
                     monitor_env: Vec::new(),
                     stream_websockets: true,
+                    stream_spdy: true,
                     allowed_annotations: Vec::new(),
                     default_annotations: std::collections::HashMap::new(),
                     privileged_without_host_devices: false,
                     privileged_without_host_devices_all_devices_allowed: false,
                     container_create_timeout: 240,
                     snapshotter: "internal-overlay-untar".to_string(),
+                    log_drivers: Vec::new(),
                 },
             )]),
             runtime_root: PathBuf::from("/tmp/crius-main-test-runtime-root"),
@@ -1185,6 +1223,10 @@ mod tests {
             image_oci_artifact_mount_support: true,
             workloads: std::collections::HashMap::new(),
             enable_pod_events: true,
+            event_log_capacity: 10_000,
+            event_sinks: Vec::new(),
+            container_events_queue_size: 1024,
+            container_events_overflow: crius::config::ContainerEventsOverflow::Disconnect,
             included_pod_metrics: vec!["all".to_string()],
             stats_collection_period: 0,
             pod_sandbox_metrics_collection_period: 0,
@@ -1287,6 +1329,7 @@ mod tests {
             exec_sync_io_drain_timeout: std::time::Duration::ZERO,
             max_container_log_line_size: 4096,
             log_to_journald: false,
+            container_log_rotation: Default::default(),
             no_sync_log: false,
             restrict_oom_score_adj: false,
             enable_unprivileged_ports: false,
@@ -1304,12 +1347,14 @@ mod tests {
                 monitor_env: Vec::new(),
                 debug: false,
                 log_to_journald: false,
+                log_drivers: Vec::new(),
                 no_sync_log: false,
                 no_pivot: false,
                 no_new_keyring: false,
                 systemd_cgroup: false,
                 runtime_path: PathBuf::from("/definitely/missing/runc"),
                 max_container_log_line_size: 4096,
+                attach_scrollback_bytes: 64 * 1024,
                 state_db_path: PathBuf::from("/tmp/crius-main-test.db"),
             },
             streaming: StreamingConfig::default(),
@@ -1636,13 +1681,18 @@ mod tests {
         let root = tempdir().unwrap();
         let mut config = test_runtime_config(root.path().to_path_buf());
         config.clean_shutdown_file = root.path().join("clean.shutdown");
-        let _service = RuntimeServiceImpl::new_with_nri_api(
+        let service = RuntimeServiceImpl::new_with_nri_api(
             config.clone(),
             NriConfig::default(),
             fake_nri.clone(),
         );
 
-        shutdown_runtime_service(&config.clean_shutdown_file, fake_nri.clone()).await;
+        shutdown_runtime_service(
+            &config.clean_shutdown_file,
+            fake_nri.clone(),
+            service.netns_pool(),
+        )
+        .await;
 
         assert!(config.clean_shutdown_file.exists());
         assert_eq!(fake_nri.calls.lock().await.clone(), vec!["shutdown"]);
@@ -0,0 +1,616 @@
+//! crius 自身资源占用（footprint）采集
+//!
+//! 汇总 daemon 进程、shim 账本中的 `crius-shim` 进程、独立的镜像拉取 cgroup 与 CNI 插件调用，
+//! 用于估算节点 system-reserved。进程类组件读取 `/proc/<pid>`，拉取 cgroup 读取 cgroup 计数。
+//!
+//! CNI 插件是短生命周期子进程，退出后其 CPU 时间无法单独归属，因此只累计调用次数、失败次数与
+//! 墙钟耗时；采样时仍在运行的插件进程计入 `cni` 组件的内存与 fd。
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use super::registry::MetricsRegistry;
+
+/// footprint 组件名，同时作为 metrics 的 `component` 标签值。
+pub const COMPONENT_DAEMON: &str = "daemon";
+pub const COMPONENT_SHIM: &str = "shim";
+pub const COMPONENT_PULL_CGROUP: &str = "pull_cgroup";
+pub const COMPONENT_CNI: &str = "cni";
+
+/// 单个组件的资源占用。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentUsage {
+    pub component: String,
+    /// 计入的进程数；拉取 cgroup 取 `pids.current`。
+    pub processes: u64,
+    /// 累计 CPU 时间（秒）；CNI 组件恒为 0。
+    pub cpu_seconds: f64,
+    /// 进程 RSS 之和，或拉取 cgroup 的内存用量（字节）。
+    pub memory_bytes: u64,
+    /// 打开的文件描述符数；拉取 cgroup 不统计。
+    pub open_fds: u64,
+    pub threads: u64,
+}
+
+/// 单个 CNI 插件的累计调用统计。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CniPluginUsage {
+    pub plugin: String,
+    pub invocations: u64,
+    pub failures: u64,
+    /// 所有已完成调用的墙钟耗时之和（秒）。
+    pub duration_seconds: f64,
+    pub in_flight: u64,
+}
+
+/// 一次 footprint 采样结果。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeFootprint {
+    pub components: Vec<ComponentUsage>,
+    pub cni_plugins: Vec<CniPluginUsage>,
+    pub warnings: Vec<String>,
+    pub collected_at_unix_nanos: i64,
+}
+
+/// 采样输入：shim 账本中的进程与当前拉取 cgroup。
+#[derive(Debug, Clone, Default)]
+pub struct FootprintSources {
+    pub shim_pids: Vec<u32>,
+    /// `separate_pull_cgroup` 为固定路径时的 cgroup 目录；`pod` 模式与 Pod 共用 cgroup，不单独计入。
+    pub pull_cgroup: Option<PathBuf>,
+}
+
+/// 按 `/proc` 与 cgroup 采样各组件占用。
+pub fn collect_runtime_footprint(sources: &FootprintSources) -> RuntimeFootprint {
+    collect_runtime_footprint_from(Path::new("/proc"), sources)
+}
+
+fn collect_runtime_footprint_from(
+    proc_root: &Path,
+    sources: &FootprintSources,
+) -> RuntimeFootprint {
+    let mut warnings = Vec::new();
+    let clock_ticks = clock_ticks_per_second();
+
+    let daemon = sum_processes(
+        proc_root,
+        COMPONENT_DAEMON,
+        [std::process::id()],
+        clock_ticks,
+    );
+    if daemon.processes == 0 {
+        warnings.push("failed to read daemon process usage from /proc".to_string());
+    }
+
+    let unique_shims: BTreeSet<u32> = sources
+        .shim_pids
+        .iter()
+        .copied()
+        .filter(|pid| *pid > 0)
+        .collect();
+    let shim = sum_processes(
+        proc_root,
+        COMPONENT_SHIM,
+        unique_shims.iter().copied(),
+        clock_ticks,
+    );
+    let missing_shims = (unique_shims.len() as u64).saturating_sub(shim.processes);
+    if missing_shims > 0 {
+        warnings.push(format!(
+            "{missing_shims} shim process(es) recorded in the shim ledger are not running"
+        ));
+    }
+
+    let mut components = vec![daemon, shim];
+    if let Some(path) = sources.pull_cgroup.as_ref() {
+        match cgroup_usage(path) {
+            Some(usage) => components.push(usage),
+            None => warnings.push(format!(
+                "failed to read pull cgroup usage from {}",
+                path.display()
+            )),
+        }
+    }
+
+    let registry = cni_plugin_registry();
+    components.push(sum_processes(
+        proc_root,
+        COMPONENT_CNI,
+        registry.running_pids(),
+        clock_ticks,
+    ));
+
+    RuntimeFootprint {
+        components,
+        cni_plugins: registry.snapshot(),
+        warnings,
+        collected_at_unix_nanos: chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default(),
+    }
+}
+
+/// 单个进程的 `/proc` 采样。
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct ProcessUsage {
+    cpu_ticks: u64,
+    rss_bytes: u64,
+    threads: u64,
+    open_fds: u64,
+}
+
+fn sum_processes(
+    proc_root: &Path,
+    component: &str,
+    pids: impl IntoIterator<Item = u32>,
+    clock_ticks: u64,
+) -> ComponentUsage {
+    let mut usage = ComponentUsage {
+        component: component.to_string(),
+        ..Default::default()
+    };
+    let mut cpu_ticks = 0u64;
+    for pid in pids {
+        let Some(process) = read_process_usage(&proc_root.join(pid.to_string())) else {
+            continue;
+        };
+        usage.processes += 1;
+        cpu_ticks = cpu_ticks.saturating_add(process.cpu_ticks);
+        usage.memory_bytes = usage.memory_bytes.saturating_add(process.rss_bytes);
+        usage.threads = usage.threads.saturating_add(process.threads);
+        usage.open_fds = usage.open_fds.saturating_add(process.open_fds);
+    }
+    usage.cpu_seconds = cpu_ticks as f64 / clock_ticks.max(1) as f64;
+    usage
+}
+
+fn read_process_usage(process_dir: &Path) -> Option<ProcessUsage> {
+    let stat = fs::read_to_string(process_dir.join("stat")).ok()?;
+    let status = fs::read_to_string(process_dir.join("status")).unwrap_or_default();
+    let open_fds = fs::read_dir(process_dir.join("fd"))
+        .map(|entries| entries.count() as u64)
+        .unwrap_or(0);
+    let (rss_bytes, threads) = parse_proc_status(&status);
+    Some(ProcessUsage {
+        cpu_ticks: parse_proc_stat_cpu_ticks(&stat)?,
+        rss_bytes,
+        threads,
+        open_fds,
+    })
+}
+
+/// 取 `/proc/<pid>/stat` 中的 utime + stime（第 14、15 字段）；comm 可能含空格，从最后一个 `)` 之后解析。
+fn parse_proc_stat_cpu_ticks(stat: &str) -> Option<u64> {
+    let rest = &stat[stat.rfind(')')? + 1..];
+    let mut fields = rest.split_whitespace().skip(11);
+    let utime = fields.next()?.parse::<u64>().ok()?;
+    let stime = fields.next()?.parse::<u64>().ok()?;
+    Some(utime.saturating_add(stime))
+}
+
+/// 取 `/proc/<pid>/status` 中的 VmRSS（字节）与 Threads；内核线程没有 VmRSS。
+fn parse_proc_status(status: &str) -> (u64, u64) {
+    let mut rss_bytes = 0;
+    let mut threads = 0;
+    for line in status.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.split_whitespace().next().unwrap_or_default();
+        match key {
+            "VmRSS" => rss_bytes = value.parse::<u64>().unwrap_or(0).saturating_mul(1024),
+            "Threads" => threads = value.parse().unwrap_or(0),
+            _ => {}
+        }
+    }
+    (rss_bytes, threads)
+}
+
+fn clock_ticks_per_second() -> u64 {
+    nix::unistd::sysconf(nix::unistd::SysconfVar::CLK_TCK)
+        .ok()
+        .flatten()
+        .and_then(|ticks| u64::try_from(ticks).ok())
+        .filter(|ticks| *ticks > 0)
+        .unwrap_or(100)
+}
+
+/// 读取 cgroup 目录的 CPU、内存与进程数；cgroup v1 下内存与 pids 位于同级控制器目录。
+fn cgroup_usage(path: &Path) -> Option<ComponentUsage> {
+    if !path.is_dir() {
+        return None;
+    }
+    let read_u64 = |path: PathBuf| {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|value| value.trim().parse::<u64>().ok())
+    };
+    let cpu_seconds = fs::read_to_string(path.join("cpu.stat"))
+        .ok()
+        .and_then(|content| {
+            content.lines().find_map(|line| {
+                line.strip_prefix("usage_usec ")
+                    .and_then(|value| value.trim().parse::<u64>().ok())
+            })
+        })
+        .map(|usec| usec as f64 / 1_000_000.0)
+        .or_else(|| {
+            read_u64(path.join("cpuacct.usage")).map(|nanos| nanos as f64 / 1_000_000_000.0)
+        })
+        .unwrap_or(0.0);
+    let memory_bytes = read_u64(path.join("memory.current"))
+        .or_else(|| {
+            cgroup_v1_sibling(path, "memory")
+                .and_then(|memory| read_u64(memory.join("memory.usage_in_bytes")))
+        })
+        .unwrap_or(0);
+    let processes = read_u64(path.join("pids.current"))
+        .or_else(|| {
+            cgroup_v1_sibling(path, "pids").and_then(|pids| read_u64(pids.join("pids.current")))
+        })
+        .unwrap_or(0);
+
+    Some(ComponentUsage {
+        component: COMPONENT_PULL_CGROUP.to_string(),
+        processes,
+        cpu_seconds,
+        memory_bytes,
+        open_fds: 0,
+        threads: 0,
+    })
+}
+
+/// 把 `/sys/fs/cgroup/cpu/<rel>` 换成 `/sys/fs/cgroup/<controller>/<rel>`。
+fn cgroup_v1_sibling(path: &Path, controller: &str) -> Option<PathBuf> {
+    let relative = path.strip_prefix("/sys/fs/cgroup").ok()?;
+    let mut components = relative.components();
+    components.next()?;
+    Some(
+        Path::new("/sys/fs/cgroup")
+            .join(controller)
+            .join(components.as_path()),
+    )
+}
+
+#[derive(Debug, Default)]
+struct CniPluginStats {
+    invocations: u64,
+    failures: u64,
+    duration: Duration,
+    running: BTreeSet<u32>,
+    in_flight: u64,
+}
+
+/// 进程级 CNI 插件调用注册表；调用点在 network 子系统，由 footprint 采样读取。
+#[derive(Debug, Default)]
+pub struct CniPluginRegistry {
+    plugins: Mutex<BTreeMap<String, CniPluginStats>>,
+}
+
+/// 返回进程级 CNI 插件调用注册表。
+pub fn cni_plugin_registry() -> &'static CniPluginRegistry {
+    static REGISTRY: OnceLock<CniPluginRegistry> = OnceLock::new();
+    REGISTRY.get_or_init(CniPluginRegistry::default)
+}
+
+impl CniPluginRegistry {
+    /// 登记一次已启动的插件进程；返回值在 `finish` 或被丢弃（按失败）时计入统计。
+    pub fn started(&'static self, plugin: &str, pid: Option<u32>) -> CniPluginInvocation {
+        if let Ok(mut plugins) = self.plugins.lock() {
+            let stats = plugins.entry(plugin.to_string()).or_default();
+            stats.in_flight += 1;
+            if let Some(pid) = pid {
+                stats.running.insert(pid);
+            }
+        }
+        CniPluginInvocation {
+            registry: self,
+            plugin: plugin.to_string(),
+            pid,
+            started: Instant::now(),
+            finished: false,
+        }
+    }
+
+    fn finished(&self, plugin: &str, pid: Option<u32>, success: bool, elapsed: Duration) {
+        if let Ok(mut plugins) = self.plugins.lock() {
+            let stats = plugins.entry(plugin.to_string()).or_default();
+            stats.in_flight = stats.in_flight.saturating_sub(1);
+            if let Some(pid) = pid {
+                stats.running.remove(&pid);
+            }
+            stats.invocations += 1;
+            if !success {
+                stats.failures += 1;
+            }
+            stats.duration += elapsed;
+        }
+    }
+
+    fn running_pids(&self) -> Vec<u32> {
+        self.plugins
+            .lock()
+            .map(|plugins| {
+                plugins
+                    .values()
+                    .flat_map(|stats| stats.running.iter().copied())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn snapshot(&self) -> Vec<CniPluginUsage> {
+        self.plugins
+            .lock()
+            .map(|plugins| {
+                plugins
+                    .iter()
+                    .map(|(plugin, stats)| CniPluginUsage {
+                        plugin: plugin.clone(),
+                        invocations: stats.invocations,
+                        failures: stats.failures,
+                        duration_seconds: stats.duration.as_secs_f64(),
+                        in_flight: stats.in_flight,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// 一次在途的 CNI 插件调用。
+#[derive(Debug)]
+pub struct CniPluginInvocation {
+    registry: &'static CniPluginRegistry,
+    plugin: String,
+    pid: Option<u32>,
+    started: Instant,
+    finished: bool,
+}
+
+impl CniPluginInvocation {
+    pub fn finish(mut self, success: bool) {
+        self.finished = true;
+        self.registry
+            .finished(&self.plugin, self.pid, success, self.started.elapsed());
+    }
+}
+
+impl Drop for CniPluginInvocation {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.registry
+                .finished(&self.plugin, self.pid, false, self.started.elapsed());
+        }
+    }
+}
+
+/// 把 footprint 各组件与 CNI 插件调用写入本次抓取的注册表。
+pub fn render_runtime_footprint(registry: &MetricsRegistry, footprint: &RuntimeFootprint) {
+    type Field = fn(&ComponentUsage) -> f64;
+    let cpu_seconds = registry.counter(
+        "crius_runtime_cpu_seconds_total",
+        "CPU time consumed by crius components.",
+        &["component"],
+    );
+    let gauges: [(&str, &str, Field); 4] = [
+        (
+            "crius_runtime_memory_bytes",
+            "Resident memory of crius processes, or memory usage of the pull cgroup.",
+            |usage| usage.memory_bytes as f64,
+        ),
+        (
+            "crius_runtime_open_fds",
+            "Open file descriptors held by crius processes.",
+            |usage| usage.open_fds as f64,
+        ),
+        (
+            "crius_runtime_processes",
+            "Processes accounted to each crius component.",
+            |usage| usage.processes as f64,
+        ),
+        (
+            "crius_runtime_threads",
+            "Threads of crius processes.",
+            |usage| usage.threads as f64,
+        ),
+    ];
+    for usage in &footprint.components {
+        cpu_seconds.set(&[usage.component.as_str()], usage.cpu_seconds);
+    }
+    for (name, help, value) in gauges {
+        let gauge = registry.gauge(name, help, &["component"]);
+        for usage in &footprint.components {
+            gauge.set(&[usage.component.as_str()], value(usage));
+        }
+    }
+
+    if footprint.cni_plugins.is_empty() {
+        return;
+    }
+    let invocations = registry.counter(
+        "crius_cni_plugin_invocations_total",
+        "CNI plugin executions by plugin and result.",
+        &["plugin", "result"],
+    );
+    let duration = registry.counter(
+        "crius_cni_plugin_duration_seconds_total",
+        "Wall-clock time spent in CNI plugin executions.",
+        &["plugin"],
+    );
+    let in_flight = registry.gauge(
+        "crius_cni_plugin_in_flight",
+        "CNI plugin executions currently running.",
+        &["plugin"],
+    );
+    for plugin in &footprint.cni_plugins {
+        invocations.set(
+            &[plugin.plugin.as_str(), "success"],
+            plugin.invocations.saturating_sub(plugin.failures) as f64,
+        );
+        invocations.set(&[plugin.plugin.as_str(), "failure"], plugin.failures as f64);
+        duration.set(&[plugin.plugin.as_str()], plugin.duration_seconds);
+        in_flight.set(&[plugin.plugin.as_str()], plugin.in_flight as f64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::registry::{encode, ExpositionFormat};
+
+    #[test]
+    fn proc_stat_cpu_ticks_skip_comm_with_spaces() {
+        let stat =
+            "4242 (crius shim) S 1 4242 4242 0 -1 4194560 100 0 0 0 17 5 0 0 20 0 3 0 100 0 0";
+        assert_eq!(parse_proc_stat_cpu_ticks(stat), Some(22));
+        assert_eq!(parse_proc_stat_cpu_ticks("garbage"), None);
+    }
+
+    #[test]
+    fn proc_status_reports_rss_bytes_and_threads() {
+        let status = "Name:\tcrius-shim\nVmRSS:\t    2048 kB\nThreads:\t4\n";
+        assert_eq!(parse_proc_status(status), (2048 * 1024, 4));
+        assert_eq!(parse_proc_status("Name:\tkthreadd\nThreads:\t1\n"), (0, 1));
+    }
+
+    #[test]
+    fn footprint_sums_shims_and_reports_missing_ledger_entries() {
+        let tempdir = tempfile::tempdir().expect("tempdir should be created");
+        let proc_root = tempdir.path();
+        for (pid, rss_kb) in [(std::process::id(), 4096u64), (101, 1024), (102, 512)] {
+            let dir = proc_root.join(pid.to_string());
+            fs::create_dir_all(dir.join("fd")).expect("proc dir should be created");
+            fs::write(
+                dir.join("stat"),
+                format!("{pid} (crius-shim) S 1 1 1 0 -1 0 0 0 0 0 200 100 0 0 20 0 2 0 1 0 0"),
+            )
+            .expect("stat should be written");
+            fs::write(
+                dir.join("status"),
+                format!("VmRSS:\t{rss_kb} kB\nThreads:\t2\n"),
+            )
+            .expect("status should be written");
+            fs::write(dir.join("fd").join("0"), "").expect("fd entry should be written");
+        }
+
+        let footprint = collect_runtime_footprint_from(
+            proc_root,
+            &FootprintSources {
+                shim_pids: vec![101, 102, 102, 103],
+                pull_cgroup: None,
+            },
+        );
+
+        let shim = footprint
+            .components
+            .iter()
+            .find(|usage| usage.component == COMPONENT_SHIM)
+            .expect("shim component should be reported");
+        assert_eq!(shim.processes, 2);
+        assert_eq!(shim.memory_bytes, 1536 * 1024);
+        assert_eq!(shim.threads, 4);
+        assert_eq!(shim.open_fds, 2);
+        let ticks = clock_ticks_per_second() as f64;
+        assert!((shim.cpu_seconds - 600.0 / ticks).abs() < 1e-9);
+        assert!(footprint
+            .warnings
+            .iter()
+            .any(|warning| warning.starts_with("1 shim process(es)")));
+        assert!(!footprint
+            .components
+            .iter()
+            .any(|usage| usage.component == COMPONENT_PULL_CGROUP));
+    }
+
+    #[test]
+    fn pull_cgroup_usage_reads_cgroup_v2_counters() {
+        let tempdir = tempfile::tempdir().expect("tempdir should be created");
+        fs::write(
+            tempdir.path().join("cpu.stat"),
+            "usage_usec 2500000\nuser_usec 2000000\n",
+        )
+        .expect("cpu.stat should be written");
+        fs::write(tempdir.path().join("memory.current"), "1048576\n")
+            .expect("memory.current should be written");
+        fs::write(tempdir.path().join("pids.current"), "3\n")
+            .expect("pids.current should be written");
+
+        let usage = cgroup_usage(tempdir.path()).expect("cgroup usage should be read");
+        assert_eq!(usage.component, COMPONENT_PULL_CGROUP);
+        assert_eq!(usage.processes, 3);
+        assert_eq!(usage.memory_bytes, 1_048_576);
+        assert!((usage.cpu_seconds - 2.5).abs() < 1e-9);
+        assert!(cgroup_usage(&tempdir.path().join("missing")).is_none());
+    }
+
+    #[test]
+    fn cni_registry_counts_failures_for_dropped_invocations() {
+        let registry: &'static CniPluginRegistry = Box::leak(Box::default());
+        registry.started("bridge", Some(4242)).finish(true);
+        drop(registry.started("bridge", None));
+        let running = registry.started("portmap", Some(4343));
+        assert_eq!(registry.running_pids(), vec![4343]);
+
+        let snapshot = registry.snapshot();
+        assert_eq!(
+            snapshot[0],
+            CniPluginUsage {
+                plugin: "bridge".to_string(),
+                invocations: 2,
+                failures: 1,
+                duration_seconds: snapshot[0].duration_seconds,
+                in_flight: 0,
+            }
+        );
+        assert_eq!(snapshot[1].in_flight, 1);
+        running.finish(true);
+        assert!(registry.running_pids().is_empty());
+    }
+
+    #[test]
+    fn render_uses_component_and_plugin_labels() {
+        let registry = MetricsRegistry::default();
+        render_runtime_footprint(
+            &registry,
+            &RuntimeFootprint {
+                components: vec![ComponentUsage {
+                    component: COMPONENT_DAEMON.to_string(),
+                    processes: 1,
+                    cpu_seconds: 1.5,
+                    memory_bytes: 4096,
+                    open_fds: 12,
+                    threads: 8,
+                }],
+                cni_plugins: vec![CniPluginUsage {
+                    plugin: "bridge".to_string(),
+                    invocations: 3,
+                    failures: 1,
+                    duration_seconds: 0.25,
+                    in_flight: 0,
+                }],
+                ..Default::default()
+            },
+        );
+        let output = encode(&[&registry], ExpositionFormat::PrometheusText);
+
+        assert!(output.contains("crius_runtime_cpu_seconds_total{component=\"daemon\"} 1.5\n"));
+        assert!(output.contains("crius_runtime_memory_bytes{component=\"daemon\"} 4096\n"));
+        assert!(output.contains("crius_runtime_open_fds{component=\"daemon\"} 12\n"));
+        assert!(output.contains(
+            "crius_cni_plugin_invocations_total{plugin=\"bridge\",result=\"success\"} 2\n"
+        ));
+        assert!(output.contains(
+            "crius_cni_plugin_invocations_total{plugin=\"bridge\",result=\"failure\"} 1\n"
+        ));
+        assert!(
+            output.contains("crius_cni_plugin_duration_seconds_total{plugin=\"bridge\"} 0.25\n")
+        );
+    }
+}
@@ -13,6 +13,9 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+pub mod footprint;
+pub mod operations;
+pub mod registry;
 pub mod server;
 
 /// 守护进程级 runtime metrics 快照。
@@ -23,9 +26,39 @@ pub struct RuntimeMetricsSnapshot {
     pub container_count: usize,
     pub pod_sandbox_count: usize,
     pub event_subscriber_count: usize,
+    /// 各 GetContainerEvents 订阅者的队列深度与丢弃计数。
+    pub event_subscribers: Vec<crate::services::CriSubscriberStats>,
+    /// 所有订阅者（含已断开的）因队列溢出丢弃的事件总数。
+    pub event_dropped_total: u64,
     pub container_stats_cache_entries: usize,
     pub pod_stats_cache_entries: usize,
     pub pod_metrics_cache_entries: usize,
+    /// 预热 netns 池占用情况。
+    pub netns_pool: crate::network::NetnsPoolStats,
+    /// 各 Pod netns 内按接口采集的网络计数。
+    pub pod_network_interfaces: Vec<PodNetworkInterfaceStats>,
+}
+
+/// 单个 Pod 接口的网络计数。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PodNetworkInterfaceStats {
+    pub pod_id: String,
+    pub pod_name: String,
+    pub pod_namespace: String,
+    pub interface: NetworkStats,
+}
+
+/// metrics `containers` collector 使用的单容器资源采样。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ContainerResourceStats {
+    pub container_id: String,
+    pub container_name: String,
+    pub image: String,
+    pub pod_name: String,
+    pub pod_namespace: String,
+    pub stats: ContainerStats,
+    /// 可写层占用字节数。
+    pub fs_usage_bytes: u64,
 }
 
 /// 镜像存储 metrics 快照。
@@ -52,10 +85,130 @@ pub struct ContainerStats {
     pub network: Option<NetworkStats>,
     /// 进程统计
     pub pids: Option<PidsStats>,
+    /// PSI 压力统计（仅 cgroup v2）
+    pub pressure: Option<ContainerPressure>,
     /// 采样时间
     pub timestamp: u64,
 }
 
+/// 容器 cgroup 的 cpu/memory/io PSI 统计
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ContainerPressure {
+    pub cpu: Option<PressureStats>,
+    pub memory: Option<PressureStats>,
+    pub io: Option<PressureStats>,
+}
+
+impl ContainerPressure {
+    /// 按资源名列出 PSI，供 metrics 渲染。
+    pub fn resources(&self) -> [(&'static str, Option<&PressureStats>); 3] {
+        [
+            ("cpu", self.cpu.as_ref()),
+            ("memory", self.memory.as_ref()),
+            ("io", self.io.as_ref()),
+        ]
+    }
+}
+
+/// 单个 `*.pressure` 文件的 some/full 两行
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct PressureStats {
+    /// 至少一个任务停顿
+    pub some: PressureLine,
+    /// 全部非空闲任务同时停顿
+    pub full: PressureLine,
+}
+
+/// PSI 一行：10s/60s/300s 平均停顿百分比与累计停顿时间
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct PressureLine {
+    pub avg10: f64,
+    pub avg60: f64,
+    pub avg300: f64,
+    /// 累计停顿时间（微秒）
+    pub total: u64,
+}
+
+impl PressureStats {
+    /// 解析 `cpu.pressure`/`memory.pressure`/`io.pressure`；缺少 `full` 行的旧内核按 0 处理。
+    pub fn parse(content: &str) -> Option<Self> {
+        let mut stats = PressureStats::default();
+        let mut saw_line = false;
+        for line in content.lines() {
+            let mut fields = line.split_whitespace();
+            let target = match fields.next() {
+                Some("some") => &mut stats.some,
+                Some("full") => &mut stats.full,
+                _ => continue,
+            };
+            for field in fields {
+                let Some((key, value)) = field.split_once('=') else {
+                    continue;
+                };
+                match key {
+                    "avg10" => target.avg10 = value.parse().ok()?,
+                    "avg60" => target.avg60 = value.parse().ok()?,
+                    "avg300" => target.avg300 = value.parse().ok()?,
+                    "total" => target.total = value.parse().ok()?,
+                    _ => {}
+                }
+            }
+            saw_line = true;
+        }
+        saw_line.then_some(stats)
+    }
+}
+
+/// `memory.events` 计数
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MemoryEvents {
+    /// 低于 memory.low 仍被回收的次数
+    pub low: u64,
+    /// 超过 memory.high 被节流的次数
+    pub high: u64,
+    /// 触及 memory.max 的次数
+    pub max: u64,
+    /// 触发 OOM 的次数
+    pub oom: u64,
+    /// OOM killer 杀死进程的次数
+    pub oom_kill: u64,
+}
+
+impl MemoryEvents {
+    /// 解析 cgroup v2 `memory.events` 或 cgroup v1 `memory.oom_control`（仅含 `oom_kill`）。
+    pub fn parse(content: &str) -> Self {
+        let mut events = MemoryEvents::default();
+        for line in content.lines() {
+            let Some((key, value)) = line.split_once(char::is_whitespace) else {
+                continue;
+            };
+            let Ok(value) = value.trim().parse::<u64>() else {
+                continue;
+            };
+            match key {
+                "low" => events.low = value,
+                "high" => events.high = value,
+                "max" => events.max = value,
+                "oom" => events.oom = value,
+                "oom_kill" => events.oom_kill = value,
+                _ => {}
+            }
+        }
+        events
+    }
+
+    /// 按名称列出计数，供 metrics 渲染。
+    pub fn counters(&self) -> [(&'static str, u64); 5] {
+        [
+            ("low", self.low),
+            ("high", self.high),
+            ("max", self.max),
+            ("oom", self.oom),
+            ("oom_kill", self.oom_kill),
+        ]
+    }
+}
+
 /// CPU统计
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CpuStats {
@@ -98,6 +251,19 @@ pub struct MemoryStats {
     pub kernel_usage: u64,
     /// TCP缓冲区内存（字节）
     pub kernel_tcp_usage: u64,
+    /// 非活跃文件页（字节），可被回收，不计入 working set
+    pub inactive_file: u64,
+    /// Swap 限制（字节）；`None` 表示不限制或无法读取
+    pub swap_limit: Option<u64>,
+    /// OOM 与内存限制相关事件计数
+    pub events: Option<MemoryEvents>,
+}
+
+impl MemoryStats {
+    /// 与 cAdvisor 一致的 working set：使用量减去可回收的非活跃文件页。
+    pub fn working_set(&self) -> u64 {
+        self.usage.saturating_sub(self.inactive_file)
+    }
 }
 
 /// 块IO统计
@@ -145,6 +311,22 @@ pub struct NetworkStats {
     pub tx_dropped: u64,
 }
 
+impl NetworkStats {
+    /// 按 cAdvisor 命名的接口计数，名称不含 `container_network_` 前缀和 `_total` 后缀。
+    pub fn counters(&self) -> [(&'static str, u64); 8] {
+        [
+            ("receive_bytes", self.rx_bytes),
+            ("receive_packets", self.rx_packets),
+            ("receive_errors", self.rx_errors),
+            ("receive_packets_dropped", self.rx_dropped),
+            ("transmit_bytes", self.tx_bytes),
+            ("transmit_packets", self.tx_packets),
+            ("transmit_errors", self.tx_errors),
+            ("transmit_packets_dropped", self.tx_dropped),
+        ]
+    }
+}
+
 /// PIDs统计
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PidsStats {
@@ -239,6 +421,13 @@ impl MetricsCollector {
             }
         }
 
+        // PSI 仅 cgroup v2 提供，三个文件与 cpu/memory 控制器位于同一目录
+        if self.cgroup_v2 {
+            if let Some(path) = cpu_path.as_ref().or(memory_path.as_ref()) {
+                stats.pressure = Some(Self::collect_pressure(path));
+            }
+        }
+
         debug!(
             "Collected stats for container {}: cpu={:?}, memory={:?}",
             container_id,
@@ -249,6 +438,36 @@ impl MetricsCollector {
         Ok(stats)
     }
 
+    /// 只读取容器的 OOM 相关事件计数，供 OOM 监视器轮询使用。
+    pub fn collect_memory_events(
+        &self,
+        container_id: &str,
+        cgroup_path: &Path,
+    ) -> Option<MemoryEvents> {
+        let path = self.find_cgroup_path(cgroup_path, container_id, "memory")?;
+        let file = if self.cgroup_v2 {
+            "memory.events"
+        } else {
+            "memory.oom_control"
+        };
+        fs::read_to_string(path.join(file))
+            .ok()
+            .map(|content| MemoryEvents::parse(&content))
+    }
+
+    fn collect_pressure(cgroup_path: &Path) -> ContainerPressure {
+        let read = |file: &str| {
+            fs::read_to_string(cgroup_path.join(file))
+                .ok()
+                .and_then(|content| PressureStats::parse(&content))
+        };
+        ContainerPressure {
+            cpu: read("cpu.pressure"),
+            memory: read("memory.pressure"),
+            io: read("io.pressure"),
+        }
+    }
+
     /// 查找容器的cgroup路径
     fn find_cgroup_path(
         &self,
@@ -487,6 +706,7 @@ impl MetricsCollector {
         let mut rss = 0u64;
         let mut pgfault = 0u64;
         let mut pgmajfault = 0u64;
+        let mut inactive_file = 0u64;
 
         let memory_stat_path = cgroup_path.join("memory.stat");
         if let Ok(content) = fs::read_to_string(&memory_stat_path) {
@@ -498,6 +718,7 @@ impl MetricsCollector {
                 match parts[0] {
                     "file" => cache = parts[1].parse()?,
                     "anon" => rss = parts[1].parse()?,
+                    "inactive_file" => inactive_file = parts[1].parse()?,
                     "pgfault" => pgfault = parts[1].parse()?,
                     "pgmajfault" => pgmajfault = parts[1].parse()?,
                     _ => {}
@@ -514,6 +735,12 @@ impl MetricsCollector {
         } else {
             0
         };
+        let swap_limit = fs::read_to_string(cgroup_path.join("memory.swap.max"))
+            .ok()
+            .and_then(|value| value.trim().parse::<u64>().ok());
+        let events = fs::read_to_string(cgroup_path.join("memory.events"))
+            .ok()
+            .map(|content| MemoryEvents::parse(&content));
 
         Ok(MemoryStats {
             usage,
@@ -526,6 +753,9 @@ impl MetricsCollector {
             pgmajfault,
             kernel_usage: 0,
             kernel_tcp_usage: 0,
+            inactive_file,
+            swap_limit,
+            events,
         })
     }
 
@@ -554,6 +784,7 @@ impl MetricsCollector {
         let mut swap = 0u64;
         let mut pgfault = 0u64;
         let mut pgmajfault = 0u64;
+        let mut inactive_file = 0u64;
 
         let memory_stat_path = cgroup_path.join("memory.stat");
         if let Ok(content) = fs::read_to_string(&memory_stat_path) {
@@ -565,6 +796,7 @@ impl MetricsCollector {
                 match parts[0] {
                     "cache" => cache = parts[1].parse()?,
                     "rss" => rss = parts[1].parse()?,
+                    "total_inactive_file" => inactive_file = parts[1].parse()?,
                     "swap" | "swapness" => swap = parts[1].parse()?,
                     "pgfault" => pgfault = parts[1].parse()?,
                     "pgmajfault" => pgmajfault = parts[1].parse()?,
@@ -584,6 +816,11 @@ impl MetricsCollector {
             pgmajfault,
             kernel_usage: 0,
             kernel_tcp_usage: 0,
+            inactive_file,
+            swap_limit: None,
+            events: fs::read_to_string(cgroup_path.join("memory.oom_control"))
+                .ok()
+                .map(|content| MemoryEvents::parse(&content)),
         })
     }
 
@@ -801,6 +1038,41 @@ mod tests {
         assert!(percent > 0.0);
     }
 
+    #[test]
+    fn test_pressure_stats_parse() {
+        let stats = PressureStats::parse(
+            "some avg10=1.50 avg60=0.75 avg300=0.10 total=123456\nfull avg10=0.00 avg60=0.20 avg300=0.05 total=789\n",
+        )
+        .unwrap();
+        assert_eq!(stats.some.avg10, 1.5);
+        assert_eq!(stats.some.total, 123456);
+        assert_eq!(stats.full.avg60, 0.2);
+        assert_eq!(stats.full.total, 789);
+
+        let some_only = PressureStats::parse("some avg10=0.00 avg60=0.00 avg300=0.00 total=5\n");
+        assert_eq!(some_only.unwrap().full, PressureLine::default());
+        assert!(PressureStats::parse("").is_none());
+    }
+
+    #[test]
+    fn test_memory_events_parse() {
+        let events =
+            MemoryEvents::parse("low 0\nhigh 4\nmax 7\noom 2\noom_kill 1\noom_group_kill 0\n");
+        assert_eq!(
+            events,
+            MemoryEvents {
+                low: 0,
+                high: 4,
+                max: 7,
+                oom: 2,
+                oom_kill: 1,
+            }
+        );
+
+        let v1 = MemoryEvents::parse("oom_kill_disable 0\nunder_oom 0\noom_kill 3\n");
+        assert_eq!(v1.oom_kill, 3);
+    }
+
     #[test]
     fn test_memory_stats_default() {
         let stats = MemoryStats::default();
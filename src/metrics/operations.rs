@@ -0,0 +1,386 @@
+//! CRI 操作耗时与错误指标
+//!
+//! - gRPC 层：按 service/method 统计请求数（按 gRPC 状态码）、耗时直方图与在途请求数
+//! - 子阶段：容器创建各阶段、CNI ADD/DEL、镜像拉取各阶段与 shim 启动耗时直方图
+//!
+//! 记录点分散在 server、network、image 与 runtime 各子系统中，指标族登记在进程级
+//! [`metrics_registry`]，由 metrics 服务在渲染时读取。
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::OnceLock;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use tonic::codegen::http;
+use tower::{Layer, Service};
+
+use super::registry::{
+    metrics_registry, Counter, Gauge, Histogram, MetricsRegistry, LATENCY_BUCKETS_SECONDS,
+};
+
+/// 计入 RPC 指标的 gRPC service；诊断、本地与反射服务不在 CRI 延迟口径内。
+const INSTRUMENTED_SERVICES: &[&str] = &["runtime.v1.RuntimeService", "runtime.v1.ImageService"];
+
+/// 子阶段耗时所属的直方图族。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum OperationPhase {
+    /// `CreateContainer` 内按截止时间执行的各阶段。
+    ContainerCreate,
+    /// CNI 插件链 ADD/DEL。
+    Cni,
+    /// 镜像拉取的 manifest 解析、层下载与落盘阶段。
+    ImagePull,
+    /// shim 进程拉起直到 RPC socket 就绪。
+    ShimSpawn,
+}
+
+impl OperationPhase {
+    const ALL: [OperationPhase; 4] = [
+        OperationPhase::ContainerCreate,
+        OperationPhase::Cni,
+        OperationPhase::ImagePull,
+        OperationPhase::ShimSpawn,
+    ];
+
+    fn metric_name(self) -> &'static str {
+        match self {
+            OperationPhase::ContainerCreate => "crius_container_create_phase_duration_seconds",
+            OperationPhase::Cni => "crius_cni_operation_duration_seconds",
+            OperationPhase::ImagePull => "crius_image_pull_stage_duration_seconds",
+            OperationPhase::ShimSpawn => "crius_shim_spawn_duration_seconds",
+        }
+    }
+
+    /// 区分同族内不同阶段的标签名；shim 启动只有一个阶段。
+    fn label_name(self) -> Option<&'static str> {
+        match self {
+            OperationPhase::ContainerCreate => Some("phase"),
+            OperationPhase::Cni => Some("command"),
+            OperationPhase::ImagePull => Some("stage"),
+            OperationPhase::ShimSpawn => None,
+        }
+    }
+
+    fn help(self) -> &'static str {
+        match self {
+            OperationPhase::ContainerCreate => "Duration of CreateContainer phases.",
+            OperationPhase::Cni => "Duration of CNI plugin chain invocations.",
+            OperationPhase::ImagePull => "Duration of image pull stages.",
+            OperationPhase::ShimSpawn => "Duration from shim spawn until its RPC socket is ready.",
+        }
+    }
+}
+
+/// CRI 操作指标族的句柄。
+#[derive(Debug)]
+pub struct OperationMetrics {
+    requests: Counter,
+    latency: Histogram,
+    in_flight: Gauge,
+    phases: Vec<(OperationPhase, Histogram)>,
+}
+
+/// 返回登记在进程级注册表中的 CRI 操作指标。
+pub fn operation_metrics() -> &'static OperationMetrics {
+    static METRICS: OnceLock<OperationMetrics> = OnceLock::new();
+    METRICS.get_or_init(|| OperationMetrics::register(metrics_registry()))
+}
+
+/// 记录一次子阶段耗时。
+pub fn observe_phase(phase: OperationPhase, label: &str, success: bool, elapsed: Duration) {
+    operation_metrics().observe_phase(phase, label, success, elapsed);
+}
+
+/// 子阶段计时器；未调用 `succeed` 即被丢弃（如 `?` 提前返回）时按失败记录。
+#[derive(Debug)]
+pub struct PhaseTimer {
+    phase: OperationPhase,
+    label: &'static str,
+    started: Instant,
+    done: bool,
+}
+
+impl PhaseTimer {
+    pub fn start(phase: OperationPhase, label: &'static str) -> Self {
+        Self {
+            phase,
+            label,
+            started: Instant::now(),
+            done: false,
+        }
+    }
+
+    pub fn succeed(mut self) {
+        self.done = true;
+        observe_phase(self.phase, self.label, true, self.started.elapsed());
+    }
+}
+
+impl Drop for PhaseTimer {
+    fn drop(&mut self) {
+        if !self.done {
+            observe_phase(self.phase, self.label, false, self.started.elapsed());
+        }
+    }
+}
+
+impl OperationMetrics {
+    /// 在注册表中登记 RPC 与全部子阶段指标族；子阶段族即使尚无样本也会输出元数据。
+    pub fn register(registry: &MetricsRegistry) -> Self {
+        let phases = OperationPhase::ALL
+            .into_iter()
+            .map(|phase| {
+                let label_names = match phase.label_name() {
+                    Some(label_name) => vec![label_name, "result"],
+                    None => vec!["result"],
+                };
+                let histogram = registry.histogram(
+                    phase.metric_name(),
+                    phase.help(),
+                    &label_names,
+                    LATENCY_BUCKETS_SECONDS,
+                );
+                (phase, histogram)
+            })
+            .collect();
+        Self {
+            requests: registry.counter(
+                "crius_grpc_requests_total",
+                "CRI gRPC requests completed, by status code.",
+                &["service", "method", "code"],
+            ),
+            latency: registry.histogram(
+                "crius_grpc_request_duration_seconds",
+                "CRI gRPC handling latency until response headers.",
+                &["service", "method"],
+                LATENCY_BUCKETS_SECONDS,
+            ),
+            in_flight: registry.gauge(
+                "crius_grpc_requests_in_flight",
+                "CRI gRPC requests being handled.",
+                &["service", "method"],
+            ),
+            phases,
+        }
+    }
+
+    /// 登记一个开始处理的 RPC；返回值在完成或被丢弃时计入计数与耗时。
+    pub fn rpc_started(&'static self, service: &str, method: &str) -> RpcInFlight {
+        self.in_flight.add(&[service, method], 1.0);
+        RpcInFlight {
+            metrics: self,
+            service: service.to_string(),
+            method: method.to_string(),
+            started: Instant::now(),
+            finished: false,
+        }
+    }
+
+    fn rpc_finished(&self, service: &str, method: &str, code: &str, elapsed: Duration) {
+        self.in_flight.add(&[service, method], -1.0);
+        self.requests.inc(&[service, method, code]);
+        self.latency.observe_duration(&[service, method], elapsed);
+    }
+
+    pub fn observe_phase(
+        &self,
+        phase: OperationPhase,
+        label: &str,
+        success: bool,
+        elapsed: Duration,
+    ) {
+        let Some((_, histogram)) = self
+            .phases
+            .iter()
+            .find(|(candidate, _)| *candidate == phase)
+        else {
+            return;
+        };
+        let result = if success { "success" } else { "error" };
+        match phase.label_name() {
+            Some(_) => histogram.observe_duration(&[label, result], elapsed),
+            None => histogram.observe_duration(&[result], elapsed),
+        }
+    }
+}
+
+/// 正在处理的 RPC；未显式完成即被丢弃时按客户端取消计数。
+#[derive(Debug)]
+pub struct RpcInFlight {
+    metrics: &'static OperationMetrics,
+    service: String,
+    method: String,
+    started: Instant,
+    finished: bool,
+}
+
+impl RpcInFlight {
+    pub fn finish(mut self, code: &str) {
+        self.finished = true;
+        self.metrics
+            .rpc_finished(&self.service, &self.method, code, self.started.elapsed());
+    }
+}
+
+impl Drop for RpcInFlight {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.metrics.rpc_finished(
+                &self.service,
+                &self.method,
+                "Cancelled",
+                self.started.elapsed(),
+            );
+        }
+    }
+}
+
+/// 从 gRPC 路径 `/package.Service/Method` 中解析出需要统计的 service 与 method。
+fn instrumented_rpc(path: &str) -> Option<(&str, &str)> {
+    let (service, method) = path.strip_prefix('/')?.split_once('/')?;
+    (INSTRUMENTED_SERVICES.contains(&service) && !method.is_empty()).then_some((service, method))
+}
+
+/// tonic 将 handler 返回的错误编码为 trailers-only 响应，状态码位于响应头；
+/// 正常响应的状态码在 trailer 中，响应头里没有 `grpc-status` 即视为 OK。
+fn response_code_label<B>(response: &http::Response<B>) -> String {
+    let code = response
+        .headers()
+        .get("grpc-status")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<i32>().ok())
+        .map(tonic::Code::from_i32)
+        .unwrap_or(tonic::Code::Ok);
+    code_label(code)
+}
+
+fn code_label(code: tonic::Code) -> String {
+    match code {
+        tonic::Code::Ok => "OK".to_string(),
+        other => format!("{other:?}"),
+    }
+}
+
+/// 为 RuntimeService/ImageService 记录请求计数、耗时与在途数的 tower layer。
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RpcMetricsLayer;
+
+impl<S> Layer<S> for RpcMetricsLayer {
+    type Service = RpcMetricsService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcMetricsService { inner }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RpcMetricsService<S> {
+    inner: S,
+}
+
+impl<S, ReqBody, ResBody> Service<http::Request<ReqBody>> for RpcMetricsService<S>
+where
+    S: Service<http::Request<ReqBody>, Response = http::Response<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<ReqBody>) -> Self::Future {
+        let in_flight = instrumented_rpc(request.uri().path())
+            .map(|(service, method)| operation_metrics().rpc_started(service, method));
+        let future = self.inner.call(request);
+        Box::pin(async move {
+            let result = future.await;
+            if let Some(in_flight) = in_flight {
+                match &result {
+                    Ok(response) => in_flight.finish(&response_code_label(response)),
+                    Err(_) => in_flight.finish(&code_label(tonic::Code::Internal)),
+                }
+            }
+            result
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::registry::{encode, ExpositionFormat};
+
+    #[test]
+    fn instrumented_rpc_only_matches_cri_services() {
+        assert_eq!(
+            instrumented_rpc("/runtime.v1.RuntimeService/RunPodSandbox"),
+            Some(("runtime.v1.RuntimeService", "RunPodSandbox"))
+        );
+        assert_eq!(
+            instrumented_rpc("/runtime.v1.ImageService/PullImage"),
+            Some(("runtime.v1.ImageService", "PullImage"))
+        );
+        assert_eq!(instrumented_rpc("/crius.local.v1.LocalService/Exec"), None);
+        assert_eq!(instrumented_rpc("/runtime.v1.RuntimeService/"), None);
+    }
+
+    #[test]
+    fn response_code_reads_trailers_only_status() {
+        let ok = http::Response::new(());
+        assert_eq!(response_code_label(&ok), "OK");
+        let not_found = http::Response::builder()
+            .header("grpc-status", "5")
+            .body(())
+            .unwrap();
+        assert_eq!(response_code_label(&not_found), "NotFound");
+    }
+
+    #[test]
+    fn render_emits_rpc_and_phase_families() {
+        let registry = MetricsRegistry::default();
+        let metrics: &'static OperationMetrics =
+            Box::leak(Box::new(OperationMetrics::register(&registry)));
+        metrics
+            .rpc_started("runtime.v1.RuntimeService", "Version")
+            .finish("OK");
+        let pending = metrics.rpc_started("runtime.v1.ImageService", "PullImage");
+        drop(metrics.rpc_started("runtime.v1.ImageService", "PullImage"));
+        metrics.observe_phase(
+            OperationPhase::ContainerCreate,
+            "build_spec",
+            true,
+            Duration::from_millis(30),
+        );
+        metrics.observe_phase(OperationPhase::ShimSpawn, "", false, Duration::from_secs(2));
+
+        let output = encode(&[&registry], ExpositionFormat::PrometheusText);
+        drop(pending);
+
+        assert!(output.contains(
+            "crius_grpc_requests_total{service=\"runtime.v1.RuntimeService\",method=\"Version\",code=\"OK\"} 1\n"
+        ));
+        assert!(output.contains(
+            "crius_grpc_requests_total{service=\"runtime.v1.ImageService\",method=\"PullImage\",code=\"Cancelled\"} 1\n"
+        ));
+        assert!(output.contains(
+            "crius_grpc_requests_in_flight{service=\"runtime.v1.ImageService\",method=\"PullImage\"} 1\n"
+        ));
+        assert!(output.contains(
+            "crius_grpc_request_duration_seconds_count{service=\"runtime.v1.RuntimeService\",method=\"Version\"} 1\n"
+        ));
+        assert!(output.contains(
+            "crius_container_create_phase_duration_seconds_bucket{phase=\"build_spec\",result=\"success\",le=\"0.05\"} 1\n"
+        ));
+        assert!(output.contains(
+            "crius_container_create_phase_duration_seconds_bucket{phase=\"build_spec\",result=\"success\",le=\"0.025\"} 0\n"
+        ));
+        assert!(output.contains(
+            "crius_shim_spawn_duration_seconds_bucket{result=\"error\",le=\"+Inf\"} 1\n"
+        ));
+        assert!(output.contains("# TYPE crius_cni_operation_duration_seconds histogram\n"));
+    }
+}
@@ -0,0 +1,596 @@
+//! 进程内指标注册表与文本编码
+//!
+//! 指标族（counter / gauge / histogram）按名称登记一次，样本以标签值区分。server、image、
+//! network、NRI 与 shim manager 把长期累计的指标登记到进程级 [`metrics_registry`]；metrics 服务
+//! 每次抓取时另建一个临时 [`MetricsRegistry`] 写入各 provider 的快照，两者按 `Accept` 头协商后
+//! 编码为 Prometheus 文本 0.0.4 或 OpenMetrics 1.0.0。
+//!
+//! 启用 tracing 导出时，直方图在观测点读取当前已采样 span 的 trace ID，作为所落桶的 exemplar；
+//! exemplar 只出现在 OpenMetrics 输出中。
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// 延迟直方图桶上界（秒），覆盖从毫秒级状态查询到分钟级镜像拉取。
+pub const LATENCY_BUCKETS_SECONDS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0,
+];
+
+pub const PROMETHEUS_TEXT_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+pub const OPENMETRICS_CONTENT_TYPE: &str =
+    "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricKind {
+    Counter,
+    Gauge,
+    Histogram,
+}
+
+impl MetricKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            MetricKind::Counter => "counter",
+            MetricKind::Gauge => "gauge",
+            MetricKind::Histogram => "histogram",
+        }
+    }
+}
+
+/// `/metrics` 响应的文本格式。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExpositionFormat {
+    /// Prometheus 文本格式 0.0.4。
+    #[default]
+    PrometheusText,
+    /// OpenMetrics 1.0.0，counter 元数据名不带 `_total`，直方图桶可携带 exemplar。
+    OpenMetrics,
+}
+
+impl ExpositionFormat {
+    /// 按 `Accept` 头协商：OpenMetrics 的 q 值大于 0 且不低于纯文本时选用 OpenMetrics，
+    /// 缺省或无法识别时退回 0.0.4。
+    pub fn negotiate(accept: Option<&str>) -> Self {
+        let Some(accept) = accept else {
+            return Self::PrometheusText;
+        };
+        let mut openmetrics = 0.0f32;
+        let mut text = 0.0f32;
+        for range in accept.split(',') {
+            let mut params = range.split(';');
+            let media_type = params
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_ascii_lowercase();
+            let quality = params
+                .filter_map(|param| param.split_once('='))
+                .find(|(key, _)| key.trim().eq_ignore_ascii_case("q"))
+                .and_then(|(_, value)| value.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            match media_type.as_str() {
+                "application/openmetrics-text" => openmetrics = openmetrics.max(quality),
+                "text/plain" | "text/*" | "*/*" => text = text.max(quality),
+                _ => {}
+            }
+        }
+        if openmetrics > 0.0 && openmetrics >= text {
+            Self::OpenMetrics
+        } else {
+            Self::PrometheusText
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::PrometheusText => PROMETHEUS_TEXT_CONTENT_TYPE,
+            Self::OpenMetrics => OPENMETRICS_CONTENT_TYPE,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Exemplar {
+    trace_id: String,
+    value: f64,
+    timestamp: f64,
+}
+
+impl Exemplar {
+    /// 当前 span 所属的已采样 trace；未安装 tracing 导出层或 trace 未采样时不记录。
+    fn current(value: f64) -> Option<Self> {
+        let context = crate::trace_export::current_trace_context()?;
+        if !context.sampled {
+            return None;
+        }
+        Some(Self {
+            trace_id: context.trace_id_hex(),
+            value,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs_f64())
+                .unwrap_or_default(),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct HistogramSeries {
+    /// 各桶的非累计计数，末尾一项对应 `+Inf`。
+    counts: Vec<u64>,
+    sum: f64,
+    /// 各桶最近一次带 trace 的观测。
+    exemplars: Vec<Option<Exemplar>>,
+}
+
+#[derive(Debug, Clone)]
+enum Series {
+    Value(f64),
+    Histogram(HistogramSeries),
+}
+
+#[derive(Debug)]
+struct Family {
+    name: String,
+    help: String,
+    kind: MetricKind,
+    label_names: Vec<String>,
+    buckets: Vec<f64>,
+    series: BTreeMap<Vec<String>, Series>,
+}
+
+impl Family {
+    fn series_mut(&mut self, labels: &[&str]) -> Option<&mut Series> {
+        debug_assert_eq!(
+            labels.len(),
+            self.label_names.len(),
+            "label values for {}",
+            self.name
+        );
+        if labels.len() != self.label_names.len() {
+            return None;
+        }
+        let kind = self.kind;
+        let slots = self.buckets.len() + 1;
+        let series = self
+            .series
+            .entry(labels.iter().map(|label| label.to_string()).collect())
+            .or_insert_with(|| match kind {
+                MetricKind::Histogram => Series::Histogram(HistogramSeries {
+                    counts: vec![0; slots],
+                    sum: 0.0,
+                    exemplars: vec![None; slots],
+                }),
+                MetricKind::Counter | MetricKind::Gauge => Series::Value(0.0),
+            });
+        Some(series)
+    }
+
+    fn render(&self, output: &mut String, format: ExpositionFormat) {
+        let metadata_name = match (format, self.kind) {
+            (ExpositionFormat::OpenMetrics, MetricKind::Counter) => {
+                self.name.strip_suffix("_total").unwrap_or(&self.name)
+            }
+            _ => self.name.as_str(),
+        };
+        let sample_name = match (format, self.kind) {
+            (ExpositionFormat::OpenMetrics, MetricKind::Counter) => {
+                format!("{metadata_name}_total")
+            }
+            _ => self.name.clone(),
+        };
+        output.push_str(&format!(
+            "# HELP {metadata_name} {}\n",
+            escape_help(&self.help, format)
+        ));
+        output.push_str(&format!("# TYPE {metadata_name} {}\n", self.kind.as_str()));
+
+        for (values, series) in &self.series {
+            let labels = self
+                .label_names
+                .iter()
+                .zip(values)
+                .map(|(name, value)| format!("{name}=\"{}\"", escape_label_value(value)))
+                .collect::<Vec<_>>()
+                .join(",");
+            match series {
+                Series::Value(value) => push_sample(output, &sample_name, &labels, *value, None),
+                Series::Histogram(histogram) => {
+                    let separator = if labels.is_empty() { "" } else { "," };
+                    let mut cumulative = 0u64;
+                    for (index, count) in histogram.counts.iter().enumerate() {
+                        cumulative += count;
+                        let bound = self
+                            .buckets
+                            .get(index)
+                            .map(|bound| format_bound(*bound, format))
+                            .unwrap_or_else(|| "+Inf".to_string());
+                        let exemplar = match format {
+                            ExpositionFormat::OpenMetrics => histogram.exemplars[index].as_ref(),
+                            ExpositionFormat::PrometheusText => None,
+                        };
+                        push_sample(
+                            output,
+                            &format!("{sample_name}_bucket"),
+                            &format!("{labels}{separator}le=\"{bound}\""),
+                            cumulative as f64,
+                            exemplar,
+                        );
+                    }
+                    push_sample(
+                        output,
+                        &format!("{sample_name}_sum"),
+                        &labels,
+                        histogram.sum,
+                        None,
+                    );
+                    push_sample(
+                        output,
+                        &format!("{sample_name}_count"),
+                        &labels,
+                        cumulative as f64,
+                        None,
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn push_sample(
+    output: &mut String,
+    name: &str,
+    labels: &str,
+    value: f64,
+    exemplar: Option<&Exemplar>,
+) {
+    output.push_str(name);
+    if !labels.is_empty() {
+        output.push('{');
+        output.push_str(labels);
+        output.push('}');
+    }
+    output.push(' ');
+    output.push_str(&format_value(value));
+    if let Some(exemplar) = exemplar {
+        output.push_str(&format!(
+            " # {{trace_id=\"{}\"}} {} {}",
+            exemplar.trace_id,
+            format_value(exemplar.value),
+            format_value(exemplar.timestamp)
+        ));
+    }
+    output.push('\n');
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value == f64::INFINITY {
+        "+Inf".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "-Inf".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// OpenMetrics 要求 `le` 为规范浮点数，整数上界输出为 `1.0`。
+fn format_bound(bound: f64, format: ExpositionFormat) -> String {
+    if format == ExpositionFormat::OpenMetrics && bound.is_finite() && bound.fract() == 0.0 {
+        format!("{bound:.1}")
+    } else {
+        format_value(bound)
+    }
+}
+
+fn escape_help(help: &str, format: ExpositionFormat) -> String {
+    let help = help.replace('\\', "\\\\").replace('\n', "\\n");
+    match format {
+        ExpositionFormat::OpenMetrics => help.replace('"', "\\\""),
+        ExpositionFormat::PrometheusText => help,
+    }
+}
+
+pub(crate) fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn update(family: &Mutex<Family>, labels: &[&str], apply: impl FnOnce(&mut Series)) {
+    if let Ok(mut family) = family.lock() {
+        if let Some(series) = family.series_mut(labels) {
+            apply(series);
+        }
+    }
+}
+
+/// 单调递增计数；`labels` 按登记时的标签名顺序给出取值。
+#[derive(Debug, Clone)]
+pub struct Counter {
+    family: Arc<Mutex<Family>>,
+}
+
+impl Counter {
+    pub fn inc(&self, labels: &[&str]) {
+        self.inc_by(labels, 1.0);
+    }
+
+    pub fn inc_by(&self, labels: &[&str], value: f64) {
+        update(&self.family, labels, |series| {
+            if let Series::Value(current) = series {
+                *current += value.max(0.0);
+            }
+        });
+    }
+
+    /// 直接写入由子系统自行累计的计数，用于抓取时的快照（如 netns 池命中数）。
+    pub fn set(&self, labels: &[&str], value: f64) {
+        update(&self.family, labels, |series| {
+            if let Series::Value(current) = series {
+                *current = value;
+            }
+        });
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Gauge {
+    family: Arc<Mutex<Family>>,
+}
+
+impl Gauge {
+    pub fn set(&self, labels: &[&str], value: f64) {
+        update(&self.family, labels, |series| {
+            if let Series::Value(current) = series {
+                *current = value;
+            }
+        });
+    }
+
+    pub fn add(&self, labels: &[&str], delta: f64) {
+        update(&self.family, labels, |series| {
+            if let Series::Value(current) = series {
+                *current += delta;
+            }
+        });
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Histogram {
+    family: Arc<Mutex<Family>>,
+}
+
+impl Histogram {
+    pub fn observe(&self, labels: &[&str], value: f64) {
+        self.record(labels, value, Exemplar::current(value));
+    }
+
+    pub fn observe_duration(&self, labels: &[&str], elapsed: Duration) {
+        self.observe(labels, elapsed.as_secs_f64());
+    }
+
+    fn record(&self, labels: &[&str], value: f64, exemplar: Option<Exemplar>) {
+        let Ok(mut family) = self.family.lock() else {
+            return;
+        };
+        let slot = family
+            .buckets
+            .iter()
+            .position(|bound| value <= *bound)
+            .unwrap_or(family.buckets.len());
+        if let Some(Series::Histogram(series)) = family.series_mut(labels) {
+            series.counts[slot] += 1;
+            series.sum += value;
+            if exemplar.is_some() {
+                series.exemplars[slot] = exemplar;
+            }
+        }
+    }
+}
+
+/// 指标族集合；同名指标族只登记一次，重复登记返回已有的族供多个调用点共享。
+#[derive(Debug, Default)]
+pub struct MetricsRegistry {
+    families: Mutex<Vec<(String, Arc<Mutex<Family>>)>>,
+}
+
+/// 返回进程级指标注册表。
+pub fn metrics_registry() -> &'static MetricsRegistry {
+    static REGISTRY: OnceLock<MetricsRegistry> = OnceLock::new();
+    REGISTRY.get_or_init(MetricsRegistry::default)
+}
+
+impl MetricsRegistry {
+    pub fn counter(&self, name: &str, help: &str, label_names: &[&str]) -> Counter {
+        Counter {
+            family: self.register(name, help, MetricKind::Counter, label_names, &[]),
+        }
+    }
+
+    pub fn gauge(&self, name: &str, help: &str, label_names: &[&str]) -> Gauge {
+        Gauge {
+            family: self.register(name, help, MetricKind::Gauge, label_names, &[]),
+        }
+    }
+
+    /// `buckets` 为递增的桶上界，`+Inf` 桶自动追加。
+    pub fn histogram(
+        &self,
+        name: &str,
+        help: &str,
+        label_names: &[&str],
+        buckets: &[f64],
+    ) -> Histogram {
+        Histogram {
+            family: self.register(name, help, MetricKind::Histogram, label_names, buckets),
+        }
+    }
+
+    fn register(
+        &self,
+        name: &str,
+        help: &str,
+        kind: MetricKind,
+        label_names: &[&str],
+        buckets: &[f64],
+    ) -> Arc<Mutex<Family>> {
+        let mut families = self
+            .families
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some((_, family)) = families.iter().find(|(existing, _)| existing == name) {
+            return family.clone();
+        }
+        let family = Arc::new(Mutex::new(Family {
+            name: name.to_string(),
+            help: help.to_string(),
+            kind,
+            label_names: label_names.iter().map(|label| label.to_string()).collect(),
+            buckets: buckets.to_vec(),
+            series: BTreeMap::new(),
+        }));
+        families.push((name.to_string(), family.clone()));
+        family
+    }
+
+    /// 按登记顺序追加全部指标族；OpenMetrics 的 `# EOF` 由 [`encode`] 统一追加。
+    pub fn render(&self, output: &mut String, format: ExpositionFormat) {
+        let families = self
+            .families
+            .lock()
+            .map(|families| {
+                families
+                    .iter()
+                    .map(|(_, family)| family.clone())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        for family in families {
+            if let Ok(family) = family.lock() {
+                family.render(output, format);
+            }
+        }
+    }
+}
+
+/// 依次编码多个注册表，得到完整的响应正文。
+pub fn encode(registries: &[&MetricsRegistry], format: ExpositionFormat) -> String {
+    let mut output = String::new();
+    for registry in registries {
+        registry.render(&mut output, format);
+    }
+    if format == ExpositionFormat::OpenMetrics {
+        output.push_str("# EOF\n");
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLED: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+    const UNSAMPLED: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-00";
+
+    #[test]
+    fn accept_header_selects_openmetrics_only_when_preferred() {
+        for (accept, expected) in [
+            (None, ExpositionFormat::PrometheusText),
+            (Some("*/*"), ExpositionFormat::PrometheusText),
+            (
+                Some("text/plain;version=0.0.4;q=1,*/*;q=0.1"),
+                ExpositionFormat::PrometheusText,
+            ),
+            (
+                Some(
+                    "application/openmetrics-text;version=1.0.0,application/openmetrics-text;version=0.0.1;q=0.75,text/plain;version=0.0.4;q=0.5,*/*;q=0.1",
+                ),
+                ExpositionFormat::OpenMetrics,
+            ),
+            (
+                Some("application/openmetrics-text;q=0.2,text/plain;q=0.5"),
+                ExpositionFormat::PrometheusText,
+            ),
+            (
+                Some("application/openmetrics-text;q=0"),
+                ExpositionFormat::PrometheusText,
+            ),
+        ] {
+            assert_eq!(ExpositionFormat::negotiate(accept), expected, "{accept:?}");
+        }
+    }
+
+    #[test]
+    fn prometheus_text_renders_families_in_registration_order() {
+        let registry = MetricsRegistry::default();
+        let requests = registry.counter("crius_test_requests_total", "Requests.", &["code"]);
+        let queued = registry.gauge("crius_test_queued", "Queued items.", &[]);
+        let latency = registry.histogram(
+            "crius_test_duration_seconds",
+            "Latency.",
+            &["method"],
+            &[0.1, 1.0],
+        );
+        requests.inc(&["OK"]);
+        requests.inc_by(&["Not\"Found"], 2.0);
+        queued.add(&[], 3.0);
+        queued.add(&[], -1.0);
+        latency.observe(&["Version"], 0.25);
+        latency.observe(&["Version"], 0.5);
+        latency.observe(&["Version"], 5.0);
+        assert!(registry
+            .counter("crius_test_requests_total", "Requests.", &["code"])
+            .family
+            .lock()
+            .is_ok_and(|family| family.series.len() == 2));
+
+        let output = encode(&[&registry], ExpositionFormat::PrometheusText);
+        assert!(output.starts_with(
+            "# HELP crius_test_requests_total Requests.\n# TYPE crius_test_requests_total counter\n"
+        ));
+        assert!(output.contains("crius_test_requests_total{code=\"Not\\\"Found\"} 2\n"));
+        assert!(output.contains("crius_test_queued 2\n"));
+        assert!(
+            output.contains("crius_test_duration_seconds_bucket{method=\"Version\",le=\"1\"} 2\n")
+        );
+        assert!(output
+            .contains("crius_test_duration_seconds_bucket{method=\"Version\",le=\"+Inf\"} 3\n"));
+        assert!(output.contains("crius_test_duration_seconds_sum{method=\"Version\"} 5.75\n"));
+        assert!(output.contains("crius_test_duration_seconds_count{method=\"Version\"} 3\n"));
+        assert!(!output.contains("# EOF"));
+    }
+
+    #[test]
+    fn openmetrics_strips_counter_suffix_and_attaches_sampled_exemplars() {
+        let registry = MetricsRegistry::default();
+        let requests = registry.counter("crius_test_requests_total", "Requests.", &[]);
+        let latency = registry.histogram("crius_test_duration_seconds", "Latency.", &[], &[1.0]);
+        requests.inc(&[]);
+        {
+            let _context = crate::trace_export::enter_remote_context(Some(SAMPLED));
+            latency.observe(&[], 0.25);
+        }
+        {
+            let _context = crate::trace_export::enter_remote_context(Some(UNSAMPLED));
+            latency.observe(&[], 2.0);
+        }
+
+        let output = encode(&[&registry], ExpositionFormat::OpenMetrics);
+        assert!(output.contains("# TYPE crius_test_requests counter\n"));
+        assert!(output.contains("crius_test_requests_total 1\n"));
+        assert!(output.contains(
+            "crius_test_duration_seconds_bucket{le=\"1.0\"} 1 # {trace_id=\"4bf92f3577b34da6a3ce929d0e0e4736\"} 0.25 "
+        ));
+        assert!(output.contains("crius_test_duration_seconds_bucket{le=\"+Inf\"} 2\n"));
+        assert!(output.ends_with("# EOF\n"));
+
+        let text = encode(&[&registry], ExpositionFormat::PrometheusText);
+        assert!(!text.contains("trace_id"));
+        assert!(text.contains("# TYPE crius_test_requests_total counter\n"));
+    }
+}
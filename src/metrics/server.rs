@@ -18,6 +18,9 @@ use tokio_rustls::TlsAcceptor;
 
 use crate::config::MetricsConfig;
 use crate::image::ImageMetricsProvider;
+use crate::metrics::registry::{
+    encode, metrics_registry, ExpositionFormat, MetricKind, MetricsRegistry,
+};
 use crate::server::RuntimeMetricsProvider;
 
 #[derive(Debug, Clone, Default)]
@@ -167,19 +170,31 @@ async fn start_uds_listener(socket_path: PathBuf, handler: MetricsHandler) -> an
     Ok(())
 }
 
+/// `/healthz` 只看 daemon 自身能否继续处理请求所依赖的子系统；`/readyz` 要求全部条件就绪。
+const LIVENESS_CONDITIONS: &[&str] = &["RuntimeReady", "ImageReady", "SnapshotReady", "ShimReady"];
+
 impl MetricsHandler {
     async fn handle(&self, req: Request<Body>) -> Response<Body> {
-        if req.method() != Method::GET || req.uri().path() != "/metrics" {
-            return Response::builder()
-                .status(StatusCode::NOT_FOUND)
-                .body(Body::from("not found"))
-                .unwrap_or_else(|_| Response::new(Body::from("not found")));
+        let head = req.method() == Method::HEAD;
+        if req.method() != Method::GET && !head {
+            return not_found();
+        }
+        match req.uri().path() {
+            "/metrics" if !head => {}
+            "/healthz" => return self.health_response(Some(LIVENESS_CONDITIONS), head).await,
+            "/readyz" => return self.health_response(None, head).await,
+            _ => return not_found(),
         }
 
-        match self.render_metrics().await {
+        let format = ExpositionFormat::negotiate(
+            req.headers()
+                .get(hyper::header::ACCEPT)
+                .and_then(|value| value.to_str().ok()),
+        );
+        match self.render_metrics(format).await {
             Ok(body) => Response::builder()
                 .status(StatusCode::OK)
-                .header("content-type", "text/plain; version=0.0.4")
+                .header("content-type", format.content_type())
                 .body(Body::from(body))
                 .unwrap_or_else(|_| Response::new(Body::from("internal error"))),
             Err(err) => Response::builder()
@@ -189,7 +204,13 @@ impl MetricsHandler {
         }
     }
 
-    async fn render_metrics(&self) -> anyhow::Result<String> {
+    async fn health_response(&self, required: Option<&[&str]>, head: bool) -> Response<Body> {
+        let conditions = self.runtime_provider.health_conditions().await;
+        let (status, body) = health_report(&conditions, required);
+        health_http_response(status, body.to_string(), head)
+    }
+
+    async fn render_metrics(&self, format: ExpositionFormat) -> anyhow::Result<String> {
         let runtime = self.runtime_provider.snapshot().await;
         let images = self.image_provider.snapshot().await;
         let enabled_collectors: HashSet<&str> = self
@@ -200,83 +221,690 @@ impl MetricsHandler {
             .filter(|collector| !collector.is_empty())
             .collect();
 
-        let mut output = String::new();
+        // 快照类指标每次抓取重新写入临时注册表；累计类指标位于进程级注册表。
+        let scrape = MetricsRegistry::default();
         if enabled_collectors.contains("runtime") {
-            output.push_str("# HELP crius_build_info Build information for crius.\n");
-            output.push_str("# TYPE crius_build_info gauge\n");
-            output.push_str(&format!(
-                "crius_build_info{{version=\"{}\"}} 1\n",
-                env!("CARGO_PKG_VERSION")
-            ));
-            output.push_str("# HELP crius_runtime_ready Runtime ready condition.\n");
-            output.push_str("# TYPE crius_runtime_ready gauge\n");
-            output.push_str(&format!(
-                "crius_runtime_ready {}\n",
-                bool_to_metric(runtime.runtime_ready)
-            ));
-            output.push_str("# HELP crius_network_ready Network ready condition.\n");
-            output.push_str("# TYPE crius_network_ready gauge\n");
-            output.push_str(&format!(
-                "crius_network_ready {}\n",
-                bool_to_metric(runtime.network_ready)
-            ));
+            scrape
+                .gauge(
+                    "crius_build_info",
+                    "Build information for crius.",
+                    &["version"],
+                )
+                .set(&[env!("CARGO_PKG_VERSION")], 1.0);
+            scrape
+                .gauge("crius_runtime_ready", "Runtime ready condition.", &[])
+                .set(&[], f64::from(bool_to_metric(runtime.runtime_ready)));
+            scrape
+                .gauge("crius_network_ready", "Network ready condition.", &[])
+                .set(&[], f64::from(bool_to_metric(runtime.network_ready)));
+            let conditions = self.runtime_provider.health_conditions().await;
+            render_health_conditions(&scrape, &conditions);
+            let nri_plugins = self.runtime_provider.nri_plugin_status().await;
+            render_nri_plugins(&scrape, &nri_plugins);
         }
         if enabled_collectors.contains("resources") {
-            output.push_str("# HELP crius_containers Number of tracked containers.\n");
-            output.push_str("# TYPE crius_containers gauge\n");
-            output.push_str(&format!("crius_containers {}\n", runtime.container_count));
-            output.push_str("# HELP crius_pod_sandboxes Number of tracked pod sandboxes.\n");
-            output.push_str("# TYPE crius_pod_sandboxes gauge\n");
-            output.push_str(&format!(
-                "crius_pod_sandboxes {}\n",
-                runtime.pod_sandbox_count
-            ));
-            output.push_str("# HELP crius_event_subscribers Number of GetEvents subscribers.\n");
-            output.push_str("# TYPE crius_event_subscribers gauge\n");
-            output.push_str(&format!(
-                "crius_event_subscribers {}\n",
-                runtime.event_subscriber_count
-            ));
-            output.push_str("# HELP crius_stats_cache_entries Number of stats cache entries.\n");
-            output.push_str("# TYPE crius_stats_cache_entries gauge\n");
-            output.push_str(&format!(
-                "crius_stats_cache_entries{{kind=\"container\"}} {}\n",
-                runtime.container_stats_cache_entries
-            ));
-            output.push_str(&format!(
-                "crius_stats_cache_entries{{kind=\"pod\"}} {}\n",
-                runtime.pod_stats_cache_entries
-            ));
-            output.push_str(&format!(
-                "crius_stats_cache_entries{{kind=\"pod_metrics\"}} {}\n",
-                runtime.pod_metrics_cache_entries
-            ));
+            scrape
+                .gauge("crius_containers", "Number of tracked containers.", &[])
+                .set(&[], runtime.container_count as f64);
+            scrape
+                .gauge(
+                    "crius_pod_sandboxes",
+                    "Number of tracked pod sandboxes.",
+                    &[],
+                )
+                .set(&[], runtime.pod_sandbox_count as f64);
+            scrape
+                .gauge(
+                    "crius_event_subscribers",
+                    "Number of GetEvents subscribers.",
+                    &[],
+                )
+                .set(&[], runtime.event_subscriber_count as f64);
+            render_event_subscribers(&scrape, &runtime);
+            let cache_entries = scrape.gauge(
+                "crius_stats_cache_entries",
+                "Number of stats cache entries.",
+                &["kind"],
+            );
+            cache_entries.set(&["container"], runtime.container_stats_cache_entries as f64);
+            cache_entries.set(&["pod"], runtime.pod_stats_cache_entries as f64);
+            cache_entries.set(&["pod_metrics"], runtime.pod_metrics_cache_entries as f64);
+            scrape
+                .gauge(
+                    "crius_netns_pool_capacity",
+                    "Configured size of the pre-warmed netns pool.",
+                    &[],
+                )
+                .set(&[], runtime.netns_pool.capacity as f64);
+            scrape
+                .gauge(
+                    "crius_netns_pool_available",
+                    "Pre-warmed netns ready to be handed out.",
+                    &[],
+                )
+                .set(&[], runtime.netns_pool.available as f64);
+            let checkouts = scrape.counter(
+                "crius_netns_pool_checkouts_total",
+                "Pod sandbox netns requests served by the pool.",
+                &["result"],
+            );
+            checkouts.set(&["hit"], runtime.netns_pool.hits as f64);
+            checkouts.set(&["miss"], runtime.netns_pool.misses as f64);
+            scrape
+                .counter(
+                    "crius_netns_pool_create_failures_total",
+                    "Failed background netns pre-creations.",
+                    &[],
+                )
+                .set(&[], runtime.netns_pool.create_failures as f64);
+            render_pod_network_interfaces(&scrape, &runtime.pod_network_interfaces);
+            let footprint = self.runtime_provider.runtime_footprint().await;
+            crate::metrics::footprint::render_runtime_footprint(&scrape, &footprint);
         }
         if enabled_collectors.contains("images") {
-            output.push_str("# HELP crius_images Number of tracked images.\n");
-            output.push_str("# TYPE crius_images gauge\n");
-            output.push_str(&format!("crius_images {}\n", images.image_count));
-            output.push_str("# HELP crius_image_size_bytes Total logical image size.\n");
-            output.push_str("# TYPE crius_image_size_bytes gauge\n");
-            output.push_str(&format!(
-                "crius_image_size_bytes {}\n",
-                images.total_image_size_bytes
-            ));
-            output.push_str("# HELP crius_image_fs_usage_bytes Image filesystem bytes used.\n");
-            output.push_str("# TYPE crius_image_fs_usage_bytes gauge\n");
-            output.push_str(&format!(
-                "crius_image_fs_usage_bytes {}\n",
-                images.image_fs_bytes_used
-            ));
-            output.push_str("# HELP crius_image_fs_inodes_used Image filesystem inodes used.\n");
-            output.push_str("# TYPE crius_image_fs_inodes_used gauge\n");
-            output.push_str(&format!(
-                "crius_image_fs_inodes_used {}\n",
-                images.image_fs_inodes_used
-            ));
+            scrape
+                .gauge("crius_images", "Number of tracked images.", &[])
+                .set(&[], images.image_count as f64);
+            scrape
+                .gauge("crius_image_size_bytes", "Total logical image size.", &[])
+                .set(&[], images.total_image_size_bytes as f64);
+            scrape
+                .gauge(
+                    "crius_image_fs_usage_bytes",
+                    "Image filesystem bytes used.",
+                    &[],
+                )
+                .set(&[], images.image_fs_bytes_used as f64);
+            scrape
+                .gauge(
+                    "crius_image_fs_inodes_used",
+                    "Image filesystem inodes used.",
+                    &[],
+                )
+                .set(&[], images.image_fs_inodes_used as f64);
+        }
+        if enabled_collectors.contains("containers") {
+            let containers = self.runtime_provider.container_resource_stats().await;
+            render_container_resources(&scrape, &containers, &runtime.pod_network_interfaces);
+        }
+
+        let mut registries = vec![&scrape];
+        if enabled_collectors.contains("operations") {
+            // 先登记 CRI 操作指标族，daemon 尚未处理请求时也输出元数据。
+            crate::metrics::operations::operation_metrics();
+            registries.push(metrics_registry());
+        }
+
+        Ok(encode(&registries, format))
+    }
+}
+
+fn not_found() -> Response<Body> {
+    Response::builder()
+        .status(StatusCode::NOT_FOUND)
+        .body(Body::from("not found"))
+        .unwrap_or_else(|_| Response::new(Body::from("not found")))
+}
+
+/// HEAD 探测与 GET 得到相同的状态码和头部，只是不带响应体。
+fn health_http_response(status: StatusCode, body: String, head: bool) -> Response<Body> {
+    let builder = Response::builder()
+        .status(status)
+        .header("content-type", "application/json")
+        .header("content-length", body.len());
+    let body = if head {
+        Body::empty()
+    } else {
+        Body::from(body)
+    };
+    builder
+        .body(body)
+        .unwrap_or_else(|_| Response::new(Body::from("internal error")))
+}
+
+/// 生成 `/healthz`、`/readyz` 的状态码与 JSON；`required` 为空时要求所有条件就绪。
+fn health_report(
+    conditions: &[crate::services::InternalHealthCondition],
+    required: Option<&[&str]>,
+) -> (StatusCode, serde_json::Value) {
+    let failed = conditions
+        .iter()
+        .filter(|condition| {
+            required
+                .map(|required| required.contains(&condition.condition_type.as_str()))
+                .unwrap_or(true)
+        })
+        .filter(|condition| !condition.ready)
+        .map(|condition| condition.condition_type.clone())
+        .collect::<Vec<_>>();
+    let status = if failed.is_empty() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    let body = serde_json::json!({
+        "status": if failed.is_empty() { "ok" } else { "failed" },
+        "failed": failed,
+        "subsystems": conditions,
+    });
+    (status, body)
+}
+
+fn render_health_conditions(
+    registry: &MetricsRegistry,
+    conditions: &[crate::services::InternalHealthCondition],
+) {
+    let gauge = registry.gauge(
+        "crius_health_condition",
+        "Internal health condition, 1 when ready, labelled with its current reason.",
+        &["type", "reason"],
+    );
+    for condition in conditions {
+        gauge.set(
+            &[condition.condition_type.as_str(), condition.reason.as_str()],
+            f64::from(bool_to_metric(condition.ready)),
+        );
+    }
+}
+
+fn render_nri_plugins(registry: &MetricsRegistry, plugins: &[crate::nri::NriPluginStatus]) {
+    if plugins.is_empty() {
+        return;
+    }
+    let gauge = registry.gauge(
+        "crius_nri_plugin_connected",
+        "NRI plugin connection state.",
+        &["plugin", "index", "synchronized"],
+    );
+    for plugin in plugins {
+        let synchronized = plugin.synchronized.to_string();
+        gauge.set(
+            &[
+                plugin.name.as_str(),
+                plugin.index.as_str(),
+                synchronized.as_str(),
+            ],
+            f64::from(bool_to_metric(plugin.connected)),
+        );
+    }
+}
+
+fn render_pod_network_interfaces(
+    registry: &MetricsRegistry,
+    interfaces: &[crate::metrics::PodNetworkInterfaceStats],
+) {
+    for (index, (counter, _)) in crate::metrics::NetworkStats::default()
+        .counters()
+        .into_iter()
+        .enumerate()
+    {
+        let family = registry.counter(
+            &format!("crius_pod_network_{counter}_total"),
+            &format!(
+                "Per-interface {} counter from the pod network namespace.",
+                counter.replace('_', " ")
+            ),
+            &["pod_id", "namespace", "pod", "interface"],
+        );
+        for stats in interfaces {
+            family.set(
+                &[
+                    stats.pod_id.as_str(),
+                    stats.pod_namespace.as_str(),
+                    stats.pod_name.as_str(),
+                    stats.interface.name.as_str(),
+                ],
+                stats.interface.counters()[index].1 as f64,
+            );
+        }
+    }
+}
+
+fn render_event_subscribers(
+    registry: &MetricsRegistry,
+    runtime: &crate::metrics::RuntimeMetricsSnapshot,
+) {
+    registry
+        .counter(
+            "crius_container_events_dropped_total",
+            "CRI events dropped because a GetContainerEvents subscriber queue overflowed.",
+            &[],
+        )
+        .set(&[], runtime.event_dropped_total as f64);
+    let dropped = registry.counter(
+        "crius_container_events_subscriber_dropped_total",
+        "CRI events dropped for a connected GetContainerEvents subscriber.",
+        &["subscriber"],
+    );
+    let queued = registry.gauge(
+        "crius_container_events_subscriber_queued",
+        "CRI events waiting in a GetContainerEvents subscriber queue.",
+        &["subscriber"],
+    );
+    let resyncs = registry.counter(
+        "crius_container_events_subscriber_resyncs_total",
+        "Current-state resyncs sent to a GetContainerEvents subscriber after overflow.",
+        &["subscriber"],
+    );
+    for subscriber in &runtime.event_subscribers {
+        let id = subscriber.id.to_string();
+        dropped.set(&[id.as_str()], subscriber.dropped as f64);
+        queued.set(&[id.as_str()], subscriber.queued as f64);
+        resyncs.set(&[id.as_str()], subscriber.resyncs as f64);
+    }
+}
+
+/// 单个容器的 cAdvisor 样本取值；返回 `None` 表示该容器缺少对应 cgroup 数据。
+type ContainerSampleFn = fn(&crate::metrics::ContainerResourceStats) -> Option<f64>;
+
+/// 与 kubelet `/metrics/cadvisor` 同名的单序列容器指标族。
+const CONTAINER_RESOURCE_FAMILIES: &[(&str, MetricKind, &str, ContainerSampleFn)] = &[
+    (
+        "container_cpu_usage_seconds_total",
+        MetricKind::Counter,
+        "Cumulative cpu time consumed in seconds.",
+        |c| {
+            c.stats
+                .cpu
+                .as_ref()
+                .map(|cpu| nanos_to_seconds(cpu.usage_total))
+        },
+    ),
+    (
+        "container_cpu_user_seconds_total",
+        MetricKind::Counter,
+        "Cumulative user cpu time consumed in seconds.",
+        |c| {
+            c.stats
+                .cpu
+                .as_ref()
+                .map(|cpu| nanos_to_seconds(cpu.usage_user))
+        },
+    ),
+    (
+        "container_cpu_system_seconds_total",
+        MetricKind::Counter,
+        "Cumulative system cpu time consumed in seconds.",
+        |c| {
+            c.stats
+                .cpu
+                .as_ref()
+                .map(|cpu| nanos_to_seconds(cpu.usage_kernel))
+        },
+    ),
+    (
+        "container_cpu_cfs_periods_total",
+        MetricKind::Counter,
+        "Number of elapsed enforcement period intervals.",
+        |c| c.stats.cpu.as_ref().map(|cpu| cpu.throttle_periods as f64),
+    ),
+    (
+        "container_cpu_cfs_throttled_periods_total",
+        MetricKind::Counter,
+        "Number of throttled period intervals.",
+        |c| c.stats.cpu.as_ref().map(|cpu| cpu.throttled_count as f64),
+    ),
+    (
+        "container_cpu_cfs_throttled_seconds_total",
+        MetricKind::Counter,
+        "Total time duration the container has been throttled.",
+        |c| {
+            c.stats
+                .cpu
+                .as_ref()
+                .map(|cpu| nanos_to_seconds(cpu.throttled_time))
+        },
+    ),
+    (
+        "container_memory_usage_bytes",
+        MetricKind::Gauge,
+        "Current memory usage in bytes, including all memory regardless of when it was accessed.",
+        |c| c.stats.memory.as_ref().map(|memory| memory.usage as f64),
+    ),
+    (
+        "container_memory_working_set_bytes",
+        MetricKind::Gauge,
+        "Current working set in bytes.",
+        |c| {
+            c.stats
+                .memory
+                .as_ref()
+                .map(|memory| memory.working_set() as f64)
+        },
+    ),
+    (
+        "container_memory_rss",
+        MetricKind::Gauge,
+        "Size of RSS in bytes.",
+        |c| c.stats.memory.as_ref().map(|memory| memory.rss as f64),
+    ),
+    (
+        "container_memory_cache",
+        MetricKind::Gauge,
+        "Number of bytes of page cache memory.",
+        |c| c.stats.memory.as_ref().map(|memory| memory.cache as f64),
+    ),
+    (
+        "container_memory_swap",
+        MetricKind::Gauge,
+        "Container swap usage in bytes.",
+        |c| c.stats.memory.as_ref().map(|memory| memory.swap as f64),
+    ),
+    (
+        "container_memory_max_usage_bytes",
+        MetricKind::Gauge,
+        "Maximum memory usage recorded in bytes.",
+        |c| {
+            c.stats
+                .memory
+                .as_ref()
+                .map(|memory| memory.max_usage as f64)
+        },
+    ),
+    (
+        "container_spec_memory_limit_bytes",
+        MetricKind::Gauge,
+        "Memory limit for the container.",
+        |c| {
+            c.stats
+                .memory
+                .as_ref()
+                .map(|memory| unlimited_as_zero(memory.limit) as f64)
+        },
+    ),
+    (
+        "container_fs_usage_bytes",
+        MetricKind::Gauge,
+        "Number of bytes that are consumed by the container writable layer.",
+        |c| Some(c.fs_usage_bytes as f64),
+    ),
+    (
+        "container_threads",
+        MetricKind::Gauge,
+        "Number of threads running inside the container.",
+        |c| c.stats.pids.as_ref().map(|pids| pids.current as f64),
+    ),
+    (
+        "container_threads_max",
+        MetricKind::Gauge,
+        "Maximum number of threads allowed inside the container, 0 when unlimited.",
+        |c| {
+            c.stats
+                .pids
+                .as_ref()
+                .map(|pids| unlimited_as_zero(pids.limit) as f64)
+        },
+    ),
+];
+
+/// 块设备 IO 指标族：(指标名, 帮助, 是否取字节数, io 方向)。
+const CONTAINER_FS_IO_FAMILIES: &[(&str, &str, bool, &str)] = &[
+    (
+        "container_fs_reads_bytes_total",
+        "Cumulative count of bytes read.",
+        true,
+        "read",
+    ),
+    (
+        "container_fs_writes_bytes_total",
+        "Cumulative count of bytes written.",
+        true,
+        "write",
+    ),
+    (
+        "container_fs_reads_total",
+        "Cumulative count of reads completed.",
+        false,
+        "read",
+    ),
+    (
+        "container_fs_writes_total",
+        "Cumulative count of writes completed.",
+        false,
+        "write",
+    ),
+];
+
+/// 按 kubelet `/metrics/cadvisor` 的指标名与 container/pod/namespace 标签输出容器资源指标；
+/// 网络计数来自 Pod netns，按 cAdvisor 约定挂在 `container=""` 的 Pod 级序列上。
+fn render_container_resources(
+    registry: &MetricsRegistry,
+    containers: &[crate::metrics::ContainerResourceStats],
+    interfaces: &[crate::metrics::PodNetworkInterfaceStats],
+) {
+    for (name, kind, help, sample) in CONTAINER_RESOURCE_FAMILIES {
+        let samples = containers.iter().filter_map(|container| {
+            sample(container).map(|value| (container_resource_labels(container), value))
+        });
+        match kind {
+            MetricKind::Counter => {
+                let family = registry.counter(name, help, CONTAINER_RESOURCE_LABELS);
+                for (labels, value) in samples {
+                    family.set(&labels, value);
+                }
+            }
+            MetricKind::Gauge | MetricKind::Histogram => {
+                let family = registry.gauge(name, help, CONTAINER_RESOURCE_LABELS);
+                for (labels, value) in samples {
+                    family.set(&labels, value);
+                }
+            }
+        }
+    }
+
+    let memory_failures = registry.counter(
+        "container_memory_failures_total",
+        "Cumulative count of memory allocation failures.",
+        &container_label_names(&["failure_type", "scope"]),
+    );
+    for container in containers {
+        let Some(memory) = container.stats.memory.as_ref() else {
+            continue;
+        };
+        for (failure_type, value) in [
+            ("pgfault", memory.pgfault),
+            ("pgmajfault", memory.pgmajfault),
+        ] {
+            memory_failures.set(
+                &container_labels_with(container, &[failure_type, "container"]),
+                value as f64,
+            );
+        }
+    }
+
+    render_container_pressure(registry, containers);
+
+    for (name, help, bytes, op) in CONTAINER_FS_IO_FAMILIES {
+        let family = registry.counter(name, help, &container_label_names(&["device"]));
+        for container in containers {
+            let Some(blkio) = container.stats.blkio.as_ref() else {
+                continue;
+            };
+            let entries = if *bytes {
+                &blkio.io_service_bytes
+            } else {
+                &blkio.io_serviced
+            };
+            let mut per_device = std::collections::BTreeMap::<(u64, u64), u64>::new();
+            for entry in entries
+                .iter()
+                .filter(|entry| entry.op.eq_ignore_ascii_case(op))
+            {
+                *per_device.entry((entry.major, entry.minor)).or_default() += entry.value;
+            }
+            for ((major, minor), value) in per_device {
+                let device = format!("{major}:{minor}");
+                family.set(
+                    &container_labels_with(container, &[device.as_str()]),
+                    value as f64,
+                );
+            }
+        }
+    }
+
+    for (index, (counter, _)) in crate::metrics::NetworkStats::default()
+        .counters()
+        .into_iter()
+        .enumerate()
+    {
+        let family = registry.counter(
+            &format!("container_network_{counter}_total"),
+            &format!(
+                "Cumulative count of {} for the pod network interface.",
+                counter.replace('_', " ")
+            ),
+            &["container", "pod", "namespace", "interface"],
+        );
+        for stats in interfaces {
+            family.set(
+                &[
+                    "",
+                    stats.pod_name.as_str(),
+                    stats.pod_namespace.as_str(),
+                    stats.interface.name.as_str(),
+                ],
+                stats.interface.counters()[index].1 as f64,
+            );
+        }
+    }
+}
+
+/// PSI 累计停顿时间沿用 cAdvisor 名称（some 为 waiting，full 为 stalled），
+/// 平均值与 `memory.events` 计数没有 cAdvisor 对应项，按资源/窗口/事件打标签输出。
+fn render_container_pressure(
+    registry: &MetricsRegistry,
+    containers: &[crate::metrics::ContainerResourceStats],
+) {
+    for resource in ["cpu", "memory", "io"] {
+        for (kind, full) in [("waiting", false), ("stalled", true)] {
+            let family = registry.counter(
+                &format!("container_pressure_{resource}_{kind}_seconds_total"),
+                &format!(
+                    "Total time duration tasks in the container have {} on {resource}.",
+                    if full { "all stalled" } else { "waited" }
+                ),
+                CONTAINER_RESOURCE_LABELS,
+            );
+            for container in containers {
+                let Some(psi) = container_pressure_of(container, resource) else {
+                    continue;
+                };
+                let line = if full { &psi.full } else { &psi.some };
+                family.set(
+                    &container_resource_labels(container),
+                    line.total as f64 / 1_000_000.0,
+                );
+            }
+        }
+    }
+
+    let average = registry.gauge(
+        "container_pressure_average_percent",
+        "Pressure stall average over the window, in percent.",
+        &container_label_names(&["resource", "kind", "window"]),
+    );
+    for container in containers {
+        for resource in ["cpu", "memory", "io"] {
+            let Some(psi) = container_pressure_of(container, resource) else {
+                continue;
+            };
+            for (kind, line) in [("some", &psi.some), ("full", &psi.full)] {
+                for (window, value) in [
+                    ("10s", line.avg10),
+                    ("60s", line.avg60),
+                    ("300s", line.avg300),
+                ] {
+                    average.set(
+                        &container_labels_with(container, &[resource, kind, window]),
+                        value,
+                    );
+                }
+            }
+        }
+    }
+
+    let oom_events = registry.counter(
+        "container_oom_events_total",
+        "Count of out of memory events observed for the container.",
+        CONTAINER_RESOURCE_LABELS,
+    );
+    let memory_events = registry.counter(
+        "container_memory_events_total",
+        "Cumulative memory.events counters of the container cgroup.",
+        &container_label_names(&["event"]),
+    );
+    for container in containers {
+        let Some(events) = container_memory_events(container) else {
+            continue;
+        };
+        oom_events.set(
+            &container_resource_labels(container),
+            events.oom_kill as f64,
+        );
+        for (event, value) in events.counters() {
+            memory_events.set(&container_labels_with(container, &[event]), value as f64);
         }
+    }
+}
+
+fn container_pressure_of<'a>(
+    container: &'a crate::metrics::ContainerResourceStats,
+    resource: &str,
+) -> Option<&'a crate::metrics::PressureStats> {
+    container
+        .stats
+        .pressure
+        .as_ref()?
+        .resources()
+        .into_iter()
+        .find(|(name, _)| *name == resource)
+        .and_then(|(_, psi)| psi)
+}
+
+fn container_memory_events(
+    container: &crate::metrics::ContainerResourceStats,
+) -> Option<&crate::metrics::MemoryEvents> {
+    container.stats.memory.as_ref()?.events.as_ref()
+}
+
+/// cAdvisor 容器序列的标签名，取值顺序见 [`container_resource_labels`]。
+const CONTAINER_RESOURCE_LABELS: &[&str] = &["container", "pod", "namespace", "id", "image"];
+
+fn container_resource_labels(container: &crate::metrics::ContainerResourceStats) -> [&str; 5] {
+    [
+        container.container_name.as_str(),
+        container.pod_name.as_str(),
+        container.pod_namespace.as_str(),
+        container.container_id.as_str(),
+        container.image.as_str(),
+    ]
+}
 
-        Ok(output)
+fn container_label_names<'a>(extra: &[&'a str]) -> Vec<&'a str> {
+    let mut names = CONTAINER_RESOURCE_LABELS.to_vec();
+    names.extend_from_slice(extra);
+    names
+}
+
+fn container_labels_with<'a>(
+    container: &'a crate::metrics::ContainerResourceStats,
+    extra: &[&'a str],
+) -> Vec<&'a str> {
+    let mut labels = container_resource_labels(container).to_vec();
+    labels.extend_from_slice(extra);
+    labels
+}
+
+fn nanos_to_seconds(value: u64) -> f64 {
+    value as f64 / 1_000_000_000.0
+}
+
+/// cgroup 中 `max` 被解析为 `u64::MAX`，cAdvisor 对无限制输出 0。
+fn unlimited_as_zero(value: u64) -> u64 {
+    if value == u64::MAX {
+        0
+    } else {
+        value
     }
 }
 
@@ -436,11 +1064,271 @@ fn load_private_key(path: &Path) -> anyhow::Result<PrivateKeyDer<'static>> {
 
 #[cfg(test)]
 mod tests {
-    use super::bool_to_metric;
+    use super::{
+        bool_to_metric, health_http_response, health_report, render_container_resources,
+        render_event_subscribers, render_health_conditions, render_nri_plugins,
+        render_pod_network_interfaces, LIVENESS_CONDITIONS,
+    };
+    use crate::metrics::registry::{encode, ExpositionFormat, MetricsRegistry};
+    use crate::metrics::{
+        BlkioDeviceStat, BlkioStats, ContainerPressure, ContainerResourceStats, ContainerStats,
+        CpuStats, MemoryEvents, MemoryStats, NetworkStats, PidsStats, PodNetworkInterfaceStats,
+        PressureLine, PressureStats,
+    };
+    use crate::services::InternalHealthCondition;
+    use hyper::StatusCode;
 
     #[test]
     fn bool_metric_is_stable() {
         assert_eq!(bool_to_metric(false), 0);
         assert_eq!(bool_to_metric(true), 1);
     }
+
+    fn condition(condition_type: &str, ready: bool, reason: &str) -> InternalHealthCondition {
+        InternalHealthCondition {
+            condition_type: condition_type.to_string(),
+            ready,
+            reason: reason.to_string(),
+            message: String::new(),
+        }
+    }
+
+    #[test]
+    fn health_endpoints_split_liveness_from_readiness() {
+        let conditions = vec![
+            condition("RuntimeReady", true, "RuntimeIsReady"),
+            condition("ImageReady", true, "ImageStoreReady"),
+            condition("NriReady", false, "NriPluginDisconnected"),
+        ];
+
+        let (status, body) = health_report(&conditions, Some(LIVENESS_CONDITIONS));
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "ok");
+        assert_eq!(body["subsystems"].as_array().map(Vec::len), Some(3));
+        assert_eq!(body["subsystems"][2]["type"], "NriReady");
+        assert_eq!(body["subsystems"][2]["reason"], "NriPluginDisconnected");
+
+        let (status, body) = health_report(&conditions, None);
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["status"], "failed");
+        assert_eq!(body["failed"], serde_json::json!(["NriReady"]));
+    }
+
+    #[tokio::test]
+    async fn health_head_response_keeps_status_and_headers_without_body() {
+        let body = serde_json::json!({ "status": "failed" }).to_string();
+
+        let head = health_http_response(StatusCode::SERVICE_UNAVAILABLE, body.clone(), true);
+        assert_eq!(head.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(head.headers()["content-type"], "application/json");
+        assert_eq!(
+            head.headers()["content-length"],
+            body.len().to_string().as_str()
+        );
+        let bytes = hyper::body::to_bytes(head.into_body()).await.unwrap();
+        assert!(bytes.is_empty());
+
+        let get = health_http_response(StatusCode::SERVICE_UNAVAILABLE, body.clone(), false);
+        let bytes = hyper::body::to_bytes(get.into_body()).await.unwrap();
+        assert_eq!(bytes, body.as_bytes());
+    }
+
+    #[test]
+    fn health_conditions_render_with_type_and_reason_labels() {
+        let registry = MetricsRegistry::default();
+        render_health_conditions(
+            &registry,
+            &[
+                condition("PullCgroupReady", false, "PullCgroupScopeFailed"),
+                condition("CniLoaded", true, "CNINetworkConfigReady"),
+            ],
+        );
+        render_nri_plugins(
+            &registry,
+            &[crate::nri::NriPluginStatus {
+                name: "topology".to_string(),
+                index: "10".to_string(),
+                registered: true,
+                connected: true,
+                synchronized: true,
+            }],
+        );
+
+        let output = encode(&[&registry], ExpositionFormat::PrometheusText);
+        assert!(output.contains("# TYPE crius_health_condition gauge\n"));
+        assert!(output.contains(
+            "crius_health_condition{type=\"PullCgroupReady\",reason=\"PullCgroupScopeFailed\"} 0\n"
+        ));
+        assert!(output.contains(
+            "crius_health_condition{type=\"CniLoaded\",reason=\"CNINetworkConfigReady\"} 1\n"
+        ));
+        assert!(output.contains(
+            "crius_nri_plugin_connected{plugin=\"topology\",index=\"10\",synchronized=\"true\"} 1\n"
+        ));
+    }
+
+    #[test]
+    fn event_subscribers_render_dropped_counters_per_subscriber() {
+        let registry = MetricsRegistry::default();
+        render_event_subscribers(
+            &registry,
+            &crate::metrics::RuntimeMetricsSnapshot {
+                event_subscribers: vec![crate::services::CriSubscriberStats {
+                    id: 3,
+                    queued: 7,
+                    dropped: 12,
+                    resyncs: 1,
+                    connected_at: 1,
+                }],
+                event_dropped_total: 20,
+                ..Default::default()
+            },
+        );
+
+        let output = encode(&[&registry], ExpositionFormat::PrometheusText);
+        assert!(output.contains("# TYPE crius_container_events_dropped_total counter\n"));
+        assert!(output.contains("crius_container_events_dropped_total 20\n"));
+        assert!(output
+            .contains("crius_container_events_subscriber_dropped_total{subscriber=\"3\"} 12\n"));
+        assert!(output.contains("crius_container_events_subscriber_queued{subscriber=\"3\"} 7\n"));
+        assert!(output
+            .contains("crius_container_events_subscriber_resyncs_total{subscriber=\"3\"} 1\n"));
+    }
+
+    #[test]
+    fn pod_network_interfaces_render_with_interface_labels() {
+        let registry = MetricsRegistry::default();
+        render_pod_network_interfaces(
+            &registry,
+            &[PodNetworkInterfaceStats {
+                pod_id: "pod-1".to_string(),
+                pod_name: "web".to_string(),
+                pod_namespace: "default".to_string(),
+                interface: NetworkStats {
+                    name: "net1".to_string(),
+                    rx_bytes: 42,
+                    tx_packets: 3,
+                    ..Default::default()
+                },
+            }],
+        );
+
+        let output = encode(&[&registry], ExpositionFormat::PrometheusText);
+        assert!(output.contains("# TYPE crius_pod_network_receive_bytes_total counter\n"));
+        assert!(output.contains(
+            "crius_pod_network_receive_bytes_total{pod_id=\"pod-1\",namespace=\"default\",pod=\"web\",interface=\"net1\"} 42\n"
+        ));
+        assert!(output.contains(
+            "crius_pod_network_transmit_packets_total{pod_id=\"pod-1\",namespace=\"default\",pod=\"web\",interface=\"net1\"} 3\n"
+        ));
+    }
+
+    #[test]
+    fn container_resources_render_with_cadvisor_names_and_labels() {
+        let registry = MetricsRegistry::default();
+        render_container_resources(
+            &registry,
+            &[ContainerResourceStats {
+                container_id: "c1".to_string(),
+                container_name: "nginx".to_string(),
+                image: "docker.io/library/nginx:latest".to_string(),
+                pod_name: "web".to_string(),
+                pod_namespace: "default".to_string(),
+                stats: ContainerStats {
+                    container_id: "c1".to_string(),
+                    cpu: Some(CpuStats {
+                        usage_total: 1_500_000_000,
+                        ..Default::default()
+                    }),
+                    memory: Some(MemoryStats {
+                        usage: 4096,
+                        inactive_file: 1024,
+                        limit: u64::MAX,
+                        events: Some(MemoryEvents {
+                            oom: 2,
+                            oom_kill: 1,
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    }),
+                    pressure: Some(ContainerPressure {
+                        memory: Some(PressureStats {
+                            some: PressureLine {
+                                avg10: 2.5,
+                                total: 1_500_000,
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    }),
+                    blkio: Some(BlkioStats {
+                        io_service_bytes: vec![
+                            BlkioDeviceStat {
+                                major: 8,
+                                minor: 0,
+                                op: "read".to_string(),
+                                value: 10,
+                            },
+                            BlkioDeviceStat {
+                                major: 8,
+                                minor: 0,
+                                op: "Read".to_string(),
+                                value: 5,
+                            },
+                        ],
+                        io_serviced: Vec::new(),
+                    }),
+                    pids: Some(PidsStats {
+                        current: 3,
+                        limit: 100,
+                    }),
+                    ..Default::default()
+                },
+                fs_usage_bytes: 2048,
+            }],
+            &[PodNetworkInterfaceStats {
+                pod_id: "pod-1".to_string(),
+                pod_name: "web".to_string(),
+                pod_namespace: "default".to_string(),
+                interface: NetworkStats {
+                    name: "eth0".to_string(),
+                    rx_bytes: 42,
+                    ..Default::default()
+                },
+            }],
+        );
+
+        let output = encode(&[&registry], ExpositionFormat::PrometheusText);
+        let labels = "container=\"nginx\",pod=\"web\",namespace=\"default\",id=\"c1\",image=\"docker.io/library/nginx:latest\"";
+        assert!(output.contains("# TYPE container_cpu_usage_seconds_total counter\n"));
+        assert!(output.contains(&format!(
+            "container_cpu_usage_seconds_total{{{labels}}} 1.5\n"
+        )));
+        assert!(output.contains(&format!(
+            "container_memory_working_set_bytes{{{labels}}} 3072\n"
+        )));
+        assert!(output.contains(&format!(
+            "container_spec_memory_limit_bytes{{{labels}}} 0\n"
+        )));
+        assert!(output.contains(&format!("container_fs_usage_bytes{{{labels}}} 2048\n")));
+        assert!(output.contains(&format!(
+            "container_fs_reads_bytes_total{{{labels},device=\"8:0\"}} 15\n"
+        )));
+        assert!(output.contains(&format!("container_threads{{{labels}}} 3\n")));
+        assert!(output.contains(&format!(
+            "container_pressure_memory_waiting_seconds_total{{{labels}}} 1.5\n"
+        )));
+        assert!(output.contains(&format!(
+            "container_pressure_average_percent{{{labels},resource=\"memory\",kind=\"some\",window=\"10s\"}} 2.5\n"
+        )));
+        assert!(!output.contains("container_pressure_cpu_waiting_seconds_total{"));
+        assert!(output.contains(&format!("container_oom_events_total{{{labels}}} 1\n")));
+        assert!(output.contains(&format!(
+            "container_memory_events_total{{{labels},event=\"oom\"}} 2\n"
+        )));
+        assert!(output.contains(
+            "container_network_receive_bytes_total{container=\"\",pod=\"web\",namespace=\"default\",interface=\"eth0\"} 42\n"
+        ));
+    }
 }
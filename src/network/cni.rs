@@ -6,8 +6,9 @@ use serde_json::{json, Value};
 use std::collections::{BTreeSet, HashMap};
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::process::Command;
+use tracing::Instrument;
 
 const DEFAULT_CNI_TEARDOWN_TIMEOUT: Duration = Duration::from_secs(60);
 
@@ -251,16 +252,24 @@ impl CniManager {
             MainIpPreference::Cni => true,
         };
 
-        if let Some((index, primary)) = ordered_ips.iter().enumerate().find(|(_, ip)| is_match(ip))
-        {
-            let mut additional = ordered_ips[..index].to_vec();
-            additional.extend_from_slice(&ordered_ips[index + 1..]);
-            return (Some(*primary), additional);
+        let index = ordered_ips.iter().position(is_match).unwrap_or(0);
+        if ordered_ips.is_empty() {
+            return (None, Vec::new());
         }
+        let mut additional = ordered_ips;
+        let primary = additional.remove(index);
+        Self::promote_other_family(primary, &mut additional);
+        (Some(primary), additional)
+    }
 
-        match ordered_ips.split_first() {
-            Some((primary, additional)) => (Some(*primary), additional.to_vec()),
-            None => (None, Vec::new()),
+    /// 双栈时把另一地址族的第一个 IP 放到附加 IP 首位，kubelet 依此得到第二个 podIP；
+    /// 其余 IP 保持 CNI 结果中的顺序。
+    fn promote_other_family(primary: std::net::IpAddr, additional: &mut [std::net::IpAddr]) {
+        if let Some(index) = additional
+            .iter()
+            .position(|ip| ip.is_ipv6() != primary.is_ipv6())
+        {
+            additional[..=index].rotate_right(1);
         }
     }
 
@@ -383,6 +392,21 @@ impl CniManager {
         self.last_load_status.as_ref()
     }
 
+    /// 当前已加载的网络配置与默认网络名，用于生成配置快照。
+    pub fn loaded_networks(&self) -> (HashMap<String, CniNetworkConfig>, Option<String>) {
+        (
+            self.network_configs.clone(),
+            self.default_network_name.clone(),
+        )
+    }
+
+    /// 直接采用已激活的快照，跳过磁盘加载。
+    pub fn use_snapshot(&mut self, snapshot: &super::CniConfigSnapshot) {
+        self.network_configs = snapshot.networks.clone();
+        self.default_network_name = snapshot.default_network_name.clone();
+        self.last_load_status = Some(snapshot.load_status.clone());
+    }
+
     pub fn set_event_sink(&mut self, event_sink: Option<crate::services::LedgerInternalEventSink>) {
         self.event_sink = event_sink;
     }
@@ -873,7 +897,8 @@ impl CniManager {
             config.name, if_name
         );
 
-        let result = match self
+        let started = Instant::now();
+        let result = self
             .exec_cni_chain(
                 config,
                 "ADD",
@@ -885,8 +910,15 @@ impl CniManager {
                 pod_uid,
                 pod_cidr,
             )
-            .await
-        {
+            .instrument(crate::trace_export::phase_span("cni", "ADD", pod_id))
+            .await;
+        crate::metrics::operations::observe_phase(
+            crate::metrics::operations::OperationPhase::Cni,
+            "ADD",
+            result.is_ok(),
+            started.elapsed(),
+        );
+        let result = match result {
             Ok(result) => {
                 self.publish_network_event(
                     pod_id,
@@ -963,18 +995,28 @@ impl CniManager {
         pod_uid: &str,
     ) -> Result<()> {
         let cache_key = Self::cache_key(pod_id, if_name);
-        let teardown = self.exec_cni_chain(
-            config,
+        let teardown = self
+            .exec_cni_chain(
+                config,
+                "DEL",
+                pod_id,
+                netns,
+                if_name,
+                pod_name,
+                pod_namespace,
+                pod_uid,
+                None,
+            )
+            .instrument(crate::trace_export::phase_span("cni", "DEL", pod_id));
+        let started = Instant::now();
+        let outcome = tokio::time::timeout(self.teardown_timeout, teardown).await;
+        crate::metrics::operations::observe_phase(
+            crate::metrics::operations::OperationPhase::Cni,
             "DEL",
-            pod_id,
-            netns,
-            if_name,
-            pod_name,
-            pod_namespace,
-            pod_uid,
-            None,
+            matches!(outcome, Ok(Ok(_))),
+            started.elapsed(),
         );
-        match tokio::time::timeout(self.teardown_timeout, teardown).await {
+        match outcome {
             Ok(Ok(_)) => {
                 self.remove_cached_config(&cache_key).await;
                 self.publish_network_event(
@@ -1105,13 +1147,29 @@ impl CniManager {
             config_value["name"] = json!(config.name);
         }
 
-        if let Some(pod_cidr) = pod_cidr {
-            if !pod_cidr.trim().is_empty() {
-                if config_value.get("ipam").is_none() {
-                    config_value["ipam"] = json!({});
-                }
-                if let Some(ipam) = config_value.get_mut("ipam") {
+        // 双栈 PodCIDR（逗号分隔）按 host-local 的 `ranges` 格式每个 CIDR 一组
+        let pod_cidrs: Vec<&str> = pod_cidr
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|cidr| !cidr.is_empty())
+            .collect();
+        if !pod_cidrs.is_empty() {
+            if config_value.get("ipam").is_none() {
+                config_value["ipam"] = json!({});
+            }
+            if let Some(ipam) = config_value.get_mut("ipam") {
+                if let [pod_cidr] = pod_cidrs.as_slice() {
                     ipam["subnet"] = json!(pod_cidr);
+                } else if let Some(ipam) = ipam.as_object_mut() {
+                    ipam.remove("subnet");
+                    ipam.insert(
+                        "ranges".to_string(),
+                        json!(pod_cidrs
+                            .iter()
+                            .map(|cidr| json!([{ "subnet": cidr }]))
+                            .collect::<Vec<_>>()),
+                    );
                 }
             }
         }
@@ -1227,6 +1285,8 @@ impl CniManager {
             .kill_on_drop(true);
 
         let mut child = cmd.spawn().context("Failed to spawn CNI plugin")?;
+        let invocation_usage = crate::metrics::footprint::cni_plugin_registry()
+            .started(invocation.plugin_name, child.id());
 
         let mut stdin = child.stdin.take().context("Failed to get stdin")?;
         let cni_json = serde_json::to_string(invocation.cni_args)?;
@@ -1240,6 +1300,7 @@ impl CniManager {
             .await
             .context("Failed to wait for CNI plugin")?;
 
+        invocation_usage.finish(output.status.success());
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
             let stdout = String::from_utf8_lossy(&output.stdout);
@@ -839,6 +839,75 @@ fn network_status_from_cni_result_falls_back_to_cni_order_when_preferred_family_
     assert_eq!(status.interfaces[0].ip.unwrap().to_string(), "fd00::12");
 }
 
+#[test]
+fn network_status_from_cni_result_orders_other_family_first_in_additional_ips() {
+    let result = serde_json::json!({
+        "cniVersion": "1.0.0",
+        "ips": [
+            {"address": "10.88.0.11/16"},
+            {"address": "10.88.0.12/16"},
+            {"address": "fd00::10/64"},
+            {"address": "fd00::12/64"}
+        ]
+    });
+
+    let status = CniManager::network_status_from_cni_result(Some(&result)).unwrap();
+
+    assert_eq!(status.ip.unwrap().to_string(), "10.88.0.11");
+    let additional: Vec<String> = status
+        .interfaces
+        .iter()
+        .map(|interface| interface.ip.unwrap().to_string())
+        .collect();
+    assert_eq!(additional, vec!["fd00::10", "10.88.0.12", "fd00::12"]);
+}
+
+#[test]
+fn build_plugin_config_splits_dual_stack_pod_cidr_into_ipam_ranges() {
+    let manager = CniManager::new(vec![], vec![], "/tmp/cache".to_string()).unwrap();
+    let network = CniNetworkConfig {
+        name: "test-net".to_string(),
+        cni_version: "1.0.0".to_string(),
+        plugin_type: "bridge".to_string(),
+        config: serde_json::json!({}),
+    };
+    let plugin = serde_json::json!({
+        "type": "bridge",
+        "ipam": {"type": "host-local", "subnet": "10.88.0.0/16"}
+    });
+
+    let single = manager.build_plugin_config(
+        &network,
+        &plugin,
+        "pod-1",
+        "pod",
+        "default",
+        "uid-1",
+        Some("fd00:10:244::/64"),
+        None,
+    );
+    assert_eq!(single["ipam"]["subnet"], "fd00:10:244::/64");
+
+    let dual = manager.build_plugin_config(
+        &network,
+        &plugin,
+        "pod-1",
+        "pod",
+        "default",
+        "uid-1",
+        Some("10.244.1.0/24, fd00:10:244::/64"),
+        None,
+    );
+    assert!(dual["ipam"].get("subnet").is_none());
+    assert_eq!(
+        dual["ipam"]["ranges"],
+        serde_json::json!([
+            [{"subnet": "10.244.1.0/24"}],
+            [{"subnet": "fd00:10:244::/64"}]
+        ])
+    );
+}
+
 #[tokio::test]
 async fn load_network_configs_reports_missing_plugin_binaries() {
     let dir = tempdir().unwrap();
@@ -0,0 +1,577 @@
+//! CNI 配置快照
+//!
+//! CNI 配置目录里的文件随时可能被替换成半成品或错误配置。这里把“从磁盘加载”
+//! 与“对新 Pod 生效”拆成两个阶段：
+//! - 先把新配置集加载成候选快照并校验（可选地在临时 netns 里跑一次 ADD/DEL）
+//! - 校验通过后原子替换当前快照，新 Pod 始终拿到同一份完整配置
+//! - 校验失败时保留上一份可用快照（last-known-good），只记录被拒绝的原因
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+
+use super::cni::{CniLoadStatus, CniManager, CniNetworkConfig};
+use super::NamespaceManager;
+
+/// dry-run 使用的临时 netns 与 Pod 标识。
+const DRY_RUN_ID: &str = "crius-cni-dry-run";
+
+/// 配置目录内单个文件的变更签名：路径、mtime（秒）、大小。
+pub type CniDirSignature = Vec<(String, i64, u64)>;
+
+/// 快照的作用域：同一组目录、加载上限与默认网络名共享一份快照。
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CniSnapshotKey {
+    pub config_dirs: Vec<String>,
+    pub max_conf_num: usize,
+    pub default_network_name: Option<String>,
+}
+
+/// 已激活的 CNI 配置集。
+#[derive(Debug, Clone)]
+pub struct CniConfigSnapshot {
+    pub generation: u64,
+    pub activated_at_unix_millis: i64,
+    pub key: CniSnapshotKey,
+    pub signature: CniDirSignature,
+    pub networks: HashMap<String, CniNetworkConfig>,
+    pub default_network_name: Option<String>,
+    pub load_status: CniLoadStatus,
+}
+
+/// 最近一次被拒绝的候选配置集。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CniSnapshotRejection {
+    pub rejected_at_unix_millis: i64,
+    pub signature: CniDirSignature,
+    pub reason: String,
+    pub message: String,
+}
+
+/// 面向诊断接口的快照摘要。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CniSnapshotSummary {
+    pub generation: u64,
+    pub activated_at_unix_millis: i64,
+    pub config_dirs: Vec<String>,
+    pub files: Vec<String>,
+    pub loaded_networks: Vec<String>,
+    pub default_network_name: Option<String>,
+}
+
+/// 一次 watcher 驱动的重新加载结果。
+#[derive(Debug, Clone)]
+pub struct CniReloadOutcome {
+    /// 对外呈现的加载状态；回退到 last-known-good 时为当前快照的状态。
+    pub status: CniLoadStatus,
+    /// 本次是否激活了新快照。
+    pub activated: bool,
+    /// 本次被拒绝的候选配置；`None` 表示没有发生回退。
+    pub rejected: Option<CniSnapshotRejection>,
+}
+
+#[derive(Debug, Default)]
+struct CniSnapshotState {
+    next_generation: u64,
+    active: HashMap<CniSnapshotKey, Arc<CniConfigSnapshot>>,
+    rejected: HashMap<CniSnapshotKey, CniSnapshotRejection>,
+}
+
+/// CNI 快照存储句柄；克隆后共享同一份状态。
+#[derive(Debug, Clone, Default)]
+pub struct CniSnapshotStore {
+    inner: Arc<Mutex<CniSnapshotState>>,
+}
+
+impl PartialEq for CniSnapshotStore {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl Eq for CniSnapshotStore {}
+
+/// 候选配置在阶段加载后的去向。
+enum CniStage {
+    /// 磁盘内容与当前快照一致，直接复用。
+    Current(Arc<CniConfigSnapshot>),
+    /// 磁盘内容与上次被拒绝的候选一致，继续使用 last-known-good。
+    KnownBad(Arc<CniConfigSnapshot>, CniSnapshotRejection),
+    /// 新加载的候选；`CniManager` 当前持有的就是候选内容。
+    Candidate {
+        signature: CniDirSignature,
+        status: CniLoadStatus,
+        error: Option<String>,
+    },
+}
+
+impl CniConfigSnapshot {
+    pub fn summary(&self) -> CniSnapshotSummary {
+        CniSnapshotSummary {
+            generation: self.generation,
+            activated_at_unix_millis: self.activated_at_unix_millis,
+            config_dirs: self.key.config_dirs.clone(),
+            files: self
+                .signature
+                .iter()
+                .map(|(path, _, _)| path.clone())
+                .collect(),
+            loaded_networks: self.load_status.loaded_networks.clone(),
+            default_network_name: self.default_network_name.clone(),
+        }
+    }
+}
+
+impl CniSnapshotStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 指定作用域下当前生效的快照。
+    pub fn active(&self, key: &CniSnapshotKey) -> Option<Arc<CniConfigSnapshot>> {
+        self.inner
+            .lock()
+            .ok()
+            .and_then(|state| state.active.get(key).cloned())
+    }
+
+    /// 指定作用域下最近一次被拒绝的候选配置。
+    pub fn rejection(&self, key: &CniSnapshotKey) -> Option<CniSnapshotRejection> {
+        self.inner
+            .lock()
+            .ok()
+            .and_then(|state| state.rejected.get(key).cloned())
+    }
+
+    /// 供 Pod 网络 setup/teardown 与状态探测使用：返回一份一致的配置集。
+    ///
+    /// 磁盘内容未变化时直接复用快照；变化且候选可用时原子切换；候选不可用但存在
+    /// last-known-good 时回退。`activate_candidates` 为 false（启用 dry-run）时，
+    /// 已有快照的作用域只由 watcher 负责切换，这里继续使用旧快照。
+    pub async fn resolve(
+        &self,
+        cni: &mut CniManager,
+        key: &CniSnapshotKey,
+        activate_candidates: bool,
+    ) -> Result<CniLoadStatus, String> {
+        match self.stage(cni, key).await {
+            CniStage::Current(snapshot) | CniStage::KnownBad(snapshot, _) => {
+                Ok(snapshot.load_status.clone())
+            }
+            CniStage::Candidate {
+                signature,
+                status,
+                error,
+            } => {
+                let active = self.active(key);
+                if let Some(active) = active.as_ref() {
+                    if !Self::acceptable(&status, true) {
+                        self.reject(key, signature, &status, error.as_deref());
+                        cni.use_snapshot(active);
+                        return Ok(active.load_status.clone());
+                    }
+                    if !activate_candidates {
+                        cni.use_snapshot(active);
+                        return Ok(active.load_status.clone());
+                    }
+                }
+                if let Some(error) = error {
+                    return Err(error);
+                }
+                if Self::acceptable(&status, active.is_some()) {
+                    self.activate(key, signature, cni, &status);
+                }
+                Ok(status)
+            }
+        }
+    }
+
+    /// 供 CNI watcher 使用：阶段加载、可选 dry-run，成功才切换。
+    pub async fn reload(
+        &self,
+        cni: &mut CniManager,
+        key: &CniSnapshotKey,
+        dry_run: Option<&NamespaceManager>,
+    ) -> CniReloadOutcome {
+        match self.stage(cni, key).await {
+            CniStage::Current(snapshot) => CniReloadOutcome {
+                status: snapshot.load_status.clone(),
+                activated: false,
+                rejected: None,
+            },
+            CniStage::KnownBad(snapshot, rejection) => CniReloadOutcome {
+                status: snapshot.load_status.clone(),
+                activated: false,
+                rejected: Some(rejection),
+            },
+            CniStage::Candidate {
+                signature,
+                status,
+                error,
+            } => {
+                let active = self.active(key);
+                let mut failure = error.or_else(|| {
+                    (!Self::acceptable(&status, active.is_some()))
+                        .then(|| Self::rejection_message(&status))
+                });
+                if failure.is_none() {
+                    if let Some(namespace_manager) = dry_run {
+                        failure = Self::dry_run(cni, namespace_manager)
+                            .await
+                            .err()
+                            .map(|err| format!("CNI dry-run failed: {}", err));
+                    }
+                }
+                match (failure, active) {
+                    (None, _) => {
+                        self.activate(key, signature, cni, &status);
+                        CniReloadOutcome {
+                            status,
+                            activated: true,
+                            rejected: None,
+                        }
+                    }
+                    (Some(message), Some(active)) => {
+                        let rejection = self.reject(key, signature, &status, Some(&message));
+                        cni.use_snapshot(&active);
+                        CniReloadOutcome {
+                            status: active.load_status.clone(),
+                            activated: false,
+                            rejected: Some(rejection),
+                        }
+                    }
+                    (Some(message), None) => {
+                        let mut status = status;
+                        if status.ready {
+                            status.ready = false;
+                            status.reason = "CNIConfigDryRunFailed".to_string();
+                            status.message = message;
+                        }
+                        CniReloadOutcome {
+                            status,
+                            activated: false,
+                            rejected: None,
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    async fn stage(&self, cni: &mut CniManager, key: &CniSnapshotKey) -> CniStage {
+        let signature = config_dir_signature(&key.config_dirs);
+        if let Some(active) = self.active(key) {
+            if active.signature == signature {
+                // 磁盘已回到当前快照的内容，之前的拒绝记录不再适用。
+                if let Ok(mut state) = self.inner.lock() {
+                    state.rejected.remove(key);
+                }
+                cni.use_snapshot(&active);
+                return CniStage::Current(active);
+            }
+            if let Some(rejection) = self
+                .rejection(key)
+                .filter(|rejection| rejection.signature == signature)
+            {
+                cni.use_snapshot(&active);
+                return CniStage::KnownBad(active, rejection);
+            }
+        }
+
+        match cni.load_network_configs().await {
+            Ok(status) => CniStage::Candidate {
+                signature,
+                status,
+                error: None,
+            },
+            Err(err) => {
+                let status = cni
+                    .last_load_status()
+                    .cloned()
+                    .unwrap_or_else(|| CniLoadStatus {
+                        checked_at_unix_millis: chrono::Utc::now().timestamp_millis(),
+                        ready: false,
+                        reason: "CNIConfigLoadFailed".to_string(),
+                        message: format!("failed to load CNI network configs: {}", err),
+                        discovered_files: Vec::new(),
+                        invalid_files: Vec::new(),
+                        loaded_networks: Vec::new(),
+                        declared_plugins: Vec::new(),
+                        missing_plugin_binaries: Vec::new(),
+                        default_network_name: None,
+                    });
+                CniStage::Candidate {
+                    signature,
+                    status,
+                    error: Some(err.to_string()),
+                }
+            }
+        }
+    }
+
+    /// 已有 last-known-good 时要求候选集中的每个文件都能解析，避免部分生效。
+    fn acceptable(status: &CniLoadStatus, has_active: bool) -> bool {
+        status.ready && (!has_active || status.invalid_files.is_empty())
+    }
+
+    fn rejection_message(status: &CniLoadStatus) -> String {
+        if status.ready && !status.invalid_files.is_empty() {
+            format!(
+                "failed to parse {} CNI config file(s): {}",
+                status.invalid_files.len(),
+                status.invalid_files.join(", ")
+            )
+        } else {
+            status.message.clone()
+        }
+    }
+
+    fn activate(
+        &self,
+        key: &CniSnapshotKey,
+        signature: CniDirSignature,
+        cni: &CniManager,
+        status: &CniLoadStatus,
+    ) -> Arc<CniConfigSnapshot> {
+        let (networks, default_network_name) = cni.loaded_networks();
+        let Ok(mut state) = self.inner.lock() else {
+            return Arc::new(CniConfigSnapshot {
+                generation: 0,
+                activated_at_unix_millis: chrono::Utc::now().timestamp_millis(),
+                key: key.clone(),
+                signature,
+                networks,
+                default_network_name,
+                load_status: status.clone(),
+            });
+        };
+        state.next_generation += 1;
+        let snapshot = Arc::new(CniConfigSnapshot {
+            generation: state.next_generation,
+            activated_at_unix_millis: chrono::Utc::now().timestamp_millis(),
+            key: key.clone(),
+            signature,
+            networks,
+            default_network_name,
+            load_status: status.clone(),
+        });
+        state.active.insert(key.clone(), snapshot.clone());
+        state.rejected.remove(key);
+        snapshot
+    }
+
+    fn reject(
+        &self,
+        key: &CniSnapshotKey,
+        signature: CniDirSignature,
+        status: &CniLoadStatus,
+        message: Option<&str>,
+    ) -> CniSnapshotRejection {
+        let rejection = CniSnapshotRejection {
+            rejected_at_unix_millis: chrono::Utc::now().timestamp_millis(),
+            signature,
+            reason: if !status.ready {
+                status.reason.clone()
+            } else if !status.invalid_files.is_empty() {
+                "CNIConfigInvalid".to_string()
+            } else {
+                "CNIConfigRejected".to_string()
+            },
+            message: message
+                .map(ToOwned::to_owned)
+                .unwrap_or_else(|| Self::rejection_message(status)),
+        };
+        if let Ok(mut state) = self.inner.lock() {
+            state.rejected.insert(key.clone(), rejection.clone());
+        }
+        log::warn!(
+            "Rejected CNI config set in {:?}, keeping last-known-good: {}",
+            key.config_dirs,
+            rejection.message
+        );
+        rejection
+    }
+
+    /// 在临时 netns 中对候选默认网络执行一次 ADD/DEL。
+    async fn dry_run(cni: &CniManager, namespace_manager: &NamespaceManager) -> Result<(), String> {
+        let netns = namespace_manager
+            .create(DRY_RUN_ID)
+            .await
+            .map_err(|err| format!("failed to create scratch netns: {}", err))?;
+        let netns = netns.display().to_string();
+        let result = match cni
+            .setup_pod_network(
+                DRY_RUN_ID,
+                &netns,
+                DRY_RUN_ID,
+                "crius-system",
+                DRY_RUN_ID,
+                None,
+            )
+            .await
+        {
+            Ok(_) => cni
+                .teardown_pod_network(DRY_RUN_ID, &netns, "crius-system", DRY_RUN_ID, DRY_RUN_ID)
+                .await
+                .map_err(|err| err.to_string()),
+            Err(err) => {
+                let _ = cni
+                    .teardown_pod_network(
+                        DRY_RUN_ID,
+                        &netns,
+                        "crius-system",
+                        DRY_RUN_ID,
+                        DRY_RUN_ID,
+                    )
+                    .await;
+                Err(err.to_string())
+            }
+        };
+        if let Err(err) = namespace_manager.remove(&netns).await {
+            log::warn!("Failed to remove CNI dry-run netns {}: {}", netns, err);
+        }
+        result
+    }
+}
+
+/// 计算配置目录下 `.conf`/`.conflist`/`.json` 文件的签名，用于判断快照是否过期。
+pub fn config_dir_signature(config_dirs: &[impl AsRef<Path>]) -> CniDirSignature {
+    let mut entries = Vec::new();
+    for dir in config_dirs {
+        let Ok(read_dir) = std::fs::read_dir(dir.as_ref()) else {
+            continue;
+        };
+        for entry in read_dir.flatten() {
+            let path: PathBuf = entry.path();
+            let Some(ext) = path.extension().and_then(|ext| ext.to_str()) else {
+                continue;
+            };
+            if !matches!(ext, "conf" | "conflist" | "json") {
+                continue;
+            }
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            let Some(modified) = metadata
+                .modified()
+                .ok()
+                .and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok())
+            else {
+                continue;
+            };
+            entries.push((
+                path.display().to_string(),
+                modified.as_secs() as i64,
+                metadata.len(),
+            ));
+        }
+    }
+    entries.sort();
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn key(dir: &Path) -> CniSnapshotKey {
+        CniSnapshotKey {
+            config_dirs: vec![dir.display().to_string()],
+            max_conf_num: 0,
+            default_network_name: None,
+        }
+    }
+
+    fn manager(config_dir: &Path, plugin_dir: &Path, cache_dir: &Path) -> CniManager {
+        CniManager::new(
+            vec![plugin_dir.display().to_string()],
+            vec![config_dir.display().to_string()],
+            cache_dir.display().to_string(),
+        )
+        .unwrap()
+    }
+
+    fn write_plugin(plugin_dir: &Path, name: &str) {
+        use std::os::unix::fs::PermissionsExt;
+        let path = plugin_dir.join(name);
+        fs::write(&path, "#!/bin/sh\nexit 0\n").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[tokio::test]
+    async fn resolve_keeps_last_known_good_when_new_config_set_is_broken() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_dir = dir.path().join("net.d");
+        let plugin_dir = dir.path().join("bin");
+        fs::create_dir_all(&config_dir).unwrap();
+        fs::create_dir_all(&plugin_dir).unwrap();
+        write_plugin(&plugin_dir, "bridge");
+        fs::write(
+            config_dir.join("10-good.conflist"),
+            r#"{"cniVersion":"1.0.0","name":"good","plugins":[{"type":"bridge"}]}"#,
+        )
+        .unwrap();
+        let store = CniSnapshotStore::new();
+        let key = key(&config_dir);
+
+        let mut cni = manager(&config_dir, &plugin_dir, dir.path());
+        let status = store.resolve(&mut cni, &key, true).await.unwrap();
+        assert!(status.ready);
+        let first = store.active(&key).expect("good config should be activated");
+        assert_eq!(first.generation, 1);
+
+        fs::write(config_dir.join("05-broken.conflist"), "{not-json").unwrap();
+        let mut cni = manager(&config_dir, &plugin_dir, dir.path());
+        let status = store.resolve(&mut cni, &key, true).await.unwrap();
+
+        assert!(status.ready);
+        assert_eq!(status.loaded_networks, vec!["good".to_string()]);
+        assert_eq!(store.active(&key).unwrap().generation, 1);
+        let rejection = store
+            .rejection(&key)
+            .expect("broken set should be recorded");
+        assert!(rejection.message.contains("05-broken.conflist"));
+        assert_eq!(cni.loaded_networks().1.as_deref(), Some("good"));
+
+        fs::remove_file(config_dir.join("05-broken.conflist")).unwrap();
+        fs::write(
+            config_dir.join("20-next.conflist"),
+            r#"{"cniVersion":"1.0.0","name":"next","plugins":[{"type":"bridge"}]}"#,
+        )
+        .unwrap();
+        let mut cni = manager(&config_dir, &plugin_dir, dir.path());
+        let outcome = store.reload(&mut cni, &key, None).await;
+
+        assert!(outcome.activated);
+        assert!(outcome.rejected.is_none());
+        let active = store.active(&key).unwrap();
+        assert_eq!(active.generation, 2);
+        assert_eq!(
+            active.summary().loaded_networks,
+            vec!["good".to_string(), "next".to_string()]
+        );
+        assert!(store.rejection(&key).is_none());
+    }
+
+    #[tokio::test]
+    async fn resolve_without_last_known_good_reports_candidate_status() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_dir = dir.path().join("net.d");
+        fs::create_dir_all(&config_dir).unwrap();
+        fs::write(config_dir.join("10-broken.conflist"), "{not-json").unwrap();
+        let store = CniSnapshotStore::new();
+        let key = key(&config_dir);
+
+        let mut cni = manager(&config_dir, dir.path(), dir.path());
+        let outcome = store.reload(&mut cni, &key, None).await;
+
+        assert!(!outcome.status.ready);
+        assert_eq!(outcome.status.reason, "CNIConfigInvalid");
+        assert!(!outcome.activated);
+        assert!(store.active(&key).is_none());
+    }
+}
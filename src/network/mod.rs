@@ -12,19 +12,28 @@ use nix::sched::{unshare, CloneFlags};
 use nix::unistd::gettid;
 
 pub mod cni;
+mod cni_snapshot;
 mod error;
 pub mod multi;
 pub(crate) mod netlink;
+mod netns_pool;
 mod port_mapping;
+mod rootless_ports;
 mod types;
 
 pub use cni::{CniLoadStatus, CniManager};
+pub use cni_snapshot::{
+    config_dir_signature, CniConfigSnapshot, CniReloadOutcome, CniSnapshotKey,
+    CniSnapshotRejection, CniSnapshotStore, CniSnapshotSummary,
+};
 pub use error::NetworkError;
 pub use multi::{
     MultiNetworkConfig, MultiNetworkManager, NetworkInterfaceStatus, NetworkSelector,
     PodNetworkStatus,
 };
+pub use netns_pool::{NetnsPool, NetnsPoolStats};
 pub use port_mapping::{PortMapping, PortMappingBackend, PortMappingManager, Protocol};
+pub use rootless_ports::{HostPort, RootlessPortForward};
 pub use types::*;
 
 /// 共享的 CNI 路径配置。
@@ -43,9 +52,12 @@ pub struct CniConfig {
     disable_hostport_mapping: bool,
     netns_mount_dir: PathBuf,
     netns_mounts_under_state_dir: bool,
+    netns_pool_size: usize,
+    reload_dry_run: bool,
     namespace_helper_path: Option<PathBuf>,
     rootless: Option<RootlessNetworkConfig>,
     event_sink: Option<crate::services::LedgerInternalEventSink>,
+    snapshot_store: Option<CniSnapshotStore>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -54,6 +66,7 @@ pub struct RootlessNetworkConfig {
     mode: crate::rootless::NetworkMode,
     slirp4netns_path: PathBuf,
     pasta_path: PathBuf,
+    runtime_root: PathBuf,
 }
 
 impl RootlessNetworkConfig {
@@ -143,9 +156,12 @@ impl Default for CniConfig {
             disable_hostport_mapping: false,
             netns_mount_dir: PathBuf::from("/var/run/netns"),
             netns_mounts_under_state_dir: false,
+            netns_pool_size: 0,
+            reload_dry_run: false,
             namespace_helper_path: None,
             rootless: None,
             event_sink: None,
+            snapshot_store: None,
         }
     }
 }
@@ -178,9 +194,12 @@ impl CniConfig {
             disable_hostport_mapping,
             netns_mount_dir: PathBuf::from("/var/run/netns"),
             netns_mounts_under_state_dir: false,
+            netns_pool_size: 0,
+            reload_dry_run: false,
             namespace_helper_path: None,
             rootless: None,
             event_sink: None,
+            snapshot_store: None,
         }
     }
 
@@ -265,9 +284,23 @@ impl CniConfig {
                     )
                 })
                 .unwrap_or(defaults.netns_mounts_under_state_dir),
+            netns_pool_size: std::env::var("CRIUS_NETNS_POOL_SIZE")
+                .ok()
+                .and_then(|value| value.trim().parse().ok())
+                .unwrap_or(defaults.netns_pool_size),
+            reload_dry_run: std::env::var("CRIUS_CNI_RELOAD_DRY_RUN")
+                .ok()
+                .map(|value| {
+                    matches!(
+                        value.trim().to_ascii_lowercase().as_str(),
+                        "1" | "true" | "yes" | "on"
+                    )
+                })
+                .unwrap_or(defaults.reload_dry_run),
             namespace_helper_path: None,
             rootless: None,
             event_sink: None,
+            snapshot_store: None,
         }
     }
 
@@ -377,6 +410,22 @@ impl CniConfig {
         self.netns_mounts_under_state_dir = enabled;
     }
 
+    pub fn netns_pool_size(&self) -> usize {
+        self.netns_pool_size
+    }
+
+    pub fn set_netns_pool_size(&mut self, pool_size: usize) {
+        self.netns_pool_size = pool_size;
+    }
+
+    pub fn reload_dry_run(&self) -> bool {
+        self.reload_dry_run
+    }
+
+    pub fn set_reload_dry_run(&mut self, enabled: bool) {
+        self.reload_dry_run = enabled;
+    }
+
     pub fn namespace_helper_path(&self) -> Option<&Path> {
         self.namespace_helper_path.as_deref()
     }
@@ -395,6 +444,7 @@ impl CniConfig {
                 mode: effective.network_mode,
                 slirp4netns_path: effective.slirp4netns_path,
                 pasta_path: effective.pasta_path,
+                runtime_root: effective.runtime_root,
             })
         });
     }
@@ -413,6 +463,23 @@ impl CniConfig {
         self.event_sink = event_sink;
     }
 
+    pub fn snapshot_store(&self) -> Option<&CniSnapshotStore> {
+        self.snapshot_store.as_ref()
+    }
+
+    pub fn set_snapshot_store(&mut self, snapshot_store: Option<CniSnapshotStore>) {
+        self.snapshot_store = snapshot_store;
+    }
+
+    /// 默认配置目录对应的快照作用域。
+    pub fn snapshot_key(&self) -> CniSnapshotKey {
+        CniSnapshotKey {
+            config_dirs: self.config_dir_strings(),
+            max_conf_num: self.max_conf_num,
+            default_network_name: self.default_network_name.clone(),
+        }
+    }
+
     pub fn netns_path(&self, ns_name_or_path: &str) -> PathBuf {
         NamespaceManager::new(self.netns_mount_dir.clone()).resolve_path(ns_name_or_path)
     }
@@ -653,6 +720,8 @@ pub struct NetworkSetupRequest<'a> {
     pub pod_uid: &'a str,
     pub runtime_handler: &'a str,
     pub pod_cidr: Option<&'a str>,
+    /// Pod 请求的 hostPort；仅由自行转发端口的后端（rootless helper）使用。
+    pub host_ports: &'a [HostPort],
 }
 
 #[async_trait]
@@ -682,6 +751,16 @@ pub trait NetworkManager: Send + Sync + 'static {
         pod_uid: &str,
         runtime_handler: &str,
     ) -> Result<(), NetworkError>;
+
+    /// 后端是否在 setup/teardown 中自行处理 hostPort；为 true 时不再写宿主机 NAT 规则。
+    fn manages_host_ports(&self) -> bool {
+        false
+    }
+
+    /// 当前生效的 rootless hostPort 转发
+    fn rootless_port_forwards(&self) -> Vec<RootlessPortForward> {
+        Vec::new()
+    }
 }
 
 /// 默认网络管理器实现
@@ -699,7 +778,11 @@ pub struct DefaultNetworkManager {
     namespace_manager: NamespaceManager,
     rootless: Option<RootlessNetworkConfig>,
     rootless_processes: std::sync::Arc<std::sync::Mutex<HashMap<String, Child>>>,
+    rootless_port_forwards:
+        std::sync::Arc<std::sync::Mutex<HashMap<String, Vec<RootlessPortForward>>>>,
     event_sink: Option<crate::services::LedgerInternalEventSink>,
+    cni_snapshots: Option<CniSnapshotStore>,
+    cni_reload_dry_run: bool,
 }
 
 impl DefaultNetworkManager {
@@ -775,10 +858,53 @@ impl DefaultNetworkManager {
             namespace_manager: cni.namespace_manager(),
             rootless: cni.rootless.clone(),
             rootless_processes: std::sync::Arc::new(std::sync::Mutex::new(HashMap::new())),
+            rootless_port_forwards: std::sync::Arc::new(std::sync::Mutex::new(HashMap::new())),
             event_sink: cni.event_sink.clone(),
+            cni_snapshots: cni.snapshot_store.clone(),
+            cni_reload_dry_run: cni.reload_dry_run,
         }
     }
 
+    /// 构造本次调用使用的 `CniManager`，配置来自快照（若启用）或磁盘。
+    async fn load_cni_manager(
+        &self,
+        pod_id: &str,
+        runtime_handler: &str,
+    ) -> Result<CniManager, NetworkError> {
+        let config_dirs = self.effective_cni_config_dirs(runtime_handler);
+        let max_conf_num = self.effective_cni_max_conf_num(runtime_handler);
+        let mut cni = CniManager::new(
+            self.cni_plugin_dirs.clone(),
+            config_dirs.clone(),
+            self.cni_cache_dir.clone(),
+        )
+        .map_err(|e| NetworkError::Other(e.to_string()))?;
+        cni.set_event_sink(self.event_sink.clone());
+        cni.set_max_conf_num(max_conf_num);
+        cni.set_ip_pref(self.cni_ip_pref);
+        cni.set_default_network_name(self.cni_default_network_name.clone());
+        cni.set_teardown_timeout(self.cni_teardown_timeout);
+        let load_status = match self.cni_snapshots.as_ref() {
+            Some(store) => {
+                let key = CniSnapshotKey {
+                    config_dirs,
+                    max_conf_num,
+                    default_network_name: self.cni_default_network_name.clone(),
+                };
+                store
+                    .resolve(&mut cni, &key, !self.cni_reload_dry_run)
+                    .await
+                    .map_err(NetworkError::Other)?
+            }
+            None => cni
+                .load_network_configs()
+                .await
+                .map_err(|e| NetworkError::Other(e.to_string()))?,
+        };
+        cni.publish_config_load_event(pod_id, runtime_handler, &load_status);
+        Ok(cni)
+    }
+
     fn effective_cni_config_dirs(&self, runtime_handler: &str) -> Vec<String> {
         self.cni_runtime_handler_config_dirs
             .get(runtime_handler)
@@ -816,10 +942,23 @@ impl DefaultNetworkManager {
             .unwrap_or(false)
     }
 
+    fn rootless_api_socket(&self, pod_id: &str) -> Option<PathBuf> {
+        self.rootless
+            .as_ref()
+            .filter(|rootless| rootless.mode == crate::rootless::NetworkMode::Slirp4netns)
+            .map(|rootless| {
+                rootless
+                    .runtime_root
+                    .join("slirp4netns")
+                    .join(format!("{pod_id}.sock"))
+            })
+    }
+
     fn start_rootless_network_helper(
         &self,
         pod_id: &str,
         netns: &str,
+        host_ports: &[HostPort],
     ) -> Result<NetworkStatus, NetworkError> {
         let Some(rootless) = self.rootless.as_ref() else {
             return Err(NetworkError::Other(
@@ -838,6 +977,7 @@ impl DefaultNetworkManager {
                 ))
             }
         };
+        rootless_ports::validate_host_ports(host_ports)?;
         let Some(resolved_program) = resolve_executable(program) else {
             self.publish_network_event(
                 pod_id,
@@ -866,18 +1006,27 @@ impl DefaultNetworkManager {
                 "netns": netns,
             }),
         );
+        let api_socket = (!host_ports.is_empty())
+            .then(|| self.rootless_api_socket(pod_id))
+            .flatten();
+        if let Some(api_socket) = api_socket.as_ref() {
+            if let Some(parent) = api_socket.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let _ = std::fs::remove_file(api_socket);
+        }
         let mut command = StdCommand::new(&resolved_program);
         match rootless.mode {
             crate::rootless::NetworkMode::Slirp4netns => {
-                command.args([
-                    "--netns-type=path",
-                    "--disable-host-loopback",
-                    netns,
-                    "tap0",
-                ]);
+                command.args(["--netns-type=path", "--disable-host-loopback"]);
+                if let Some(api_socket) = api_socket.as_ref() {
+                    command.arg("--api-socket").arg(api_socket);
+                }
+                command.args([netns, "tap0"]);
             }
             crate::rootless::NetworkMode::Pasta => {
                 command.args(["--netns", netns, "--config-net", "--quiet"]);
+                command.args(rootless_ports::pasta_port_args(host_ports)?);
             }
             crate::rootless::NetworkMode::None | crate::rootless::NetworkMode::Rootlesskit => {}
         }
@@ -943,10 +1092,122 @@ impl DefaultNetworkManager {
         if let Ok(mut processes) = self.rootless_processes.lock() {
             processes.insert(pod_id.to_string(), child);
         }
+        if let Err(err) = self.add_rootless_port_forwards(pod_id, name, host_ports, api_socket) {
+            self.publish_network_event(
+                pod_id,
+                "rootless_hostport_fail",
+                crate::services::InternalEventSeverity::Error,
+                serde_json::json!({
+                    "helper": name,
+                    "message": err.to_string(),
+                }),
+            );
+            self.stop_rootless_network_helper(pod_id);
+            return Err(err);
+        }
         Ok(self.rootless_network_status(name))
     }
 
+    fn add_rootless_port_forwards(
+        &self,
+        pod_id: &str,
+        helper: &str,
+        host_ports: &[HostPort],
+        api_socket: Option<PathBuf>,
+    ) -> Result<(), NetworkError> {
+        if host_ports.is_empty() {
+            return Ok(());
+        }
+        let mut forwards = Vec::new();
+        let result = match api_socket.as_ref() {
+            Some(api_socket) => self.add_slirp4netns_port_forwards(
+                pod_id,
+                helper,
+                host_ports,
+                api_socket,
+                &mut forwards,
+            ),
+            None => {
+                forwards.extend(
+                    host_ports
+                        .iter()
+                        .map(|port| RootlessPortForward::new(pod_id, helper, port, None)),
+                );
+                Ok(())
+            }
+        };
+        // 即使部分失败也先登记已生效的转发，交由 stop 路径统一撤销。
+        if let Ok(mut registry) = self.rootless_port_forwards.lock() {
+            registry.insert(pod_id.to_string(), forwards.clone());
+        }
+        result?;
+        self.publish_network_event(
+            pod_id,
+            "rootless_hostport_add",
+            crate::services::InternalEventSeverity::Info,
+            serde_json::json!({
+                "helper": helper,
+                "forwards": forwards,
+            }),
+        );
+        Ok(())
+    }
+
+    fn add_slirp4netns_port_forwards(
+        &self,
+        pod_id: &str,
+        helper: &str,
+        host_ports: &[HostPort],
+        api_socket: &Path,
+        forwards: &mut Vec<RootlessPortForward>,
+    ) -> Result<(), NetworkError> {
+        rootless_ports::wait_for_api_socket(api_socket, std::time::Duration::from_secs(2))?;
+        for port in host_ports {
+            for (_, id) in rootless_ports::slirp4netns_add_hostfwd(api_socket, port)? {
+                forwards.push(RootlessPortForward::new(pod_id, helper, port, Some(id)));
+            }
+        }
+        Ok(())
+    }
+
+    fn remove_rootless_port_forwards(&self, pod_id: &str) {
+        let forwards = self
+            .rootless_port_forwards
+            .lock()
+            .ok()
+            .and_then(|mut registry| registry.remove(pod_id))
+            .unwrap_or_default();
+        let Some(api_socket) = self.rootless_api_socket(pod_id) else {
+            return;
+        };
+        for forward in &forwards {
+            let Some(id) = forward.forward_id else {
+                continue;
+            };
+            if let Err(err) = rootless_ports::slirp4netns_remove_hostfwd(&api_socket, id) {
+                log::debug!(
+                    "Failed to remove rootless hostPort {}:{} for {}: {}",
+                    forward.host_ip,
+                    forward.host_port,
+                    pod_id,
+                    err
+                );
+            }
+        }
+        if !forwards.is_empty() {
+            self.publish_network_event(
+                pod_id,
+                "rootless_hostport_remove",
+                crate::services::InternalEventSeverity::Info,
+                serde_json::json!({
+                    "forwards": forwards,
+                }),
+            );
+        }
+    }
+
     fn stop_rootless_network_helper(&self, pod_id: &str) {
+        self.remove_rootless_port_forwards(pod_id);
         let child = self
             .rootless_processes
             .lock()
@@ -989,6 +1250,9 @@ impl DefaultNetworkManager {
                 let _ = child.wait();
             }
         }
+        if let Some(api_socket) = self.rootless_api_socket(pod_id) {
+            let _ = std::fs::remove_file(api_socket);
+        }
     }
 }
 
@@ -1022,25 +1286,16 @@ impl NetworkManager for DefaultNetworkManager {
         }
 
         if self.rootless.is_some() {
-            return self.start_rootless_network_helper(request.pod_id, request.netns);
+            return self.start_rootless_network_helper(
+                request.pod_id,
+                request.netns,
+                request.host_ports,
+            );
         }
 
-        let mut cni = CniManager::new(
-            self.cni_plugin_dirs.clone(),
-            self.effective_cni_config_dirs(request.runtime_handler),
-            self.cni_cache_dir.clone(),
-        )
-        .map_err(|e| NetworkError::Other(e.to_string()))?;
-        cni.set_event_sink(self.event_sink.clone());
-        cni.set_max_conf_num(self.effective_cni_max_conf_num(request.runtime_handler));
-        cni.set_ip_pref(self.cni_ip_pref);
-        cni.set_default_network_name(self.cni_default_network_name.clone());
-        cni.set_teardown_timeout(self.cni_teardown_timeout);
-        let load_status = cni
-            .load_network_configs()
-            .await
-            .map_err(|e| NetworkError::Other(e.to_string()))?;
-        cni.publish_config_load_event(request.pod_id, request.runtime_handler, &load_status);
+        let cni = self
+            .load_cni_manager(request.pod_id, request.runtime_handler)
+            .await?;
 
         cni.setup_pod_network(
             request.pod_id,
@@ -1067,26 +1322,31 @@ impl NetworkManager for DefaultNetworkManager {
             self.stop_rootless_network_helper(pod_id);
             return Ok(());
         }
-        let mut cni = CniManager::new(
-            self.cni_plugin_dirs.clone(),
-            self.effective_cni_config_dirs(runtime_handler),
-            self.cni_cache_dir.clone(),
-        )
-        .map_err(|e| NetworkError::Other(e.to_string()))?;
-        cni.set_event_sink(self.event_sink.clone());
-        cni.set_max_conf_num(self.effective_cni_max_conf_num(runtime_handler));
-        cni.set_ip_pref(self.cni_ip_pref);
-        cni.set_default_network_name(self.cni_default_network_name.clone());
-        cni.set_teardown_timeout(self.cni_teardown_timeout);
-        let load_status = cni
-            .load_network_configs()
-            .await
-            .map_err(|e| NetworkError::Other(e.to_string()))?;
-        cni.publish_config_load_event(pod_id, runtime_handler, &load_status);
+        let cni = self.load_cni_manager(pod_id, runtime_handler).await?;
         cni.teardown_pod_network(pod_id, netns, pod_namespace, pod_name, pod_uid)
             .await
             .map_err(|e| NetworkError::Other(e.to_string()))
     }
+
+    fn manages_host_ports(&self) -> bool {
+        self.rootless_helper_manages_interface()
+    }
+
+    fn rootless_port_forwards(&self) -> Vec<RootlessPortForward> {
+        let mut forwards: Vec<_> = self
+            .rootless_port_forwards
+            .lock()
+            .map(|registry| registry.values().flatten().cloned().collect())
+            .unwrap_or_default();
+        forwards.sort_by(|left: &RootlessPortForward, right| {
+            (&left.pod_id, left.host_port, &left.protocol).cmp(&(
+                &right.pod_id,
+                right.host_port,
+                &right.protocol,
+            ))
+        });
+        forwards
+    }
 }
 
 #[cfg(test)]
@@ -1185,7 +1445,7 @@ mod tests {
         )));
         let manager = DefaultNetworkManager::from_cni_config(cni);
         let status = manager
-            .start_rootless_network_helper("pod-1", "/tmp/nonexistent-netns")
+            .start_rootless_network_helper("pod-1", "/tmp/nonexistent-netns", &[])
             .unwrap();
         assert_eq!(status.name, "none");
         assert!(status.ip.is_none());
@@ -1227,7 +1487,7 @@ mod tests {
         let manager = DefaultNetworkManager::from_cni_config(cni);
 
         let err = manager
-            .start_rootless_network_helper("pod-1", "/tmp/nonexistent-netns")
+            .start_rootless_network_helper("pod-1", "/tmp/nonexistent-netns", &[])
             .unwrap_err();
 
         assert!(matches!(
@@ -1290,7 +1550,7 @@ mod tests {
         let manager = DefaultNetworkManager::from_cni_config(cni);
 
         let err = manager
-            .start_rootless_network_helper("pod-1", "/tmp/nonexistent-netns")
+            .start_rootless_network_helper("pod-1", "/tmp/nonexistent-netns", &[])
             .unwrap_err();
 
         assert!(err
@@ -1307,6 +1567,67 @@ mod tests {
         assert_eq!(events[1].event_type, "network.rootless_helper_start");
     }
 
+    #[test]
+    fn rootless_pasta_helper_forwards_host_ports_until_stopped() {
+        let dir = tempfile::tempdir().unwrap();
+        let helper_path = dir.path().join("pasta");
+        let args_path = dir.path().join("pasta.args");
+        fs::write(
+            &helper_path,
+            format!(
+                "#!/bin/sh\necho \"$@\" > {}\nexec sleep 30\n",
+                args_path.display()
+            ),
+        )
+        .unwrap();
+        let mut perms = fs::metadata(&helper_path).unwrap().permissions();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            perms.set_mode(0o755);
+        }
+        fs::set_permissions(&helper_path, perms).unwrap();
+        let mut cni = CniConfig::default();
+        cni.set_rootless_config(Some(rootless_effective_config(
+            crate::rootless::NetworkMode::Pasta,
+            PathBuf::from("slirp4netns"),
+            helper_path,
+        )));
+        let manager = DefaultNetworkManager::from_cni_config(cni);
+        assert!(manager.manages_host_ports());
+
+        manager
+            .start_rootless_network_helper(
+                "pod-1",
+                "/tmp/nonexistent-netns",
+                &[HostPort {
+                    protocol: Protocol::Tcp,
+                    host_ip: None,
+                    host_port: 8080,
+                    container_port: 80,
+                }],
+            )
+            .unwrap();
+
+        let forwards = manager.rootless_port_forwards();
+        assert_eq!(forwards.len(), 1);
+        assert_eq!(forwards[0].pod_id, "pod-1");
+        assert_eq!(forwards[0].helper, "pasta");
+        assert_eq!(forwards[0].host_port, 8080);
+        assert_eq!(forwards[0].container_port, 80);
+        assert!(forwards[0].forward_id.is_none());
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(2);
+        while !args_path.exists() && std::time::Instant::now() < deadline {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        let args = fs::read_to_string(&args_path).unwrap();
+        assert!(args.contains("--netns /tmp/nonexistent-netns"));
+        assert!(args.contains("-t 8080:80"));
+
+        manager.stop_rootless_network_helper("pod-1");
+        assert!(manager.rootless_port_forwards().is_empty());
+    }
+
     #[test]
     fn rootless_helper_stop_event_is_persisted() {
         let dir = tempfile::tempdir().unwrap();
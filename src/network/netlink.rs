@@ -11,6 +11,7 @@ use rtnetlink::packet_route::{
 };
 
 use super::{NetworkError, NetworkInterface};
+use crate::metrics::NetworkStats;
 
 pub(crate) async fn set_loopback_up(netns_path: PathBuf) -> Result<(), NetworkError> {
     tokio::task::spawn_blocking(move || {
@@ -46,17 +47,11 @@ pub(crate) async fn discover_interfaces(
                 })?;
                 tokio::spawn(connection);
 
-                let mut links = HashMap::new();
-                let mut link_stream = handle.link().get().execute();
-                while let Some(link) = link_stream
-                    .try_next()
-                    .await
-                    .map_err(|err| NetworkError::Other(format!("failed to list links: {err}")))?
-                {
-                    if let Some(name) = link_name(&link) {
-                        links.insert(link.header.index, name);
-                    }
-                }
+                let links: HashMap<_, _> = list_links(&handle)
+                    .await?
+                    .iter()
+                    .filter_map(|link| link_name(link).map(|name| (link.header.index, name)))
+                    .collect();
 
                 let mut seen = HashSet::new();
                 let mut interfaces = Vec::new();
@@ -92,6 +87,42 @@ pub(crate) async fn discover_interfaces(
     .map_err(|err| NetworkError::Other(format!("netns interface probe task failed: {err}")))?
 }
 
+/// 读取 netns 内各接口（不含 lo）的收发计数，按接口名排序。
+pub(crate) async fn interface_stats(netns_path: &Path) -> Result<Vec<NetworkStats>, NetworkError> {
+    let netns_path = netns_path.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        run_in_netns_thread(netns_path, || {
+            run_netlink(async {
+                let (connection, handle, _) = rtnetlink::new_connection().map_err(|err| {
+                    NetworkError::Other(format!("netlink connection failed: {err}"))
+                })?;
+                tokio::spawn(connection);
+
+                let mut stats: Vec<_> = list_links(&handle)
+                    .await?
+                    .iter()
+                    .filter_map(link_stats)
+                    .filter(|stats| stats.name != "lo")
+                    .collect();
+                stats.sort_by(|left, right| left.name.cmp(&right.name));
+                Ok(stats)
+            })
+        })
+    })
+    .await
+    .map_err(|err| NetworkError::Other(format!("netns interface stats task failed: {err}")))?
+}
+
+async fn list_links(handle: &rtnetlink::Handle) -> Result<Vec<LinkMessage>, NetworkError> {
+    handle
+        .link()
+        .get()
+        .execute()
+        .try_collect()
+        .await
+        .map_err(|err| NetworkError::Other(format!("failed to list links: {err}")))
+}
+
 fn run_in_netns_thread<T, F>(netns_path: PathBuf, f: F) -> Result<T, NetworkError>
 where
     T: Send + 'static,
@@ -133,6 +164,41 @@ fn link_name(link: &LinkMessage) -> Option<String> {
     })
 }
 
+/// 优先使用 64 位计数（IFLA_STATS64），旧内核只提供 32 位计数时回退。
+fn link_stats(link: &LinkMessage) -> Option<NetworkStats> {
+    let name = link_name(link)?;
+    let stats64 = link.attributes.iter().find_map(|attr| match attr {
+        LinkAttribute::Stats64(stats) => Some(NetworkStats {
+            name: name.clone(),
+            rx_bytes: stats.rx_bytes,
+            rx_packets: stats.rx_packets,
+            rx_errors: stats.rx_errors,
+            rx_dropped: stats.rx_dropped,
+            tx_bytes: stats.tx_bytes,
+            tx_packets: stats.tx_packets,
+            tx_errors: stats.tx_errors,
+            tx_dropped: stats.tx_dropped,
+        }),
+        _ => None,
+    });
+    stats64.or_else(|| {
+        link.attributes.iter().find_map(|attr| match attr {
+            LinkAttribute::Stats(stats) => Some(NetworkStats {
+                name: name.clone(),
+                rx_bytes: stats.rx_bytes.into(),
+                rx_packets: stats.rx_packets.into(),
+                rx_errors: stats.rx_errors.into(),
+                rx_dropped: stats.rx_dropped.into(),
+                tx_bytes: stats.tx_bytes.into(),
+                tx_packets: stats.tx_packets.into(),
+                tx_errors: stats.tx_errors.into(),
+                tx_dropped: stats.tx_dropped.into(),
+            }),
+            _ => None,
+        })
+    })
+}
+
 fn address_ip(attributes: &[AddressAttribute]) -> Option<IpAddr> {
     let mut address = None;
     for attr in attributes {
@@ -188,6 +254,42 @@ mod tests {
             .expect("remove netns");
     }
 
+    #[test]
+    fn link_stats_prefers_64bit_counters_and_falls_back_to_32bit() {
+        use rtnetlink::packet_route::link::{Stats, Stats64};
+
+        let mut stats64 = Stats64::default();
+        stats64.rx_bytes = u64::from(u32::MAX) + 10;
+        stats64.tx_packets = 7;
+        stats64.rx_dropped = 2;
+        let mut stats32 = Stats::default();
+        stats32.rx_bytes = 1;
+        stats32.tx_errors = 3;
+
+        let mut link = LinkMessage::default();
+        link.attributes = vec![
+            LinkAttribute::IfName("net1".to_string()),
+            LinkAttribute::Stats(stats32),
+            LinkAttribute::Stats64(stats64),
+        ];
+        let stats = link_stats(&link).expect("stats from IFLA_STATS64");
+        assert_eq!(stats.name, "net1");
+        assert_eq!(stats.rx_bytes, u64::from(u32::MAX) + 10);
+        assert_eq!(stats.tx_packets, 7);
+        assert_eq!(stats.rx_dropped, 2);
+
+        link.attributes = vec![
+            LinkAttribute::IfName("eth0".to_string()),
+            LinkAttribute::Stats(stats32),
+        ];
+        let stats = link_stats(&link).expect("stats from IFLA_STATS");
+        assert_eq!(stats.rx_bytes, 1);
+        assert_eq!(stats.tx_errors, 3);
+
+        link.attributes = vec![LinkAttribute::Stats(stats32)];
+        assert!(link_stats(&link).is_none());
+    }
+
     async fn add_dummy_interface_with_address(netns_path: PathBuf) -> Result<(), NetworkError> {
         tokio::task::spawn_blocking(move || {
             run_in_netns_thread(netns_path, || {
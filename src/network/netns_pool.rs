@@ -0,0 +1,388 @@
+//! 预热 netns 池
+//!
+//! 批量作业场景下 `RunPodSandbox` 的 netns 创建（pinns/unshare + bind mount + 拉起 lo）
+//! 会成为冷启动延迟的一部分。池子预先创建好带 loopback 的 netns：
+//! - 取出时把池内挂载点 bind mount 到 Pod 的 netns 路径，再卸载池内挂载点
+//! - 每次取出后在后台补齐到配置的容量
+//! - 容量为 0 时完全关闭，行为与未启用时一致
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use nix::mount::{mount, umount2, MntFlags, MsFlags};
+use serde::{Deserialize, Serialize};
+
+use super::{netlink, NamespaceManager, NetworkError};
+
+/// 池内 netns 的文件名前缀；启动时会清理上一次进程遗留的同名挂载点。
+const POOL_ENTRY_PREFIX: &str = "crius-netns-pool-";
+
+/// netns 池占用情况快照。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetnsPoolStats {
+    /// 配置的目标容量。
+    pub capacity: usize,
+    /// 当前可直接取用的 netns 数量。
+    pub available: usize,
+    /// 从池中取到 netns 的次数。
+    pub hits: u64,
+    /// 池为空而回退到同步创建的次数。
+    pub misses: u64,
+    /// 后台补齐时创建失败的次数。
+    pub create_failures: u64,
+}
+
+#[derive(Debug, Default)]
+struct NetnsPoolState {
+    namespace_manager: Option<NamespaceManager>,
+    capacity: usize,
+    generation: u64,
+    next_entry: u64,
+    idle: VecDeque<PathBuf>,
+    hits: u64,
+    misses: u64,
+    create_failures: u64,
+}
+
+#[derive(Debug, Default)]
+struct NetnsPoolInner {
+    state: Mutex<NetnsPoolState>,
+    replenishing: AtomicBool,
+}
+
+/// 预热 netns 池句柄；克隆后共享同一个池。
+#[derive(Debug, Clone, Default)]
+pub struct NetnsPool {
+    inner: Arc<NetnsPoolInner>,
+}
+
+impl NetnsPool {
+    /// 更新池使用的 namespace 管理器和容量；配置变化时丢弃已有的预热 netns。
+    pub fn configure(&self, namespace_manager: NamespaceManager, capacity: usize) {
+        let stale = {
+            let Ok(mut state) = self.inner.state.lock() else {
+                return;
+            };
+            if state.capacity == capacity
+                && state.namespace_manager.as_ref() == Some(&namespace_manager)
+            {
+                return;
+            }
+            let first_use = state.namespace_manager.is_none();
+            state.generation += 1;
+            state.capacity = capacity;
+            let mut stale: Vec<PathBuf> = state.idle.drain(..).collect();
+            if first_use && capacity > 0 {
+                stale.extend(leftover_entries(namespace_manager.mount_dir()));
+            }
+            state.namespace_manager = Some(namespace_manager);
+            stale
+        };
+        self.discard(stale);
+        self.spawn_replenish();
+    }
+
+    pub fn stats(&self) -> NetnsPoolStats {
+        self.inner
+            .state
+            .lock()
+            .map(|state| NetnsPoolStats {
+                capacity: state.capacity,
+                available: state.idle.len(),
+                hits: state.hits,
+                misses: state.misses,
+                create_failures: state.create_failures,
+            })
+            .unwrap_or_default()
+    }
+
+    /// 从池中取出一个 netns 并挂到 `target`；池为空或未启用时返回 `Ok(false)`，由调用方同步创建。
+    pub async fn checkout(&self, target: &Path) -> Result<bool, NetworkError> {
+        let entry = {
+            let mut state = self
+                .inner
+                .state
+                .lock()
+                .map_err(|_| NetworkError::Other("netns pool lock poisoned".to_string()))?;
+            if state.capacity == 0 || target.exists() {
+                return Ok(false);
+            }
+            match state.idle.pop_front() {
+                Some(entry) => {
+                    state.hits += 1;
+                    entry
+                }
+                None => {
+                    state.misses += 1;
+                    drop(state);
+                    self.spawn_replenish();
+                    return Ok(false);
+                }
+            }
+        };
+
+        let target = target.to_path_buf();
+        let source = entry.clone();
+        let moved = tokio::task::spawn_blocking(move || move_namespace(&source, &target))
+            .await
+            .map_err(|err| {
+                NetworkError::Other(format!("netns pool checkout task failed: {err}"))
+            })?;
+        self.spawn_replenish();
+        match moved {
+            Ok(()) => Ok(true),
+            Err(err) => {
+                log::warn!(
+                    "Failed to hand out pooled netns {}: {}",
+                    entry.display(),
+                    err
+                );
+                self.discard(vec![entry]);
+                Ok(false)
+            }
+        }
+    }
+
+    /// 在后台把池补齐到目标容量；同一时间只有一个补齐任务。
+    pub fn spawn_replenish(&self) {
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        if self.inner.replenishing.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let pool = self.clone();
+        handle.spawn(async move {
+            pool.replenish().await;
+            pool.inner.replenishing.store(false, Ordering::Release);
+        });
+    }
+
+    /// 同步补齐到目标容量，遇到创建失败即停止，等待下一次取出时重试。
+    pub async fn replenish(&self) {
+        loop {
+            let (manager, generation, name) = {
+                let Ok(mut state) = self.inner.state.lock() else {
+                    return;
+                };
+                let Some(manager) = state.namespace_manager.clone() else {
+                    return;
+                };
+                if state.idle.len() >= state.capacity {
+                    return;
+                }
+                state.next_entry += 1;
+                let name = format!(
+                    "{POOL_ENTRY_PREFIX}{}-{}",
+                    std::process::id(),
+                    state.next_entry
+                );
+                (manager, state.generation, name)
+            };
+
+            let created = match manager.create(&name).await {
+                Ok(path) => match netlink::set_loopback_up(path.clone()).await {
+                    Ok(()) => Ok(path),
+                    Err(err) => {
+                        let _ = manager.remove(&name).await;
+                        Err(err)
+                    }
+                },
+                Err(err) => Err(err),
+            };
+            let path = match created {
+                Ok(path) => path,
+                Err(err) => {
+                    log::warn!("Failed to pre-create pooled netns {}: {}", name, err);
+                    if let Ok(mut state) = self.inner.state.lock() {
+                        state.create_failures += 1;
+                    }
+                    return;
+                }
+            };
+
+            let stale = {
+                let Ok(mut state) = self.inner.state.lock() else {
+                    return;
+                };
+                if state.generation == generation && state.idle.len() < state.capacity {
+                    state.idle.push_back(path);
+                    None
+                } else {
+                    Some(path)
+                }
+            };
+            if let Some(path) = stale {
+                remove_entry(&path);
+            }
+        }
+    }
+
+    /// 卸载并删除所有预热 netns。
+    pub fn drain(&self) {
+        let stale = self
+            .inner
+            .state
+            .lock()
+            .map(|mut state| {
+                state.generation += 1;
+                state.idle.drain(..).collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        for path in stale {
+            remove_entry(&path);
+        }
+    }
+
+    fn discard(&self, entries: Vec<PathBuf>) {
+        if entries.is_empty() {
+            return;
+        }
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn_blocking(move || entries.iter().for_each(|path| remove_entry(path)));
+            }
+            Err(_) => entries.iter().for_each(|path| remove_entry(path)),
+        }
+    }
+}
+
+fn leftover_entries(mount_dir: &Path) -> Vec<PathBuf> {
+    std::fs::read_dir(mount_dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| {
+                    entry
+                        .file_name()
+                        .to_str()
+                        .is_some_and(|name| name.starts_with(POOL_ENTRY_PREFIX))
+                })
+                .map(|entry| entry.path())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 把池内 netns 挂载点转移到 Pod 的 netns 路径；namespace 由新挂载点继续持有。
+fn move_namespace(source: &Path, target: &Path) -> Result<(), NetworkError> {
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let _mountpoint = std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(target)?;
+    if let Err(err) = mount(
+        Some(source),
+        target,
+        None::<&str>,
+        MsFlags::MS_BIND,
+        None::<&str>,
+    ) {
+        let _ = std::fs::remove_file(target);
+        return Err(NetworkError::Other(format!(
+            "failed to bind-mount pooled netns {} at {}: {err}",
+            source.display(),
+            target.display()
+        )));
+    }
+    remove_entry(source);
+    Ok(())
+}
+
+fn remove_entry(path: &Path) {
+    match umount2(path, MntFlags::MNT_DETACH) {
+        Ok(()) | Err(nix::errno::Errno::EINVAL | nix::errno::Errno::ENOENT) => {}
+        Err(err) => {
+            log::warn!("Failed to unmount pooled netns {}: {}", path.display(), err);
+            return;
+        }
+    }
+    if let Err(err) = std::fs::remove_file(path) {
+        if err.kind() != std::io::ErrorKind::NotFound {
+            log::warn!("Failed to remove pooled netns {}: {}", path.display(), err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn can_create_network_namespace() -> bool {
+        std::process::Command::new("unshare")
+            .args(["-n", "true"])
+            .status()
+            .map(|status| status.success())
+            .unwrap_or(false)
+    }
+
+    #[tokio::test]
+    async fn disabled_pool_falls_back_without_counting_misses() {
+        let temp = tempfile::tempdir().unwrap();
+        let pool = NetnsPool::default();
+        pool.configure(NamespaceManager::new(temp.path().to_path_buf()), 0);
+
+        assert!(!pool.checkout(&temp.path().join("pod")).await.unwrap());
+        assert_eq!(pool.stats(), NetnsPoolStats::default());
+    }
+
+    #[tokio::test]
+    async fn configure_discards_leftover_entries_from_previous_process() {
+        let temp = tempfile::tempdir().unwrap();
+        let leftover = temp.path().join(format!("{POOL_ENTRY_PREFIX}1-1"));
+        std::fs::write(&leftover, b"").unwrap();
+        std::fs::write(temp.path().join("crius-default-pod"), b"").unwrap();
+
+        let pool = NetnsPool::default();
+        let manager = NamespaceManager::new(temp.path().to_path_buf());
+        assert_eq!(
+            leftover_entries(manager.mount_dir()),
+            vec![leftover.clone()]
+        );
+        pool.discard(leftover_entries(manager.mount_dir()));
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+        assert!(!leftover.exists());
+        assert!(temp.path().join("crius-default-pod").exists());
+    }
+
+    #[tokio::test]
+    async fn pooled_netns_is_handed_out_and_replenished() {
+        if !can_create_network_namespace() {
+            eprintln!("skipping netns pool test because CLONE_NEWNET is unavailable");
+            return;
+        }
+
+        let temp = tempfile::tempdir().unwrap();
+        let manager = NamespaceManager::new(temp.path().to_path_buf());
+        let pool = NetnsPool::default();
+        pool.configure(manager.clone(), 1);
+        pool.replenish().await;
+        assert_eq!(pool.stats().available, 1);
+
+        let target = manager.resolve_path("crius-default-pod");
+        assert!(pool.checkout(&target).await.unwrap());
+        assert!(target.exists());
+        netlink::discover_interfaces(&target)
+            .await
+            .expect("pooled netns is usable");
+
+        pool.replenish().await;
+        let stats = pool.stats();
+        assert_eq!(stats.capacity, 1);
+        assert_eq!(stats.available, 1);
+        assert_eq!(stats.hits, 1);
+
+        pool.drain();
+        manager
+            .remove(target.to_str().unwrap())
+            .await
+            .expect("remove handed out netns");
+        assert!(leftover_entries(temp.path()).is_empty());
+    }
+}
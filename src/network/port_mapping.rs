@@ -1,6 +1,9 @@
 //! 端口映射模块
 //!
 //! 提供容器端口到主机端口的映射功能，支持iptables和nftables
+//!
+//! IPv4 映射走 `iptables` / nft `ip` 规则，IPv6 映射走 `ip6tables` / nft `ip6` 规则；
+//! 双栈 Pod 每个地址族各产生一条映射。
 
 use anyhow::{Context, Result};
 use log::{debug, info};
@@ -42,6 +45,90 @@ impl Protocol {
     }
 }
 
+impl PortMapping {
+    /// 未指定主机IP时按容器IP地址族返回通配地址
+    fn host_ip_or_unspecified(&self) -> String {
+        match (self.host_ip, self.container_ip) {
+            (Some(ip), _) => ip.to_string(),
+            (None, IpAddr::V4(_)) => "0.0.0.0".to_string(),
+            (None, IpAddr::V6(_)) => "::".to_string(),
+        }
+    }
+
+    /// DNAT 目标地址；IPv6 地址需要加方括号
+    fn dnat_destination(&self) -> String {
+        match self.container_ip {
+            IpAddr::V4(ip) => format!("{}:{}", ip, self.container_port),
+            IpAddr::V6(ip) => format!("[{}]:{}", ip, self.container_port),
+        }
+    }
+
+    /// 对应地址族的 iptables 命令
+    fn iptables_command(&self) -> &'static str {
+        if self.container_ip.is_ipv6() {
+            "ip6tables"
+        } else {
+            "iptables"
+        }
+    }
+
+    /// nftables 规则中的地址族关键字
+    fn nft_family(&self) -> &'static str {
+        if self.container_ip.is_ipv6() {
+            "ip6"
+        } else {
+            "ip"
+        }
+    }
+
+    /// DNAT / MASQUERADE / FORWARD 三条 iptables 规则
+    fn iptables_rules(&self) -> [String; 3] {
+        let proto = self.protocol.as_str();
+        let container_ip = self.container_ip;
+        [
+            format!(
+                "-p {} --dport {} -j DNAT --to-destination {}",
+                proto,
+                self.host_port,
+                self.dnat_destination()
+            ),
+            format!("-p {} -s {} -j MASQUERADE", proto, container_ip),
+            format!(
+                "-p {} -d {} --dport {} -j ACCEPT",
+                proto, container_ip, self.container_port
+            ),
+        ]
+    }
+
+    /// `table inet crius` 规则集
+    fn nftables_rule_set(&self) -> String {
+        let proto = self.protocol.as_str();
+        let family = self.nft_family();
+        format!(
+            "table inet crius {{
+    chain prerouting {{
+        type nat hook prerouting priority 0; policy accept;
+        {proto} dport {host_port} dnat {family} to {destination}
+    }}
+    chain postrouting {{
+        type nat hook postrouting priority 100; policy accept;
+        {family} saddr {container_ip} masquerade
+    }}
+    chain forward {{
+        type filter hook forward priority 0; policy accept;
+        {family} daddr {container_ip} {proto} dport {container_port} accept
+    }}
+}}",
+            proto = proto,
+            family = family,
+            host_port = self.host_port,
+            destination = self.dnat_destination(),
+            container_ip = self.container_ip,
+            container_port = self.container_port,
+        )
+    }
+}
+
 /// 端口映射管理器
 #[derive(Debug)]
 pub struct PortMappingManager {
@@ -127,46 +214,27 @@ impl PortMappingManager {
 
     /// 添加iptables规则
     fn add_iptables_rule(&self, mapping: &PortMapping) -> Result<()> {
-        let host_ip = mapping
-            .host_ip
-            .map(|ip| ip.to_string())
-            .unwrap_or_else(|| "0.0.0.0".to_string());
-        let container_ip = mapping.container_ip.to_string();
+        let command = mapping.iptables_command();
+        let [dnat_rule, masquerade_rule, forward_rule] = mapping.iptables_rules();
 
         // DNAT规则：将主机端口映射到容器端口
-        let dnat_rule = format!(
-            "-p {} --dport {} -j DNAT --to-destination {}:{}",
-            mapping.protocol.as_str(),
-            mapping.host_port,
-            container_ip,
-            mapping.container_port
-        );
+        self.run_iptables(command, &["-t", "nat", "-A", "PREROUTING", &dnat_rule])?;
 
         // MASQUERADE规则：允许容器访问外部
-        let masquerade_rule = format!(
-            "-p {} -s {} -j MASQUERADE",
-            mapping.protocol.as_str(),
-            container_ip
-        );
-
-        // 添加DNAT规则到PREROUTING链
-        self.run_iptables(&["-t", "nat", "-A", "PREROUTING", &dnat_rule])?;
-
-        // 添加MASQUERADE规则到POSTROUTING链
-        self.run_iptables(&["-t", "nat", "-A", "POSTROUTING", &masquerade_rule])?;
+        self.run_iptables(
+            command,
+            &["-t", "nat", "-A", "POSTROUTING", &masquerade_rule],
+        )?;
 
         // 允许转发
-        let forward_rule = format!(
-            "-p {} -d {} --dport {} -j ACCEPT",
-            mapping.protocol.as_str(),
-            container_ip,
-            mapping.container_port
-        );
-        self.run_iptables(&["-A", "FORWARD", &forward_rule])?;
+        self.run_iptables(command, &["-A", "FORWARD", &forward_rule])?;
 
         info!(
-            "Added iptables port mapping: {}:{} -> {}:{}",
-            host_ip, mapping.host_port, container_ip, mapping.container_port
+            "Added {} port mapping: {}:{} -> {}",
+            command,
+            mapping.host_ip_or_unspecified(),
+            mapping.host_port,
+            mapping.dnat_destination()
         );
 
         Ok(())
@@ -174,53 +242,38 @@ impl PortMappingManager {
 
     /// 删除iptables规则
     fn remove_iptables_rule(&self, mapping: &PortMapping) -> Result<()> {
-        let container_ip = mapping.container_ip.to_string();
-
+        let command = mapping.iptables_command();
         // 构建与添加时相同的规则，但使用-D删除
-        let dnat_rule = format!(
-            "-p {} --dport {} -j DNAT --to-destination {}:{}",
-            mapping.protocol.as_str(),
-            mapping.host_port,
-            container_ip,
-            mapping.container_port
-        );
-
-        let masquerade_rule = format!(
-            "-p {} -s {} -j MASQUERADE",
-            mapping.protocol.as_str(),
-            container_ip
-        );
-
-        let forward_rule = format!(
-            "-p {} -d {} --dport {} -j ACCEPT",
-            mapping.protocol.as_str(),
-            container_ip,
-            mapping.container_port
-        );
+        let [dnat_rule, masquerade_rule, forward_rule] = mapping.iptables_rules();
 
         // 删除规则（忽略错误，规则可能不存在）
-        let _ = self.run_iptables(&["-t", "nat", "-D", "PREROUTING", &dnat_rule]);
-        let _ = self.run_iptables(&["-t", "nat", "-D", "POSTROUTING", &masquerade_rule]);
-        let _ = self.run_iptables(&["-D", "FORWARD", &forward_rule]);
+        let _ = self.run_iptables(command, &["-t", "nat", "-D", "PREROUTING", &dnat_rule]);
+        let _ = self.run_iptables(
+            command,
+            &["-t", "nat", "-D", "POSTROUTING", &masquerade_rule],
+        );
+        let _ = self.run_iptables(command, &["-D", "FORWARD", &forward_rule]);
 
         info!(
-            "Removed iptables port mapping: {} -> {}:{}",
-            mapping.host_port, container_ip, mapping.container_port
+            "Removed {} port mapping: {} -> {}",
+            command,
+            mapping.host_port,
+            mapping.dnat_destination()
         );
 
         Ok(())
     }
 
-    /// 运行iptables命令
-    fn run_iptables(&self, args: &[&str]) -> Result<()> {
-        let output = Command::new("iptables")
-            .args(args)
+    /// 运行iptables / ip6tables命令；规则字符串按空白拆成独立参数
+    fn run_iptables(&self, command: &str, args: &[&str]) -> Result<()> {
+        let output = Command::new(command)
+            .args(args.iter().flat_map(|arg| arg.split_whitespace()))
             .output()
-            .context("Failed to execute iptables command")?;
+            .with_context(|| format!("Failed to execute {} command", command))?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(anyhow::anyhow!("iptables command failed: {}", stderr));
+            return Err(anyhow::anyhow!("{} command failed: {}", command, stderr));
         }
 
         Ok(())
@@ -228,37 +281,7 @@ impl PortMappingManager {
 
     /// 添加nftables规则
     fn add_nftables_rule(&self, mapping: &PortMapping) -> Result<()> {
-        let container_ip = mapping.container_ip.to_string();
-        let host_ip = mapping
-            .host_ip
-            .map(|ip| ip.to_string())
-            .unwrap_or_else(|| "0.0.0.0".to_string());
-
-        let proto = mapping.protocol.as_str();
-        let host_port = mapping.host_port;
-        let container_port = mapping.container_port;
-
-        // 创建nftables规则集 - 使用字符串拼接避免格式字符串问题
-        let rule_set = format!(
-            "table inet crius {{
-    chain prerouting {{
-        type nat hook prerouting priority 0; policy accept;
-        {proto} dport {host_port} dnat to {container_ip}:{container_port}
-    }}
-    chain postrouting {{
-        type nat hook postrouting priority 100; policy accept;
-        ip saddr {container_ip} masquerade
-    }}
-    chain forward {{
-        type filter hook forward priority 0; policy accept;
-        ip daddr {container_ip} {proto} dport {container_port} accept
-    }}
-}}",
-            proto = proto,
-            host_port = host_port,
-            container_ip = container_ip,
-            container_port = container_port,
-        );
+        let rule_set = mapping.nftables_rule_set();
 
         // 使用nft命令添加规则 - 写入临时文件
         use std::io::Write;
@@ -283,8 +306,10 @@ impl PortMappingManager {
         }
 
         info!(
-            "Added nftables port mapping: {}:{} -> {}:{}",
-            host_ip, host_port, container_ip, container_port
+            "Added nftables port mapping: {}:{} -> {}",
+            mapping.host_ip_or_unspecified(),
+            mapping.host_port,
+            mapping.dnat_destination()
         );
 
         Ok(())
@@ -319,9 +344,11 @@ impl PortMappingManager {
             PortMappingBackend::Iptables => {
                 // 删除crius相关的所有规则
                 // 注意：这里需要谨慎，避免删除系统规则
-                let _ = self.run_iptables(&["-t", "nat", "-F", "PREROUTING"]);
-                let _ = self.run_iptables(&["-t", "nat", "-F", "POSTROUTING"]);
-                let _ = self.run_iptables(&["-F", "FORWARD"]);
+                for command in ["iptables", "ip6tables"] {
+                    let _ = self.run_iptables(command, &["-t", "nat", "-F", "PREROUTING"]);
+                    let _ = self.run_iptables(command, &["-t", "nat", "-F", "POSTROUTING"]);
+                    let _ = self.run_iptables(command, &["-F", "FORWARD"]);
+                }
             }
             PortMappingBackend::Nftables => {
                 let _ = Command::new("nft")
@@ -338,7 +365,7 @@ impl PortMappingManager {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::net::Ipv4Addr;
+    use std::net::{Ipv4Addr, Ipv6Addr};
 
     #[test]
     fn test_protocol_as_str() {
@@ -361,4 +388,52 @@ mod tests {
         assert_eq!(mapping.container_port, 80);
         assert_eq!(mapping.host_port, 8080);
     }
+
+    #[test]
+    fn ipv4_mapping_uses_iptables_and_nft_ip_family() {
+        let mapping = PortMapping {
+            protocol: Protocol::Tcp,
+            container_port: 80,
+            host_port: 8080,
+            host_ip: None,
+            container_ip: IpAddr::V4(Ipv4Addr::new(10, 88, 0, 2)),
+        };
+
+        assert_eq!(mapping.iptables_command(), "iptables");
+        assert_eq!(mapping.host_ip_or_unspecified(), "0.0.0.0");
+        assert_eq!(
+            mapping.iptables_rules()[0],
+            "-p tcp --dport 8080 -j DNAT --to-destination 10.88.0.2:80"
+        );
+        let rule_set = mapping.nftables_rule_set();
+        assert!(rule_set.contains("tcp dport 8080 dnat ip to 10.88.0.2:80"));
+        assert!(rule_set.contains("ip saddr 10.88.0.2 masquerade"));
+    }
+
+    #[test]
+    fn ipv6_mapping_uses_ip6tables_and_bracketed_dnat_destination() {
+        let container_ip: Ipv6Addr = "fd00:10:88::2".parse().unwrap();
+        let mapping = PortMapping {
+            protocol: Protocol::Udp,
+            container_port: 53,
+            host_port: 5353,
+            host_ip: None,
+            container_ip: IpAddr::V6(container_ip),
+        };
+
+        assert_eq!(mapping.iptables_command(), "ip6tables");
+        assert_eq!(mapping.host_ip_or_unspecified(), "::");
+        assert_eq!(
+            mapping.iptables_rules(),
+            [
+                "-p udp --dport 5353 -j DNAT --to-destination [fd00:10:88::2]:53".to_string(),
+                "-p udp -s fd00:10:88::2 -j MASQUERADE".to_string(),
+                "-p udp -d fd00:10:88::2 --dport 53 -j ACCEPT".to_string(),
+            ]
+        );
+        let rule_set = mapping.nftables_rule_set();
+        assert!(rule_set.contains("udp dport 5353 dnat ip6 to [fd00:10:88::2]:53"));
+        assert!(rule_set.contains("ip6 saddr fd00:10:88::2 masquerade"));
+        assert!(rule_set.contains("ip6 daddr fd00:10:88::2 udp dport 53 accept"));
+    }
 }
@@ -0,0 +1,296 @@
+//! Rootless hostPort 转发
+//!
+//! rootless 模式下无法写入宿主机 iptables/nftables，hostPort 由网络 helper 在用户态转发：
+//! - slirp4netns：通过 `--api-socket` 的 `add_hostfwd`/`remove_hostfwd` 动态增删
+//! - pasta：启动时通过 `-t`/`-u` 端口转发参数声明，helper 退出时一并失效
+
+use std::io::{Read, Write};
+use std::net::IpAddr;
+use std::os::unix::net::UnixStream;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use super::{NetworkError, Protocol};
+
+const SLIRP4NETNS_API_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Pod 请求的 hostPort（与容器 IP 无关，rootless helper 直接转发到 netns 内）。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HostPort {
+    pub protocol: Protocol,
+    pub host_ip: Option<IpAddr>,
+    pub host_port: u16,
+    pub container_port: u16,
+}
+
+/// 当前生效的 rootless hostPort 转发。
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RootlessPortForward {
+    pub pod_id: String,
+    pub helper: String,
+    pub protocol: String,
+    pub host_ip: String,
+    pub host_port: u16,
+    pub container_port: u16,
+    /// slirp4netns `add_hostfwd` 返回的转发 ID；pasta 无此概念。
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forward_id: Option<u64>,
+}
+
+impl RootlessPortForward {
+    pub(crate) fn new(
+        pod_id: &str,
+        helper: &str,
+        port: &HostPort,
+        forward_id: Option<u64>,
+    ) -> Self {
+        Self {
+            pod_id: pod_id.to_string(),
+            helper: helper.to_string(),
+            protocol: port.protocol.as_str().to_string(),
+            host_ip: host_ip_string(port),
+            host_port: port.host_port,
+            container_port: port.container_port,
+            forward_id,
+        }
+    }
+}
+
+fn host_ip_string(port: &HostPort) -> String {
+    port.host_ip
+        .map(|ip| ip.to_string())
+        .unwrap_or_else(|| "0.0.0.0".to_string())
+}
+
+fn expand_protocols(protocol: Protocol) -> Result<&'static [&'static str], NetworkError> {
+    match protocol {
+        Protocol::Tcp => Ok(&["tcp"]),
+        Protocol::Udp => Ok(&["udp"]),
+        Protocol::Both => Ok(&["tcp", "udp"]),
+        Protocol::Sctp => Err(NetworkError::UnsupportedConfig(
+            "rootless hostPort forwarding does not support SCTP".to_string(),
+        )),
+    }
+}
+
+/// 校验 hostPort 是否能由 rootless helper 转发。
+pub(crate) fn validate_host_ports(ports: &[HostPort]) -> Result<(), NetworkError> {
+    ports
+        .iter()
+        .try_for_each(|port| expand_protocols(port.protocol).map(|_| ()))
+}
+
+/// 生成 pasta 的端口转发参数，例如 `-t 8080:80`、`-u 127.0.0.1/5353:53`。
+pub(crate) fn pasta_port_args(ports: &[HostPort]) -> Result<Vec<String>, NetworkError> {
+    let mut args = Vec::new();
+    for port in ports {
+        for protocol in expand_protocols(port.protocol)? {
+            args.push(if *protocol == "tcp" { "-t" } else { "-u" }.to_string());
+            let spec = format!("{}:{}", port.host_port, port.container_port);
+            args.push(match port.host_ip {
+                Some(ip) if !ip.is_unspecified() => format!("{ip}/{spec}"),
+                _ => spec,
+            });
+        }
+    }
+    Ok(args)
+}
+
+/// 等待 slirp4netns 创建 API socket。
+pub(crate) fn wait_for_api_socket(path: &Path, timeout: Duration) -> Result<(), NetworkError> {
+    let deadline = Instant::now() + timeout;
+    while !path.exists() {
+        if Instant::now() >= deadline {
+            return Err(NetworkError::Other(format!(
+                "slirp4netns API socket {} was not created within {:?}",
+                path.display(),
+                timeout
+            )));
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    Ok(())
+}
+
+fn slirp4netns_request(api_socket: &Path, request: &Value) -> Result<Value, NetworkError> {
+    let mut stream = UnixStream::connect(api_socket).map_err(|err| {
+        NetworkError::Other(format!(
+            "failed to connect slirp4netns API socket {}: {err}",
+            api_socket.display()
+        ))
+    })?;
+    stream.set_read_timeout(Some(SLIRP4NETNS_API_TIMEOUT))?;
+    stream.set_write_timeout(Some(SLIRP4NETNS_API_TIMEOUT))?;
+    stream.write_all(&serde_json::to_vec(request)?)?;
+    stream.shutdown(std::net::Shutdown::Write)?;
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response)?;
+    let response: Value = serde_json::from_slice(&response)?;
+    if let Some(error) = response.get("error") {
+        return Err(NetworkError::Network(format!(
+            "slirp4netns API {} failed: {}",
+            request["execute"].as_str().unwrap_or_default(),
+            error
+                .get("desc")
+                .and_then(Value::as_str)
+                .map(ToOwned::to_owned)
+                .unwrap_or_else(|| error.to_string())
+        )));
+    }
+    Ok(response.get("return").cloned().unwrap_or(Value::Null))
+}
+
+/// 通过 slirp4netns API 添加转发，返回每条转发及其 ID。
+pub(crate) fn slirp4netns_add_hostfwd(
+    api_socket: &Path,
+    port: &HostPort,
+) -> Result<Vec<(&'static str, u64)>, NetworkError> {
+    let mut added = Vec::new();
+    for protocol in expand_protocols(port.protocol)? {
+        let request = json!({
+            "execute": "add_hostfwd",
+            "arguments": {
+                "proto": protocol,
+                "host_addr": host_ip_string(port),
+                "host_port": port.host_port,
+                "guest_port": port.container_port,
+            },
+        });
+        let result = slirp4netns_request(api_socket, &request).and_then(|value| {
+            value.get("id").and_then(Value::as_u64).ok_or_else(|| {
+                NetworkError::Network(format!(
+                    "slirp4netns add_hostfwd returned no forward id: {value}"
+                ))
+            })
+        });
+        match result {
+            Ok(id) => added.push((*protocol, id)),
+            Err(err) => {
+                for (_, id) in &added {
+                    let _ = slirp4netns_remove_hostfwd(api_socket, *id);
+                }
+                return Err(err);
+            }
+        }
+    }
+    Ok(added)
+}
+
+/// 通过 slirp4netns API 删除转发。
+pub(crate) fn slirp4netns_remove_hostfwd(api_socket: &Path, id: u64) -> Result<(), NetworkError> {
+    slirp4netns_request(
+        api_socket,
+        &json!({
+            "execute": "remove_hostfwd",
+            "arguments": { "id": id },
+        }),
+    )
+    .map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+    use std::os::unix::net::UnixListener;
+
+    fn host_port(protocol: Protocol, host_ip: Option<IpAddr>) -> HostPort {
+        HostPort {
+            protocol,
+            host_ip,
+            host_port: 8080,
+            container_port: 80,
+        }
+    }
+
+    #[test]
+    fn pasta_port_args_cover_protocols_and_host_ip() {
+        let args = pasta_port_args(&[
+            host_port(Protocol::Tcp, None),
+            host_port(Protocol::Udp, Some(IpAddr::V4(Ipv4Addr::LOCALHOST))),
+            host_port(Protocol::Both, Some(IpAddr::V4(Ipv4Addr::UNSPECIFIED))),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            args,
+            vec![
+                "-t",
+                "8080:80",
+                "-u",
+                "127.0.0.1/8080:80",
+                "-t",
+                "8080:80",
+                "-u",
+                "8080:80"
+            ]
+        );
+    }
+
+    #[test]
+    fn pasta_port_args_reject_sctp() {
+        let err = pasta_port_args(&[host_port(Protocol::Sctp, None)]).unwrap_err();
+        assert!(err.to_string().contains("SCTP"));
+    }
+
+    #[test]
+    fn slirp4netns_hostfwd_round_trips_over_api_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("slirp4netns.sock");
+        let listener = UnixListener::bind(&socket).unwrap();
+        let server = std::thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (index, stream) in listener.incoming().take(2).enumerate() {
+                let mut stream = stream.unwrap();
+                let mut request = Vec::new();
+                stream.read_to_end(&mut request).unwrap();
+                requests.push(serde_json::from_slice::<Value>(&request).unwrap());
+                let response = if index == 0 {
+                    json!({ "return": { "id": 7 } })
+                } else {
+                    json!({ "return": {} })
+                };
+                stream
+                    .write_all(&serde_json::to_vec(&response).unwrap())
+                    .unwrap();
+            }
+            requests
+        });
+
+        let added = slirp4netns_add_hostfwd(&socket, &host_port(Protocol::Tcp, None)).unwrap();
+        slirp4netns_remove_hostfwd(&socket, 7).unwrap();
+        let requests = server.join().unwrap();
+
+        assert_eq!(added, vec![("tcp", 7)]);
+        assert_eq!(requests[0]["execute"], "add_hostfwd");
+        assert_eq!(requests[0]["arguments"]["host_addr"], "0.0.0.0");
+        assert_eq!(requests[0]["arguments"]["host_port"], 8080);
+        assert_eq!(requests[0]["arguments"]["guest_port"], 80);
+        assert_eq!(requests[1]["execute"], "remove_hostfwd");
+        assert_eq!(requests[1]["arguments"]["id"], 7);
+    }
+
+    #[test]
+    fn slirp4netns_api_error_is_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("slirp4netns.sock");
+        let listener = UnixListener::bind(&socket).unwrap();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            stream.read_to_end(&mut request).unwrap();
+            stream
+                .write_all(br#"{"error":{"desc":"bad request: add_hostfwd: bind failed"}}"#)
+                .unwrap();
+        });
+
+        let err = slirp4netns_add_hostfwd(&socket, &host_port(Protocol::Tcp, None)).unwrap_err();
+        server.join().unwrap();
+
+        assert!(err.to_string().contains("bind failed"));
+    }
+}
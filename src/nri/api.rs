@@ -37,6 +37,17 @@ pub struct NriStopContainerResult {
     pub updates: Vec<nri_api::ContainerUpdate>,
 }
 
+/// 已知 NRI 插件的连接状态。
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NriPluginStatus {
+    pub name: String,
+    pub index: String,
+    pub registered: bool,
+    pub connected: bool,
+    pub synchronized: bool,
+}
+
 #[async_trait]
 pub trait NriDomain: Send + Sync {
     async fn snapshot(&self) -> Result<RuntimeSnapshot>;
@@ -66,6 +77,9 @@ pub trait NriApi: Send + Sync {
     async fn block_plugin_sync(&self) -> Box<dyn NriPluginSyncBlock> {
         Box::new(NopNriPluginSyncBlock)
     }
+    async fn plugin_status(&self) -> Vec<NriPluginStatus> {
+        Vec::new()
+    }
     async fn run_pod_sandbox(&self, _event: NriPodEvent) -> Result<()> {
         Ok(())
     }
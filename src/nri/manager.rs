@@ -1541,6 +1541,21 @@ impl NriApi for NriManager {
         Box::new(NriManager::block_plugin_sync(self).await)
     }
 
+    async fn plugin_status(&self) -> Vec<crate::nri::NriPluginStatus> {
+        let state = self.state.read().await;
+        state
+            .plugins
+            .iter()
+            .map(|plugin| crate::nri::NriPluginStatus {
+                name: plugin.record.name.clone(),
+                index: plugin.record.index.clone(),
+                registered: plugin.registered,
+                connected: plugin.client.is_some(),
+                synchronized: plugin.synchronized,
+            })
+            .collect()
+    }
+
     async fn run_pod_sandbox(&self, event: NriPodEvent) -> Result<()> {
         self.dispatch_lifecycle_with_pod_event(nri_api::Event::RUN_POD_SANDBOX, &event)
             .await
@@ -261,6 +261,50 @@ async fn plugins_are_sorted_by_index_then_name() {
     assert_eq!(plugins[2].name, "b");
 }
 
+#[tokio::test]
+async fn plugin_status_reports_connection_state() {
+    let manager = manager_for_tests();
+    manager
+        .register_plugin(PluginRecord {
+            name: "pending".to_string(),
+            index: "01".to_string(),
+            events_mask: 0,
+            socket_path: String::new(),
+            config: String::new(),
+        })
+        .await;
+    insert_plugin_for_test(
+        &manager,
+        "connected",
+        "02",
+        0,
+        true,
+        Arc::new(FakePluginClient::default()),
+    )
+    .await;
+
+    let status = NriApi::plugin_status(&manager).await;
+    assert_eq!(
+        status,
+        vec![
+            crate::nri::NriPluginStatus {
+                name: "pending".to_string(),
+                index: "01".to_string(),
+                registered: true,
+                connected: false,
+                synchronized: false,
+            },
+            crate::nri::NriPluginStatus {
+                name: "connected".to_string(),
+                index: "02".to_string(),
+                registered: true,
+                connected: true,
+                synchronized: false,
+            },
+        ]
+    );
+}
+
 #[tokio::test]
 async fn dispatch_only_targets_subscribed_plugins() {
     let manager = manager_for_tests();
@@ -20,7 +20,8 @@ pub use adjust::{
 };
 pub use api::{
     NopNri, NopNriPluginSyncBlock, NriApi, NriContainerEvent, NriCreateContainerResult, NriDomain,
-    NriPluginSyncBlock, NriPodEvent, NriStopContainerResult, NriUpdateContainerResult,
+    NriPluginStatus, NriPluginSyncBlock, NriPodEvent, NriStopContainerResult,
+    NriUpdateContainerResult,
 };
 pub use config::NriManagerConfig;
 pub use convert::{
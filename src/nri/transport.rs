@@ -2,9 +2,9 @@ use std::collections::{HashMap, VecDeque};
 use std::os::unix::net::UnixStream as StdUnixStream;
 use std::pin::Pin;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::task::{Context, Poll, Waker};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use protobuf::{CodedInputStream, CodedOutputStream, Message};
@@ -12,8 +12,10 @@ use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf};
 use tokio::net::UnixStream;
 use tokio::sync::mpsc;
 use tokio_stream::iter;
+use tracing::Instrument;
 use ttrpc::context;
 
+use crate::metrics::registry::{metrics_registry, Histogram, LATENCY_BUCKETS_SECONDS};
 use crate::nri::{NriError, Result};
 use crate::nri_proto::api as nri_api;
 
@@ -29,6 +31,36 @@ fn map_ttrpc_error(err: ttrpc::Error) -> NriError {
     NriError::Transport(err.to_string())
 }
 
+/// NRI 插件请求耗时，按方法与结果区分。
+fn plugin_request_duration() -> &'static Histogram {
+    static METRIC: OnceLock<Histogram> = OnceLock::new();
+    METRIC.get_or_init(|| {
+        metrics_registry().histogram(
+            "crius_nri_plugin_request_duration_seconds",
+            "Duration of NRI plugin requests by method and result.",
+            &["method", "result"],
+            LATENCY_BUCKETS_SECONDS,
+        )
+    })
+}
+
+/// 插件请求计时；请求被外层截止时间丢弃时按 `cancelled` 记录。
+struct PluginRequestTimer<'a> {
+    method: &'a str,
+    span: tracing::Span,
+    started: Instant,
+    result: &'static str,
+}
+
+impl Drop for PluginRequestTimer<'_> {
+    fn drop(&mut self) {
+        let labels = [self.method, self.result];
+        let elapsed = self.started.elapsed();
+        self.span
+            .in_scope(|| plugin_request_duration().observe_duration(&labels, elapsed));
+    }
+}
+
 fn request_context(timeout: Duration) -> context::Context {
     if timeout.is_zero() {
         context::Context::default()
@@ -668,20 +700,37 @@ impl PluginTtrpcClient {
         let service = PLUGIN_SERVICE.to_string();
         let method_name = method.to_string();
         let payload = encode_message(req).map_err(map_ttrpc_error)?;
-        let ctx = request_context(self.request_timeout);
-        self.with_client(move |client| async move {
-            let request = ttrpc::Request {
-                service,
-                method: method_name,
-                timeout_nano: ctx.timeout_nano,
-                metadata: ttrpc::context::to_pb(ctx.metadata),
-                payload,
-                ..Default::default()
-            };
-            let response = client.request(request).await?;
-            decode_message::<Resp>(&response.payload)
-        })
-        .await
+        let span = crate::trace_export::phase_span("nri", method, &self.socket_path);
+        let mut ctx = request_context(self.request_timeout);
+        if let Some(traceparent) = span.in_scope(crate::trace_export::current_traceparent) {
+            ctx.add(
+                crate::trace_export::TRACEPARENT_HEADER.to_string(),
+                traceparent,
+            );
+        }
+        let mut timer = PluginRequestTimer {
+            method,
+            span: span.clone(),
+            started: Instant::now(),
+            result: "cancelled",
+        };
+        let result = self
+            .with_client(move |client| async move {
+                let request = ttrpc::Request {
+                    service,
+                    method: method_name,
+                    timeout_nano: ctx.timeout_nano,
+                    metadata: ttrpc::context::to_pb(ctx.metadata),
+                    payload,
+                    ..Default::default()
+                };
+                let response = client.request(request).await?;
+                decode_message::<Resp>(&response.payload)
+            })
+            .instrument(span)
+            .await;
+        timer.result = if result.is_ok() { "success" } else { "error" };
+        result
     }
 
     pub async fn shutdown(&self) -> Result<()> {
@@ -3,16 +3,16 @@
 //! 提供Pod沙箱的创建、管理和清理功能
 
 use anyhow::{Context, Result};
-use log::{debug, error, info};
+use log::{debug, error, info, warn};
 use std::collections::HashMap;
 use std::net::IpAddr;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 use crate::network::{
-    CniConfig, DefaultNetworkManager, NamespaceManager, NetworkInterface, NetworkManager,
-    NetworkSetupRequest, NetworkStatus, PortMapping as HostPortMapping, PortMappingManager,
-    Protocol,
+    CniConfig, DefaultNetworkManager, HostPort, NamespaceManager, NetnsPool, NetworkInterface,
+    NetworkManager, NetworkSetupRequest, NetworkStatus, PortMapping as HostPortMapping,
+    PortMappingManager, Protocol, RootlessPortForward,
 };
 use crate::proto::runtime::v1::{LinuxContainerResources, NamespaceOption};
 use crate::runtime::{
@@ -160,6 +160,8 @@ pub struct PodSandboxManager<R: ContainerRuntime, N: NetworkManager = DefaultNet
     root_dir: PathBuf,
     /// 受管 netns 的生命周期管理器。
     namespace_manager: NamespaceManager,
+    /// 预热 netns 池；容量为 0 时关闭。
+    netns_pool: NetnsPool,
     /// 默认pause镜像（CRI-O风格：由运行时配置提供）
     pause_image: String,
     /// pause 镜像内的 infra 命令路径。
@@ -186,7 +188,7 @@ struct PodSandboxRollbackContext<'a> {
     pause_container_id: Option<&'a str>,
     network_attempted: bool,
     netns_created: bool,
-    pod_ip: Option<&'a str>,
+    pod_ips: &'a [IpAddr],
 }
 
 impl<R: ContainerRuntime, N: NetworkManager> std::fmt::Debug for PodSandboxManager<R, N> {
@@ -213,6 +215,7 @@ impl<R: ContainerRuntime> PodSandboxManager<R, DefaultNetworkManager> {
     ) -> Self {
         let disable_hostport_mapping = cni_config.disable_hostport_mapping();
         let namespace_manager = cni_config.namespace_manager();
+        let netns_pool_size = cni_config.netns_pool_size();
         let mut manager = Self::with_network_manager(
             runtime,
             DefaultNetworkManager::from_cni_config(cni_config),
@@ -225,6 +228,9 @@ impl<R: ContainerRuntime> PodSandboxManager<R, DefaultNetworkManager> {
                 infra_ctr_cpuset,
             },
         );
+        manager
+            .netns_pool
+            .configure(namespace_manager.clone(), netns_pool_size);
         manager.namespace_manager = namespace_manager;
         manager
     }
@@ -233,6 +239,8 @@ impl<R: ContainerRuntime> PodSandboxManager<R, DefaultNetworkManager> {
         self.pause_image = pause_image;
         self.disable_hostport_mapping = cni_config.disable_hostport_mapping();
         self.namespace_manager = cni_config.namespace_manager();
+        self.netns_pool
+            .configure(self.namespace_manager.clone(), cni_config.netns_pool_size());
         self.network_manager = DefaultNetworkManager::from_cni_config(cni_config);
     }
 }
@@ -407,6 +415,7 @@ impl<R: ContainerRuntime, N: NetworkManager> PodSandboxManager<R, N> {
             disable_hostport_mapping,
             root_dir,
             namespace_manager: NamespaceManager::new(PathBuf::from("/var/run/netns")),
+            netns_pool: NetnsPool::default(),
             pause_image,
             pause_command,
             infra_ctr_cpuset,
@@ -414,19 +423,7 @@ impl<R: ContainerRuntime, N: NetworkManager> PodSandboxManager<R, N> {
         }
     }
 
-    fn host_port_mappings(
-        &self,
-        pod: &PodSandboxConfig,
-        pod_ip: &str,
-    ) -> Result<Vec<HostPortMapping>> {
-        if pod_ip.trim().is_empty() || !Self::pod_requires_managed_netns(pod) {
-            return Ok(Vec::new());
-        }
-
-        let container_ip: IpAddr = pod_ip
-            .parse()
-            .with_context(|| format!("invalid pod IP for port mappings: {pod_ip}"))?;
-
+    fn requested_host_ports(pod: &PodSandboxConfig) -> Result<Vec<HostPort>> {
         pod.port_mappings
             .iter()
             .filter(|mapping| mapping.host_port > 0 && mapping.container_port > 0)
@@ -449,23 +446,92 @@ impl<R: ContainerRuntime, N: NetworkManager> PodSandboxManager<R, N> {
                             .with_context(|| format!("invalid host IP {}", mapping.host_ip))?,
                     )
                 };
-                Ok(HostPortMapping {
+                Ok(HostPort {
                     protocol,
-                    container_port: u16::try_from(mapping.container_port)
-                        .context("container_port out of range")?,
+                    host_ip,
                     host_port: u16::try_from(mapping.host_port)
                         .context("host_port out of range")?,
-                    host_ip,
-                    container_ip,
+                    container_port: u16::try_from(mapping.container_port)
+                        .context("container_port out of range")?,
                 })
             })
             .collect()
     }
 
+    /// hostPort 映射的目标 Pod IP：主 IP 加上另一地址族的第一个附加 IP（双栈）。
+    fn port_mapping_ips(network_status: Option<&NetworkStatus>, pod_ip: &str) -> Vec<IpAddr> {
+        let primary = network_status
+            .and_then(|status| status.ip)
+            .or_else(|| pod_ip.trim().parse().ok());
+        let Some(primary) = primary else {
+            return Vec::new();
+        };
+        let secondary = network_status.and_then(|status| {
+            status
+                .interfaces
+                .iter()
+                .filter_map(|interface| interface.ip)
+                .find(|ip| ip.is_ipv6() != primary.is_ipv6())
+        });
+        std::iter::once(primary).chain(secondary).collect()
+    }
+
+    fn host_port_mappings(
+        &self,
+        pod: &PodSandboxConfig,
+        pod_ips: &[IpAddr],
+    ) -> Result<Vec<HostPortMapping>> {
+        if pod_ips.is_empty()
+            || !Self::pod_requires_managed_netns(pod)
+            || self.network_manager.manages_host_ports()
+        {
+            return Ok(Vec::new());
+        }
+
+        let ports = Self::requested_host_ports(pod)?;
+        Ok(pod_ips
+            .iter()
+            .flat_map(|container_ip| {
+                ports
+                    .iter()
+                    // 指定了 hostIP 的端口只映射到同一地址族的 Pod IP
+                    .filter(|port| {
+                        port.host_ip
+                            .is_none_or(|host_ip| host_ip.is_ipv6() == container_ip.is_ipv6())
+                    })
+                    .map(|port| HostPortMapping {
+                        protocol: port.protocol,
+                        container_port: port.container_port,
+                        host_port: port.host_port,
+                        host_ip: port.host_ip,
+                        container_ip: *container_ip,
+                    })
+            })
+            .collect())
+    }
+
+    /// 由网络 helper 自行转发的 hostPort（rootless 模式）
+    fn helper_host_ports(&self, pod: &PodSandboxConfig) -> Result<Vec<HostPort>> {
+        if self.disable_hostport_mapping || !self.network_manager.manages_host_ports() {
+            return Ok(Vec::new());
+        }
+        Self::requested_host_ports(pod)
+    }
+
+    /// 当前生效的 rootless hostPort 转发
+    pub fn rootless_port_forwards(&self) -> Vec<RootlessPortForward> {
+        self.network_manager.rootless_port_forwards()
+    }
+
+    /// 预热 netns 池句柄，供 metrics 读取占用情况。
+    pub fn netns_pool(&self) -> NetnsPool {
+        self.netns_pool.clone()
+    }
+
     fn apply_port_mappings(
         &self,
         pod: &PodSandboxConfig,
-        pod_ip: &str,
+        pod_ips: &[IpAddr],
     ) -> Result<Vec<HostPortMapping>> {
         if self.disable_hostport_mapping {
             debug!(
@@ -474,7 +540,7 @@ impl<R: ContainerRuntime, N: NetworkManager> PodSandboxManager<R, N> {
             );
             return Ok(Vec::new());
         }
-        let mappings = self.host_port_mappings(pod, pod_ip)?;
+        let mappings = self.host_port_mappings(pod, pod_ips)?;
         let mut applied = Vec::new();
         for mapping in mappings {
             if let Err(err) = self.port_mapper.add_port_mapping(&mapping) {
@@ -489,7 +555,7 @@ impl<R: ContainerRuntime, N: NetworkManager> PodSandboxManager<R, N> {
         Ok(applied)
     }
 
-    fn remove_port_mappings(&self, pod: &PodSandboxConfig, pod_ip: &str) {
+    fn remove_port_mappings(&self, pod: &PodSandboxConfig, pod_ips: &[IpAddr]) {
         if self.disable_hostport_mapping {
             debug!(
                 "hostPort mapping is disabled; skipping cleanup for pod {}",
@@ -497,7 +563,7 @@ impl<R: ContainerRuntime, N: NetworkManager> PodSandboxManager<R, N> {
             );
             return;
         }
-        match self.host_port_mappings(pod, pod_ip) {
+        match self.host_port_mappings(pod, pod_ips) {
             Ok(mappings) => {
                 for mapping in mappings.iter().rev() {
                     if let Err(err) = self.port_mapper.remove_port_mapping(mapping) {
@@ -514,6 +580,10 @@ impl<R: ContainerRuntime, N: NetworkManager> PodSandboxManager<R, N> {
             debug!("hostPort mapping is disabled; skipping rebuild");
             return;
         }
+        if self.network_manager.manages_host_ports() {
+            debug!("hostPorts are forwarded by the rootless network helper; skipping rebuild");
+            return;
+        }
         if let Err(err) = self.port_mapper.cleanup_all() {
             debug!(
                 "Failed to cleanup existing hostPort rules before rebuild: {}",
@@ -522,7 +592,8 @@ impl<R: ContainerRuntime, N: NetworkManager> PodSandboxManager<R, N> {
         }
 
         for pod in self.pods.values() {
-            if let Err(err) = self.apply_port_mappings(&pod.config, &pod.ip) {
+            let pod_ips = Self::port_mapping_ips(pod.network_status.as_ref(), &pod.ip);
+            if let Err(err) = self.apply_port_mappings(&pod.config, &pod_ips) {
                 debug!(
                     "Failed to rebuild hostPort mappings for pod {}: {}",
                     pod.id, err
@@ -544,7 +615,7 @@ impl<R: ContainerRuntime, N: NetworkManager> PodSandboxManager<R, N> {
             pause_container_id,
             network_attempted,
             netns_created,
-            pod_ip,
+            pod_ips,
         } = rollback;
         if let Some(pause_container_id) = pause_container_id {
             if let Err(err) = self.runtime.stop_container(pause_container_id, None) {
@@ -561,9 +632,7 @@ impl<R: ContainerRuntime, N: NetworkManager> PodSandboxManager<R, N> {
             }
         }
 
-        if let Some(pod_ip) = pod_ip {
-            self.remove_port_mappings(config, pod_ip);
-        }
+        self.remove_port_mappings(config, pod_ips);
 
         if network_attempted {
             if let Err(err) = self
@@ -643,7 +712,7 @@ impl<R: ContainerRuntime, N: NetworkManager> PodSandboxManager<R, N> {
                     pause_container_id: None,
                     network_attempted,
                     netns_created,
-                    pod_ip: None,
+                    pod_ips: &[],
                 },
                 &config,
             )
@@ -662,12 +731,22 @@ impl<R: ContainerRuntime, N: NetworkManager> PodSandboxManager<R, N> {
 
         let network_status = if managed_netns {
             debug!("Creating network namespace: {}", netns_name);
-            if let Err(err) = self
-                .network_manager
-                .create_network_namespace(&netns_name)
-                .await
-                .context("Failed to create network namespace")
-            {
+            let pooled = match self.netns_pool.checkout(&netns_path).await {
+                Ok(pooled) => pooled,
+                Err(err) => {
+                    warn!("Failed to take network namespace from pool: {}", err);
+                    false
+                }
+            };
+            let created = if pooled {
+                debug!("Using pre-warmed network namespace for {}", netns_name);
+                Ok(())
+            } else {
+                self.network_manager
+                    .create_network_namespace(&netns_name)
+                    .await
+            };
+            if let Err(err) = created.context("Failed to create network namespace") {
                 self.rollback_create_pod_sandbox(
                     PodSandboxRollbackContext {
                         pod_id: &pod_id,
@@ -677,7 +756,7 @@ impl<R: ContainerRuntime, N: NetworkManager> PodSandboxManager<R, N> {
                         pause_container_id: None,
                         network_attempted,
                         netns_created,
-                        pod_ip: None,
+                        pod_ips: &[],
                     },
                     &config,
                 )
@@ -686,6 +765,27 @@ impl<R: ContainerRuntime, N: NetworkManager> PodSandboxManager<R, N> {
             }
             netns_created = true;
 
+            let helper_host_ports = match self.helper_host_ports(&config) {
+                Ok(ports) => ports,
+                Err(err) => {
+                    self.rollback_create_pod_sandbox(
+                        PodSandboxRollbackContext {
+                            pod_id: &pod_id,
+                            pod_dir: &pod_dir,
+                            netns_name: &netns_name,
+                            netns_path: &netns_path,
+                            pause_container_id: None,
+                            network_attempted,
+                            netns_created,
+                            pod_ips: &[],
+                        },
+                        &config,
+                    )
+                    .await;
+                    return Err(err).context("Failed to build pod hostPort forwards");
+                }
+            };
+
             debug!("Setting up pod network for {}", pod_id);
             network_attempted = true;
             let mut network_status = match self
@@ -701,6 +801,7 @@ impl<R: ContainerRuntime, N: NetworkManager> PodSandboxManager<R, N> {
                         .network_config
                         .as_ref()
                         .map(|network| network.pod_cidr.as_str()),
+                    host_ports: &helper_host_ports,
                 })
                 .await
             {
@@ -715,7 +816,7 @@ impl<R: ContainerRuntime, N: NetworkManager> PodSandboxManager<R, N> {
                             pause_container_id: None,
                             network_attempted,
                             netns_created,
-                            pod_ip: None,
+                            pod_ips: &[],
                         },
                         &config,
                     )
@@ -754,11 +855,7 @@ impl<R: ContainerRuntime, N: NetworkManager> PodSandboxManager<R, N> {
                         pause_container_id: None,
                         network_attempted,
                         netns_created,
-                        pod_ip: network_status
-                            .as_ref()
-                            .and_then(|status| status.ip.as_ref())
-                            .map(|ip| ip.to_string())
-                            .as_deref(),
+                        pod_ips: &Self::port_mapping_ips(network_status.as_ref(), ""),
                     },
                     &config,
                 )
@@ -779,7 +876,8 @@ impl<R: ContainerRuntime, N: NetworkManager> PodSandboxManager<R, N> {
             .and_then(|status| status.ip.as_ref())
             .map(|ip| ip.to_string())
             .unwrap_or_default();
-        if let Err(err) = self.apply_port_mappings(&config, &pod_ip) {
+        let pod_ips = Self::port_mapping_ips(network_status.as_ref(), &pod_ip);
+        if let Err(err) = self.apply_port_mappings(&config, &pod_ips) {
             self.rollback_create_pod_sandbox(
                 PodSandboxRollbackContext {
                     pod_id: &pod_id,
@@ -789,7 +887,7 @@ impl<R: ContainerRuntime, N: NetworkManager> PodSandboxManager<R, N> {
                     pause_container_id: Some(&created_pause_container_id),
                     network_attempted,
                     netns_created,
-                    pod_ip: Some(&pod_ip),
+                    pod_ips: &pod_ips,
                 },
                 &config,
             )
@@ -1017,7 +1115,8 @@ impl<R: ContainerRuntime, N: NetworkManager> PodSandboxManager<R, N> {
             let _ = self.runtime.stop_container(&pod.pause_container_id, None);
             let _ = self.runtime.remove_container(&pod.pause_container_id);
 
-            self.remove_port_mappings(&pod.config, &pod.ip);
+            let pod_ips = Self::port_mapping_ips(pod.network_status.as_ref(), &pod.ip);
+            self.remove_port_mappings(&pod.config, &pod_ips);
 
             // 2. 清理网络
             debug!("Tearing down pod network for {}", pod_id);
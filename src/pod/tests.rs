@@ -64,6 +64,7 @@ impl ContainerRuntime for RecordingRuntime {
         _container_id: &str,
         _command: &[String],
         _tty: bool,
+        _overrides: &crate::security::spec_patch::ExecOverrides,
     ) -> Result<i32> {
         Ok(0)
     }
@@ -82,6 +83,9 @@ struct RecordingNetworkManager {
     calls: Arc<Mutex<Vec<String>>>,
     fail_setup: Arc<Mutex<bool>>,
     fail_teardown: Arc<Mutex<bool>>,
+    manages_host_ports: bool,
+    host_ports: Arc<Mutex<Vec<HostPort>>>,
+    secondary_ip: Option<std::net::IpAddr>,
 }
 
 impl RecordingNetworkManager {
@@ -134,6 +138,10 @@ impl NetworkManager for RecordingNetworkManager {
             .lock()
             .unwrap()
             .push(format!("setup_network:{}", request.pod_id));
+        self.host_ports
+            .lock()
+            .unwrap()
+            .extend_from_slice(request.host_ports);
         if *self.fail_setup.lock().unwrap() {
             return Err(NetworkError::Other("setup failed".to_string()));
         }
@@ -141,7 +149,17 @@ impl NetworkManager for RecordingNetworkManager {
             name: "test".to_string(),
             ip: Some("10.88.0.2".parse().unwrap()),
             mac: None,
-            interfaces: vec![],
+            interfaces: self
+                .secondary_ip
+                .map(|ip| NetworkInterface {
+                    name: "additional0".to_string(),
+                    ip: Some(ip),
+                    mac: None,
+                    netmask: None,
+                    gateway: None,
+                })
+                .into_iter()
+                .collect(),
             raw_result: None,
         })
     }
@@ -164,6 +182,10 @@ impl NetworkManager for RecordingNetworkManager {
         }
         Ok(())
     }
+
+    fn manages_host_ports(&self) -> bool {
+        self.manages_host_ports
+    }
 }
 
 #[derive(Clone, Default)]
@@ -784,6 +806,95 @@ async fn create_pod_sandbox_applies_port_mappings_and_stop_removes_them() {
     assert_eq!(removed[0].host_port, 8080);
 }
 
+#[tokio::test]
+async fn create_pod_sandbox_maps_host_ports_for_each_ip_family() {
+    let temp_dir = tempdir().unwrap();
+    let runtime = RecordingRuntime::default();
+    let network = RecordingNetworkManager {
+        secondary_ip: Some("fd00::2".parse().unwrap()),
+        ..Default::default()
+    };
+    let port_mapper = RecordingPortMapper::default();
+    let mut manager = PodSandboxManager::with_network_manager(
+        runtime,
+        network,
+        Box::new(port_mapper.clone()),
+        test_manager_options(temp_dir.path().join("pods")),
+    );
+
+    let mut config = test_pod_config();
+    config.port_mappings = vec![
+        PortMapping {
+            protocol: "TCP".to_string(),
+            container_port: 80,
+            host_port: 8080,
+            host_ip: String::new(),
+        },
+        PortMapping {
+            protocol: "TCP".to_string(),
+            container_port: 443,
+            host_port: 8443,
+            host_ip: "::1".to_string(),
+        },
+    ];
+
+    let pod_id = manager.create_pod_sandbox(config).await.unwrap();
+    let added: Vec<(u16, String)> = port_mapper
+        .take_added()
+        .iter()
+        .map(|mapping| (mapping.host_port, mapping.container_ip.to_string()))
+        .collect();
+    assert_eq!(
+        added,
+        vec![
+            (8080, "10.88.0.2".to_string()),
+            (8080, "fd00::2".to_string()),
+            (8443, "fd00::2".to_string()),
+        ]
+    );
+
+    manager.stop_pod_sandbox(&pod_id).await.unwrap();
+    assert_eq!(port_mapper.take_removed().len(), 3);
+}
+
+#[tokio::test]
+async fn create_pod_sandbox_hands_host_ports_to_rootless_network_helper() {
+    let temp_dir = tempdir().unwrap();
+    let runtime = RecordingRuntime::default();
+    let network = RecordingNetworkManager {
+        manages_host_ports: true,
+        ..Default::default()
+    };
+    let port_mapper = RecordingPortMapper::default();
+    let mut manager = PodSandboxManager::with_network_manager(
+        runtime,
+        network.clone(),
+        Box::new(port_mapper.clone()),
+        test_manager_options(temp_dir.path().join("pods")),
+    );
+
+    let mut config = test_pod_config();
+    config.port_mappings = vec![PortMapping {
+        protocol: "UDP".to_string(),
+        container_port: 53,
+        host_port: 5353,
+        host_ip: String::new(),
+    }];
+
+    let pod_id = manager.create_pod_sandbox(config).await.unwrap();
+    let host_ports = network.host_ports.lock().unwrap().clone();
+    assert_eq!(host_ports.len(), 1);
+    assert_eq!(host_ports[0].protocol, Protocol::Udp);
+    assert_eq!(host_ports[0].host_port, 5353);
+    assert_eq!(host_ports[0].container_port, 53);
+    assert!(port_mapper.take_added().is_empty());
+
+    manager.stop_pod_sandbox(&pod_id).await.unwrap();
+    manager.rebuild_port_mappings();
+    assert!(port_mapper.take_removed().is_empty());
+    assert_eq!(port_mapper.cleanup_calls(), 0);
+}
+
 #[tokio::test]
 async fn create_pod_sandbox_accepts_sctp_port_mappings() {
     let temp_dir = tempdir().unwrap();
@@ -5,6 +5,7 @@ use anyhow::Result;
 use crate::config::CgroupDriverConfig;
 use crate::oci::spec::Spec;
 use crate::proto::runtime::v1::LinuxContainerResources;
+use crate::security::spec_patch::ExecOverrides;
 use crate::shim_rpc::OpenAttachStreamResponse;
 
 use super::{
@@ -24,7 +25,13 @@ pub trait TaskController: Send + Sync {
     fn remove_container(&self, container_id: &str) -> Result<()>;
     fn container_status(&self, container_id: &str) -> Result<ContainerStatus>;
     fn reopen_container_log(&self, container_id: &str) -> Result<()>;
-    fn exec_in_container(&self, container_id: &str, command: &[String], tty: bool) -> Result<i32>;
+    fn exec_in_container(
+        &self,
+        container_id: &str,
+        command: &[String],
+        tty: bool,
+        overrides: &ExecOverrides,
+    ) -> Result<i32>;
     fn update_container_resources(
         &self,
         container_id: &str,
@@ -0,0 +1,1384 @@
+//! 容器文件复制
+//!
+//! crius 直接读写容器根文件系统，不依赖镜像内的 tar（distroless 镜像也能复制）：
+//! - 容器根通过 `/proc/<pid>/root` 打开，该魔术链接指向容器进程挂载命名空间的根，
+//!   之后的遍历会经过该命名空间里的全部挂载（包括卷）；多线程的 crius 进程无法
+//!   `setns(CLONE_NEWNS)`，因此以这个目录 fd 作为入口
+//! - 每一级路径都以 `O_NOFOLLOW` 打开，符号链接在根内展开，`..` 与绝对链接都被钳制
+//!   在根目录，不会逃逸到宿主机
+//! - 归档格式为 ustar + PAX 扩展头；属主按容器的 uid_map/gid_map 在宿主视角与容器
+//!   视角之间换算，权限位（含 setuid/setgid/sticky）与修改时间原样保留
+
+use std::collections::{HashMap, VecDeque};
+use std::ffi::{OsStr, OsString};
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
+use std::path::Path;
+
+use nix::dir::Dir;
+use nix::errno::Errno;
+use nix::fcntl::{openat, readlinkat, AtFlags, OFlag};
+use nix::sys::stat::{
+    fchmod, fstat, fstatat, futimens, major, makedev, minor, mkdirat, mknodat, utimensat, FileStat,
+    Mode, SFlag, UtimensatFlags,
+};
+use nix::sys::time::TimeSpec;
+use nix::unistd::{
+    fchown, fchownat, linkat, symlinkat, unlinkat, FchownatFlags, Gid, LinkatFlags, Uid,
+    UnlinkatFlags,
+};
+
+const BLOCK_SIZE: usize = 512;
+/// 路径解析时最多跟随的符号链接数，与内核 MAXSYMLINKS 一致。
+const MAX_SYMLINK_FOLLOWS: usize = 40;
+/// 宿主 id 在容器内没有映射时，归档里记为 overflow id。
+const OVERFLOW_ID: u32 = 65534;
+const USTAR_MAX_NAME: usize = 100;
+const USTAR_MAX_SIZE: u64 = 0o777_7777_7777;
+const USTAR_MAX_ID: u32 = 0o777_7777;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ContainerCopyError {
+    #[error("{0}")]
+    NotFound(String),
+    #[error("{0}")]
+    InvalidArgument(String),
+    #[error("{0}")]
+    FailedPrecondition(String),
+    #[error("invalid archive: {0}")]
+    Archive(String),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+impl From<Errno> for ContainerCopyError {
+    fn from(err: Errno) -> Self {
+        Self::Io(err.into())
+    }
+}
+
+pub type CopyResult<T> = std::result::Result<T, ContainerCopyError>;
+
+/// 一次归档或解包处理的条目数与普通文件字节数。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CopySummary {
+    pub entries: u64,
+    pub bytes: u64,
+}
+
+/// `/proc/<pid>/{uid_map,gid_map}` 描述的 id 映射。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IdMap {
+    ranges: Vec<IdMapRange>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct IdMapRange {
+    inside: u32,
+    outside: u32,
+    count: u32,
+}
+
+impl IdMap {
+    pub fn identity() -> Self {
+        Self {
+            ranges: vec![IdMapRange {
+                inside: 0,
+                outside: 0,
+                count: u32::MAX,
+            }],
+        }
+    }
+
+    pub fn parse(raw: &str) -> CopyResult<Self> {
+        let mut ranges = Vec::new();
+        for line in raw.lines() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.is_empty() {
+                continue;
+            }
+            let parsed = (fields.len() == 3).then(|| {
+                (
+                    fields[0].parse::<u32>(),
+                    fields[1].parse::<u32>(),
+                    fields[2].parse::<u32>(),
+                )
+            });
+            let Some((Ok(inside), Ok(outside), Ok(count))) = parsed else {
+                return Err(ContainerCopyError::InvalidArgument(format!(
+                    "malformed id map line {:?}",
+                    line
+                )));
+            };
+            ranges.push(IdMapRange {
+                inside,
+                outside,
+                count,
+            });
+        }
+        Ok(Self { ranges })
+    }
+
+    /// 宿主 id 换算为容器内 id。
+    pub fn to_container(&self, host: u32) -> Option<u32> {
+        self.ranges.iter().find_map(|range| {
+            host.checked_sub(range.outside)
+                .filter(|offset| *offset < range.count)
+                .map(|offset| range.inside + offset)
+        })
+    }
+
+    /// 容器内 id 换算为宿主 id。
+    pub fn to_host(&self, container: u32) -> Option<u32> {
+        self.ranges.iter().find_map(|range| {
+            container
+                .checked_sub(range.inside)
+                .filter(|offset| *offset < range.count)
+                .map(|offset| range.outside + offset)
+        })
+    }
+}
+
+/// 复制操作的根目录：所有路径都在它之内解析。
+#[derive(Debug)]
+pub struct CopyRoot {
+    root: OwnedFd,
+    uid_map: IdMap,
+    gid_map: IdMap,
+    preserve_ownership: bool,
+}
+
+impl CopyRoot {
+    /// 以容器 init 进程的挂载命名空间根为复制根。
+    pub fn for_container_pid(pid: i32) -> CopyResult<Self> {
+        let proc_dir = format!("/proc/{}", pid);
+        let root = open_directory_path(Path::new(&format!("{}/root", proc_dir))).map_err(
+            |err| match err {
+                Errno::ENOENT | Errno::ESRCH => ContainerCopyError::FailedPrecondition(format!(
+                    "container process {} is no longer running",
+                    pid
+                )),
+                other => other.into(),
+            },
+        )?;
+        let uid_map = IdMap::parse(&std::fs::read_to_string(format!("{}/uid_map", proc_dir))?)?;
+        let gid_map = IdMap::parse(&std::fs::read_to_string(format!("{}/gid_map", proc_dir))?)?;
+        Ok(Self {
+            root,
+            uid_map,
+            gid_map,
+            preserve_ownership: nix::unistd::geteuid().is_root(),
+        })
+    }
+
+    /// 以本地目录为复制根，id 不做换算；与 tar 一致，只有 root 才恢复属主。
+    pub fn for_directory(path: &Path) -> CopyResult<Self> {
+        let root = open_directory_path(path).map_err(|err| match err {
+            Errno::ENOENT => {
+                ContainerCopyError::NotFound(format!("{} does not exist", path.display()))
+            }
+            other => other.into(),
+        })?;
+        Ok(Self {
+            root,
+            uid_map: IdMap::identity(),
+            gid_map: IdMap::identity(),
+            preserve_ownership: nix::unistd::geteuid().is_root(),
+        })
+    }
+
+    /// 把 `path` 打包写入 `writer`。
+    ///
+    /// 归档顶层条目以源路径的最后一级命名；路径以 `/.` 结尾（或是根目录）时只打包目录内容。
+    /// 末级符号链接按链接本身打包，除非路径以 `/` 结尾。
+    pub fn archive<W: Write>(&self, path: &str, writer: W) -> CopyResult<CopySummary> {
+        let trimmed = path.trim_end_matches('/');
+        let contents_only = trimmed.is_empty() || trimmed == "." || trimmed.ends_with("/.");
+        let components = self.resolve(path.as_bytes(), path.ends_with('/') || contents_only)?;
+        let mut tar = TarWriter::new(writer);
+        let mut links = HashMap::new();
+        match components.split_last() {
+            Some((name, parents)) if !contents_only => {
+                let parent = self.open_dir(parents)?;
+                self.archive_node(
+                    &mut tar,
+                    parent.as_raw_fd(),
+                    name,
+                    name.as_bytes().to_vec(),
+                    &mut links,
+                )
+                .map_err(|err| not_found_as(err, path))?;
+            }
+            _ => {
+                let dir = self.open_dir(&components)?;
+                self.archive_node(
+                    &mut tar,
+                    dir.as_raw_fd(),
+                    OsStr::new("."),
+                    b".".to_vec(),
+                    &mut links,
+                )?;
+            }
+        }
+        Ok(tar.finish()?)
+    }
+
+    /// 从 `reader` 读取归档并解包到 `path`。
+    ///
+    /// `path` 是已存在的目录时，条目按原名解包到其中；否则其父目录必须存在，
+    /// 归档唯一的顶层条目被重命名为 `path` 的最后一级（与 `cp` 的语义一致）。
+    pub fn extract<R: Read>(&self, path: &str, reader: R) -> CopyResult<CopySummary> {
+        let destination = self.resolve(path.as_bytes(), true)?;
+        let is_dir = self
+            .stat_components(&destination)?
+            .is_some_and(|stat| file_kind(&stat) == SFlag::S_IFDIR);
+        let (target, rename) = match destination.split_last() {
+            Some((name, parents)) if !is_dir => (parents.to_vec(), Some(name.clone())),
+            _ => (destination, None),
+        };
+        if rename.is_some() {
+            self.open_dir(&target)
+                .map_err(|err| not_found_as(err, path))?;
+        }
+
+        let mut tar = TarReader::new(reader);
+        let mut top_level: Option<OsString> = None;
+        let mut directories = Vec::new();
+        let mut summary = CopySummary::default();
+        while let Some(header) = tar.next_header()? {
+            let mut components = archive_components(&header.path)?;
+            if let Some(rename) = &rename {
+                rename_top_level(&mut components, rename, &mut top_level, &header.path)?;
+            } else if components.is_empty() {
+                // `.` 条目即目标目录本身，保留其现有属性。
+                continue;
+            }
+            let (name, parents) = components
+                .split_last()
+                .map(|(name, parents)| (name.clone(), parents.to_vec()))
+                .unwrap_or_default();
+            let (resolved_parent, parent) = self.open_parent(&target, &parents)?;
+            let parent_fd = parent.as_raw_fd();
+            let existing = match fstatat(parent_fd, name.as_os_str(), AtFlags::AT_SYMLINK_NOFOLLOW)
+            {
+                Ok(stat) => Some(stat),
+                Err(Errno::ENOENT) => None,
+                Err(err) => return Err(err.into()),
+            };
+
+            if header.kind == EntryKind::Directory {
+                match existing {
+                    Some(stat) if file_kind(&stat) == SFlag::S_IFDIR => {}
+                    Some(_) => {
+                        unlinkat(
+                            Some(parent_fd),
+                            name.as_os_str(),
+                            UnlinkatFlags::NoRemoveDir,
+                        )?;
+                        mkdirat(parent_fd, name.as_os_str(), Mode::S_IRWXU)?;
+                    }
+                    None => mkdirat(parent_fd, name.as_os_str(), Mode::S_IRWXU)?,
+                }
+                // 目录属性在所有条目写完后再设置，避免只读目录挡住子条目。
+                directories.push((resolved_parent, name, header));
+                summary.entries += 1;
+                continue;
+            }
+
+            if let Some(stat) = existing {
+                if file_kind(&stat) == SFlag::S_IFDIR {
+                    return Err(ContainerCopyError::InvalidArgument(format!(
+                        "cannot overwrite directory {} with a non-directory",
+                        String::from_utf8_lossy(&header.path)
+                    )));
+                }
+                unlinkat(
+                    Some(parent_fd),
+                    name.as_os_str(),
+                    UnlinkatFlags::NoRemoveDir,
+                )?;
+            }
+
+            match header.kind {
+                EntryKind::Regular => {
+                    let fd = openat(
+                        parent_fd,
+                        name.as_os_str(),
+                        OFlag::O_WRONLY
+                            | OFlag::O_CREAT
+                            | OFlag::O_EXCL
+                            | OFlag::O_NOFOLLOW
+                            | OFlag::O_CLOEXEC,
+                        Mode::S_IRUSR | Mode::S_IWUSR,
+                    )?;
+                    let mut file = unsafe { File::from_raw_fd(fd) };
+                    summary.bytes += tar.copy_data(&mut file)?;
+                    // 先 chown 再 chmod：chown 会清掉 setuid/setgid 位。
+                    self.apply_file_metadata(file.as_raw_fd(), &header)?;
+                }
+                EntryKind::Symlink => {
+                    symlinkat(
+                        OsStr::from_bytes(&header.link_name),
+                        Some(parent_fd),
+                        name.as_os_str(),
+                    )?;
+                    self.apply_link_metadata(parent_fd, &name, &header)?;
+                }
+                EntryKind::HardLink => {
+                    let mut link_components = archive_components(&header.link_name)?;
+                    if let Some(rename) = &rename {
+                        rename_top_level(
+                            &mut link_components,
+                            rename,
+                            &mut top_level,
+                            &header.link_name,
+                        )?;
+                    }
+                    let Some((link_name, link_parents)) = link_components.split_last() else {
+                        return Err(ContainerCopyError::Archive(format!(
+                            "hard link {} has an empty target",
+                            String::from_utf8_lossy(&header.path)
+                        )));
+                    };
+                    let (_, link_parent) = self.open_parent(&target, link_parents)?;
+                    linkat(
+                        Some(link_parent.as_raw_fd()),
+                        link_name.as_os_str(),
+                        Some(parent_fd),
+                        name.as_os_str(),
+                        LinkatFlags::NoSymlinkFollow,
+                    )?;
+                }
+                EntryKind::CharDevice | EntryKind::BlockDevice | EntryKind::Fifo => {
+                    let kind = match header.kind {
+                        EntryKind::CharDevice => SFlag::S_IFCHR,
+                        EntryKind::BlockDevice => SFlag::S_IFBLK,
+                        _ => SFlag::S_IFIFO,
+                    };
+                    mknodat(
+                        parent_fd,
+                        name.as_os_str(),
+                        kind,
+                        Mode::from_bits_truncate(header.mode),
+                        makedev(header.device.0, header.device.1),
+                    )?;
+                    self.apply_link_metadata(parent_fd, &name, &header)?;
+                }
+                EntryKind::Directory | EntryKind::PaxExtended => {}
+            }
+            summary.entries += 1;
+        }
+
+        for (parents, name, header) in directories.iter().rev() {
+            let parent = self.open_dir(parents)?;
+            let fd = openat(
+                parent.as_raw_fd(),
+                name.as_os_str(),
+                OFlag::O_RDONLY | OFlag::O_DIRECTORY | OFlag::O_NOFOLLOW | OFlag::O_CLOEXEC,
+                Mode::empty(),
+            )?;
+            let dir = unsafe { OwnedFd::from_raw_fd(fd) };
+            self.apply_file_metadata(dir.as_raw_fd(), header)?;
+        }
+        Ok(summary)
+    }
+
+    /// 在根内解析路径，返回不含 `.`、`..` 与符号链接的组件列表。
+    ///
+    /// 末级不存在时仍返回（供创建目标使用）；`follow_last` 为 false 时保留末级符号链接本身。
+    fn resolve(&self, path: &[u8], follow_last: bool) -> CopyResult<Vec<OsString>> {
+        let mut pending: VecDeque<OsString> = split_components(path).collect();
+        let mut resolved: Vec<OsString> = Vec::new();
+        let mut follows = 0;
+        while let Some(component) = pending.pop_front() {
+            if component == ".." {
+                resolved.pop();
+                continue;
+            }
+            let last = pending.is_empty();
+            let parent = self.open_dir(&resolved)?;
+            let stat = match fstatat(
+                parent.as_raw_fd(),
+                component.as_os_str(),
+                AtFlags::AT_SYMLINK_NOFOLLOW,
+            ) {
+                Ok(stat) => stat,
+                Err(Errno::ENOENT) if last => {
+                    resolved.push(component);
+                    break;
+                }
+                Err(Errno::ENOENT) => {
+                    return Err(ContainerCopyError::NotFound(format!(
+                        "{} does not exist",
+                        String::from_utf8_lossy(path)
+                    )))
+                }
+                Err(err) => return Err(err.into()),
+            };
+            if file_kind(&stat) == SFlag::S_IFLNK && (!last || follow_last) {
+                follows += 1;
+                if follows > MAX_SYMLINK_FOLLOWS {
+                    return Err(ContainerCopyError::InvalidArgument(format!(
+                        "too many levels of symbolic links resolving {}",
+                        String::from_utf8_lossy(path)
+                    )));
+                }
+                let target = readlinkat(parent.as_raw_fd(), component.as_os_str())?;
+                // 绝对链接相对复制根解析，而不是宿主机根。
+                if target.as_bytes().starts_with(b"/") {
+                    resolved.clear();
+                }
+                for component in split_components(target.as_bytes())
+                    .collect::<Vec<_>>()
+                    .into_iter()
+                    .rev()
+                {
+                    pending.push_front(component);
+                }
+                continue;
+            }
+            resolved.push(component);
+        }
+        Ok(resolved)
+    }
+
+    /// 逐级以 `O_NOFOLLOW` 打开已解析的目录；中途被替换成符号链接会直接失败。
+    fn open_dir(&self, components: &[OsString]) -> CopyResult<OwnedFd> {
+        let mut current = self.root.try_clone()?;
+        for component in components {
+            let fd = openat(
+                current.as_raw_fd(),
+                component.as_os_str(),
+                OFlag::O_PATH | OFlag::O_DIRECTORY | OFlag::O_NOFOLLOW | OFlag::O_CLOEXEC,
+                Mode::empty(),
+            )?;
+            current = unsafe { OwnedFd::from_raw_fd(fd) };
+        }
+        Ok(current)
+    }
+
+    /// 打开（必要时创建）`base` 下的父目录，返回解析后的组件与目录 fd。
+    fn open_parent(
+        &self,
+        base: &[OsString],
+        components: &[OsString],
+    ) -> CopyResult<(Vec<OsString>, OwnedFd)> {
+        let mut resolved = base.to_vec();
+        for component in components {
+            resolved.push(component.clone());
+            resolved = self.resolve_components(&resolved)?;
+            if let Some((name, parents)) = resolved.split_last() {
+                let parent = self.open_dir(parents)?;
+                match mkdirat(
+                    parent.as_raw_fd(),
+                    name.as_os_str(),
+                    Mode::from_bits_truncate(0o755),
+                ) {
+                    Ok(()) | Err(Errno::EEXIST) => {}
+                    Err(err) => return Err(err.into()),
+                }
+            }
+        }
+        let dir = self.open_dir(&resolved)?;
+        Ok((resolved, dir))
+    }
+
+    fn resolve_components(&self, components: &[OsString]) -> CopyResult<Vec<OsString>> {
+        let mut path = Vec::new();
+        for component in components {
+            path.push(b'/');
+            path.extend_from_slice(component.as_bytes());
+        }
+        self.resolve(&path, true)
+    }
+
+    fn stat_components(&self, components: &[OsString]) -> CopyResult<Option<FileStat>> {
+        let Some((name, parents)) = components.split_last() else {
+            return Ok(Some(fstat(self.root.as_raw_fd())?));
+        };
+        let parent = self.open_dir(parents)?;
+        match fstatat(
+            parent.as_raw_fd(),
+            name.as_os_str(),
+            AtFlags::AT_SYMLINK_NOFOLLOW,
+        ) {
+            Ok(stat) => Ok(Some(stat)),
+            Err(Errno::ENOENT) => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn archive_node<W: Write>(
+        &self,
+        tar: &mut TarWriter<W>,
+        parent: RawFd,
+        name: &OsStr,
+        archive_name: Vec<u8>,
+        links: &mut HashMap<(u64, u64), Vec<u8>>,
+    ) -> CopyResult<()> {
+        let stat = fstatat(parent, name, AtFlags::AT_SYMLINK_NOFOLLOW)?;
+        let mut header = TarHeader {
+            path: archive_name,
+            link_name: Vec::new(),
+            kind: EntryKind::Regular,
+            mode: stat.st_mode & 0o7777,
+            uid: self
+                .uid_map
+                .to_container(stat.st_uid)
+                .unwrap_or(OVERFLOW_ID),
+            gid: self
+                .gid_map
+                .to_container(stat.st_gid)
+                .unwrap_or(OVERFLOW_ID),
+            size: 0,
+            mtime: stat.st_mtime.max(0) as u64,
+            device: (0, 0),
+        };
+        let kind = file_kind(&stat);
+        if kind == SFlag::S_IFDIR {
+            header.kind = EntryKind::Directory;
+            tar.append(&header, io::empty())?;
+            let mut dir = Dir::openat(
+                parent,
+                name,
+                OFlag::O_RDONLY | OFlag::O_DIRECTORY | OFlag::O_NOFOLLOW | OFlag::O_CLOEXEC,
+                Mode::empty(),
+            )?;
+            let mut children = Vec::new();
+            for entry in dir.iter() {
+                let entry = entry?;
+                let child = entry.file_name().to_bytes();
+                if child != b"." && child != b".." {
+                    children.push(OsStr::from_bytes(child).to_os_string());
+                }
+            }
+            children.sort();
+            for child in children {
+                let mut child_name = header.path.clone();
+                child_name.push(b'/');
+                child_name.extend_from_slice(child.as_bytes());
+                self.archive_node(tar, dir.as_raw_fd(), &child, child_name, links)?;
+            }
+            return Ok(());
+        }
+
+        if kind == SFlag::S_IFREG {
+            if stat.st_nlink > 1 {
+                let key = (stat.st_dev, stat.st_ino);
+                if let Some(target) = links.get(&key) {
+                    header.kind = EntryKind::HardLink;
+                    header.link_name = target.clone();
+                    return Ok(tar.append(&header, io::empty())?);
+                }
+                links.insert(key, header.path.clone());
+            }
+            let fd = openat(
+                parent,
+                name,
+                OFlag::O_RDONLY | OFlag::O_NOFOLLOW | OFlag::O_CLOEXEC,
+                Mode::empty(),
+            )?;
+            let file = unsafe { File::from_raw_fd(fd) };
+            header.size = stat.st_size.max(0) as u64;
+            return Ok(tar.append(&header, file)?);
+        }
+
+        header.kind = match kind {
+            SFlag::S_IFLNK => {
+                header.link_name = readlinkat(parent, name)?.into_vec();
+                EntryKind::Symlink
+            }
+            SFlag::S_IFCHR => EntryKind::CharDevice,
+            SFlag::S_IFBLK => EntryKind::BlockDevice,
+            SFlag::S_IFIFO => EntryKind::Fifo,
+            _ => {
+                log::debug!(
+                    "Skipping unsupported file {} while archiving",
+                    String::from_utf8_lossy(&header.path)
+                );
+                return Ok(());
+            }
+        };
+        if matches!(header.kind, EntryKind::CharDevice | EntryKind::BlockDevice) {
+            header.device = (major(stat.st_rdev), minor(stat.st_rdev));
+        }
+        Ok(tar.append(&header, io::empty())?)
+    }
+
+    fn host_ids(&self, header: &TarHeader) -> (Uid, Gid) {
+        // 容器内没有映射的 id 落到容器 root 对应的宿主 id 上，避免写出容器外的属主。
+        let uid = self
+            .uid_map
+            .to_host(header.uid)
+            .or_else(|| self.uid_map.to_host(0))
+            .unwrap_or(header.uid);
+        let gid = self
+            .gid_map
+            .to_host(header.gid)
+            .or_else(|| self.gid_map.to_host(0))
+            .unwrap_or(header.gid);
+        (Uid::from_raw(uid), Gid::from_raw(gid))
+    }
+
+    fn apply_file_metadata(&self, fd: RawFd, header: &TarHeader) -> CopyResult<()> {
+        if self.preserve_ownership {
+            let (uid, gid) = self.host_ids(header);
+            fchown(fd, Some(uid), Some(gid))?;
+        }
+        fchmod(fd, Mode::from_bits_truncate(header.mode))?;
+        let mtime = TimeSpec::new(header.mtime as i64, 0);
+        futimens(fd, &mtime, &mtime)?;
+        Ok(())
+    }
+
+    fn apply_link_metadata(
+        &self,
+        parent: RawFd,
+        name: &OsStr,
+        header: &TarHeader,
+    ) -> CopyResult<()> {
+        if self.preserve_ownership {
+            let (uid, gid) = self.host_ids(header);
+            fchownat(
+                Some(parent),
+                name,
+                Some(uid),
+                Some(gid),
+                FchownatFlags::NoFollowSymlink,
+            )?;
+        }
+        let mtime = TimeSpec::new(header.mtime as i64, 0);
+        utimensat(
+            Some(parent),
+            name,
+            &mtime,
+            &mtime,
+            UtimensatFlags::NoFollowSymlink,
+        )?;
+        Ok(())
+    }
+}
+
+/// 把同步写入切成固定大小的块交给 `sink`，用于把归档送进异步通道。
+pub struct ChunkWriter<F> {
+    buffer: Vec<u8>,
+    chunk_size: usize,
+    sink: F,
+}
+
+impl<F: FnMut(Vec<u8>) -> io::Result<()>> ChunkWriter<F> {
+    pub fn new(chunk_size: usize, sink: F) -> Self {
+        Self {
+            buffer: Vec::with_capacity(chunk_size),
+            chunk_size: chunk_size.max(1),
+            sink,
+        }
+    }
+}
+
+impl<F: FnMut(Vec<u8>) -> io::Result<()>> Write for ChunkWriter<F> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let take = buf.len().min(self.chunk_size - self.buffer.len());
+        self.buffer.extend_from_slice(&buf[..take]);
+        if self.buffer.len() == self.chunk_size {
+            let chunk = std::mem::replace(&mut self.buffer, Vec::with_capacity(self.chunk_size));
+            (self.sink)(chunk)?;
+        }
+        Ok(take)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.buffer.is_empty() {
+            (self.sink)(std::mem::take(&mut self.buffer))?;
+        }
+        Ok(())
+    }
+}
+
+/// 从 `source` 逐块拉取数据的同步读取器；`source` 返回 `None` 表示数据结束。
+pub struct ChunkReader<F> {
+    chunk: Vec<u8>,
+    offset: usize,
+    source: F,
+    done: bool,
+}
+
+impl<F: FnMut() -> Option<io::Result<Vec<u8>>>> ChunkReader<F> {
+    pub fn new(source: F) -> Self {
+        Self {
+            chunk: Vec::new(),
+            offset: 0,
+            source,
+            done: false,
+        }
+    }
+}
+
+impl<F: FnMut() -> Option<io::Result<Vec<u8>>>> Read for ChunkReader<F> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            if self.offset < self.chunk.len() {
+                let take = buf.len().min(self.chunk.len() - self.offset);
+                buf[..take].copy_from_slice(&self.chunk[self.offset..self.offset + take]);
+                self.offset += take;
+                return Ok(take);
+            }
+            if self.done {
+                return Ok(0);
+            }
+            match (self.source)() {
+                Some(Ok(chunk)) => {
+                    self.chunk = chunk;
+                    self.offset = 0;
+                }
+                Some(Err(err)) => return Err(err),
+                None => self.done = true,
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EntryKind {
+    Regular,
+    HardLink,
+    Symlink,
+    CharDevice,
+    BlockDevice,
+    Directory,
+    Fifo,
+    PaxExtended,
+}
+
+impl EntryKind {
+    fn typeflag(self) -> u8 {
+        match self {
+            Self::Regular => b'0',
+            Self::HardLink => b'1',
+            Self::Symlink => b'2',
+            Self::CharDevice => b'3',
+            Self::BlockDevice => b'4',
+            Self::Directory => b'5',
+            Self::Fifo => b'6',
+            Self::PaxExtended => b'x',
+        }
+    }
+
+    fn from_typeflag(flag: u8) -> Option<Self> {
+        match flag {
+            b'0' | b'\0' | b'7' => Some(Self::Regular),
+            b'1' => Some(Self::HardLink),
+            b'2' => Some(Self::Symlink),
+            b'3' => Some(Self::CharDevice),
+            b'4' => Some(Self::BlockDevice),
+            b'5' => Some(Self::Directory),
+            b'6' => Some(Self::Fifo),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TarHeader {
+    path: Vec<u8>,
+    link_name: Vec<u8>,
+    kind: EntryKind,
+    mode: u32,
+    uid: u32,
+    gid: u32,
+    size: u64,
+    mtime: u64,
+    device: (u64, u64),
+}
+
+impl TarHeader {
+    fn display_path(&self) -> Vec<u8> {
+        let mut path = self.path.clone();
+        if self.kind == EntryKind::Directory && !path.ends_with(b"/") {
+            path.push(b'/');
+        }
+        path
+    }
+
+    /// 超出 ustar 字段范围的值写入 PAX 扩展头。
+    fn pax_records(&self) -> Vec<u8> {
+        let mut records = Vec::new();
+        let path = self.display_path();
+        if path.len() > USTAR_MAX_NAME {
+            push_pax_record(&mut records, "path", &path);
+        }
+        if self.link_name.len() > USTAR_MAX_NAME {
+            push_pax_record(&mut records, "linkpath", &self.link_name);
+        }
+        if self.size > USTAR_MAX_SIZE {
+            push_pax_record(&mut records, "size", self.size.to_string().as_bytes());
+        }
+        if self.uid > USTAR_MAX_ID {
+            push_pax_record(&mut records, "uid", self.uid.to_string().as_bytes());
+        }
+        if self.gid > USTAR_MAX_ID {
+            push_pax_record(&mut records, "gid", self.gid.to_string().as_bytes());
+        }
+        records
+    }
+
+    fn encode(&self) -> [u8; BLOCK_SIZE] {
+        let mut block = [0u8; BLOCK_SIZE];
+        let path = self.display_path();
+        copy_field(&mut block[0..100], &path);
+        write_octal(&mut block[100..108], u64::from(self.mode));
+        write_octal(&mut block[108..116], u64::from(self.uid.min(USTAR_MAX_ID)));
+        write_octal(&mut block[116..124], u64::from(self.gid.min(USTAR_MAX_ID)));
+        write_octal(&mut block[124..136], self.size.min(USTAR_MAX_SIZE));
+        write_octal(&mut block[136..148], self.mtime.min(USTAR_MAX_SIZE));
+        block[156] = self.kind.typeflag();
+        copy_field(&mut block[157..257], &self.link_name);
+        block[257..263].copy_from_slice(b"ustar\0");
+        block[263..265].copy_from_slice(b"00");
+        write_octal(&mut block[329..337], self.device.0);
+        write_octal(&mut block[337..345], self.device.1);
+        block[148..156].fill(b' ');
+        let checksum: u64 = block.iter().map(|byte| u64::from(*byte)).sum();
+        write_octal(&mut block[148..155], checksum);
+        block[155] = b' ';
+        block
+    }
+
+    fn decode(block: &[u8; BLOCK_SIZE]) -> CopyResult<(u8, Self)> {
+        let stored = parse_numeric(&block[148..156])?;
+        let computed: u64 = block
+            .iter()
+            .enumerate()
+            .map(|(index, byte)| {
+                if (148..156).contains(&index) {
+                    u64::from(b' ')
+                } else {
+                    u64::from(*byte)
+                }
+            })
+            .sum();
+        if stored != computed {
+            return Err(ContainerCopyError::Archive(
+                "header checksum mismatch".to_string(),
+            ));
+        }
+
+        let mut path = field_bytes(&block[0..100]).to_vec();
+        // 只有 POSIX ustar 使用 prefix 字段，GNU 格式在同一位置存放其他数据。
+        let prefix = field_bytes(&block[345..500]);
+        if &block[257..263] == b"ustar\0" && !prefix.is_empty() {
+            let mut full = prefix.to_vec();
+            full.push(b'/');
+            full.extend_from_slice(&path);
+            path = full;
+        }
+        let typeflag = block[156];
+        Ok((
+            typeflag,
+            Self {
+                path,
+                link_name: field_bytes(&block[157..257]).to_vec(),
+                kind: EntryKind::from_typeflag(typeflag).unwrap_or(EntryKind::Regular),
+                mode: parse_numeric(&block[100..108])? as u32 & 0o7777,
+                uid: parse_numeric(&block[108..116])? as u32,
+                gid: parse_numeric(&block[116..124])? as u32,
+                size: parse_numeric(&block[124..136])?,
+                mtime: parse_numeric(&block[136..148])?,
+                device: (
+                    parse_numeric(&block[329..337])?,
+                    parse_numeric(&block[337..345])?,
+                ),
+            },
+        ))
+    }
+}
+
+struct TarWriter<W> {
+    inner: W,
+    summary: CopySummary,
+}
+
+impl<W: Write> TarWriter<W> {
+    fn new(inner: W) -> Self {
+        Self {
+            inner,
+            summary: CopySummary::default(),
+        }
+    }
+
+    fn append<R: Read>(&mut self, header: &TarHeader, data: R) -> io::Result<()> {
+        let records = header.pax_records();
+        if !records.is_empty() {
+            let pax = TarHeader {
+                path: b"././@PaxHeader".to_vec(),
+                link_name: Vec::new(),
+                kind: EntryKind::PaxExtended,
+                mode: 0o644,
+                uid: 0,
+                gid: 0,
+                size: records.len() as u64,
+                mtime: header.mtime,
+                device: (0, 0),
+            };
+            self.inner.write_all(&pax.encode())?;
+            self.inner.write_all(&records)?;
+            self.write_padding(records.len() as u64)?;
+        }
+        self.inner.write_all(&header.encode())?;
+        if header.kind == EntryKind::Regular {
+            let copied = io::copy(&mut data.take(header.size), &mut self.inner)?;
+            // 文件在读取过程中变短时按头部声明的大小补零，保证归档结构完整。
+            if copied < header.size {
+                io::copy(
+                    &mut io::repeat(0).take(header.size - copied),
+                    &mut self.inner,
+                )?;
+            }
+            self.write_padding(header.size)?;
+            self.summary.bytes += header.size;
+        }
+        self.summary.entries += 1;
+        Ok(())
+    }
+
+    fn write_padding(&mut self, size: u64) -> io::Result<()> {
+        let padding = padding_for(size);
+        if padding > 0 {
+            self.inner
+                .write_all(&[0u8; BLOCK_SIZE][..padding as usize])?;
+        }
+        Ok(())
+    }
+
+    fn finish(mut self) -> io::Result<CopySummary> {
+        self.inner.write_all(&[0u8; BLOCK_SIZE * 2])?;
+        self.inner.flush()?;
+        Ok(self.summary)
+    }
+}
+
+struct TarReader<R> {
+    inner: R,
+    remaining: u64,
+    padding: u64,
+}
+
+impl<R: Read> TarReader<R> {
+    fn new(inner: R) -> Self {
+        Self {
+            inner,
+            remaining: 0,
+            padding: 0,
+        }
+    }
+
+    fn next_header(&mut self) -> CopyResult<Option<TarHeader>> {
+        self.skip_data()?;
+        let mut pax: HashMap<String, Vec<u8>> = HashMap::new();
+        let mut long_name = None;
+        let mut long_link = None;
+        loop {
+            let mut block = [0u8; BLOCK_SIZE];
+            // 发送端出错时不会写出结尾零块，借此区分截断的归档与正常结束。
+            if !self.read_block(&mut block)? {
+                return Err(ContainerCopyError::Archive(
+                    "archive ended without an end-of-archive marker".to_string(),
+                ));
+            }
+            if block.iter().all(|byte| *byte == 0) {
+                return Ok(None);
+            }
+            let (typeflag, mut header) = TarHeader::decode(&block)?;
+            self.remaining = header.size;
+            self.padding = padding_for(header.size);
+            match typeflag {
+                b'x' => {
+                    let data = self.read_data()?;
+                    parse_pax_records(&data, &mut pax)?;
+                    continue;
+                }
+                b'L' => {
+                    long_name = Some(trim_nul(self.read_data()?));
+                    continue;
+                }
+                b'K' => {
+                    long_link = Some(trim_nul(self.read_data()?));
+                    continue;
+                }
+                _ => {}
+            }
+            if EntryKind::from_typeflag(typeflag).is_none() {
+                log::warn!(
+                    "Skipping archive entry {} with unsupported type {:?}",
+                    String::from_utf8_lossy(&header.path),
+                    typeflag as char
+                );
+                self.skip_data()?;
+                pax.clear();
+                long_name = None;
+                long_link = None;
+                continue;
+            }
+
+            if let Some(name) = long_name.take() {
+                header.path = name;
+            }
+            if let Some(link) = long_link.take() {
+                header.link_name = link;
+            }
+            for (key, value) in pax.drain() {
+                let number = || {
+                    std::str::from_utf8(&value)
+                        .ok()
+                        .and_then(|raw| raw.parse::<u64>().ok())
+                        .ok_or_else(|| {
+                            ContainerCopyError::Archive(format!("invalid PAX {} record", key))
+                        })
+                };
+                match key.as_str() {
+                    "path" => header.path = value.clone(),
+                    "linkpath" => header.link_name = value.clone(),
+                    "size" => header.size = number()?,
+                    "uid" => header.uid = number()? as u32,
+                    "gid" => header.gid = number()? as u32,
+                    _ => {}
+                }
+            }
+            self.remaining = header.size;
+            self.padding = padding_for(header.size);
+            return Ok(Some(header));
+        }
+    }
+
+    /// 把当前条目剩余数据写入 `writer`，返回写入字节数。
+    fn copy_data<W: Write>(&mut self, writer: &mut W) -> CopyResult<u64> {
+        let expected = self.remaining;
+        let copied = io::copy(&mut (&mut self.inner).take(expected), writer)?;
+        if copied < expected {
+            return Err(ContainerCopyError::Archive(
+                "archive ended inside an entry".to_string(),
+            ));
+        }
+        self.remaining = 0;
+        Ok(copied)
+    }
+
+    /// 读取扩展头这类元数据条目的全部内容（含对齐填充）。
+    fn read_data(&mut self) -> CopyResult<Vec<u8>> {
+        let mut data = Vec::new();
+        self.copy_data(&mut data)?;
+        self.skip_data()?;
+        Ok(data)
+    }
+
+    fn skip_data(&mut self) -> CopyResult<()> {
+        self.copy_data(&mut io::sink())?;
+        let padding = std::mem::take(&mut self.padding);
+        let skipped = io::copy(&mut (&mut self.inner).take(padding), &mut io::sink())?;
+        if skipped < padding {
+            return Err(ContainerCopyError::Archive(
+                "archive ended inside entry padding".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// 读满一个块；在块边界遇到 EOF 时返回 false。
+    fn read_block(&mut self, block: &mut [u8; BLOCK_SIZE]) -> CopyResult<bool> {
+        let mut filled = 0;
+        while filled < BLOCK_SIZE {
+            match self.inner.read(&mut block[filled..]) {
+                Ok(0) if filled == 0 => return Ok(false),
+                Ok(0) => {
+                    return Err(ContainerCopyError::Archive(
+                        "archive ended inside a header".to_string(),
+                    ))
+                }
+                Ok(read) => filled += read,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Ok(true)
+    }
+}
+
+fn open_directory_path(path: &Path) -> nix::Result<OwnedFd> {
+    let fd = nix::fcntl::open(
+        path,
+        OFlag::O_PATH | OFlag::O_DIRECTORY | OFlag::O_CLOEXEC,
+        Mode::empty(),
+    )?;
+    Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+}
+
+fn file_kind(stat: &FileStat) -> SFlag {
+    SFlag::from_bits_truncate(stat.st_mode & SFlag::S_IFMT.bits())
+}
+
+fn not_found_as(err: ContainerCopyError, path: &str) -> ContainerCopyError {
+    match err {
+        ContainerCopyError::Io(io_err) if io_err.kind() == io::ErrorKind::NotFound => {
+            ContainerCopyError::NotFound(format!("{} does not exist", path))
+        }
+        other => other,
+    }
+}
+
+fn split_components(path: &[u8]) -> impl Iterator<Item = OsString> + '_ {
+    path.split(|byte| *byte == b'/')
+        .filter(|component| !component.is_empty() && *component != b".")
+        .map(|component| OsStr::from_bytes(component).to_os_string())
+}
+
+/// 归档条目名转为相对组件；含 `..` 的条目会逃出目标目录，直接拒绝。
+fn archive_components(path: &[u8]) -> CopyResult<Vec<OsString>> {
+    let components: Vec<OsString> = split_components(path).collect();
+    if components.iter().any(|component| component == "..") {
+        return Err(ContainerCopyError::Archive(format!(
+            "entry {} escapes the destination",
+            String::from_utf8_lossy(path)
+        )));
+    }
+    Ok(components)
+}
+
+fn rename_top_level(
+    components: &mut Vec<OsString>,
+    rename: &OsString,
+    top_level: &mut Option<OsString>,
+    path: &[u8],
+) -> CopyResult<()> {
+    let current = components
+        .first()
+        .cloned()
+        .unwrap_or_else(|| OsString::from("."));
+    match top_level {
+        Some(existing) if *existing != current => {
+            return Err(ContainerCopyError::InvalidArgument(format!(
+                "archive entry {} is outside the single top-level entry; copy into an existing directory instead",
+                String::from_utf8_lossy(path)
+            )));
+        }
+        Some(_) => {}
+        None => *top_level = Some(current),
+    }
+    if components.is_empty() {
+        components.push(rename.clone());
+    } else {
+        components[0] = rename.clone();
+    }
+    Ok(())
+}
+
+fn padding_for(size: u64) -> u64 {
+    (BLOCK_SIZE as u64 - size % BLOCK_SIZE as u64) % BLOCK_SIZE as u64
+}
+
+fn copy_field(field: &mut [u8], value: &[u8]) {
+    let len = value.len().min(field.len());
+    field[..len].copy_from_slice(&value[..len]);
+}
+
+/// 以 NUL 结尾的定宽八进制数字。
+fn write_octal(field: &mut [u8], value: u64) {
+    let digits = field.len() - 1;
+    let text = format!("{:0width$o}", value, width = digits);
+    field[..digits].copy_from_slice(&text.as_bytes()[text.len() - digits..]);
+    field[digits] = 0;
+}
+
+/// 解析八进制数字段，兼容 GNU 的 base-256 编码。
+fn parse_numeric(field: &[u8]) -> CopyResult<u64> {
+    if field.first().is_some_and(|byte| byte & 0x80 != 0) {
+        let mut value = u64::from(field[0] & 0x7f);
+        for byte in &field[1..] {
+            value = (value << 8) | u64::from(*byte);
+        }
+        return Ok(value);
+    }
+    let text = std::str::from_utf8(field)
+        .map_err(|_| ContainerCopyError::Archive("non-ASCII numeric field".to_string()))?
+        .trim_matches(|c: char| c == '\0' || c == ' ');
+    if text.is_empty() {
+        return Ok(0);
+    }
+    u64::from_str_radix(text, 8)
+        .map_err(|_| ContainerCopyError::Archive(format!("invalid numeric field {:?}", text)))
+}
+
+fn field_bytes(field: &[u8]) -> &[u8] {
+    let end = field
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or(field.len());
+    &field[..end]
+}
+
+fn trim_nul(mut value: Vec<u8>) -> Vec<u8> {
+    let end = value
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or(value.len());
+    value.truncate(end);
+    value
+}
+
+/// PAX 记录格式为 `<len> <key>=<value>\n`，长度包含自身的十进制位数。
+fn push_pax_record(records: &mut Vec<u8>, key: &str, value: &[u8]) {
+    let base = key.len() + value.len() + 3;
+    let mut len = base + base.to_string().len();
+    if len.to_string().len() > base.to_string().len() {
+        len += 1;
+    }
+    records.extend_from_slice(format!("{} {}=", len, key).as_bytes());
+    records.extend_from_slice(value);
+    records.push(b'\n');
+}
+
+fn parse_pax_records(data: &[u8], records: &mut HashMap<String, Vec<u8>>) -> CopyResult<()> {
+    let mut rest = data;
+    while !rest.is_empty() && rest[0] != 0 {
+        let invalid = || ContainerCopyError::Archive("malformed PAX record".to_string());
+        let space = rest
+            .iter()
+            .position(|byte| *byte == b' ')
+            .ok_or_else(invalid)?;
+        let len: usize = std::str::from_utf8(&rest[..space])
+            .ok()
+            .and_then(|raw| raw.parse().ok())
+            .filter(|len| *len > space && *len <= rest.len())
+            .ok_or_else(invalid)?;
+        let record = &rest[space + 1..len];
+        let record = record.strip_suffix(b"\n").unwrap_or(record);
+        let equals = record
+            .iter()
+            .position(|byte| *byte == b'=')
+            .ok_or_else(invalid)?;
+        records.insert(
+            String::from_utf8_lossy(&record[..equals]).into_owned(),
+            record[equals + 1..].to_vec(),
+        );
+        rest = &rest[len..];
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+    #[test]
+    fn archive_round_trip_preserves_modes_links_and_long_names() {
+        let source = tempfile::tempdir().unwrap();
+        let data = source.path().join("data");
+        std::fs::create_dir(&data).unwrap();
+        std::fs::write(data.join("config"), b"listen 80\n").unwrap();
+        std::fs::set_permissions(data.join("config"), std::fs::Permissions::from_mode(0o4750))
+            .unwrap();
+        std::fs::hard_link(data.join("config"), data.join("config.bak")).unwrap();
+        std::os::unix::fs::symlink("/etc/passwd", data.join("passwd")).unwrap();
+        let long_name = "n".repeat(150);
+        std::fs::write(data.join(&long_name), b"long").unwrap();
+
+        let mut archive = Vec::new();
+        let summary = CopyRoot::for_directory(source.path())
+            .unwrap()
+            .archive("/data", &mut archive)
+            .unwrap();
+        assert_eq!(summary.entries, 5);
+        assert_eq!(summary.bytes, 14);
+
+        let destination = tempfile::tempdir().unwrap();
+        let extracted = CopyRoot::for_directory(destination.path())
+            .unwrap()
+            .extract("/restored", archive.as_slice())
+            .unwrap();
+        assert_eq!(extracted.entries, 5);
+
+        let restored = destination.path().join("restored");
+        assert_eq!(
+            std::fs::read(restored.join("config")).unwrap(),
+            b"listen 80\n"
+        );
+        assert_eq!(
+            std::fs::metadata(restored.join("config")).unwrap().mode() & 0o7777,
+            0o4750
+        );
+        assert_eq!(
+            std::fs::metadata(restored.join("config")).unwrap().ino(),
+            std::fs::metadata(restored.join("config.bak"))
+                .unwrap()
+                .ino()
+        );
+        assert_eq!(
+            std::fs::read_link(restored.join("passwd")).unwrap(),
+            Path::new("/etc/passwd")
+        );
+        assert_eq!(std::fs::read(restored.join(&long_name)).unwrap(), b"long");
+    }
+
+    #[test]
+    fn symlinks_and_archive_entries_stay_inside_the_root() {
+        let root_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(root_dir.path().join("etc")).unwrap();
+        std::os::unix::fs::symlink("../../../..", root_dir.path().join("up")).unwrap();
+        std::os::unix::fs::symlink("/", root_dir.path().join("abs")).unwrap();
+        let root = CopyRoot::for_directory(root_dir.path()).unwrap();
+
+        assert_eq!(
+            root.resolve(b"/up/etc", true).unwrap(),
+            vec![OsString::from("etc")]
+        );
+        assert_eq!(
+            root.resolve(b"/abs/../../etc", true).unwrap(),
+            vec![OsString::from("etc")]
+        );
+
+        // 经由指向 "/" 的链接写入的条目落在复制根内。
+        let mut archive = Vec::new();
+        let mut tar = TarWriter::new(&mut archive);
+        let header = TarHeader {
+            path: b"abs/pwned".to_vec(),
+            link_name: Vec::new(),
+            kind: EntryKind::Regular,
+            mode: 0o644,
+            uid: 0,
+            gid: 0,
+            size: 2,
+            mtime: 0,
+            device: (0, 0),
+        };
+        tar.append(&header, &b"ok"[..]).unwrap();
+        tar.finish().unwrap();
+        root.extract("/", archive.as_slice()).unwrap();
+        assert_eq!(std::fs::read(root_dir.path().join("pwned")).unwrap(), b"ok");
+
+        let mut escaping = Vec::new();
+        let mut tar = TarWriter::new(&mut escaping);
+        let header = TarHeader {
+            path: b"../outside".to_vec(),
+            ..header
+        };
+        tar.append(&header, &b"no"[..]).unwrap();
+        tar.finish().unwrap();
+        assert!(matches!(
+            root.extract("/", escaping.as_slice()),
+            Err(ContainerCopyError::Archive(_))
+        ));
+    }
+
+    #[test]
+    fn id_maps_translate_between_host_and_container_views() {
+        let map = IdMap::parse("         0     100000      65536\n").unwrap();
+        assert_eq!(map.to_container(100_033), Some(33));
+        assert_eq!(map.to_container(0), None);
+        assert_eq!(map.to_host(33), Some(100_033));
+        assert_eq!(map.to_host(70_000), None);
+        assert!(IdMap::parse("0 100000").is_err());
+        assert_eq!(IdMap::identity().to_host(1000), Some(1000));
+    }
+}
@@ -25,6 +25,7 @@ pub use crate::security::devices::DeviceMapping;
 use crate::storage::{RuntimeArtifactRecord, StorageManager};
 
 pub mod backend;
+pub mod container_copy;
 pub mod runc_backend;
 pub mod shim_manager;
 pub mod wasm_direct_backend;
@@ -83,8 +84,14 @@ pub trait ContainerRuntime {
     /// 重新打开容器日志
     fn reopen_container_log(&self, container_id: &str) -> Result<()>;
 
-    /// 在容器中执行命令
-    fn exec_in_container(&self, container_id: &str, command: &[String], tty: bool) -> Result<i32>;
+    /// 在容器中执行命令；`overrides` 非空时按容器安全上下文校验后构造 exec process spec
+    fn exec_in_container(
+        &self,
+        container_id: &str,
+        command: &[String],
+        tty: bool,
+        overrides: &crate::security::spec_patch::ExecOverrides,
+    ) -> Result<i32>;
 
     /// 更新容器资源限制
     fn update_container_resources(
@@ -458,6 +465,33 @@ impl MountSemanticsError {
     }
 }
 
+/// 构造 exec process spec 失败：bundle 配置不可用，或覆盖项越过容器的安全上下文。
+#[derive(Debug, Error)]
+pub enum ExecProcessSpecError {
+    #[error("failed to read OCI config {path}: {source}")]
+    ReadConfig {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("failed to parse OCI config {path}: {source}")]
+    ParseConfig {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    #[error("{0:#}")]
+    Rejected(anyhow::Error),
+}
+
+impl ExecProcessSpecError {
+    pub(crate) fn to_status(&self) -> tonic::Status {
+        match self {
+            Self::ReadConfig { .. } => tonic::Status::failed_precondition(self.to_string()),
+            Self::ParseConfig { .. } => tonic::Status::internal(self.to_string()),
+            Self::Rejected(_) => tonic::Status::invalid_argument(self.to_string()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct RestoreCheckpointMetadata {
     checkpoint_location: String,
@@ -764,6 +798,33 @@ impl RuncRuntime {
             .with_context(|| format!("Failed to parse OCI config {}", config_path.display()))
     }
 
+    /// 覆盖项为空时返回 `None`，沿用 runtime 从 bundle 派生 exec 进程的默认行为；否则以
+    /// bundle 中的主进程为基础构造 exec process spec，并按容器安全上下文与 rootless 限制校验。
+    /// CRI streaming exec 与 runtime 直接 exec 共用这一实现。
+    pub(crate) fn exec_process_spec(
+        bundle_path: &Path,
+        command: &[String],
+        tty: bool,
+        overrides: &crate::security::spec_patch::ExecOverrides,
+        rootless: bool,
+    ) -> std::result::Result<Option<Process>, ExecProcessSpecError> {
+        if overrides.is_empty() {
+            return Ok(None);
+        }
+        let path = bundle_path.join("config.json");
+        let raw = match std::fs::read(&path) {
+            Ok(raw) => raw,
+            Err(source) => return Err(ExecProcessSpecError::ReadConfig { path, source }),
+        };
+        let spec: Spec = match serde_json::from_slice(&raw) {
+            Ok(spec) => spec,
+            Err(source) => return Err(ExecProcessSpecError::ParseConfig { path, source }),
+        };
+        crate::security::spec_patch::build_exec_process(&spec, command, tty, overrides, rootless)
+            .map(Some)
+            .map_err(ExecProcessSpecError::Rejected)
+    }
+
     fn container_uses_terminal(&self, container_id: &str) -> Result<bool> {
         let config = self.load_bundle_config_value(container_id)?;
         let from_process = config
@@ -1036,6 +1097,12 @@ impl RuncRuntime {
                         Some(mode) if mode == NamespaceMode::Node as i32
                     ) {
                         namespace.path = Self::host_namespace_path("net");
+                    } else if matches!(
+                        options.map(|o| o.network),
+                        Some(mode) if mode == NamespaceMode::Target as i32
+                    ) {
+                        namespace.path =
+                            options.and_then(|o| self.target_namespace_path(&o.target_id, "net"));
                     }
                 }
                 "pid" => {
@@ -1843,6 +1910,18 @@ impl RuncRuntime {
         }
     }
 
+    /// 把覆盖后的 exec process spec 写入临时文件，交给 `runtime exec --process`；
+    /// 返回的文件句柄需要保持到 runtime 进程退出。
+    pub(crate) fn write_exec_process_spec(process: &Process) -> Result<tempfile::NamedTempFile> {
+        let mut file = tempfile::Builder::new()
+            .prefix("crius-exec-")
+            .suffix(".json")
+            .tempfile()
+            .context("Failed to create exec process spec file")?;
+        serde_json::to_writer(&mut file, process).context("Failed to write exec process spec")?;
+        Ok(file)
+    }
+
     fn load_seccomp_profile(
         &self,
         profile: Option<&SeccompProfile>,
@@ -3949,7 +4028,20 @@ impl ContainerRuntime for RuncRuntime {
         self.notify_shim_to_reopen_log(container_id)
     }
 
-    fn exec_in_container(&self, container_id: &str, command: &[String], tty: bool) -> Result<i32> {
+    fn exec_in_container(
+        &self,
+        container_id: &str,
+        command: &[String],
+        tty: bool,
+        overrides: &crate::security::spec_patch::ExecOverrides,
+    ) -> Result<i32> {
+        let process = Self::exec_process_spec(
+            &self.bundle_path(container_id),
+            command,
+            tty,
+            overrides,
+            self.rootless.enabled,
+        )?;
         if let Some(ref shim_manager) = self.shim_manager {
             let affinity_cpu = if self.exec_cpu_affinity == "first" {
                 self.load_bundle_config_value(container_id)
@@ -3966,7 +4058,7 @@ impl ContainerRuntime for RuncRuntime {
             } else {
                 None
             };
-            return shim_manager.exec_process(container_id, command, tty, affinity_cpu);
+            return shim_manager.exec_process(container_id, command, tty, affinity_cpu, process);
         }
         info!(
             "Executing command in container {}: {:?}",
@@ -3979,6 +4071,13 @@ impl ContainerRuntime for RuncRuntime {
         if tty {
             cmd.arg("-t");
         }
+        let process_file = process
+            .as_ref()
+            .map(Self::write_exec_process_spec)
+            .transpose()?;
+        if let Some(file) = process_file.as_ref() {
+            cmd.arg("--process").arg(file.path());
+        }
 
         // 添加容器ID
         cmd.arg(container_id);
@@ -5,6 +5,7 @@ use anyhow::Result;
 use crate::config::CgroupDriverConfig;
 use crate::oci::spec::Spec;
 use crate::proto::runtime::v1::LinuxContainerResources;
+use crate::security::spec_patch::ExecOverrides;
 
 use super::backend::{RuntimeBackend, RuntimeContextManager, TaskController};
 use super::{
@@ -52,8 +53,15 @@ impl TaskController for RuncBackend {
         self.inner.reopen_container_log(container_id)
     }
 
-    fn exec_in_container(&self, container_id: &str, command: &[String], tty: bool) -> Result<i32> {
-        self.inner.exec_in_container(container_id, command, tty)
+    fn exec_in_container(
+        &self,
+        container_id: &str,
+        command: &[String],
+        tty: bool,
+        overrides: &ExecOverrides,
+    ) -> Result<i32> {
+        self.inner
+            .exec_in_container(container_id, command, tty, overrides)
     }
 
     fn update_container_resources(
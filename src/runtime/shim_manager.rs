@@ -67,6 +67,8 @@ pub struct ShimConfig {
     pub debug: bool,
     /// 是否将容器输出双写到 journald。
     pub log_to_journald: bool,
+    /// 传给 shim 的日志驱动声明，格式为 `name[:key=value,...]`。
+    pub log_drivers: Vec<String>,
     /// 是否在日志轮转和容器退出时跳过 sync。
     pub no_sync_log: bool,
     /// 是否禁用 pivot_root，改用 MS_MOVE。
@@ -79,6 +81,8 @@ pub struct ShimConfig {
     pub runtime_path: PathBuf,
     /// CRI 单条日志记录切分阈值（字节）。
     pub max_container_log_line_size: usize,
+    /// attach 回放缓冲中每个输出流保留的字节数；0 表示关闭。
+    pub attach_scrollback_bytes: usize,
     /// 状态账本数据库路径。
     pub state_db_path: PathBuf,
 }
@@ -97,12 +101,14 @@ impl Default for ShimConfig {
             monitor_env: Vec::new(),
             debug: false,
             log_to_journald: false,
+            log_drivers: Vec::new(),
             no_sync_log: false,
             no_pivot: false,
             no_new_keyring: false,
             systemd_cgroup: false,
             runtime_path: PathBuf::from("runc"),
             max_container_log_line_size: 4096,
+            attach_scrollback_bytes: 64 * 1024,
             state_db_path: PathBuf::new(),
         }
     }
@@ -454,6 +460,7 @@ impl ShimManager {
 
     /// 启动shim进程来管理容器
     pub fn start_shim(&self, container_id: &str, bundle_path: &Path) -> Result<ShimProcess> {
+        let _span = crate::trace_export::phase_span("shim", "spawn", container_id).entered();
         info!("Starting shim for container {}", container_id);
 
         if let Some(existing) = self
@@ -526,6 +533,9 @@ impl ShimManager {
         if self.config.log_to_journald {
             cmd.arg("--log-to-journald");
         }
+        for log_driver in &self.config.log_drivers {
+            cmd.arg("--log-driver").arg(log_driver);
+        }
         if self.config.no_sync_log {
             cmd.arg("--no-sync-log");
         }
@@ -548,10 +558,13 @@ impl ShimManager {
         cmd.arg("--log").arg(&log_file);
         cmd.arg("--max-container-log-line-size")
             .arg(self.config.max_container_log_line_size.to_string());
+        cmd.arg("--attach-scrollback-bytes")
+            .arg(self.config.attach_scrollback_bytes.to_string());
 
         // 启动shim进程
         debug!("Executing: {:?}", cmd);
 
+        let spawn_started = Instant::now();
         let mut child = cmd
             .stdin(Stdio::null())
             .stdout(Stdio::null())
@@ -596,7 +609,14 @@ impl ShimManager {
         processes.push(process.clone());
         drop(processes);
         self.persist_process_metadata(&process);
-        self.wait_for_rpc_socket(container_id)?;
+        let socket_ready = self.wait_for_rpc_socket(container_id);
+        crate::metrics::operations::observe_phase(
+            crate::metrics::operations::OperationPhase::ShimSpawn,
+            "",
+            socket_ready.is_ok(),
+            spawn_started.elapsed(),
+        );
+        socket_ready?;
         self.persist_shim_ledger_state(ShimLedgerState {
             container_id,
             shim_pid,
@@ -682,6 +702,7 @@ impl ShimManager {
         command: &[String],
         tty: bool,
         exec_cpu_affinity: Option<usize>,
+        process: Option<crate::oci::spec::Process>,
     ) -> Result<i32> {
         match self
             .rpc_client(container_id)
@@ -693,6 +714,7 @@ impl ShimManager {
                 timeout_ms: None,
                 io_drain_timeout_ms: None,
                 exec_cpu_affinity,
+                process,
             }))? {
             ShimRpcResponse::ExecProcess(response) => Ok(response.exit_code),
             other => Err(anyhow::anyhow!(
@@ -720,6 +742,7 @@ impl ShimManager {
                 timeout_ms: timeout.map(|value| value.as_millis() as u64),
                 io_drain_timeout_ms: None,
                 exec_cpu_affinity,
+                process: None,
             }))? {
             ShimRpcResponse::ExecProcess(response) => {
                 Ok((response.exit_code, response.stdout, response.stderr))
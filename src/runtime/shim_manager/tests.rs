@@ -605,6 +605,79 @@ fn test_start_shim_passes_no_sync_log_flag() {
     manager.stop_shim("container-1").unwrap();
 }
 
+#[test]
+fn test_start_shim_passes_attach_scrollback_bytes() {
+    let temp_dir = tempdir().unwrap();
+    let args_path = temp_dir.path().join("shim.args");
+    let shim_path = write_ping_shim(temp_dir.path(), Some(&args_path));
+
+    let manager = ShimManager::new(ShimConfig {
+        shim_path,
+        work_dir: temp_dir.path().join("shims"),
+        attach_socket_dir: temp_dir.path().join("attach"),
+        container_exits_dir: temp_dir.path().join("exits"),
+        attach_scrollback_bytes: 0,
+        runtime_path: PathBuf::from("/bin/false"),
+        ..Default::default()
+    });
+    let bundle = temp_dir.path().join("bundle");
+    fs::create_dir_all(&bundle).unwrap();
+
+    manager.start_shim("container-1", &bundle).unwrap();
+    for _ in 0..50 {
+        if args_path.exists() {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+    let args = fs::read_to_string(&args_path).unwrap();
+    let args = args.lines().collect::<Vec<_>>();
+    let flag = args
+        .iter()
+        .position(|line| *line == "--attach-scrollback-bytes")
+        .expect("scrollback flag should be passed");
+    assert_eq!(args.get(flag + 1), Some(&"0"));
+
+    manager.stop_shim("container-1").unwrap();
+}
+
+#[test]
+fn test_start_shim_passes_log_drivers() {
+    let temp_dir = tempdir().unwrap();
+    let args_path = temp_dir.path().join("shim.args");
+    let shim_path = write_ping_shim(temp_dir.path(), Some(&args_path));
+
+    let manager = ShimManager::new(ShimConfig {
+        shim_path,
+        work_dir: temp_dir.path().join("shims"),
+        attach_socket_dir: temp_dir.path().join("attach"),
+        container_exits_dir: temp_dir.path().join("exits"),
+        log_drivers: vec!["cri-file".to_string(), "json-file:max-size=1Mi".to_string()],
+        runtime_path: PathBuf::from("/bin/false"),
+        ..Default::default()
+    });
+    let bundle = temp_dir.path().join("bundle");
+    fs::create_dir_all(&bundle).unwrap();
+
+    manager.start_shim("container-1", &bundle).unwrap();
+    for _ in 0..50 {
+        if args_path.exists() {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+    let args = fs::read_to_string(&args_path).unwrap();
+    let args = args.lines().collect::<Vec<_>>();
+    let drivers = args
+        .windows(2)
+        .filter(|pair| pair[0] == "--log-driver")
+        .map(|pair| pair[1])
+        .collect::<Vec<_>>();
+    assert_eq!(drivers, vec!["cri-file", "json-file:max-size=1Mi"]);
+
+    manager.stop_shim("container-1").unwrap();
+}
+
 #[test]
 fn test_start_shim_passes_runtime_config_path_and_monitor_cgroup() {
     let temp_dir = tempdir().unwrap();
@@ -1762,7 +1762,12 @@ esac
     .unwrap();
 
     let exit_code = runtime
-        .exec_in_container("container-1", &["true".to_string()], false)
+        .exec_in_container(
+            "container-1",
+            &["true".to_string()],
+            false,
+            &crate::security::spec_patch::ExecOverrides::default(),
+        )
         .unwrap();
     assert_eq!(exit_code, 0);
     assert_eq!(fs::read_to_string(&affinity_path).unwrap().trim(), "0");
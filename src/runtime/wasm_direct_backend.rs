@@ -10,6 +10,7 @@ use serde::{Deserialize, Serialize};
 use crate::config::CgroupDriverConfig;
 use crate::oci::spec::Spec;
 use crate::proto::runtime::v1::LinuxContainerResources;
+use crate::security::spec_patch::ExecOverrides;
 
 use super::{
     ContainerConfig, ContainerStatus, MountSemanticsError, RuntimeBackend, RuntimeContextKind,
@@ -435,10 +436,19 @@ impl TaskController for WasmDirectBackend {
         Err(Self::unsupported_task("reopen_container_log"))
     }
 
-    fn exec_in_container(&self, container_id: &str, command: &[String], _tty: bool) -> Result<i32> {
+    fn exec_in_container(
+        &self,
+        container_id: &str,
+        command: &[String],
+        _tty: bool,
+        overrides: &ExecOverrides,
+    ) -> Result<i32> {
         if !self.options.allow_exec {
             return Err(Self::unsupported_task("exec_in_container"));
         }
+        if !overrides.is_empty() {
+            bail!("wasm-direct exec does not support env, workdir, user or capability overrides");
+        }
         let status = self.container_status(container_id)?;
         if !matches!(status, ContainerStatus::Running) {
             bail!("wasm-direct task {container_id} is not running");
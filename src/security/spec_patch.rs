@@ -4,7 +4,8 @@ use std::path::PathBuf;
 use anyhow::Result;
 
 use crate::oci::spec::{
-    Linux, LinuxCapabilities, LinuxDeviceCgroup, LinuxResources, Process, Root, Seccomp, Spec,
+    IdMapping, Linux, LinuxCapabilities, LinuxDeviceCgroup, LinuxResources, Process, Root, Seccomp,
+    Spec, User,
 };
 use crate::proto::runtime::v1::Capability;
 use crate::security::devices::{self, DeviceMapping};
@@ -171,6 +172,179 @@ pub fn validate_rootless_requests(input: &SpecPatchInput<'_>) -> Result<()> {
     .map(|_| ())
 }
 
+/// crius 扩展的 exec 覆盖项（`LocalService` / `crs exec`），CRI exec 始终为空。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExecOverrides {
+    /// 追加或覆盖的 `KEY=VALUE` 环境变量
+    pub env: Vec<String>,
+    /// 工作目录，必须是绝对路径
+    pub cwd: Option<String>,
+    /// 运行身份
+    pub user: Option<ExecUser>,
+    /// 授予容器 bounding 集中的全部 capabilities
+    pub privileged: bool,
+    /// 额外授予的 capabilities
+    pub add_capabilities: Vec<String>,
+}
+
+/// exec 进程的 uid/gid；未指定 gid 时沿用容器主进程的 gid。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExecUser {
+    pub uid: u32,
+    pub gid: Option<u32>,
+}
+
+impl ExecOverrides {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// 以容器 bundle 中的主进程为基础构造 exec 的 process spec。
+///
+/// 覆盖项不能越过容器自身的安全上下文：以非 root 运行的容器不能切回 uid 0，主组不是 0 的
+/// 容器也不能切到 gid 0（否则 `runAsNonRoot` / `runAsGroup` 可以借 root 组绕过），capabilities
+/// 只能在容器的 bounding 集内提升，`privileged` 要求容器本身是特权容器；rootless 下
+/// uid/gid 还必须落在容器 user namespace 的映射范围内。
+pub fn build_exec_process(
+    spec: &Spec,
+    command: &[String],
+    tty: bool,
+    overrides: &ExecOverrides,
+    rootless: bool,
+) -> Result<Process> {
+    let mut process = spec
+        .process
+        .clone()
+        .ok_or_else(|| anyhow::anyhow!("container spec has no process"))?;
+    process.args = command.to_vec();
+    process.terminal = Some(tty);
+
+    for entry in &overrides.env {
+        let Some((key, _)) = entry.split_once('=').filter(|(key, _)| !key.is_empty()) else {
+            return Err(anyhow::anyhow!(
+                "invalid exec env \"{entry}\": expected KEY=VALUE"
+            ));
+        };
+        let env = process.env.get_or_insert_with(Vec::new);
+        env.retain(|existing| existing.split_once('=').map(|(name, _)| name) != Some(key));
+        env.push(entry.clone());
+    }
+
+    if let Some(cwd) = overrides.cwd.as_deref() {
+        if !cwd.starts_with('/') {
+            return Err(anyhow::anyhow!(
+                "exec working directory \"{cwd}\" must be an absolute path"
+            ));
+        }
+        process.cwd = cwd.to_string();
+    }
+
+    if let Some(requested) = overrides.user {
+        let current = process.user.get_or_insert(User {
+            uid: 0,
+            gid: 0,
+            additional_gids: None,
+            username: None,
+        });
+        if requested.uid == 0 && current.uid != 0 {
+            return Err(anyhow::anyhow!(
+                "container runs as non-root uid {}; exec cannot switch to uid 0",
+                current.uid
+            ));
+        }
+        let gid = requested.gid.unwrap_or(current.gid);
+        if gid == 0 && current.gid != 0 {
+            return Err(anyhow::anyhow!(
+                "container runs with non-root gid {}; exec cannot switch to gid 0",
+                current.gid
+            ));
+        }
+        if rootless {
+            let linux = spec.linux.as_ref();
+            ensure_id_mapped(
+                "uid",
+                requested.uid,
+                linux.and_then(|linux| linux.uid_mappings.as_deref()),
+            )?;
+            ensure_id_mapped(
+                "gid",
+                gid,
+                linux.and_then(|linux| linux.gid_mappings.as_deref()),
+            )?;
+        }
+        // 附加组属于容器原来的身份，切换身份后不再保留。
+        if current.uid != requested.uid || current.gid != gid {
+            current.additional_gids = None;
+            current.username = None;
+        }
+        current.uid = requested.uid;
+        current.gid = gid;
+    }
+
+    let bounding = process
+        .capabilities
+        .as_ref()
+        .and_then(|capabilities| capabilities.bounding.clone())
+        .unwrap_or_default();
+    let mut added = Vec::new();
+    if overrides.privileged {
+        if !privileged_capabilities()
+            .iter()
+            .all(|cap| bounding.contains(cap))
+        {
+            return Err(anyhow::anyhow!(
+                "privileged exec requires a privileged container"
+            ));
+        }
+        added = bounding.clone();
+    }
+    for cap in &overrides.add_capabilities {
+        let cap = normalize_capability_name(cap);
+        if !bounding.contains(&cap) {
+            return Err(anyhow::anyhow!(
+                "capability {cap} is not in the bounding set of the container"
+            ));
+        }
+        if !added.contains(&cap) {
+            added.push(cap);
+        }
+    }
+    if let Some(capabilities) = process.capabilities.as_mut().filter(|_| !added.is_empty()) {
+        // 同时进入 inheritable / ambient，非 root 身份 execve 后也能保留。
+        for set in [
+            &mut capabilities.effective,
+            &mut capabilities.permitted,
+            &mut capabilities.inheritable,
+            &mut capabilities.ambient,
+        ] {
+            let set = set.get_or_insert_with(Vec::new);
+            for cap in &added {
+                if !set.contains(cap) {
+                    set.push(cap.clone());
+                }
+            }
+        }
+    }
+
+    Ok(process)
+}
+
+fn ensure_id_mapped(kind: &str, id: u32, mappings: Option<&[IdMapping]>) -> Result<()> {
+    let Some(mappings) = mappings.filter(|mappings| !mappings.is_empty()) else {
+        return Ok(());
+    };
+    if mappings
+        .iter()
+        .any(|mapping| id >= mapping.container_id && id - mapping.container_id < mapping.size)
+    {
+        return Ok(());
+    }
+    Err(anyhow::anyhow!(
+        "rootless exec {kind} {id} is not mapped in the container user namespace"
+    ))
+}
+
 pub fn normalize_capability_name(name: &str) -> String {
     let upper = name.trim().to_ascii_uppercase();
     if upper.starts_with("CAP_") {
@@ -472,4 +646,199 @@ mod tests {
             vec!["CAP_NET_BIND_SERVICE".to_string()]
         );
     }
+
+    fn exec_base_spec(uid: u32, bounding: &[&str]) -> Spec {
+        let mut spec = Spec::new("1.0.2");
+        spec.process = Some(Process {
+            terminal: None,
+            user: Some(User {
+                uid,
+                gid: uid,
+                additional_gids: Some(vec![10]),
+                username: None,
+            }),
+            args: vec!["/pause".to_string()],
+            env: Some(vec!["PATH=/bin".to_string(), "MODE=prod".to_string()]),
+            cwd: "/".to_string(),
+            capabilities: Some(apply_capability_overrides(
+                &bounding.iter().map(ToString::to_string).collect::<Vec<_>>(),
+                None,
+                false,
+            )),
+            rlimits: None,
+            oom_score_adj: None,
+            scheduler: None,
+            no_new_privileges: Some(true),
+            apparmor_profile: None,
+            selinux_label: None,
+            io_priority: None,
+        });
+        spec
+    }
+
+    #[test]
+    fn exec_process_applies_env_cwd_user_and_bounded_capabilities() {
+        let spec = exec_base_spec(1000, &["CAP_CHOWN", "CAP_NET_ADMIN"]);
+        let overrides = ExecOverrides {
+            env: vec!["MODE=debug".to_string(), "TRACE=1".to_string()],
+            cwd: Some("/work".to_string()),
+            user: Some(ExecUser {
+                uid: 1001,
+                gid: None,
+            }),
+            privileged: false,
+            add_capabilities: vec!["net_admin".to_string()],
+        };
+
+        let process =
+            build_exec_process(&spec, &["sh".to_string()], true, &overrides, false).unwrap();
+
+        assert_eq!(process.args, vec!["sh".to_string()]);
+        assert_eq!(process.terminal, Some(true));
+        assert_eq!(
+            process.env.unwrap(),
+            vec!["PATH=/bin", "MODE=debug", "TRACE=1"]
+        );
+        assert_eq!(process.cwd, "/work");
+        let user = process.user.unwrap();
+        assert_eq!((user.uid, user.gid), (1001, 1000));
+        assert!(user.additional_gids.is_none());
+        let capabilities = process.capabilities.unwrap();
+        assert_eq!(
+            capabilities.effective.unwrap(),
+            vec!["CAP_CHOWN", "CAP_NET_ADMIN"]
+        );
+        assert_eq!(capabilities.ambient.unwrap(), vec!["CAP_NET_ADMIN"]);
+        assert_eq!(process.no_new_privileges, Some(true));
+    }
+
+    #[test]
+    fn exec_process_rejects_overrides_beyond_container_security_context() {
+        let spec = exec_base_spec(1000, &["CAP_CHOWN"]);
+        let reject = |overrides: ExecOverrides, rootless: bool| {
+            build_exec_process(&spec, &["id".to_string()], false, &overrides, rootless)
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert!(reject(
+            ExecOverrides {
+                user: Some(ExecUser {
+                    uid: 0,
+                    gid: Some(0),
+                }),
+                ..Default::default()
+            },
+            false,
+        )
+        .contains("cannot switch to uid 0"));
+        for uid in [1000, 1001] {
+            assert!(reject(
+                ExecOverrides {
+                    user: Some(ExecUser { uid, gid: Some(0) }),
+                    ..Default::default()
+                },
+                false,
+            )
+            .contains("cannot switch to gid 0"));
+        }
+        assert!(reject(
+            ExecOverrides {
+                add_capabilities: vec!["sys_admin".to_string()],
+                ..Default::default()
+            },
+            false,
+        )
+        .contains("CAP_SYS_ADMIN is not in the bounding set"));
+        assert!(reject(
+            ExecOverrides {
+                privileged: true,
+                ..Default::default()
+            },
+            false,
+        )
+        .contains("requires a privileged container"));
+        assert!(reject(
+            ExecOverrides {
+                cwd: Some("work".to_string()),
+                ..Default::default()
+            },
+            false,
+        )
+        .contains("absolute path"));
+        assert!(reject(
+            ExecOverrides {
+                env: vec!["=value".to_string()],
+                ..Default::default()
+            },
+            false,
+        )
+        .contains("expected KEY=VALUE"));
+
+        let mut rootless_spec = spec.clone();
+        rootless_spec.linux = Some(Linux {
+            namespaces: None,
+            uid_mappings: Some(vec![IdMapping {
+                container_id: 0,
+                host_id: 100000,
+                size: 65536,
+            }]),
+            gid_mappings: Some(vec![IdMapping {
+                container_id: 0,
+                host_id: 100000,
+                size: 1,
+            }]),
+            devices: None,
+            net_devices: None,
+            cgroups_path: None,
+            resources: None,
+            rootfs_propagation: None,
+            seccomp: None,
+            sysctl: None,
+            mount_label: None,
+            masked_paths: None,
+            readonly_paths: None,
+            intel_rdt: None,
+        });
+        let error = build_exec_process(
+            &rootless_spec,
+            &["id".to_string()],
+            false,
+            &ExecOverrides {
+                user: Some(ExecUser {
+                    uid: 2000,
+                    gid: Some(2000),
+                }),
+                ..Default::default()
+            },
+            true,
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(error.contains("gid 2000 is not mapped"), "{error}");
+    }
+
+    #[test]
+    fn exec_process_privileged_grants_full_bounding_set_for_privileged_container() {
+        let privileged = privileged_capabilities();
+        let spec = exec_base_spec(
+            0,
+            &privileged.iter().map(String::as_str).collect::<Vec<_>>(),
+        );
+        let process = build_exec_process(
+            &spec,
+            &["sh".to_string()],
+            false,
+            &ExecOverrides {
+                privileged: true,
+                ..Default::default()
+            },
+            false,
+        )
+        .unwrap();
+
+        let capabilities = process.capabilities.unwrap();
+        assert_eq!(capabilities.effective.unwrap(), privileged);
+        assert_eq!(capabilities.permitted.unwrap(), privileged);
+    }
 }
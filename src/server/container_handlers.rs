@@ -60,6 +60,13 @@ const RANDOM_NAME_RIGHT: &[&str] = &[
     "yonath",
 ];
 
+/// 调试容器中目标容器根文件系统的挂载点。
+const DEBUG_TARGET_ROOTFS_PATH: &str = "/target";
+/// 调试容器必须在创建后这段时间内启动，否则视为发起它的 `crs debug container` 已经中断。
+pub(super) const DEBUG_CONTAINER_START_LEASE: std::time::Duration =
+    std::time::Duration::from_secs(60);
+const DEBUG_CONTAINER_REAPER_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
 enum ContainerOwner {
     Local { runtime_handler: Option<String> },
     Pod { pod_sandbox_id: String },
@@ -114,6 +121,7 @@ struct ContainerCreateInput {
     config: crate::proto::runtime::v1::ContainerConfig,
     sandbox_config: Option<crate::proto::runtime::v1::PodSandboxConfig>,
     owner: ContainerOwner,
+    debug_target: Option<String>,
 }
 
 impl RuntimeServiceImpl {
@@ -239,6 +247,7 @@ impl RuntimeServiceImpl {
                 pod_uid: &pod_uid,
                 runtime_handler: &runtime_handler,
                 pod_cidr: None,
+                host_ports: &[],
             })
             .await;
         if let Err(err) = setup_result {
@@ -1839,6 +1848,7 @@ impl RuntimeServiceImpl {
             config,
             sandbox_config: req.sandbox_config,
             owner: ContainerOwner::Pod { pod_sandbox_id },
+            debug_target: None,
         })
         .await
     }
@@ -1880,6 +1890,7 @@ impl RuntimeServiceImpl {
                 config,
                 sandbox_config: Some(sandbox_config),
                 owner: ContainerOwner::Local { runtime_handler },
+                debug_target: None,
             })
             .await?;
         Ok(Response::new(
@@ -1889,6 +1900,243 @@ impl RuntimeServiceImpl {
         ))
     }
 
+    /// 在目标容器所属 pod（本地容器则为本地）中创建临时调试容器：加入目标的
+    /// pid/net/ipc 命名空间，按需把目标根文件系统挂到 `/target`，并在账本中标记为临时容器。
+    pub async fn create_debug_container_impl(
+        &self,
+        request: Request<crate::proto::local::v1::CreateDebugContainerRequest>,
+    ) -> Result<Response<crate::proto::local::v1::CreateDebugContainerResponse>, Status> {
+        let _sync_block = self.nri.block_plugin_sync().await;
+        log::info!("CreateDebugContainer called");
+        let req = request.into_inner();
+        if req.target_container_id.trim().is_empty() {
+            return Err(Status::invalid_argument("target container id must be set"));
+        }
+        let mut config = req
+            .config
+            .ok_or_else(|| Status::invalid_argument("Container config not specified"))?;
+        let target_id = self.resolve_container_id(&req.target_container_id).await?;
+        self.ensure_container_loaded(&target_id).await?;
+        let target_pod_id = {
+            let containers = self.containers.lock().await;
+            containers
+                .get(&target_id)
+                .map(|container| container.pod_sandbox_id.clone())
+                .ok_or_else(|| Status::not_found("Container not found"))?
+        };
+        let target_state = self.container_internal_state(&target_id).await;
+        if let Some(target) = target_state
+            .as_ref()
+            .and_then(|state| state.ephemeral_debug_target.as_deref())
+        {
+            return Err(Status::failed_precondition(format!(
+                "container {} is itself a debug container for {}; debug the target instead",
+                target_id, target
+            )));
+        }
+        if self
+            .runtime_namespace_path_for_container(&target_id, "pid")
+            .await?
+            .is_none()
+        {
+            return Err(Status::failed_precondition(format!(
+                "container {} has no running process; debug containers join the namespaces of a running container",
+                target_id
+            )));
+        }
+
+        let linux = config.linux.get_or_insert_with(Default::default);
+        let security = linux.security_context.get_or_insert_with(Default::default);
+        let userns_options = security
+            .namespace_options
+            .take()
+            .and_then(|options| options.userns_options);
+        security.namespace_options = Some(crate::proto::runtime::v1::NamespaceOption {
+            network: NamespaceMode::Target as i32,
+            pid: NamespaceMode::Target as i32,
+            ipc: NamespaceMode::Target as i32,
+            target_id: target_id.clone(),
+            userns_options,
+        });
+        if req.mount_target_rootfs {
+            let rootfs = self.container_rootfs_host_path(&target_id).await?;
+            config.mounts.push(crate::proto::runtime::v1::Mount {
+                container_path: DEBUG_TARGET_ROOTFS_PATH.to_string(),
+                host_path: rootfs.display().to_string(),
+                ..Default::default()
+            });
+        }
+
+        let owner = if target_pod_id.is_empty() {
+            ContainerOwner::Local {
+                runtime_handler: target_state
+                    .and_then(|state| state.local_network)
+                    .map(|network| network.runtime_handler)
+                    .filter(|handler| !handler.is_empty()),
+            }
+        } else {
+            ContainerOwner::Pod {
+                pod_sandbox_id: target_pod_id.clone(),
+            }
+        };
+        let response = self
+            .create_container_from_input(ContainerCreateInput {
+                config,
+                sandbox_config: None,
+                owner,
+                debug_target: Some(target_id),
+            })
+            .await?;
+        let container_id = response.into_inner().container_id;
+        self.spawn_ephemeral_debug_container_reaper(&container_id, DEBUG_CONTAINER_START_LEASE);
+        Ok(Response::new(
+            crate::proto::local::v1::CreateDebugContainerResponse {
+                container_id,
+                pod_sandbox_id: target_pod_id,
+            },
+        ))
+    }
+
+    /// 守护临时调试容器：超过启动租约仍未启动，或进程已经退出时删除容器，
+    /// 不依赖发起它的 CLI 存活。
+    pub(super) fn spawn_ephemeral_debug_container_reaper(
+        &self,
+        container_id: &str,
+        start_lease: std::time::Duration,
+    ) {
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let service = self.clone_for_background();
+        let container_id = container_id.to_string();
+        let mut shutdown = self.reload_watcher_shutdown.subscribe();
+        handle.spawn(async move {
+            let deadline = tokio::time::Instant::now() + start_lease;
+            loop {
+                tokio::select! {
+                    _ = shutdown.recv() => return,
+                    _ = tokio::time::sleep(DEBUG_CONTAINER_REAPER_INTERVAL) => {}
+                }
+                let state = {
+                    let containers = service.containers.lock().await;
+                    containers
+                        .get(&container_id)
+                        .map(|container| container.state)
+                };
+                let reason = match state {
+                    None => return,
+                    Some(state) if state == ContainerState::ContainerExited as i32 => {
+                        "its process exited"
+                    }
+                    Some(state)
+                        if state == ContainerState::ContainerCreated as i32
+                            && tokio::time::Instant::now() >= deadline =>
+                    {
+                        "it was not started before its lease expired"
+                    }
+                    Some(_) => continue,
+                };
+                service
+                    .remove_ephemeral_debug_container(&container_id, reason)
+                    .await;
+                return;
+            }
+        });
+    }
+
+    /// 立即停止并删除临时调试容器；容器已被删除时不做任何事。
+    pub(super) async fn remove_ephemeral_debug_container(&self, container_id: &str, reason: &str) {
+        if let Err(status) = self.stop_container_internal(container_id, 0).await {
+            if status.code() != tonic::Code::NotFound {
+                log::debug!(
+                    "Failed to stop ephemeral debug container {}: {}",
+                    container_id,
+                    status.message()
+                );
+            }
+        }
+        match self.cleanup_stale_logical_container(container_id).await {
+            Ok(()) => log::info!(
+                "Removed ephemeral debug container {} because {}",
+                container_id,
+                reason
+            ),
+            Err(status) => log::warn!(
+                "Failed to remove ephemeral debug container {}: {}",
+                container_id,
+                status.message()
+            ),
+        }
+    }
+
+    /// 读取容器 bundle 的 OCI 配置，返回根文件系统在宿主机上的路径。
+    async fn container_rootfs_host_path(&self, container_id: &str) -> Result<PathBuf, Status> {
+        let bundle_path = self
+            .runtime
+            .bundle_path_for_container(container_id)
+            .map_err(|err| Status::failed_precondition(err.to_string()))?;
+        let config_path = bundle_path.join("config.json");
+        let raw = tokio::fs::read(&config_path).await.map_err(|err| {
+            Status::failed_precondition(format!(
+                "failed to read OCI config {}: {}",
+                config_path.display(),
+                err
+            ))
+        })?;
+        let spec: crate::oci::spec::Spec = serde_json::from_slice(&raw).map_err(|err| {
+            Status::internal(format!(
+                "failed to parse OCI config {}: {}",
+                config_path.display(),
+                err
+            ))
+        })?;
+        let root = spec
+            .root
+            .map(|root| PathBuf::from(root.path))
+            .filter(|path| !path.as_os_str().is_empty())
+            .ok_or_else(|| {
+                Status::failed_precondition(format!(
+                    "container {} has no root filesystem in its OCI config",
+                    container_id
+                ))
+            })?;
+        Ok(if root.is_absolute() {
+            root
+        } else {
+            bundle_path.join(root)
+        })
+    }
+
+    /// 解析文件复制的目标容器，返回完整 id 与容器 init 进程 pid。
+    pub async fn container_copy_target(
+        &self,
+        requested_id: &str,
+    ) -> std::result::Result<(String, i32), crate::runtime::container_copy::ContainerCopyError>
+    {
+        use crate::runtime::container_copy::ContainerCopyError;
+
+        let container_id = self
+            .resolve_container_id(requested_id)
+            .await
+            .map_err(|status| match status.code() {
+                tonic::Code::NotFound => {
+                    ContainerCopyError::NotFound(format!("container {} not found", requested_id))
+                }
+                _ => ContainerCopyError::InvalidArgument(status.message().to_string()),
+            })?;
+        let pid = self
+            .runtime_container_pid_checked(&container_id)
+            .await
+            .filter(|pid| *pid > 0)
+            .ok_or_else(|| {
+                ContainerCopyError::FailedPrecondition(format!(
+                    "container {} has no running process; copying requires a created or running container",
+                    container_id
+                ))
+            })?;
+        Ok((container_id, pid))
+    }
+
     async fn create_container_from_input(
         &self,
         input: ContainerCreateInput,
@@ -1897,6 +2145,7 @@ impl RuntimeServiceImpl {
             mut config,
             sandbox_config,
             owner,
+            debug_target,
         } = input;
         let pod_sandbox_id = owner.pod_sandbox_id().to_string();
         let mut container_metadata = config
@@ -1929,9 +2178,10 @@ impl RuntimeServiceImpl {
         };
 
         let container_id = uuid::Uuid::new_v4().to_simple().to_string();
-        if config.log_path.trim().is_empty()
-            && Self::should_assign_default_log_path(&owner, &config)
-        {
+        // kubelet 自己轮转它指定的日志；只有 daemon 分配的日志路径才交给内建轮转。
+        let daemon_managed_log = config.log_path.trim().is_empty()
+            && (debug_target.is_some() || Self::should_assign_default_log_path(&owner, &config));
+        if daemon_managed_log {
             config.log_path = self.default_container_log_path(&container_id);
         }
         let mut container_name_guard = self
@@ -2076,6 +2326,16 @@ impl RuntimeServiceImpl {
                 .as_ref()
                 .and_then(|state| state.netns_path.as_ref().map(PathBuf::from))
         });
+        let network_target = namespace_options
+            .as_ref()
+            .filter(|options| options.network == NamespaceMode::Target as i32);
+        let network_namespace_path = match network_target {
+            Some(options) => {
+                self.runtime_namespace_path_for_target(&options.target_id, "net")
+                    .await?
+            }
+            None => network_namespace_path,
+        };
         let pause_container_id = match &owner {
             ContainerOwner::Local { .. } => None,
             ContainerOwner::Pod { pod_sandbox_id } => {
@@ -2161,7 +2421,10 @@ impl RuntimeServiceImpl {
                     .filter(|handler| !handler.is_empty())
             })
             .unwrap_or(self.config.runtime.as_str());
-        let local_network = if matches!(owner, ContainerOwner::Local { .. }) && !host_network {
+        let local_network = if matches!(owner, ContainerOwner::Local { .. })
+            && !host_network
+            && network_target.is_none()
+        {
             Some(
                 self.setup_local_container_network(
                     &container_id,
@@ -2377,6 +2640,8 @@ impl RuntimeServiceImpl {
                         .and_then(|state| state.cgroup_parent.clone())
                 }),
             log_path: log_path.as_ref().map(|path| path.display().to_string()),
+            daemon_managed_log,
+            ephemeral_debug_target: debug_target,
             tty: config.tty,
             stdin: config.stdin,
             stdin_once: config.stdin_once,
@@ -15,7 +15,8 @@ impl RuntimeServiceImpl {
         containers: Arc<Mutex<HashMap<String, Container>>>,
         pod_sandboxes: Arc<Mutex<HashMap<String, crate::proto::runtime::v1::PodSandbox>>>,
         persistence: Arc<Mutex<PersistenceManager>>,
-        events: tokio::sync::broadcast::Sender<ContainerEventResponse>,
+        events: crate::services::EventService,
+        oom_observations: Arc<std::sync::Mutex<oom_monitor::OomKillObservations>>,
         exit_monitors: Arc<Mutex<HashSet<String>>>,
     ) {
         let Ok(handle) = tokio::runtime::Handle::try_current() else {
@@ -65,9 +66,15 @@ impl RuntimeServiceImpl {
             };
 
             if let Some(exit_code) = exit_code {
+                let oom_kill =
+                    tokio::fs::read_to_string(crate::shim_rpc::oom_kill_file_path(&exit_code_path))
+                        .await
+                        .ok()
+                        .and_then(|raw| raw.trim().parse::<u64>().ok());
                 Self::record_container_exit_from_monitor(
                     &container_id,
                     exit_code,
+                    oom_kill,
                     &config,
                     &nri_config,
                     &runtime,
@@ -76,6 +83,7 @@ impl RuntimeServiceImpl {
                     &pod_sandboxes,
                     &persistence,
                     &events,
+                    &oom_observations,
                 )
                 .await;
             }
@@ -158,6 +166,7 @@ impl RuntimeServiceImpl {
         let pod_sandboxes = self.pod_sandboxes.clone();
         let persistence = self.persistence.clone();
         let events = self.events.clone();
+        let oom_observations = self.oom_observations.clone();
         let exit_monitors = self.exit_monitors.clone();
 
         let Ok(handle) = tokio::runtime::Handle::try_current() else {
@@ -186,6 +195,7 @@ impl RuntimeServiceImpl {
                 pod_sandboxes,
                 persistence,
                 events,
+                oom_observations,
                 exit_monitors,
             );
         });
@@ -505,7 +515,7 @@ impl RuntimeServiceImpl {
         container: &Container,
         runtime_state: Option<i32>,
     ) {
-        if self.events.receiver_count() == 0 {
+        if !self.events.has_cri_receivers() {
             return;
         }
         let pod_status = self
@@ -530,7 +540,7 @@ impl RuntimeServiceImpl {
         pod_sandbox: &crate::proto::runtime::v1::PodSandbox,
         containers_statuses: Vec<CriContainerStatus>,
     ) {
-        if !self.config.enable_pod_events || self.events.receiver_count() == 0 {
+        if !self.config.enable_pod_events || !self.events.has_cri_receivers() {
             return;
         }
         self.publish_event(ContainerEventResponse {
@@ -543,18 +553,17 @@ impl RuntimeServiceImpl {
     }
 
     pub(super) fn publish_event_via_sender(
-        events: &tokio::sync::broadcast::Sender<ContainerEventResponse>,
+        events: &crate::services::EventService,
         event: ContainerEventResponse,
     ) {
-        if let Err(err) = events.send(event) {
-            log::debug!("Dropping CRI event without subscribers: {}", err);
-        }
+        events.publish(event);
     }
 
     #[allow(clippy::too_many_arguments)]
     pub(super) async fn record_container_exit_from_monitor(
         container_id: &str,
         exit_code: i32,
+        oom_kill: Option<u64>,
         config: &RuntimeConfig,
         nri_config: &NriConfig,
         runtime: &RuntimeRegistry,
@@ -562,7 +571,8 @@ impl RuntimeServiceImpl {
         containers: &Arc<Mutex<HashMap<String, Container>>>,
         pod_sandboxes: &Arc<Mutex<HashMap<String, crate::proto::runtime::v1::PodSandbox>>>,
         persistence: &Arc<Mutex<PersistenceManager>>,
-        events: &tokio::sync::broadcast::Sender<ContainerEventResponse>,
+        events: &crate::services::EventService,
+        oom_observations: &std::sync::Mutex<oom_monitor::OomKillObservations>,
     ) {
         let now = Self::now_nanos();
         let (updated_container, should_notify_nri_stop) = {
@@ -760,6 +770,14 @@ impl RuntimeServiceImpl {
                 containers_statuses: vec![snapshot],
             },
         );
+        Self::publish_container_exit_oom_event(
+            container_id,
+            exit_code,
+            oom_kill,
+            oom_observations,
+            events,
+        )
+        .await;
 
         if let Some(updated_pod) = updated_pod {
             let container_snapshots = {
@@ -814,7 +832,7 @@ impl RuntimeServiceImpl {
         containers: &Arc<Mutex<HashMap<String, Container>>>,
         pod_sandboxes: &Arc<Mutex<HashMap<String, crate::proto::runtime::v1::PodSandbox>>>,
         persistence: &Arc<Mutex<PersistenceManager>>,
-        events: &tokio::sync::broadcast::Sender<ContainerEventResponse>,
+        events: &crate::services::EventService,
     ) {
         let now = Self::now_nanos();
         let updated_pod = {
@@ -876,7 +894,7 @@ impl RuntimeServiceImpl {
         containers: &Arc<Mutex<HashMap<String, Container>>>,
         pod_sandboxes: &Arc<Mutex<HashMap<String, crate::proto::runtime::v1::PodSandbox>>>,
         persistence: &Arc<Mutex<PersistenceManager>>,
-        events: &tokio::sync::broadcast::Sender<ContainerEventResponse>,
+        events: &crate::services::EventService,
     ) {
         let container_ids: Vec<String> = {
             let containers = containers.lock().await;
@@ -1139,3 +1157,74 @@ impl RuntimeServiceImpl {
         }
     }
 }
+
+/// GetContainerEvents 订阅者溢出后的补发来源：每个容器一条与其当前状态对应的事件，
+/// 附带所在 pod 的状态与全部容器状态，kubelet 据此刷新缓存而不必等待下一次 relist。
+pub(super) struct ContainerStateResync {
+    pub(super) containers: Arc<Mutex<HashMap<String, Container>>>,
+    pub(super) pod_sandboxes: Arc<Mutex<HashMap<String, crate::proto::runtime::v1::PodSandbox>>>,
+    pub(super) config: RuntimeConfig,
+}
+
+#[tonic::async_trait]
+impl crate::services::ContainerEventResync for ContainerStateResync {
+    async fn current_events(&self) -> Vec<ContainerEventResponse> {
+        let pods = self.pod_sandboxes.lock().await.clone();
+        let mut containers = self
+            .containers
+            .lock()
+            .await
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+        containers.sort_by(|left, right| {
+            (
+                left.pod_sandbox_id.as_str(),
+                left.created_at,
+                left.id.as_str(),
+            )
+                .cmp(&(
+                    right.pod_sandbox_id.as_str(),
+                    right.created_at,
+                    right.id.as_str(),
+                ))
+        });
+        let created_at = RuntimeServiceImpl::now_nanos();
+        containers
+            .iter()
+            .filter_map(|container| {
+                let event_type = match ContainerState::try_from(container.state) {
+                    Ok(ContainerState::ContainerCreated) => {
+                        ContainerEventType::ContainerCreatedEvent
+                    }
+                    Ok(ContainerState::ContainerRunning) => {
+                        ContainerEventType::ContainerStartedEvent
+                    }
+                    Ok(ContainerState::ContainerExited) => {
+                        ContainerEventType::ContainerStoppedEvent
+                    }
+                    _ => return None,
+                };
+                let containers_statuses = containers
+                    .iter()
+                    .filter(|sibling| sibling.pod_sandbox_id == container.pod_sandbox_id)
+                    .map(|sibling| {
+                        RuntimeServiceImpl::build_container_status_snapshot(sibling, sibling.state)
+                    })
+                    .collect();
+                Some(ContainerEventResponse {
+                    container_id: container.id.clone(),
+                    container_event_type: event_type as i32,
+                    created_at,
+                    pod_sandbox_status: pods.get(&container.pod_sandbox_id).map(|pod| {
+                        RuntimeServiceImpl::build_pod_sandbox_status_snapshot_with_config(
+                            &self.config,
+                            pod,
+                        )
+                    }),
+                    containers_statuses,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,334 @@
+//! daemon 内建的容器日志轮转
+//!
+//! 没有 kubelet 时（`crs run` 与 `LocalService`），容器日志路径由 daemon 分配，也就没有外部
+//! rotator 调用 `ReopenContainerLog`。启用 `logging.container_log_rotation` 后，daemon 周期检查
+//! 这些日志：超过大小阈值时把 `<log>.N[.gz]` 依次后移、把当前日志改名为 `<log>.1`，
+//! 通过 shim reopen socket 让 shim 切到新文件，最后压缩刚轮转出的文件。
+//! kubelet 创建的容器日志始终交给 kubelet 轮转。
+
+use super::*;
+use crate::config::ContainerLogRotationConfig;
+use std::fs;
+use std::io::Read;
+
+const GZIP_COMPRESSION_LEVEL: u8 = 6;
+const GZIP_CHUNK_SIZE: usize = 64 * 1024;
+
+/// 单个容器的轮转统计，只保存在内存中，供 inspect 展示。
+#[derive(Debug, Clone, Default)]
+pub(super) struct ContainerLogRotationStatus {
+    pub(super) rotations: u64,
+    pub(super) last_rotated_at: Option<i64>,
+    pub(super) last_error: Option<String>,
+}
+
+pub(super) fn rotated_log_path(log_path: &Path, index: usize, compressed: bool) -> PathBuf {
+    let mut path = log_path.as_os_str().to_os_string();
+    path.push(format!(".{index}"));
+    if compressed {
+        path.push(".gz");
+    }
+    PathBuf::from(path)
+}
+
+fn remove_if_exists(path: &Path) -> std::io::Result<()> {
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+fn rename_if_exists(from: &Path, to: &Path) -> std::io::Result<()> {
+    match fs::rename(from, to) {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+const CRC32_TABLE: [u32; 256] = crc32_table();
+
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut index = 0;
+    while index < 256 {
+        let mut value = index as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            value = if value & 1 == 1 {
+                0xedb8_8320 ^ (value >> 1)
+            } else {
+                value >> 1
+            };
+            bit += 1;
+        }
+        table[index] = value;
+        index += 1;
+    }
+    table
+}
+
+/// 累加 CRC-32；初值为 `!0`，全部数据处理完后取反得到校验和。
+fn crc32_update(crc: u32, data: &[u8]) -> u32 {
+    data.iter().fold(crc, |crc, byte| {
+        CRC32_TABLE[((crc ^ u32::from(*byte)) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+/// 把 `source` 压缩为 gzip 格式的 `target`，先写临时文件再改名，成功后删除源文件。
+/// 按 `GZIP_CHUNK_SIZE` 分块读取和压缩，内存占用与日志大小无关。
+pub(super) fn gzip_file(source: &Path, target: &Path) -> std::io::Result<()> {
+    use miniz_oxide::deflate::core::{
+        compress_to_output, create_comp_flags_from_zip_params, CompressorOxide, TDEFLFlush,
+        TDEFLStatus,
+    };
+
+    let mut input = fs::File::open(source)?;
+    let mut temp = target.as_os_str().to_os_string();
+    temp.push(".tmp");
+    let temp = PathBuf::from(temp);
+    let mut output = std::io::BufWriter::new(fs::File::create(&temp)?);
+    // RFC 1952 头：无文件名、无时间戳，OS = Unix。
+    output.write_all(&[0x1f, 0x8b, 0x08, 0, 0, 0, 0, 0, 0, 0x03])?;
+
+    let mut compressor = CompressorOxide::new(create_comp_flags_from_zip_params(
+        GZIP_COMPRESSION_LEVEL.into(),
+        0,
+        0,
+    ));
+    let mut buffer = vec![0u8; GZIP_CHUNK_SIZE];
+    let mut crc = !0u32;
+    let mut size = 0u64;
+    loop {
+        let read = match input.read(&mut buffer) {
+            Ok(read) => read,
+            Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+        let mut chunk = &buffer[..read];
+        crc = crc32_update(crc, chunk);
+        size += read as u64;
+        let flush = if read == 0 {
+            TDEFLFlush::Finish
+        } else {
+            TDEFLFlush::None
+        };
+        loop {
+            let mut write_error = None;
+            let (status, consumed) = compress_to_output(&mut compressor, chunk, flush, |bytes| {
+                output
+                    .write_all(bytes)
+                    .map_err(|err| write_error = Some(err))
+                    .is_ok()
+            });
+            if let Some(err) = write_error {
+                return Err(err);
+            }
+            match status {
+                TDEFLStatus::Done => break,
+                TDEFLStatus::Okay => {
+                    chunk = &chunk[consumed.min(chunk.len())..];
+                    // 收尾时要一直调用到 `Done`，确保最后的块和结束标记都已写出。
+                    if chunk.is_empty() && read != 0 {
+                        break;
+                    }
+                }
+                status => {
+                    return Err(std::io::Error::other(format!(
+                        "deflate failed with status {status:?}"
+                    )))
+                }
+            }
+        }
+        if read == 0 {
+            break;
+        }
+    }
+
+    output.write_all(&(!crc).to_le_bytes())?;
+    // ISIZE 为原始长度对 2^32 取模。
+    output.write_all(&(size as u32).to_le_bytes())?;
+    output
+        .into_inner()
+        .map_err(|err| err.into_error())?
+        .sync_all()?;
+    fs::rename(&temp, target)?;
+    fs::remove_file(source)
+}
+
+/// 日志超过阈值时执行一次轮转；`reopen` 负责让 shim 切换到新文件。返回是否发生了轮转。
+pub(super) fn rotate_log_file(
+    log_path: &Path,
+    config: &ContainerLogRotationConfig,
+    reopen: impl FnOnce() -> anyhow::Result<()>,
+) -> anyhow::Result<bool> {
+    let size = match fs::metadata(log_path) {
+        Ok(metadata) => metadata.len(),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+        Err(err) => return Err(err.into()),
+    };
+    if size < config.max_size {
+        return Ok(false);
+    }
+
+    let max_files = config.max_files.max(1);
+    remove_if_exists(&rotated_log_path(log_path, max_files, false))?;
+    remove_if_exists(&rotated_log_path(log_path, max_files, true))?;
+    for index in (1..max_files).rev() {
+        for compressed in [false, true] {
+            rename_if_exists(
+                &rotated_log_path(log_path, index, compressed),
+                &rotated_log_path(log_path, index + 1, compressed),
+            )?;
+        }
+    }
+    let rotated = rotated_log_path(log_path, 1, false);
+    fs::rename(log_path, &rotated)?;
+    reopen()?;
+    if config.compress {
+        gzip_file(&rotated, &rotated_log_path(log_path, 1, true))?;
+    }
+    Ok(true)
+}
+
+impl RuntimeServiceImpl {
+    /// daemon 只轮转自己分配路径的日志；kubelet 管理的容器日志由 kubelet 负责。
+    fn log_rotation_disabled_reason(
+        &self,
+        container_state: Option<&StoredContainerState>,
+    ) -> Option<&'static str> {
+        if !self.config.container_log_rotation.enabled {
+            return Some("disabled");
+        }
+        let Some(state) = container_state else {
+            return Some("no-state");
+        };
+        if state.log_path.as_deref().is_none_or(str::is_empty) {
+            return Some("no-log-path");
+        }
+        if !state.daemon_managed_log {
+            return Some("kubelet-managed");
+        }
+        None
+    }
+
+    pub(super) fn container_log_rotation_info(
+        &self,
+        container_id: &str,
+        container_state: Option<&StoredContainerState>,
+    ) -> serde_json::Value {
+        let config = &self.config.container_log_rotation;
+        let disabled_reason = self.log_rotation_disabled_reason(container_state);
+        let status = self
+            .log_rotation_status
+            .lock()
+            .ok()
+            .and_then(|statuses| statuses.get(container_id).cloned())
+            .unwrap_or_default();
+        json!({
+            "enabled": disabled_reason.is_none(),
+            "disabledReason": disabled_reason,
+            "maxSize": config.max_size,
+            "maxFiles": config.max_files,
+            "compress": config.compress,
+            "rotations": status.rotations,
+            "lastRotatedAt": status.last_rotated_at,
+            "lastError": status.last_error,
+        })
+    }
+
+    /// 检查一轮所有可轮转的运行中容器，返回本轮轮转的容器数。
+    pub(super) async fn rotate_container_logs_once(&self) -> usize {
+        let candidates = {
+            let containers = self.containers.lock().await;
+            if let Ok(mut statuses) = self.log_rotation_status.lock() {
+                statuses.retain(|id, _| containers.contains_key(id));
+            }
+            containers
+                .values()
+                .filter_map(|container| {
+                    let state = Self::read_internal_state::<StoredContainerState>(
+                        &container.annotations,
+                        INTERNAL_CONTAINER_STATE_KEY,
+                    );
+                    if self.log_rotation_disabled_reason(state.as_ref()).is_some() {
+                        return None;
+                    }
+                    state
+                        .and_then(|state| state.log_path)
+                        .map(|log_path| (container.id.clone(), PathBuf::from(log_path)))
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut rotated = 0;
+        for (container_id, log_path) in candidates {
+            if !matches!(
+                self.runtime_container_status_checked(&container_id).await,
+                ContainerStatus::Running
+            ) {
+                continue;
+            }
+            // 改名、reopen 与压缩都是阻塞 IO，压缩大日志可能耗时较长，放到阻塞线程池执行。
+            let runtime = self.runtime.clone();
+            let config = self.config.container_log_rotation.clone();
+            let rotate_container_id = container_id.clone();
+            let rotate_log_path = log_path.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                rotate_log_file(&rotate_log_path, &config, || {
+                    runtime.reopen_container_log(&rotate_container_id)
+                })
+            })
+            .await
+            .unwrap_or_else(|err| Err(anyhow::anyhow!("log rotation task failed: {err}")));
+            let Ok(mut statuses) = self.log_rotation_status.lock() else {
+                continue;
+            };
+            let status = statuses.entry(container_id.clone()).or_default();
+            match result {
+                Ok(true) => {
+                    rotated += 1;
+                    status.rotations += 1;
+                    status.last_rotated_at = Some(chrono::Utc::now().timestamp_millis());
+                    status.last_error = None;
+                    log::info!(
+                        "Rotated container {} log {}",
+                        container_id,
+                        log_path.display()
+                    );
+                }
+                Ok(false) => {}
+                Err(err) => {
+                    log::warn!(
+                        "Failed to rotate container {} log {}: {:#}",
+                        container_id,
+                        log_path.display(),
+                        err
+                    );
+                    status.last_error = Some(format!("{:#}", err));
+                }
+            }
+        }
+        rotated
+    }
+
+    pub(super) fn spawn_container_log_rotator(&self) {
+        if !self.config.container_log_rotation.enabled {
+            return;
+        }
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let service = self.clone_for_background();
+        let interval = self.config.container_log_rotation.check_interval;
+        let mut shutdown = self.reload_watcher_shutdown.subscribe();
+        handle.spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = shutdown.recv() => break,
+                    _ = tokio::time::sleep(interval) => {}
+                }
+                service.rotate_container_logs_once().await;
+            }
+        });
+    }
+}
@@ -16,6 +16,7 @@ use tokio::process::Command as TokioCommand;
 use tokio::sync::Mutex;
 use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Request, Response, Status};
+use tracing::Instrument;
 
 use crate::proto::runtime::v1::{
     runtime_service_server::RuntimeService, Container, ContainerState,
@@ -68,6 +69,8 @@ use crate::streaming::StreamingServer;
 mod annotations;
 mod container_handlers;
 mod events;
+mod log_rotation;
+mod oom_monitor;
 mod pod_handlers;
 mod recovery;
 mod responses;
@@ -535,29 +538,41 @@ impl RuntimeServiceImpl {
         Ok(cidrs)
     }
 
-    fn routes_for_runtime_pod_cidrs(cidrs: &[String]) -> anyhow::Result<Vec<String>> {
-        let mut has_v4 = false;
-        let mut has_v6 = false;
+    fn pod_cidr_is_ipv6(cidr: &str) -> anyhow::Result<bool> {
+        let (ip, _) = cidr
+            .split_once('/')
+            .ok_or_else(|| anyhow::anyhow!("CIDR {} is missing '/'", cidr))?;
+        let ip: std::net::IpAddr = ip
+            .trim()
+            .parse()
+            .with_context(|| format!("invalid IP in CIDR {}", cidr))?;
+        Ok(ip.is_ipv6())
+    }
+
+    /// 每个地址族的第一个 PodCIDR，返回 `(IPv4, IPv6)`，缺失的地址族为空字符串。
+    fn pod_cidrs_by_family(cidrs: &[String]) -> anyhow::Result<(String, String)> {
+        let mut v4 = String::new();
+        let mut v6 = String::new();
         for cidr in cidrs {
-            let (ip, _) = cidr
-                .split_once('/')
-                .ok_or_else(|| anyhow::anyhow!("CIDR {} is missing '/'", cidr))?;
-            let ip: std::net::IpAddr = ip
-                .trim()
-                .parse()
-                .with_context(|| format!("invalid IP in CIDR {}", cidr))?;
-            if ip.is_ipv4() {
-                has_v4 = true;
+            let slot = if Self::pod_cidr_is_ipv6(cidr)? {
+                &mut v6
             } else {
-                has_v6 = true;
+                &mut v4
+            };
+            if slot.is_empty() {
+                *slot = cidr.clone();
             }
         }
+        Ok((v4, v6))
+    }
 
+    fn routes_for_runtime_pod_cidrs(cidrs: &[String]) -> anyhow::Result<Vec<String>> {
+        let (v4, v6) = Self::pod_cidrs_by_family(cidrs)?;
         let mut routes = Vec::new();
-        if has_v4 {
+        if !v4.is_empty() {
             routes.push("0.0.0.0/0".to_string());
         }
-        if has_v6 {
+        if !v6.is_empty() {
             routes.push("::/0".to_string());
         }
         Ok(routes)
@@ -580,6 +595,69 @@ impl RuntimeServiceImpl {
         Ok(rendered)
     }
 
+    /// 查找与 `body_start` 之前的 `{{if`/`{{range` 配对的 `{{end}}` 位置，支持嵌套。
+    fn find_template_block_end(rendered: &str, body_start: usize) -> anyhow::Result<usize> {
+        let end_tag = "{{end}}";
+        let mut depth = 1usize;
+        let mut search_from = body_start;
+        loop {
+            let next_if = rendered[search_from..]
+                .find("{{if")
+                .map(|offset| search_from + offset);
+            let next_range = rendered[search_from..]
+                .find("{{range")
+                .map(|offset| search_from + offset);
+            let next_end = rendered[search_from..]
+                .find(end_tag)
+                .map(|offset| search_from + offset)
+                .ok_or_else(|| anyhow::anyhow!("unterminated block in CNI template"))?;
+
+            let next_open = [next_if, next_range]
+                .into_iter()
+                .flatten()
+                .filter(|pos| *pos < next_end)
+                .min();
+            match next_open {
+                Some(next_open) => {
+                    depth += 1;
+                    search_from = next_open + 2;
+                }
+                None => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(next_end);
+                    }
+                    search_from = next_end + end_tag.len();
+                }
+            }
+        }
+    }
+
+    /// 渲染 `{{if .Field}}...{{end}}`：字段非空时保留块内容，否则整块删除。
+    fn render_field_conditionals(
+        template: &str,
+        name: &str,
+        present: bool,
+    ) -> anyhow::Result<String> {
+        let mut rendered = template.to_string();
+        for start_tag in [
+            format!("{{{{if .{name}}}}}"),
+            format!("{{{{ if .{name} }}}}"),
+        ] {
+            while let Some(start) = rendered.find(&start_tag) {
+                let body_start = start + start_tag.len();
+                let body_end = Self::find_template_block_end(&rendered, body_start)?;
+                let replacement = if present {
+                    rendered[body_start..body_end].to_string()
+                } else {
+                    String::new()
+                };
+                rendered.replace_range(start..body_end + "{{end}}".len(), &replacement);
+            }
+        }
+        Ok(rendered)
+    }
+
     fn render_repeated_section(
         template: &str,
         start_tag: &str,
@@ -590,45 +668,7 @@ impl RuntimeServiceImpl {
         let end_tag = "{{end}}";
         while let Some(start) = rendered.find(start_tag) {
             let body_start = start + start_tag.len();
-            let mut depth = 1usize;
-            let mut search_from = body_start;
-            let body_end = loop {
-                let next_if = rendered[search_from..]
-                    .find("{{if")
-                    .map(|offset| search_from + offset);
-                let next_range = rendered[search_from..]
-                    .find("{{range")
-                    .map(|offset| search_from + offset);
-                let next_end = rendered[search_from..]
-                    .find(end_tag)
-                    .map(|offset| search_from + offset)
-                    .ok_or_else(|| anyhow::anyhow!("unterminated range block in CNI template"))?;
-
-                let mut next_control = next_end;
-                let mut control_kind = "end";
-                if let Some(next_if) = next_if.filter(|pos| *pos < next_control) {
-                    next_control = next_if;
-                    control_kind = "if";
-                }
-                if let Some(next_range) = next_range.filter(|pos| *pos < next_control) {
-                    next_control = next_range;
-                    control_kind = "range";
-                }
-
-                match control_kind {
-                    "if" | "range" => {
-                        depth += 1;
-                        search_from = next_control + 2;
-                    }
-                    _ => {
-                        depth -= 1;
-                        if depth == 0 {
-                            break next_control;
-                        }
-                        search_from = next_control + end_tag.len();
-                    }
-                }
-            };
+            let body_end = Self::find_template_block_end(&rendered, body_start)?;
 
             let body = rendered[body_start..body_end].to_string();
             let mut section = String::new();
@@ -656,6 +696,12 @@ impl RuntimeServiceImpl {
         context: &CniTemplateContext,
     ) -> anyhow::Result<String> {
         let mut rendered = template.to_string();
+        for (name, value) in [
+            ("PodCIDRv4", &context.pod_cidr_v4),
+            ("PodCIDRv6", &context.pod_cidr_v6),
+        ] {
+            rendered = Self::render_field_conditionals(&rendered, name, !value.is_empty())?;
+        }
         rendered = Self::render_repeated_section(
             &rendered,
             "{{range $i, $range := .PodCIDRRanges}}",
@@ -668,6 +714,8 @@ impl RuntimeServiceImpl {
             "{{$route}}",
             &context.routes,
         )?;
+        rendered = Self::replace_scalar_placeholders(&rendered, "PodCIDRv4", &context.pod_cidr_v4);
+        rendered = Self::replace_scalar_placeholders(&rendered, "PodCIDRv6", &context.pod_cidr_v6);
         rendered = Self::replace_scalar_placeholders(&rendered, "PodCIDR", &context.pod_cidr);
         rendered = Self::replace_scalar_placeholders(
             &rendered,
@@ -708,6 +756,7 @@ impl RuntimeServiceImpl {
         let runtime_network_config = runtime_network_config.expect("checked above");
         let cidrs = Self::parse_runtime_pod_cidrs(&runtime_network_config.pod_cidr)?;
         let routes = Self::routes_for_runtime_pod_cidrs(&cidrs)?;
+        let (pod_cidr_v4, pod_cidr_v6) = Self::pod_cidrs_by_family(&cidrs)?;
         let template = std::fs::read_to_string(template_path).with_context(|| {
             format!(
                 "Failed to read CNI config template {}",
@@ -720,6 +769,8 @@ impl RuntimeServiceImpl {
                 pod_cidr: cidrs[0].clone(),
                 pod_cidr_ranges: cidrs,
                 routes,
+                pod_cidr_v4,
+                pod_cidr_v6,
             },
         )?;
 
@@ -2356,14 +2407,12 @@ impl RuntimeServiceImpl {
     }
 
     fn container_writable_layer_usage(
-        &self,
+        root_dir: &Path,
         container_id: &str,
     ) -> Option<crate::proto::runtime::v1::FilesystemUsage> {
         use crate::proto::runtime::v1::{FilesystemIdentifier, FilesystemUsage, UInt64Value};
 
-        let rootfs_path = self
-            .config
-            .root_dir
+        let rootfs_path = root_dir
             .join("containers")
             .join(container_id)
             .join("rootfs");
@@ -3453,7 +3502,14 @@ impl RuntimeService for RuntimeServiceImpl {
         &self,
         _request: Request<GetEventsRequest>,
     ) -> Result<Response<Self::GetContainerEventsStream>, Status> {
-        Ok(Response::new(self.internal_services.events.stream()))
+        let resync = Arc::new(events::ContainerStateResync {
+            containers: self.containers.clone(),
+            pod_sandboxes: self.pod_sandboxes.clone(),
+            config: self.config.clone(),
+        });
+        Ok(Response::new(
+            self.internal_services.events.stream_with_resync(resync),
+        ))
     }
 
     //
@@ -0,0 +1,186 @@
+//! 容器 OOM 事件监视
+//!
+//! 周期读取运行中容器 cgroup 的 `memory.events`（cgroup v1 为 `memory.oom_control`），
+//! 比较 `oom_kill` 计数：计数增长说明容器内有进程被内核 OOM killer 杀死，此时发布
+//! `container.oom` 内部事件（reason 为 `OOMKilled`）。daemon 启动后的第一轮只记录基线，
+//! 不为历史计数补发事件。
+//!
+//! 主进程被 OOM 杀死时容器随即退出，shim 在写退出码前已经 `delete` 掉容器和 cgroup，
+//! 轮询读不到最终计数。shim 在删除前把 `oom_kill` 计数写到退出码旁的 `.oom` 文件，
+//! 退出监视在容器以 137 退出且该计数大于 0 时补发同一事件。
+
+use super::*;
+use crate::metrics::MemoryEvents;
+use crate::services::{InternalEvent, InternalEventSeverity};
+use std::sync::Mutex as StdMutex;
+use std::time::Duration;
+
+const OOM_MONITOR_INTERVAL: Duration = Duration::from_secs(5);
+/// 被 SIGKILL 结束的容器退出码。SIGKILL 也可能来自停止超时或手动 kill，
+/// 因此只有 cgroup 同时记录到 OOM kill 时才视为 `OOMKilled`。
+const SIGKILL_EXIT_CODE: i32 = 137;
+
+/// 轮询和退出监视共享的 OOM 观测状态。
+#[derive(Debug, Default)]
+pub(super) struct OomKillObservations {
+    /// 各运行中容器上一轮的 `oom_kill` 计数；`None` 表示尚未建立基线。
+    pub(super) counts: Option<HashMap<String, u64>>,
+    /// 最后一次被轮询看到运行时已经上报过 OOM 的容器，退出监视据此避免重复上报。
+    pub(super) reported: HashSet<String>,
+}
+
+/// 对比上一轮记录的 `oom_kill` 计数，返回本轮需要上报的容器。
+/// `previous` 为 `None` 表示尚未建立基线；基线之后新出现的容器若计数已大于 0 同样上报。
+pub(super) fn oom_kill_increases(
+    previous: Option<&HashMap<String, u64>>,
+    current: &HashMap<String, MemoryEvents>,
+) -> Vec<(String, MemoryEvents)> {
+    let Some(previous) = previous else {
+        return Vec::new();
+    };
+    let mut increases = current
+        .iter()
+        .filter(|(id, events)| events.oom_kill > previous.get(*id).copied().unwrap_or(0))
+        .map(|(id, events)| (id.clone(), *events))
+        .collect::<Vec<_>>();
+    increases.sort_by(|left, right| left.0.cmp(&right.0));
+    increases
+}
+
+impl RuntimeServiceImpl {
+    /// 检查一轮运行中容器的 OOM 计数，返回本轮发布的 OOMKilled 事件数。
+    pub(super) async fn observe_container_oom_events_once(&self) -> usize {
+        let (known, candidates) = {
+            let containers = self.containers.lock().await;
+            let known = containers.keys().cloned().collect::<HashSet<_>>();
+            let candidates = containers
+                .values()
+                .filter(|container| container.state == ContainerState::ContainerRunning as i32)
+                .map(|container| {
+                    let cgroup_parent = Self::read_internal_state::<StoredContainerState>(
+                        &container.annotations,
+                        INTERNAL_CONTAINER_STATE_KEY,
+                    )
+                    .and_then(|state| state.cgroup_parent)
+                    .map(PathBuf::from)
+                    .unwrap_or_else(|| PathBuf::from("/sys/fs/cgroup"));
+                    (container.id.clone(), cgroup_parent)
+                })
+                .collect::<Vec<_>>();
+            (known, candidates)
+        };
+        let Ok(collector) = MetricsCollector::new() else {
+            return 0;
+        };
+
+        // 只读取 cgroup 文件，不调用运行时查询状态，保证轮询足够轻量。
+        let current = candidates
+            .into_iter()
+            .filter_map(|(container_id, cgroup_parent)| {
+                collector
+                    .collect_memory_events(&container_id, &cgroup_parent)
+                    .map(|events| (container_id, events))
+            })
+            .collect::<HashMap<_, _>>();
+
+        let increases = {
+            let Ok(mut observed) = self.oom_observations.lock() else {
+                return 0;
+            };
+            let increases = oom_kill_increases(observed.counts.as_ref(), &current);
+            observed.counts = Some(
+                current
+                    .iter()
+                    .map(|(id, events)| (id.clone(), events.oom_kill))
+                    .collect(),
+            );
+            // 已上报的容器一直保留到退出监视消费或容器被删除。
+            observed.reported.retain(|id| known.contains(id));
+            observed
+                .reported
+                .extend(increases.iter().map(|(id, _)| id.clone()));
+            increases
+        };
+
+        for (container_id, events) in &increases {
+            log::warn!(
+                "Container {} was OOM killed (oom_kill={}, oom={})",
+                container_id,
+                events.oom_kill,
+                events.oom
+            );
+            self.publish_container_lifecycle_event(
+                container_id,
+                "oom",
+                InternalEventSeverity::Warning,
+                json!({
+                    "reason": "OOMKilled",
+                    "oomKill": events.oom_kill,
+                    "oom": events.oom,
+                }),
+            )
+            .await;
+        }
+        increases.len()
+    }
+
+    /// 退出监视在容器以 137 退出、且 shim 记录的 `oom_kill` 计数大于 0 时补发 `container.oom`；
+    /// 轮询已上报过的容器不再重复。返回是否发布。
+    pub(super) async fn publish_container_exit_oom_event(
+        container_id: &str,
+        exit_code: i32,
+        oom_kill: Option<u64>,
+        observations: &StdMutex<OomKillObservations>,
+        events: &crate::services::EventService,
+    ) -> bool {
+        let reported_by_poller = observations
+            .lock()
+            .map(|mut observed| observed.reported.remove(container_id))
+            .unwrap_or(false);
+        let Some(oom_kill) = oom_kill.filter(|count| *count > 0) else {
+            return false;
+        };
+        if exit_code != SIGKILL_EXIT_CODE || reported_by_poller {
+            return false;
+        }
+
+        log::warn!(
+            "Container {} exited with code {} after {} OOM kill(s), reporting OOMKilled",
+            container_id,
+            exit_code,
+            oom_kill
+        );
+        let event = InternalEvent::new(
+            "container.oom",
+            "container",
+            container_id,
+            InternalEventSeverity::Warning,
+            json!({
+                "reason": "OOMKilled",
+                "exitCode": exit_code,
+                "oomKill": oom_kill,
+            }),
+        );
+        if let Err(err) = events.publish_internal(event).await {
+            log::debug!("Failed to publish container oom event for {container_id}: {err}");
+        }
+        true
+    }
+
+    pub(super) fn spawn_container_oom_monitor(&self) {
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let service = self.clone_for_background();
+        let mut shutdown = self.reload_watcher_shutdown.subscribe();
+        handle.spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = shutdown.recv() => break,
+                    _ = tokio::time::sleep(OOM_MONITOR_INTERVAL) => {}
+                }
+                service.observe_container_oom_events_once().await;
+            }
+        });
+    }
+}
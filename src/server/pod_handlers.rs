@@ -228,6 +228,11 @@ impl RuntimeServiceImpl {
                     "hostPort mapping is disabled; skipping fallback hostPort cleanup for pod {}",
                     pod_id
                 );
+            } else if self.config.rootless.enabled {
+                log::debug!(
+                    "hostPorts are forwarded by the rootless network helper; skipping fallback hostPort cleanup for pod {}",
+                    pod_id
+                );
             } else if let Ok(port_manager) = crate::network::PortMappingManager::auto() {
                 for mapping in state.port_mappings.iter().rev() {
                     let protocol = match mapping.protocol.to_ascii_uppercase().as_str() {
@@ -742,6 +747,11 @@ impl RuntimeServiceImpl {
         if let Err(status) = self
             .image_service
             .ensure_image_exists_for_sandbox(&pause_image, &pod_config)
+            .instrument(crate::trace_export::phase_span(
+                "sandbox",
+                "ensurePauseImage",
+                &pod_id,
+            ))
             .await
         {
             let mapped = Status::new(
@@ -770,6 +780,11 @@ impl RuntimeServiceImpl {
         let mut pod_manager = self.pod_manager.lock().await;
         let pod_id = match pod_manager
             .create_pod_sandbox_with_id(pod_id.clone(), sandbox_config)
+            .instrument(crate::trace_export::phase_span(
+                "sandbox",
+                "createPodSandbox",
+                &pod_id,
+            ))
             .await
         {
             Ok(pod_id) => pod_id,
@@ -1041,6 +1056,11 @@ impl RuntimeServiceImpl {
         if let Err(err) = self
             .nri
             .run_pod_sandbox(self.nri_pod_event(&pod_id).await)
+            .instrument(crate::trace_export::phase_span(
+                "sandbox",
+                "nriRunPodSandbox",
+                &pod_id,
+            ))
             .await
         {
             self.rollback_failed_pod_sandbox_run(&pod_id).await;
@@ -1344,7 +1364,15 @@ impl RuntimeServiceImpl {
             let mut pod_manager = self.pod_manager.lock().await;
             let has_entry = pod_manager.get_pod_sandbox(&pod_id).is_some();
             if has_entry {
-                if let Err(err) = pod_manager.stop_pod_sandbox(&pod_id).await {
+                if let Err(err) = pod_manager
+                    .stop_pod_sandbox(&pod_id)
+                    .instrument(crate::trace_export::phase_span(
+                        "sandbox",
+                        "stopPodSandbox",
+                        &pod_id,
+                    ))
+                    .await
+                {
                     self.publish_network_internal_event(
                         &pod_id,
                         "teardown_failed",
@@ -1551,7 +1579,15 @@ impl RuntimeServiceImpl {
             let mut pod_manager = self.pod_manager.lock().await;
             let has_entry = pod_manager.get_pod_sandbox(&pod_id).is_some();
             if has_entry {
-                if let Err(err) = pod_manager.remove_pod_sandbox(&pod_id).await {
+                if let Err(err) = pod_manager
+                    .remove_pod_sandbox(&pod_id)
+                    .instrument(crate::trace_export::phase_span(
+                        "sandbox",
+                        "removePodSandbox",
+                        &pod_id,
+                    ))
+                    .await
+                {
                     self.publish_network_internal_event(
                         &pod_id,
                         "teardown_failed",
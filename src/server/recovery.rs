@@ -1610,6 +1610,45 @@ impl RuntimeServiceImpl {
         restored_seccomp_notifiers
     }
 
+    /// 删除账本中标记为临时的调试容器：发起它们的 `crs debug container` 已经不在，
+    /// 重启后不会再有人附着或清理它们。
+    async fn cleanup_ephemeral_debug_containers(&self) -> usize {
+        let ephemeral = {
+            let containers = self.containers.lock().await;
+            containers
+                .iter()
+                .filter_map(|(container_id, container)| {
+                    Self::read_internal_state::<StoredContainerState>(
+                        &container.annotations,
+                        INTERNAL_CONTAINER_STATE_KEY,
+                    )
+                    .and_then(|state| state.ephemeral_debug_target)
+                    .map(|target| (container_id.clone(), target))
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut removed = 0;
+        for (container_id, target) in ephemeral {
+            match self.cleanup_stale_logical_container(&container_id).await {
+                Ok(()) => {
+                    log::info!(
+                        "Removed ephemeral debug container {} left behind for {}",
+                        container_id,
+                        target
+                    );
+                    removed += 1;
+                }
+                Err(status) => log::warn!(
+                    "Failed to remove ephemeral debug container {} during recovery: {}",
+                    container_id,
+                    status.message()
+                ),
+            }
+        }
+        removed
+    }
+
     async fn cleanup_recovery_orphans(
         &self,
         snapshot: &RecoveryLedgerSnapshot,
@@ -1934,6 +1973,16 @@ impl RuntimeServiceImpl {
             }
         }
 
+        let stage_started = Instant::now();
+        let removed_ephemeral_containers = self.cleanup_ephemeral_debug_containers().await;
+        recovery_result.stages.push(Self::recovery_stage_summary(
+            RecoveryStage::CleanupEphemeralContainers,
+            stage_started,
+            true,
+            removed_ephemeral_containers,
+            None,
+        ));
+
         let stage_started = Instant::now();
         self.ensure_exit_monitors_for_active_containers().await;
         recovery_result.stages.push(Self::recovery_stage_summary(
@@ -144,6 +144,7 @@ impl RuntimeServiceImpl {
             "runtimeSpec": runtime_spec,
             "privileged": container_state.as_ref().map(|state| state.privileged).unwrap_or(false),
             "logPath": container_state.as_ref().and_then(|state| state.log_path.clone()),
+            "logRotation": self.container_log_rotation_info(&container.id, container_state.as_ref()),
             "tty": container_state.as_ref().map(|state| state.tty).unwrap_or(false),
             "stdin": container_state.as_ref().map(|state| state.stdin).unwrap_or(false),
             "stdinOnce": container_state.as_ref().map(|state| state.stdin_once).unwrap_or(false),
@@ -86,10 +86,12 @@ pub struct RuntimeServiceImpl {
     pub(super) nri: Arc<dyn NriApi>,
     pub(super) runtime: RuntimeRegistry,
     pub(super) pod_manager: Arc<tokio::sync::Mutex<PodSandboxManager<RuntimeRegistry>>>,
+    pub(super) netns_pool: crate::network::NetnsPool,
+    pub(super) cni_snapshots: crate::network::CniSnapshotStore,
     pub(super) image_service: ImageServiceImpl,
     pub(super) persistence: Arc<Mutex<PersistenceManager>>,
     pub(super) streaming: Arc<Mutex<Option<StreamingServer>>>,
-    pub(super) events: tokio::sync::broadcast::Sender<ContainerEventResponse>,
+    pub(super) events: crate::services::EventService,
     pub(super) internal_services: crate::services::InternalServices,
     pub(super) shim_work_dir: PathBuf,
     pub(super) attach_socket_dir: PathBuf,
@@ -117,11 +119,17 @@ pub struct RuntimeServiceImpl {
     pub(super) reload_watcher_shutdown: tokio::sync::broadcast::Sender<()>,
     pub(super) exit_monitors: Arc<Mutex<HashSet<String>>>,
     pub(super) container_stats_cache:
-        Arc<Mutex<HashMap<String, CachedStatsEntry<crate::proto::runtime::v1::ContainerStats>>>>,
+        Arc<Mutex<HashMap<String, CachedStatsEntry<CachedContainerStats>>>>,
     pub(super) pod_stats_cache:
         Arc<Mutex<HashMap<String, CachedStatsEntry<crate::proto::runtime::v1::PodSandboxStats>>>>,
     pub(super) pod_metrics_cache:
         Arc<Mutex<HashMap<String, CachedStatsEntry<crate::proto::runtime::v1::PodSandboxMetrics>>>>,
+    pub(super) pod_network_stats_cache:
+        Arc<Mutex<HashMap<String, CachedStatsEntry<Vec<crate::metrics::NetworkStats>>>>>,
+    pub(super) log_rotation_status:
+        StdArc<StdMutex<HashMap<String, log_rotation::ContainerLogRotationStatus>>>,
+    /// OOM 监视的计数基线和已上报容器，轮询与退出监视共用。
+    pub(super) oom_observations: StdArc<StdMutex<oom_monitor::OomKillObservations>>,
 }
 
 #[derive(Debug, Clone, Default, serde::Serialize)]
@@ -147,6 +155,7 @@ pub(super) enum RecoveryStage {
     ProbeRuntimeLiveState,
     ReconnectShims,
     ReconcileObjects,
+    CleanupEphemeralContainers,
     RestoreExitMonitors,
     CleanupOrphans,
 }
@@ -164,6 +173,7 @@ impl RecoveryStage {
             Self::ProbeRuntimeLiveState => "probeRuntimeLiveState",
             Self::ReconnectShims => "reconnectShims",
             Self::ReconcileObjects => "reconcileObjects",
+            Self::CleanupEphemeralContainers => "cleanupEphemeralContainers",
             Self::RestoreExitMonitors => "restoreExitMonitors",
             Self::CleanupOrphans => "cleanupOrphans",
         }
@@ -182,6 +192,7 @@ pub(super) const RECOVERY_STAGE_ORDER: &[RecoveryStage] = &[
     RecoveryStage::ProbeRuntimeLiveState,
     RecoveryStage::ReconnectShims,
     RecoveryStage::ReconcileObjects,
+    RecoveryStage::CleanupEphemeralContainers,
     RecoveryStage::RestoreExitMonitors,
     RecoveryStage::CleanupOrphans,
 ];
@@ -249,13 +260,17 @@ pub struct RuntimeMetricsProvider {
     containers: Arc<Mutex<HashMap<String, Container>>>,
     pod_sandboxes: Arc<Mutex<HashMap<String, crate::proto::runtime::v1::PodSandbox>>>,
     config: RuntimeConfig,
-    events: tokio::sync::broadcast::Sender<ContainerEventResponse>,
-    container_stats_cache:
-        Arc<Mutex<HashMap<String, CachedStatsEntry<crate::proto::runtime::v1::ContainerStats>>>>,
+    events: crate::services::EventService,
+    container_stats_cache: Arc<Mutex<HashMap<String, CachedStatsEntry<CachedContainerStats>>>>,
     pod_stats_cache:
         Arc<Mutex<HashMap<String, CachedStatsEntry<crate::proto::runtime::v1::PodSandboxStats>>>>,
     pod_metrics_cache:
         Arc<Mutex<HashMap<String, CachedStatsEntry<crate::proto::runtime::v1::PodSandboxMetrics>>>>,
+    pod_network_stats_cache:
+        Arc<Mutex<HashMap<String, CachedStatsEntry<Vec<crate::metrics::NetworkStats>>>>>,
+    netns_pool: crate::network::NetnsPool,
+    /// footprint 与健康条件需要 shim 账本、镜像服务、NRI 与重载状态，直接复用后台克隆。
+    runtime_service: RuntimeServiceImpl,
 }
 
 #[derive(Clone)]
@@ -271,6 +286,7 @@ pub struct RuntimeDiagnosticsSnapshot {
 pub struct RuntimeSecurityDiagnosticsSnapshot {
     pub selinux_enabled: bool,
     pub rootless_enabled: bool,
+    pub rootless_network_mode: String,
     pub allowed_device_count: usize,
     pub additional_device_count: usize,
     pub device_ownership_from_security_context: bool,
@@ -285,6 +301,13 @@ pub(super) struct CachedStatsEntry<T> {
     pub(super) value: T,
 }
 
+/// 同一次采样的原始 cgroup 统计与 CRI 视图；metrics collector 需要原始值中的 PSI、blkio 等字段。
+#[derive(Debug, Clone)]
+pub(super) struct CachedContainerStats {
+    pub(super) raw: crate::metrics::ContainerStats,
+    pub(super) proto: crate::proto::runtime::v1::ContainerStats,
+}
+
 #[derive(Clone, Copy, Debug)]
 pub(super) struct ContainerCreateDeadline {
     pub(super) timeout_secs: u32,
@@ -333,6 +356,10 @@ pub struct RuntimeConfig {
     pub image_oci_artifact_mount_support: bool,
     pub workloads: HashMap<String, crate::config::RuntimeWorkloadConfig>,
     pub enable_pod_events: bool,
+    pub event_log_capacity: u64,
+    pub event_sinks: Vec<crate::config::EventSinkConfig>,
+    pub container_events_queue_size: usize,
+    pub container_events_overflow: crate::config::ContainerEventsOverflow,
     pub included_pod_metrics: Vec<String>,
     pub stats_collection_period: u64,
     pub pod_sandbox_metrics_collection_period: u64,
@@ -416,6 +443,7 @@ pub struct RuntimeConfig {
     pub exec_sync_io_drain_timeout: std::time::Duration,
     pub max_container_log_line_size: usize,
     pub log_to_journald: bool,
+    pub container_log_rotation: crate::config::ContainerLogRotationConfig,
     pub no_sync_log: bool,
     pub restrict_oom_score_adj: bool,
     pub enable_unprivileged_ports: bool,
@@ -444,12 +472,14 @@ impl Default for RuntimeConfig {
                     monitor_cgroup: loaded.runtime.monitor_cgroup.clone(),
                     monitor_env: loaded.runtime.monitor_env.clone(),
                     stream_websockets: true,
+                    stream_spdy: true,
                     allowed_annotations: Vec::new(),
                     default_annotations: HashMap::new(),
                     privileged_without_host_devices: false,
                     privileged_without_host_devices_all_devices_allowed: false,
                     container_create_timeout: 240,
                     snapshotter: "internal-overlay-untar".to_string(),
+                    log_drivers: Vec::new(),
                 },
             )])
         });
@@ -494,6 +524,7 @@ impl Default for RuntimeConfig {
             monitor_env: loaded.runtime.monitor_env.clone(),
             debug: loaded.runtime.shim_debug,
             log_to_journald: loaded.runtime.log_to_journald,
+            log_drivers: Vec::new(),
             no_sync_log: loaded.runtime.no_sync_log,
             no_pivot: loaded.runtime.no_pivot,
             no_new_keyring: loaded.runtime.no_new_keyring,
@@ -503,6 +534,7 @@ impl Default for RuntimeConfig {
             ),
             runtime_path: runtime_path.clone(),
             max_container_log_line_size: loaded.logging.max_container_log_line_size,
+            attach_scrollback_bytes: loaded.logging.attach_scrollback_bytes,
             state_db_path: root_dir.join("crius.db"),
         };
 
@@ -546,6 +578,10 @@ impl Default for RuntimeConfig {
             image_oci_artifact_mount_support: loaded.image.oci_artifact_mount_support,
             workloads: loaded.runtime.workloads.clone(),
             enable_pod_events: loaded.api.enable_pod_events,
+            event_log_capacity: loaded.api.event_log_capacity,
+            event_sinks: loaded.events.sinks.clone(),
+            container_events_queue_size: loaded.api.container_events_queue_size,
+            container_events_overflow: loaded.api.container_events_overflow,
             included_pod_metrics: loaded.api.included_pod_metrics.clone(),
             stats_collection_period: loaded.api.stats_collection_period,
             pod_sandbox_metrics_collection_period: loaded.api.pod_sandbox_metrics_collection_period,
@@ -651,6 +687,7 @@ impl Default for RuntimeConfig {
             exec_sync_io_drain_timeout: loaded.api.exec_sync_io_drain_timeout,
             max_container_log_line_size: loaded.logging.max_container_log_line_size,
             log_to_journald: loaded.runtime.log_to_journald,
+            container_log_rotation: loaded.logging.container_log_rotation.clone(),
             no_sync_log: loaded.runtime.no_sync_log,
             restrict_oom_score_adj: loaded.runtime.restrict_oom_score_adj,
             enable_unprivileged_ports: loaded.runtime.enable_unprivileged_ports,
@@ -840,6 +877,9 @@ pub struct RuntimeReloadState {
     pub cni_watch_dirs: Vec<String>,
     pub last_cni_watch_at_unix_millis: Option<i64>,
     pub last_cni_watch_error: Option<String>,
+    pub cni_last_known_good: Option<crate::network::CniSnapshotSummary>,
+    pub last_cni_rejected_at_unix_millis: Option<i64>,
+    pub last_cni_rejected_error: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize)]
@@ -1254,10 +1294,11 @@ impl crate::runtime::ContainerRuntime for RuntimeRegistry {
         container_id: &str,
         command: &[String],
         tty: bool,
+        overrides: &crate::security::spec_patch::ExecOverrides,
     ) -> anyhow::Result<i32> {
         self.runtime_for_container(container_id)?
             .task_controller()
-            .exec_in_container(container_id, command, tty)
+            .exec_in_container(container_id, command, tty, overrides)
     }
 
     fn update_container_resources(
@@ -1304,7 +1345,23 @@ impl RuntimeServiceImpl {
                 deadline.timeout_secs
             )));
         }
-        let output = tokio::time::timeout(remaining, future).await.map_err(|_| {
+        let started = Instant::now();
+        let output = tokio::time::timeout(
+            remaining,
+            future.instrument(crate::trace_export::phase_span(
+                "container.create",
+                phase,
+                "",
+            )),
+        )
+        .await;
+        crate::metrics::operations::observe_phase(
+            crate::metrics::operations::OperationPhase::ContainerCreate,
+            phase,
+            matches!(output, Ok(Ok(_))),
+            started.elapsed(),
+        );
+        let output = output.map_err(|_| {
             Status::deadline_exceeded(format!(
                 "container create phase {phase} exceeded runtime handler create timeout of {}s",
                 deadline.timeout_secs
@@ -1326,13 +1383,49 @@ impl RuntimeServiceImpl {
             containers: self.containers.clone(),
             pod_sandboxes: self.pod_sandboxes.clone(),
             config: self.config.clone(),
-            events: self.events.clone(),
+            events: self.internal_services.events.clone(),
             container_stats_cache: self.container_stats_cache.clone(),
             pod_stats_cache: self.pod_stats_cache.clone(),
             pod_metrics_cache: self.pod_metrics_cache.clone(),
+            pod_network_stats_cache: self.pod_network_stats_cache.clone(),
+            netns_pool: self.netns_pool.clone(),
+            runtime_service: self.clone_for_background(),
         }
     }
 
+    /// 按 shim 账本与最近一次拉取 cgroup 作用域采样 crius 自身的资源占用。
+    pub async fn runtime_footprint(&self) -> crate::metrics::footprint::RuntimeFootprint {
+        let mut warnings = Vec::new();
+        let shim_pids = match self.persistence.lock().await.list_shim_process_records() {
+            Ok(records) => records.into_iter().map(|record| record.shim_pid).collect(),
+            Err(err) => {
+                warnings.push(format!("failed to inspect shim ledger: {err}"));
+                Vec::new()
+            }
+        };
+        let pull_cgroup = self
+            .image_service
+            .last_pull_cgroup_scope()
+            .filter(|scope| scope.entered && scope.mode == crate::image::PullCgroupMode::Path)
+            .and_then(|scope| scope.effective_path)
+            .map(PathBuf::from);
+
+        let mut footprint = crate::metrics::footprint::collect_runtime_footprint(
+            &crate::metrics::footprint::FootprintSources {
+                shim_pids,
+                pull_cgroup,
+            },
+        );
+        warnings.append(&mut footprint.warnings);
+        footprint.warnings = warnings;
+        footprint
+    }
+
+    /// 预热 netns 池句柄。
+    pub fn netns_pool(&self) -> crate::network::NetnsPool {
+        self.netns_pool.clone()
+    }
+
     pub fn image_service(&self) -> ImageServiceImpl {
         self.image_service.clone()
     }
@@ -1453,6 +1546,65 @@ impl RuntimeServiceImpl {
         Ok(path)
     }
 
+    /// 同一 Pod 内同名容器的其他 attempt，附带 `container_id` 自身的创建时间。
+    fn container_attempt_siblings<'a>(
+        containers: &'a HashMap<String, Container>,
+        container_id: &str,
+    ) -> Option<(i64, Vec<&'a Container>)> {
+        let current = containers.get(container_id)?;
+        let name = current
+            .metadata
+            .as_ref()
+            .map(|metadata| metadata.name.as_str());
+        let siblings = containers
+            .values()
+            .filter(|container| {
+                container.id != current.id
+                    && container.pod_sandbox_id == current.pod_sandbox_id
+                    && container
+                        .metadata
+                        .as_ref()
+                        .map(|metadata| metadata.name.as_str())
+                        == name
+            })
+            .collect();
+        Some((current.created_at, siblings))
+    }
+
+    /// 内部事件服务，诊断接口经它订阅持久化事件日志。
+    pub fn event_service(&self) -> &crate::services::EventService {
+        &self.internal_services.events
+    }
+
+    /// `logs --previous` 使用的上一次已退出 attempt。
+    pub async fn previous_container_attempt(&self, container_id: &str) -> Option<String> {
+        let containers = self.containers.lock().await;
+        let (created_at, siblings) = Self::container_attempt_siblings(&containers, container_id)?;
+        siblings
+            .into_iter()
+            .filter(|container| {
+                container.created_at < created_at
+                    && container.state == ContainerState::ContainerExited as i32
+            })
+            .max_by_key(|container| container.created_at)
+            .map(|container| container.id.clone())
+    }
+
+    /// 容器重启后创建的下一次 attempt，供日志 follow 跨重启继续。
+    pub async fn next_container_attempt(&self, container_id: &str) -> Option<String> {
+        let containers = self.containers.lock().await;
+        let (created_at, siblings) = Self::container_attempt_siblings(&containers, container_id)?;
+        siblings
+            .into_iter()
+            .filter(|container| container.created_at > created_at)
+            .min_by_key(|container| container.created_at)
+            .map(|container| container.id.clone())
+    }
+
+    pub async fn container_exists(&self, container_id: &str) -> bool {
+        self.containers.lock().await.contains_key(container_id)
+    }
+
     #[cfg(test)]
     pub async fn insert_diagnostics_test_container(
         &self,
@@ -1511,16 +1663,120 @@ impl RuntimeMetricsProvider {
             Err(_) => false,
         };
 
+        let pods: Vec<_> = self
+            .pod_sandboxes
+            .lock()
+            .await
+            .iter()
+            .map(|(pod_id, pod)| (pod_id.clone(), pod.clone()))
+            .collect();
+        let mut pod_network_interfaces = Vec::new();
+        for (pod_id, pod) in &pods {
+            let metadata = pod.metadata.clone().unwrap_or_default();
+            let interfaces = RuntimeServiceImpl::cached_pod_interface_stats(
+                &self.pod_network_stats_cache,
+                self.config.stats_collection_period,
+                pod_id,
+                pod,
+            )
+            .await;
+            pod_network_interfaces.extend(interfaces.into_iter().map(|interface| {
+                crate::metrics::PodNetworkInterfaceStats {
+                    pod_id: pod_id.clone(),
+                    pod_name: metadata.name.clone(),
+                    pod_namespace: metadata.namespace.clone(),
+                    interface,
+                }
+            }));
+        }
+
         crate::metrics::RuntimeMetricsSnapshot {
             runtime_ready,
             network_ready,
             container_count: self.containers.lock().await.len(),
-            pod_sandbox_count: self.pod_sandboxes.lock().await.len(),
-            event_subscriber_count: self.events.receiver_count(),
+            pod_sandbox_count: pods.len(),
+            event_subscriber_count: self.events.subscriber_count(),
+            event_subscribers: self.events.subscriber_stats(),
+            event_dropped_total: self.events.dropped_event_count(),
             container_stats_cache_entries: self.container_stats_cache.lock().await.len(),
             pod_stats_cache_entries: self.pod_stats_cache.lock().await.len(),
             pod_metrics_cache_entries: self.pod_metrics_cache.lock().await.len(),
+            netns_pool: self.netns_pool.stats(),
+            pod_network_interfaces,
+        }
+    }
+
+    /// 采样 daemon、shim、拉取 cgroup 与 CNI 插件的资源占用。
+    pub async fn runtime_footprint(&self) -> crate::metrics::footprint::RuntimeFootprint {
+        self.runtime_service.runtime_footprint().await
+    }
+
+    /// 供 metrics 与 `/healthz`、`/readyz` 使用的全部内部健康条件。
+    pub async fn health_conditions(&self) -> Vec<crate::services::InternalHealthCondition> {
+        self.runtime_service.health_conditions().await
+    }
+
+    /// 各 NRI 插件的连接状态。
+    pub async fn nri_plugin_status(&self) -> Vec<crate::nri::NriPluginStatus> {
+        self.runtime_service.nri.plugin_status().await
+    }
+
+    /// 为 metrics `containers` collector 复用 CRI stats 缓存，逐个给出未退出容器的 cgroup 计数与可写层用量。
+    pub async fn container_resource_stats(&self) -> Vec<crate::metrics::ContainerResourceStats> {
+        let pods: HashMap<String, PodSandboxMetadata> = self
+            .pod_sandboxes
+            .lock()
+            .await
+            .iter()
+            .map(|(pod_id, pod)| (pod_id.clone(), pod.metadata.clone().unwrap_or_default()))
+            .collect();
+        let containers: Vec<Container> = self.containers.lock().await.values().cloned().collect();
+
+        let mut resources = Vec::new();
+        for container in containers {
+            if container.state == ContainerState::ContainerExited as i32
+                || container.state == ContainerState::ContainerUnknown as i32
+            {
+                continue;
+            }
+            let Some(stats) = self
+                .runtime_service
+                .cached_container_stats_entry(&container.id, &container)
+                .await
+            else {
+                continue;
+            };
+            let fs_usage_bytes = stats
+                .proto
+                .writable_layer
+                .as_ref()
+                .and_then(|usage| usage.used_bytes.as_ref())
+                .map(|bytes| bytes.value)
+                .unwrap_or_default();
+            let pod = pods
+                .get(&container.pod_sandbox_id)
+                .cloned()
+                .unwrap_or_default();
+            resources.push(crate::metrics::ContainerResourceStats {
+                container_id: container.id.clone(),
+                container_name: container
+                    .metadata
+                    .as_ref()
+                    .map(|metadata| metadata.name.clone())
+                    .unwrap_or_default(),
+                image: container
+                    .image
+                    .as_ref()
+                    .map(|image| image.image.clone())
+                    .unwrap_or_default(),
+                pod_name: pod.name,
+                pod_namespace: pod.namespace,
+                stats: stats.raw,
+                fs_usage_bytes,
+            });
         }
+        resources.sort_by(|left, right| left.container_id.cmp(&right.container_id));
+        resources
     }
 }
 
@@ -2239,12 +2495,14 @@ impl RuntimeServiceImpl {
                 monitor_cgroup: config.monitor_cgroup.clone(),
                 monitor_env: config.monitor_env.clone(),
                 stream_websockets: true,
+                stream_spdy: true,
                 allowed_annotations: Vec::new(),
                 default_annotations: HashMap::new(),
                 privileged_without_host_devices: false,
                 privileged_without_host_devices_all_devices_allowed: false,
                 container_create_timeout: 240,
                 snapshotter: "internal-overlay-untar".to_string(),
+                log_drivers: Vec::new(),
             });
         config.runtime_configs = runtime_configs;
         let container_create_timeouts = config
@@ -2282,6 +2540,7 @@ impl RuntimeServiceImpl {
                         shim_config.io_gid = config.io_gid;
                         shim_config.runtime_path = PathBuf::from(&runtime_config.runtime_path);
                         shim_config.monitor_env = runtime_config.monitor_env.clone();
+                        shim_config.log_drivers = runtime_config.log_drivers.clone();
                         shim_config.no_sync_log = config.no_sync_log;
                         shim_config.no_new_keyring = config.no_new_keyring;
                         shim_config.systemd_cgroup =
@@ -2457,19 +2716,38 @@ impl RuntimeServiceImpl {
             enable_recovery: true,
             auto_save_interval: 30,
         };
-        let persistence = PersistenceManager::new(persistence_config)
+        let mut persistence = PersistenceManager::new(persistence_config)
             .expect("Failed to create persistence manager");
+        if let Err(err) = crate::state::StateLedgerWriter::new(&mut persistence)
+            .configure_event_log(config.event_log_capacity)
+        {
+            log::warn!("Failed to configure event log: {}", err);
+        }
         let persistence = Arc::new(Mutex::new(persistence));
-        let (events, _) = tokio::sync::broadcast::channel(256);
-        let internal_services = crate::services::InternalServices::new(
-            crate::services::EventService::from_sender(events.clone())
-                .with_ledger(persistence.clone()),
-        );
+        let mut event_service = crate::services::EventService::with_capacity(256)
+            .with_ledger(persistence.clone())
+            .with_subscriber_queue(
+                config.container_events_queue_size,
+                config.container_events_overflow,
+            );
+        if config.event_log_capacity > 0 {
+            event_service = event_service.with_event_log();
+        }
+        if !config.event_sinks.is_empty() {
+            match crate::services::EventSinkSet::start(&config.event_sinks) {
+                Ok(sinks) => event_service = event_service.with_event_sinks(sinks),
+                Err(err) => log::warn!("Failed to start event sinks: {:#}", err),
+            }
+        }
+        let events = event_service.clone();
+        let internal_services = crate::services::InternalServices::new(event_service);
         let network_event_sink =
             crate::services::LedgerInternalEventSink::new(config.root_dir.join("crius.db"));
+        let cni_snapshots = crate::network::CniSnapshotStore::new();
         let mut pod_cni_config = config.cni_config.clone();
         pod_cni_config.set_rootless_config(Some(config.rootless.clone()));
         pod_cni_config.set_event_sink(Some(network_event_sink.clone()));
+        pod_cni_config.set_snapshot_store(Some(cni_snapshots.clone()));
         if config.rootless.enabled {
             pod_cni_config.set_netns_mount_dir(config.rootless.netns_dir.clone());
         }
@@ -2585,6 +2863,8 @@ impl RuntimeServiceImpl {
             nri_config,
             nri,
             runtime,
+            netns_pool: pod_manager.netns_pool(),
+            cni_snapshots,
             pod_manager: Arc::new(tokio::sync::Mutex::new(pod_manager)),
             image_service,
             persistence,
@@ -2616,8 +2896,13 @@ impl RuntimeServiceImpl {
             container_stats_cache: Arc::new(Mutex::new(HashMap::new())),
             pod_stats_cache: Arc::new(Mutex::new(HashMap::new())),
             pod_metrics_cache: Arc::new(Mutex::new(HashMap::new())),
+            pod_network_stats_cache: Arc::new(Mutex::new(HashMap::new())),
+            log_rotation_status: StdArc::new(StdMutex::new(HashMap::new())),
+            oom_observations: StdArc::new(StdMutex::new(Default::default())),
         };
         service.spawn_reload_watchers();
+        service.spawn_container_log_rotator();
+        service.spawn_container_oom_monitor();
         service
     }
 
@@ -2651,6 +2936,7 @@ impl RuntimeServiceImpl {
         RuntimeSecurityDiagnosticsSnapshot {
             selinux_enabled: self.config.enable_selinux,
             rootless_enabled: self.config.rootless.enabled,
+            rootless_network_mode: self.config.rootless.network_mode.as_str().to_string(),
             allowed_device_count: self.config.allowed_devices.len(),
             additional_device_count: self.config.additional_devices.len(),
             device_ownership_from_security_context: self
@@ -2662,7 +2948,29 @@ impl RuntimeServiceImpl {
         }
     }
 
+    pub async fn rootless_port_forwards(&self) -> Vec<crate::network::RootlessPortForward> {
+        self.pod_manager.lock().await.rootless_port_forwards()
+    }
+
     pub async fn set_streaming_server(&self, streaming_server: StreamingServer) {
+        // 审计记录可能在转录线程上产出，发布内部事件需要显式带上运行时句柄。
+        let events = self.internal_services.events.clone();
+        let handle = tokio::runtime::Handle::current();
+        streaming_server.auditor().set_sink(move |record| {
+            let event = crate::services::InternalEvent::new(
+                record.event_kind(),
+                "container",
+                record.container_id.clone(),
+                crate::services::InternalEventSeverity::Info,
+                serde_json::to_value(record).unwrap_or(serde_json::Value::Null),
+            );
+            let events = events.clone();
+            handle.spawn(async move {
+                if let Err(err) = events.publish_internal(event).await {
+                    log::debug!("Failed to publish streaming audit event: {}", err);
+                }
+            });
+        });
         let mut streaming = self.streaming.lock().await;
         *streaming = Some(streaming_server);
     }
@@ -2675,10 +2983,21 @@ impl RuntimeServiceImpl {
     }
 
     pub fn current_reload_state(&self) -> RuntimeReloadState {
-        self.reload_state
+        let mut state = self
+            .reload_state
             .lock()
             .expect("reload state lock poisoned")
-            .clone()
+            .clone();
+        let key = self.current_cni_config().snapshot_key();
+        state.cni_last_known_good = self
+            .cni_snapshots
+            .active(&key)
+            .map(|snapshot| snapshot.summary());
+        if let Some(rejection) = self.cni_snapshots.rejection(&key) {
+            state.last_cni_rejected_at_unix_millis = Some(rejection.rejected_at_unix_millis);
+            state.last_cni_rejected_error = Some(rejection.message);
+        }
+        state
     }
 
     pub(super) fn current_cni_config(&self) -> crate::network::CniConfig {
@@ -2689,6 +3008,7 @@ impl RuntimeServiceImpl {
         config.set_event_sink(Some(crate::services::LedgerInternalEventSink::new(
             self.config.root_dir.join("crius.db"),
         )));
+        config.set_snapshot_store(Some(self.cni_snapshots.clone()));
         if self.config.rootless.enabled {
             config.set_netns_mount_dir(self.config.rootless.netns_dir.clone());
         }
@@ -2727,6 +3047,7 @@ impl RuntimeServiceImpl {
         config.set_event_sink(Some(crate::services::LedgerInternalEventSink::new(
             self.config.root_dir.join("crius.db"),
         )));
+        config.set_snapshot_store(Some(self.cni_snapshots.clone()));
         if self.config.rootless.enabled {
             config.set_netns_mount_dir(self.config.rootless.netns_dir.clone());
         }
@@ -2781,6 +3102,7 @@ impl RuntimeServiceImpl {
             next_cni.set_event_sink(Some(crate::services::LedgerInternalEventSink::new(
                 self.config.root_dir.join("crius.db"),
             )));
+            next_cni.set_snapshot_store(Some(self.cni_snapshots.clone()));
             if self.config.rootless.enabled {
                 next_cni.set_netns_mount_dir(self.config.rootless.netns_dir.clone());
             }
@@ -2867,7 +3189,8 @@ impl RuntimeServiceImpl {
             Self::sync_generated_cni_config(&cni_config, runtime_network_config.as_ref())
                 .err()
                 .map(|err| err.to_string());
-        let status = self.probe_cni_load_status().await;
+        let outcome = self.stage_cni_reload(&cni_config).await;
+        let status = outcome.status;
         self.update_reload_state(|state| {
             state.last_cni_watch_at_unix_millis = Some(chrono::Utc::now().timestamp_millis());
             state.last_cni_watch_error = sync_error
@@ -2877,7 +3200,7 @@ impl RuntimeServiceImpl {
         self.publish_network_internal_event(
             "cni",
             "reload_result",
-            if sync_error.is_none() && status.ready {
+            if sync_error.is_none() && status.ready && outcome.rejected.is_none() {
                 crate::services::InternalEventSeverity::Info
             } else {
                 crate::services::InternalEventSeverity::Warning
@@ -2890,12 +3213,49 @@ impl RuntimeServiceImpl {
                 "declaredPlugins": status.declared_plugins,
                 "missingPluginBinaries": status.missing_plugin_binaries,
                 "syncError": sync_error,
+                "activated": outcome.activated,
+                "rejected": outcome.rejected.as_ref().map(|rejection| json!({
+                    "reason": rejection.reason,
+                    "message": rejection.message,
+                })),
             }),
         )
         .await;
         status
     }
 
+    /// 分阶段加载 CNI 配置集；失败时保留 last-known-good 快照。
+    async fn stage_cni_reload(
+        &self,
+        cni_config: &crate::network::CniConfig,
+    ) -> crate::network::CniReloadOutcome {
+        if cni_config.rootless_config().is_some() {
+            return crate::network::CniReloadOutcome {
+                status: self
+                    .probe_cni_load_status_for_config(cni_config.clone())
+                    .await,
+                activated: false,
+                rejected: None,
+            };
+        }
+        let mut cni = match Self::cni_manager_for_config(cni_config) {
+            Ok(cni) => cni,
+            Err(status) => {
+                return crate::network::CniReloadOutcome {
+                    status: *status,
+                    activated: false,
+                    rejected: None,
+                }
+            }
+        };
+        let dry_run = cni_config
+            .reload_dry_run()
+            .then(|| cni_config.namespace_manager());
+        self.cni_snapshots
+            .reload(&mut cni, &cni_config.snapshot_key(), dry_run.as_ref())
+            .await
+    }
+
     fn config_watch_signature(path: &Path) -> Option<(i64, u64)> {
         let metadata = std::fs::metadata(path).ok()?;
         let modified = metadata.modified().ok()?;
@@ -3026,7 +3386,7 @@ impl RuntimeServiceImpl {
         });
     }
 
-    fn clone_for_background(&self) -> Self {
+    pub(super) fn clone_for_background(&self) -> Self {
         Self {
             containers: self.containers.clone(),
             pod_sandboxes: self.pod_sandboxes.clone(),
@@ -3039,6 +3399,8 @@ impl RuntimeServiceImpl {
             nri: self.nri.clone(),
             runtime: self.runtime.clone(),
             pod_manager: self.pod_manager.clone(),
+            netns_pool: self.netns_pool.clone(),
+            cni_snapshots: self.cni_snapshots.clone(),
             image_service: self.image_service.clone(),
             persistence: self.persistence.clone(),
             streaming: self.streaming.clone(),
@@ -3069,6 +3431,9 @@ impl RuntimeServiceImpl {
             container_stats_cache: self.container_stats_cache.clone(),
             pod_stats_cache: self.pod_stats_cache.clone(),
             pod_metrics_cache: self.pod_metrics_cache.clone(),
+            pod_network_stats_cache: self.pod_network_stats_cache.clone(),
+            log_rotation_status: self.log_rotation_status.clone(),
+            oom_observations: self.oom_observations.clone(),
         }
     }
 }
@@ -565,6 +565,10 @@ pub(super) struct StoredPortMapping {
 #[serde(default)]
 pub(super) struct StoredContainerState {
     pub(super) log_path: Option<String>,
+    /// 日志路径由 daemon 分配（本地容器或 `crs run`），可由内建轮转处理。
+    pub(super) daemon_managed_log: bool,
+    /// 临时调试容器所依附的目标容器；非空时恢复流程会直接清理该容器。
+    pub(super) ephemeral_debug_target: Option<String>,
     pub(super) tty: bool,
     pub(super) stdin: bool,
     pub(super) stdin_once: bool,
@@ -627,6 +631,10 @@ pub(super) struct CniTemplateContext {
     pub(super) pod_cidr: String,
     pub(super) pod_cidr_ranges: Vec<String>,
     pub(super) routes: Vec<String>,
+    /// 第一个 IPv4 PodCIDR；单栈 IPv6 时为空。
+    pub(super) pod_cidr_v4: String,
+    /// 第一个 IPv6 PodCIDR；单栈 IPv4 时为空。
+    pub(super) pod_cidr_v6: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -649,5 +657,5 @@ pub(super) struct NriRuntimeDomain {
     pub(super) nri_config: NriConfig,
     pub(super) runtime: RuntimeRegistry,
     pub(super) persistence: Arc<Mutex<PersistenceManager>>,
-    pub(super) events: tokio::sync::broadcast::Sender<ContainerEventResponse>,
+    pub(super) events: crate::services::EventService,
 }
@@ -8,6 +8,7 @@ pub(super) struct PodMetricTotals {
     pub(super) filesystem_usage: u64,
     pub(super) rx_bytes: u64,
     pub(super) tx_bytes: u64,
+    pub(super) interfaces: Vec<crate::metrics::NetworkStats>,
 }
 
 impl RuntimeServiceImpl {
@@ -20,6 +21,112 @@ impl RuntimeServiceImpl {
             && now.duration_since(collected_at) < std::time::Duration::from_secs(period_secs)
     }
 
+    /// 在 Pod netns 内通过 rtnetlink 采集各接口计数（含 secondary network 接口），
+    /// 按 `stats_collection_period` 缓存；host network 或 netns 已不存在时返回空。
+    pub(super) async fn cached_pod_interface_stats(
+        cache: &Mutex<
+            HashMap<
+                String,
+                crate::server::service::CachedStatsEntry<Vec<crate::metrics::NetworkStats>>,
+            >,
+        >,
+        period_secs: u64,
+        pod_id: &str,
+        pod: &crate::proto::runtime::v1::PodSandbox,
+    ) -> Vec<crate::metrics::NetworkStats> {
+        let now = std::time::Instant::now();
+        if period_secs != 0 {
+            if let Some(entry) = cache.lock().await.get(pod_id).cloned() {
+                if Self::stats_cache_is_fresh(entry.collected_at, period_secs, now) {
+                    return entry.value;
+                }
+            }
+        }
+
+        let Some(netns_path) =
+            Self::read_internal_state::<StoredPodState>(&pod.annotations, INTERNAL_POD_STATE_KEY)
+                .and_then(|state| state.netns_path)
+                .filter(|path| !path.is_empty())
+        else {
+            return Vec::new();
+        };
+        let stats = match crate::network::netlink::interface_stats(Path::new(&netns_path)).await {
+            Ok(stats) => stats,
+            Err(err) => {
+                log::debug!(
+                    "Failed to collect interface stats for pod {} in {}: {}",
+                    pod_id,
+                    netns_path,
+                    err
+                );
+                return Vec::new();
+            }
+        };
+
+        if period_secs != 0 {
+            cache.lock().await.insert(
+                pod_id.to_string(),
+                crate::server::service::CachedStatsEntry {
+                    collected_at: now,
+                    value: stats.clone(),
+                },
+            );
+        }
+        stats
+    }
+
+    async fn pod_interface_stats(
+        &self,
+        pod_id: &str,
+        pod: &crate::proto::runtime::v1::PodSandbox,
+    ) -> Vec<crate::metrics::NetworkStats> {
+        Self::cached_pod_interface_stats(
+            &self.pod_network_stats_cache,
+            self.config.stats_collection_period,
+            pod_id,
+            pod,
+        )
+        .await
+    }
+
+    /// `eth0` 作为默认接口，其余接口（secondary network）放入 `interfaces`。
+    pub(super) fn pod_network_usage(
+        timestamp: i64,
+        interfaces: &[crate::metrics::NetworkStats],
+    ) -> crate::proto::runtime::v1::NetworkUsage {
+        use crate::proto::runtime::v1::{NetworkInterfaceUsage, NetworkUsage, UInt64Value};
+
+        let to_usage = |stats: &crate::metrics::NetworkStats| NetworkInterfaceUsage {
+            name: stats.name.clone(),
+            rx_bytes: Some(UInt64Value {
+                value: stats.rx_bytes,
+            }),
+            rx_errors: Some(UInt64Value {
+                value: stats.rx_errors,
+            }),
+            tx_bytes: Some(UInt64Value {
+                value: stats.tx_bytes,
+            }),
+            tx_errors: Some(UInt64Value {
+                value: stats.tx_errors,
+            }),
+        };
+        let default_index = interfaces
+            .iter()
+            .position(|stats| stats.name == "eth0")
+            .unwrap_or(0);
+        NetworkUsage {
+            timestamp,
+            default_interface: interfaces.get(default_index).map(to_usage),
+            interfaces: interfaces
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| *index != default_index)
+                .map(|(_, stats)| to_usage(stats))
+                .collect(),
+        }
+    }
+
     fn pod_metric_enabled(&self, category: &str) -> bool {
         self.config
             .included_pod_metrics
@@ -32,6 +139,17 @@ impl RuntimeServiceImpl {
         container_id: &str,
         container: &Container,
     ) -> Option<crate::proto::runtime::v1::ContainerStats> {
+        self.cached_container_stats_entry(container_id, container)
+            .await
+            .map(|stats| stats.proto)
+    }
+
+    /// 按 `stats_collection_period` 复用缓存；未命中时在 blocking 线程池读取 cgroup 并遍历可写层。
+    pub(super) async fn cached_container_stats_entry(
+        &self,
+        container_id: &str,
+        container: &Container,
+    ) -> Option<crate::server::service::CachedContainerStats> {
         let period_secs = self.config.stats_collection_period;
         let now = std::time::Instant::now();
         if period_secs != 0 {
@@ -49,30 +167,37 @@ impl RuntimeServiceImpl {
         }
 
         let cgroup_parent = self.container_cgroup_hint(container_id, container).await;
-        let stats = match MetricsCollector::new() {
-            Ok(collector) => {
-                match collector.collect_container_stats(container_id, &cgroup_parent) {
-                    Ok(stats) => {
-                        let mut proto_stats = self.convert_to_proto_container_stats(stats);
-                        Self::populate_container_stats_attributes(&mut proto_stats, container);
-                        proto_stats
-                    }
-                    Err(e) => {
-                        log::warn!(
-                            "Failed to collect stats for container {}: {}",
-                            container_id,
-                            e
-                        );
-                        return None;
-                    }
-                }
-            }
+        let root_dir = self.config.root_dir.clone();
+        let id = container_id.to_string();
+        let collected = tokio::task::spawn_blocking(move || {
+            let collector = MetricsCollector::new()
+                .map_err(|e| format!("Failed to create MetricsCollector: {}", e))?;
+            let raw = collector
+                .collect_container_stats(&id, &cgroup_parent)
+                .map_err(|e| format!("Failed to collect stats for container {}: {}", id, e))?;
+            let writable_layer = Self::container_writable_layer_usage(&root_dir, &id);
+            Ok::<_, String>((raw, writable_layer))
+        })
+        .await
+        .map_err(|e| {
+            format!(
+                "Stats collection task for container {} failed: {}",
+                container_id, e
+            )
+        })
+        .and_then(|result| result);
+        let (raw, writable_layer) = match collected {
+            Ok(collected) => collected,
             Err(e) => {
-                log::warn!("Failed to create MetricsCollector: {}", e);
+                log::warn!("{}", e);
                 return None;
             }
         };
 
+        let mut proto = Self::container_stats_to_proto(raw.clone(), writable_layer);
+        Self::populate_container_stats_attributes(&mut proto, container);
+        let stats = crate::server::service::CachedContainerStats { raw, proto };
+
         if period_secs != 0 {
             self.container_stats_cache.lock().await.insert(
                 container_id.to_string(),
@@ -174,6 +299,72 @@ impl RuntimeServiceImpl {
                     value: totals.tx_bytes,
                 }),
             });
+            for interface in &totals.interfaces {
+                for (counter, value) in interface.counters() {
+                    metrics.push(Metric {
+                        name: format!("container_network_{counter}_total"),
+                        timestamp,
+                        metric_type: MetricType::Counter as i32,
+                        label_values: vec![pod_id.to_string(), interface.name.clone()],
+                        value: Some(UInt64Value { value }),
+                    });
+                }
+            }
+        }
+        metrics
+    }
+
+    /// PSI 累计停顿时间与 OOM 事件计数；CRI `Metric` 只能携带整数，停顿时间按整秒截断。
+    pub(super) fn container_pressure_metrics(
+        stats: &crate::metrics::ContainerStats,
+        container_id: &str,
+        pod_id: &str,
+        timestamp: i64,
+    ) -> Vec<crate::proto::runtime::v1::Metric> {
+        use crate::proto::runtime::v1::{Metric, MetricType, UInt64Value};
+
+        let counter = |name: String, mut label_values: Vec<String>, value: u64| {
+            label_values.splice(0..0, [container_id.to_string(), pod_id.to_string()]);
+            Metric {
+                name,
+                timestamp,
+                metric_type: MetricType::Counter as i32,
+                label_values,
+                value: Some(UInt64Value { value }),
+            }
+        };
+        let mut metrics = Vec::new();
+        if let Some(pressure) = stats.pressure.as_ref() {
+            for (resource, psi) in pressure.resources() {
+                let Some(psi) = psi else {
+                    continue;
+                };
+                for (kind, line) in [("waiting", &psi.some), ("stalled", &psi.full)] {
+                    metrics.push(counter(
+                        format!("container_pressure_{resource}_{kind}_seconds_total"),
+                        Vec::new(),
+                        line.total / 1_000_000,
+                    ));
+                }
+            }
+        }
+        if let Some(events) = stats
+            .memory
+            .as_ref()
+            .and_then(|memory| memory.events.as_ref())
+        {
+            metrics.push(counter(
+                "container_oom_events_total".to_string(),
+                Vec::new(),
+                events.oom_kill,
+            ));
+            for (event, value) in events.counters() {
+                metrics.push(counter(
+                    "container_memory_events_total".to_string(),
+                    vec![event.to_string()],
+                    value,
+                ));
+            }
         }
         metrics
     }
@@ -181,15 +372,25 @@ impl RuntimeServiceImpl {
     pub(super) fn convert_to_proto_container_stats(
         &self,
         stats: crate::metrics::ContainerStats,
+    ) -> crate::proto::runtime::v1::ContainerStats {
+        let writable_layer =
+            Self::container_writable_layer_usage(&self.config.root_dir, &stats.container_id);
+        Self::container_stats_to_proto(stats, writable_layer)
+    }
+
+    fn container_stats_to_proto(
+        stats: crate::metrics::ContainerStats,
+        writable_layer: Option<crate::proto::runtime::v1::FilesystemUsage>,
     ) -> crate::proto::runtime::v1::ContainerStats {
         use crate::proto::runtime::v1::{
             ContainerStats, CpuUsage, MemoryUsage, SwapUsage, UInt64Value,
         };
 
-        let writable_layer = self.container_writable_layer_usage(&stats.container_id);
         let swap = stats.memory.as_ref().map(|mem| SwapUsage {
             timestamp: stats.timestamp as i64,
-            swap_available_bytes: None,
+            swap_available_bytes: mem.swap_limit.map(|limit| UInt64Value {
+                value: limit.saturating_sub(mem.swap),
+            }),
             swap_usage_bytes: Some(UInt64Value { value: mem.swap }),
         });
 
@@ -333,6 +534,30 @@ impl RuntimeServiceImpl {
             .unwrap_or_default()
             .as_nanos() as i64;
         let container_count = container_stats_list.len() as u64;
+        let interface_stats = self.pod_interface_stats(pod_id, pod).await;
+        let network = if interface_stats.is_empty() {
+            NetworkUsage {
+                timestamp,
+                default_interface: Some(NetworkInterfaceUsage {
+                    name: "pod".to_string(),
+                    rx_bytes: Some(UInt64Value {
+                        value: total_rx_bytes,
+                    }),
+                    rx_errors: Some(UInt64Value {
+                        value: total_rx_errors,
+                    }),
+                    tx_bytes: Some(UInt64Value {
+                        value: total_tx_bytes,
+                    }),
+                    tx_errors: Some(UInt64Value {
+                        value: total_tx_errors,
+                    }),
+                }),
+                interfaces: Vec::new(),
+            }
+        } else {
+            Self::pod_network_usage(timestamp, &interface_stats)
+        };
 
         Some(PodSandboxStats {
             attributes: Some(PodSandboxAttributes {
@@ -369,25 +594,7 @@ impl RuntimeServiceImpl {
                     major_page_faults: Some(UInt64Value { value: 0 }),
                 }),
                 containers: container_stats_list,
-                network: Some(NetworkUsage {
-                    timestamp,
-                    default_interface: Some(NetworkInterfaceUsage {
-                        name: "pod".to_string(),
-                        rx_bytes: Some(UInt64Value {
-                            value: total_rx_bytes,
-                        }),
-                        rx_errors: Some(UInt64Value {
-                            value: total_rx_errors,
-                        }),
-                        tx_bytes: Some(UInt64Value {
-                            value: total_tx_bytes,
-                        }),
-                        tx_errors: Some(UInt64Value {
-                            value: total_tx_errors,
-                        }),
-                    }),
-                    interfaces: Vec::new(),
-                }),
+                network: Some(network),
                 process: Some(ProcessUsage {
                     timestamp,
                     process_count: Some(UInt64Value {
@@ -488,10 +695,12 @@ impl RuntimeServiceImpl {
                         let container_mem_limit =
                             stats.memory.as_ref().map(|m| m.limit).unwrap_or(0);
                         let container_pids = stats.pids.as_ref().map(|p| p.current).unwrap_or(0);
-                        let container_fs_usage = self
-                            .container_writable_layer_usage(&container_id)
-                            .and_then(|usage| usage.used_bytes.map(|bytes| bytes.value))
-                            .unwrap_or(0);
+                        let container_fs_usage = Self::container_writable_layer_usage(
+                            &self.config.root_dir,
+                            &container_id,
+                        )
+                        .and_then(|usage| usage.used_bytes.map(|bytes| bytes.value))
+                        .unwrap_or(0);
                         let container_network = self.container_network_stats(&container_id).await;
 
                         total_cpu_usage += container_cpu;
@@ -508,7 +717,7 @@ impl RuntimeServiceImpl {
                             .duration_since(UNIX_EPOCH)
                             .unwrap_or_default()
                             .as_nanos() as i64;
-                        let container_metric_list = vec![
+                        let mut container_metric_list = vec![
                             Metric {
                                 name: "container_cpu_usage_seconds_total".to_string(),
                                 timestamp,
@@ -546,6 +755,12 @@ impl RuntimeServiceImpl {
                                 }),
                             },
                         ];
+                        container_metric_list.extend(Self::container_pressure_metrics(
+                            &stats,
+                            &container_id,
+                            pod_id,
+                            timestamp,
+                        ));
 
                         container_metrics_list.push(ContainerMetrics {
                             container_id,
@@ -560,6 +775,11 @@ impl RuntimeServiceImpl {
             return None;
         }
 
+        let interfaces = self.pod_interface_stats(pod_id, pod).await;
+        if !interfaces.is_empty() {
+            total_rx_bytes = interfaces.iter().map(|stats| stats.rx_bytes).sum();
+            total_tx_bytes = interfaces.iter().map(|stats| stats.tx_bytes).sum();
+        }
         let timestamp = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap_or_default()
@@ -575,6 +795,7 @@ impl RuntimeServiceImpl {
                 filesystem_usage: total_filesystem_usage,
                 rx_bytes: total_rx_bytes,
                 tx_bytes: total_tx_bytes,
+                interfaces,
             },
         );
 
@@ -766,7 +987,7 @@ impl RuntimeServiceImpl {
     ) -> Result<Response<ListMetricDescriptorsResponse>, Status> {
         use crate::proto::runtime::v1::MetricDescriptor;
 
-        let descriptors = vec![
+        let mut descriptors = vec![
             MetricDescriptor {
                 name: "container_cpu_usage_seconds_total".to_string(),
                 help: "Total CPU usage in seconds".to_string(),
@@ -808,6 +1029,45 @@ impl RuntimeServiceImpl {
                 label_keys: vec!["pod_id".to_string()],
             },
         ];
+        for resource in ["cpu", "memory", "io"] {
+            for (kind, scope) in [("waiting", "some"), ("stalled", "full")] {
+                descriptors.push(MetricDescriptor {
+                    name: format!("container_pressure_{resource}_{kind}_seconds_total"),
+                    help: format!(
+                        "Total {resource} pressure stall time ({scope}) in whole seconds"
+                    ),
+                    label_keys: vec!["container_id".to_string(), "pod_id".to_string()],
+                });
+            }
+        }
+        descriptors.push(MetricDescriptor {
+            name: "container_oom_events_total".to_string(),
+            help: "Number of processes killed by the OOM killer in the container cgroup"
+                .to_string(),
+            label_keys: vec!["container_id".to_string(), "pod_id".to_string()],
+        });
+        descriptors.push(MetricDescriptor {
+            name: "container_memory_events_total".to_string(),
+            help: "memory.events counters (low, high, max, oom, oom_kill)".to_string(),
+            label_keys: vec![
+                "container_id".to_string(),
+                "pod_id".to_string(),
+                "event".to_string(),
+            ],
+        });
+        descriptors.extend(
+            crate::metrics::NetworkStats::default()
+                .counters()
+                .into_iter()
+                .map(|(counter, _)| MetricDescriptor {
+                    name: format!("container_network_{counter}_total"),
+                    help: format!(
+                        "Per-interface {} counter from the pod network namespace",
+                        counter.replace('_', " ")
+                    ),
+                    label_keys: vec!["pod_id".to_string(), "interface".to_string()],
+                }),
+        );
 
         Ok(Response::new(ListMetricDescriptorsResponse { descriptors }))
     }
@@ -559,6 +559,78 @@ impl RuntimeServiceImpl {
             })
     }
 
+    /// image、snapshot、shim、security、recovery 与拉取 cgroup 的扩展健康条件。
+    pub(super) fn extended_health_conditions(
+        &self,
+        security_capabilities: &crate::security::HostCapabilityReport,
+        recovery_ledger_summary: Option<&crate::services::RecoveryLedgerHealthSummary>,
+        recovery_ledger_check: Option<&crate::state::LedgerCheckReport>,
+    ) -> Vec<crate::services::InternalHealthCondition> {
+        let mut conditions = self.internal_services.health.extended_conditions(
+            crate::services::health::ExtendedConditionsInput {
+                image_root: &self.config.image_root,
+                snapshot_root: &self.config.image_root.join("snapshots"),
+                shim_work_dir: &self.shim_work_dir,
+                security_capabilities: Some(security_capabilities),
+                attempted_repair: self.last_startup_attempted_repair(),
+                repair_succeeded: self.last_startup_repair_succeeded(),
+                shim_reconnect_supported: true,
+                recovery_ledger_summary,
+                recovery_ledger_check_report: recovery_ledger_check,
+            },
+        );
+        conditions.push(self.internal_services.health.pull_cgroup_condition(
+            &self.image_service.pull_cgroup_effective_config(),
+            self.image_service.last_pull_cgroup_scope().as_ref(),
+        ));
+        conditions
+    }
+
+    /// 汇总 CRI 两个条件、扩展条件以及 NRI、CNI 加载与配置重载状态，供 metrics 与
+    /// `/healthz`、`/readyz` 使用；ledger 读取失败时对应条件按未知状态计算。
+    pub async fn health_conditions(&self) -> Vec<crate::services::InternalHealthCondition> {
+        let health = &self.internal_services.health;
+        let cni_load_status = self.probe_cni_load_status().await;
+        let reload_state = self.current_reload_state();
+        let mut conditions = vec![
+            health
+                .runtime_condition_for_path(
+                    &self.config.runtime_path,
+                    self.runtime_binary_version(),
+                )
+                .internal("RuntimeReady"),
+            health
+                .network_condition(
+                    &cni_load_status,
+                    reload_state.last_reload_error.as_deref(),
+                    reload_state.last_cni_watch_error.as_deref(),
+                )
+                .internal("NetworkReady"),
+        ];
+
+        let security_capabilities = crate::security::SecurityManager::new().host_capability_report(
+            &self.nri_config.cdi_spec_dirs,
+            Some(&self.nri_config.blockio_config_path),
+        );
+        let recovery_ledger_summary = self.recovery_ledger_health_summary().await.ok();
+        let recovery_ledger_check = self.recovery_ledger_check_report().await.ok();
+        conditions.extend(self.extended_health_conditions(
+            &security_capabilities,
+            recovery_ledger_summary.as_ref(),
+            recovery_ledger_check.as_ref(),
+        ));
+
+        conditions
+            .push(health.nri_condition(self.nri_config.enable, &self.nri.plugin_status().await));
+        conditions.push(health.cni_load_condition(&cni_load_status));
+        conditions.push(health.config_reload_condition(
+            reload_state.last_reload_at_unix_millis,
+            reload_state.last_reload_error.as_deref(),
+            reload_state.watcher_last_error.as_deref(),
+        ));
+        conditions
+    }
+
     pub(super) async fn probe_cni_load_status(&self) -> crate::network::CniLoadStatus {
         self.probe_cni_load_status_for_config(self.current_cni_config())
             .await
@@ -571,7 +643,39 @@ impl RuntimeServiceImpl {
         if let Some(status) = cni_config.rootless_load_status() {
             return status;
         }
-        let mut cni = match crate::network::CniManager::new(
+        let mut cni = match Self::cni_manager_for_config(&cni_config) {
+            Ok(cni) => cni,
+            Err(status) => return *status,
+        };
+
+        let result = match cni_config.snapshot_store() {
+            Some(store) => {
+                store
+                    .resolve(
+                        &mut cni,
+                        &cni_config.snapshot_key(),
+                        !cni_config.reload_dry_run(),
+                    )
+                    .await
+            }
+            None => cni
+                .load_network_configs()
+                .await
+                .map_err(|err| err.to_string()),
+        };
+        match result {
+            Ok(status) => status,
+            Err(err) => cni.last_load_status().cloned().unwrap_or_else(|| {
+                Self::cni_load_failed_status(format!("failed to load CNI network configs: {}", err))
+            }),
+        }
+    }
+
+    /// 按 CNI 配置构造只用于加载/校验的 `CniManager`。
+    pub(super) fn cni_manager_for_config(
+        cni_config: &crate::network::CniConfig,
+    ) -> Result<crate::network::CniManager, Box<crate::network::CniLoadStatus>> {
+        let mut cni = crate::network::CniManager::new(
             cni_config
                 .plugin_dirs()
                 .iter()
@@ -583,44 +687,32 @@ impl RuntimeServiceImpl {
                 .map(|dir| dir.display().to_string())
                 .collect(),
             cni_config.cache_dir().display().to_string(),
-        ) {
-            Ok(cni) => cni,
-            Err(err) => {
-                return crate::network::CniLoadStatus {
-                    checked_at_unix_millis: chrono::Utc::now().timestamp_millis(),
-                    ready: false,
-                    reason: "CNIConfigLoadFailed".to_string(),
-                    message: format!("failed to initialize CNI manager: {}", err),
-                    discovered_files: Vec::new(),
-                    invalid_files: Vec::new(),
-                    loaded_networks: Vec::new(),
-                    declared_plugins: Vec::new(),
-                    missing_plugin_binaries: Vec::new(),
-                    default_network_name: None,
-                };
-            }
-        };
+        )
+        .map_err(|err| {
+            Box::new(Self::cni_load_failed_status(format!(
+                "failed to initialize CNI manager: {}",
+                err
+            )))
+        })?;
         cni.set_max_conf_num(cni_config.max_conf_num());
         cni.set_default_network_name(cni_config.default_network_name().map(ToOwned::to_owned));
+        cni.set_ip_pref(cni_config.ip_pref());
+        cni.set_teardown_timeout(cni_config.teardown_timeout());
+        Ok(cni)
+    }
 
-        match cni.load_network_configs().await {
-            Ok(status) => status,
-            Err(err) => {
-                cni.last_load_status()
-                    .cloned()
-                    .unwrap_or_else(|| crate::network::CniLoadStatus {
-                        checked_at_unix_millis: chrono::Utc::now().timestamp_millis(),
-                        ready: false,
-                        reason: "CNIConfigLoadFailed".to_string(),
-                        message: format!("failed to load CNI network configs: {}", err),
-                        discovered_files: Vec::new(),
-                        invalid_files: Vec::new(),
-                        loaded_networks: Vec::new(),
-                        declared_plugins: Vec::new(),
-                        missing_plugin_binaries: Vec::new(),
-                        default_network_name: None,
-                    })
-            }
+    fn cni_load_failed_status(message: String) -> crate::network::CniLoadStatus {
+        crate::network::CniLoadStatus {
+            checked_at_unix_millis: chrono::Utc::now().timestamp_millis(),
+            ready: false,
+            reason: "CNIConfigLoadFailed".to_string(),
+            message,
+            discovered_files: Vec::new(),
+            invalid_files: Vec::new(),
+            loaded_networks: Vec::new(),
+            declared_plugins: Vec::new(),
+            missing_plugin_binaries: Vec::new(),
+            default_network_name: None,
         }
     }
 
@@ -937,23 +1029,10 @@ impl RuntimeServiceImpl {
                     &self.nri_config.cdi_spec_dirs,
                     Some(&self.nri_config.blockio_config_path),
                 );
-            let mut extended_health_conditions = self.internal_services.health.extended_conditions(
-                crate::services::health::ExtendedConditionsInput {
-                    image_root: &self.config.image_root,
-                    snapshot_root: &self.config.image_root.join("snapshots"),
-                    shim_work_dir: &self.shim_work_dir,
-                    security_capabilities: Some(&security_capabilities),
-                    attempted_repair: self.last_startup_attempted_repair(),
-                    repair_succeeded: self.last_startup_repair_succeeded(),
-                    shim_reconnect_supported: true,
-                    recovery_ledger_summary: recovery_ledger_summary.as_ref(),
-                    recovery_ledger_check_report: recovery_ledger_check.as_ref(),
-                },
-            );
-            extended_health_conditions.push(
-                self.internal_services
-                    .health
-                    .pull_cgroup_condition(&pull_cgroup_effective, pull_cgroup_last_scope.as_ref()),
+            let extended_health_conditions = self.extended_health_conditions(
+                &security_capabilities,
+                recovery_ledger_summary.as_ref(),
+                recovery_ledger_check.as_ref(),
             );
             let runtime_feature_flags = self.runtime_feature_flags();
             let security_availability = self.security_availability_info();
@@ -1145,6 +1224,7 @@ impl RuntimeServiceImpl {
                     .introspection
                     .snapshot_stats_collection(&self.config),
                 "maxContainerLogLineSize": self.config.max_container_log_line_size,
+                "attachScrollbackBytes": self.config.shim.attach_scrollback_bytes,
                 "pauseImage": self.config.pause_image.clone(),
                 "pauseCommand": self.config.pause_command.clone(),
                 "dropInfraCtr": self.config.drop_infra_ctr,
@@ -1306,6 +1386,8 @@ impl RuntimeServiceImpl {
                 "internalServices": {
                     "events": {
                         "subscriberCount": self.internal_services.events.subscriber_count(),
+                        "subscribers": self.internal_services.events.subscriber_stats(),
+                        "droppedEvents": self.internal_services.events.dropped_event_count(),
                     },
                     "introspection": {
                         "runtimeBackend": self.internal_services.introspection.runtime_backend(&self.config),
@@ -1,7 +1,23 @@
 use super::*;
+use crate::security::spec_patch::ExecOverrides;
 use crate::services::{InternalEvent, InternalEventSeverity};
 
 impl RuntimeServiceImpl {
+    /// handler 允许的 streaming 传输；未知 handler 只保留 SPDY。
+    fn stream_transports(&self, runtime_handler: &str) -> crate::streaming::StreamTransports {
+        self.config
+            .runtime_configs
+            .get(runtime_handler)
+            .map(|config| crate::streaming::StreamTransports {
+                websocket: config.stream_websockets,
+                spdy: config.stream_spdy,
+            })
+            .unwrap_or(crate::streaming::StreamTransports {
+                websocket: false,
+                spdy: true,
+            })
+    }
+
     async fn await_exec_sync_reader(
         &self,
         mut task: tokio::task::JoinHandle<std::io::Result<Vec<u8>>>,
@@ -64,6 +80,105 @@ impl RuntimeServiceImpl {
             .ok_or_else(|| Status::unavailable("streaming server is not initialized"))
     }
 
+    /// 流式会话登记表；流式服务尚未启动时返回 `None`。
+    pub async fn streaming_sessions(&self) -> Option<crate::streaming::StreamingSessionRegistry> {
+        self.streaming
+            .lock()
+            .await
+            .as_ref()
+            .map(|server| server.sessions().clone())
+    }
+
+    /// exec/attach 会话审计器；流式服务尚未启动时返回 `None`。
+    pub async fn streaming_auditor(&self) -> Option<crate::streaming::StreamingAuditor> {
+        self.streaming
+            .lock()
+            .await
+            .as_ref()
+            .map(|server| server.auditor().clone())
+    }
+
+    /// 从内部事件账本还原会话审计记录，同一会话的结束记录覆盖开始记录，按开始时间倒序。
+    pub async fn streaming_audit_records(
+        &self,
+        container_id: Option<&str>,
+    ) -> anyhow::Result<Vec<crate::streaming::StreamingAuditRecord>> {
+        let events = self
+            .internal_services
+            .events
+            .internal_events_since("container", 0)
+            .await?;
+        let mut records: HashMap<String, crate::streaming::StreamingAuditRecord> = HashMap::new();
+        for event in events {
+            if !event.kind.ends_with(".session_started") && !event.kind.ends_with(".session_ended")
+            {
+                continue;
+            }
+            if container_id.is_some_and(|container_id| container_id != event.subject_id) {
+                continue;
+            }
+            let Ok(record) =
+                serde_json::from_value::<crate::streaming::StreamingAuditRecord>(event.details)
+            else {
+                continue;
+            };
+            match records.get(&record.session_id) {
+                Some(existing) if existing.ended() || !record.ended() => {}
+                _ => {
+                    records.insert(record.session_id.clone(), record);
+                }
+            }
+        }
+        let mut records = records.into_values().collect::<Vec<_>>();
+        records.sort_by(|left, right| {
+            right
+                .started_at_unix_millis
+                .cmp(&left.started_at_unix_millis)
+                .then_with(|| left.session_id.cmp(&right.session_id))
+        });
+        Ok(records)
+    }
+
+    /// 按 `api.streaming.audit` 构造会话审计目标；该 handler / namespace 不需要审计时返回 `None`。
+    async fn streaming_audit_target(
+        &self,
+        streaming: &StreamingServer,
+        container_id: &str,
+        runtime_handler: &str,
+        command: Vec<String>,
+        tty: bool,
+    ) -> Option<crate::streaming::StreamingAuditTarget> {
+        let pod_sandbox_id = {
+            let containers = self.containers.lock().await;
+            containers
+                .get(container_id)
+                .map(|container| container.pod_sandbox_id.clone())
+                .unwrap_or_default()
+        };
+        let metadata = {
+            let pod_sandboxes = self.pod_sandboxes.lock().await;
+            pod_sandboxes
+                .get(&pod_sandbox_id)
+                .and_then(|pod| pod.metadata.clone())
+                .unwrap_or_default()
+        };
+        if !streaming
+            .auditor()
+            .config()
+            .applies_to(runtime_handler, &metadata.namespace)
+        {
+            return None;
+        }
+        Some(crate::streaming::StreamingAuditTarget {
+            pod_sandbox_id,
+            pod_namespace: metadata.namespace,
+            pod_name: metadata.name,
+            runtime_handler: runtime_handler.to_string(),
+            command,
+            tty,
+        })
+    }
+
     pub(super) async fn ensure_container_is_streamable(
         &self,
         container_id: &str,
@@ -179,7 +294,16 @@ impl RuntimeServiceImpl {
         &self,
         request: Request<ExecRequest>,
     ) -> Result<Response<ExecResponse>, Status> {
-        let mut req = request.into_inner();
+        self.exec_with_overrides(request.into_inner(), &ExecOverrides::default())
+            .await
+    }
+
+    /// CRI `Exec` 与 `LocalService` 扩展 exec 的共同实现。
+    pub(crate) async fn exec_with_overrides(
+        &self,
+        mut req: ExecRequest,
+        overrides: &ExecOverrides,
+    ) -> Result<Response<ExecResponse>, Status> {
         req.container_id = self.resolve_container_id(&req.container_id).await?;
         let task_socket_path = self.task_socket_path(&req.container_id);
         if !task_socket_path.exists() {
@@ -195,6 +319,9 @@ impl RuntimeServiceImpl {
         }
         self.ensure_container_is_running_for_exec(&req.container_id, "exec")
             .await?;
+        let exec_process = self
+            .exec_process_spec(&req.container_id, &req.cmd, req.tty, overrides)
+            .await?;
         let runtime = self
             .runtime_for_container_request(&req.container_id)
             .await?;
@@ -203,12 +330,7 @@ impl RuntimeServiceImpl {
         let runtime_handler = self
             .runtime_handler_name_for_container_request(&req.container_id)
             .await?;
-        let websocket_enabled = self
-            .config
-            .runtime_configs
-            .get(runtime_handler.as_str())
-            .map(|config| config.stream_websockets)
-            .unwrap_or(false);
+        let transports = self.stream_transports(runtime_handler.as_str());
         let exec_cpu_affinity = self.effective_exec_cpu_affinity(&req.container_id).await;
         let (exec_io_socket_path, exec_resize_socket_path) = if task_socket_path.exists() {
             let request = crate::shim_rpc::ShimRpcRequest::OpenExecSession(
@@ -220,6 +342,7 @@ impl RuntimeServiceImpl {
                     stdout: req.stdout,
                     stderr: req.stderr,
                     exec_cpu_affinity,
+                    process: exec_process.clone(),
                 },
             );
             let task_socket_path_clone = task_socket_path.clone();
@@ -259,6 +382,15 @@ impl RuntimeServiceImpl {
             (None, None)
         };
         let streaming = self.get_streaming_server().await?;
+        let audit = self
+            .streaming_audit_target(
+                &streaming,
+                &req.container_id,
+                &runtime_handler,
+                req.cmd.clone(),
+                req.tty,
+            )
+            .await;
         let response = streaming
             .get_exec(
                 &req,
@@ -266,9 +398,11 @@ impl RuntimeServiceImpl {
                     runtime_path,
                     runtime_config_path,
                     exec_cpu_affinity,
+                    exec_process,
                     exec_io_socket_path,
                     exec_resize_socket_path,
-                    websocket_enabled,
+                    transports,
+                    audit,
                 },
             )
             .await?;
@@ -279,7 +413,16 @@ impl RuntimeServiceImpl {
         &self,
         request: Request<ExecSyncRequest>,
     ) -> Result<Response<ExecSyncResponse>, Status> {
-        let req = request.into_inner();
+        self.exec_sync_with_overrides(request.into_inner(), &ExecOverrides::default())
+            .await
+    }
+
+    /// CRI `ExecSync` 与 `LocalService` 扩展 exec 的共同实现。
+    pub(crate) async fn exec_sync_with_overrides(
+        &self,
+        req: ExecSyncRequest,
+        overrides: &ExecOverrides,
+    ) -> Result<Response<ExecSyncResponse>, Status> {
         let container_id = self.resolve_container_id(&req.container_id).await?;
         let cmd = req.cmd;
         let timeout = req.timeout;
@@ -305,6 +448,9 @@ impl RuntimeServiceImpl {
 
         self.ensure_container_is_running_for_exec(&container_id, "exec_sync")
             .await?;
+        let exec_process = self
+            .exec_process_spec(&container_id, &cmd, false, overrides)
+            .await?;
 
         let runtime = self.runtime_for_container_request(&container_id).await?;
         let exec_cpu_affinity = self.effective_exec_cpu_affinity(&container_id).await;
@@ -319,6 +465,7 @@ impl RuntimeServiceImpl {
                     io_drain_timeout_ms: (!self.config.exec_sync_io_drain_timeout.is_zero())
                         .then_some(self.config.exec_sync_io_drain_timeout.as_millis() as u64),
                     exec_cpu_affinity,
+                    process: exec_process,
                 });
             let task_socket_path_clone = task_socket_path.clone();
             let response = tokio::task::spawn_blocking(move || {
@@ -371,6 +518,14 @@ impl RuntimeServiceImpl {
             command.arg("--config").arg(&runtime_config_path);
         }
         command.arg("exec");
+        let process_file = exec_process
+            .as_ref()
+            .map(crate::runtime::RuncRuntime::write_exec_process_spec)
+            .transpose()
+            .map_err(|err| Status::internal(err.to_string()))?;
+        if let Some(file) = process_file.as_ref() {
+            command.arg("--process").arg(file.path());
+        }
         command.arg(&container_id);
         for arg in &cmd {
             command.arg(arg);
@@ -433,6 +588,39 @@ impl RuntimeServiceImpl {
         }))
     }
 
+    /// 解析容器 bundle 后在 blocking 线程池交给 [`crate::runtime::RuncRuntime::exec_process_spec`]，
+    /// 避免读取 `config.json` 阻塞 executor。
+    async fn exec_process_spec(
+        &self,
+        container_id: &str,
+        command: &[String],
+        tty: bool,
+        overrides: &ExecOverrides,
+    ) -> Result<Option<crate::oci::spec::Process>, Status> {
+        if overrides.is_empty() {
+            return Ok(None);
+        }
+        let bundle_path = self
+            .runtime
+            .bundle_path_for_container(container_id)
+            .map_err(|err| Status::failed_precondition(err.to_string()))?;
+        let command = command.to_vec();
+        let overrides = overrides.clone();
+        let rootless = self.config.rootless.enabled;
+        tokio::task::spawn_blocking(move || {
+            crate::runtime::RuncRuntime::exec_process_spec(
+                &bundle_path,
+                &command,
+                tty,
+                &overrides,
+                rootless,
+            )
+        })
+        .await
+        .map_err(|err| Status::internal(format!("Failed to join exec process spec task: {}", err)))?
+        .map_err(|err| err.to_status())
+    }
+
     pub(super) async fn port_forward(
         &self,
         request: Request<PortForwardRequest>,
@@ -505,14 +693,9 @@ impl RuntimeServiceImpl {
             .map(|state| state.runtime_handler.as_str())
             .filter(|handler| !handler.is_empty())
             .unwrap_or(pod.runtime_handler.as_str());
-        let websocket_enabled = self
-            .config
-            .runtime_configs
-            .get(runtime_handler)
-            .map(|config| config.stream_websockets)
-            .unwrap_or(false);
+        let transports = self.stream_transports(runtime_handler);
         let response = streaming
-            .get_port_forward(&req, PathBuf::from(netns_path), websocket_enabled)
+            .get_port_forward(&req, PathBuf::from(netns_path), transports)
             .await?;
         Ok(Response::new(response))
     }
@@ -528,12 +711,7 @@ impl RuntimeServiceImpl {
         let runtime_handler = self
             .runtime_handler_name_for_container_request(&req.container_id)
             .await?;
-        let websocket_enabled = self
-            .config
-            .runtime_configs
-            .get(runtime_handler.as_str())
-            .map(|config| config.stream_websockets)
-            .unwrap_or(false);
+        let transports = self.stream_transports(runtime_handler.as_str());
         let attach_socket_path = self.attach_socket_path(&req.container_id);
         if !attach_socket_path.exists() {
             let should_try_restore = {
@@ -602,8 +780,17 @@ impl RuntimeServiceImpl {
                     )));
                 };
                 let streaming = self.get_streaming_server().await?;
+                let audit = self
+                    .streaming_audit_target(
+                        &streaming,
+                        &req.container_id,
+                        &runtime_handler,
+                        Vec::new(),
+                        req.tty,
+                    )
+                    .await;
                 let response = streaming
-                    .get_attach_log(&req, PathBuf::from(log_path), websocket_enabled)
+                    .get_attach_log(&req, PathBuf::from(log_path), transports, audit)
                     .await?;
                 return Ok(Response::new(response));
             }
@@ -636,11 +823,38 @@ impl RuntimeServiceImpl {
                     ))
                 })?;
                 let close_runtime = self.runtime.clone();
+                // 交互式会话结束即说明 `crs debug container` 已经退出，临时调试容器随之删除。
+                let ephemeral_debug_cleanup = if req.stdin
+                    && self
+                        .container_internal_state(&req.container_id)
+                        .await
+                        .and_then(|state| state.ephemeral_debug_target)
+                        .is_some()
+                {
+                    Some(self.clone_for_background())
+                } else {
+                    None
+                };
                 let close = crate::streaming::AttachStreamClose::new(
                     req.container_id.clone(),
                     response.stream_id,
                     move |container_id, stream_id| {
-                        close_runtime.close_attach_stream(container_id, stream_id)
+                        let result = close_runtime.close_attach_stream(container_id, stream_id);
+                        if let Some(service) = &ephemeral_debug_cleanup {
+                            if let Ok(handle) = tokio::runtime::Handle::try_current() {
+                                let service = service.clone_for_background();
+                                let container_id = container_id.to_string();
+                                handle.spawn(async move {
+                                    service
+                                        .remove_ephemeral_debug_container(
+                                            &container_id,
+                                            "its attach session ended",
+                                        )
+                                        .await;
+                                });
+                            }
+                        }
+                        result
                     },
                 );
                 (
@@ -652,13 +866,23 @@ impl RuntimeServiceImpl {
                 (None, None, None)
             };
         let streaming = self.get_streaming_server().await?;
+        let audit = self
+            .streaming_audit_target(
+                &streaming,
+                &req.container_id,
+                &runtime_handler,
+                Vec::new(),
+                req.tty,
+            )
+            .await;
         let response = streaming
             .get_attach_with_close(
                 &req,
                 attach_io_socket_path,
                 attach_resize_socket_path,
                 attach_stream_close,
-                websocket_enabled,
+                transports,
+                audit,
             )
             .await?;
         Ok(Response::new(response))
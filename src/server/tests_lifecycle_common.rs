@@ -53,12 +53,14 @@ fn test_runtime_config(root_dir: PathBuf) -> RuntimeConfig {
                     monitor_cgroup: String::new(),
                     monitor_env: Vec::new(),
                     stream_websockets: false,
+                    stream_spdy: true,
                     allowed_annotations: Vec::new(),
                     default_annotations: HashMap::new(),
                     privileged_without_host_devices: false,
                     privileged_without_host_devices_all_devices_allowed: false,
                     container_create_timeout: 240,
                     snapshotter: "internal-overlay-untar".to_string(),
+                    log_drivers: Vec::new(),
                 },
             ),
             (
@@ -74,12 +76,14 @@ fn test_runtime_config(root_dir: PathBuf) -> RuntimeConfig {
                     monitor_cgroup: String::new(),
                     monitor_env: Vec::new(),
                     stream_websockets: false,
+                    stream_spdy: true,
                     allowed_annotations: Vec::new(),
                     default_annotations: HashMap::new(),
                     privileged_without_host_devices: false,
                     privileged_without_host_devices_all_devices_allowed: false,
                     container_create_timeout: 240,
                     snapshotter: "internal-overlay-untar".to_string(),
+                    log_drivers: Vec::new(),
                 },
             ),
         ]),
@@ -111,6 +115,10 @@ fn test_runtime_config(root_dir: PathBuf) -> RuntimeConfig {
         image_oci_artifact_mount_support: true,
         workloads: HashMap::new(),
         enable_pod_events: true,
+        event_log_capacity: 10_000,
+        event_sinks: Vec::new(),
+        container_events_queue_size: 1024,
+        container_events_overflow: crate::config::ContainerEventsOverflow::Disconnect,
         included_pod_metrics: vec!["all".to_string()],
         stats_collection_period: 0,
         pod_sandbox_metrics_collection_period: 0,
@@ -213,6 +221,7 @@ fn test_runtime_config(root_dir: PathBuf) -> RuntimeConfig {
         exec_sync_io_drain_timeout: Duration::ZERO,
         max_container_log_line_size: 4096,
         log_to_journald: false,
+        container_log_rotation: Default::default(),
         no_sync_log: false,
         restrict_oom_score_adj: false,
         enable_unprivileged_ports: false,
@@ -230,12 +239,14 @@ fn test_runtime_config(root_dir: PathBuf) -> RuntimeConfig {
             monitor_env: Vec::new(),
             debug: false,
             log_to_journald: false,
+            log_drivers: Vec::new(),
             no_sync_log: false,
             no_pivot: false,
             no_new_keyring: false,
             systemd_cgroup: false,
             runtime_path: PathBuf::from("/definitely/missing/runc"),
             max_container_log_line_size: 4096,
+            attach_scrollback_bytes: 64 * 1024,
             state_db_path: test_base.join("crius-test.db"),
         },
         streaming: crate::streaming::StreamingConfig::default(),
@@ -1293,12 +1304,14 @@ where
                 monitor_cgroup: String::new(),
                 monitor_env: Vec::new(),
                 stream_websockets: false,
+                stream_spdy: true,
                 allowed_annotations: Vec::new(),
                 default_annotations: HashMap::new(),
                 privileged_without_host_devices: false,
                 privileged_without_host_devices_all_devices_allowed: false,
                 container_create_timeout: 240,
                 snapshotter: "internal-overlay-untar".to_string(),
+                log_drivers: Vec::new(),
             },
         )]),
         runtime_root: dir.path().join("runtime-root"),
@@ -1329,6 +1342,10 @@ where
         image_oci_artifact_mount_support: true,
         workloads: HashMap::new(),
         enable_pod_events: true,
+        event_log_capacity: 10_000,
+        event_sinks: Vec::new(),
+        container_events_queue_size: 1024,
+        container_events_overflow: crate::config::ContainerEventsOverflow::Disconnect,
         included_pod_metrics: vec!["all".to_string()],
         stats_collection_period: 0,
         pod_sandbox_metrics_collection_period: 0,
@@ -1431,6 +1448,7 @@ where
         exec_sync_io_drain_timeout: Duration::ZERO,
         max_container_log_line_size: 4096,
         log_to_journald: false,
+        container_log_rotation: Default::default(),
         no_sync_log: false,
         restrict_oom_score_adj: false,
         enable_unprivileged_ports: false,
@@ -1448,12 +1466,14 @@ where
             monitor_env: Vec::new(),
             debug: false,
             log_to_journald: false,
+            log_drivers: Vec::new(),
             no_sync_log: false,
             no_pivot: false,
             no_new_keyring: false,
             systemd_cgroup: false,
             runtime_path: shim_runtime_path,
             max_container_log_line_size: 4096,
+            attach_scrollback_bytes: 64 * 1024,
             state_db_path: dir.path().join("root").join("crius.db"),
         },
         streaming: crate::streaming::StreamingConfig::default(),
@@ -1529,12 +1549,14 @@ fn test_service_with_fake_runtime_and_nri_and_shim(
                 monitor_cgroup: String::new(),
                 monitor_env: Vec::new(),
                 stream_websockets: false,
+                stream_spdy: true,
                 allowed_annotations: Vec::new(),
                 default_annotations: HashMap::new(),
                 privileged_without_host_devices: false,
                 privileged_without_host_devices_all_devices_allowed: false,
                 container_create_timeout: 240,
                 snapshotter: "internal-overlay-untar".to_string(),
+                log_drivers: Vec::new(),
             },
         )]),
         runtime_root: dir.path().join("runtime-root"),
@@ -1565,6 +1587,10 @@ fn test_service_with_fake_runtime_and_nri_and_shim(
         image_oci_artifact_mount_support: true,
         workloads: HashMap::new(),
         enable_pod_events: true,
+        event_log_capacity: 10_000,
+        event_sinks: Vec::new(),
+        container_events_queue_size: 1024,
+        container_events_overflow: crate::config::ContainerEventsOverflow::Disconnect,
         included_pod_metrics: vec!["all".to_string()],
         stats_collection_period: 0,
         pod_sandbox_metrics_collection_period: 0,
@@ -1667,6 +1693,7 @@ fn test_service_with_fake_runtime_and_nri_and_shim(
         exec_sync_io_drain_timeout: Duration::ZERO,
         max_container_log_line_size: 4096,
         log_to_journald: false,
+        container_log_rotation: Default::default(),
         no_sync_log: false,
         restrict_oom_score_adj: false,
         enable_unprivileged_ports: false,
@@ -1684,12 +1711,14 @@ fn test_service_with_fake_runtime_and_nri_and_shim(
             monitor_env: Vec::new(),
             debug: false,
             log_to_journald: false,
+            log_drivers: Vec::new(),
             no_sync_log: false,
             no_pivot: false,
             no_new_keyring: false,
             systemd_cgroup: false,
             runtime_path: shim_runtime_path,
             max_container_log_line_size: 4096,
+            attach_scrollback_bytes: 64 * 1024,
             state_db_path: dir.path().join("root").join("crius.db"),
         },
         streaming: crate::streaming::StreamingConfig::default(),
@@ -1825,6 +1825,104 @@ async fn reopen_container_log_validates_running_state_and_log_path() {
     assert!(running.message().contains("is not running"));
 }
 
+#[tokio::test]
+async fn container_log_rotation_only_rotates_daemon_managed_logs() {
+    let (dir, service) = test_service_with_fake_runtime_configure(|config| {
+        config.container_log_rotation = crate::config::ContainerLogRotationConfig {
+            enabled: true,
+            max_size: 16,
+            max_files: 2,
+            compress: true,
+            check_interval: std::time::Duration::from_secs(3600),
+        };
+    });
+    let logs_dir = dir.path().join("logs");
+    fs::create_dir_all(&logs_dir).unwrap();
+    let payload = b"2026-10-19T00:00:00Z stdout F rotate me please\n".repeat(4);
+
+    for (id, daemon_managed_log) in [("container-local", true), ("container-kubelet", false)] {
+        let log_path = logs_dir.join(format!("{id}.log"));
+        fs::write(&log_path, &payload).unwrap();
+        let mut annotations = HashMap::new();
+        RuntimeServiceImpl::insert_internal_state(
+            &mut annotations,
+            INTERNAL_CONTAINER_STATE_KEY,
+            &StoredContainerState {
+                log_path: Some(log_path.display().to_string()),
+                daemon_managed_log,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        service
+            .containers
+            .lock()
+            .await
+            .insert(id.to_string(), test_container(id, "pod-1", annotations));
+        set_fake_runtime_state(&dir, id, "running");
+    }
+
+    assert_eq!(service.rotate_container_logs_once().await, 1);
+
+    let local_log = logs_dir.join("container-local.log");
+    assert!(!local_log.exists());
+    assert!(!log_rotation::rotated_log_path(&local_log, 1, false).exists());
+    let gzip = fs::read(log_rotation::rotated_log_path(&local_log, 1, true)).unwrap();
+    assert_eq!(&gzip[..2], &[0x1f, 0x8b]);
+    let inflated = miniz_oxide::inflate::decompress_to_vec(&gzip[10..gzip.len() - 8]).unwrap();
+    assert_eq!(inflated, payload);
+    assert_eq!(
+        fs::read(logs_dir.join("container-kubelet.log")).unwrap(),
+        payload
+    );
+
+    let containers = service.containers.lock().await.clone();
+    let info = service
+        .build_container_verbose_info(&containers["container-local"], ContainerState::ContainerRunning as i32)
+        .await
+        .unwrap();
+    let info: serde_json::Value = serde_json::from_str(&info["info"]).unwrap();
+    assert_eq!(info["logRotation"]["enabled"], true);
+    assert_eq!(info["logRotation"]["rotations"], 1);
+    let info = service
+        .build_container_verbose_info(&containers["container-kubelet"], ContainerState::ContainerRunning as i32)
+        .await
+        .unwrap();
+    let info: serde_json::Value = serde_json::from_str(&info["info"]).unwrap();
+    assert_eq!(info["logRotation"]["enabled"], false);
+    assert_eq!(info["logRotation"]["disabledReason"], "kubelet-managed");
+}
+
+#[test]
+fn log_rotation_gzip_streams_large_files_with_gzip_trailer() {
+    let dir = tempdir().unwrap();
+    let trailer = |gzip: &[u8]| {
+        (
+            u32::from_le_bytes(gzip[gzip.len() - 8..gzip.len() - 4].try_into().unwrap()),
+            u32::from_le_bytes(gzip[gzip.len() - 4..].try_into().unwrap()),
+        )
+    };
+
+    let small = dir.path().join("small.log");
+    fs::write(&small, b"123456789").unwrap();
+    log_rotation::gzip_file(&small, &dir.path().join("small.log.gz")).unwrap();
+    let gzip = fs::read(dir.path().join("small.log.gz")).unwrap();
+    assert_eq!(trailer(&gzip), (0xcbf4_3926, 9));
+
+    // 跨越多个读取块的日志仍是单个完整的 deflate 流。
+    let payload = (0..300_000u32)
+        .map(|index| (index * 7 % 251) as u8)
+        .collect::<Vec<_>>();
+    let large = dir.path().join("large.log");
+    fs::write(&large, &payload).unwrap();
+    log_rotation::gzip_file(&large, &dir.path().join("large.log.gz")).unwrap();
+    assert!(!large.exists());
+    let gzip = fs::read(dir.path().join("large.log.gz")).unwrap();
+    let inflated = miniz_oxide::inflate::decompress_to_vec(&gzip[10..gzip.len() - 8]).unwrap();
+    assert_eq!(inflated, payload);
+    assert_eq!(trailer(&gzip).1, payload.len() as u32);
+}
+
 #[tokio::test]
 async fn update_container_resources_validates_state_and_persists_resources() {
     let (dir, service) = test_service_with_fake_runtime();
@@ -202,12 +202,14 @@ async fn create_container_direct_task_backend_skips_oci_context() {
             monitor_cgroup: String::new(),
             monitor_env: Vec::new(),
             stream_websockets: false,
+            stream_spdy: true,
             allowed_annotations: Vec::new(),
             default_annotations: HashMap::new(),
             privileged_without_host_devices: false,
             privileged_without_host_devices_all_devices_allowed: false,
             container_create_timeout: 30,
             snapshotter: "internal-overlay-untar".to_string(),
+            log_drivers: Vec::new(),
         },
     )]);
     let service = RuntimeServiceImpl::new_with_runtime_backends(
@@ -327,12 +329,14 @@ async fn create_container_oci_backend_creates_task_after_adjusted_bundle() {
             monitor_cgroup: String::new(),
             monitor_env: Vec::new(),
             stream_websockets: false,
+            stream_spdy: true,
             allowed_annotations: Vec::new(),
             default_annotations: HashMap::new(),
             privileged_without_host_devices: false,
             privileged_without_host_devices_all_devices_allowed: false,
             container_create_timeout: 30,
             snapshotter: "internal-overlay-untar".to_string(),
+            log_drivers: Vec::new(),
         },
     )]);
     let service = RuntimeServiceImpl::new_with_runtime_backends(
@@ -458,12 +462,14 @@ async fn create_container_from_crs_run_pod_gets_default_log_path() {
             monitor_cgroup: String::new(),
             monitor_env: Vec::new(),
             stream_websockets: false,
+            stream_spdy: true,
             allowed_annotations: Vec::new(),
             default_annotations: HashMap::new(),
             privileged_without_host_devices: false,
             privileged_without_host_devices_all_devices_allowed: false,
             container_create_timeout: 30,
             snapshotter: "internal-overlay-untar".to_string(),
+            log_drivers: Vec::new(),
         },
     )]);
     let service = RuntimeServiceImpl::new_with_runtime_backends(
@@ -541,6 +547,152 @@ async fn create_container_from_crs_run_pod_gets_default_log_path() {
     );
 }
 
+#[tokio::test]
+async fn create_debug_container_joins_target_namespaces_and_is_marked_ephemeral() {
+    let dir = tempdir().unwrap();
+    let runtime_root = dir.path().join("fake-runtime-root");
+    let fake_backend = Arc::new(common::FakeRuntimeBackend::new(&runtime_root));
+    let mut config = test_runtime_config(dir.path().join("root"));
+    config.runtime = "fake".to_string();
+    config.runtime_handlers = vec!["fake".to_string()];
+    config.runtime_root = runtime_root.clone();
+    config.runtime_configs = HashMap::from([(
+        "fake".to_string(),
+        crate::config::ResolvedRuntimeHandlerConfig {
+            backend: "fake".to_string(),
+            backend_options: HashMap::new(),
+            runtime_path: "/fake/runtime".to_string(),
+            runtime_config_path: String::new(),
+            runtime_root: runtime_root.display().to_string(),
+            platform_runtime_paths: HashMap::new(),
+            monitor_path: "/fake/shim".to_string(),
+            monitor_cgroup: String::new(),
+            monitor_env: Vec::new(),
+            stream_websockets: false,
+            stream_spdy: true,
+            allowed_annotations: Vec::new(),
+            default_annotations: HashMap::new(),
+            privileged_without_host_devices: false,
+            privileged_without_host_devices_all_devices_allowed: false,
+            container_create_timeout: 30,
+            snapshotter: "internal-overlay-untar".to_string(),
+            log_drivers: Vec::new(),
+        },
+    )]);
+    let service = RuntimeServiceImpl::new_with_runtime_backends(
+        config,
+        HashMap::from([(
+            "fake".to_string(),
+            fake_backend as Arc<dyn crate::runtime::RuntimeBackend>,
+        )]),
+    );
+    let mut pod = test_pod("pod-debug-target", HashMap::new());
+    pod.runtime_handler = "fake".to_string();
+    service
+        .pod_sandboxes
+        .lock()
+        .await
+        .insert("pod-debug-target".to_string(), pod);
+
+    let container_config = |name: &str| crate::proto::runtime::v1::ContainerConfig {
+        metadata: Some(ContainerMetadata {
+            name: name.to_string(),
+            attempt: 0,
+        }),
+        image: Some(ImageSpec {
+            image: "busybox:latest".to_string(),
+            user_specified_image: "busybox:latest".to_string(),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let target_id = RuntimeService::create_container(
+        &service,
+        Request::new(CreateContainerRequest {
+            pod_sandbox_id: "pod-debug-target".to_string(),
+            config: Some(crate::proto::runtime::v1::ContainerConfig {
+                log_path: "app.log".to_string(),
+                ..container_config("app")
+            }),
+            sandbox_config: Some(crate::proto::runtime::v1::PodSandboxConfig {
+                log_directory: dir.path().join("logs").display().to_string(),
+                ..Default::default()
+            }),
+        }),
+    )
+    .await
+    .expect("target container should be created")
+    .into_inner()
+    .container_id;
+    let mut target_spec = crate::oci::spec::Spec::new("1.0.2");
+    target_spec.root = Some(crate::oci::spec::Root {
+        path: "rootfs".to_string(),
+        readonly: None,
+    });
+    fs::write(
+        runtime_root.join(&target_id).join("config.json"),
+        serde_json::to_vec(&target_spec).unwrap(),
+    )
+    .unwrap();
+
+    let response = service
+        .create_debug_container_impl(Request::new(
+            crate::proto::local::v1::CreateDebugContainerRequest {
+                target_container_id: target_id[..12].to_string(),
+                config: Some(container_config("debugger")),
+                mount_target_rootfs: true,
+            },
+        ))
+        .await
+        .expect("debug container should be created next to the target")
+        .into_inner();
+    assert_eq!(response.pod_sandbox_id, "pod-debug-target");
+
+    let container = service
+        .containers
+        .lock()
+        .await
+        .get(&response.container_id)
+        .cloned()
+        .expect("debug container should be stored");
+    assert_eq!(container.pod_sandbox_id, "pod-debug-target");
+    let state = RuntimeServiceImpl::read_internal_state::<StoredContainerState>(
+        &container.annotations,
+        INTERNAL_CONTAINER_STATE_KEY,
+    )
+    .expect("stored container state");
+    assert_eq!(
+        state.ephemeral_debug_target.as_deref(),
+        Some(target_id.as_str())
+    );
+    assert!(state.daemon_managed_log);
+    assert_eq!(
+        state.network_namespace_path.as_deref(),
+        Some("/proc/1/ns/net")
+    );
+    let target_mount = state
+        .mounts
+        .iter()
+        .find(|mount| mount.container_path == "/target")
+        .expect("target rootfs should be mounted at /target");
+    assert_eq!(
+        PathBuf::from(&target_mount.host_path),
+        runtime_root.join(&target_id).join("rootfs")
+    );
+
+    let err = service
+        .create_debug_container_impl(Request::new(
+            crate::proto::local::v1::CreateDebugContainerRequest {
+                target_container_id: response.container_id.clone(),
+                config: Some(container_config("nested")),
+                mount_target_rootfs: false,
+            },
+        ))
+        .await
+        .expect_err("debug containers should not target other debug containers");
+    assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+}
+
 #[tokio::test]
 async fn create_container_applies_cdi_devices_without_nri_adjustment() {
     let cdi_dir = tempdir().unwrap();
@@ -600,12 +752,14 @@ async fn create_container_applies_cdi_devices_without_nri_adjustment() {
             monitor_cgroup: String::new(),
             monitor_env: Vec::new(),
             stream_websockets: false,
+            stream_spdy: true,
             allowed_annotations: Vec::new(),
             default_annotations: HashMap::new(),
             privileged_without_host_devices: false,
             privileged_without_host_devices_all_devices_allowed: false,
             container_create_timeout: 30,
             snapshotter: "internal-overlay-untar".to_string(),
+            log_drivers: Vec::new(),
         },
     )]);
     let mut nri_config = NriConfig {
@@ -748,12 +902,14 @@ async fn create_container_merges_nri_cdi_devices_without_duplicate_injection() {
             monitor_cgroup: String::new(),
             monitor_env: Vec::new(),
             stream_websockets: false,
+            stream_spdy: true,
             allowed_annotations: Vec::new(),
             default_annotations: HashMap::new(),
             privileged_without_host_devices: false,
             privileged_without_host_devices_all_devices_allowed: false,
             container_create_timeout: 30,
             snapshotter: "internal-overlay-untar".to_string(),
+            log_drivers: Vec::new(),
         },
     )]);
     let fake_nri = Arc::new(FakeNri {
@@ -889,12 +1045,14 @@ async fn create_container_applies_blockio_and_rdt_classes_from_annotations() {
             monitor_cgroup: String::new(),
             monitor_env: Vec::new(),
             stream_websockets: false,
+            stream_spdy: true,
             allowed_annotations: Vec::new(),
             default_annotations: HashMap::new(),
             privileged_without_host_devices: false,
             privileged_without_host_devices_all_devices_allowed: false,
             container_create_timeout: 30,
             snapshotter: "internal-overlay-untar".to_string(),
+            log_drivers: Vec::new(),
         },
     )]);
     let mut service = RuntimeServiceImpl::new_with_runtime_backends(
@@ -1023,12 +1181,14 @@ async fn create_container_rejects_cdi_before_injection_when_host_cdi_is_degraded
             monitor_cgroup: String::new(),
             monitor_env: Vec::new(),
             stream_websockets: false,
+            stream_spdy: true,
             allowed_annotations: Vec::new(),
             default_annotations: HashMap::new(),
             privileged_without_host_devices: false,
             privileged_without_host_devices_all_devices_allowed: false,
             container_create_timeout: 30,
             snapshotter: "internal-overlay-untar".to_string(),
+            log_drivers: Vec::new(),
         },
     )]);
     let mut service = RuntimeServiceImpl::new_with_runtime_backends(
@@ -1099,12 +1259,14 @@ async fn create_container_rejects_blockio_class_when_host_blockio_is_degraded()
             monitor_cgroup: String::new(),
             monitor_env: Vec::new(),
             stream_websockets: false,
+            stream_spdy: true,
             allowed_annotations: Vec::new(),
             default_annotations: HashMap::new(),
             privileged_without_host_devices: false,
             privileged_without_host_devices_all_devices_allowed: false,
             container_create_timeout: 30,
             snapshotter: "internal-overlay-untar".to_string(),
+            log_drivers: Vec::new(),
         },
     )]);
     let service = RuntimeServiceImpl::new_with_runtime_backends(
@@ -226,6 +226,102 @@ async fn exec_sync_prefers_task_shim_rpc_when_socket_is_available() {
     handle.join().unwrap();
 }
 
+#[tokio::test]
+async fn exec_sync_with_overrides_sends_validated_process_spec_to_shim() {
+    let (dir, service) = test_service_with_fake_runtime();
+
+    service.containers.lock().await.insert(
+        "container-running".to_string(),
+        test_container("container-running", "pod-1", HashMap::new()),
+    );
+    set_fake_runtime_state_without_shim(&dir, "container-running", "running");
+    let bundle = service
+        .runtime
+        .bundle_path_for_container("container-running")
+        .unwrap();
+    std::fs::create_dir_all(&bundle).unwrap();
+    std::fs::write(
+        bundle.join("config.json"),
+        serde_json::json!({
+            "ociVersion": "1.0.2",
+            "process": {
+                "user": {"uid": 1000, "gid": 1000},
+                "args": ["/pause"],
+                "cwd": "/",
+            },
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let task_socket = dir
+        .path()
+        .join("shims")
+        .join("container-running")
+        .join("task.sock");
+    let running = Arc::new(std::sync::atomic::AtomicBool::new(true));
+    let running_for_thread = running.clone();
+    let socket_for_thread = task_socket.clone();
+    let handle = std::thread::spawn(move || {
+        crate::shim_rpc::server::serve(
+            &socket_for_thread,
+            running_for_thread,
+            Arc::new(TestShimExecSyncHandler),
+        )
+        .unwrap();
+    });
+
+    let deadline = std::time::Instant::now() + Duration::from_secs(2);
+    while !task_socket.exists() {
+        if std::time::Instant::now() >= deadline {
+            panic!("task shim socket was not created before deadline");
+        }
+        tokio::time::sleep(Duration::from_millis(25)).await;
+    }
+
+    let request = ExecSyncRequest {
+        container_id: "container-running".to_string(),
+        cmd: vec!["id".to_string()],
+        timeout: 0,
+    };
+    let response = service
+        .exec_sync_with_overrides(
+            request.clone(),
+            &crate::security::spec_patch::ExecOverrides {
+                cwd: Some("/srv".to_string()),
+                user: Some(crate::security::spec_patch::ExecUser {
+                    uid: 1001,
+                    gid: None,
+                }),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(response.stdout, b"shim:id cwd=/srv uid=1001");
+
+    let status = service
+        .exec_sync_with_overrides(
+            request,
+            &crate::security::spec_patch::ExecOverrides {
+                user: Some(crate::security::spec_patch::ExecUser {
+                    uid: 0,
+                    gid: None,
+                }),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    assert!(status.message().contains("cannot switch to uid 0"));
+
+    running.store(false, std::sync::atomic::Ordering::Relaxed);
+    let _ = std::os::unix::net::UnixStream::connect(&task_socket);
+    handle.join().unwrap();
+}
+
 #[tokio::test]
 async fn exec_sync_rejects_shim_owned_container_when_task_socket_is_missing() {
     let (dir, service) = test_service_with_fake_runtime();
@@ -489,12 +585,14 @@ async fn exec_sync_uses_runtime_binary_for_non_default_handler() {
                 monitor_cgroup: String::new(),
                 monitor_env: Vec::new(),
                 stream_websockets: false,
+                stream_spdy: true,
                 allowed_annotations: Vec::new(),
                 default_annotations: HashMap::new(),
                 privileged_without_host_devices: false,
                 privileged_without_host_devices_all_devices_allowed: false,
                 container_create_timeout: 240,
                 snapshotter: "internal-overlay-untar".to_string(),
+                log_drivers: Vec::new(),
             },
         ),
         (
@@ -510,12 +608,14 @@ async fn exec_sync_uses_runtime_binary_for_non_default_handler() {
                 monitor_cgroup: String::new(),
                 monitor_env: Vec::new(),
                 stream_websockets: false,
+                stream_spdy: true,
                 allowed_annotations: Vec::new(),
                 default_annotations: HashMap::new(),
                 privileged_without_host_devices: false,
                 privileged_without_host_devices_all_devices_allowed: false,
                 container_create_timeout: 240,
                 snapshotter: "internal-overlay-untar".to_string(),
+                log_drivers: Vec::new(),
             },
         ),
     ]);
@@ -624,12 +724,14 @@ esac
             monitor_cgroup: String::new(),
             monitor_env: Vec::new(),
             stream_websockets: false,
+            stream_spdy: true,
             allowed_annotations: Vec::new(),
             default_annotations: HashMap::new(),
             privileged_without_host_devices: false,
             privileged_without_host_devices_all_devices_allowed: false,
             container_create_timeout: 240,
             snapshotter: "internal-overlay-untar".to_string(),
+            log_drivers: Vec::new(),
         },
     );
     config.exec_cpu_affinity = "first".to_string();
@@ -741,12 +843,14 @@ esac
             monitor_cgroup: String::new(),
             monitor_env: Vec::new(),
             stream_websockets: false,
+            stream_spdy: true,
             allowed_annotations: Vec::new(),
             default_annotations: HashMap::new(),
             privileged_without_host_devices: false,
             privileged_without_host_devices_all_devices_allowed: false,
             container_create_timeout: 240,
             snapshotter: "internal-overlay-untar".to_string(),
+            log_drivers: Vec::new(),
         },
     );
     config.exec_cpu_affinity = "first".to_string();
@@ -1292,12 +1396,14 @@ sleep 1
                     monitor_cgroup: String::new(),
                     monitor_env: Vec::new(),
                     stream_websockets: false,
+                    stream_spdy: true,
                     allowed_annotations: Vec::new(),
                     default_annotations: HashMap::new(),
                     privileged_without_host_devices: false,
                     privileged_without_host_devices_all_devices_allowed: false,
                     container_create_timeout: 240,
                     snapshotter: "internal-overlay-untar".to_string(),
+                    log_drivers: Vec::new(),
                 },
             )]),
             runtime_root: dir.path().join("runtime-root"),
@@ -1325,6 +1431,10 @@ sleep 1
             image_oci_artifact_mount_support: true,
             workloads: HashMap::new(),
             enable_pod_events: true,
+            event_log_capacity: 10_000,
+            event_sinks: Vec::new(),
+            container_events_queue_size: 1024,
+            container_events_overflow: crate::config::ContainerEventsOverflow::Disconnect,
             included_pod_metrics: vec!["all".to_string()],
             stats_collection_period: 0,
             pod_sandbox_metrics_collection_period: 0,
@@ -1427,6 +1537,7 @@ sleep 1
             exec_sync_io_drain_timeout: Duration::ZERO,
             max_container_log_line_size: 4096,
             log_to_journald: false,
+            container_log_rotation: Default::default(),
             no_sync_log: false,
             restrict_oom_score_adj: false,
             enable_unprivileged_ports: false,
@@ -1444,12 +1555,14 @@ sleep 1
                 monitor_env: Vec::new(),
                 debug: false,
                 log_to_journald: false,
+                log_drivers: Vec::new(),
                 no_sync_log: false,
                 no_pivot: false,
                 no_new_keyring: false,
                 systemd_cgroup: false,
                 runtime_path: PathBuf::from("/definitely/missing/runc"),
                 max_container_log_line_size: 4096,
+                attach_scrollback_bytes: 64 * 1024,
                 state_db_path: dir.path().join("root").join("crius.db"),
             },
             streaming: crate::streaming::StreamingConfig::default(),
@@ -1582,12 +1695,14 @@ sleep 1
                     monitor_cgroup: String::new(),
                     monitor_env: Vec::new(),
                     stream_websockets: false,
+                    stream_spdy: true,
                     allowed_annotations: Vec::new(),
                     default_annotations: HashMap::new(),
                     privileged_without_host_devices: false,
                     privileged_without_host_devices_all_devices_allowed: false,
                     container_create_timeout: 240,
                     snapshotter: "internal-overlay-untar".to_string(),
+                    log_drivers: Vec::new(),
                 },
             )]),
             runtime_root: dir.path().join("runtime-root"),
@@ -1618,6 +1733,10 @@ sleep 1
             image_oci_artifact_mount_support: true,
             workloads: HashMap::new(),
             enable_pod_events: true,
+            event_log_capacity: 10_000,
+            event_sinks: Vec::new(),
+            container_events_queue_size: 1024,
+            container_events_overflow: crate::config::ContainerEventsOverflow::Disconnect,
             included_pod_metrics: vec!["all".to_string()],
             stats_collection_period: 0,
             pod_sandbox_metrics_collection_period: 0,
@@ -1720,6 +1839,7 @@ sleep 1
             exec_sync_io_drain_timeout: Duration::ZERO,
             max_container_log_line_size: 4096,
             log_to_journald: false,
+            container_log_rotation: Default::default(),
             no_sync_log: false,
             restrict_oom_score_adj: false,
             enable_unprivileged_ports: false,
@@ -1737,12 +1857,14 @@ sleep 1
                 monitor_env: Vec::new(),
                 debug: false,
                 log_to_journald: false,
+                log_drivers: Vec::new(),
                 no_sync_log: false,
                 no_pivot: false,
                 no_new_keyring: false,
                 systemd_cgroup: false,
                 runtime_path: PathBuf::from("/definitely/missing/runc"),
                 max_container_log_line_size: 4096,
+                attach_scrollback_bytes: 64 * 1024,
                 state_db_path: dir.path().join("root").join("crius.db"),
             },
             streaming: crate::streaming::StreamingConfig::default(),
@@ -1880,12 +2002,14 @@ sleep 1
                     monitor_cgroup: String::new(),
                     monitor_env: Vec::new(),
                     stream_websockets: false,
+                    stream_spdy: true,
                     allowed_annotations: Vec::new(),
                     default_annotations: HashMap::new(),
                     privileged_without_host_devices: false,
                     privileged_without_host_devices_all_devices_allowed: false,
                     container_create_timeout: 240,
                     snapshotter: "internal-overlay-untar".to_string(),
+                    log_drivers: Vec::new(),
                 },
             )]),
             runtime_root: dir.path().join("runtime-root"),
@@ -1916,6 +2040,10 @@ sleep 1
             image_oci_artifact_mount_support: true,
             workloads: HashMap::new(),
             enable_pod_events: true,
+            event_log_capacity: 10_000,
+            event_sinks: Vec::new(),
+            container_events_queue_size: 1024,
+            container_events_overflow: crate::config::ContainerEventsOverflow::Disconnect,
             included_pod_metrics: vec!["all".to_string()],
             stats_collection_period: 0,
             pod_sandbox_metrics_collection_period: 0,
@@ -2018,6 +2146,7 @@ sleep 1
             exec_sync_io_drain_timeout: Duration::ZERO,
             max_container_log_line_size: 4096,
             log_to_journald: false,
+            container_log_rotation: Default::default(),
             no_sync_log: false,
             restrict_oom_score_adj: false,
             enable_unprivileged_ports: false,
@@ -2035,12 +2164,14 @@ sleep 1
                 monitor_env: Vec::new(),
                 debug: false,
                 log_to_journald: false,
+                log_drivers: Vec::new(),
                 no_sync_log: false,
                 no_pivot: false,
                 no_new_keyring: false,
                 systemd_cgroup: false,
                 runtime_path: PathBuf::from("/definitely/missing/runc"),
                 max_container_log_line_size: 4096,
+                attach_scrollback_bytes: 64 * 1024,
                 state_db_path: dir.path().join("root").join("crius.db"),
             },
             streaming: crate::streaming::StreamingConfig::default(),
@@ -1875,12 +1875,14 @@ async fn recover_state_reconciles_direct_task_backend_without_oci_bundle() {
             monitor_cgroup: String::new(),
             monitor_env: Vec::new(),
             stream_websockets: false,
+            stream_spdy: true,
             allowed_annotations: Vec::new(),
             default_annotations: HashMap::new(),
             privileged_without_host_devices: false,
             privileged_without_host_devices_all_devices_allowed: false,
             container_create_timeout: 30,
             snapshotter: "internal-overlay-untar".to_string(),
+            log_drivers: Vec::new(),
         },
     )]);
     let service = RuntimeServiceImpl::new_with_runtime_backends(
@@ -2451,3 +2453,100 @@ async fn recover_state_cleans_orphaned_pod_workspaces_but_keeps_recovered_ones()
             && event.details["state"] == "pending"
             && event.details["ownerKind"] == "pod"));
 }
+
+#[tokio::test]
+async fn ephemeral_debug_reaper_removes_exited_and_unstarted_debug_containers() {
+    let (dir, service) = test_service_with_fake_runtime();
+    for (container_id, runtime_state, state) in [
+        ("debug-exited", "stopped", ContainerState::ContainerExited),
+        ("debug-unstarted", "created", ContainerState::ContainerCreated),
+        ("debug-running", "running", ContainerState::ContainerRunning),
+    ] {
+        set_fake_runtime_state(&dir, container_id, runtime_state);
+        let mut annotations = HashMap::new();
+        RuntimeServiceImpl::insert_internal_state(
+            &mut annotations,
+            INTERNAL_CONTAINER_STATE_KEY,
+            &StoredContainerState {
+                ephemeral_debug_target: Some("debug-target".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        service.containers.lock().await.insert(
+            container_id.to_string(),
+            Container {
+                id: container_id.to_string(),
+                state: state as i32,
+                annotations,
+                ..Default::default()
+            },
+        );
+        service.spawn_ephemeral_debug_container_reaper(container_id, Duration::ZERO);
+    }
+
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(10);
+    loop {
+        let remaining = service.containers.lock().await.len();
+        if remaining == 1 || tokio::time::Instant::now() >= deadline {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    let containers = service.containers.lock().await;
+    assert!(!containers.contains_key("debug-exited"));
+    assert!(!containers.contains_key("debug-unstarted"));
+    assert!(containers.contains_key("debug-running"));
+}
+
+#[tokio::test]
+async fn recover_state_removes_ephemeral_debug_containers() {
+    let (dir, service) = test_service_with_fake_runtime();
+    set_fake_runtime_state(&dir, "debug-target", "running");
+    set_fake_runtime_state(&dir, "debug-session", "running");
+
+    for (container_id, ephemeral_debug_target) in [
+        ("debug-target", None),
+        ("debug-session", Some("debug-target".to_string())),
+    ] {
+        let mut annotations = HashMap::new();
+        RuntimeServiceImpl::insert_internal_state(
+            &mut annotations,
+            INTERNAL_CONTAINER_STATE_KEY,
+            &StoredContainerState {
+                ephemeral_debug_target,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        service
+            .persistence
+            .lock()
+            .await
+            .save_container(
+                container_id,
+                None,
+                crate::runtime::ContainerStatus::Running,
+                "busybox:latest",
+                &Vec::new(),
+                &HashMap::new(),
+                &annotations,
+            )
+            .unwrap();
+    }
+
+    service.recover_state().await.unwrap();
+
+    let containers = service.containers.lock().await;
+    assert!(containers.contains_key("debug-target"));
+    assert!(!containers.contains_key("debug-session"));
+    drop(containers);
+    assert!(service
+        .persistence
+        .lock()
+        .await
+        .storage()
+        .get_container("debug-session")
+        .unwrap()
+        .is_none());
+}
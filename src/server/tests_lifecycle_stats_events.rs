@@ -413,6 +413,7 @@ fn build_pod_metrics_returns_all_metrics_when_configured_with_all() {
             filesystem_usage: 50,
             rx_bytes: 60,
             tx_bytes: 70,
+            interfaces: Vec::new(),
         },
     );
     let names: Vec<&str> = metrics.iter().map(|metric| metric.name.as_str()).collect();
@@ -431,6 +432,91 @@ fn build_pod_metrics_returns_all_metrics_when_configured_with_all() {
     );
 }
 
+#[test]
+fn build_pod_metrics_labels_network_counters_per_interface() {
+    let mut service = test_service();
+    service.config.included_pod_metrics = vec!["network".to_string()];
+    let interface = |name: &str, rx_bytes| crate::metrics::NetworkStats {
+        name: name.to_string(),
+        rx_bytes,
+        ..Default::default()
+    };
+
+    let metrics = service.build_pod_metrics(
+        "pod-net",
+        1,
+        crate::server::stats::PodMetricTotals {
+            cpu_usage: 0,
+            memory_usage: 0,
+            memory_limit: 0,
+            pids: 0,
+            filesystem_usage: 0,
+            rx_bytes: 30,
+            tx_bytes: 0,
+            interfaces: vec![interface("eth0", 10), interface("net1", 20)],
+        },
+    );
+
+    let receive: Vec<(Vec<String>, u64)> = metrics
+        .iter()
+        .filter(|metric| metric.name == "container_network_receive_bytes_total")
+        .map(|metric| {
+            (
+                metric.label_values.clone(),
+                metric.value.as_ref().unwrap().value,
+            )
+        })
+        .collect();
+    assert_eq!(
+        receive,
+        vec![
+            (vec!["pod-net".to_string(), "eth0".to_string()], 10),
+            (vec!["pod-net".to_string(), "net1".to_string()], 20),
+        ]
+    );
+    assert_eq!(metrics.len(), 2 + 2 * 8);
+}
+
+#[test]
+fn pod_network_usage_uses_eth0_as_default_interface() {
+    let interfaces = ["cali0", "eth0", "net1"].map(|name| crate::metrics::NetworkStats {
+        name: name.to_string(),
+        tx_bytes: 5,
+        ..Default::default()
+    });
+
+    let usage = RuntimeServiceImpl::pod_network_usage(1, &interfaces);
+
+    assert_eq!(usage.default_interface.unwrap().name, "eth0");
+    let names: Vec<_> = usage
+        .interfaces
+        .iter()
+        .map(|interface| interface.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["cali0", "net1"]);
+    assert_eq!(usage.interfaces[1].tx_bytes.as_ref().unwrap().value, 5);
+}
+
+#[tokio::test]
+async fn list_metric_descriptors_include_per_interface_network_counters() {
+    let service = test_service();
+
+    let descriptors = RuntimeService::list_metric_descriptors(
+        &service,
+        Request::new(ListMetricDescriptorsRequest {}),
+    )
+    .await
+    .unwrap()
+    .into_inner()
+    .descriptors;
+
+    let transmit_dropped = descriptors
+        .iter()
+        .find(|descriptor| descriptor.name == "container_network_transmit_packets_dropped_total")
+        .expect("per-interface descriptor");
+    assert_eq!(transmit_dropped.label_keys, vec!["pod_id", "interface"]);
+}
+
 #[tokio::test]
 async fn list_containers_returns_empty_when_short_id_filter_is_ambiguous() {
     let service = test_service();
@@ -815,6 +901,7 @@ async fn list_pod_sandbox_metrics_respects_included_pod_metrics_configuration()
             filesystem_usage: 50,
             rx_bytes: 60,
             tx_bytes: 70,
+            interfaces: Vec::new(),
         },
     );
     let metric_names: Vec<&str> = pod_metrics
@@ -1032,7 +1119,8 @@ async fn get_container_events_reports_lagged_consumers() {
             .unwrap()
             .into_inner();
 
-    for idx in 0..600 {
+    // 超过订阅者队列上限（测试配置为 1024）。
+    for idx in 0..1100 {
         service.publish_event(ContainerEventResponse {
             container_id: format!("container-{}", idx),
             container_event_type: ContainerEventType::ContainerCreatedEvent as i32,
@@ -1057,6 +1145,70 @@ async fn get_container_events_reports_lagged_consumers() {
     assert!(saw_lagged, "expected lagged consumer error");
 }
 
+#[tokio::test]
+async fn get_container_events_resyncs_current_states_after_overflow() {
+    let (_dir, service) = test_service_with_fake_runtime_configure(|config| {
+        config.container_events_queue_size = 2;
+        config.container_events_overflow = crate::config::ContainerEventsOverflow::Resync;
+    });
+    service.pod_sandboxes.lock().await.insert(
+        "pod-resync".to_string(),
+        test_pod("pod-resync", HashMap::new()),
+    );
+    let mut running = test_container("running-ctr", "pod-resync", HashMap::new());
+    running.state = ContainerState::ContainerRunning as i32;
+    let mut exited = test_container("exited-ctr", "pod-resync", HashMap::new());
+    exited.state = ContainerState::ContainerExited as i32;
+    {
+        let mut containers = service.containers.lock().await;
+        containers.insert(running.id.clone(), running);
+        containers.insert(exited.id.clone(), exited);
+    }
+    let mut stream =
+        RuntimeService::get_container_events(&service, Request::new(GetEventsRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+
+    for idx in 0..5 {
+        service.publish_event(ContainerEventResponse {
+            container_id: format!("stale-{}", idx),
+            container_event_type: ContainerEventType::ContainerCreatedEvent as i32,
+            created_at: idx,
+            pod_sandbox_status: None,
+            containers_statuses: Vec::new(),
+        });
+    }
+
+    let mut resynced = HashMap::new();
+    for _ in 0..2 {
+        let event = timeout(Duration::from_secs(5), stream.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            event.pod_sandbox_status.as_ref().map(|pod| pod.id.as_str()),
+            Some("pod-resync")
+        );
+        assert_eq!(event.containers_statuses.len(), 2);
+        resynced.insert(event.container_id, event.container_event_type);
+    }
+    assert_eq!(
+        resynced.get("running-ctr"),
+        Some(&(ContainerEventType::ContainerStartedEvent as i32))
+    );
+    assert_eq!(
+        resynced.get("exited-ctr"),
+        Some(&(ContainerEventType::ContainerStoppedEvent as i32))
+    );
+
+    let snapshot = service.metrics_provider().snapshot().await;
+    assert_eq!(snapshot.event_subscribers.len(), 1);
+    assert_eq!(snapshot.event_subscribers[0].resyncs, 1);
+    assert_eq!(snapshot.event_dropped_total, 5);
+}
+
 #[tokio::test]
 async fn exit_monitor_publishes_async_stop_events() {
     let fake_nri = Arc::new(FakeNri::default());
@@ -1127,6 +1127,73 @@ async fn reload_cni_watch_once_records_last_error() {
             && event.details["reason"] == "CNIConfigInvalid"));
 }
 
+#[tokio::test]
+async fn reload_cni_watch_once_keeps_last_known_good_for_broken_config_set() {
+    let mut service = test_service();
+    let dir = tempdir().unwrap();
+    let cni_dir = dir.path().join("cni");
+    let plugin_dir = dir.path().join("bin");
+    fs::create_dir_all(&cni_dir).unwrap();
+    fs::create_dir_all(&plugin_dir).unwrap();
+    let plugin = plugin_dir.join("bridge");
+    fs::write(&plugin, "#!/bin/sh\nexit 0\n").unwrap();
+    fs::set_permissions(&plugin, fs::Permissions::from_mode(0o755)).unwrap();
+    service
+        .config
+        .cni_config
+        .set_plugin_dirs(vec![plugin_dir.clone()]);
+    fs::write(
+        cni_dir.join("10-good.conflist"),
+        r#"{"cniVersion":"1.0.0","name":"good","plugins":[{"type":"bridge"}]}"#,
+    )
+    .unwrap();
+
+    let mut next = service.current_reloadable_config();
+    next.cni_config_dirs = vec![cni_dir.clone()];
+    service.apply_reloadable_config(next, "test").await.unwrap();
+    let status = service.reload_cni_watch_once().await;
+    assert!(status.ready);
+    let state = service.current_reload_state();
+    assert_eq!(state.cni_last_known_good.as_ref().unwrap().generation, 1);
+
+    fs::write(cni_dir.join("05-broken.conflist"), "{not-json").unwrap();
+    let status = service.reload_cni_watch_once().await;
+
+    assert!(status.ready);
+    assert_eq!(status.loaded_networks, vec!["good".to_string()]);
+    assert!(service.probe_cni_load_status().await.ready);
+    let state = service.current_reload_state();
+    assert!(state.last_cni_watch_error.is_none());
+    assert!(state
+        .last_cni_rejected_error
+        .as_deref()
+        .unwrap()
+        .contains("05-broken.conflist"));
+    let last_known_good = state.cni_last_known_good.unwrap();
+    assert_eq!(last_known_good.generation, 1);
+    assert_eq!(last_known_good.loaded_networks, vec!["good".to_string()]);
+
+    let response = RuntimeService::status(&service, Request::new(StatusRequest { verbose: true }))
+        .await
+        .unwrap()
+        .into_inner();
+    let info: serde_json::Value =
+        serde_json::from_str(response.info.get("config").unwrap()).unwrap();
+    assert_eq!(info["reload"]["cniLastKnownGood"]["generation"], 1);
+    assert!(info["reload"]["lastCniRejectedError"]
+        .as_str()
+        .unwrap()
+        .contains("05-broken.conflist"));
+    assert_eq!(info["networkDiagnostics"]["ready"], true);
+
+    fs::remove_file(cni_dir.join("05-broken.conflist")).unwrap();
+    let status = service.reload_cni_watch_once().await;
+    assert!(status.ready);
+    let state = service.current_reload_state();
+    assert!(state.last_cni_rejected_error.is_none());
+    assert_eq!(state.cni_last_known_good.unwrap().generation, 1);
+}
+
 #[tokio::test]
 async fn status_verbose_correlates_network_reason_reload_and_recent_events() {
     let service = test_service();
@@ -1489,7 +1556,16 @@ impl crate::shim_rpc::server::ShimRpcHandler for TestShimExecSyncHandler {
                 Ok(crate::shim_rpc::ShimRpcResponse::ExecProcess(
                     crate::shim_rpc::ExecProcessResponse {
                         exit_code: 0,
-                        stdout: format!("shim:{}", request.command.join(" ")).into_bytes(),
+                        stdout: match request.process {
+                            Some(process) => format!(
+                                "shim:{} cwd={} uid={}",
+                                process.args.join(" "),
+                                process.cwd,
+                                process.user.map(|user| user.uid).unwrap_or_default()
+                            ),
+                            None => format!("shim:{}", request.command.join(" ")),
+                        }
+                        .into_bytes(),
                         stderr: b"shim-stderr".to_vec(),
                     },
                 ))
@@ -1583,6 +1659,7 @@ impl crate::runtime::TaskController for FakeBackend {
         _container_id: &str,
         _command: &[String],
         _tty: bool,
+        _overrides: &crate::security::spec_patch::ExecOverrides,
     ) -> anyhow::Result<i32> {
         Ok(0)
     }
@@ -1811,12 +1888,14 @@ fn runtime_service_can_use_injected_fake_backend_for_handler() {
             monitor_cgroup: String::new(),
             monitor_env: Vec::new(),
             stream_websockets: false,
+            stream_spdy: true,
             allowed_annotations: Vec::new(),
             default_annotations: HashMap::new(),
             privileged_without_host_devices: false,
             privileged_without_host_devices_all_devices_allowed: false,
             container_create_timeout: 77,
             snapshotter: "internal-overlay-untar".to_string(),
+            log_drivers: Vec::new(),
         },
     )]);
     let service = RuntimeServiceImpl::new_with_runtime_backends(
@@ -1914,12 +1993,14 @@ exit 0
             monitor_cgroup: String::new(),
             monitor_env: Vec::new(),
             stream_websockets: false,
+            stream_spdy: true,
             allowed_annotations: Vec::new(),
             default_annotations: HashMap::new(),
             privileged_without_host_devices: false,
             privileged_without_host_devices_all_devices_allowed: false,
             container_create_timeout: 240,
             snapshotter: "internal-overlay-untar".to_string(),
+            log_drivers: Vec::new(),
         },
     );
     let service = RuntimeServiceImpl::new(config);
@@ -1970,12 +2051,14 @@ exit 0
             monitor_cgroup: String::new(),
             monitor_env: Vec::new(),
             stream_websockets: false,
+            stream_spdy: true,
             allowed_annotations: Vec::new(),
             default_annotations: HashMap::new(),
             privileged_without_host_devices: false,
             privileged_without_host_devices_all_devices_allowed: false,
             container_create_timeout: 240,
             snapshotter: "internal-overlay-untar".to_string(),
+            log_drivers: Vec::new(),
         },
     );
     let service = RuntimeServiceImpl::new(config);
@@ -2030,12 +2113,14 @@ async fn status_reports_runtime_not_ready_when_binary_is_not_executable() {
             monitor_cgroup: String::new(),
             monitor_env: Vec::new(),
             stream_websockets: false,
+            stream_spdy: true,
             allowed_annotations: Vec::new(),
             default_annotations: HashMap::new(),
             privileged_without_host_devices: false,
             privileged_without_host_devices_all_devices_allowed: false,
             container_create_timeout: 240,
             snapshotter: "internal-overlay-untar".to_string(),
+            log_drivers: Vec::new(),
         },
     );
     let service = RuntimeServiceImpl::new(config);
@@ -2202,6 +2287,52 @@ async fn update_runtime_config_renders_cni_config_template_from_pod_cidrs() {
     );
 }
 
+#[tokio::test]
+async fn update_runtime_config_renders_per_family_pod_cidrs_for_ipv6_only_cluster() {
+    let root_dir = tempdir().unwrap().keep();
+    let config_dir = root_dir.join("net.d");
+    let template_path = root_dir.join("template.conflist");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        &template_path,
+        r#"{"ranges": [{{if .PodCIDRv4}}[{"subnet": "{{.PodCIDRv4}}"}]{{end}}{{if .PodCIDRv6}}[{"subnet": "{{.PodCIDRv6}}"}]{{end}}], "routes": [{{range $i, $route := .Routes}}{{if $i}}, {{end}}{"dst": "{{$route}}"}{{end}}]}"#,
+    )
+    .unwrap();
+
+    let mut config = test_runtime_config(root_dir.clone());
+    let mut cni_config = crate::network::CniConfig::new(
+        vec![config_dir.clone()],
+        Vec::new(),
+        root_dir.join("cache"),
+        0,
+        crate::network::MainIpPreference::Ipv6,
+        None,
+        false,
+    );
+    cni_config.set_conf_template(Some(template_path));
+    config.cni_config = cni_config;
+    let service = RuntimeServiceImpl::new(config);
+
+    RuntimeService::update_runtime_config(
+        &service,
+        Request::new(UpdateRuntimeConfigRequest {
+            runtime_config: Some(crate::proto::runtime::v1::RuntimeConfig {
+                network_config: Some(crate::proto::runtime::v1::NetworkConfig {
+                    pod_cidr: "fd00:10:244::/64".to_string(),
+                }),
+            }),
+        }),
+    )
+    .await
+    .unwrap();
+
+    let rendered = fs::read_to_string(config_dir.join("10-crius-net.conflist")).unwrap();
+    assert_eq!(
+        rendered,
+        r#"{"ranges": [[{"subnet": "fd00:10:244::/64"}]], "routes": [{"dst": "::/0"}]}"#
+    );
+}
+
 #[tokio::test]
 async fn update_runtime_config_rejects_invalid_pod_cidr_for_cni_template() {
     let root_dir = tempdir().unwrap().keep();
@@ -9,3 +9,309 @@ async fn metrics_provider_snapshot_counts_event_subscribers() {
 
     assert_eq!(snapshot.event_subscriber_count, 1);
 }
+
+#[tokio::test]
+async fn metrics_provider_snapshot_reports_netns_pool_occupancy() {
+    let service = lifecycle::test_service();
+    let dir = tempfile::tempdir().unwrap();
+    let pool = service.netns_pool();
+    pool.configure(
+        crate::network::NamespaceManager::with_helper(
+            dir.path().join("netns"),
+            Some(dir.path().join("missing-pinns")),
+        ),
+        2,
+    );
+    pool.replenish().await;
+
+    let snapshot = service.metrics_provider().snapshot().await;
+
+    assert_eq!(snapshot.netns_pool.capacity, 2);
+    assert_eq!(snapshot.netns_pool.available, 0);
+    assert!(snapshot.netns_pool.create_failures >= 1);
+}
+
+#[tokio::test]
+async fn metrics_provider_container_resources_carry_pod_labels_and_skip_exited() {
+    let service = lifecycle::test_service();
+    service.pod_sandboxes.lock().await.insert(
+        "pod-metrics".to_string(),
+        crate::proto::runtime::v1::PodSandbox {
+            id: "pod-metrics".to_string(),
+            metadata: Some(PodSandboxMetadata {
+                name: "web".to_string(),
+                namespace: "default".to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+    );
+    for (id, state) in [
+        ("container-running", ContainerState::ContainerRunning),
+        ("container-exited", ContainerState::ContainerExited),
+    ] {
+        service.containers.lock().await.insert(
+            id.to_string(),
+            Container {
+                id: id.to_string(),
+                pod_sandbox_id: "pod-metrics".to_string(),
+                state: state as i32,
+                metadata: Some(ContainerMetadata {
+                    name: "nginx".to_string(),
+                    attempt: 0,
+                }),
+                image: Some(ImageSpec {
+                    image: "nginx:latest".to_string(),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        );
+    }
+
+    let resources = service.metrics_provider().container_resource_stats().await;
+
+    assert_eq!(resources.len(), 1);
+    assert_eq!(resources[0].container_id, "container-running");
+    assert_eq!(resources[0].container_name, "nginx");
+    assert_eq!(resources[0].image, "nginx:latest");
+    assert_eq!(resources[0].pod_name, "web");
+    assert_eq!(resources[0].pod_namespace, "default");
+}
+
+#[tokio::test]
+async fn metrics_provider_container_resources_reuse_fresh_stats_cache() {
+    let mut service = lifecycle::test_service();
+    service.config.stats_collection_period = 60;
+    let container = Container {
+        id: "container-cached".to_string(),
+        state: ContainerState::ContainerRunning as i32,
+        ..Default::default()
+    };
+    service
+        .containers
+        .lock()
+        .await
+        .insert(container.id.clone(), container.clone());
+    service.container_stats_cache.lock().await.insert(
+        container.id.clone(),
+        crate::server::service::CachedStatsEntry {
+            collected_at: std::time::Instant::now(),
+            value: crate::server::service::CachedContainerStats {
+                raw: crate::metrics::ContainerStats {
+                    container_id: container.id.clone(),
+                    pids: Some(crate::metrics::PidsStats {
+                        current: 7,
+                        limit: 0,
+                    }),
+                    ..Default::default()
+                },
+                proto: crate::proto::runtime::v1::ContainerStats {
+                    writable_layer: Some(crate::proto::runtime::v1::FilesystemUsage {
+                        used_bytes: Some(crate::proto::runtime::v1::UInt64Value { value: 4096 }),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+            },
+        },
+    );
+
+    let resources = service.metrics_provider().container_resource_stats().await;
+
+    assert_eq!(resources.len(), 1);
+    assert_eq!(
+        resources[0].stats.pids.as_ref().map(|pids| pids.current),
+        Some(7)
+    );
+    assert_eq!(resources[0].fs_usage_bytes, 4096);
+}
+
+#[test]
+fn oom_monitor_reports_only_oom_kill_increases_after_baseline() {
+    let events = |oom_kill| crate::metrics::MemoryEvents {
+        oom: oom_kill + 1,
+        oom_kill,
+        ..Default::default()
+    };
+    let current = HashMap::from([
+        ("steady".to_string(), events(1)),
+        ("killed".to_string(), events(3)),
+        ("fresh".to_string(), events(1)),
+        ("clean".to_string(), events(0)),
+    ]);
+
+    assert!(oom_monitor::oom_kill_increases(None, &current).is_empty());
+
+    let previous = HashMap::from([("steady".to_string(), 1), ("killed".to_string(), 2)]);
+    let increases = oom_monitor::oom_kill_increases(Some(&previous), &current);
+    let ids = increases
+        .iter()
+        .map(|(id, _)| id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec!["fresh", "killed"]);
+    assert_eq!(increases[1].1.oom_kill, 3);
+}
+
+#[tokio::test]
+async fn oom_monitor_polls_memory_events_and_exit_path_reports_unseen_oom_kills() {
+    let service = lifecycle::test_service();
+    let cgroup_parent = tempfile::tempdir().unwrap();
+    let write_oom_kill = |container_id: &str, oom_kill: u64| {
+        let dir = cgroup_parent.path().join(container_id);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("memory.events"),
+            format!("low 0\nhigh 0\nmax 0\noom {oom_kill}\noom_kill {oom_kill}\n"),
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("memory.oom_control"),
+            format!("oom_kill_disable 0\nunder_oom 0\noom_kill {oom_kill}\n"),
+        )
+        .unwrap();
+    };
+    for id in ["container-polled", "container-main-oom"] {
+        write_oom_kill(id, 0);
+        let mut annotations = HashMap::new();
+        RuntimeServiceImpl::insert_internal_state(
+            &mut annotations,
+            INTERNAL_CONTAINER_STATE_KEY,
+            &StoredContainerState {
+                cgroup_parent: Some(cgroup_parent.path().display().to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        service.containers.lock().await.insert(
+            id.to_string(),
+            Container {
+                id: id.to_string(),
+                state: ContainerState::ContainerRunning as i32,
+                annotations,
+                ..Default::default()
+            },
+        );
+    }
+
+    // 第一轮只建立基线；之后计数增长才上报。
+    assert_eq!(service.observe_container_oom_events_once().await, 0);
+    write_oom_kill("container-polled", 1);
+    assert_eq!(service.observe_container_oom_events_once().await, 1);
+    assert_eq!(service.observe_container_oom_events_once().await, 0);
+
+    // 轮询已上报的容器即使之后多轮没有新增，退出时也不重复上报。
+    let events = &service.internal_services.events;
+    assert!(
+        !RuntimeServiceImpl::publish_container_exit_oom_event(
+            "container-polled",
+            137,
+            Some(1),
+            &service.oom_observations,
+            events,
+        )
+        .await
+    );
+    // 主进程 OOM 的容器 cgroup 已被删除，由退出路径凭 shim 记录的计数上报。
+    std::fs::remove_dir_all(cgroup_parent.path().join("container-main-oom")).unwrap();
+    assert!(
+        RuntimeServiceImpl::publish_container_exit_oom_event(
+            "container-main-oom",
+            137,
+            Some(1),
+            &service.oom_observations,
+            events,
+        )
+        .await
+    );
+    assert!(
+        !RuntimeServiceImpl::publish_container_exit_oom_event(
+            "container-main-oom",
+            0,
+            Some(1),
+            &service.oom_observations,
+            events,
+        )
+        .await
+    );
+    // 没有 OOM kill 记录的 SIGKILL（停止超时、手动 kill）不算 OOM。
+    for oom_kill in [None, Some(0)] {
+        assert!(
+            !RuntimeServiceImpl::publish_container_exit_oom_event(
+                "container-killed",
+                137,
+                oom_kill,
+                &service.oom_observations,
+                events,
+            )
+            .await
+        );
+    }
+
+    let polled = events
+        .recent_internal_events("container", "container-polled", 10)
+        .await
+        .unwrap();
+    assert_eq!(
+        polled
+            .iter()
+            .filter(|event| event.kind == "container.oom")
+            .count(),
+        1
+    );
+    assert_eq!(polled[0].details["oomKill"], 1);
+    let main_oom = events
+        .recent_internal_events("container", "container-main-oom", 10)
+        .await
+        .unwrap();
+    assert_eq!(main_oom.len(), 1);
+    assert_eq!(main_oom[0].kind, "container.oom");
+    assert_eq!(main_oom[0].details["reason"], "OOMKilled");
+    assert_eq!(main_oom[0].details["exitCode"], 137);
+    assert_eq!(main_oom[0].details["oomKill"], 1);
+    assert!(events
+        .recent_internal_events("container", "container-killed", 10)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+async fn metrics_provider_health_conditions_cover_every_subsystem() {
+    let service = lifecycle::test_service();
+    service
+        .reload_state
+        .lock()
+        .expect("reload state lock poisoned")
+        .last_reload_error = Some("invalid runtime handler".to_string());
+
+    let conditions = service.metrics_provider().health_conditions().await;
+
+    for condition_type in [
+        "RuntimeReady",
+        "NetworkReady",
+        "ImageReady",
+        "SnapshotReady",
+        "ShimReady",
+        "SecurityReady",
+        "RecoveryReady",
+        "PullCgroupReady",
+        "NriReady",
+        "CniLoaded",
+        "ConfigReloadReady",
+    ] {
+        assert!(
+            conditions
+                .iter()
+                .any(|condition| condition.condition_type == condition_type),
+            "missing {condition_type} condition"
+        );
+    }
+    let reload = conditions
+        .iter()
+        .find(|condition| condition.condition_type == "ConfigReloadReady")
+        .expect("config reload condition should be reported");
+    assert!(!reload.ready);
+    assert_eq!(reload.reason, "ConfigReloadFailed");
+    assert_eq!(reload.message, "invalid runtime handler");
+}
@@ -0,0 +1,334 @@
+//! `ContainerLog` 诊断 RPC 的日志读取
+//!
+//! 语义对齐 `kubectl logs`：`previous` 读取同一 Pod 内同名容器上一次退出的 attempt（读完即结束），
+//! `since` / `tail_lines` / `limit_bytes` 依次过滤输出。follow 时持有打开的文件句柄，
+//! 日志被轮转（路径 inode 变化）后先读完旧文件再切到新文件；容器重启后切到同名容器的
+//! 下一次 attempt，容器被删除且没有后继时结束。CRI 日志中 `P` 标记的分段会与后续分段
+//! 合并为完整的一行再输出。
+
+use std::collections::HashMap;
+use std::os::unix::fs::MetadataExt;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use tokio::io::AsyncReadExt;
+use tonic::Status;
+
+use crate::proto::diagnostics::v1::{ContainerLogChunk, ContainerLogRequest};
+use crate::server::RuntimeServiceImpl;
+
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+type ChunkSender = tokio::sync::mpsc::Sender<Result<ContainerLogChunk, Status>>;
+
+/// 一行 CRI 日志的拆分结果：`<timestamp> <stream> <tag> <content>`。
+struct CriLogLine<'a> {
+    timestamp: &'a str,
+    stream: &'a str,
+    partial: bool,
+    content: &'a str,
+}
+
+fn split_cri_log_line(line: &str) -> Option<CriLogLine<'_>> {
+    let mut parts = line.splitn(4, ' ');
+    let timestamp = parts.next()?;
+    let stream = parts.next()?;
+    let tag = parts.next()?;
+    Some(CriLogLine {
+        timestamp,
+        stream,
+        partial: tag.split(':').any(|flag| flag == "P"),
+        content: parts.next().unwrap_or_default(),
+    })
+}
+
+struct PendingLine {
+    timestamp: String,
+    content: String,
+}
+
+/// 按流合并 `P` 分段；合并后的行使用第一段的时间戳。
+struct CriLogDecoder {
+    timestamps: bool,
+    pending: HashMap<String, PendingLine>,
+}
+
+impl CriLogDecoder {
+    fn new(timestamps: bool) -> Self {
+        Self {
+            timestamps,
+            pending: HashMap::new(),
+        }
+    }
+
+    fn decode(&mut self, line: &str) -> Option<ContainerLogChunk> {
+        let line = split_cri_log_line(line)?;
+        if line.partial {
+            self.pending
+                .entry(line.stream.to_string())
+                .or_insert_with(|| PendingLine {
+                    timestamp: line.timestamp.to_string(),
+                    content: String::new(),
+                })
+                .content
+                .push_str(line.content);
+            return None;
+        }
+        let (timestamp, content) = match self.pending.remove(line.stream) {
+            Some(mut pending) => {
+                pending.content.push_str(line.content);
+                (pending.timestamp, pending.content)
+            }
+            None => (line.timestamp.to_string(), line.content.to_string()),
+        };
+        Some(self.chunk(line.stream.to_string(), &timestamp, content, true))
+    }
+
+    /// 输出仍未收到结束分段的行，不追加换行。
+    fn flush(&mut self) -> Vec<ContainerLogChunk> {
+        let mut pending = self.pending.drain().collect::<Vec<_>>();
+        pending.sort_by(|(_, left), (_, right)| left.timestamp.cmp(&right.timestamp));
+        pending
+            .into_iter()
+            .map(|(stream, line)| self.chunk(stream, &line.timestamp, line.content, false))
+            .collect()
+    }
+
+    fn chunk(
+        &self,
+        stream: String,
+        timestamp: &str,
+        content: String,
+        newline: bool,
+    ) -> ContainerLogChunk {
+        let mut data = if self.timestamps {
+            format!("{timestamp} {content}")
+        } else {
+            content
+        };
+        if newline {
+            data.push('\n');
+        }
+        ContainerLogChunk {
+            data: data.into_bytes(),
+            stream,
+            timestamp_unix_nanos: parse_rfc3339_nanos(timestamp).unwrap_or_default(),
+        }
+    }
+}
+
+fn parse_rfc3339_nanos(value: &str) -> Option<i64> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .ok()
+        .and_then(|timestamp| timestamp.timestamp_nanos_opt())
+}
+
+/// 应用 `since` 与 `limit_bytes` 后发送日志块。
+struct LogOutput {
+    since_unix_nanos: i64,
+    remaining_bytes: Option<u64>,
+}
+
+impl LogOutput {
+    fn new(request: &ContainerLogRequest) -> Self {
+        Self {
+            since_unix_nanos: request.since_unix_nanos,
+            remaining_bytes: (request.limit_bytes > 0).then_some(request.limit_bytes as u64),
+        }
+    }
+
+    fn accepts(&self, chunk: &ContainerLogChunk) -> bool {
+        chunk.timestamp_unix_nanos >= self.since_unix_nanos
+    }
+
+    /// 返回 `false` 表示客户端已断开或已达到 `limit_bytes`，调用方应结束输出。
+    async fn send(&mut self, tx: &ChunkSender, mut chunk: ContainerLogChunk) -> bool {
+        if !self.accepts(&chunk) {
+            return true;
+        }
+        if let Some(remaining) = self.remaining_bytes.as_mut() {
+            if chunk.data.len() as u64 > *remaining {
+                chunk.data.truncate(*remaining as usize);
+            }
+            *remaining -= chunk.data.len() as u64;
+        }
+        if tx.send(Ok(chunk)).await.is_err() {
+            return false;
+        }
+        self.remaining_bytes != Some(0)
+    }
+
+    async fn send_all(&mut self, tx: &ChunkSender, chunks: Vec<ContainerLogChunk>) -> bool {
+        for chunk in chunks {
+            if !self.send(tx, chunk).await {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// 持有打开的日志文件，按完整行读取新追加的内容。
+struct LogFile {
+    path: PathBuf,
+    file: Option<tokio::fs::File>,
+    inode: u64,
+    partial: Vec<u8>,
+}
+
+impl LogFile {
+    fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            file: None,
+            inode: 0,
+            partial: Vec::new(),
+        }
+    }
+
+    async fn open(&mut self) -> std::io::Result<()> {
+        let file = tokio::fs::File::open(&self.path).await?;
+        self.inode = file.metadata().await?.ino();
+        self.file = Some(file);
+        self.partial.clear();
+        Ok(())
+    }
+
+    /// 读到当前文件末尾，返回其中的完整行；文件尚不存在时返回空。
+    async fn read_lines(&mut self) -> std::io::Result<Vec<String>> {
+        if self.file.is_none() {
+            match self.open().await {
+                Ok(()) => {}
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+                Err(err) => return Err(err),
+            }
+        }
+        let Some(file) = self.file.as_mut() else {
+            return Ok(Vec::new());
+        };
+        file.read_to_end(&mut self.partial).await?;
+        let Some(end) = self.partial.iter().rposition(|byte| *byte == b'\n') else {
+            return Ok(Vec::new());
+        };
+        let rest = self.partial.split_off(end + 1);
+        let complete = std::mem::replace(&mut self.partial, rest);
+        Ok(String::from_utf8_lossy(&complete)
+            .lines()
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// 路径已指向另一个文件（日志被轮转并由 shim reopen）。
+    fn rotated(&self) -> bool {
+        self.file.is_some()
+            && std::fs::metadata(&self.path)
+                .map(|metadata| metadata.ino() != self.inode)
+                .unwrap_or(false)
+    }
+
+    fn switch_to(&mut self, path: PathBuf) {
+        *self = Self::new(path);
+    }
+}
+
+pub(super) async fn stream_container_log(
+    runtime: RuntimeServiceImpl,
+    mut container_id: String,
+    log_path: PathBuf,
+    request: ContainerLogRequest,
+    tx: ChunkSender,
+) -> Result<(), Status> {
+    let mut decoder = CriLogDecoder::new(request.timestamps);
+    let mut output = LogOutput::new(&request);
+    let mut file = LogFile::new(log_path);
+    file.open()
+        .await
+        .map_err(|err| Status::not_found(format!("failed to read container log: {err}")))?;
+
+    let mut history = read_chunks(&mut file, &mut decoder)
+        .await?
+        .into_iter()
+        .filter(|chunk| output.accepts(chunk))
+        .collect::<Vec<_>>();
+    if request.tail_lines >= 0 {
+        let tail = request.tail_lines as usize;
+        if tail < history.len() {
+            history = history.split_off(history.len() - tail);
+        }
+    }
+    if !output.send_all(&tx, history).await {
+        return Ok(());
+    }
+    // `previous` 读的是已经退出的 attempt，文件读完即结束，不再跟随后续容器。
+    if !request.follow || request.previous {
+        output.send_all(&tx, decoder.flush()).await;
+        return Ok(());
+    }
+
+    loop {
+        tokio::time::sleep(FOLLOW_POLL_INTERVAL).await;
+        if !output
+            .send_all(&tx, read_chunks(&mut file, &mut decoder).await?)
+            .await
+        {
+            return Ok(());
+        }
+
+        if file.rotated() {
+            // 先读完旧文件在 reopen 前写入的内容；同一 attempt 的 `P` 分段跨轮转继续合并。
+            if !output
+                .send_all(&tx, drain_chunks(&mut file, &mut decoder).await?)
+                .await
+            {
+                return Ok(());
+            }
+            let path = file.path.clone();
+            file.switch_to(path);
+            continue;
+        }
+        if let Some(next_id) = runtime.next_container_attempt(&container_id).await {
+            if let Ok(path) = runtime.container_log_path(&next_id).await {
+                // 上一次 attempt 已退出：读完文件尾部并输出未结束的分段，不与新 attempt 的输出混合。
+                let mut chunks = drain_chunks(&mut file, &mut decoder).await?;
+                chunks.extend(decoder.flush());
+                if !output.send_all(&tx, chunks).await {
+                    return Ok(());
+                }
+                container_id = next_id;
+                file.switch_to(path);
+            }
+        } else if !runtime.container_exists(&container_id).await {
+            let mut chunks = drain_chunks(&mut file, &mut decoder).await?;
+            chunks.extend(decoder.flush());
+            output.send_all(&tx, chunks).await;
+            return Ok(());
+        }
+    }
+}
+
+async fn read_chunks(
+    file: &mut LogFile,
+    decoder: &mut CriLogDecoder,
+) -> Result<Vec<ContainerLogChunk>, Status> {
+    let lines = file
+        .read_lines()
+        .await
+        .map_err(|err| Status::internal(format!("failed to read container log: {err}")))?;
+    Ok(lines
+        .iter()
+        .filter_map(|line| decoder.decode(line))
+        .collect())
+}
+
+/// 读完当前文件剩余的内容，末尾缺少换行的残行也按一行解析。
+async fn drain_chunks(
+    file: &mut LogFile,
+    decoder: &mut CriLogDecoder,
+) -> Result<Vec<ContainerLogChunk>, Status> {
+    let mut chunks = read_chunks(file, decoder).await?;
+    let rest = std::mem::take(&mut file.partial);
+    if !rest.is_empty() {
+        chunks.extend(decoder.decode(&String::from_utf8_lossy(&rest)));
+    }
+    Ok(chunks)
+}
@@ -1,19 +1,28 @@
 use serde_json::Value;
-use std::{path::Path, time::Duration};
 use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Request, Response, Status};
 
+use super::container_log;
 use crate::image::{content_store::TransferState, ImageServiceImpl};
 use crate::proto::diagnostics::v1::{
-    diagnostics_service_server::DiagnosticsService, ContainerLogChunk, ContainerLogRequest,
-    ContentGcCandidate as ProtoContentGcCandidate, ContentGcRequest, ContentGcResponse,
-    EffectiveConfigRequest, EffectiveConfigResponse, ImageTransferInfo, ImageTransfersRequest,
-    ImageTransfersResponse, NriStatusRequest, NriStatusResponse, RecoveryAction,
-    RecoveryCheckRequest, RecoveryCheckResponse, RecoveryStatusRequest, RecoveryStatusResponse,
-    RuntimeHandlerInfo, RuntimeHandlersRequest, RuntimeHandlersResponse, SecurityStatusRequest,
-    SecurityStatusResponse, ServerInfoRequest, ServerInfoResponse, ShimInfo, ShimStatusRequest,
-    ShimStatusResponse,
+    diagnostics_service_server::DiagnosticsService, CniPluginUsage, ContainerLogChunk,
+    ContainerLogRequest, ContentGcCandidate as ProtoContentGcCandidate, ContentGcRequest,
+    ContentGcResponse, EffectiveConfigRequest, EffectiveConfigResponse, EventRecord, EventSinkInfo,
+    EventSinkStatusRequest, EventSinkStatusResponse, ImageTransferInfo, ImageTransfersRequest,
+    ImageTransfersResponse, KillStreamingSessionRequest, KillStreamingSessionResponse,
+    NriStatusRequest, NriStatusResponse, RecoveryAction, RecoveryCheckRequest,
+    RecoveryCheckResponse, RecoveryStatusRequest, RecoveryStatusResponse, RuntimeComponentUsage,
+    RuntimeFootprintRequest, RuntimeFootprintResponse, RuntimeHandlerInfo, RuntimeHandlersRequest,
+    RuntimeHandlersResponse, SecurityStatusRequest, SecurityStatusResponse, ServerInfoRequest,
+    ServerInfoResponse, ShimInfo, ShimStatusRequest, ShimStatusResponse, StreamingAuditSession,
+    StreamingAuditSessionsRequest, StreamingAuditSessionsResponse, StreamingAuditTranscriptChunk,
+    StreamingAuditTranscriptRequest, StreamingSession, StreamingSessionsRequest,
+    StreamingSessionsResponse, WatchEventsRequest,
 };
+use crate::services::{EventLogFilter, EventLogWatch, EventSinkHealth, InternalEventSeverity};
+use crate::storage::EventLogRecord;
+
+const STREAMING_TRANSCRIPT_CHUNK_BYTES: usize = 64 * 1024;
 
 #[derive(Clone, Default)]
 pub struct DiagnosticsState {
@@ -316,6 +325,8 @@ impl DiagnosticsService for DiagnosticsServiceImpl {
                 rootless_enabled: false,
                 devices_policy_json: "{}".to_string(),
                 warnings: vec!["runtime diagnostics state is not available".to_string()],
+                rootless_network_mode: String::new(),
+                rootless_port_forwards_json: "[]".to_string(),
             }));
         };
         let security = crate::security::SecurityManager::new();
@@ -329,6 +340,10 @@ impl DiagnosticsService for DiagnosticsServiceImpl {
             "apparmorDefaultProfile": snapshot.apparmor_default_profile,
         }))
         .map_err(|err| Status::internal(format!("failed to encode devices policy: {err}")))?;
+        let rootless_port_forwards_json =
+            serde_json::to_string(&runtime.rootless_port_forwards().await).map_err(|err| {
+                Status::internal(format!("failed to encode rootless port forwards: {err}"))
+            })?;
 
         Ok(Response::new(SecurityStatusResponse {
             seccomp_available: security.is_seccomp_available(),
@@ -338,6 +353,8 @@ impl DiagnosticsService for DiagnosticsServiceImpl {
             rootless_enabled: snapshot.rootless_enabled,
             devices_policy_json,
             warnings: Vec::new(),
+            rootless_network_mode: snapshot.rootless_network_mode,
+            rootless_port_forwards_json,
         }))
     }
 
@@ -420,108 +437,396 @@ impl DiagnosticsService for DiagnosticsServiceImpl {
                 "runtime diagnostics state is not available",
             ));
         };
-        let log_path = runtime.container_log_path(&request.container_id).await?;
+        let container_id = if request.previous {
+            runtime
+                .previous_container_attempt(&request.container_id)
+                .await
+                .ok_or_else(|| {
+                    Status::not_found(format!(
+                        "previous terminated container of {} not found",
+                        request.container_id
+                    ))
+                })?
+        } else {
+            request.container_id.clone()
+        };
+        let log_path = runtime.container_log_path(&container_id).await?;
+        let runtime = runtime.clone();
         let (tx, rx) = tokio::sync::mpsc::channel(16);
 
         tokio::spawn(async move {
-            if let Err(status) = stream_container_log(log_path, request, tx.clone()).await {
+            if let Err(status) = container_log::stream_container_log(
+                runtime,
+                container_id,
+                log_path,
+                request,
+                tx.clone(),
+            )
+            .await
+            {
                 let _ = tx.send(Err(status)).await;
             }
         });
 
         Ok(Response::new(ReceiverStream::new(rx)))
     }
-}
 
-async fn stream_container_log(
-    log_path: std::path::PathBuf,
-    request: ContainerLogRequest,
-    tx: tokio::sync::mpsc::Sender<Result<ContainerLogChunk, Status>>,
-) -> Result<(), Status> {
-    let mut offset = stream_existing_container_log(&log_path, &request, &tx).await?;
-    if !request.follow {
-        return Ok(());
-    }
-
-    loop {
-        tokio::time::sleep(Duration::from_millis(250)).await;
-        let bytes = match tokio::fs::read(&log_path).await {
-            Ok(bytes) => bytes,
-            Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
-            Err(err) => {
-                return Err(Status::internal(format!(
-                    "failed to read container log: {err}"
-                )));
+    async fn streaming_sessions(
+        &self,
+        request: Request<StreamingSessionsRequest>,
+    ) -> Result<Response<StreamingSessionsResponse>, Status> {
+        let container_id = request.into_inner().container_id;
+        let Some(runtime) = self.state.runtime_service.as_ref() else {
+            return Ok(Response::new(StreamingSessionsResponse {
+                warnings: vec!["runtime diagnostics state is not available".to_string()],
+                ..Default::default()
+            }));
+        };
+        let Some(registry) = runtime.streaming_sessions().await else {
+            return Ok(Response::new(StreamingSessionsResponse {
+                warnings: vec!["streaming server is not initialized".to_string()],
+                ..Default::default()
+            }));
+        };
+        let limits = registry.limits();
+        let sessions = registry
+            .list((!container_id.is_empty()).then_some(container_id.as_str()))
+            .into_iter()
+            .map(streaming_session_to_proto)
+            .collect();
+
+        Ok(Response::new(StreamingSessionsResponse {
+            sessions,
+            max_sessions: limits.max_sessions as u64,
+            max_sessions_per_container: limits.max_sessions_per_container as u64,
+            warnings: Vec::new(),
+        }))
+    }
+
+    async fn kill_streaming_session(
+        &self,
+        request: Request<KillStreamingSessionRequest>,
+    ) -> Result<Response<KillStreamingSessionResponse>, Status> {
+        let session_id = request.into_inner().session_id;
+        if session_id.trim().is_empty() {
+            return Err(Status::invalid_argument("session_id must not be empty"));
+        }
+        let Some(runtime) = self.state.runtime_service.as_ref() else {
+            return Err(Status::failed_precondition(
+                "runtime diagnostics state is not available",
+            ));
+        };
+        let registry = runtime
+            .streaming_sessions()
+            .await
+            .ok_or_else(|| Status::unavailable("streaming server is not initialized"))?;
+        let session = registry
+            .list(None)
+            .into_iter()
+            .find(|session| session.id == session_id);
+        match session {
+            Some(session) if registry.kill(&session.id) => {
+                Ok(Response::new(KillStreamingSessionResponse {
+                    session: Some(streaming_session_to_proto(session)),
+                }))
             }
+            _ => Err(Status::not_found(format!(
+                "streaming session {session_id} not found"
+            ))),
+        }
+    }
+
+    async fn streaming_audit_sessions(
+        &self,
+        request: Request<StreamingAuditSessionsRequest>,
+    ) -> Result<Response<StreamingAuditSessionsResponse>, Status> {
+        let container_id = request.into_inner().container_id;
+        let Some(runtime) = self.state.runtime_service.as_ref() else {
+            return Ok(Response::new(StreamingAuditSessionsResponse {
+                warnings: vec!["runtime diagnostics state is not available".to_string()],
+                ..Default::default()
+            }));
         };
-        if bytes.len() <= offset {
-            continue;
+        let Some(auditor) = runtime.streaming_auditor().await else {
+            return Ok(Response::new(StreamingAuditSessionsResponse {
+                warnings: vec!["streaming server is not initialized".to_string()],
+                ..Default::default()
+            }));
+        };
+        let records = runtime
+            .streaming_audit_records((!container_id.is_empty()).then_some(container_id.as_str()))
+            .await
+            .map_err(|err| {
+                Status::internal(format!("failed to read streaming audit records: {err}"))
+            })?;
+        let config = auditor.config();
+        let mut warnings = Vec::new();
+        if !config.enabled {
+            warnings.push(
+                "streaming session audit is disabled (api.streaming.audit.enabled = false)"
+                    .to_string(),
+            );
         }
-        let appended = &bytes[offset..];
-        offset = bytes.len();
-        for line in String::from_utf8_lossy(appended).lines() {
-            if let Some(chunk) = parse_container_log_line(line, request.timestamps) {
-                if chunk.timestamp_unix_nanos >= request.since_unix_nanos
-                    && tx.send(Ok(chunk)).await.is_err()
-                {
-                    return Ok(());
+
+        Ok(Response::new(StreamingAuditSessionsResponse {
+            sessions: records.into_iter().map(streaming_audit_to_proto).collect(),
+            audit_enabled: config.enabled,
+            record_transcripts: config.enabled && config.record_transcripts,
+            warnings,
+        }))
+    }
+
+    type StreamingAuditTranscriptStream =
+        ReceiverStream<Result<StreamingAuditTranscriptChunk, Status>>;
+
+    async fn streaming_audit_transcript(
+        &self,
+        request: Request<StreamingAuditTranscriptRequest>,
+    ) -> Result<Response<Self::StreamingAuditTranscriptStream>, Status> {
+        let session_id = request.into_inner().session_id;
+        if session_id.trim().is_empty() {
+            return Err(Status::invalid_argument("session_id must not be empty"));
+        }
+        let Some(runtime) = self.state.runtime_service.as_ref() else {
+            return Err(Status::failed_precondition(
+                "runtime diagnostics state is not available",
+            ));
+        };
+        let auditor = runtime
+            .streaming_auditor()
+            .await
+            .ok_or_else(|| Status::unavailable("streaming server is not initialized"))?;
+        let path = auditor
+            .transcript_path(&session_id)
+            .ok_or_else(|| Status::invalid_argument("session_id must be alphanumeric"))?;
+        let mut file = tokio::fs::File::open(&path)
+            .await
+            .map_err(|err| match err.kind() {
+                std::io::ErrorKind::NotFound => Status::not_found(format!(
+                    "no transcript recorded for streaming session {session_id}"
+                )),
+                _ => Status::internal(format!(
+                    "failed to open transcript for streaming session {session_id}: {err}"
+                )),
+            })?;
+        let (tx, rx) = tokio::sync::mpsc::channel(16);
+
+        tokio::spawn(async move {
+            use tokio::io::AsyncReadExt;
+
+            let mut buffer = vec![0u8; STREAMING_TRANSCRIPT_CHUNK_BYTES];
+            loop {
+                let chunk = match file.read(&mut buffer).await {
+                    Ok(0) => return,
+                    Ok(read) => Ok(StreamingAuditTranscriptChunk {
+                        data: buffer[..read].to_vec(),
+                    }),
+                    Err(err) => Err(Status::internal(format!(
+                        "failed to read streaming transcript: {err}"
+                    ))),
+                };
+                let failed = chunk.is_err();
+                if tx.send(chunk).await.is_err() || failed {
+                    return;
                 }
             }
+        });
+
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    type WatchEventsStream = ReceiverStream<Result<EventRecord, Status>>;
+
+    async fn watch_events(
+        &self,
+        request: Request<WatchEventsRequest>,
+    ) -> Result<Response<Self::WatchEventsStream>, Status> {
+        let request = request.into_inner();
+        if request.since_seq < 0 {
+            return Err(Status::invalid_argument("since_seq must not be negative"));
         }
+        let min_severity = match request.min_severity.trim() {
+            "" => None,
+            value => Some(
+                InternalEventSeverity::parse(&value.to_ascii_lowercase())
+                    .map_err(|err| Status::invalid_argument(err.to_string()))?,
+            ),
+        };
+        let Some(runtime) = self.state.runtime_service.as_ref() else {
+            return Err(Status::failed_precondition(
+                "runtime diagnostics state is not available",
+            ));
+        };
+        let stream = runtime
+            .event_service()
+            .watch_event_log(EventLogWatch {
+                since_seq: request.since_seq,
+                from_latest: request.from_latest,
+                follow: request.follow,
+                filter: EventLogFilter {
+                    kinds: request
+                        .kinds
+                        .into_iter()
+                        .map(|kind| kind.trim().to_string())
+                        .filter(|kind| !kind.is_empty())
+                        .collect(),
+                    subject_kind: request.subject_kind,
+                    subject_id: request.subject_id,
+                    pod_id: request.pod_id,
+                    namespace: request.namespace,
+                    min_severity,
+                },
+            })
+            .await?;
+        Ok(Response::new(stream))
+    }
+
+    async fn event_sink_status(
+        &self,
+        _request: Request<EventSinkStatusRequest>,
+    ) -> Result<Response<EventSinkStatusResponse>, Status> {
+        let Some(runtime) = self.state.runtime_service.as_ref() else {
+            return Ok(Response::new(EventSinkStatusResponse {
+                sinks: Vec::new(),
+                warnings: vec!["runtime diagnostics state is not available".to_string()],
+            }));
+        };
+        Ok(Response::new(EventSinkStatusResponse {
+            sinks: runtime
+                .event_service()
+                .event_sink_health()
+                .into_iter()
+                .map(EventSinkInfo::from)
+                .collect(),
+            warnings: Vec::new(),
+        }))
+    }
+
+    async fn runtime_footprint(
+        &self,
+        _request: Request<RuntimeFootprintRequest>,
+    ) -> Result<Response<RuntimeFootprintResponse>, Status> {
+        let Some(runtime) = self.state.runtime_service.as_ref() else {
+            return Ok(Response::new(RuntimeFootprintResponse {
+                warnings: vec!["runtime diagnostics state is not available".to_string()],
+                ..Default::default()
+            }));
+        };
+        Ok(Response::new(RuntimeFootprintResponse::from(
+            runtime.runtime_footprint().await,
+        )))
     }
 }
 
-async fn stream_existing_container_log(
-    log_path: &Path,
-    request: &ContainerLogRequest,
-    tx: &tokio::sync::mpsc::Sender<Result<ContainerLogChunk, Status>>,
-) -> Result<usize, Status> {
-    let bytes = tokio::fs::read(log_path)
-        .await
-        .map_err(|err| Status::not_found(format!("failed to read container log: {err}")))?;
-    let mut chunks = String::from_utf8_lossy(&bytes)
-        .lines()
-        .filter_map(|line| parse_container_log_line(line, request.timestamps))
-        .filter(|chunk| chunk.timestamp_unix_nanos >= request.since_unix_nanos)
-        .collect::<Vec<_>>();
-    if request.tail_lines >= 0 {
-        let tail = request.tail_lines as usize;
-        if tail < chunks.len() {
-            chunks = chunks.split_off(chunks.len() - tail);
+impl From<crate::metrics::footprint::RuntimeFootprint> for RuntimeFootprintResponse {
+    fn from(footprint: crate::metrics::footprint::RuntimeFootprint) -> Self {
+        Self {
+            components: footprint
+                .components
+                .into_iter()
+                .map(|usage| RuntimeComponentUsage {
+                    component: usage.component,
+                    processes: usage.processes,
+                    cpu_seconds: usage.cpu_seconds,
+                    memory_bytes: usage.memory_bytes,
+                    open_fds: usage.open_fds,
+                    threads: usage.threads,
+                })
+                .collect(),
+            cni_plugins: footprint
+                .cni_plugins
+                .into_iter()
+                .map(|plugin| CniPluginUsage {
+                    plugin: plugin.plugin,
+                    invocations: plugin.invocations,
+                    failures: plugin.failures,
+                    duration_seconds: plugin.duration_seconds,
+                    in_flight: plugin.in_flight,
+                })
+                .collect(),
+            warnings: footprint
+                .warnings
+                .iter()
+                .map(|warning| redact_host_paths(warning))
+                .collect(),
+            collected_at_unix_nanos: footprint.collected_at_unix_nanos,
         }
     }
+}
 
-    for chunk in chunks {
-        if tx.send(Ok(chunk)).await.is_err() {
-            break;
+impl From<EventSinkHealth> for EventSinkInfo {
+    fn from(health: EventSinkHealth) -> Self {
+        Self {
+            name: health.name,
+            sink_type: health.sink_type.to_string(),
+            target: health.target,
+            healthy: health.healthy,
+            queued: health.queued,
+            delivered: health.delivered,
+            failed: health.failed,
+            dropped: health.dropped,
+            last_error: health.last_error.unwrap_or_default(),
+            last_delivered_unix_nanos: health.last_delivered_at.unwrap_or_default(),
+            last_failed_unix_nanos: health.last_failed_at.unwrap_or_default(),
         }
     }
+}
 
-    Ok(bytes.len())
+impl From<EventLogRecord> for EventRecord {
+    fn from(record: EventLogRecord) -> Self {
+        Self {
+            seq: record.seq,
+            timestamp_unix_nanos: record.timestamp_nanos,
+            source: record.source,
+            kind: record.kind,
+            subject_kind: record.subject_kind,
+            subject_id: record.subject_id,
+            pod_id: record.pod_id,
+            namespace: record.namespace,
+            severity: record.severity,
+            details_json: record.details.unwrap_or_default(),
+        }
+    }
 }
 
-fn parse_container_log_line(line: &str, timestamps: bool) -> Option<ContainerLogChunk> {
-    let mut parts = line.splitn(4, ' ');
-    let timestamp = parts.next()?;
-    let stream = parts.next()?.to_string();
-    let _tag = parts.next()?;
-    let payload = parts.next().unwrap_or_default();
-    let data = if timestamps {
-        format!("{timestamp} {payload}\n").into_bytes()
-    } else {
-        format!("{payload}\n").into_bytes()
-    };
-    Some(ContainerLogChunk {
-        data,
-        stream,
-        timestamp_unix_nanos: parse_rfc3339_nanos(timestamp).unwrap_or_default(),
-    })
+fn streaming_session_to_proto(session: crate::streaming::StreamingSessionInfo) -> StreamingSession {
+    StreamingSession {
+        id: session.id,
+        container_id: session.container_id,
+        kind: session.kind,
+        protocol: session.protocol,
+        client_address: session.client_address.unwrap_or_default(),
+        started_at_unix_millis: session.started_at_unix_millis,
+        bytes_received: session.bytes_received,
+        bytes_sent: session.bytes_sent,
+    }
 }
 
-fn parse_rfc3339_nanos(value: &str) -> Option<i64> {
-    chrono::DateTime::parse_from_rfc3339(value)
-        .ok()
-        .and_then(|timestamp| timestamp.timestamp_nanos_opt())
+fn streaming_audit_to_proto(
+    record: crate::streaming::StreamingAuditRecord,
+) -> StreamingAuditSession {
+    StreamingAuditSession {
+        session_id: record.session_id,
+        token: record.token,
+        kind: record.kind,
+        container_id: record.container_id,
+        pod_sandbox_id: record.pod_sandbox_id,
+        pod_namespace: record.pod_namespace,
+        pod_name: record.pod_name,
+        runtime_handler: record.runtime_handler,
+        client_address: record.client_address.unwrap_or_default(),
+        protocol: record.protocol,
+        command: record.command,
+        tty: record.tty,
+        started_at_unix_millis: record.started_at_unix_millis,
+        ended_at_unix_millis: record.ended_at_unix_millis.unwrap_or_default(),
+        exit_code_known: record.exit_code.is_some(),
+        exit_code: record.exit_code.unwrap_or_default(),
+        bytes_received: record.bytes_received,
+        bytes_sent: record.bytes_sent,
+        transcript_path: record.transcript_path.unwrap_or_default(),
+        transcript_truncated: record.transcript_truncated,
+    }
 }
 
 fn redact_sensitive_config(value: &mut Value, path: &str, redacted_fields: &mut Vec<String>) {
@@ -1062,6 +1367,7 @@ mod tests {
         );
         assert!(!response.devices_policy_json.contains("/dev/fuse"));
         assert!(response.warnings.is_empty());
+        assert_eq!(response.rootless_port_forwards_json, "[]");
     }
 
     #[tokio::test]
@@ -1135,6 +1441,202 @@ mod tests {
         assert!(shim.error.contains("task socket is missing"));
     }
 
+    #[tokio::test]
+    async fn streaming_sessions_lists_filters_and_kills_sessions() {
+        let tempdir = tempfile::tempdir().expect("tempdir should be created");
+        let runtime_config = crate::server::RuntimeConfig {
+            root_dir: tempdir.path().join("state"),
+            ..Default::default()
+        };
+        let runtime = crate::server::RuntimeServiceImpl::new(runtime_config);
+        let streaming = crate::streaming::StreamingServer::for_test_with_config(
+            "http://127.0.0.1:10010",
+            crate::streaming::StreamingConfig {
+                max_sessions_per_container: 4,
+                ..Default::default()
+            },
+        );
+        let registry = streaming.sessions().clone();
+        runtime.set_streaming_server(streaming).await;
+        let exec = registry
+            .open(
+                "target",
+                crate::streaming::StreamingSessionKind::Exec,
+                "websocket/v5.channel.k8s.io",
+                Some("10.0.0.9:51000".parse().unwrap()),
+            )
+            .expect("session should be registered");
+        let _other = registry
+            .open(
+                "other",
+                crate::streaming::StreamingSessionKind::Attach,
+                "spdy/v4.channel.k8s.io",
+                None,
+            )
+            .expect("session should be registered");
+        let state = DiagnosticsState::from_runtime(
+            "0.1.0",
+            "abc123",
+            &crate::config::Config::default(),
+            &runtime,
+            "/run/crius/crius.sock",
+        );
+        let service = DiagnosticsServiceImpl::new(state);
+
+        let response = service
+            .streaming_sessions(Request::new(StreamingSessionsRequest {
+                container_id: "target".to_string(),
+            }))
+            .await
+            .expect("streaming sessions should succeed")
+            .into_inner();
+        assert_eq!(response.max_sessions_per_container, 4);
+        assert_eq!(response.sessions.len(), 1);
+        let session = &response.sessions[0];
+        assert_eq!(session.id, exec.id());
+        assert_eq!(session.kind, "exec");
+        assert_eq!(session.protocol, "websocket/v5.channel.k8s.io");
+        assert_eq!(session.client_address, "10.0.0.9:51000");
+
+        let killed = service
+            .kill_streaming_session(Request::new(KillStreamingSessionRequest {
+                session_id: exec.id().to_string(),
+            }))
+            .await
+            .expect("kill should succeed")
+            .into_inner();
+        assert_eq!(
+            killed.session.expect("killed session").container_id,
+            "target"
+        );
+        assert_eq!(registry.list(None).len(), 1);
+
+        let err = service
+            .kill_streaming_session(Request::new(KillStreamingSessionRequest {
+                session_id: exec.id().to_string(),
+            }))
+            .await
+            .expect_err("second kill should fail");
+        assert_eq!(err.code(), tonic::Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn streaming_audit_sessions_merge_ledger_records_and_stream_transcripts() {
+        use tokio_stream::StreamExt;
+
+        let tempdir = tempfile::tempdir().expect("tempdir should be created");
+        let runtime_config = crate::server::RuntimeConfig {
+            root_dir: tempdir.path().join("state"),
+            ..Default::default()
+        };
+        let runtime = crate::server::RuntimeServiceImpl::new(runtime_config);
+        let streaming = crate::streaming::StreamingServer::for_test_with_config(
+            "http://127.0.0.1:10010",
+            crate::streaming::StreamingConfig {
+                audit: crate::streaming::StreamingAuditConfig {
+                    enabled: true,
+                    record_transcripts: true,
+                    transcript_dir: tempdir.path().join("audit").display().to_string(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+        let registry = streaming.sessions().clone();
+        let auditor = streaming.auditor().clone();
+        runtime.set_streaming_server(streaming).await;
+
+        let session = registry
+            .open(
+                "target",
+                crate::streaming::StreamingSessionKind::Exec,
+                "websocket/v5.channel.k8s.io",
+                None,
+            )
+            .expect("session should be registered");
+        let session_id = session.id().to_string();
+        let (audited, transcript) = auditor.begin(
+            crate::streaming::StreamingAuditRecord {
+                session_id: session_id.clone(),
+                token: "token-1".to_string(),
+                kind: "exec".to_string(),
+                container_id: "target".to_string(),
+                protocol: "websocket/v5.channel.k8s.io".to_string(),
+                command: vec!["id".to_string()],
+                tty: true,
+                started_at_unix_millis: 1_700_000_000_000,
+                ..Default::default()
+            },
+            session.counters(),
+        );
+        let transcript = transcript.expect("transcript should be recorded");
+        transcript.output(&[0x82, 4, 1, b'o', b'k', b'\n']);
+        drop(transcript);
+        drop(audited);
+        drop(session);
+
+        let state = DiagnosticsState::from_runtime(
+            "0.1.0",
+            "abc123",
+            &crate::config::Config::default(),
+            &runtime,
+            "/run/crius/crius.sock",
+        );
+        let service = DiagnosticsServiceImpl::new(state);
+        let mut response = StreamingAuditSessionsResponse::default();
+        for _ in 0..100 {
+            response = service
+                .streaming_audit_sessions(Request::new(StreamingAuditSessionsRequest {
+                    container_id: "target".to_string(),
+                }))
+                .await
+                .expect("audit sessions should succeed")
+                .into_inner();
+            if response
+                .sessions
+                .first()
+                .is_some_and(|session| session.ended_at_unix_millis != 0)
+            {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert!(response.audit_enabled);
+        assert!(response.record_transcripts);
+        assert_eq!(response.sessions.len(), 1);
+        let recorded = &response.sessions[0];
+        assert_eq!(recorded.session_id, session_id);
+        assert_eq!(recorded.token, "token-1");
+        assert_eq!(recorded.command, vec!["id".to_string()]);
+        assert_ne!(recorded.ended_at_unix_millis, 0);
+        assert!(!recorded.exit_code_known);
+        assert!(recorded.transcript_path.ends_with(".cast"));
+        assert!(!recorded.transcript_truncated);
+
+        let mut stream = service
+            .streaming_audit_transcript(Request::new(StreamingAuditTranscriptRequest {
+                session_id: session_id.clone(),
+            }))
+            .await
+            .expect("transcript should stream")
+            .into_inner();
+        let mut contents = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            contents.extend(chunk.expect("transcript chunk").data);
+        }
+        let contents = String::from_utf8(contents).expect("transcript is utf-8");
+        assert!(contents.starts_with('{'));
+        assert!(contents.contains(r#""o","ok\n""#));
+
+        let err = service
+            .streaming_audit_transcript(Request::new(StreamingAuditTranscriptRequest {
+                session_id: "../etc/passwd".to_string(),
+            }))
+            .await
+            .expect_err("path-like session ids must be rejected");
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+    }
+
     #[tokio::test]
     async fn content_gc_returns_candidates_and_item_errors() {
         let tempdir = tempfile::tempdir().expect("tempdir should be created");
@@ -1229,6 +1731,250 @@ mod tests {
         assert!(stream.next().await.is_none());
     }
 
+    async fn collect_container_log(
+        service: &DiagnosticsServiceImpl,
+        request: ContainerLogRequest,
+    ) -> Vec<ContainerLogChunk> {
+        use tokio_stream::StreamExt;
+
+        service
+            .container_log(Request::new(request))
+            .await
+            .expect("container log should stream")
+            .into_inner()
+            .map(|chunk| chunk.expect("log chunk should be ok"))
+            .collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn container_log_merges_partial_lines_and_applies_since_tail_and_limit_bytes() {
+        let tempdir = tempfile::tempdir().expect("tempdir should be created");
+        let root_dir = tempdir.path().join("state");
+        let log_dir = root_dir.join("logs");
+        std::fs::create_dir_all(&log_dir).expect("log directory should be created");
+        let log_path = log_dir.join("container.log");
+        std::fs::write(
+            &log_path,
+            concat!(
+                "2026-06-02T01:00:00Z stdout F old\n",
+                "2026-06-02T01:00:01Z stdout P hel\n",
+                "2026-06-02T01:00:01Z stderr F warn\n",
+                "2026-06-02T01:00:02Z stdout P lo \n",
+                "2026-06-02T01:00:03Z stdout F world\n",
+                "2026-06-02T01:00:04Z stdout F last\n",
+                "2026-06-02T01:00:05Z stdout P unfinished\n",
+            ),
+        )
+        .expect("log should be written");
+        let runtime = runtime_with_container_log(&root_dir, "container-log", &log_path).await;
+        let service = DiagnosticsServiceImpl::new(DiagnosticsState::from_runtime(
+            "0.1.0",
+            "abc123",
+            &crate::config::Config::default(),
+            &runtime,
+            "/run/crius/crius.sock",
+        ));
+
+        let chunks = collect_container_log(
+            &service,
+            ContainerLogRequest {
+                container_id: "container-log".to_string(),
+                tail_lines: -1,
+                since_unix_nanos: rfc3339_nanos("2026-06-02T01:00:01Z"),
+                ..Default::default()
+            },
+        )
+        .await;
+        let data = chunks
+            .iter()
+            .map(|chunk| String::from_utf8_lossy(&chunk.data).into_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(data, ["warn\n", "hello world\n", "last\n", "unfinished"]);
+        assert_eq!(chunks[1].stream, "stdout");
+        assert_eq!(
+            chunks[1].timestamp_unix_nanos,
+            rfc3339_nanos("2026-06-02T01:00:01Z")
+        );
+
+        let chunks = collect_container_log(
+            &service,
+            ContainerLogRequest {
+                container_id: "container-log".to_string(),
+                tail_lines: 2,
+                limit_bytes: 8,
+                ..Default::default()
+            },
+        )
+        .await;
+        let data = chunks
+            .iter()
+            .flat_map(|chunk| chunk.data.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(data, b"hello wo");
+    }
+
+    #[tokio::test]
+    async fn container_log_previous_reads_last_exited_attempt() {
+        let tempdir = tempfile::tempdir().expect("tempdir should be created");
+        let root_dir = tempdir.path().join("state");
+        let log_dir = root_dir.join("logs");
+        std::fs::create_dir_all(&log_dir).expect("log directory should be created");
+        let first_log = log_dir.join("app-0.log");
+        let second_log = log_dir.join("app-1.log");
+        let current_log = log_dir.join("app-2.log");
+        std::fs::write(&first_log, "2026-06-02T01:00:00Z stdout F first\n")
+            .expect("first log should be written");
+        std::fs::write(&second_log, "2026-06-02T01:00:01Z stdout F second\n")
+            .expect("second log should be written");
+        std::fs::write(&current_log, "2026-06-02T01:00:02Z stdout F current\n")
+            .expect("current log should be written");
+        let runtime = crate::server::RuntimeServiceImpl::new(crate::server::RuntimeConfig {
+            root_dir: root_dir.clone(),
+            ..Default::default()
+        });
+        insert_container_log_attempt(
+            &runtime,
+            "app-0",
+            "app-0-name",
+            0,
+            ContainerState::ContainerExited,
+            &first_log,
+        )
+        .await;
+        insert_container_log_attempt(
+            &runtime,
+            "app-1",
+            "app-0-name",
+            1,
+            ContainerState::ContainerExited,
+            &second_log,
+        )
+        .await;
+        insert_container_log_attempt(
+            &runtime,
+            "app-2",
+            "app-0-name",
+            2,
+            ContainerState::ContainerRunning,
+            &current_log,
+        )
+        .await;
+        let service = DiagnosticsServiceImpl::new(DiagnosticsState::from_runtime(
+            "0.1.0",
+            "abc123",
+            &crate::config::Config::default(),
+            &runtime,
+            "/run/crius/crius.sock",
+        ));
+
+        let chunks = collect_container_log(
+            &service,
+            ContainerLogRequest {
+                container_id: "app-2".to_string(),
+                tail_lines: -1,
+                previous: true,
+                ..Default::default()
+            },
+        )
+        .await;
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].data, b"second\n");
+
+        let error = service
+            .container_log(Request::new(ContainerLogRequest {
+                container_id: "app-0".to_string(),
+                previous: true,
+                ..Default::default()
+            }))
+            .await
+            .expect_err("first attempt has no previous container");
+        assert_eq!(error.code(), tonic::Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn container_log_follow_continues_across_rotation_and_restart() {
+        let tempdir = tempfile::tempdir().expect("tempdir should be created");
+        let root_dir = tempdir.path().join("state");
+        let log_dir = root_dir.join("logs");
+        std::fs::create_dir_all(&log_dir).expect("log directory should be created");
+        let log_path = log_dir.join("app-0.log");
+        std::fs::write(&log_path, "2026-06-02T01:00:00Z stdout F one\n")
+            .expect("log should be written");
+        let runtime = runtime_with_container_log(&root_dir, "app-0", &log_path).await;
+        let service = DiagnosticsServiceImpl::new(DiagnosticsState::from_runtime(
+            "0.1.0",
+            "abc123",
+            &crate::config::Config::default(),
+            &runtime,
+            "/run/crius/crius.sock",
+        ));
+        let mut stream = service
+            .container_log(Request::new(ContainerLogRequest {
+                container_id: "app-0".to_string(),
+                tail_lines: -1,
+                follow: true,
+                ..Default::default()
+            }))
+            .await
+            .expect("container log should stream")
+            .into_inner();
+        assert_eq!(next_log_data(&mut stream).await, b"one\n");
+
+        // 模拟轮转：旧文件改名后仍有尾部写入，随后 shim reopen 出新文件。
+        let rotated = log_dir.join("app-0.log.1");
+        std::fs::rename(&log_path, &rotated).expect("log should be rotated");
+        append_log(&rotated, "2026-06-02T01:00:01Z stdout F two\n");
+        append_log(&log_path, "2026-06-02T01:00:02Z stdout F three\n");
+        assert_eq!(next_log_data(&mut stream).await, b"two\n");
+        assert_eq!(next_log_data(&mut stream).await, b"three\n");
+
+        let restarted_log = log_dir.join("app-1.log");
+        std::fs::write(&restarted_log, "2026-06-02T01:00:03Z stdout F four\n")
+            .expect("restarted log should be written");
+        insert_container_log_attempt(
+            &runtime,
+            "app-1",
+            "app-0-name",
+            2,
+            ContainerState::ContainerRunning,
+            &restarted_log,
+        )
+        .await;
+        assert_eq!(next_log_data(&mut stream).await, b"four\n");
+    }
+
+    async fn next_log_data(
+        stream: &mut tokio_stream::wrappers::ReceiverStream<Result<ContainerLogChunk, Status>>,
+    ) -> Vec<u8> {
+        use tokio_stream::StreamExt;
+
+        tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await
+            .expect("log chunk should arrive")
+            .expect("stream should stay open")
+            .expect("log chunk should be ok")
+            .data
+    }
+
+    fn append_log(path: &std::path::Path, line: &str) {
+        use std::io::Write;
+
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .expect("log should be appended");
+    }
+
+    fn rfc3339_nanos(value: &str) -> i64 {
+        chrono::DateTime::parse_from_rfc3339(value)
+            .expect("timestamp should parse")
+            .timestamp_nanos_opt()
+            .expect("timestamp should fit in nanos")
+    }
+
     #[tokio::test]
     async fn container_log_rejects_unowned_host_path() {
         let tempdir = tempfile::tempdir().expect("tempdir should be created");
@@ -1391,6 +2137,26 @@ mod tests {
             ..Default::default()
         };
         let runtime = crate::server::RuntimeServiceImpl::new(runtime_config);
+        insert_container_log_attempt(
+            &runtime,
+            container_id,
+            &format!("{container_id}-name"),
+            1,
+            ContainerState::ContainerRunning,
+            log_path,
+        )
+        .await;
+        runtime
+    }
+
+    async fn insert_container_log_attempt(
+        runtime: &crate::server::RuntimeServiceImpl,
+        container_id: &str,
+        name: &str,
+        attempt: u32,
+        state: ContainerState,
+        log_path: &std::path::Path,
+    ) {
         let annotations = std::collections::HashMap::from([(
             "io.crius.internal/container-state".to_string(),
             serde_json::json!({ "log_path": log_path.display().to_string() }).to_string(),
@@ -1399,10 +2165,10 @@ mod tests {
             .insert_diagnostics_test_container(Container {
                 id: container_id.to_string(),
                 pod_sandbox_id: "pod-1".to_string(),
-                state: ContainerState::ContainerRunning as i32,
+                state: state as i32,
                 metadata: Some(ContainerMetadata {
-                    name: format!("{container_id}-name"),
-                    attempt: 1,
+                    name: name.to_string(),
+                    attempt,
                 }),
                 image: Some(ImageSpec {
                     image: "busybox:latest".to_string(),
@@ -1412,10 +2178,10 @@ mod tests {
                 annotations,
                 created_at: chrono::Utc::now()
                     .timestamp_nanos_opt()
-                    .expect("current timestamp should fit in nanos"),
+                    .expect("current timestamp should fit in nanos")
+                    + i64::from(attempt),
                 ..Default::default()
             })
             .await;
-        runtime
     }
 }
@@ -1,10 +1,23 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
 use tokio_stream::wrappers::ReceiverStream;
 use tonic::Status;
 
-use crate::proto::runtime::v1::ContainerEventResponse;
+use crate::config::ContainerEventsOverflow;
+use crate::proto::runtime::v1::{
+    ContainerEventResponse, ContainerEventType, ContainerState, PodSandboxState,
+};
+use crate::services::{EventSinkHealth, EventSinkSet};
+use crate::storage::{EventLogInput, EventLogRecord};
 
 const MAX_INTERNAL_EVENT_DETAIL_BYTES: usize = 16 * 1024;
 const DEFAULT_INTERNAL_EVENT_RETENTION_PER_SUBJECT: usize = 256;
+const DEFAULT_CRI_SUBSCRIBER_QUEUE_SIZE: usize = 1024;
+const EVENT_LOG_PAGE_SIZE: usize = 256;
+/// 跟随模式下兜底轮询账本的周期，用于发现 shim 等其他进程直接写入的事件。
+const EVENT_LOG_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
 const INTERNAL_EVENT_PREFIXES: &[&str] = &[
     "pod.",
     "container.",
@@ -44,9 +57,198 @@ pub struct EventService {
     ledger:
         Option<std::sync::Arc<tokio::sync::Mutex<crate::storage::persistence::PersistenceManager>>>,
     internal_retention_per_subject: usize,
+    /// 事件日志最新序号，用于唤醒跟随中的 `WatchEvents` 订阅。
+    event_log_notify: tokio::sync::watch::Sender<i64>,
+    /// 把 CRI 事件交给事件日志记录任务的队列。
+    cri_event_log: Option<tokio::sync::mpsc::UnboundedSender<ContainerEventResponse>>,
+    event_sinks: Option<std::sync::Arc<EventSinkSet>>,
+    cri_subscribers: Arc<CriSubscribers>,
+    cri_queue_size: usize,
+    cri_overflow: ContainerEventsOverflow,
+}
+
+/// 订阅者队列溢出后用于补发当前容器状态的数据源。
+#[tonic::async_trait]
+pub trait ContainerEventResync: Send + Sync {
+    async fn current_events(&self) -> Vec<ContainerEventResponse>;
+}
+
+/// 单个 GetContainerEvents 订阅者的队列状态，供 metrics 和诊断展示。
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CriSubscriberStats {
+    pub id: u64,
+    pub queued: usize,
+    pub dropped: u64,
+    pub resyncs: u64,
+    pub connected_at: i64,
+}
+
+#[derive(Debug, Default)]
+struct CriSubscribers {
+    next_id: AtomicU64,
+    subscribers: std::sync::Mutex<Vec<Arc<CriSubscriber>>>,
+    /// 已断开订阅者累计丢弃的事件，保证总数单调递增。
+    disconnected_dropped: AtomicU64,
+}
+
+#[derive(Debug)]
+struct CriSubscriber {
+    id: u64,
+    queue: std::sync::Mutex<CriSubscriberQueue>,
+    notify: tokio::sync::Notify,
+    dropped: AtomicU64,
+    resyncs: AtomicU64,
+    connected_at: i64,
+}
+
+#[derive(Debug, Default)]
+struct CriSubscriberQueue {
+    events: VecDeque<ContainerEventResponse>,
+    /// 队列曾满，下一次取出时按溢出策略处理。
+    overflowed: bool,
+}
+
+enum CriSubscriberItem {
+    Event(Box<ContainerEventResponse>),
+    Overflow,
+}
+
+impl CriSubscribers {
+    fn register(&self) -> Arc<CriSubscriber> {
+        let subscriber = Arc::new(CriSubscriber {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed) + 1,
+            queue: std::sync::Mutex::new(CriSubscriberQueue::default()),
+            notify: tokio::sync::Notify::new(),
+            dropped: AtomicU64::new(0),
+            resyncs: AtomicU64::new(0),
+            connected_at: chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default(),
+        });
+        self.lock().push(subscriber.clone());
+        subscriber
+    }
+
+    fn unregister(&self, id: u64) {
+        let mut subscribers = self.lock();
+        if let Some(index) = subscribers
+            .iter()
+            .position(|subscriber| subscriber.id == id)
+        {
+            let subscriber = subscribers.remove(index);
+            self.disconnected_dropped.fetch_add(
+                subscriber.dropped.load(Ordering::Relaxed),
+                Ordering::Relaxed,
+            );
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Arc<CriSubscriber>>> {
+        self.subscribers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// 放入每个订阅者的队列，从不等待；队列满时清空积压并标记溢出。
+    fn dispatch(&self, event: &ContainerEventResponse, capacity: usize) {
+        for subscriber in self.lock().iter() {
+            let mut queue = subscriber.lock_queue();
+            if queue.overflowed {
+                subscriber.dropped.fetch_add(1, Ordering::Relaxed);
+                continue;
+            }
+            if queue.events.len() >= capacity {
+                let dropped = queue.events.len() as u64 + 1;
+                queue.events.clear();
+                queue.overflowed = true;
+                subscriber.dropped.fetch_add(dropped, Ordering::Relaxed);
+                log::warn!(
+                    "CRI event subscriber {} fell behind; dropped {} queued events",
+                    subscriber.id,
+                    dropped
+                );
+            } else {
+                queue.events.push_back(event.clone());
+            }
+            drop(queue);
+            subscriber.notify.notify_one();
+        }
+    }
+
+    fn stats(&self) -> Vec<CriSubscriberStats> {
+        self.lock()
+            .iter()
+            .map(|subscriber| CriSubscriberStats {
+                id: subscriber.id,
+                queued: subscriber.lock_queue().events.len(),
+                dropped: subscriber.dropped.load(Ordering::Relaxed),
+                resyncs: subscriber.resyncs.load(Ordering::Relaxed),
+                connected_at: subscriber.connected_at,
+            })
+            .collect()
+    }
+
+    fn dropped_total(&self) -> u64 {
+        let connected = self
+            .lock()
+            .iter()
+            .map(|subscriber| subscriber.dropped.load(Ordering::Relaxed))
+            .sum::<u64>();
+        self.disconnected_dropped.load(Ordering::Relaxed) + connected
+    }
 }
 
-#[derive(Debug, Clone, Copy, serde::Deserialize, serde::Serialize, PartialEq, Eq)]
+impl CriSubscriber {
+    fn lock_queue(&self) -> std::sync::MutexGuard<'_, CriSubscriberQueue> {
+        self.queue
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn pop(&self) -> Option<CriSubscriberItem> {
+        let mut queue = self.lock_queue();
+        if queue.overflowed {
+            queue.overflowed = false;
+            return Some(CriSubscriberItem::Overflow);
+        }
+        queue
+            .events
+            .pop_front()
+            .map(|event| CriSubscriberItem::Event(Box::new(event)))
+    }
+}
+
+/// 事件日志过滤条件；空字段表示不过滤。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventLogFilter {
+    /// 事件类型，精确匹配；以 `.` 或 `*` 结尾时按前缀匹配，如 `container.*`。
+    pub kinds: Vec<String>,
+    pub subject_kind: String,
+    pub subject_id: String,
+    pub pod_id: String,
+    pub namespace: String,
+    /// 最低严重级别，如 `Warning` 同时匹配 `warning` 和 `error`。
+    pub min_severity: Option<InternalEventSeverity>,
+}
+
+/// `WatchEvents` 订阅参数。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventLogWatch {
+    /// 只返回序号大于该值的事件；0 表示从最早保留的事件开始。
+    pub since_seq: i64,
+    /// 忽略 `since_seq`，从订阅时的最新序号开始。
+    pub from_latest: bool,
+    /// 追完历史后继续等待新事件。
+    pub follow: bool,
+    pub filter: EventLogFilter,
+}
+
+#[derive(
+    Debug, Clone, Copy, serde::Deserialize, serde::Serialize, PartialEq, Eq, PartialOrd, Ord,
+)]
 #[serde(rename_all = "lowercase")]
 pub enum InternalEventSeverity {
     Debug,
@@ -65,7 +267,7 @@ impl InternalEventSeverity {
         }
     }
 
-    fn parse(value: &str) -> anyhow::Result<Self> {
+    pub fn parse(value: &str) -> anyhow::Result<Self> {
         match value {
             "debug" => Ok(Self::Debug),
             "info" => Ok(Self::Info),
@@ -158,6 +360,50 @@ impl InternalEvent {
     fn details_for_ledger(&self) -> Option<String> {
         (!self.details.is_null()).then(|| self.details.to_string())
     }
+
+    fn event_log_input<'a>(&'a self, details: Option<&'a str>) -> EventLogInput<'a> {
+        EventLogInput {
+            source: "internal",
+            kind: &self.kind,
+            subject_kind: &self.subject_kind,
+            subject_id: &self.subject_id,
+            pod_id: self.details["podSandboxId"].as_str(),
+            namespace: self.details["namespace"].as_str(),
+            severity: self.severity.as_str(),
+            timestamp_nanos: self.timestamp.saturating_mul(1_000_000_000),
+            details,
+        }
+    }
+}
+
+impl EventLogFilter {
+    pub fn matches(&self, record: &EventLogRecord) -> bool {
+        let field_matches =
+            |expected: &str, actual: &str| expected.is_empty() || expected == actual;
+        (self.kinds.is_empty()
+            || self
+                .kinds
+                .iter()
+                .any(|kind| kind_matches(kind, &record.kind)))
+            && field_matches(&self.subject_kind, &record.subject_kind)
+            && field_matches(&self.subject_id, &record.subject_id)
+            && field_matches(&self.pod_id, &record.pod_id)
+            && field_matches(&self.namespace, &record.namespace)
+            && self.min_severity.is_none_or(|min_severity| {
+                InternalEventSeverity::parse(&record.severity)
+                    .is_ok_and(|severity| severity >= min_severity)
+            })
+    }
+}
+
+fn kind_matches(pattern: &str, kind: &str) -> bool {
+    if let Some(prefix) = pattern.strip_suffix('*') {
+        return kind.starts_with(prefix);
+    }
+    if pattern.ends_with('.') {
+        return kind.starts_with(pattern);
+    }
+    pattern == kind
 }
 
 impl LedgerInternalEventSink {
@@ -170,15 +416,19 @@ impl LedgerInternalEventSink {
     pub fn publish(&self, event: &InternalEvent) -> anyhow::Result<()> {
         event.validate_schema()?;
         let mut storage = crate::storage::StorageManager::new(&self.db_path)?;
+        let details = event.details_for_ledger();
         storage.append_typed_event_at(crate::storage::TypedEventInput {
             event_type: &event.kind,
             entity_type: &event.subject_kind,
             entity_id: &event.subject_id,
             old_state: None,
             new_state: Some(event.severity.as_str()),
-            details: event.details_for_ledger().as_deref(),
+            details: details.as_deref(),
             timestamp: event.timestamp,
-        })
+        })?;
+        // daemon 的跟随订阅通过轮询发现这里写入的记录。
+        storage.append_event_log(event.event_log_input(details.as_deref()))?;
+        Ok(())
     }
 }
 
@@ -186,24 +436,44 @@ impl EventService {
     pub fn with_capacity(capacity: usize) -> Self {
         let (sender, _) = tokio::sync::broadcast::channel(capacity);
         let (internal_sender, _) = tokio::sync::broadcast::channel(capacity);
-        Self {
-            sender,
-            internal_sender,
-            ledger: None,
-            internal_retention_per_subject: DEFAULT_INTERNAL_EVENT_RETENTION_PER_SUBJECT,
-        }
+        Self::from_channels(sender, internal_sender)
     }
 
     pub fn from_sender(sender: tokio::sync::broadcast::Sender<ContainerEventResponse>) -> Self {
         let (internal_sender, _) = tokio::sync::broadcast::channel(256);
+        Self::from_channels(sender, internal_sender)
+    }
+
+    fn from_channels(
+        sender: tokio::sync::broadcast::Sender<ContainerEventResponse>,
+        internal_sender: tokio::sync::broadcast::Sender<InternalEvent>,
+    ) -> Self {
+        let (event_log_notify, _) = tokio::sync::watch::channel(0);
         Self {
             sender,
             internal_sender,
             ledger: None,
             internal_retention_per_subject: DEFAULT_INTERNAL_EVENT_RETENTION_PER_SUBJECT,
+            event_log_notify,
+            cri_event_log: None,
+            event_sinks: None,
+            cri_subscribers: Arc::new(CriSubscribers::default()),
+            cri_queue_size: DEFAULT_CRI_SUBSCRIBER_QUEUE_SIZE,
+            cri_overflow: ContainerEventsOverflow::default(),
         }
     }
 
+    /// GetContainerEvents 每个订阅者的队列上限和溢出处理方式。
+    pub fn with_subscriber_queue(
+        mut self,
+        queue_size: usize,
+        overflow: ContainerEventsOverflow,
+    ) -> Self {
+        self.cri_queue_size = queue_size.max(1);
+        self.cri_overflow = overflow;
+        self
+    }
+
     pub fn with_ledger(
         mut self,
         ledger: std::sync::Arc<tokio::sync::Mutex<crate::storage::persistence::PersistenceManager>>,
@@ -217,6 +487,105 @@ impl EventService {
         self
     }
 
+    /// 把 CRI 事件同时写入持久化事件日志。需要先配置账本且处于 tokio 运行时中，
+    /// 否则只有内部事件进入事件日志。
+    ///
+    /// `publish` 不能等待，而丢掉的 CRI 事件不占序号、续读方无从察觉，因此记录队列不设上限；
+    /// 积压只取决于账本写入速度。
+    pub fn with_event_log(mut self) -> Self {
+        let Some(ledger) = self.ledger.clone() else {
+            return self;
+        };
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return self;
+        };
+        // 所有 EventService 副本释放后发送端关闭，任务自然退出。
+        let (recorder, mut events) = tokio::sync::mpsc::unbounded_channel();
+        let notify = self.event_log_notify.clone();
+        handle.spawn(async move {
+            while let Some(event) = events.recv().await {
+                record_cri_event(&ledger, &notify, &event).await;
+            }
+        });
+        self.cri_event_log = Some(recorder);
+        self
+    }
+
+    /// 跟随事件日志把新事件交给外部 sink。需要先配置账本且处于 tokio 运行时中；
+    /// 读取事件日志失败时从最后投递的序号重新订阅。
+    pub fn with_event_sinks(mut self, sinks: EventSinkSet) -> Self {
+        if sinks.is_empty() || self.ledger.is_none() {
+            return self;
+        }
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return self;
+        };
+        // 只外发之后的新事件；能立即拿到账本时在这里确定起点，避免与随后发布的事件竞争。
+        let since_seq = self.ledger.as_ref().and_then(|ledger| {
+            let persistence = ledger.try_lock().ok()?;
+            crate::state::StateLedger::new(&persistence)
+                .event_log_latest_seq()
+                .ok()
+        });
+        let sinks = std::sync::Arc::new(sinks);
+        let dispatcher = sinks.clone();
+        let events = self.clone();
+        handle.spawn(async move {
+            let mut watch = EventLogWatch {
+                since_seq: since_seq.unwrap_or_default(),
+                from_latest: since_seq.is_none(),
+                follow: true,
+                ..Default::default()
+            };
+            loop {
+                match events
+                    .watch_event_log::<EventLogRecord>(watch.clone())
+                    .await
+                {
+                    Ok(mut records) => {
+                        while let Some(record) = tokio_stream::StreamExt::next(&mut records).await {
+                            match record {
+                                Ok(record) => {
+                                    watch.since_seq = record.seq;
+                                    watch.from_latest = false;
+                                    dispatcher.dispatch(&record);
+                                }
+                                Err(status) => {
+                                    log::warn!(
+                                        "Event sinks lost the event log: {}",
+                                        status.message()
+                                    );
+                                    if status.code() == tonic::Code::OutOfRange {
+                                        // 落后于淘汰位置，从最早保留的事件继续外发。
+                                        watch.since_seq = 0;
+                                    }
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                    Err(status) if status.code() == tonic::Code::FailedPrecondition => {
+                        log::warn!("Event sinks are disabled: {}", status.message());
+                        return;
+                    }
+                    Err(status) => {
+                        log::warn!("Event sinks lost the event log: {}", status.message());
+                    }
+                }
+                tokio::time::sleep(EVENT_LOG_POLL_INTERVAL).await;
+            }
+        });
+        self.event_sinks = Some(sinks);
+        self
+    }
+
+    pub fn event_sink_health(&self) -> Vec<EventSinkHealth> {
+        self.event_sinks
+            .as_ref()
+            .map(|sinks| sinks.health())
+            .unwrap_or_default()
+    }
+
     pub fn sender(&self) -> tokio::sync::broadcast::Sender<ContainerEventResponse> {
         self.sender.clone()
     }
@@ -225,8 +594,26 @@ impl EventService {
         self.sender.subscribe()
     }
 
+    /// CRI 事件订阅者数量，不含事件日志记录任务。
     pub fn subscriber_count(&self) -> usize {
-        self.sender.receiver_count()
+        self.sender.receiver_count() + self.cri_subscribers.len()
+    }
+
+    /// 是否有任何消费者会收到 CRI 事件，包括事件日志记录任务。
+    pub fn has_cri_receivers(&self) -> bool {
+        self.cri_event_log.is_some()
+            || self.sender.receiver_count() > 0
+            || self.cri_subscribers.len() > 0
+    }
+
+    /// 各 GetContainerEvents 订阅者的队列深度与丢弃计数。
+    pub fn subscriber_stats(&self) -> Vec<CriSubscriberStats> {
+        self.cri_subscribers.stats()
+    }
+
+    /// 所有订阅者（含已断开的）因队列溢出丢弃的事件总数。
+    pub fn dropped_event_count(&self) -> u64 {
+        self.cri_subscribers.dropped_total()
     }
 
     pub fn internal_subscriber_count(&self) -> usize {
@@ -234,8 +621,17 @@ impl EventService {
     }
 
     pub fn publish(&self, event: ContainerEventResponse) {
-        if let Err(err) = self.sender.send(event) {
-            log::debug!("Dropping CRI event without subscribers: {}", err);
+        if let Some(recorder) = &self.cri_event_log {
+            if recorder.send(event.clone()).is_err() {
+                log::warn!(
+                    "Event log recorder stopped; CRI event for {} is not recorded",
+                    event.container_id
+                );
+            }
+        }
+        self.cri_subscribers.dispatch(&event, self.cri_queue_size);
+        if self.sender.send(event).is_err() && self.cri_subscribers.len() == 0 {
+            log::debug!("Dropping CRI event without subscribers");
         }
     }
 
@@ -273,6 +669,27 @@ impl EventService {
         Ok(events)
     }
 
+    /// 账本中某类主体自 `since`（秒）以来的内部事件，按时间倒序。
+    pub async fn internal_events_since(
+        &self,
+        subject_kind: &str,
+        since: i64,
+    ) -> anyhow::Result<Vec<InternalEvent>> {
+        let Some(ledger) = &self.ledger else {
+            return Ok(Vec::new());
+        };
+        let persistence = ledger.lock().await;
+        let ledger = crate::state::StateLedger::new(&persistence);
+        let mut events = Vec::new();
+        for event in ledger.recent_events(subject_kind, since)? {
+            match InternalEvent::from_state_event(event) {
+                Ok(event) => events.push(event),
+                Err(err) => log::debug!("Skipping non-internal ledger event: {}", err),
+            }
+        }
+        Ok(events)
+    }
+
     async fn persist_internal_event(&self, event: &InternalEvent) -> anyhow::Result<()> {
         let Some(ledger) = &self.ledger else {
             return Ok(());
@@ -280,13 +697,14 @@ impl EventService {
         event.validate_schema()?;
         let mut persistence = ledger.lock().await;
         let mut ledger = crate::state::StateLedgerWriter::new(&mut persistence);
+        let details = event.details_for_ledger();
         ledger.append_typed_event_at(crate::storage::TypedEventInput {
             event_type: &event.kind,
             entity_type: &event.subject_kind,
             entity_id: &event.subject_id,
             old_state: None,
             new_state: Some(event.severity.as_str()),
-            details: event.details_for_ledger().as_deref(),
+            details: details.as_deref(),
             timestamp: event.timestamp,
         })?;
         ledger.prune_events_for_subject(
@@ -294,41 +712,240 @@ impl EventService {
             &event.subject_id,
             self.internal_retention_per_subject,
         )?;
+        if let Some(record) = ledger.append_event_log(event.event_log_input(details.as_deref()))? {
+            self.event_log_notify.send_replace(record.seq);
+        }
         Ok(())
     }
 
-    pub fn stream(&self) -> ReceiverStream<Result<ContainerEventResponse, Status>> {
-        let mut events = self.subscribe();
+    /// 订阅持久化事件日志：先按序号补齐历史，`follow` 时继续推送新事件。
+    /// 保留区间内序号连续；续读位置之后的事件已被淘汰时（包括跟随过程中被追上），
+    /// 以 `OUT_OF_RANGE` 结束流，调用方应重新同步后从 0（最早保留的事件）开始续读。
+    pub async fn watch_event_log<T>(
+        &self,
+        watch: EventLogWatch,
+    ) -> Result<ReceiverStream<Result<T, Status>>, Status>
+    where
+        T: From<EventLogRecord> + Send + 'static,
+    {
+        let Some(ledger) = self.ledger.clone() else {
+            return Err(Status::failed_precondition(
+                "event log requires the state ledger",
+            ));
+        };
+        let mut notify = self.event_log_notify.subscribe();
+        let mut cursor = {
+            let persistence = ledger.lock().await;
+            let capacity = persistence
+                .storage()
+                .event_log_capacity()
+                .map_err(|err| Status::internal(format!("failed to read event log: {err}")))?;
+            if capacity == 0 {
+                return Err(Status::failed_precondition("event log is disabled"));
+            }
+            if watch.from_latest {
+                crate::state::StateLedger::new(&persistence)
+                    .event_log_latest_seq()
+                    .map_err(|err| Status::internal(format!("failed to read event log: {err}")))?
+            } else {
+                watch.since_seq.max(0)
+            }
+        };
         let (tx, rx) = tokio::sync::mpsc::channel(128);
 
         tokio::spawn(async move {
             loop {
-                match events.recv().await {
-                    Ok(event) => {
-                        if tx.send(Ok(event)).await.is_err() {
-                            break;
+                loop {
+                    let page = {
+                        let persistence = ledger.lock().await;
+                        crate::state::StateLedger::new(&persistence)
+                            .event_log_since(cursor, EVENT_LOG_PAGE_SIZE)
+                    };
+                    let page = match page {
+                        Ok(page) => page,
+                        Err(err) => {
+                            let _ = tx
+                                .send(Err(Status::internal(format!(
+                                    "failed to read event log: {err}"
+                                ))))
+                                .await;
+                            return;
+                        }
+                    };
+                    if let Some(first) = page.first() {
+                        if cursor > 0 && first.seq > cursor + 1 {
+                            let _ = tx.send(Err(event_log_truncated(cursor, first.seq))).await;
+                            return;
                         }
                     }
-                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {
-                        if tx
-                            .send(Err(Status::resource_exhausted(
-                                "CRI event stream lagged behind producer",
-                            )))
-                            .await
-                            .is_err()
+                    let exhausted = page.len() < EVENT_LOG_PAGE_SIZE;
+                    for record in page {
+                        cursor = record.seq;
+                        if watch.filter.matches(&record)
+                            && tx.send(Ok(record.into())).await.is_err()
                         {
+                            return;
+                        }
+                    }
+                    if exhausted {
+                        break;
+                    }
+                }
+                if !watch.follow {
+                    return;
+                }
+                tokio::select! {
+                    changed = notify.changed() => {
+                        if changed.is_err() {
+                            return;
+                        }
+                    }
+                    _ = tokio::time::sleep(EVENT_LOG_POLL_INTERVAL) => {}
+                    _ = tx.closed() => return,
+                }
+            }
+        });
+
+        Ok(ReceiverStream::new(rx))
+    }
+
+    pub fn stream(&self) -> ReceiverStream<Result<ContainerEventResponse, Status>> {
+        self.subscribe_stream(None)
+    }
+
+    /// GetContainerEvents 事件流。每个订阅者有独立的有界队列，发布方从不等待；
+    /// 队列溢出后按配置断开订阅，或在提供了 `resync` 时补发当前容器状态。
+    pub fn stream_with_resync(
+        &self,
+        resync: Arc<dyn ContainerEventResync>,
+    ) -> ReceiverStream<Result<ContainerEventResponse, Status>> {
+        self.subscribe_stream(Some(resync))
+    }
+
+    fn subscribe_stream(
+        &self,
+        resync: Option<Arc<dyn ContainerEventResync>>,
+    ) -> ReceiverStream<Result<ContainerEventResponse, Status>> {
+        let subscribers = self.cri_subscribers.clone();
+        let subscriber = subscribers.register();
+        let resync = resync.filter(|_| self.cri_overflow == ContainerEventsOverflow::Resync);
+        let (tx, rx) = tokio::sync::mpsc::channel(1);
+
+        tokio::spawn(async move {
+            loop {
+                match subscriber.pop() {
+                    Some(CriSubscriberItem::Event(event)) => {
+                        if tx.send(Ok(*event)).await.is_err() {
                             break;
                         }
                     }
-                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                    Some(CriSubscriberItem::Overflow) => {
+                        let Some(resync) = resync.as_ref() else {
+                            let _ = tx
+                                .send(Err(Status::resource_exhausted(format!(
+                                    "CRI event subscriber {} fell behind and dropped {} events; relist and resubscribe",
+                                    subscriber.id,
+                                    subscriber.dropped.load(Ordering::Relaxed)
+                                ))))
+                                .await;
+                            break;
+                        };
+                        subscriber.resyncs.fetch_add(1, Ordering::Relaxed);
+                        let mut closed = false;
+                        for event in resync.current_events().await {
+                            if tx.send(Ok(event)).await.is_err() {
+                                closed = true;
+                                break;
+                            }
+                        }
+                        if closed {
+                            break;
+                        }
+                    }
+                    None => {
+                        tokio::select! {
+                            _ = subscriber.notify.notified() => {}
+                            _ = tx.closed() => break,
+                        }
+                    }
                 }
             }
+            subscribers.unregister(subscriber.id);
         });
 
         ReceiverStream::new(rx)
     }
 }
 
+/// 续读位置 `cursor` 之后到 `oldest_seq` 之前的事件已被淘汰。
+fn event_log_truncated(cursor: i64, oldest_seq: i64) -> Status {
+    Status::out_of_range(format!(
+        "event log truncated: events {}..{} are no longer retained; resync and resume from seq 0 (oldest retained {})",
+        cursor + 1,
+        oldest_seq - 1,
+        oldest_seq
+    ))
+}
+
+async fn record_cri_event(
+    ledger: &tokio::sync::Mutex<crate::storage::persistence::PersistenceManager>,
+    notify: &tokio::sync::watch::Sender<i64>,
+    event: &ContainerEventResponse,
+) {
+    let kind = match ContainerEventType::try_from(event.container_event_type) {
+        Ok(ContainerEventType::ContainerCreatedEvent) => "cri.container_created",
+        Ok(ContainerEventType::ContainerStartedEvent) => "cri.container_started",
+        Ok(ContainerEventType::ContainerStoppedEvent) => "cri.container_stopped",
+        Ok(ContainerEventType::ContainerDeletedEvent) => "cri.container_deleted",
+        Err(_) => "cri.unknown",
+    };
+    let pod_status = event.pod_sandbox_status.as_ref();
+    let details = serde_json::json!({
+        "podSandboxState": pod_status
+            .and_then(|status| PodSandboxState::try_from(status.state).ok())
+            .map(|state| state.as_str_name()),
+        "containerStates": event
+            .containers_statuses
+            .iter()
+            .map(|status| {
+                (
+                    status.id.clone(),
+                    ContainerState::try_from(status.state)
+                        .map(|state| state.as_str_name())
+                        .unwrap_or("CONTAINER_UNKNOWN"),
+                )
+            })
+            .collect::<std::collections::BTreeMap<_, _>>(),
+    })
+    .to_string();
+    let mut persistence = ledger.lock().await;
+    let result =
+        crate::state::StateLedgerWriter::new(&mut persistence).append_event_log(EventLogInput {
+            source: "cri",
+            kind,
+            subject_kind: "container",
+            subject_id: &event.container_id,
+            pod_id: pod_status.map(|status| status.id.as_str()),
+            namespace: pod_status
+                .and_then(|status| status.metadata.as_ref())
+                .map(|metadata| metadata.namespace.as_str()),
+            severity: InternalEventSeverity::Info.as_str(),
+            timestamp_nanos: event.created_at,
+            details: Some(&details),
+        });
+    match result {
+        Ok(Some(record)) => {
+            notify.send_replace(record.seq);
+        }
+        Ok(None) => {}
+        Err(err) => log::warn!(
+            "Failed to record CRI event for {} in event log: {}",
+            event.container_id,
+            err
+        ),
+    }
+}
+
 fn validate_internal_event_kind(kind: &str) -> anyhow::Result<()> {
     let kind = kind.trim();
     if kind.is_empty() {
@@ -398,6 +1015,80 @@ mod tests {
         );
     }
 
+    fn started_event(container_id: &str) -> ContainerEventResponse {
+        ContainerEventResponse {
+            container_id: container_id.to_string(),
+            container_event_type: ContainerEventType::ContainerStartedEvent as i32,
+            created_at: 42,
+            pod_sandbox_status: None,
+            containers_statuses: Vec::new(),
+        }
+    }
+
+    struct FixedResync(Vec<ContainerEventResponse>);
+
+    #[tonic::async_trait]
+    impl ContainerEventResync for FixedResync {
+        async fn current_events(&self) -> Vec<ContainerEventResponse> {
+            self.0.clone()
+        }
+    }
+
+    #[tokio::test]
+    async fn slow_subscriber_overflow_disconnects_with_relist_status() {
+        let events = EventService::with_capacity(16)
+            .with_subscriber_queue(4, ContainerEventsOverflow::Disconnect);
+        let mut slow = events.stream();
+
+        // 发布方从不等待订阅者；第 5 条事件使队列溢出，积压和之后的事件都被丢弃。
+        for idx in 0..10 {
+            events.publish(started_event(&format!("container-{idx}")));
+        }
+        assert_eq!(events.subscriber_stats()[0].dropped, 10);
+
+        let status = slow.next().await.unwrap().unwrap_err();
+        assert_eq!(status.code(), tonic::Code::ResourceExhausted);
+        assert!(status.message().contains("relist"), "{}", status.message());
+        assert!(slow.next().await.is_none());
+        assert!(events.subscriber_stats().is_empty());
+        assert_eq!(events.dropped_event_count(), 10);
+
+        let mut fresh = events.stream();
+        events.publish(started_event("after-relist"));
+        assert_eq!(
+            fresh.next().await.unwrap().unwrap().container_id,
+            "after-relist"
+        );
+        assert_eq!(events.subscriber_stats()[0].dropped, 0);
+    }
+
+    #[tokio::test]
+    async fn overflow_resyncs_current_states_when_configured() {
+        let events = EventService::with_capacity(16)
+            .with_subscriber_queue(2, ContainerEventsOverflow::Resync);
+        let mut stream = events.stream_with_resync(Arc::new(FixedResync(vec![
+            started_event("current-1"),
+            started_event("current-2"),
+        ])));
+
+        for idx in 0..5 {
+            events.publish(started_event(&format!("stale-{idx}")));
+        }
+        let mut received = Vec::new();
+        for _ in 0..2 {
+            received.push(stream.next().await.unwrap().unwrap().container_id);
+        }
+        assert_eq!(received, vec!["current-1", "current-2"]);
+
+        events.publish(started_event("after-resync"));
+        assert_eq!(
+            stream.next().await.unwrap().unwrap().container_id,
+            "after-resync"
+        );
+        let stats = &events.subscriber_stats()[0];
+        assert_eq!((stats.resyncs, stats.dropped, stats.queued), (1, 5, 0));
+    }
+
     #[tokio::test]
     async fn reports_subscriber_count() {
         let events = EventService::with_capacity(16);
@@ -631,4 +1322,281 @@ mod tests {
         assert_eq!(recent[0].details["index"], 3);
         assert_eq!(recent[1].details["index"], 2);
     }
+
+    fn event_log_persistence(
+        dir: &tempfile::TempDir,
+        capacity: u64,
+    ) -> Arc<tokio::sync::Mutex<PersistenceManager>> {
+        let mut persistence = PersistenceManager::new(PersistenceConfig {
+            db_path: dir.path().join("events.db"),
+            enable_recovery: true,
+            auto_save_interval: 30,
+        })
+        .unwrap();
+        persistence
+            .storage_mut()
+            .configure_event_log(capacity)
+            .unwrap();
+        Arc::new(tokio::sync::Mutex::new(persistence))
+    }
+
+    async fn collect_event_log(events: &EventService, watch: EventLogWatch) -> Vec<EventLogRecord> {
+        events
+            .watch_event_log::<EventLogRecord>(watch)
+            .await
+            .unwrap()
+            .map(|record| record.unwrap())
+            .collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn event_log_replays_retained_events_from_a_sequence_with_filters() {
+        let dir = tempdir().unwrap();
+        let events = EventService::with_capacity(16).with_ledger(event_log_persistence(&dir, 4));
+        for (index, severity) in [
+            InternalEventSeverity::Info,
+            InternalEventSeverity::Warning,
+            InternalEventSeverity::Info,
+            InternalEventSeverity::Error,
+            InternalEventSeverity::Info,
+            InternalEventSeverity::Warning,
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            events
+                .publish_internal(InternalEvent::with_timestamp(
+                    "container.state",
+                    "container",
+                    format!("container-{}", index % 2),
+                    severity,
+                    100 + index as i64,
+                    serde_json::json!({ "podSandboxId": "pod-1", "index": index }),
+                ))
+                .await
+                .unwrap();
+        }
+
+        let all = collect_event_log(&events, EventLogWatch::default()).await;
+        assert_eq!(
+            all.iter().map(|record| record.seq).collect::<Vec<_>>(),
+            vec![3, 4, 5, 6]
+        );
+        assert!(all.iter().all(|record| record.pod_id == "pod-1"));
+        assert_eq!(all[0].timestamp_nanos, 102_000_000_000);
+
+        let resumed = collect_event_log(
+            &events,
+            EventLogWatch {
+                since_seq: 4,
+                ..Default::default()
+            },
+        )
+        .await;
+        assert_eq!(
+            resumed.iter().map(|record| record.seq).collect::<Vec<_>>(),
+            vec![5, 6]
+        );
+
+        let mut truncated = events
+            .watch_event_log::<EventLogRecord>(EventLogWatch {
+                since_seq: 1,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let status = truncated.next().await.unwrap().unwrap_err();
+        assert_eq!(status.code(), tonic::Code::OutOfRange);
+        assert!(status.message().contains("events 2..2"), "{status:?}");
+        assert!(truncated.next().await.is_none());
+
+        let filtered = collect_event_log(
+            &events,
+            EventLogWatch {
+                filter: EventLogFilter {
+                    kinds: vec!["container.*".to_string()],
+                    subject_id: "container-1".to_string(),
+                    min_severity: Some(InternalEventSeverity::Warning),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        )
+        .await;
+        assert_eq!(
+            filtered.iter().map(|record| record.seq).collect::<Vec<_>>(),
+            vec![4, 6]
+        );
+    }
+
+    #[tokio::test]
+    async fn event_log_follow_streams_cri_and_internal_events_in_sequence() {
+        use crate::proto::runtime::v1::{PodSandboxMetadata, PodSandboxStatus};
+
+        let dir = tempdir().unwrap();
+        let events = EventService::with_capacity(16)
+            .with_ledger(event_log_persistence(&dir, 100))
+            .with_event_log();
+        let mut stream = events
+            .watch_event_log::<EventLogRecord>(EventLogWatch {
+                from_latest: true,
+                follow: true,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(events.subscriber_count(), 0);
+
+        events.publish(ContainerEventResponse {
+            container_id: "container-1".to_string(),
+            container_event_type: ContainerEventType::ContainerStartedEvent as i32,
+            created_at: 42,
+            pod_sandbox_status: Some(PodSandboxStatus {
+                id: "pod-1".to_string(),
+                metadata: Some(PodSandboxMetadata {
+                    namespace: "team-a".to_string(),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            containers_statuses: Vec::new(),
+        });
+        let started = tokio::time::timeout(std::time::Duration::from_secs(5), stream.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(started.source, "cri");
+        assert_eq!(started.kind, "cri.container_started");
+        assert_eq!(started.namespace, "team-a");
+
+        events
+            .publish_internal(InternalEvent::with_timestamp(
+                "container.oom",
+                "container",
+                "container-1",
+                InternalEventSeverity::Warning,
+                43,
+                serde_json::json!({ "reason": "OOMKilled" }),
+            ))
+            .await
+            .unwrap();
+        let oom = tokio::time::timeout(std::time::Duration::from_secs(5), stream.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(oom.seq, started.seq + 1);
+        assert_eq!(oom.kind, "container.oom");
+        assert_eq!(
+            (oom.pod_id.as_str(), oom.namespace.as_str()),
+            ("pod-1", "team-a")
+        );
+    }
+
+    #[tokio::test]
+    async fn event_log_records_every_cri_event_of_a_burst() {
+        let dir = tempdir().unwrap();
+        let events = EventService::with_capacity(4)
+            .with_ledger(event_log_persistence(&dir, 1000))
+            .with_event_log();
+        assert!(events.has_cri_receivers());
+        for index in 0..64 {
+            events.publish(started_event(&format!("container-{index}")));
+        }
+
+        let mut stream = events
+            .watch_event_log::<EventLogRecord>(EventLogWatch {
+                follow: true,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        for expected in 1..=64 {
+            let record = tokio::time::timeout(std::time::Duration::from_secs(5), stream.next())
+                .await
+                .unwrap()
+                .unwrap()
+                .unwrap();
+            assert_eq!(record.seq, expected);
+            assert_eq!(record.subject_id, format!("container-{}", expected - 1));
+        }
+    }
+
+    #[tokio::test]
+    async fn event_sinks_export_new_event_log_records_including_direct_ledger_writes() {
+        let dir = tempdir().unwrap();
+        let oom = |subject: &str| {
+            InternalEvent::with_timestamp(
+                "container.oom",
+                "container",
+                subject,
+                InternalEventSeverity::Warning,
+                43,
+                serde_json::json!({ "reason": "OOMKilled" }),
+            )
+        };
+        let events = EventService::with_capacity(16).with_ledger(event_log_persistence(&dir, 100));
+        events.publish_internal(oom("before-sinks")).await.unwrap();
+
+        let path = dir.path().join("sink").join("events.jsonl");
+        let sinks = EventSinkSet::start(&[crate::config::EventSinkConfig {
+            name: "file".to_string(),
+            sink_type: crate::config::EventSinkType::File,
+            path: path.display().to_string(),
+            kinds: vec!["container.".to_string()],
+            ..Default::default()
+        }])
+        .unwrap();
+        let events = events.with_event_sinks(sinks);
+        events.publish_internal(oom("from-daemon")).await.unwrap();
+        events
+            .publish_internal(InternalEvent::new(
+                "image.pulled",
+                "image",
+                "busybox",
+                InternalEventSeverity::Info,
+                serde_json::Value::Null,
+            ))
+            .await
+            .unwrap();
+        LedgerInternalEventSink::new(dir.path().join("events.db"))
+            .publish(&oom("from-shim"))
+            .unwrap();
+
+        for _ in 0..500 {
+            if events
+                .event_sink_health()
+                .first()
+                .is_some_and(|health| health.delivered == 2)
+            {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        let subjects = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| {
+                serde_json::from_str::<serde_json::Value>(line).unwrap()["subjectId"]
+                    .as_str()
+                    .unwrap()
+                    .to_string()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(subjects, vec!["from-daemon", "from-shim"]);
+        assert!(events.event_sink_health()[0].healthy);
+    }
+
+    #[tokio::test]
+    async fn event_log_watch_requires_enabled_log() {
+        let dir = tempdir().unwrap();
+        let events = EventService::with_capacity(16).with_ledger(event_log_persistence(&dir, 0));
+        let err = events
+            .watch_event_log::<EventLogRecord>(EventLogWatch::default())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+    }
 }
@@ -0,0 +1,729 @@
+//! 事件外发 sink
+//!
+//! sink 跟随持久化事件日志，而不是挂在 `publish_internal` 上：shim、镜像和 CNI 等直接写账本的
+//! 事件同样会外发。每个 sink 有独立的有界队列和投递任务，队列满时丢弃新事件并计数，
+//! 慢或失败的 sink 不会阻塞生命周期路径，也不会拖慢其他 sink。
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context;
+use chrono::SecondsFormat;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::mpsc;
+
+use crate::config::{EventSinkConfig, EventSinkType};
+use crate::services::{EventLogFilter, InternalEventSeverity};
+use crate::storage::EventLogRecord;
+use crate::utils::log_output::{
+    rotate_numbered, syslog_field, syslog_hostname, syslog_octet_frame, SyslogAddress,
+    SYSLOG_APP_NAME_MAX_LEN, SYSLOG_MSG_ID_MAX_LEN,
+};
+
+const MAX_WEBHOOK_BACKOFF: Duration = Duration::from_secs(30);
+const SYSLOG_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 单个 sink 的健康状态，供诊断接口和 `crs debug metrics` 展示。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventSinkHealth {
+    pub name: String,
+    pub sink_type: &'static str,
+    pub target: String,
+    /// 最近一次投递成功，或尚未投递过。
+    pub healthy: bool,
+    pub queued: u64,
+    pub delivered: u64,
+    pub failed: u64,
+    pub dropped: u64,
+    pub last_error: Option<String>,
+    pub last_delivered_at: Option<i64>,
+    pub last_failed_at: Option<i64>,
+}
+
+#[derive(Debug, Default)]
+struct EventSinkStats {
+    delivered: AtomicU64,
+    failed: AtomicU64,
+    dropped: AtomicU64,
+    consecutive_failures: AtomicU64,
+    last_delivered_at: AtomicI64,
+    last_failed_at: AtomicI64,
+    last_error: std::sync::Mutex<Option<String>>,
+}
+
+impl EventSinkStats {
+    fn record_success(&self) {
+        self.delivered.fetch_add(1, Ordering::Relaxed);
+        self.consecutive_failures.store(0, Ordering::Relaxed);
+        self.last_delivered_at.store(now_nanos(), Ordering::Relaxed);
+    }
+
+    /// 记录一次失败，返回此前是否健康，用于只在状态切换时打日志。
+    fn record_failure(&self, error: String) -> bool {
+        self.failed.fetch_add(1, Ordering::Relaxed);
+        self.last_failed_at.store(now_nanos(), Ordering::Relaxed);
+        if let Ok(mut last_error) = self.last_error.lock() {
+            *last_error = Some(error);
+        }
+        self.consecutive_failures.fetch_add(1, Ordering::Relaxed) == 0
+    }
+}
+
+struct EventSinkHandle {
+    name: String,
+    sink_type: EventSinkType,
+    target: String,
+    filter: EventLogFilter,
+    sender: mpsc::Sender<EventLogRecord>,
+    stats: Arc<EventSinkStats>,
+}
+
+/// 已启动的 sink 集合。
+#[derive(Default)]
+pub struct EventSinkSet {
+    sinks: Vec<EventSinkHandle>,
+}
+
+impl std::fmt::Debug for EventSinkSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.sinks.iter().map(|sink| &sink.name))
+            .finish()
+    }
+}
+
+impl EventSinkSet {
+    /// 为每个 sink 启动独立的投递任务；需要在 tokio 运行时中调用。
+    pub fn start(configs: &[EventSinkConfig]) -> anyhow::Result<Self> {
+        let runtime =
+            tokio::runtime::Handle::try_current().context("event sinks require a tokio runtime")?;
+        let hostname = syslog_hostname();
+        let mut sinks = Vec::with_capacity(configs.len());
+        for config in configs {
+            let min_severity = InternalEventSeverity::parse(config.min_severity.trim())
+                .with_context(|| format!("event sink {}", config.name))?;
+            let backend = SinkBackend::new(config, hostname.clone())
+                .with_context(|| format!("event sink {}", config.name))?;
+            let (sender, receiver) = mpsc::channel(config.queue_size.max(1));
+            let stats = Arc::new(EventSinkStats::default());
+            runtime.spawn(run_sink(
+                config.name.clone(),
+                backend,
+                receiver,
+                stats.clone(),
+            ));
+            sinks.push(EventSinkHandle {
+                name: config.name.clone(),
+                sink_type: config.sink_type,
+                target: sink_target(config),
+                filter: EventLogFilter {
+                    kinds: config.kinds.clone(),
+                    min_severity: Some(min_severity),
+                    ..Default::default()
+                },
+                sender,
+                stats,
+            });
+        }
+        Ok(Self { sinks })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sinks.is_empty()
+    }
+
+    /// 把事件放入匹配 sink 的队列，从不等待。
+    pub fn dispatch(&self, record: &EventLogRecord) {
+        for sink in &self.sinks {
+            if !sink.filter.matches(record) {
+                continue;
+            }
+            if sink.sender.try_send(record.clone()).is_err() {
+                sink.stats.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    pub fn health(&self) -> Vec<EventSinkHealth> {
+        self.sinks
+            .iter()
+            .map(|sink| {
+                let stats = &sink.stats;
+                let nonzero = |value: i64| (value != 0).then_some(value);
+                EventSinkHealth {
+                    name: sink.name.clone(),
+                    sink_type: sink.sink_type.as_str(),
+                    target: sink.target.clone(),
+                    healthy: stats.consecutive_failures.load(Ordering::Relaxed) == 0,
+                    queued: sink
+                        .sender
+                        .max_capacity()
+                        .saturating_sub(sink.sender.capacity()) as u64,
+                    delivered: stats.delivered.load(Ordering::Relaxed),
+                    failed: stats.failed.load(Ordering::Relaxed),
+                    dropped: stats.dropped.load(Ordering::Relaxed),
+                    last_error: stats.last_error.lock().ok().and_then(|error| error.clone()),
+                    last_delivered_at: nonzero(stats.last_delivered_at.load(Ordering::Relaxed)),
+                    last_failed_at: nonzero(stats.last_failed_at.load(Ordering::Relaxed)),
+                }
+            })
+            .collect()
+    }
+}
+
+async fn run_sink(
+    name: String,
+    mut backend: SinkBackend,
+    mut receiver: mpsc::Receiver<EventLogRecord>,
+    stats: Arc<EventSinkStats>,
+) {
+    while let Some(record) = receiver.recv().await {
+        match backend.deliver(&record).await {
+            Ok(()) => {
+                if stats.consecutive_failures.load(Ordering::Relaxed) > 0 {
+                    log::info!("Event sink {} recovered", name);
+                }
+                stats.record_success();
+            }
+            Err(err) => {
+                let error = format!("{err:#}");
+                if stats.record_failure(error.clone()) {
+                    log::warn!("Event sink {} failed to deliver event: {}", name, error);
+                }
+            }
+        }
+    }
+}
+
+/// 展示用的投递目标；webhook 只保留 scheme、host 和 path，避免泄露 URL 中的凭据。
+fn sink_target(config: &EventSinkConfig) -> String {
+    match config.sink_type {
+        EventSinkType::Webhook => reqwest::Url::parse(&config.url)
+            .map(|url| {
+                format!(
+                    "{}://{}{}",
+                    url.scheme(),
+                    url.host_str().unwrap_or_default(),
+                    url.port()
+                        .map(|port| format!(":{port}"))
+                        .unwrap_or_default()
+                ) + url.path()
+            })
+            .unwrap_or_else(|_| "<invalid url>".to_string()),
+        EventSinkType::Syslog => config.address.clone(),
+        EventSinkType::File => config.path.clone(),
+    }
+}
+
+fn now_nanos() -> i64 {
+    chrono::Utc::now().timestamp_nanos_opt().unwrap_or(i64::MAX)
+}
+
+/// webhook 和文件 sink 输出的单条事件。
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SinkEvent<'a> {
+    seq: i64,
+    time: String,
+    hostname: &'a str,
+    source: &'a str,
+    kind: &'a str,
+    subject_kind: &'a str,
+    subject_id: &'a str,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    pod_sandbox_id: &'a str,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    namespace: &'a str,
+    severity: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    details: Option<serde_json::Value>,
+}
+
+impl<'a> SinkEvent<'a> {
+    fn new(record: &'a EventLogRecord, hostname: &'a str) -> Self {
+        Self {
+            seq: record.seq,
+            time: record_time(record),
+            hostname,
+            source: &record.source,
+            kind: &record.kind,
+            subject_kind: &record.subject_kind,
+            subject_id: &record.subject_id,
+            pod_sandbox_id: &record.pod_id,
+            namespace: &record.namespace,
+            severity: &record.severity,
+            details: record
+                .details
+                .as_deref()
+                .and_then(|details| serde_json::from_str(details).ok()),
+        }
+    }
+
+    fn to_json(&self) -> anyhow::Result<Vec<u8>> {
+        Ok(serde_json::to_vec(self)?)
+    }
+}
+
+fn record_time(record: &EventLogRecord) -> String {
+    chrono::DateTime::from_timestamp_nanos(record.timestamp_nanos)
+        .to_rfc3339_opts(SecondsFormat::Nanos, true)
+}
+
+enum SinkBackend {
+    Webhook(WebhookSink),
+    Syslog(SyslogSink),
+    File(FileSink),
+}
+
+impl SinkBackend {
+    fn new(config: &EventSinkConfig, hostname: String) -> anyhow::Result<Self> {
+        Ok(match config.sink_type {
+            EventSinkType::Webhook => Self::Webhook(WebhookSink::new(config, hostname)?),
+            EventSinkType::Syslog => Self::Syslog(SyslogSink::new(config, hostname)?),
+            EventSinkType::File => Self::File(FileSink::new(config, hostname)),
+        })
+    }
+
+    async fn deliver(&mut self, record: &EventLogRecord) -> anyhow::Result<()> {
+        match self {
+            Self::Webhook(sink) => sink.deliver(record).await,
+            Self::Syslog(sink) => sink.deliver(record).await,
+            Self::File(sink) => sink.deliver(record),
+        }
+    }
+}
+
+/// 逐条 POST JSON；网络错误、5xx 和 429 按指数退避重试，其他非 2xx 直接失败。
+struct WebhookSink {
+    client: reqwest::Client,
+    url: String,
+    headers: reqwest::header::HeaderMap,
+    max_retries: u32,
+    retry_backoff: Duration,
+    hostname: String,
+}
+
+impl WebhookSink {
+    fn new(config: &EventSinkConfig, hostname: String) -> anyhow::Result<Self> {
+        let mut headers = reqwest::header::HeaderMap::new();
+        for (name, value) in &config.headers {
+            headers.insert(
+                reqwest::header::HeaderName::from_bytes(name.as_bytes())
+                    .with_context(|| format!("invalid header name {name:?}"))?,
+                reqwest::header::HeaderValue::from_str(value)
+                    .with_context(|| format!("invalid value for header {name}"))?,
+            );
+        }
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(config.timeout_ms.max(1)))
+            .build()?;
+        Ok(Self {
+            client,
+            url: config.url.clone(),
+            headers,
+            max_retries: config.max_retries,
+            retry_backoff: Duration::from_millis(config.retry_backoff_ms),
+            hostname,
+        })
+    }
+
+    async fn deliver(&self, record: &EventLogRecord) -> anyhow::Result<()> {
+        let body = SinkEvent::new(record, &self.hostname).to_json()?;
+        let mut backoff = self.retry_backoff;
+        let mut attempt = 0;
+        loop {
+            let result = self
+                .client
+                .post(&self.url)
+                .headers(self.headers.clone())
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body.clone())
+                .send()
+                .await;
+            let error = match result {
+                Ok(response) if response.status().is_success() => return Ok(()),
+                Ok(response)
+                    if response.status().is_server_error()
+                        || response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS =>
+                {
+                    anyhow::anyhow!("webhook returned HTTP {}", response.status())
+                }
+                Ok(response) => anyhow::bail!("webhook returned HTTP {}", response.status()),
+                Err(err) => anyhow::Error::new(err).context("webhook request failed"),
+            };
+            if attempt >= self.max_retries {
+                return Err(error.context(format!("giving up after {} attempts", attempt + 1)));
+            }
+            attempt += 1;
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(MAX_WEBHOOK_BACKOFF);
+        }
+    }
+}
+
+enum SyslogConnection {
+    Unix(tokio::net::UnixDatagram),
+    Udp(tokio::net::UdpSocket),
+    Tcp(tokio::net::TcpStream),
+}
+
+/// RFC 5424 syslog；TCP 使用 RFC 6587 octet-counting 分帧，发送失败后下次投递时重连。
+/// 没有注册的 PEN 可用作 SD-ID，结构化数据留空，事件字段全部在 MSG 的 JSON 里。
+struct SyslogSink {
+    address: SyslogAddress,
+    facility: u8,
+    app_name: String,
+    hostname: String,
+    connection: Option<SyslogConnection>,
+}
+
+impl SyslogSink {
+    fn new(config: &EventSinkConfig, hostname: String) -> anyhow::Result<Self> {
+        let facility = crate::config::syslog_facility_code(&config.facility)
+            .ok_or_else(|| anyhow::anyhow!("unknown syslog facility {:?}", config.facility))?;
+        Ok(Self {
+            address: SyslogAddress::parse(&config.address)?,
+            facility,
+            app_name: syslog_field(&config.app_name, SYSLOG_APP_NAME_MAX_LEN),
+            hostname,
+            connection: None,
+        })
+    }
+
+    fn severity_code(severity: &str) -> u8 {
+        match severity {
+            "error" => 3,
+            "warning" => 4,
+            "debug" => 7,
+            _ => 6,
+        }
+    }
+
+    fn encode(&self, record: &EventLogRecord) -> anyhow::Result<Vec<u8>> {
+        let mut message = format!(
+            "<{}>1 {} {} {} {} {} - ",
+            u16::from(self.facility) * 8 + u16::from(Self::severity_code(&record.severity)),
+            record_time(record),
+            self.hostname,
+            self.app_name,
+            std::process::id(),
+            syslog_field(&record.kind, SYSLOG_MSG_ID_MAX_LEN),
+        )
+        .into_bytes();
+        message.extend_from_slice(&SinkEvent::new(record, &self.hostname).to_json()?);
+        Ok(message)
+    }
+
+    async fn connect(&mut self) -> std::io::Result<&mut SyslogConnection> {
+        if self.connection.is_none() {
+            let connection = match &self.address {
+                SyslogAddress::Unix(_) => {
+                    SyslogConnection::Unix(tokio::net::UnixDatagram::unbound()?)
+                }
+                SyslogAddress::Udp(_) => {
+                    SyslogConnection::Udp(tokio::net::UdpSocket::bind("0.0.0.0:0").await?)
+                }
+                SyslogAddress::Tcp(address) => {
+                    let stream = tokio::time::timeout(
+                        SYSLOG_CONNECT_TIMEOUT,
+                        tokio::net::TcpStream::connect(address.as_str()),
+                    )
+                    .await
+                    .map_err(|_| {
+                        std::io::Error::new(
+                            std::io::ErrorKind::TimedOut,
+                            format!("connecting to syslog {} timed out", address),
+                        )
+                    })??;
+                    SyslogConnection::Tcp(stream)
+                }
+            };
+            self.connection = Some(connection);
+        }
+        Ok(self
+            .connection
+            .as_mut()
+            .expect("syslog connection was just created"))
+    }
+
+    async fn send(&mut self, message: &[u8]) -> std::io::Result<()> {
+        let address = self.address.clone();
+        match self.connect().await? {
+            SyslogConnection::Unix(socket) => {
+                let SyslogAddress::Unix(path) = &address else {
+                    unreachable!("unix syslog connection without unix address");
+                };
+                socket.send_to(message, path).await.map(|_| ())
+            }
+            SyslogConnection::Udp(socket) => {
+                let SyslogAddress::Udp(target) = &address else {
+                    unreachable!("udp syslog connection without udp address");
+                };
+                socket.send_to(message, target.as_str()).await.map(|_| ())
+            }
+            SyslogConnection::Tcp(stream) => stream.write_all(&syslog_octet_frame(message)).await,
+        }
+    }
+
+    async fn deliver(&mut self, record: &EventLogRecord) -> anyhow::Result<()> {
+        let message = self.encode(record)?;
+        if let Err(err) = self.send(&message).await {
+            self.connection = None;
+            return Err(anyhow::Error::new(err).context("syslog send failed"));
+        }
+        Ok(())
+    }
+}
+
+/// JSON 行文件；超过大小阈值时把 `<path>.N` 依次后移，最多保留 `max_files` 个文件（含当前文件）。
+struct FileSink {
+    path: PathBuf,
+    file: Option<std::fs::File>,
+    size: u64,
+    max_size: u64,
+    max_files: usize,
+    hostname: String,
+}
+
+impl FileSink {
+    fn new(config: &EventSinkConfig, hostname: String) -> Self {
+        Self {
+            path: PathBuf::from(&config.path),
+            file: None,
+            size: 0,
+            max_size: config.max_size_bytes,
+            max_files: config.max_files.max(1),
+            hostname,
+        }
+    }
+
+    fn open(&mut self) -> anyhow::Result<&mut std::fs::File> {
+        if self.file.is_none() {
+            if let Some(parent) = self.path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)
+                .with_context(|| format!("failed to open {}", self.path.display()))?;
+            self.size = file.metadata()?.len();
+            self.file = Some(file);
+        }
+        Ok(self.file.as_mut().expect("event sink file was just opened"))
+    }
+
+    fn rotate(&mut self) -> anyhow::Result<()> {
+        self.file = None;
+        rotate_numbered(&self.path, self.max_files)
+            .with_context(|| format!("failed to rotate {}", self.path.display()))
+    }
+
+    fn deliver(&mut self, record: &EventLogRecord) -> anyhow::Result<()> {
+        let mut line = SinkEvent::new(record, &self.hostname).to_json()?;
+        line.push(b'\n');
+        self.open()?;
+        if self.size > 0 && self.size + line.len() as u64 > self.max_size {
+            self.rotate()?;
+        }
+        let file = self.open()?;
+        let result = file.write_all(&line).and_then(|_| file.flush());
+        if let Err(err) = result {
+            // 文件被外部删除或磁盘错误时下次投递重新打开。
+            self.file = None;
+            return Err(anyhow::Error::new(err).context("failed to write event sink file"));
+        }
+        self.size += line.len() as u64;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hyper::service::{make_service_fn, service_fn};
+
+    fn record(seq: i64, kind: &str, severity: &str) -> EventLogRecord {
+        EventLogRecord {
+            seq,
+            timestamp_nanos: 1_700_000_000_000_000_000,
+            source: "internal".to_string(),
+            kind: kind.to_string(),
+            subject_kind: "container".to_string(),
+            subject_id: "ctr-1".to_string(),
+            pod_id: "pod-1".to_string(),
+            namespace: "default".to_string(),
+            severity: severity.to_string(),
+            details: Some(r#"{"reason":"OOMKilled"}"#.to_string()),
+        }
+    }
+
+    fn sink_config(name: &str, sink_type: EventSinkType) -> EventSinkConfig {
+        EventSinkConfig {
+            name: name.to_string(),
+            sink_type,
+            retry_backoff_ms: 10,
+            ..Default::default()
+        }
+    }
+
+    async fn wait_for(set: &EventSinkSet, done: impl Fn(&EventSinkHealth) -> bool) {
+        for _ in 0..200 {
+            if set.health().iter().all(&done) {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("event sinks did not settle: {:?}", set.health());
+    }
+
+    /// 前 `failures` 次请求返回 503，之后返回 200，把成功收到的 body 转发给测试。
+    async fn start_webhook(failures: usize) -> (String, mpsc::UnboundedReceiver<Vec<u8>>) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let remaining = Arc::new(AtomicU64::new(failures as u64));
+        let make_service = make_service_fn(move |_| {
+            let sender = sender.clone();
+            let remaining = remaining.clone();
+            async move {
+                Ok::<_, hyper::Error>(service_fn(move |request: hyper::Request<hyper::Body>| {
+                    let sender = sender.clone();
+                    let remaining = remaining.clone();
+                    async move {
+                        let body = hyper::body::to_bytes(request.into_body()).await?;
+                        let failing = remaining
+                            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |left| {
+                                left.checked_sub(1)
+                            })
+                            .is_ok();
+                        let mut response = hyper::Response::new(hyper::Body::empty());
+                        if failing {
+                            *response.status_mut() = hyper::StatusCode::SERVICE_UNAVAILABLE;
+                        } else {
+                            let _ = sender.send(body.to_vec());
+                        }
+                        Ok::<_, hyper::Error>(response)
+                    }
+                }))
+            }
+        });
+        let server = hyper::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(make_service);
+        let endpoint = format!("http://{}/events", server.local_addr());
+        tokio::spawn(server);
+        (endpoint, receiver)
+    }
+
+    #[tokio::test]
+    async fn webhook_sink_retries_with_backoff_and_filters_events() {
+        let (url, mut received) = start_webhook(2).await;
+        let mut config = sink_config("siem", EventSinkType::Webhook);
+        config.url = url;
+        config.max_retries = 3;
+        config.min_severity = "warning".to_string();
+        config.kinds = vec!["container.*".to_string()];
+        let set = EventSinkSet::start(&[config]).unwrap();
+
+        set.dispatch(&record(1, "container.started", "info"));
+        set.dispatch(&record(2, "image.pulled", "error"));
+        set.dispatch(&record(3, "container.oom", "warning"));
+
+        let body = tokio::time::timeout(Duration::from_secs(5), received.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        let event: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(event["seq"], 3);
+        assert_eq!(event["kind"], "container.oom");
+        assert_eq!(event["podSandboxId"], "pod-1");
+        assert_eq!(event["details"]["reason"], "OOMKilled");
+        wait_for(&set, |health| health.delivered == 1).await;
+        let health = &set.health()[0];
+        assert!(health.healthy);
+        assert_eq!((health.failed, health.dropped), (0, 0));
+        assert!(received.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn webhook_sink_reports_failures_without_blocking_dispatch() {
+        let (url, _received) = start_webhook(usize::MAX).await;
+        let mut config = sink_config("down", EventSinkType::Webhook);
+        config.url = format!("{url}?token=secret");
+        config.max_retries = 1;
+        config.queue_size = 1;
+        let set = EventSinkSet::start(&[config]).unwrap();
+
+        for seq in 1..=5 {
+            set.dispatch(&record(seq, "container.oom", "warning"));
+        }
+        wait_for(&set, |health| health.failed >= 1).await;
+        let health = &set.health()[0];
+        assert!(!health.healthy);
+        assert!(health.dropped >= 1, "{health:?}");
+        assert!(health
+            .last_error
+            .as_deref()
+            .is_some_and(|error| error.contains("503")));
+        assert_eq!(health.target, url);
+    }
+
+    #[tokio::test]
+    async fn syslog_sink_sends_rfc5424_messages() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket_path = dir.path().join("syslog.sock");
+        let listener = tokio::net::UnixDatagram::bind(&socket_path).unwrap();
+        let mut config = sink_config("syslog", EventSinkType::Syslog);
+        config.address = format!("unix://{}", socket_path.display());
+        config.facility = "local0".to_string();
+        let set = EventSinkSet::start(&[config]).unwrap();
+
+        set.dispatch(&record(7, "container.oom", "warning"));
+
+        let mut buf = vec![0; 4096];
+        let len = tokio::time::timeout(Duration::from_secs(5), listener.recv(&mut buf))
+            .await
+            .unwrap()
+            .unwrap();
+        let message = String::from_utf8_lossy(&buf[..len]).into_owned();
+        // local0(16) * 8 + warning(4)
+        assert!(
+            message.starts_with("<132>1 2023-11-14T22:13:20"),
+            "{message}"
+        );
+        let (header, json) = message.split_once(" - {").unwrap();
+        assert!(header.ends_with(" container.oom"), "{message}");
+        let event: serde_json::Value = serde_json::from_str(&format!("{{{json}")).unwrap();
+        assert_eq!(event["seq"], 7);
+        assert_eq!(event["podSandboxId"], "pod-1");
+        assert_eq!(event["namespace"], "default");
+    }
+
+    #[tokio::test]
+    async fn file_sink_writes_json_lines_and_rotates() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events").join("events.jsonl");
+        let mut config = sink_config("file", EventSinkType::File);
+        config.path = path.display().to_string();
+        config.max_size_bytes = 1;
+        config.max_files = 2;
+        let set = EventSinkSet::start(&[config]).unwrap();
+
+        for seq in 1..=3 {
+            set.dispatch(&record(seq, "container.oom", "warning"));
+        }
+        wait_for(&set, |health| health.delivered == 3).await;
+
+        let seq_of = |path: &std::path::Path| -> i64 {
+            let content = std::fs::read_to_string(path).unwrap();
+            let lines = content.lines().collect::<Vec<_>>();
+            assert_eq!(lines.len(), 1, "{content}");
+            serde_json::from_str::<serde_json::Value>(lines[0]).unwrap()["seq"]
+                .as_i64()
+                .unwrap()
+        };
+        assert_eq!(seq_of(&path), 3);
+        assert_eq!(seq_of(&dir.path().join("events").join("events.jsonl.1")), 2);
+        assert!(!dir.path().join("events").join("events.jsonl.2").exists());
+    }
+}
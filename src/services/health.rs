@@ -4,6 +4,7 @@ use serde::Serialize;
 
 use crate::image::{PullCgroupEffectiveConfig, PullCgroupMode, PullCgroupScopeRecord};
 use crate::network::CniLoadStatus;
+use crate::nri::NriPluginStatus;
 use crate::proto::runtime::v1::RuntimeCondition;
 use crate::security::HostCapabilityReport;
 use crate::state::LedgerCheckReport;
@@ -100,6 +101,16 @@ impl InternalHealthCondition {
 }
 
 impl HealthCondition {
+    /// 以给定类型转换为内部健康条件，便于与扩展条件一起导出。
+    pub fn internal(&self, condition_type: &str) -> InternalHealthCondition {
+        InternalHealthCondition {
+            condition_type: condition_type.to_string(),
+            ready: self.ready,
+            reason: self.reason.clone(),
+            message: self.message.clone(),
+        }
+    }
+
     pub fn runtime_condition(&self) -> RuntimeCondition {
         RuntimeCondition {
             r#type: "RuntimeReady".to_string(),
@@ -445,6 +456,97 @@ impl HealthService {
         }
     }
 
+    pub fn nri_condition(
+        &self,
+        enabled: bool,
+        plugins: &[NriPluginStatus],
+    ) -> InternalHealthCondition {
+        if !enabled {
+            return InternalHealthCondition::ready(
+                "NriReady",
+                "NriDisabled",
+                "NRI is disabled".to_string(),
+            );
+        }
+
+        let disconnected = plugins
+            .iter()
+            .filter(|plugin| !plugin.connected)
+            .map(|plugin| format!("{}-{}", plugin.index, plugin.name))
+            .collect::<Vec<_>>();
+        if !disconnected.is_empty() {
+            return InternalHealthCondition::not_ready(
+                "NriReady",
+                "NriPluginDisconnected",
+                format!(
+                    "{} NRI plugin(s) are not connected: {}",
+                    disconnected.len(),
+                    disconnected.join(", ")
+                ),
+            );
+        }
+
+        if plugins.is_empty() {
+            return InternalHealthCondition::ready(
+                "NriReady",
+                "NriNoPlugins",
+                "NRI is enabled and no plugin is connected".to_string(),
+            );
+        }
+
+        InternalHealthCondition::ready(
+            "NriReady",
+            "NriPluginsConnected",
+            format!("{} NRI plugin(s) are connected", plugins.len()),
+        )
+    }
+
+    pub fn cni_load_condition(&self, cni_status: &CniLoadStatus) -> InternalHealthCondition {
+        let (ready, reason, message) = cni_status.condition();
+        InternalHealthCondition {
+            condition_type: "CniLoaded".to_string(),
+            ready,
+            reason,
+            message,
+        }
+    }
+
+    pub fn config_reload_condition(
+        &self,
+        last_reload_at_unix_millis: Option<i64>,
+        reload_error: Option<&str>,
+        watcher_last_error: Option<&str>,
+    ) -> InternalHealthCondition {
+        if let Some(error) = reload_error {
+            return InternalHealthCondition::not_ready(
+                "ConfigReloadReady",
+                "ConfigReloadFailed",
+                error.to_string(),
+            );
+        }
+
+        if let Some(error) = watcher_last_error {
+            return InternalHealthCondition::not_ready(
+                "ConfigReloadReady",
+                "ConfigWatcherFailed",
+                error.to_string(),
+            );
+        }
+
+        match last_reload_at_unix_millis {
+            Some(at) => InternalHealthCondition::ready(
+                "ConfigReloadReady",
+                "ConfigReloadSucceeded",
+                format!("last configuration reload succeeded at unix millis {at}"),
+            ),
+            None => InternalHealthCondition::ready(
+                "ConfigReloadReady",
+                "ConfigReloadNotAttempted",
+                "configuration has not been reloaded since startup".to_string(),
+            ),
+        }
+    }
+
     pub fn extended_conditions(
         &self,
         input: ExtendedConditionsInput<'_>,
@@ -814,4 +916,69 @@ mod tests {
         assert_eq!(condition.reason, "PullCgroupScopeFailed");
         assert_eq!(condition.message, "failed to create pull cgroup");
     }
+
+    #[test]
+    fn nri_condition_reports_disconnected_plugins() {
+        let service = HealthService;
+        let connected = NriPluginStatus {
+            name: "topology".to_string(),
+            index: "10".to_string(),
+            registered: true,
+            connected: true,
+            synchronized: true,
+        };
+        let pending = NriPluginStatus {
+            name: "logger".to_string(),
+            index: "20".to_string(),
+            registered: true,
+            connected: false,
+            synchronized: false,
+        };
+
+        assert_eq!(service.nri_condition(false, &[]).reason, "NriDisabled");
+        assert_eq!(service.nri_condition(true, &[]).reason, "NriNoPlugins");
+        let ready = service.nri_condition(true, std::slice::from_ref(&connected));
+        assert!(ready.ready);
+        assert_eq!(ready.reason, "NriPluginsConnected");
+
+        let condition = service.nri_condition(true, &[connected, pending]);
+        assert_eq!(condition.condition_type, "NriReady");
+        assert!(!condition.ready);
+        assert_eq!(condition.reason, "NriPluginDisconnected");
+        assert!(condition.message.contains("20-logger"));
+    }
+
+    #[test]
+    fn config_reload_condition_prefers_reload_error() {
+        let service = HealthService;
+
+        let failed =
+            service.config_reload_condition(Some(5), Some("invalid config"), Some("watch failed"));
+        assert!(!failed.ready);
+        assert_eq!(failed.reason, "ConfigReloadFailed");
+        assert_eq!(failed.message, "invalid config");
+
+        let watcher = service.config_reload_condition(None, None, Some("inotify limit reached"));
+        assert!(!watcher.ready);
+        assert_eq!(watcher.reason, "ConfigWatcherFailed");
+
+        assert_eq!(
+            service.config_reload_condition(Some(5), None, None).reason,
+            "ConfigReloadSucceeded"
+        );
+        assert_eq!(
+            service.config_reload_condition(None, None, None).reason,
+            "ConfigReloadNotAttempted"
+        );
+    }
+
+    #[test]
+    fn cni_load_condition_mirrors_load_status() {
+        let service = HealthService;
+        let condition = service.cni_load_condition(&ready_cni_status());
+
+        assert_eq!(condition.condition_type, "CniLoaded");
+        assert!(condition.ready);
+        assert_eq!(condition.reason, "CNINetworkConfigReady");
+    }
 }
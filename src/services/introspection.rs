@@ -619,6 +619,9 @@ impl IntrospectionService {
             "lastReloadError": reload_state.last_reload_error,
             "lastCniWatchAtUnixMillis": reload_state.last_cni_watch_at_unix_millis,
             "lastCniWatchError": reload_state.last_cni_watch_error,
+            "cniLastKnownGood": reload_state.cni_last_known_good,
+            "lastCniRejectedAtUnixMillis": reload_state.last_cni_rejected_at_unix_millis,
+            "lastCniRejectedError": reload_state.last_cni_rejected_error,
             "runtimeConfigApiOnly": [
                 "UpdateRuntimeConfig.network_config.pod_cidr"
             ],
@@ -1,8 +1,21 @@
-use tonic::{Request, Response, Status};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Request, Response, Status, Streaming};
 
 use crate::proto::local::v1::{
-    local_service_server::LocalService, CreateLocalContainerRequest, CreateLocalContainerResponse,
+    local_service_server::LocalService, CopyArchiveChunk, CopyFromContainerRequest,
+    CopyToContainerRequest, CopyToContainerResponse, CreateDebugContainerRequest,
+    CreateDebugContainerResponse, CreateLocalContainerRequest, CreateLocalContainerResponse,
+    ExecOptions, LocalExecRequest, LocalExecSyncRequest,
 };
+use crate::proto::runtime::v1::{ExecResponse, ExecSyncResponse};
+use crate::runtime::container_copy::{
+    ChunkReader, ChunkWriter, ContainerCopyError, CopyResult, CopyRoot,
+};
+use crate::security::spec_patch::{ExecOverrides, ExecUser};
+
+const COPY_CHUNK_BYTES: usize = 64 * 1024;
+const COPY_CHANNEL_CAPACITY: usize = 16;
 
 #[derive(Clone)]
 pub struct LocalServiceImpl {
@@ -13,14 +26,203 @@ impl LocalServiceImpl {
     pub fn new(runtime: crate::server::RuntimeServiceImpl) -> Self {
         Self { runtime }
     }
+
+    async fn open_copy_root(&self, container_id: &str, path: &str) -> CopyResult<CopyRoot> {
+        if container_id.trim().is_empty() {
+            return Err(ContainerCopyError::InvalidArgument(
+                "container_id must not be empty".to_string(),
+            ));
+        }
+        if path.trim().is_empty() {
+            return Err(ContainerCopyError::InvalidArgument(
+                "path must not be empty".to_string(),
+            ));
+        }
+        let (_, pid) = self.runtime.container_copy_target(container_id).await?;
+        tokio::task::spawn_blocking(move || CopyRoot::for_container_pid(pid))
+            .await
+            .map_err(std::io::Error::other)?
+    }
+}
+
+fn copy_error_status(err: ContainerCopyError) -> Status {
+    match err {
+        ContainerCopyError::NotFound(message) => Status::not_found(message),
+        ContainerCopyError::InvalidArgument(message) => Status::invalid_argument(message),
+        ContainerCopyError::FailedPrecondition(message) => Status::failed_precondition(message),
+        err @ ContainerCopyError::Archive(_) => Status::invalid_argument(err.to_string()),
+        ContainerCopyError::Io(err) => match err.kind() {
+            std::io::ErrorKind::NotFound => Status::not_found(err.to_string()),
+            std::io::ErrorKind::PermissionDenied => Status::permission_denied(err.to_string()),
+            _ => Status::internal(err.to_string()),
+        },
+    }
+}
+
+fn exec_overrides(options: Option<ExecOptions>) -> Result<ExecOverrides, String> {
+    let Some(options) = options else {
+        return Ok(ExecOverrides::default());
+    };
+    let user = options
+        .user
+        .map(|user| {
+            Ok::<_, String>(ExecUser {
+                uid: exec_id("uid", user.uid)?,
+                gid: user.gid.map(|gid| exec_id("gid", gid.value)).transpose()?,
+            })
+        })
+        .transpose()?;
+    Ok(ExecOverrides {
+        env: options.env,
+        cwd: (!options.workdir.is_empty()).then_some(options.workdir),
+        user,
+        privileged: options.privileged,
+        add_capabilities: options.add_capabilities,
+    })
+}
+
+fn exec_id(kind: &str, value: i64) -> Result<u32, String> {
+    u32::try_from(value).map_err(|_| format!("exec {kind} {value} is out of range"))
 }
 
 #[tonic::async_trait]
 impl LocalService for LocalServiceImpl {
+    type CopyFromContainerStream = ReceiverStream<Result<CopyArchiveChunk, Status>>;
+
     async fn create_local_container(
         &self,
         request: Request<CreateLocalContainerRequest>,
     ) -> Result<Response<CreateLocalContainerResponse>, Status> {
         self.runtime.create_local_container_impl(request).await
     }
+
+    async fn copy_from_container(
+        &self,
+        request: Request<CopyFromContainerRequest>,
+    ) -> Result<Response<Self::CopyFromContainerStream>, Status> {
+        let req = request.into_inner();
+        let root = self
+            .open_copy_root(&req.container_id, &req.path)
+            .await
+            .map_err(copy_error_status)?;
+        let (tx, rx) = mpsc::channel(COPY_CHANNEL_CAPACITY);
+        tokio::task::spawn_blocking(move || {
+            let chunks = tx.clone();
+            let writer = ChunkWriter::new(COPY_CHUNK_BYTES, move |data| {
+                chunks
+                    .blocking_send(Ok(CopyArchiveChunk { data }))
+                    .map_err(|_| std::io::Error::from(std::io::ErrorKind::BrokenPipe))
+            });
+            match root.archive(&req.path, writer) {
+                Ok(summary) => log::info!(
+                    "Copied {} entries ({} bytes) from container {}:{}",
+                    summary.entries,
+                    summary.bytes,
+                    req.container_id,
+                    req.path
+                ),
+                Err(err) => {
+                    log::warn!(
+                        "Failed to copy {} from container {}: {}",
+                        req.path,
+                        req.container_id,
+                        err
+                    );
+                    let _ = tx.blocking_send(Err(copy_error_status(err)));
+                }
+            }
+        });
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    async fn copy_to_container(
+        &self,
+        request: Request<Streaming<CopyToContainerRequest>>,
+    ) -> Result<Response<CopyToContainerResponse>, Status> {
+        let mut stream = request.into_inner();
+        let first = stream
+            .message()
+            .await?
+            .ok_or_else(|| Status::invalid_argument("copy stream ended before the header"))?;
+        let root = self
+            .open_copy_root(&first.container_id, &first.path)
+            .await
+            .map_err(copy_error_status)?;
+        let (tx, mut rx) = mpsc::channel::<std::io::Result<Vec<u8>>>(COPY_CHANNEL_CAPACITY);
+        let path = first.path.clone();
+        let extract = tokio::task::spawn_blocking(move || {
+            root.extract(&path, ChunkReader::new(move || rx.blocking_recv()))
+        });
+
+        let mut pending = Some(first.data);
+        loop {
+            let data = match pending.take() {
+                Some(data) => data,
+                None => match stream.message().await {
+                    Ok(Some(message)) => message.data,
+                    Ok(None) => break,
+                    Err(status) => {
+                        let _ = tx
+                            .send(Err(std::io::Error::other(status.message().to_string())))
+                            .await;
+                        break;
+                    }
+                },
+            };
+            // 解包任务提前结束（通常是出错）时不再转发，结果以任务返回值为准。
+            if !data.is_empty() && tx.send(Ok(data)).await.is_err() {
+                break;
+            }
+        }
+        drop(tx);
+
+        let summary = extract
+            .await
+            .map_err(|err| Status::internal(format!("copy task failed: {}", err)))?
+            .map_err(copy_error_status)?;
+        log::info!(
+            "Copied {} entries ({} bytes) into container {}:{}",
+            summary.entries,
+            summary.bytes,
+            first.container_id,
+            first.path
+        );
+        Ok(Response::new(CopyToContainerResponse {
+            entries: summary.entries,
+            bytes: summary.bytes,
+        }))
+    }
+
+    async fn exec(
+        &self,
+        request: Request<LocalExecRequest>,
+    ) -> Result<Response<ExecResponse>, Status> {
+        let req = request.into_inner();
+        let overrides = exec_overrides(req.options).map_err(Status::invalid_argument)?;
+        let exec = req
+            .request
+            .ok_or_else(|| Status::invalid_argument("exec request must be set"))?;
+        self.runtime.exec_with_overrides(exec, &overrides).await
+    }
+
+    async fn exec_sync(
+        &self,
+        request: Request<LocalExecSyncRequest>,
+    ) -> Result<Response<ExecSyncResponse>, Status> {
+        let req = request.into_inner();
+        let overrides = exec_overrides(req.options).map_err(Status::invalid_argument)?;
+        let exec = req
+            .request
+            .ok_or_else(|| Status::invalid_argument("exec_sync request must be set"))?;
+        self.runtime
+            .exec_sync_with_overrides(exec, &overrides)
+            .await
+    }
+
+    async fn create_debug_container(
+        &self,
+        request: Request<CreateDebugContainerRequest>,
+    ) -> Result<Response<CreateDebugContainerResponse>, Status> {
+        self.runtime.create_debug_container_impl(request).await
+    }
 }
@@ -1,11 +1,17 @@
+mod container_log;
 pub mod diagnostics;
 pub mod event;
+pub mod event_sink;
 pub mod health;
 pub mod introspection;
 pub mod local;
 
 pub use diagnostics::{DiagnosticsServiceImpl, DiagnosticsState};
-pub use event::{EventService, InternalEvent, InternalEventSeverity, LedgerInternalEventSink};
+pub use event::{
+    ContainerEventResync, CriSubscriberStats, EventLogFilter, EventLogWatch, EventService,
+    InternalEvent, InternalEventSeverity, LedgerInternalEventSink,
+};
+pub use event_sink::{EventSinkHealth, EventSinkSet};
 pub use health::{
     HealthCondition, HealthService, InternalHealthCondition, RecoveryLedgerHealthSummary,
 };
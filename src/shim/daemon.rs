@@ -30,6 +30,7 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
+use super::io::log_driver::{select_log_drivers, LogDriverSpec, LogSource, LOG_DRIVER_ANNOTATION};
 use super::io::{IoConfig, IoManager, JournalConfig, DEFAULT_JOURNALD_SOCKET_PATH};
 use crate::image::snapshotter::{RootfsHandle, RootfsHandleKind, RootfsMountSpec};
 use crate::runtime::RuncRuntime;
@@ -46,6 +47,10 @@ use crate::shim_rpc::{
 use crate::storage::StorageManager;
 
 const INTERNAL_CONTAINER_STATE_KEY: &str = "io.crius.internal/container-state";
+/// 设为 `false`/`disabled` 时不为该容器保留 attach 回放输出。
+const ATTACH_SCROLLBACK_ANNOTATION: &str = "io.crius.attach-scrollback";
+/// 前台 `runc run` 启动后查询容器 PID 的最多轮数（每轮约 100ms）。
+const MEMORY_CGROUP_LOOKUP_ATTEMPTS: u32 = 50;
 
 fn parse_mount_options(options: &[String]) -> Result<(libc::c_ulong, Option<String>)> {
     let mut flags: libc::c_ulong = 0;
@@ -173,8 +178,12 @@ pub struct Daemon {
     io_gid: u32,
     /// CRI 单条日志记录切分阈值（字节）。
     max_container_log_line_size: usize,
+    /// attach 回放缓冲中每个输出流保留的字节数。
+    attach_scrollback_bytes: usize,
     /// 是否额外写 journald。
     log_to_journald: bool,
+    /// runtime handler 配置的日志驱动；为空时使用 CRI 文件。
+    log_drivers: Vec<LogDriverSpec>,
     /// 是否在日志轮转和容器退出时跳过 sync。
     no_sync_log: bool,
     /// 是否禁用 pivot_root，改用 MS_MOVE。
@@ -195,6 +204,8 @@ pub struct Daemon {
     task_state: Arc<Mutex<DaemonTaskState>>,
     /// 最近已知的容器 PID。
     container_pid: Arc<Mutex<Option<i32>>>,
+    /// 容器 init 进程所在的 memory cgroup 目录，退出后、`delete` 前读取 OOM 计数。
+    memory_cgroup: Arc<Mutex<Option<PathBuf>>>,
     /// 最近已知的退出码。
     exit_code: Arc<Mutex<Option<i32>>>,
     /// task 后台线程。
@@ -228,7 +239,9 @@ pub struct DaemonOptions {
     pub io_uid: u32,
     pub io_gid: u32,
     pub max_container_log_line_size: usize,
+    pub attach_scrollback_bytes: usize,
     pub log_to_journald: bool,
+    pub log_drivers: Vec<LogDriverSpec>,
     pub no_sync_log: bool,
     pub no_pivot: bool,
     pub no_new_keyring: bool,
@@ -253,7 +266,9 @@ impl Daemon {
             io_uid,
             io_gid,
             max_container_log_line_size,
+            attach_scrollback_bytes,
             log_to_journald,
+            log_drivers,
             no_sync_log,
             no_pivot,
             no_new_keyring,
@@ -272,7 +287,9 @@ impl Daemon {
             io_uid,
             io_gid,
             max_container_log_line_size,
+            attach_scrollback_bytes,
             log_to_journald,
+            log_drivers,
             no_sync_log,
             no_pivot,
             no_new_keyring,
@@ -281,6 +298,7 @@ impl Daemon {
             running: Arc::new(AtomicBool::new(true)),
             task_state: Arc::new(Mutex::new(DaemonTaskState::Init)),
             container_pid: Arc::new(Mutex::new(None)),
+            memory_cgroup: Arc::new(Mutex::new(None)),
             exit_code: Arc::new(Mutex::new(None)),
             task_thread: Arc::new(Mutex::new(None)),
             exec_sessions: Arc::new(Mutex::new(HashMap::new())),
@@ -765,10 +783,13 @@ impl Daemon {
             stderr: None,
             stdin: None,
             journald: None,
+            log_drivers: Vec::new(),
+            log_source: LogSource::default(),
             no_sync_log: true,
             io_uid: self.io_uid,
             io_gid: self.io_gid,
             max_log_line_size: self.max_container_log_line_size,
+            attach_scrollback_bytes: 0,
         })?;
         io_manager.start_attach_server()?;
         io_manager.start_resize_server()?;
@@ -804,11 +825,19 @@ impl Daemon {
         request: &OpenExecSessionRequest,
         io_manager: &IoManager,
     ) -> Result<()> {
+        let process_file = request
+            .process
+            .as_ref()
+            .map(crate::runtime::RuncRuntime::write_exec_process_spec)
+            .transpose()?;
         let mut command = self.runtime_command();
         command.arg("exec");
         if request.stdin {
             command.arg("-i");
         }
+        if let Some(file) = process_file.as_ref() {
+            command.arg("--process").arg(file.path());
+        }
         command.arg(&request.container_id);
         for arg in &request.command {
             command.arg(arg);
@@ -942,8 +971,17 @@ impl Daemon {
         let slave_stderr = slave;
         let slave_fd = slave_stderr.as_raw_fd();
 
+        let process_file = request
+            .process
+            .as_ref()
+            .map(crate::runtime::RuncRuntime::write_exec_process_spec)
+            .transpose()?;
         let mut command = self.runtime_command();
-        command.arg("exec").arg("-t").arg(&request.container_id);
+        command.arg("exec").arg("-t");
+        if let Some(file) = process_file.as_ref() {
+            command.arg("--process").arg(file.path());
+        }
+        command.arg(&request.container_id);
         for arg in &request.command {
             command.arg(arg);
         }
@@ -1065,6 +1103,10 @@ impl Daemon {
         if !xdg_runtime_dir.as_os_str().is_empty() {
             cmd.env("XDG_RUNTIME_DIR", xdg_runtime_dir);
         }
+        // 把当前 shim RPC 携带的 trace 上下文按 OTel 环境变量约定交给 OCI 运行时及其 hook。
+        if let Some(traceparent) = crate::trace_export::current_traceparent() {
+            cmd.env("TRACEPARENT", traceparent);
+        }
         cmd
     }
 
@@ -1111,6 +1153,32 @@ impl Daemon {
             .and_then(|raw| serde_json::from_str(raw).ok())
     }
 
+    fn attach_scrollback_bytes(&self, config: &ShimBundleConfig) -> usize {
+        let disabled = config
+            .annotations
+            .as_ref()
+            .and_then(|annotations| annotations.get(ATTACH_SCROLLBACK_ANNOTATION))
+            .is_some_and(|value| {
+                matches!(
+                    value.trim().to_ascii_lowercase().as_str(),
+                    "false" | "disabled" | "off"
+                )
+            });
+        if disabled {
+            0
+        } else {
+            self.attach_scrollback_bytes
+        }
+    }
+
+    fn log_drivers(&self, config: &ShimBundleConfig) -> Result<Vec<LogDriverSpec>> {
+        let annotation = config
+            .annotations
+            .as_ref()
+            .and_then(|annotations| annotations.get(LOG_DRIVER_ANNOTATION));
+        select_log_drivers(&self.log_drivers, annotation.map(String::as_str))
+    }
+
     fn is_terminal(&self) -> Result<bool> {
         let bundle_config = self.load_bundle_config()?;
         let container_state = self
@@ -1241,10 +1309,16 @@ impl Daemon {
                 container_name: container_state.metadata_name.clone(),
                 syslog_identifier: "crius-shim".to_string(),
             }),
+            log_drivers: self.log_drivers(&bundle_config)?,
+            log_source: LogSource {
+                container_id: self.container_id.clone(),
+                container_name: container_state.metadata_name.clone(),
+            },
             no_sync_log: self.no_sync_log,
             io_uid: self.io_uid,
             io_gid: self.io_gid,
             max_log_line_size: self.max_container_log_line_size,
+            attach_scrollback_bytes: self.attach_scrollback_bytes(&bundle_config),
         };
         self.io_manager.configure(io_config)?;
         self.io_manager.start_attach_server()?;
@@ -1514,10 +1588,19 @@ impl Daemon {
 
         let shutdown_grace = std::time::Duration::from_secs(5);
         let mut shutdown_deadline: Option<std::time::Instant> = None;
+        // 前台 `runc run` 不直接给出容器 PID，启动后的前几轮通过 `runc state` 查询。
+        let mut cgroup_lookups_left = MEMORY_CGROUP_LOOKUP_ATTEMPTS;
         let status = loop {
             match child.try_wait().context("Failed to poll runc run status")? {
                 Some(status) => break status,
                 None => {
+                    if cgroup_lookups_left > 0 {
+                        cgroup_lookups_left -= 1;
+                        if let Some(pid) = self.runtime_state_pid() {
+                            self.remember_memory_cgroup(pid);
+                            cgroup_lookups_left = 0;
+                        }
+                    }
                     if !self.running.load(Ordering::SeqCst) {
                         let deadline = shutdown_deadline
                             .get_or_insert_with(|| std::time::Instant::now() + shutdown_grace);
@@ -1547,6 +1630,7 @@ impl Daemon {
             let _ = handle.join();
         }
 
+        self.record_oom_kill();
         self.cleanup_container()?;
         self.io_manager.shutdown()?;
         self.cleanup_attach_socket_directory();
@@ -1556,6 +1640,7 @@ impl Daemon {
     /// 监控容器进程
     fn monitor_container(&self, container_pid: Pid) -> Result<i32> {
         info!("Monitoring container process: {}", container_pid);
+        self.remember_memory_cgroup(container_pid.as_raw());
 
         let mut exit_code = 0;
 
@@ -1584,6 +1669,7 @@ impl Daemon {
         }
 
         // 清理容器状态
+        self.record_oom_kill();
         self.cleanup_container()?;
         self.io_manager.shutdown()?;
         self.cleanup_attach_socket_directory();
@@ -1606,6 +1692,59 @@ impl Daemon {
         Ok(())
     }
 
+    /// 通过 `runc state` 取容器 init 进程 PID。
+    fn runtime_state_pid(&self) -> Option<i32> {
+        let output = self
+            .runtime_command_output(&["state", &self.container_id])
+            .ok()
+            .filter(|output| output.status.success())?;
+        let state: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+        state
+            .get("pid")
+            .and_then(|pid| pid.as_i64())
+            .filter(|pid| *pid > 0)
+            .map(|pid| pid as i32)
+    }
+
+    fn remember_memory_cgroup(&self, pid: i32) {
+        let cgroup = fs::read_to_string(format!("/proc/{pid}/cgroup"))
+            .ok()
+            .and_then(|raw| memory_cgroup_dir(&raw));
+        debug!(
+            "Container {} memory cgroup: {:?}",
+            self.container_id, cgroup
+        );
+        *self.memory_cgroup.lock().unwrap() = cgroup;
+    }
+
+    /// 在 `runc delete` 删除 cgroup 之前把 `oom_kill` 计数写到退出码旁的文件，
+    /// daemon 据此判断 137 退出是否真的来自 OOM killer；读不到时删除旧文件。
+    fn record_oom_kill(&self) {
+        let Some(exit_code_file) = &self.exit_code_file else {
+            return;
+        };
+        let path = crate::shim_rpc::oom_kill_file_path(exit_code_file);
+        let oom_kill = self
+            .memory_cgroup
+            .lock()
+            .unwrap()
+            .as_deref()
+            .and_then(read_oom_kill);
+        let result = match oom_kill {
+            Some(oom_kill) => path
+                .parent()
+                .map_or(Ok(()), |parent| fs::create_dir_all(parent))
+                .and_then(|()| fs::write(&path, oom_kill.to_string())),
+            None => match fs::remove_file(&path) {
+                Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err),
+                _ => Ok(()),
+            },
+        };
+        if let Err(err) = result {
+            warn!("Failed to record oom_kill count to {:?}: {}", path, err);
+        }
+    }
+
     /// 记录退出码
     fn record_exit_code(&self, exit_code: i32) -> Result<()> {
         if let Some(path) = &self.exit_code_file {
@@ -1724,11 +1863,19 @@ impl Daemon {
             return Err(anyhow::anyhow!("exec command must not be empty"));
         }
         self.record_exec_event(&format!("start:{:?}", request.command))?;
+        let process_file = request
+            .process
+            .as_ref()
+            .map(crate::runtime::RuncRuntime::write_exec_process_spec)
+            .transpose()?;
         let mut cmd = self.runtime_command();
         cmd.arg("exec");
         if request.tty {
             cmd.arg("-t");
         }
+        if let Some(file) = process_file.as_ref() {
+            cmd.arg("--process").arg(file.path());
+        }
         cmd.arg(&request.container_id);
         for arg in &request.command {
             cmd.arg(arg);
@@ -2045,6 +2192,40 @@ fn wait_exec_reader(
     }
 }
 
+/// 从 `/proc/<pid>/cgroup` 解析 memory cgroup 目录：优先 v1 的 memory 控制器（含混合模式），
+/// 否则取 v2 统一层级。
+fn memory_cgroup_dir(proc_cgroup: &str) -> Option<PathBuf> {
+    let mount_point = Path::new("/sys/fs/cgroup");
+    let entries = proc_cgroup
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, ':');
+            let hierarchy = parts.next()?;
+            let controllers = parts.next()?;
+            let relative = parts.next()?.trim_start_matches('/');
+            Some((hierarchy, controllers, relative))
+        })
+        .collect::<Vec<_>>();
+    if let Some((_, _, relative)) = entries
+        .iter()
+        .find(|(_, controllers, _)| controllers.split(',').any(|c| c == "memory"))
+    {
+        return Some(mount_point.join("memory").join(relative));
+    }
+    entries
+        .iter()
+        .find(|(hierarchy, controllers, _)| *hierarchy == "0" && controllers.is_empty())
+        .map(|(_, _, relative)| mount_point.join(relative))
+}
+
+/// 读取 cgroup v2 `memory.events` 或 v1 `memory.oom_control` 中的 `oom_kill` 计数。
+fn read_oom_kill(dir: &Path) -> Option<u64> {
+    ["memory.events", "memory.oom_control"]
+        .into_iter()
+        .find_map(|file| fs::read_to_string(dir.join(file)).ok())
+        .map(|content| crate::metrics::MemoryEvents::parse(&content).oom_kill)
+}
+
 fn move_pid_to_cgroup(pid: u32, target: &str) -> Result<()> {
     let mount_point = Path::new("/sys/fs/cgroup");
     let relative = target
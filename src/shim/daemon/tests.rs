@@ -39,7 +39,9 @@ fn new_attach_test_daemon(temp_dir: &tempfile::TempDir, container_id: &str) -> D
             io_uid: 0,
             io_gid: 0,
             max_container_log_line_size: 4096,
+            attach_scrollback_bytes: 0,
             log_to_journald: false,
+            log_drivers: Vec::new(),
             no_sync_log: false,
             no_pivot: false,
             no_new_keyring: false,
@@ -138,6 +140,22 @@ fn attach_resize_rejects_non_tty_stream() {
     assert!(err.to_string().contains("is not a TTY stream"));
 }
 
+#[test]
+fn attach_scrollback_annotation_opts_container_out() {
+    let temp_dir = tempdir().unwrap();
+    let mut daemon = new_attach_test_daemon(&temp_dir, "container-1");
+    daemon.attach_scrollback_bytes = 4096;
+
+    let default_config: ShimBundleConfig = serde_json::from_value(json!({})).unwrap();
+    let disabled_config: ShimBundleConfig = serde_json::from_value(json!({
+        "annotations": { ATTACH_SCROLLBACK_ANNOTATION: "Disabled" }
+    }))
+    .unwrap();
+
+    assert_eq!(daemon.attach_scrollback_bytes(&default_config), 4096);
+    assert_eq!(daemon.attach_scrollback_bytes(&disabled_config), 0);
+}
+
 #[test]
 fn attach_resize_for_open_tty_stream_reaches_io_manager() {
     let temp_dir = tempdir().unwrap();
@@ -283,7 +301,9 @@ fn shim_daemon_owns_rootfs_snapshot_lifecycle() {
             io_uid: 0,
             io_gid: 0,
             max_container_log_line_size: 4096,
+            attach_scrollback_bytes: 0,
             log_to_journald: false,
+            log_drivers: Vec::new(),
             no_sync_log: false,
             no_pivot: false,
             no_new_keyring: false,
@@ -388,7 +408,9 @@ fn create_task_mount_failure_marks_snapshot_broken() {
             io_uid: 0,
             io_gid: 0,
             max_container_log_line_size: 4096,
+            attach_scrollback_bytes: 0,
             log_to_journald: false,
+            log_drivers: Vec::new(),
             no_sync_log: false,
             no_pivot: false,
             no_new_keyring: false,
@@ -452,7 +474,9 @@ async fn shim_daemon_records_task_and_exec_internal_events() {
             io_uid: 0,
             io_gid: 0,
             max_container_log_line_size: 4096,
+            attach_scrollback_bytes: 0,
             log_to_journald: false,
+            log_drivers: Vec::new(),
             no_sync_log: false,
             no_pivot: false,
             no_new_keyring: false,
@@ -529,7 +553,9 @@ exit 0
             io_uid: 0,
             io_gid: 0,
             max_container_log_line_size: 4096,
+            attach_scrollback_bytes: 0,
             log_to_journald: false,
+            log_drivers: Vec::new(),
             no_sync_log: false,
             no_pivot: false,
             no_new_keyring: false,
@@ -548,6 +574,7 @@ exit 0
                 stdout: true,
                 stderr: true,
                 exec_cpu_affinity: None,
+                process: None,
             },
             &io_manager,
         )
@@ -568,6 +595,7 @@ exit 0
                 stdout: true,
                 stderr: true,
                 exec_cpu_affinity: None,
+                process: None,
             },
             &io_manager,
         )
@@ -579,6 +607,89 @@ exit 0
     );
 }
 
+#[test]
+fn pipe_exec_session_passes_overridden_process_spec_to_runtime() {
+    let temp_dir = tempdir().unwrap();
+    let bundle_dir = temp_dir.path().join("bundle");
+    fs::create_dir_all(&bundle_dir).unwrap();
+
+    let args_path = temp_dir.path().join("runtime.args");
+    let spec_path = temp_dir.path().join("process.json");
+    let runtime_path = temp_dir.path().join("fake-runtime.sh");
+    fs::write(
+        &runtime_path,
+        format!(
+            r#"#!/bin/sh
+set -eu
+printf '%s\n' "$@" > "{}"
+cp "$3" "{}"
+exit 0
+"#,
+            args_path.display(),
+            spec_path.display()
+        ),
+    )
+    .unwrap();
+    fs::set_permissions(&runtime_path, fs::Permissions::from_mode(0o755)).unwrap();
+
+    let daemon = Daemon::new(
+        "container-1".to_string(),
+        bundle_dir,
+        runtime_path,
+        DaemonOptions {
+            runtime_config_path: PathBuf::new(),
+            monitor_cgroup: String::new(),
+            work_dir: temp_dir.path().join("shim"),
+            state_db_path: None,
+            exit_code_file: None,
+            attach_socket_dir: None,
+            io_uid: 0,
+            io_gid: 0,
+            max_container_log_line_size: 4096,
+            attach_scrollback_bytes: 0,
+            log_to_journald: false,
+            log_drivers: Vec::new(),
+            no_sync_log: false,
+            no_pivot: false,
+            no_new_keyring: false,
+            systemd_cgroup: false,
+        },
+    );
+    let process = serde_json::from_value(json!({
+        "args": ["id"],
+        "env": ["MODE=debug"],
+        "cwd": "/work",
+        "user": {"uid": 1000, "gid": 1000},
+    }))
+    .unwrap();
+
+    daemon
+        .serve_pipe_exec_session(
+            &OpenExecSessionRequest {
+                container_id: "container-1".to_string(),
+                command: vec!["id".to_string()],
+                tty: false,
+                stdin: false,
+                stdout: true,
+                stderr: true,
+                exec_cpu_affinity: None,
+                process: Some(process),
+            },
+            &IoManager::new(),
+        )
+        .unwrap();
+
+    let args = fs::read_to_string(&args_path).unwrap();
+    let args = args.lines().collect::<Vec<_>>();
+    assert_eq!(args[..2], ["exec", "--process"]);
+    assert_eq!(args[3..], ["container-1", "id"]);
+    let spec: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&spec_path).unwrap()).unwrap();
+    assert_eq!(spec["cwd"], "/work");
+    assert_eq!(spec["env"], json!(["MODE=debug"]));
+    assert_eq!(spec["user"]["uid"], 1000);
+}
+
 #[test]
 fn test_non_terminal_container_stdio_capture() {
     let temp_dir = tempdir().unwrap();
@@ -648,7 +759,9 @@ esac
             io_uid: 0,
             io_gid: 0,
             max_container_log_line_size: 4096,
+            attach_scrollback_bytes: 0,
             log_to_journald: false,
+            log_drivers: Vec::new(),
             no_sync_log: false,
             no_pivot: false,
             no_new_keyring: false,
@@ -756,7 +869,9 @@ esac
             io_uid: 0,
             io_gid: 0,
             max_container_log_line_size: 4096,
+            attach_scrollback_bytes: 0,
             log_to_journald: false,
+            log_drivers: Vec::new(),
             no_sync_log: false,
             no_pivot: true,
             no_new_keyring: false,
@@ -852,7 +967,9 @@ esac
             io_uid: 0,
             io_gid: 0,
             max_container_log_line_size: 4096,
+            attach_scrollback_bytes: 0,
             log_to_journald: false,
+            log_drivers: Vec::new(),
             no_sync_log: false,
             no_pivot: false,
             no_new_keyring: true,
@@ -930,7 +1047,9 @@ exit 0
             io_uid: 0,
             io_gid: 0,
             max_container_log_line_size: 4096,
+            attach_scrollback_bytes: 0,
             log_to_journald: false,
+            log_drivers: Vec::new(),
             no_sync_log: false,
             no_pivot: false,
             no_new_keyring: false,
@@ -1026,7 +1145,9 @@ esac
             io_uid: 0,
             io_gid: 0,
             max_container_log_line_size: 4096,
+            attach_scrollback_bytes: 0,
             log_to_journald: false,
+            log_drivers: Vec::new(),
             no_sync_log: false,
             no_pivot: false,
             no_new_keyring: false,
@@ -1063,3 +1184,27 @@ esac
     daemon.io_manager.shutdown().unwrap();
     daemon.cleanup_attach_socket_directory();
 }
+
+#[test]
+fn memory_cgroup_dir_prefers_v1_memory_controller_and_reads_oom_kill() {
+    assert_eq!(
+        memory_cgroup_dir("0::/kubepods.slice/crius-abc.scope\n"),
+        Some(PathBuf::from(
+            "/sys/fs/cgroup/kubepods.slice/crius-abc.scope"
+        ))
+    );
+    assert_eq!(
+        memory_cgroup_dir("5:cpu,cpuacct:/kubepods/abc\n4:memory:/kubepods/abc\n0::/\n"),
+        Some(PathBuf::from("/sys/fs/cgroup/memory/kubepods/abc"))
+    );
+    assert_eq!(memory_cgroup_dir(""), None);
+
+    let dir = tempdir().unwrap();
+    assert_eq!(read_oom_kill(dir.path()), None);
+    fs::write(
+        dir.path().join("memory.events"),
+        "low 0\nhigh 0\nmax 4\noom 2\noom_kill 2\n",
+    )
+    .unwrap();
+    assert_eq!(read_oom_kill(dir.path()), Some(2));
+}
@@ -4,32 +4,42 @@
 //! 1. 重定向容器IO流到文件或socket
 //! 2. 支持attach功能（多客户端连接）
 //! 3. 支持TTY模式
-//! 4. 日志持久化（CRI text log格式）
+//! 4. 日志持久化（可插拔日志驱动，默认 CRI text log格式）
 
-use crate::attach::{encode_attach_output_frame, ATTACH_PIPE_STDERR, ATTACH_PIPE_STDOUT};
+pub mod log_driver;
+
+use crate::attach::{
+    encode_attach_output_frame, encode_attach_scrollback_frame, ATTACH_PIPE_STDERR,
+    ATTACH_PIPE_STDOUT,
+};
 use anyhow::{Context, Result};
-use log::{debug, error, info};
+use log::{debug, error, info, warn};
+use log_driver::{
+    CriFileDriver, FileOptions, ForwardDriver, JournaldDriver, JsonFileDriver, LogDriver,
+    LogDriverSpec, LogRecord, LogSource, SyslogDriver,
+};
 use nix::unistd::{chown, Gid, Uid};
 use serde::Deserialize;
+use std::collections::VecDeque;
 use std::fs::{File, OpenOptions};
 use std::io::{BufRead, BufReader, Read, Write};
 use std::os::unix::io::AsRawFd;
-use std::os::unix::net::{UnixDatagram, UnixListener, UnixStream};
+use std::os::unix::net::{UnixListener, UnixStream};
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread::JoinHandle;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
 const CRI_LOG_STREAM_STDOUT: &str = "stdout";
 const CRI_LOG_STREAM_STDERR: &str = "stderr";
-const CRI_LOG_TAG_FULL: &str = "F";
-const CRI_LOG_TAG_PARTIAL: &str = "P";
 // Match containerd/cri-o's default CRI logger buffer so long lines split
 // into `P` / `F` records at the same boundary `crictl logs` expects.
 const DEFAULT_CRI_LOG_LINE_BUFFER_SIZE: usize = 4096;
 pub const DEFAULT_JOURNALD_SOCKET_PATH: &str = "/run/systemd/journal/socket";
 const MAX_PENDING_ATTACH_BYTES: usize = 64 * 1024;
+pub const DEFAULT_ATTACH_SCROLLBACK_BYTES: usize = 64 * 1024;
+const ATTACH_REPLAY_WRITE_TIMEOUT: Duration = Duration::from_secs(1);
 
 #[derive(Debug, Default)]
 struct PendingLogBytes {
@@ -37,6 +47,87 @@ struct PendingLogBytes {
     stderr: Vec<u8>,
 }
 
+#[derive(Debug)]
+struct ScrollbackChunk {
+    seq: u64,
+    recorded_at: SystemTime,
+    data: Vec<u8>,
+}
+
+#[derive(Debug, Default)]
+struct ScrollbackStream {
+    chunks: VecDeque<ScrollbackChunk>,
+    bytes: usize,
+}
+
+impl ScrollbackStream {
+    fn push(&mut self, chunk: ScrollbackChunk, limit: usize) {
+        self.bytes += chunk.data.len();
+        self.chunks.push_back(chunk);
+        while self.bytes > limit {
+            let overflow = self.bytes - limit;
+            let Some(oldest) = self.chunks.front_mut() else {
+                break;
+            };
+            if oldest.data.len() > overflow {
+                oldest.data.drain(..overflow);
+                self.bytes -= overflow;
+            } else {
+                self.bytes -= oldest.data.len();
+                self.chunks.pop_front();
+            }
+        }
+    }
+}
+
+/// 最近输出的环形缓冲，stdout/stderr 各自限额，回放时按写入顺序合并。
+#[derive(Debug, Default)]
+struct AttachScrollback {
+    stdout: ScrollbackStream,
+    stderr: ScrollbackStream,
+    next_seq: u64,
+}
+
+impl AttachScrollback {
+    fn record(&mut self, pipe: u8, data: &[u8], limit: usize) {
+        if limit == 0 || data.is_empty() {
+            return;
+        }
+        let chunk = ScrollbackChunk {
+            seq: self.next_seq,
+            recorded_at: SystemTime::now(),
+            data: data.to_vec(),
+        };
+        self.next_seq += 1;
+        match pipe {
+            ATTACH_PIPE_STDERR => self.stderr.push(chunk, limit),
+            _ => self.stdout.push(chunk, limit),
+        }
+    }
+
+    fn encode_replay(&self) -> Vec<u8> {
+        let mut stdout = self.stdout.chunks.iter().peekable();
+        let mut stderr = self.stderr.chunks.iter().peekable();
+        let mut replay = Vec::new();
+        loop {
+            let (pipe, chunk) = match (stdout.peek(), stderr.peek()) {
+                (Some(out), Some(err)) if err.seq < out.seq => {
+                    (ATTACH_PIPE_STDERR, stderr.next().unwrap())
+                }
+                (Some(_), _) => (ATTACH_PIPE_STDOUT, stdout.next().unwrap()),
+                (None, Some(_)) => (ATTACH_PIPE_STDERR, stderr.next().unwrap()),
+                (None, None) => break,
+            };
+            replay.extend(encode_attach_scrollback_frame(
+                pipe,
+                chunk.recorded_at,
+                &chunk.data,
+            ));
+        }
+        replay
+    }
+}
+
 /// IO配置
 #[derive(Debug, Clone)]
 pub struct IoConfig {
@@ -56,6 +147,10 @@ pub struct IoConfig {
     pub reopen_socket: Option<PathBuf>,
     /// journald 双写配置
     pub journald: Option<JournalConfig>,
+    /// 启用的日志驱动；为空时沿用 CRI 文件加可选 journald 双写。
+    pub log_drivers: Vec<LogDriverSpec>,
+    /// 日志驱动标注记录来源时使用的容器身份。
+    pub log_source: LogSource,
     /// 是否在日志轮转和容器退出时跳过 sync。
     pub no_sync_log: bool,
     /// shim 创建的宿主 IO 工件默认 UID。
@@ -64,6 +159,8 @@ pub struct IoConfig {
     pub io_gid: u32,
     /// CRI 单条日志记录切分阈值（字节）。
     pub max_log_line_size: usize,
+    /// attach 回放缓冲中每个输出流保留的字节数；0 表示不保留。
+    pub attach_scrollback_bytes: usize,
 }
 
 impl Default for IoConfig {
@@ -77,10 +174,13 @@ impl Default for IoConfig {
             resize_socket: None,
             reopen_socket: None,
             journald: None,
+            log_drivers: Vec::new(),
+            log_source: LogSource::default(),
             no_sync_log: false,
             io_uid: 0,
             io_gid: 0,
             max_log_line_size: DEFAULT_CRI_LOG_LINE_BUFFER_SIZE,
+            attach_scrollback_bytes: DEFAULT_ATTACH_SCROLLBACK_BYTES,
         }
     }
 }
@@ -99,12 +199,14 @@ pub struct IoManager {
     config: Arc<Mutex<IoConfig>>,
     /// 当前attach的客户端
     clients: Arc<Mutex<Vec<ClientConnection>>>,
-    /// 日志文件
-    log_file: Arc<Mutex<Option<File>>>,
+    /// 已打开的日志驱动
+    log_drivers: Arc<Mutex<Vec<Box<dyn LogDriver>>>>,
     /// 尚未形成完整 CRI 记录的 stdout/stderr 缓冲
     pending_logs: Arc<Mutex<PendingLogBytes>>,
     /// Output produced before the first attach client is accepted.
     pending_attach_output: Arc<Mutex<Vec<u8>>>,
+    /// 新 attach 客户端优先回放的最近输出
+    scrollback: Arc<Mutex<AttachScrollback>>,
     /// 当前TTY控制台文件，用于resize
     console_file: Arc<Mutex<Option<File>>>,
     /// attach/resize/reopen listener 生命周期
@@ -116,6 +218,8 @@ pub struct IoManager {
 struct ClientConnection {
     id: usize,
     stream: UnixStream,
+    /// 回放尚未写完时新产生的输出帧；为 `None` 表示已追上，直接写实时输出。
+    catch_up: Option<Vec<u8>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -134,99 +238,7 @@ struct ServerThread {
 
 impl IoManager {
     fn should_record_logs(&self) -> bool {
-        let config = self.config.lock().unwrap();
-        config.stdout.is_some() || config.journald.is_some()
-    }
-
-    fn parse_cri_log_record(record: &[u8]) -> Option<(&str, &str, &[u8])> {
-        let first_space = record.iter().position(|byte| *byte == b' ')?;
-        let second_space = record[first_space + 1..]
-            .iter()
-            .position(|byte| *byte == b' ')?;
-        let second_space = first_space + 1 + second_space;
-        let third_space = record[second_space + 1..]
-            .iter()
-            .position(|byte| *byte == b' ')?;
-        let third_space = second_space + 1 + third_space;
-
-        let stream = std::str::from_utf8(&record[first_space + 1..second_space]).ok()?;
-        let tag = std::str::from_utf8(&record[second_space + 1..third_space]).ok()?;
-        let payload = record
-            .get(third_space + 1..)
-            .map(|payload| payload.strip_suffix(b"\n").unwrap_or(payload))?;
-        Some((stream, tag, payload))
-    }
-
-    fn append_journald_text_field(entry: &mut Vec<u8>, key: &str, value: &str) {
-        entry.extend_from_slice(key.as_bytes());
-        entry.push(b'=');
-        entry.extend_from_slice(value.as_bytes());
-        entry.push(b'\n');
-    }
-
-    fn append_journald_binary_field(entry: &mut Vec<u8>, key: &str, value: &[u8]) {
-        entry.extend_from_slice(key.as_bytes());
-        entry.push(b'\n');
-        entry.extend_from_slice(&(value.len() as u64).to_le_bytes());
-        entry.extend_from_slice(value);
-        entry.push(b'\n');
-    }
-
-    fn send_journald_records(&self, records: &[Vec<u8>]) {
-        let journal = self.config.lock().unwrap().journald.clone();
-        let Some(journal) = journal.as_ref() else {
-            return;
-        };
-        if records.is_empty() {
-            return;
-        }
-
-        let socket = match UnixDatagram::unbound() {
-            Ok(socket) => socket,
-            Err(err) => {
-                debug!("Failed to create journald datagram socket: {}", err);
-                return;
-            }
-        };
-
-        for record in records {
-            let Some((stream, tag, payload)) = Self::parse_cri_log_record(record) else {
-                debug!("Skipping unparsable CRI log record for journald duplication");
-                continue;
-            };
-
-            let mut entry = Vec::new();
-            Self::append_journald_text_field(
-                &mut entry,
-                "SYSLOG_IDENTIFIER",
-                &journal.syslog_identifier,
-            );
-            Self::append_journald_text_field(&mut entry, "CONTAINER_ID", &journal.container_id);
-            if let Some(container_name) = journal.container_name.as_deref() {
-                Self::append_journald_text_field(&mut entry, "CONTAINER_NAME", container_name);
-            }
-            Self::append_journald_text_field(&mut entry, "CONTAINER_STREAM", stream);
-            Self::append_journald_text_field(&mut entry, "CRI_LOG_TAG", tag);
-            Self::append_journald_text_field(
-                &mut entry,
-                "PRIORITY",
-                if stream == CRI_LOG_STREAM_STDERR {
-                    "3"
-                } else {
-                    "6"
-                },
-            );
-            Self::append_journald_binary_field(&mut entry, "MESSAGE", payload);
-
-            if let Err(err) = socket.send_to(&entry, &journal.socket_path) {
-                debug!(
-                    "Failed to duplicate container log to journald socket {}: {}",
-                    journal.socket_path.display(),
-                    err
-                );
-                break;
-            }
-        }
+        !self.log_drivers.lock().unwrap().is_empty()
     }
 
     fn cleanup_socket_path(socket: &PathBuf) {
@@ -292,27 +304,13 @@ impl IoManager {
         }
     }
 
-    fn encode_cri_log_record(stream: &str, tag: &str, content: &[u8]) -> Vec<u8> {
-        let mut record = chrono::Local::now()
-            .to_rfc3339_opts(chrono::SecondsFormat::Nanos, true)
-            .into_bytes();
-        record.push(b' ');
-        record.extend_from_slice(stream.as_bytes());
-        record.push(b' ');
-        record.extend_from_slice(tag.as_bytes());
-        record.push(b' ');
-        record.extend_from_slice(content);
-        record.push(b'\n');
-        record
-    }
-
     fn drain_cri_log_records(
         &self,
         pending: &mut Vec<u8>,
-        stream: &str,
+        stream: &'static str,
         data: &[u8],
         flush_partial: bool,
-    ) -> Vec<Vec<u8>> {
+    ) -> Vec<LogRecord> {
         pending.extend_from_slice(data);
         let mut records = Vec::new();
 
@@ -324,11 +322,7 @@ impl IoManager {
                 if matches!(content.last(), Some(b'\r')) {
                     content.pop();
                 }
-                records.push(Self::encode_cri_log_record(
-                    stream,
-                    CRI_LOG_TAG_FULL,
-                    &content,
-                ));
+                records.push(LogRecord::new(stream, false, content));
                 continue;
             }
 
@@ -345,53 +339,113 @@ impl IoManager {
             }
 
             let content = pending.drain(..split_at).collect::<Vec<u8>>();
-            records.push(Self::encode_cri_log_record(
-                stream,
-                CRI_LOG_TAG_PARTIAL,
-                &content,
-            ));
+            records.push(LogRecord::new(stream, true, content));
         }
 
         if flush_partial && !pending.is_empty() {
             let content = std::mem::take(pending);
-            records.push(Self::encode_cri_log_record(
-                stream,
-                CRI_LOG_TAG_PARTIAL,
-                &content,
-            ));
+            records.push(LogRecord::new(stream, true, content));
         }
 
         records
     }
 
-    fn take_log_records(&self, stream: &str, data: &[u8], flush_partial: bool) -> Vec<Vec<u8>> {
+    fn take_log_records(
+        &self,
+        stream: &'static str,
+        data: &[u8],
+        flush_partial: bool,
+    ) -> Vec<LogRecord> {
         let mut pending_logs = self.pending_logs.lock().unwrap();
         let pending = Self::pending_buffer_mut(&mut pending_logs, stream);
         self.drain_cri_log_records(pending, stream, data, flush_partial)
     }
 
-    fn write_log_records(&self, records: &[Vec<u8>]) -> Result<()> {
+    /// 对每个驱动执行同一操作；单个驱动失败不影响其余驱动，返回第一个错误。
+    fn for_each_log_driver(
+        &self,
+        mut op: impl FnMut(&mut dyn LogDriver) -> Result<()>,
+    ) -> Result<()> {
+        let mut first_error = None;
+        for driver in self.log_drivers.lock().unwrap().iter_mut() {
+            if let Err(err) = op(driver.as_mut()) {
+                warn!("Log driver {} failed: {:#}", driver.name(), err);
+                first_error.get_or_insert(err);
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+
+    fn write_log_records(&self, records: &[LogRecord]) -> Result<()> {
         if records.is_empty() {
             return Ok(());
         }
+        self.for_each_log_driver(|driver| driver.write(records))
+    }
 
-        if let Some(file) = &mut *self.log_file.lock().unwrap() {
-            for record in records {
-                file.write_all(record)?;
+    fn open_log_drivers(config: &IoConfig) -> Result<Vec<Box<dyn LogDriver>>> {
+        let mut specs = config.log_drivers.clone();
+        if specs.is_empty() {
+            specs.push(LogDriverSpec::CriFile);
+            if config.journald.is_some() {
+                specs.push(LogDriverSpec::Journald);
             }
-            file.flush()?;
         }
-        self.send_journald_records(records);
-
-        Ok(())
-    }
+        let file_options = FileOptions {
+            io_uid: config.io_uid,
+            io_gid: config.io_gid,
+            no_sync: config.no_sync_log,
+        };
 
-    fn maybe_sync_log_file(&self, file: &File) -> Result<bool> {
-        if self.config.lock().unwrap().no_sync_log {
-            return Ok(false);
+        let mut drivers: Vec<Box<dyn LogDriver>> = Vec::new();
+        for spec in specs {
+            match spec {
+                LogDriverSpec::CriFile => {
+                    // 没有 CRI 日志路径（例如 exec session）时不落盘。
+                    if let Some(stdout) = &config.stdout {
+                        drivers.push(Box::new(CriFileDriver::open(stdout.clone(), file_options)?));
+                    }
+                }
+                LogDriverSpec::Journald => {
+                    let journal = config
+                        .journald
+                        .clone()
+                        .unwrap_or_else(|| JournaldDriver::default_config(&config.log_source));
+                    drivers.push(Box::new(JournaldDriver::new(journal)));
+                }
+                LogDriverSpec::JsonFile {
+                    path,
+                    max_size,
+                    max_files,
+                } => {
+                    let Some(path) =
+                        path.or_else(|| config.stdout.as_ref().map(|p| p.with_extension("json")))
+                    else {
+                        warn!("Skipping json-file log driver without a path");
+                        continue;
+                    };
+                    drivers.push(Box::new(JsonFileDriver::open(
+                        path,
+                        max_size,
+                        max_files,
+                        file_options,
+                    )?));
+                }
+                LogDriverSpec::Forward { path } => {
+                    drivers.push(Box::new(ForwardDriver::new(
+                        path,
+                        config.log_source.clone(),
+                    )));
+                }
+                LogDriverSpec::Syslog { address } => {
+                    drivers.push(Box::new(SyslogDriver::new(
+                        address,
+                        config.log_source.clone(),
+                    )));
+                }
+            }
         }
-        file.sync_all().context("Failed to sync log file to disk")?;
-        Ok(true)
+        Ok(drivers)
     }
 
     fn remove_clients(&self, disconnected_ids: &[usize]) {
@@ -403,29 +457,48 @@ impl IoManager {
         clients.retain(|client| !disconnected_ids.contains(&client.id));
     }
 
-    fn broadcast(&self, data: &[u8]) -> Result<()> {
+    fn attach_scrollback_bytes(&self) -> usize {
+        self.config.lock().unwrap().attach_scrollback_bytes
+    }
+
+    fn broadcast(&self, pipe: u8, data: &[u8]) -> Result<()> {
+        let scrollback_bytes = self.attach_scrollback_bytes();
+        let frame = encode_attach_output_frame(pipe, data);
+        // 记录回放、快照客户端和追加追赶缓冲在同一把锁内完成，与 `accept_attach_client`
+        // 登记新客户端互斥，新客户端不会漏掉或重复收到这段输出。
+        let mut disconnected = Vec::new();
         let streams: Vec<(usize, UnixStream)> = {
-            let clients = self.clients.lock().unwrap();
+            let mut scrollback = self.scrollback.lock().unwrap();
+            scrollback.record(pipe, data, scrollback_bytes);
+            let mut clients = self.clients.lock().unwrap();
+            if clients.is_empty() && scrollback_bytes == 0 {
+                self.buffer_pending_attach_output(&frame);
+            }
             clients
-                .iter()
-                .filter_map(|client| {
-                    client
+                .iter_mut()
+                .filter_map(|client| match client.catch_up.as_mut() {
+                    Some(catch_up) => {
+                        catch_up.extend_from_slice(&frame);
+                        if catch_up.len() > MAX_PENDING_ATTACH_BYTES {
+                            debug!(
+                                "Client {} fell behind while replaying scrollback",
+                                client.id
+                            );
+                            disconnected.push(client.id);
+                        }
+                        None
+                    }
+                    None => client
                         .stream
                         .try_clone()
                         .ok()
-                        .map(|stream| (client.id, stream))
+                        .map(|stream| (client.id, stream)),
                 })
                 .collect()
         };
 
-        if streams.is_empty() {
-            self.buffer_pending_attach_output(data);
-            return Ok(());
-        }
-
-        let mut disconnected = Vec::new();
         for (id, mut stream) in streams {
-            if let Err(e) = stream.write_all(data) {
+            if let Err(e) = stream.write_all(&frame) {
                 debug!("Client {} disconnected: {}", id, e);
                 disconnected.push(id);
             }
@@ -451,20 +524,82 @@ impl IoManager {
         std::mem::take(&mut *self.pending_attach_output.lock().unwrap())
     }
 
-    fn broadcast_output(&self, pipe: u8, data: &[u8]) -> Result<()> {
-        let frame = encode_attach_output_frame(pipe, data);
-        self.broadcast(&frame)
+    /// 回放内容可能超过 socket 缓冲，临时切回阻塞写并设置超时，避免卡住输出泵。
+    fn write_replay(stream: &UnixStream, replay: &[u8]) -> std::io::Result<()> {
+        let mut writer = stream.try_clone()?;
+        writer.set_nonblocking(false)?;
+        writer.set_write_timeout(Some(ATTACH_REPLAY_WRITE_TIMEOUT))?;
+        let result = writer.write_all(replay);
+        writer.set_write_timeout(None)?;
+        writer.set_nonblocking(true)?;
+        result
+    }
+
+    /// 接入新客户端并先写出回放内容；关闭回放时退回到只补发首个客户端之前的输出。
+    ///
+    /// 回放快照与登记客户端在 scrollback 锁内一起完成，写回放在锁外进行：期间的新输出进入
+    /// 该客户端的追赶缓冲，慢客户端不会卡住输出泵。
+    fn accept_attach_client(&self, id: usize, stream: UnixStream) {
+        let writer = match stream.try_clone() {
+            Ok(writer) => writer,
+            Err(err) => {
+                debug!("Failed to clone attach client {} stream: {}", id, err);
+                return;
+            }
+        };
+        let mut chunk = {
+            let scrollback = self.scrollback.lock().unwrap();
+            let replay = if self.attach_scrollback_bytes() > 0 {
+                scrollback.encode_replay()
+            } else {
+                self.take_pending_attach_output()
+            };
+            self.clients.lock().unwrap().push(ClientConnection {
+                id,
+                stream,
+                catch_up: Some(Vec::new()),
+            });
+            replay
+        };
+        loop {
+            if !chunk.is_empty() {
+                if let Err(err) = Self::write_replay(&writer, &chunk) {
+                    debug!(
+                        "Failed to flush attach scrollback to client {}: {}",
+                        id, err
+                    );
+                    self.remove_clients(&[id]);
+                    return;
+                }
+            }
+            let mut clients = self.clients.lock().unwrap();
+            let Some(client) = clients.iter_mut().find(|client| client.id == id) else {
+                return;
+            };
+            // 追赶缓冲为空时保持 `None`，之后的输出直接写给该客户端。
+            match client.catch_up.take() {
+                Some(pending) if !pending.is_empty() => {
+                    chunk = pending;
+                    client.catch_up = Some(Vec::new());
+                }
+                _ => return,
+            }
+        }
     }
 
+    /// 回放中的客户端临时处于阻塞模式，不参与 stdin 读取。
     fn first_client_stream(&self) -> Option<(usize, UnixStream)> {
         let clients = self.clients.lock().unwrap();
-        clients.iter().find_map(|client| {
-            client
-                .stream
-                .try_clone()
-                .ok()
-                .map(|stream| (client.id, stream))
-        })
+        clients
+            .iter()
+            .filter(|client| client.catch_up.is_none())
+            .find_map(|client| {
+                client
+                    .stream
+                    .try_clone()
+                    .ok()
+                    .map(|stream| (client.id, stream))
+            })
     }
 
     /// 创建新的IO管理器
@@ -472,9 +607,10 @@ impl IoManager {
         Self {
             config: Arc::new(Mutex::new(IoConfig::default())),
             clients: Arc::new(Mutex::new(Vec::new())),
-            log_file: Arc::new(Mutex::new(None)),
+            log_drivers: Arc::new(Mutex::new(Vec::new())),
             pending_logs: Arc::new(Mutex::new(PendingLogBytes::default())),
             pending_attach_output: Arc::new(Mutex::new(Vec::new())),
+            scrollback: Arc::new(Mutex::new(AttachScrollback::default())),
             console_file: Arc::new(Mutex::new(None)),
             server_threads: Arc::new(Mutex::new(Vec::new())),
         }
@@ -484,18 +620,19 @@ impl IoManager {
     pub fn configure(&mut self, config: IoConfig) -> Result<()> {
         *self.config.lock().unwrap() = config.clone();
 
-        // 设置日志文件
-        if let Some(stdout) = &config.stdout {
-            let parent = stdout.parent().context("Invalid stdout path")?;
-            std::fs::create_dir_all(parent)?;
-            let mut log_file = self.log_file.lock().unwrap();
-            *log_file = Some(Self::open_output_file(
-                stdout,
-                config.io_uid,
-                config.io_gid,
-            )?);
-            info!("Log file configured: {:?}", stdout);
+        // 打开日志驱动
+        let drivers = Self::open_log_drivers(&config)?;
+        if !drivers.is_empty() {
+            info!(
+                "Log drivers configured: {}",
+                drivers
+                    .iter()
+                    .map(|driver| driver.name())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            );
         }
+        *self.log_drivers.lock().unwrap() = drivers;
 
         Ok(())
     }
@@ -516,7 +653,6 @@ impl IoManager {
             info!("Attach server listening on {:?}", socket);
 
             let io_manager = self.clone();
-            let clients = self.clients.clone();
             let stop = Arc::new(AtomicBool::new(false));
             let stop_for_thread = stop.clone();
             let handle = std::thread::spawn(move || {
@@ -531,20 +667,7 @@ impl IoManager {
                                 continue;
                             }
                             debug!("New attach client connected: {}", id);
-                            let pending = io_manager.take_pending_attach_output();
-                            if !pending.is_empty() {
-                                if let Err(err) = stream
-                                    .try_clone()
-                                    .and_then(|mut stream| stream.write_all(&pending))
-                                {
-                                    debug!(
-                                        "Failed to flush pending attach output to client {}: {}",
-                                        id, err
-                                    );
-                                }
-                            }
-                            let mut clients = clients.lock().unwrap();
-                            clients.push(ClientConnection { id, stream });
+                            io_manager.accept_attach_client(id, stream);
                         }
                         Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => {
                             std::thread::sleep(Duration::from_millis(25));
@@ -727,21 +850,8 @@ impl IoManager {
     }
 
     pub fn reopen_log_file(&self) -> Result<()> {
-        let config = self.config.lock().unwrap().clone();
-        let Some(stdout) = config.stdout.as_ref() else {
-            return Ok(());
-        };
-        let parent = stdout.parent().context("Invalid stdout path")?;
-        std::fs::create_dir_all(parent)?;
-
-        let reopened = Self::open_output_file(stdout, config.io_uid, config.io_gid)?;
-        let mut log_file = self.log_file.lock().unwrap();
-        if let Some(file) = log_file.as_mut() {
-            file.flush()?;
-            self.maybe_sync_log_file(file)?;
-        }
-        *log_file = Some(reopened);
-        info!("Reopened log file: {:?}", stdout);
+        self.for_each_log_driver(|driver| driver.reopen())?;
+        info!("Reopened log drivers");
         Ok(())
     }
 
@@ -769,7 +879,7 @@ impl IoManager {
         }
 
         // 发送到所有attach客户端
-        self.broadcast_output(ATTACH_PIPE_STDOUT, data)?;
+        self.broadcast(ATTACH_PIPE_STDOUT, data)?;
 
         Ok(())
     }
@@ -781,7 +891,7 @@ impl IoManager {
             self.write_log_records(&records)?;
         }
 
-        self.broadcast_output(ATTACH_PIPE_STDERR, data)
+        self.broadcast(ATTACH_PIPE_STDERR, data)
     }
 
     /// 读取stdin
@@ -915,10 +1025,7 @@ impl IoManager {
         clients.clear();
         drop(clients);
 
-        if let Some(file) = &mut *self.log_file.lock().unwrap() {
-            file.flush()?;
-            self.maybe_sync_log_file(file)?;
-        }
+        self.for_each_log_driver(|driver| driver.close())?;
         *self.console_file.lock().unwrap() = None;
 
         Ok(())
@@ -930,6 +1037,7 @@ mod tests {
     use super::*;
     use nix::unistd::{getegid, geteuid};
     use std::os::unix::fs::MetadataExt;
+    use std::os::unix::net::UnixDatagram;
     use tempfile::tempdir;
 
     fn parse_cri_log_lines(contents: &str) -> Vec<(String, String, String, String)> {
@@ -965,6 +1073,7 @@ mod tests {
         manager.clients.lock().unwrap().push(ClientConnection {
             id: 1,
             stream: server,
+            catch_up: None,
         });
 
         assert_eq!(manager.read_stdin().unwrap(), Vec::<u8>::new());
@@ -974,6 +1083,114 @@ mod tests {
         let _ = manager.read_stdin();
     }
 
+    fn read_replayed_frames(client: &mut UnixStream) -> Vec<crate::attach::AttachOutputFrame> {
+        client
+            .set_read_timeout(Some(Duration::from_millis(200)))
+            .unwrap();
+        let mut decoder = crate::attach::AttachOutputDecoder::default();
+        let mut frames = Vec::new();
+        let mut buffer = [0u8; 4096];
+        while let Ok(n) = client.read(&mut buffer) {
+            if n == 0 {
+                break;
+            }
+            frames.extend(decoder.push(&buffer[..n]).unwrap());
+        }
+        frames
+    }
+
+    #[test]
+    fn late_attach_client_replays_bounded_scrollback_per_stream() {
+        let mut manager = IoManager::new();
+        manager
+            .configure(IoConfig {
+                attach_scrollback_bytes: 8,
+                ..Default::default()
+            })
+            .unwrap();
+        manager.write_stdout(b"0123456789").unwrap();
+        manager.write_stderr(b"err").unwrap();
+        manager.write_stdout(b"ab").unwrap();
+
+        let (mut first, server) = UnixStream::pair().unwrap();
+        server.set_nonblocking(true).unwrap();
+        manager.accept_attach_client(1, server);
+        let frames = read_replayed_frames(&mut first)
+            .into_iter()
+            .map(|frame| (frame.pipe, frame.payload))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            frames,
+            vec![
+                (ATTACH_PIPE_STDOUT, b"456789".to_vec()),
+                (ATTACH_PIPE_STDERR, b"err".to_vec()),
+                (ATTACH_PIPE_STDOUT, b"ab".to_vec()),
+            ]
+        );
+
+        // 后来的客户端同样能看到回放，而不是只补发给第一个客户端。
+        let (mut second, server) = UnixStream::pair().unwrap();
+        server.set_nonblocking(true).unwrap();
+        manager.accept_attach_client(2, server);
+        assert_eq!(read_replayed_frames(&mut second).len(), 3);
+    }
+
+    #[test]
+    fn slow_replay_does_not_block_output_or_lose_frames() {
+        let mut manager = IoManager::new();
+        manager
+            .configure(IoConfig {
+                attach_scrollback_bytes: 1024 * 1024,
+                ..Default::default()
+            })
+            .unwrap();
+        // 超过 socket 缓冲，客户端开始读取前回放写会一直阻塞。
+        let history = vec![b'h'; 512 * 1024];
+        manager.write_stdout(&history).unwrap();
+
+        let (mut client, server) = UnixStream::pair().unwrap();
+        server.set_nonblocking(true).unwrap();
+        let accepting = {
+            let manager = manager.clone();
+            std::thread::spawn(move || manager.accept_attach_client(1, server))
+        };
+        std::thread::sleep(Duration::from_millis(100));
+        let started = std::time::Instant::now();
+        manager.write_stdout(b"live").unwrap();
+        assert!(started.elapsed() < Duration::from_millis(500));
+
+        let payloads = read_replayed_frames(&mut client)
+            .into_iter()
+            .map(|frame| frame.payload)
+            .collect::<Vec<_>>();
+        accepting.join().unwrap();
+        assert_eq!(payloads, vec![history, b"live".to_vec()]);
+        assert!(manager.clients.lock().unwrap()[0].catch_up.is_none());
+    }
+
+    #[test]
+    fn disabled_scrollback_only_flushes_pending_output_once() {
+        let mut manager = IoManager::new();
+        manager
+            .configure(IoConfig {
+                attach_scrollback_bytes: 0,
+                ..Default::default()
+            })
+            .unwrap();
+        manager.write_stdout(b"secret").unwrap();
+
+        let (mut first, server) = UnixStream::pair().unwrap();
+        server.set_nonblocking(true).unwrap();
+        manager.accept_attach_client(1, server);
+        assert_eq!(read_replayed_frames(&mut first).len(), 1);
+        manager.clients.lock().unwrap().clear();
+
+        let (mut second, server) = UnixStream::pair().unwrap();
+        server.set_nonblocking(true).unwrap();
+        manager.accept_attach_client(2, server);
+        assert!(read_replayed_frames(&mut second).is_empty());
+    }
+
     #[test]
     fn test_io_manager_creation() {
         let manager = IoManager::new();
@@ -1000,7 +1217,14 @@ mod tests {
         };
 
         manager.configure(config).unwrap();
-        assert!(manager.log_file.lock().unwrap().is_some());
+        let drivers = manager.log_drivers.lock().unwrap();
+        assert_eq!(
+            drivers
+                .iter()
+                .map(|driver| driver.name())
+                .collect::<Vec<_>>(),
+            vec!["cri-file"]
+        );
     }
 
     #[test]
@@ -1072,6 +1296,42 @@ mod tests {
         assert_eq!(records[0].3, "partial line");
     }
 
+    #[test]
+    fn test_log_drivers_receive_same_partial_line_split() {
+        let temp_dir = tempdir().unwrap();
+        let log_path = temp_dir.path().join("0.log");
+        let mut manager = IoManager::new();
+        manager
+            .configure(IoConfig {
+                stdout: Some(log_path.clone()),
+                log_drivers: vec![
+                    LogDriverSpec::CriFile,
+                    "json-file".parse::<LogDriverSpec>().unwrap(),
+                ],
+                max_log_line_size: 4,
+                no_sync_log: true,
+                ..Default::default()
+            })
+            .unwrap();
+
+        manager.write_stdout(b"abcdef\n").unwrap();
+
+        let records = parse_cri_log_lines(&std::fs::read_to_string(&log_path).unwrap());
+        assert_eq!(
+            records
+                .iter()
+                .map(|record| (record.2.as_str(), record.3.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("P", "abcd"), ("F", "ef")]
+        );
+        let json = std::fs::read_to_string(temp_dir.path().join("0.json")).unwrap();
+        let logs = json
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap()["log"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(logs, vec!["abcd", "ef\n"]);
+    }
+
     #[test]
     fn test_write_stdout_splits_multiline_chunk_into_multiple_records() {
         let temp_dir = tempdir().unwrap();
@@ -1342,7 +1602,7 @@ mod tests {
     }
 
     #[test]
-    fn test_maybe_sync_log_file_skips_sync_when_no_sync_log_enabled() {
+    fn test_sync_log_file_skips_sync_when_no_sync_log_enabled() {
         let temp_dir = tempdir().unwrap();
         let log_path = temp_dir.path().join("stdout.log");
         let mut manager = IoManager::new();
@@ -1358,11 +1618,13 @@ mod tests {
             .append(true)
             .open(&log_path)
             .unwrap();
-        assert!(!manager.maybe_sync_log_file(&file).unwrap());
+        assert!(
+            !log_driver::sync_log_file(&file, manager.config.lock().unwrap().no_sync_log).unwrap()
+        );
     }
 
     #[test]
-    fn test_maybe_sync_log_file_syncs_by_default() {
+    fn test_sync_log_file_syncs_by_default() {
         let temp_dir = tempdir().unwrap();
         let log_path = temp_dir.path().join("stdout.log");
         let mut manager = IoManager::new();
@@ -1377,7 +1639,9 @@ mod tests {
             .append(true)
             .open(&log_path)
             .unwrap();
-        assert!(manager.maybe_sync_log_file(&file).unwrap());
+        assert!(
+            log_driver::sync_log_file(&file, manager.config.lock().unwrap().no_sync_log).unwrap()
+        );
     }
 
     #[test]
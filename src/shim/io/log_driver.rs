@@ -0,0 +1,880 @@
+//! 容器日志驱动
+//!
+//! `IoManager` 先把输出切成带 `P`/`F` 标记的 [`LogRecord`]，驱动只负责编码和投递，
+//! 因此同一段输出在各个驱动里的分行结果一致：
+//! - `cri-file`：kubelet CRI 文本日志（默认）
+//! - `journald`：按 journald native 协议写 datagram
+//! - `json-file`：本地 JSON 行日志，按大小和个数轮转
+//! - `forward`：长度前缀 JSON 帧写入 Unix socket
+//! - `syslog`：RFC 5424 消息写入 Unix/UDP/TCP 端点
+
+use super::{IoManager, JournalConfig, CRI_LOG_STREAM_STDERR, DEFAULT_JOURNALD_SOCKET_PATH};
+pub use crate::utils::log_output::SyslogAddress;
+use crate::utils::log_output::{
+    rotate_numbered, syslog_field, syslog_hostname, syslog_octet_frame, SYSLOG_APP_NAME_MAX_LEN,
+};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, SecondsFormat};
+use log::debug;
+use serde_json::json;
+use std::fs::File;
+use std::io::Write;
+use std::net::{TcpStream, ToSocketAddrs, UdpSocket};
+use std::os::unix::net::{UnixDatagram, UnixStream};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// 容器注解，按名字从 handler 已配置或无需目标地址的驱动中挑选，多个名字用逗号分隔。
+pub const LOG_DRIVER_ANNOTATION: &str = "io.crius.log-driver";
+const DEFAULT_JSON_FILE_MAX_SIZE: u64 = 10 * 1024 * 1024;
+const DEFAULT_JSON_FILE_MAX_FILES: usize = 5;
+const DEFAULT_SYSLOG_SOCKET_PATH: &str = "/dev/log";
+const FORWARD_IO_TIMEOUT: Duration = Duration::from_secs(1);
+const SYSLOG_FACILITY_USER: u8 = 1;
+
+/// 一条已经按 CRI 规则切分好的日志记录。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogRecord {
+    pub timestamp: DateTime<Local>,
+    pub stream: &'static str,
+    /// 对应 CRI `P` 标记：这一条还没有遇到换行。
+    pub partial: bool,
+    pub content: Vec<u8>,
+}
+
+impl LogRecord {
+    pub fn new(stream: &'static str, partial: bool, content: Vec<u8>) -> Self {
+        Self {
+            timestamp: Local::now(),
+            stream,
+            partial,
+            content,
+        }
+    }
+
+    pub fn tag(&self) -> &'static str {
+        if self.partial {
+            "P"
+        } else {
+            "F"
+        }
+    }
+
+    fn rfc3339(&self) -> String {
+        self.timestamp.to_rfc3339_opts(SecondsFormat::Nanos, true)
+    }
+
+    pub fn encode_cri(&self) -> Vec<u8> {
+        let mut record = self.rfc3339().into_bytes();
+        record.push(b' ');
+        record.extend_from_slice(self.stream.as_bytes());
+        record.push(b' ');
+        record.extend_from_slice(self.tag().as_bytes());
+        record.push(b' ');
+        record.extend_from_slice(&self.content);
+        record.push(b'\n');
+        record
+    }
+}
+
+/// 日志记录来源，供需要标注容器身份的驱动使用。
+#[derive(Debug, Clone, Default)]
+pub struct LogSource {
+    pub container_id: String,
+    pub container_name: Option<String>,
+}
+
+/// 可插拔的容器日志驱动。
+pub trait LogDriver: Send {
+    fn name(&self) -> &'static str;
+
+    /// 写入一批记录；只有本地文件类故障才返回错误，远端不可达时由驱动自行丢弃并记录。
+    fn write(&mut self, records: &[LogRecord]) -> Result<()>;
+
+    /// 外部轮转后重新打开目标。
+    fn reopen(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// 容器退出时刷出缓冲。
+    fn close(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// 驱动声明，格式为 `name[:key=value,...]`。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogDriverSpec {
+    CriFile,
+    Journald,
+    JsonFile {
+        path: Option<PathBuf>,
+        max_size: u64,
+        max_files: usize,
+    },
+    Forward {
+        path: PathBuf,
+    },
+    Syslog {
+        address: SyslogAddress,
+    },
+}
+
+impl LogDriverSpec {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::CriFile => "cri-file",
+            Self::Journald => "journald",
+            Self::JsonFile { .. } => "json-file",
+            Self::Forward { .. } => "forward",
+            Self::Syslog { .. } => "syslog",
+        }
+    }
+
+    /// 只凭名字即可使用的驱动（不需要 handler 提供目标地址）。
+    fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "cri-file" => Some(Self::CriFile),
+            "journald" => Some(Self::Journald),
+            "json-file" => Some(Self::JsonFile {
+                path: None,
+                max_size: DEFAULT_JSON_FILE_MAX_SIZE,
+                max_files: DEFAULT_JSON_FILE_MAX_FILES,
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for LogDriverSpec {
+    type Err = anyhow::Error;
+
+    fn from_str(raw: &str) -> Result<Self> {
+        let raw = raw.trim();
+        let (name, options) = raw.split_once(':').unwrap_or((raw, ""));
+        let mut options = options
+            .split(',')
+            .map(str::trim)
+            .filter(|option| !option.is_empty())
+            .map(|option| {
+                option
+                    .split_once('=')
+                    .map(|(key, value)| (key.trim(), value.trim()))
+                    .with_context(|| format!("invalid log driver option {:?}", option))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let mut take = |key: &str| {
+            options
+                .iter()
+                .position(|(candidate, _)| *candidate == key)
+                .map(|index| options.remove(index).1.to_string())
+        };
+
+        let spec = match name {
+            "cri-file" => Self::CriFile,
+            "journald" => Self::Journald,
+            "json-file" => Self::JsonFile {
+                path: take("path").map(PathBuf::from),
+                max_size: take("max-size")
+                    .map(|value| parse_byte_size(&value))
+                    .transpose()?
+                    .unwrap_or(DEFAULT_JSON_FILE_MAX_SIZE),
+                max_files: take("max-files")
+                    .map(|value| {
+                        value
+                            .parse::<usize>()
+                            .ok()
+                            .filter(|count| *count > 0)
+                            .with_context(|| format!("invalid max-files {:?}", value))
+                    })
+                    .transpose()?
+                    .unwrap_or(DEFAULT_JSON_FILE_MAX_FILES),
+            },
+            "forward" => {
+                let address = take("address").context("forward log driver requires address")?;
+                Self::Forward {
+                    path: PathBuf::from(address.strip_prefix("unix://").unwrap_or(&address)),
+                }
+            }
+            "syslog" => Self::Syslog {
+                address: take("address")
+                    .map(|address| SyslogAddress::parse(&address))
+                    .transpose()?
+                    .unwrap_or_else(|| {
+                        SyslogAddress::Unix(PathBuf::from(DEFAULT_SYSLOG_SOCKET_PATH))
+                    }),
+            },
+            other => return Err(anyhow::anyhow!("unknown log driver {:?}", other)),
+        };
+        if let Some((key, _)) = options.first() {
+            return Err(anyhow::anyhow!(
+                "unsupported option {:?} for log driver {}",
+                key,
+                spec.name()
+            ));
+        }
+        Ok(spec)
+    }
+}
+
+fn parse_byte_size(raw: &str) -> Result<u64> {
+    let split_at = raw.find(|c: char| !c.is_ascii_digit()).unwrap_or(raw.len());
+    let (digits, unit) = raw.split_at(split_at);
+    let amount = digits
+        .parse::<u64>()
+        .with_context(|| format!("invalid byte size {:?}", raw))?;
+    let multiplier: u64 = match unit {
+        "" | "B" => 1,
+        "KiB" | "Ki" | "K" => 1024,
+        "MiB" | "Mi" | "M" => 1024 * 1024,
+        "GiB" | "Gi" | "G" => 1024 * 1024 * 1024,
+        _ => return Err(anyhow::anyhow!("invalid byte size {:?}", raw)),
+    };
+    amount
+        .checked_mul(multiplier)
+        .filter(|size| *size > 0)
+        .with_context(|| format!("invalid byte size {:?}", raw))
+}
+
+/// 计算容器实际启用的驱动。注解只能按名字挑选：handler 已配置的同名驱动沿用其参数，
+/// 否则只允许不需要目标地址的内建驱动，避免 Pod 借注解让 shim 连接任意端点。
+pub fn select_log_drivers(
+    configured: &[LogDriverSpec],
+    annotation: Option<&str>,
+) -> Result<Vec<LogDriverSpec>> {
+    let Some(annotation) = annotation.map(str::trim).filter(|value| !value.is_empty()) else {
+        return Ok(configured.to_vec());
+    };
+    let mut selected = Vec::new();
+    for name in annotation
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+    {
+        let spec = configured
+            .iter()
+            .find(|spec| spec.name() == name)
+            .cloned()
+            .or_else(|| LogDriverSpec::builtin(name))
+            .with_context(|| {
+                format!(
+                    "log driver {:?} from annotation {} is not configured for this runtime handler",
+                    name, LOG_DRIVER_ANNOTATION
+                )
+            })?;
+        if !selected.contains(&spec) {
+            selected.push(spec);
+        }
+    }
+    Ok(selected)
+}
+
+pub(super) fn sync_log_file(file: &File, no_sync: bool) -> Result<bool> {
+    if no_sync {
+        return Ok(false);
+    }
+    file.sync_all().context("Failed to sync log file to disk")?;
+    Ok(true)
+}
+
+/// 文件类驱动共用的打开参数。
+#[derive(Debug, Clone, Copy)]
+pub(super) struct FileOptions {
+    pub io_uid: u32,
+    pub io_gid: u32,
+    pub no_sync: bool,
+}
+
+fn open_log_file(path: &Path, options: FileOptions) -> Result<File> {
+    let parent = path.parent().context("Invalid log path")?;
+    std::fs::create_dir_all(parent)?;
+    IoManager::open_output_file(&path.to_path_buf(), options.io_uid, options.io_gid)
+}
+
+/// kubelet CRI 文本日志。
+pub(super) struct CriFileDriver {
+    path: PathBuf,
+    file: File,
+    options: FileOptions,
+}
+
+impl CriFileDriver {
+    pub(super) fn open(path: PathBuf, options: FileOptions) -> Result<Self> {
+        let file = open_log_file(&path, options)?;
+        Ok(Self {
+            path,
+            file,
+            options,
+        })
+    }
+}
+
+impl LogDriver for CriFileDriver {
+    fn name(&self) -> &'static str {
+        "cri-file"
+    }
+
+    fn write(&mut self, records: &[LogRecord]) -> Result<()> {
+        for record in records {
+            self.file.write_all(&record.encode_cri())?;
+        }
+        self.file.flush()?;
+        Ok(())
+    }
+
+    fn reopen(&mut self) -> Result<()> {
+        let reopened = open_log_file(&self.path, self.options)?;
+        self.file.flush()?;
+        sync_log_file(&self.file, self.options.no_sync)?;
+        self.file = reopened;
+        Ok(())
+    }
+
+    fn close(&mut self) -> Result<()> {
+        self.file.flush()?;
+        sync_log_file(&self.file, self.options.no_sync)?;
+        Ok(())
+    }
+}
+
+/// journald native 协议，字段与 CRI 记录一一对应。
+pub(super) struct JournaldDriver {
+    config: JournalConfig,
+}
+
+impl JournaldDriver {
+    pub(super) fn new(config: JournalConfig) -> Self {
+        Self { config }
+    }
+
+    pub(super) fn default_config(source: &LogSource) -> JournalConfig {
+        JournalConfig {
+            socket_path: PathBuf::from(DEFAULT_JOURNALD_SOCKET_PATH),
+            container_id: source.container_id.clone(),
+            container_name: source.container_name.clone(),
+            syslog_identifier: "crius-shim".to_string(),
+        }
+    }
+
+    fn append_text_field(entry: &mut Vec<u8>, key: &str, value: &str) {
+        entry.extend_from_slice(key.as_bytes());
+        entry.push(b'=');
+        entry.extend_from_slice(value.as_bytes());
+        entry.push(b'\n');
+    }
+
+    fn append_binary_field(entry: &mut Vec<u8>, key: &str, value: &[u8]) {
+        entry.extend_from_slice(key.as_bytes());
+        entry.push(b'\n');
+        entry.extend_from_slice(&(value.len() as u64).to_le_bytes());
+        entry.extend_from_slice(value);
+        entry.push(b'\n');
+    }
+
+    fn encode(&self, record: &LogRecord) -> Vec<u8> {
+        let mut entry = Vec::new();
+        Self::append_text_field(
+            &mut entry,
+            "SYSLOG_IDENTIFIER",
+            &self.config.syslog_identifier,
+        );
+        Self::append_text_field(&mut entry, "CONTAINER_ID", &self.config.container_id);
+        if let Some(container_name) = self.config.container_name.as_deref() {
+            Self::append_text_field(&mut entry, "CONTAINER_NAME", container_name);
+        }
+        Self::append_text_field(&mut entry, "CONTAINER_STREAM", record.stream);
+        Self::append_text_field(&mut entry, "CRI_LOG_TAG", record.tag());
+        Self::append_text_field(
+            &mut entry,
+            "PRIORITY",
+            if record.stream == CRI_LOG_STREAM_STDERR {
+                "3"
+            } else {
+                "6"
+            },
+        );
+        Self::append_binary_field(&mut entry, "MESSAGE", &record.content);
+        entry
+    }
+}
+
+impl LogDriver for JournaldDriver {
+    fn name(&self) -> &'static str {
+        "journald"
+    }
+
+    fn write(&mut self, records: &[LogRecord]) -> Result<()> {
+        if records.is_empty() {
+            return Ok(());
+        }
+        let socket = match UnixDatagram::unbound() {
+            Ok(socket) => socket,
+            Err(err) => {
+                debug!("Failed to create journald datagram socket: {}", err);
+                return Ok(());
+            }
+        };
+        for record in records {
+            if let Err(err) = socket.send_to(&self.encode(record), &self.config.socket_path) {
+                debug!(
+                    "Failed to duplicate container log to journald socket {}: {}",
+                    self.config.socket_path.display(),
+                    err
+                );
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// docker `json-file` 兼容的 JSON 行日志：完整行的 `log` 以换行结尾，`P` 记录没有换行。
+pub(super) struct JsonFileDriver {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    max_size: u64,
+    max_files: usize,
+    options: FileOptions,
+}
+
+impl JsonFileDriver {
+    pub(super) fn open(
+        path: PathBuf,
+        max_size: u64,
+        max_files: usize,
+        options: FileOptions,
+    ) -> Result<Self> {
+        let file = open_log_file(&path, options)?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            path,
+            file,
+            size,
+            max_size,
+            max_files,
+            options,
+        })
+    }
+
+    fn rotate(&mut self) -> Result<()> {
+        self.file.flush()?;
+        sync_log_file(&self.file, self.options.no_sync)?;
+        rotate_numbered(&self.path, self.max_files)?;
+        self.file = open_log_file(&self.path, self.options)?;
+        self.size = 0;
+        Ok(())
+    }
+
+    fn encode(record: &LogRecord) -> Result<Vec<u8>> {
+        let mut log = String::from_utf8_lossy(&record.content).into_owned();
+        if !record.partial {
+            log.push('\n');
+        }
+        let mut line = serde_json::to_vec(&json!({
+            "log": log,
+            "stream": record.stream,
+            "time": record.rfc3339(),
+        }))?;
+        line.push(b'\n');
+        Ok(line)
+    }
+}
+
+impl LogDriver for JsonFileDriver {
+    fn name(&self) -> &'static str {
+        "json-file"
+    }
+
+    fn write(&mut self, records: &[LogRecord]) -> Result<()> {
+        for record in records {
+            let line = Self::encode(record)?;
+            if self.size > 0 && self.size + line.len() as u64 > self.max_size {
+                self.rotate()?;
+            }
+            self.file.write_all(&line)?;
+            self.size += line.len() as u64;
+        }
+        self.file.flush()?;
+        Ok(())
+    }
+
+    fn reopen(&mut self) -> Result<()> {
+        let reopened = open_log_file(&self.path, self.options)?;
+        self.file.flush()?;
+        self.size = reopened.metadata()?.len();
+        self.file = reopened;
+        Ok(())
+    }
+
+    fn close(&mut self) -> Result<()> {
+        self.file.flush()?;
+        sync_log_file(&self.file, self.options.no_sync)?;
+        Ok(())
+    }
+}
+
+/// 把记录编码为 `u32 大端长度 + JSON` 帧写入 Unix stream socket，断开后下次写入时重连。
+pub(super) struct ForwardDriver {
+    path: PathBuf,
+    source: LogSource,
+    stream: Option<UnixStream>,
+}
+
+impl ForwardDriver {
+    pub(super) fn new(path: PathBuf, source: LogSource) -> Self {
+        Self {
+            path,
+            source,
+            stream: None,
+        }
+    }
+
+    fn connect(&mut self) -> std::io::Result<&mut UnixStream> {
+        if self.stream.is_none() {
+            let stream = UnixStream::connect(&self.path)?;
+            stream.set_write_timeout(Some(FORWARD_IO_TIMEOUT))?;
+            self.stream = Some(stream);
+        }
+        Ok(self
+            .stream
+            .as_mut()
+            .expect("forward stream was just connected"))
+    }
+
+    fn encode(&self, record: &LogRecord) -> Result<Vec<u8>> {
+        let payload = serde_json::to_vec(&json!({
+            "time": record.rfc3339(),
+            "containerId": self.source.container_id,
+            "containerName": self.source.container_name,
+            "stream": record.stream,
+            "tag": record.tag(),
+            "log": String::from_utf8_lossy(&record.content),
+        }))?;
+        let mut frame = Vec::with_capacity(4 + payload.len());
+        frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        frame.extend_from_slice(&payload);
+        Ok(frame)
+    }
+}
+
+impl LogDriver for ForwardDriver {
+    fn name(&self) -> &'static str {
+        "forward"
+    }
+
+    fn write(&mut self, records: &[LogRecord]) -> Result<()> {
+        let mut frames = Vec::new();
+        for record in records {
+            frames.extend(self.encode(record)?);
+        }
+        if frames.is_empty() {
+            return Ok(());
+        }
+        let result = self.connect().and_then(|stream| stream.write_all(&frames));
+        if let Err(err) = result {
+            debug!(
+                "Failed to forward container log to {}: {}",
+                self.path.display(),
+                err
+            );
+            self.stream = None;
+        }
+        Ok(())
+    }
+
+    fn reopen(&mut self) -> Result<()> {
+        self.stream = None;
+        Ok(())
+    }
+}
+
+enum SyslogConnection {
+    Unix(UnixDatagram),
+    Udp(UdpSocket),
+    Tcp(TcpStream),
+}
+
+/// RFC 5424 syslog；TCP 使用 RFC 6587 octet-counting 分帧。没有注册的 PEN 可用作 SD-ID，
+/// 结构化数据留空，容器 ID 和 `P`/`F` 标记以 `key=value` 前缀写在 MSG 里（只保留可见 ASCII）。
+pub(super) struct SyslogDriver {
+    address: SyslogAddress,
+    source: LogSource,
+    hostname: String,
+    connection: Option<SyslogConnection>,
+}
+
+impl SyslogDriver {
+    pub(super) fn new(address: SyslogAddress, source: LogSource) -> Self {
+        Self {
+            address,
+            source,
+            hostname: syslog_hostname(),
+            connection: None,
+        }
+    }
+
+    fn connect(&mut self) -> std::io::Result<&SyslogConnection> {
+        if self.connection.is_none() {
+            let connection = match &self.address {
+                SyslogAddress::Unix(_) => SyslogConnection::Unix(UnixDatagram::unbound()?),
+                SyslogAddress::Udp(_) => SyslogConnection::Udp(UdpSocket::bind("0.0.0.0:0")?),
+                SyslogAddress::Tcp(address) => {
+                    let target = address.to_socket_addrs()?.next().ok_or_else(|| {
+                        std::io::Error::new(
+                            std::io::ErrorKind::NotFound,
+                            format!("syslog address {} did not resolve", address),
+                        )
+                    })?;
+                    let stream = TcpStream::connect_timeout(&target, FORWARD_IO_TIMEOUT)?;
+                    stream.set_write_timeout(Some(FORWARD_IO_TIMEOUT))?;
+                    SyslogConnection::Tcp(stream)
+                }
+            };
+            self.connection = Some(connection);
+        }
+        Ok(self
+            .connection
+            .as_ref()
+            .expect("syslog connection was just created"))
+    }
+
+    fn app_name(&self) -> String {
+        let name = self
+            .source
+            .container_name
+            .as_deref()
+            .unwrap_or(&self.source.container_id);
+        syslog_field(name, SYSLOG_APP_NAME_MAX_LEN)
+    }
+
+    fn encode(&self, record: &LogRecord) -> Vec<u8> {
+        let severity = if record.stream == CRI_LOG_STREAM_STDERR {
+            3
+        } else {
+            6
+        };
+        let mut message = format!(
+            "<{}>1 {} {} {} - {} - containerId={} tag={} ",
+            SYSLOG_FACILITY_USER * 8 + severity,
+            record
+                .timestamp
+                .to_rfc3339_opts(SecondsFormat::Micros, true),
+            self.hostname,
+            self.app_name(),
+            record.stream,
+            syslog_field(&self.source.container_id, usize::MAX),
+            record.tag(),
+        )
+        .into_bytes();
+        message.extend_from_slice(&record.content);
+        message
+    }
+
+    fn send(&mut self, message: &[u8]) -> std::io::Result<()> {
+        let address = self.address.clone();
+        match self.connect()? {
+            SyslogConnection::Unix(socket) => {
+                let SyslogAddress::Unix(path) = &address else {
+                    unreachable!("unix syslog connection without unix address");
+                };
+                socket.send_to(message, path).map(|_| ())
+            }
+            SyslogConnection::Udp(socket) => {
+                let SyslogAddress::Udp(target) = &address else {
+                    unreachable!("udp syslog connection without udp address");
+                };
+                socket.send_to(message, target.as_str()).map(|_| ())
+            }
+            SyslogConnection::Tcp(stream) => {
+                let mut stream = stream;
+                stream.write_all(&syslog_octet_frame(message))
+            }
+        }
+    }
+}
+
+impl LogDriver for SyslogDriver {
+    fn name(&self) -> &'static str {
+        "syslog"
+    }
+
+    fn write(&mut self, records: &[LogRecord]) -> Result<()> {
+        for record in records {
+            let message = self.encode(record);
+            if let Err(err) = self.send(&message) {
+                debug!("Failed to send container log to syslog: {}", err);
+                self.connection = None;
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn reopen(&mut self) -> Result<()> {
+        self.connection = None;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::log_output::rotated_path;
+    use std::io::Read;
+    use tempfile::tempdir;
+
+    fn test_file_options() -> FileOptions {
+        FileOptions {
+            io_uid: nix::unistd::geteuid().as_raw(),
+            io_gid: nix::unistd::getegid().as_raw(),
+            no_sync: true,
+        }
+    }
+
+    #[test]
+    fn parses_driver_specs_with_options() {
+        assert_eq!(
+            "cri-file".parse::<LogDriverSpec>().unwrap(),
+            LogDriverSpec::CriFile
+        );
+        assert_eq!(
+            "json-file:max-size=1Mi,max-files=3"
+                .parse::<LogDriverSpec>()
+                .unwrap(),
+            LogDriverSpec::JsonFile {
+                path: None,
+                max_size: 1024 * 1024,
+                max_files: 3,
+            }
+        );
+        assert_eq!(
+            "forward:address=unix:///run/logs.sock"
+                .parse::<LogDriverSpec>()
+                .unwrap(),
+            LogDriverSpec::Forward {
+                path: PathBuf::from("/run/logs.sock"),
+            }
+        );
+        assert_eq!(
+            "syslog:address=udp://10.0.0.1:514"
+                .parse::<LogDriverSpec>()
+                .unwrap(),
+            LogDriverSpec::Syslog {
+                address: SyslogAddress::Udp("10.0.0.1:514".to_string()),
+            }
+        );
+        assert!("forward".parse::<LogDriverSpec>().is_err());
+        assert!("json-file:colour=red".parse::<LogDriverSpec>().is_err());
+        assert!("fluentd".parse::<LogDriverSpec>().is_err());
+    }
+
+    #[test]
+    fn annotation_selects_only_configured_or_builtin_drivers() {
+        let configured = vec![
+            LogDriverSpec::CriFile,
+            "syslog:address=udp://127.0.0.1:514".parse().unwrap(),
+        ];
+
+        assert_eq!(select_log_drivers(&configured, None).unwrap(), configured);
+        assert_eq!(
+            select_log_drivers(&configured, Some("syslog, json-file")).unwrap(),
+            vec![
+                configured[1].clone(),
+                LogDriverSpec::builtin("json-file").unwrap(),
+            ]
+        );
+        let err = select_log_drivers(&configured, Some("forward")).unwrap_err();
+        assert!(err.to_string().contains("is not configured"));
+    }
+
+    #[test]
+    fn json_file_driver_marks_partial_lines_and_rotates() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("container.json");
+        let mut driver = JsonFileDriver::open(path.clone(), 150, 2, test_file_options()).unwrap();
+
+        driver
+            .write(&[
+                LogRecord::new("stdout", true, b"part".to_vec()),
+                LogRecord::new("stdout", false, b"ial".to_vec()),
+            ])
+            .unwrap();
+        let lines = std::fs::read_to_string(&path).unwrap();
+        let records = lines
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(records[0]["log"], "part");
+        assert_eq!(records[1]["log"], "ial\n");
+        assert_eq!(records[1]["stream"], "stdout");
+
+        driver
+            .write(&[LogRecord::new("stderr", false, b"next".to_vec())])
+            .unwrap();
+        driver
+            .write(&[LogRecord::new("stderr", false, b"again".to_vec())])
+            .unwrap();
+        assert!(rotated_path(&path, 1).exists());
+        assert!(!rotated_path(&path, 2).exists());
+        assert!(std::fs::read_to_string(&path).unwrap().contains("again"));
+    }
+
+    #[test]
+    fn forward_driver_writes_length_prefixed_frames() {
+        let temp_dir = tempdir().unwrap();
+        let socket = temp_dir.path().join("forward.sock");
+        let listener = std::os::unix::net::UnixListener::bind(&socket).unwrap();
+        let mut driver = ForwardDriver::new(
+            socket,
+            LogSource {
+                container_id: "ctr-forward".to_string(),
+                container_name: Some("web".to_string()),
+            },
+        );
+
+        driver
+            .write(&[LogRecord::new("stdout", true, b"hello".to_vec())])
+            .unwrap();
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut length = [0u8; 4];
+        stream.read_exact(&mut length).unwrap();
+        let mut payload = vec![0u8; u32::from_be_bytes(length) as usize];
+        stream.read_exact(&mut payload).unwrap();
+        let frame: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(frame["containerId"], "ctr-forward");
+        assert_eq!(frame["tag"], "P");
+        assert_eq!(frame["log"], "hello");
+    }
+
+    #[test]
+    fn syslog_driver_sends_rfc5424_datagrams() {
+        let temp_dir = tempdir().unwrap();
+        let socket = temp_dir.path().join("syslog.sock");
+        let listener = UnixDatagram::bind(&socket).unwrap();
+        listener
+            .set_read_timeout(Some(Duration::from_secs(1)))
+            .unwrap();
+        let mut driver = SyslogDriver::new(
+            SyslogAddress::Unix(socket),
+            LogSource {
+                container_id: "ctr-syslog".to_string(),
+                container_name: Some("web".to_string()),
+            },
+        );
+
+        driver
+            .write(&[LogRecord::new("stderr", false, b"boom".to_vec())])
+            .unwrap();
+        let mut buffer = [0u8; 1024];
+        let size = listener.recv(&mut buffer).unwrap();
+        let message = String::from_utf8_lossy(&buffer[..size]).into_owned();
+        assert!(message.starts_with("<11>1 "), "{}", message);
+        assert!(
+            message.contains(" web - stderr - containerId=ctr-syslog tag=F boom"),
+            "{}",
+            message
+        );
+    }
+}
@@ -9,6 +9,7 @@
 
 use anyhow::{Context, Result};
 use clap::Parser;
+use crius::shim::io::log_driver::LogDriverSpec;
 use crius::shim::{Daemon, DaemonOptions};
 use log::{debug, info};
 use std::fs;
@@ -50,6 +51,10 @@ struct Args {
     #[clap(long)]
     log_to_journald: bool,
 
+    /// Container log driver (`name[:key=value,...]`), may be repeated
+    #[clap(long = "log-driver")]
+    log_drivers: Vec<LogDriverSpec>,
+
     /// Skip syncing CRI log files on reopen and container exit
     #[clap(long)]
     no_sync_log: bool,
@@ -90,6 +95,10 @@ struct Args {
     #[clap(long, default_value_t = 4096)]
     max_container_log_line_size: usize,
 
+    /// Bytes of recent stdout/stderr kept per stream for attach replay (0 disables)
+    #[clap(long, default_value_t = crius::shim::io::DEFAULT_ATTACH_SCROLLBACK_BYTES)]
+    attach_scrollback_bytes: usize,
+
     /// Optional unified state ledger path
     #[clap(long)]
     state_db_path: Option<PathBuf>,
@@ -141,7 +150,9 @@ fn main() -> Result<()> {
             io_uid: args.io_uid,
             io_gid: args.io_gid,
             max_container_log_line_size: args.max_container_log_line_size,
+            attach_scrollback_bytes: args.attach_scrollback_bytes,
             log_to_journald: args.log_to_journald,
+            log_drivers: args.log_drivers,
             no_sync_log: args.no_sync_log,
             no_pivot: args.no_pivot,
             no_new_keyring: args.no_new_keyring,
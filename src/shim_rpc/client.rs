@@ -1,12 +1,27 @@
 use std::io::{Read, Write};
 use std::os::unix::net::UnixStream;
 use std::path::{Path, PathBuf};
-use std::time::Duration;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
 
 use super::proto::{ShimRpcRequest, ShimRpcResponse};
 use super::wire::{RpcEnvelope, RpcResultEnvelope};
+use crate::metrics::registry::{metrics_registry, Histogram, LATENCY_BUCKETS_SECONDS};
+
+/// shim manager 发往 shim 的 RPC 耗时，按方法与结果区分。
+fn shim_rpc_duration() -> &'static Histogram {
+    static METRIC: OnceLock<Histogram> = OnceLock::new();
+    METRIC.get_or_init(|| {
+        metrics_registry().histogram(
+            "crius_shim_rpc_duration_seconds",
+            "Duration of shim RPC requests by method and result.",
+            &["method", "result"],
+            LATENCY_BUCKETS_SECONDS,
+        )
+    })
+}
 
 #[derive(Debug, Clone)]
 pub struct ShimRpcClient {
@@ -27,6 +42,22 @@ impl ShimRpcClient {
     }
 
     pub fn request(&self, payload: ShimRpcRequest) -> Result<ShimRpcResponse> {
+        let _span = tracing::info_span!(
+            target: "crius::trace",
+            "shim.rpc",
+            otel.name = %format_args!("shim.{}", payload.method()),
+            otel.kind = "client",
+        )
+        .entered();
+        let method = payload.method();
+        let started = Instant::now();
+        let result = self.send(payload);
+        let outcome = if result.is_ok() { "success" } else { "error" };
+        shim_rpc_duration().observe_duration(&[method, outcome], started.elapsed());
+        result
+    }
+
+    fn send(&self, payload: ShimRpcRequest) -> Result<ShimRpcResponse> {
         let mut stream = UnixStream::connect(&self.socket_path).with_context(|| {
             format!(
                 "failed to connect to shim RPC socket {}",
@@ -40,8 +71,9 @@ impl ShimRpcClient {
             .set_write_timeout(Some(self.timeout))
             .context("failed to configure shim RPC write timeout")?;
 
-        let request = serde_json::to_vec(&RpcEnvelope::new(payload))
-            .context("failed to encode shim RPC request")?;
+        let envelope =
+            RpcEnvelope::new(payload).with_traceparent(crate::trace_export::current_traceparent());
+        let request = serde_json::to_vec(&envelope).context("failed to encode shim RPC request")?;
         stream
             .write_all(&request)
             .context("failed to write shim RPC request")?;
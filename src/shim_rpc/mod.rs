@@ -14,3 +14,10 @@ pub use proto::{
     WaitProcessResponse,
 };
 pub use server::default_task_socket_path;
+
+/// shim 在删除容器前写下 `oom_kill` 计数的文件，与退出码文件同目录：`<exit_code_file>.oom`。
+pub fn oom_kill_file_path(exit_code_file: &std::path::Path) -> std::path::PathBuf {
+    let mut path = exit_code_file.as_os_str().to_os_string();
+    path.push(".oom");
+    std::path::PathBuf::from(path)
+}
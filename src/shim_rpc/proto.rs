@@ -40,6 +40,9 @@ pub struct ExecProcessRequest {
     pub timeout_ms: Option<u64>,
     pub io_drain_timeout_ms: Option<u64>,
     pub exec_cpu_affinity: Option<usize>,
+    /// 带覆盖项的 exec process spec，存在时通过 `runtime exec --process` 执行。
+    #[serde(default)]
+    pub process: Option<crate::oci::spec::Process>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -60,6 +63,9 @@ pub struct OpenExecSessionRequest {
     pub stdout: bool,
     pub stderr: bool,
     pub exec_cpu_affinity: Option<usize>,
+    /// 带覆盖项的 exec process spec，存在时通过 `runtime exec --process` 执行。
+    #[serde(default)]
+    pub process: Option<crate::oci::spec::Process>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -250,6 +256,34 @@ pub enum ShimRpcRequest {
     ContainerPid(StatusRequest),
 }
 
+impl ShimRpcRequest {
+    /// 与 RPC 报文中 `method` 标签一致的方法名。
+    pub fn method(&self) -> &'static str {
+        match self {
+            Self::Ping => "ping",
+            Self::CreateTask(_) => "create_task",
+            Self::StartTask(_) => "start_task",
+            Self::ExecProcess(_) => "exec_process",
+            Self::OpenExecSession(_) => "open_exec_session",
+            Self::OpenAttachStream(_) => "open_attach_stream",
+            Self::CloseAttachStream(_) => "close_attach_stream",
+            Self::WaitProcess(_) => "wait_process",
+            Self::KillTask(_) => "kill_task",
+            Self::DeleteTask(_) => "delete_task",
+            Self::UpdateResources(_) => "update_resources",
+            Self::CheckpointTask(_) => "checkpoint_task",
+            Self::RestoreTask(_) => "restore_task",
+            Self::ReopenLog(_) => "reopen_log",
+            Self::ResizePty(_) => "resize_pty",
+            Self::ResizeAttachPty(_) => "resize_attach_pty",
+            Self::Status(_) => "status",
+            Self::PauseTask(_) => "pause_task",
+            Self::ResumeTask(_) => "resume_task",
+            Self::ContainerPid(_) => "container_pid",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "kind", content = "payload", rename_all = "snake_case")]
 pub enum ShimRpcResponse {
@@ -64,7 +64,9 @@ fn handle_stream(mut stream: UnixStream, handler: &dyn ShimRpcHandler) -> Result
     let envelope: RpcEnvelope<ShimRpcRequest> =
         serde_json::from_slice(&request).context("failed to decode shim RPC request")?;
 
-    let response = match handler.handle_request(envelope.into_payload()) {
+    let (payload, traceparent) = envelope.into_parts();
+    let _trace_context = crate::trace_export::enter_remote_context(traceparent.as_deref());
+    let response = match handler.handle_request(payload) {
         Ok(payload) => RpcResultEnvelope::success(payload),
         Err(err) => {
             debug!("shim RPC request returned an error: {}", err);
@@ -117,6 +119,18 @@ mod tests {
         }
     }
 
+    #[derive(Default)]
+    struct TraceCapturingHandler {
+        traceparent: std::sync::Mutex<Option<String>>,
+    }
+
+    impl ShimRpcHandler for TraceCapturingHandler {
+        fn handle_request(&self, _request: ShimRpcRequest) -> Result<ShimRpcResponse> {
+            *self.traceparent.lock().unwrap() = crate::trace_export::current_traceparent();
+            Ok(ShimRpcResponse::Empty)
+        }
+    }
+
     #[test]
     fn default_task_socket_path_uses_work_dir_and_container_id() {
         let path = default_task_socket_path(Path::new("/var/run/crius/shims"), "abc123");
@@ -153,4 +167,38 @@ mod tests {
         let _ = std::os::unix::net::UnixStream::connect(client.socket_path());
         handle.join().unwrap();
     }
+
+    #[test]
+    fn shim_rpc_request_carries_caller_traceparent_to_handler() {
+        const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let temp_dir = tempdir().unwrap();
+        let socket_path = temp_dir.path().join("task.sock");
+        let running = Arc::new(AtomicBool::new(true));
+        let handler = Arc::new(TraceCapturingHandler::default());
+        let server = {
+            let running = running.clone();
+            let handler = handler.clone();
+            let socket_path = socket_path.clone();
+            std::thread::spawn(move || serve(&socket_path, running, handler).unwrap())
+        };
+
+        let client = ShimRpcClient::new(socket_path, Duration::from_secs(1));
+        let _context = crate::trace_export::enter_remote_context(Some(TRACEPARENT));
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while client.request(ShimRpcRequest::Ping).is_err() {
+            assert!(
+                Instant::now() < deadline,
+                "shim RPC server did not answer ping before deadline"
+            );
+            std::thread::sleep(Duration::from_millis(25));
+        }
+
+        assert_eq!(
+            handler.traceparent.lock().unwrap().as_deref(),
+            Some(TRACEPARENT)
+        );
+        running.store(false, Ordering::Relaxed);
+        let _ = std::os::unix::net::UnixStream::connect(client.socket_path());
+        server.join().unwrap();
+    }
 }
@@ -6,15 +6,26 @@ use super::proto::ShimRpcResponse;
 #[derive(Debug, Serialize, Deserialize)]
 pub(super) struct RpcEnvelope<T> {
     payload: T,
+    /// 调用方 span 的 W3C `traceparent`；旧版本两端忽略该字段即可互通。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    traceparent: Option<String>,
 }
 
 impl<T> RpcEnvelope<T> {
     pub(super) fn new(payload: T) -> Self {
-        Self { payload }
+        Self {
+            payload,
+            traceparent: None,
+        }
+    }
+
+    pub(super) fn with_traceparent(mut self, traceparent: Option<String>) -> Self {
+        self.traceparent = traceparent;
+        self
     }
 
-    pub(super) fn into_payload(self) -> T {
-        self.payload
+    pub(super) fn into_parts(self) -> (T, Option<String>) {
+        (self.payload, self.traceparent)
     }
 }
 
@@ -840,6 +840,18 @@ impl<'a> StateLedger<'a> {
             .get_recent_events_for_subject(subject_kind, subject_id, limit)
     }
 
+    pub fn event_log_since(
+        &self,
+        since_seq: i64,
+        limit: usize,
+    ) -> Result<Vec<crate::storage::EventLogRecord>> {
+        self.persistence.storage().event_log_since(since_seq, limit)
+    }
+
+    pub fn event_log_latest_seq(&self) -> Result<i64> {
+        self.persistence.storage().event_log_latest_seq()
+    }
+
     pub fn recovery_snapshot(&self) -> Result<RecoveryLedgerSnapshot> {
         let containers = self.containers()?;
         let pods = self.pod_sandboxes()?;
@@ -1162,6 +1174,17 @@ impl<'a> StateLedgerWriter<'a> {
             .prune_events_for_subject(subject_kind, subject_id, keep)
     }
 
+    pub fn append_event_log(
+        &mut self,
+        input: crate::storage::EventLogInput<'_>,
+    ) -> Result<Option<crate::storage::EventLogRecord>> {
+        self.persistence.storage_mut().append_event_log(input)
+    }
+
+    pub fn configure_event_log(&mut self, capacity: u64) -> Result<()> {
+        self.persistence.storage_mut().configure_event_log(capacity)
+    }
+
     pub fn repair(
         &mut self,
         options: LedgerRepairOptions,
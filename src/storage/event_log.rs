@@ -0,0 +1,284 @@
+//! 持久化事件日志
+//!
+//! `event_log` 表以 `seq`（AUTOINCREMENT）记录 CRI 事件与内部事件，序号单调递增、删除后不复用。
+//! 表按容量环形保留：每次追加后删除 `seq <= 最新序号 - 容量` 的记录，因此保留区间内的序号
+//! 始终连续，订阅方可以据此判断断线期间是否有事件已被淘汰。
+//!
+//! 容量写在 `event_log_config` 表中，daemon 启动时配置；shim 等独立进程经账本写事件时读取
+//! 同一配置，未配置或容量为 0 时不写事件日志。
+
+use anyhow::{Context, Result};
+use rusqlite::OptionalExtension;
+
+use super::StorageManager;
+
+/// 追加到事件日志的一条事件。
+#[derive(Debug, Clone, Copy)]
+pub struct EventLogInput<'a> {
+    pub source: &'a str,
+    pub kind: &'a str,
+    pub subject_kind: &'a str,
+    pub subject_id: &'a str,
+    /// 为空时按主体从账本推导。
+    pub pod_id: Option<&'a str>,
+    /// 为空时按 Pod 从账本推导。
+    pub namespace: Option<&'a str>,
+    pub severity: &'a str,
+    pub timestamp_nanos: i64,
+    pub details: Option<&'a str>,
+}
+
+/// 事件日志中的一条记录。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventLogRecord {
+    pub seq: i64,
+    pub timestamp_nanos: i64,
+    pub source: String,
+    pub kind: String,
+    pub subject_kind: String,
+    pub subject_id: String,
+    pub pod_id: String,
+    pub namespace: String,
+    pub severity: String,
+    pub details: Option<String>,
+}
+
+fn event_log_record_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<EventLogRecord> {
+    Ok(EventLogRecord {
+        seq: row.get(0)?,
+        timestamp_nanos: row.get(1)?,
+        source: row.get(2)?,
+        kind: row.get(3)?,
+        subject_kind: row.get(4)?,
+        subject_id: row.get(5)?,
+        pod_id: row.get(6)?,
+        namespace: row.get(7)?,
+        severity: row.get(8)?,
+        details: row.get(9)?,
+    })
+}
+
+fn non_empty(value: Option<&str>) -> Option<&str> {
+    value.filter(|value| !value.trim().is_empty())
+}
+
+impl StorageManager {
+    pub(super) fn init_event_log_tables(&self) -> Result<()> {
+        self.conn
+            .execute(
+                "CREATE TABLE IF NOT EXISTS event_log (
+                seq INTEGER PRIMARY KEY AUTOINCREMENT,
+                timestamp INTEGER NOT NULL,
+                source TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                subject_kind TEXT NOT NULL,
+                subject_id TEXT NOT NULL,
+                pod_id TEXT NOT NULL DEFAULT '',
+                namespace TEXT NOT NULL DEFAULT '',
+                severity TEXT NOT NULL,
+                details TEXT
+            )",
+                [],
+            )
+            .context("Failed to create event_log table")?;
+        self.conn
+            .execute(
+                "CREATE TABLE IF NOT EXISTS event_log_config (
+                id INTEGER PRIMARY KEY CHECK (id = 1),
+                capacity INTEGER NOT NULL
+            )",
+                [],
+            )
+            .context("Failed to create event_log_config table")?;
+        self.conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_event_log_subject ON event_log(subject_kind, subject_id)",
+            [],
+        )?;
+        self.conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_event_log_pod ON event_log(pod_id)",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// 设置事件日志容量；0 表示停用，同时清空已有记录。
+    pub fn configure_event_log(&mut self, capacity: u64) -> Result<()> {
+        let capacity = i64::try_from(capacity).unwrap_or(i64::MAX);
+        self.conn
+            .execute(
+                "INSERT INTO event_log_config (id, capacity) VALUES (1, ?1)
+                 ON CONFLICT(id) DO UPDATE SET capacity = excluded.capacity",
+                [capacity],
+            )
+            .context("Failed to configure event log")?;
+        self.prune_event_log(capacity)?;
+        Ok(())
+    }
+
+    /// 当前事件日志容量；未配置时返回 0。
+    pub fn event_log_capacity(&self) -> Result<u64> {
+        let capacity: Option<i64> = self
+            .conn
+            .query_row(
+                "SELECT capacity FROM event_log_config WHERE id = 1",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(capacity.unwrap_or(0).max(0) as u64)
+    }
+
+    /// 追加一条事件并按容量淘汰最旧的记录；事件日志停用时返回 `None`。
+    pub fn append_event_log(&mut self, input: EventLogInput<'_>) -> Result<Option<EventLogRecord>> {
+        let capacity = i64::try_from(self.event_log_capacity()?).unwrap_or(i64::MAX);
+        if capacity == 0 {
+            return Ok(None);
+        }
+        let pod_id = match non_empty(input.pod_id) {
+            Some(pod_id) => pod_id.to_string(),
+            None => self.event_log_pod_of(input.subject_kind, input.subject_id)?,
+        };
+        let namespace = match non_empty(input.namespace) {
+            Some(namespace) => namespace.to_string(),
+            None => self.event_log_namespace_of(&pod_id)?,
+        };
+        self.conn
+            .execute(
+                "INSERT INTO event_log
+                 (timestamp, source, kind, subject_kind, subject_id, pod_id, namespace, severity, details)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                rusqlite::params![
+                    input.timestamp_nanos,
+                    input.source,
+                    input.kind,
+                    input.subject_kind,
+                    input.subject_id,
+                    pod_id,
+                    namespace,
+                    input.severity,
+                    input.details,
+                ],
+            )
+            .context("Failed to append event log record")?;
+        let seq = self.conn.last_insert_rowid();
+        self.prune_event_log(capacity)?;
+        Ok(Some(EventLogRecord {
+            seq,
+            timestamp_nanos: input.timestamp_nanos,
+            source: input.source.to_string(),
+            kind: input.kind.to_string(),
+            subject_kind: input.subject_kind.to_string(),
+            subject_id: input.subject_id.to_string(),
+            pod_id,
+            namespace,
+            severity: input.severity.to_string(),
+            details: input.details.map(ToString::to_string),
+        }))
+    }
+
+    /// 按序号升序返回 `seq > since_seq` 的至多 `limit` 条记录。
+    pub fn event_log_since(&self, since_seq: i64, limit: usize) -> Result<Vec<EventLogRecord>> {
+        let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+        let mut stmt = self.conn.prepare(
+            "SELECT seq, timestamp, source, kind, subject_kind, subject_id, pod_id, namespace,
+                    severity, details
+             FROM event_log
+             WHERE seq > ?1
+             ORDER BY seq ASC
+             LIMIT ?2",
+        )?;
+        let records = stmt
+            .query_map(
+                rusqlite::params![since_seq, limit],
+                event_log_record_from_row,
+            )?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(records)
+    }
+
+    /// 已分配的最大序号；即使对应记录已被淘汰也不会回退。
+    pub fn event_log_latest_seq(&self) -> Result<i64> {
+        let seq: Option<i64> = self
+            .conn
+            .query_row(
+                "SELECT seq FROM sqlite_sequence WHERE name = 'event_log'",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(seq.unwrap_or(0))
+    }
+
+    fn prune_event_log(&mut self, capacity: i64) -> Result<usize> {
+        let latest = self.event_log_latest_seq()?;
+        let deleted = self
+            .conn
+            .execute(
+                "DELETE FROM event_log WHERE seq <= ?1",
+                [latest.saturating_sub(capacity)],
+            )
+            .context("Failed to prune event log")?;
+        Ok(deleted)
+    }
+
+    /// 推导事件所属 Pod：Pod/网络主体即自身，容器主体查容器表，容器已删除时沿用日志中最近的归属。
+    fn event_log_pod_of(&self, subject_kind: &str, subject_id: &str) -> Result<String> {
+        match subject_kind {
+            "pod" | "network" => Ok(subject_id.to_string()),
+            "container" | "task" | "shim" => {
+                let pod_id: Option<Option<String>> = self
+                    .conn
+                    .query_row(
+                        "SELECT pod_id FROM containers WHERE id = ?1",
+                        [subject_id],
+                        |row| row.get(0),
+                    )
+                    .optional()?;
+                if let Some(pod_id) = pod_id.flatten().filter(|pod_id| !pod_id.is_empty()) {
+                    return Ok(pod_id);
+                }
+                let pod_id: Option<String> = self
+                    .conn
+                    .query_row(
+                        "SELECT pod_id FROM event_log
+                         WHERE subject_kind IN ('container', 'task', 'shim')
+                           AND subject_id = ?1 AND pod_id != ''
+                         ORDER BY seq DESC LIMIT 1",
+                        [subject_id],
+                        |row| row.get(0),
+                    )
+                    .optional()?;
+                Ok(pod_id.unwrap_or_default())
+            }
+            _ => Ok(String::new()),
+        }
+    }
+
+    fn event_log_namespace_of(&self, pod_id: &str) -> Result<String> {
+        if pod_id.is_empty() {
+            return Ok(String::new());
+        }
+        let namespace: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT namespace FROM pod_sandboxes WHERE id = ?1",
+                [pod_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if let Some(namespace) = namespace.filter(|namespace| !namespace.is_empty()) {
+            return Ok(namespace);
+        }
+        let namespace: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT namespace FROM event_log
+                 WHERE pod_id = ?1 AND namespace != ''
+                 ORDER BY seq DESC LIMIT 1",
+                [pod_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(namespace.unwrap_or_default())
+    }
+}
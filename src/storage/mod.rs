@@ -7,7 +7,9 @@ use log::{debug, info};
 use rusqlite::{Connection, OptionalExtension};
 use std::path::Path;
 
+pub mod event_log;
 pub mod volume;
+pub use event_log::{EventLogInput, EventLogRecord};
 pub use volume::{MountedVolume, VolumeConfig, VolumeManager, VolumeType};
 
 const CURRENT_SCHEMA_VERSION: i64 = 2;
@@ -474,6 +476,7 @@ impl StorageManager {
             .context("Failed to create events table")?;
         self.ensure_event_type_column()?;
         self.migrate_state_events_to_events()?;
+        self.init_event_log_tables()?;
         self.conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_image_refs_image_id ON image_refs(image_id)",
             [],
@@ -2200,6 +2203,87 @@ mod tests {
         assert_eq!(legacy_count, 0);
     }
 
+    #[test]
+    fn event_log_is_a_ring_with_monotonic_sequences_and_resolves_pod_scope() {
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let mut manager = StorageManager::new(&db_path).unwrap();
+        let input = |subject_id| EventLogInput {
+            source: "internal",
+            kind: "container.state",
+            subject_kind: "container",
+            subject_id,
+            pod_id: None,
+            namespace: None,
+            severity: "info",
+            timestamp_nanos: 1,
+            details: None,
+        };
+
+        assert!(manager.append_event_log(input("ctr-1")).unwrap().is_none());
+        manager.configure_event_log(3).unwrap();
+        manager
+            .save_pod_sandbox(&PodSandboxRecord {
+                id: "pod-1".to_string(),
+                state: "ready".to_string(),
+                name: "web".to_string(),
+                namespace: "team-a".to_string(),
+                uid: "uid-1".to_string(),
+                created_at: 1,
+                netns_path: String::new(),
+                labels: "{}".to_string(),
+                annotations: "{}".to_string(),
+                pause_container_id: None,
+                ip: None,
+            })
+            .unwrap();
+        manager
+            .save_container(&ContainerRecord {
+                id: "ctr-1".to_string(),
+                pod_id: Some("pod-1".to_string()),
+                state: "created".to_string(),
+                image: "test:latest".to_string(),
+                command: "sleep 60".to_string(),
+                created_at: 1,
+                labels: "{}".to_string(),
+                annotations: "{}".to_string(),
+                exit_code: None,
+                exit_time: None,
+                runtime_handler: None,
+                runtime_backend: None,
+                snapshot_key: None,
+            })
+            .unwrap();
+
+        let first = manager.append_event_log(input("ctr-1")).unwrap().unwrap();
+        assert_eq!(first.pod_id, "pod-1");
+        assert_eq!(first.namespace, "team-a");
+
+        // 容器和 Pod 记录删除后沿用日志中最近的归属。
+        manager.delete_container("ctr-1").unwrap();
+        manager.delete_pod_sandbox("pod-1").unwrap();
+        for _ in 0..3 {
+            manager.append_event_log(input("ctr-1")).unwrap();
+        }
+        let retained = manager.event_log_since(0, 10).unwrap();
+        assert_eq!(
+            retained.iter().map(|record| record.seq).collect::<Vec<_>>(),
+            vec![first.seq + 1, first.seq + 2, first.seq + 3]
+        );
+        assert!(retained
+            .iter()
+            .all(|record| record.pod_id == "pod-1" && record.namespace == "team-a"));
+        assert_eq!(manager.event_log_latest_seq().unwrap(), first.seq + 3);
+
+        // 停用后清空记录，但序号不会回退。
+        manager.configure_event_log(0).unwrap();
+        assert!(manager.event_log_since(0, 10).unwrap().is_empty());
+        manager.configure_event_log(3).unwrap();
+        let next = manager.append_event_log(input("ctr-2")).unwrap().unwrap();
+        assert_eq!(next.seq, first.seq + 4);
+        assert_eq!(next.pod_id, "");
+    }
+
     #[test]
     fn legacy_state_events_are_migrated_to_unified_events_once() {
         let temp_dir = tempdir().unwrap();
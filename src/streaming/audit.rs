@@ -0,0 +1,822 @@
+//! exec/attach 会话审计
+//!
+//! 会话升级后按配置记录审计信息：
+//! - 会话开始/结束时把元数据（谁、哪个 token、命令、tty、起止时间、退出码、字节数）交给
+//!   审计 sink，由运行时写入内部事件账本
+//! - 可选地把输入输出转录为 asciicast v2 文件，超过大小上限后截断
+//!
+//! 转录在独立线程中完成：连接读写只把原始字节送入通道，协议帧（SPDY / websocket
+//! channel）的解析与落盘都不占用会话任务。
+
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use super::config::StreamingAuditConfig;
+use super::sessions::SessionCounters;
+use super::spdy;
+use super::{WS_CHANNEL_RESIZE, WS_CHANNEL_STDERR, WS_CHANNEL_STDIN, WS_CHANNEL_STDOUT};
+
+const TRANSCRIPT_EXTENSION: &str = "cast";
+const TRANSCRIPT_DEFAULT_WIDTH: u16 = 80;
+const TRANSCRIPT_DEFAULT_HEIGHT: u16 = 24;
+const TRANSCRIPT_TRUNCATED_MARKER: &str = "transcript truncated";
+/// 单个 websocket 帧的解析上限；超过后视为无法解析，停止转录该方向。
+const MAX_DECODED_FRAME_BYTES: u64 = 16 * 1024 * 1024;
+
+/// 需要审计的会话目标，由 CRI 层在签发 token 时填充。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StreamingAuditTarget {
+    pub pod_sandbox_id: String,
+    pub pod_namespace: String,
+    pub pod_name: String,
+    pub runtime_handler: String,
+    /// exec 的命令行；attach 为空。
+    pub command: Vec<String>,
+    pub tty: bool,
+}
+
+/// 一次会话的审计记录，开始与结束时各产出一次。
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct StreamingAuditRecord {
+    pub session_id: String,
+    pub token: String,
+    pub kind: String,
+    pub container_id: String,
+    pub pod_sandbox_id: String,
+    pub pod_namespace: String,
+    pub pod_name: String,
+    pub runtime_handler: String,
+    pub client_address: Option<String>,
+    pub protocol: String,
+    pub command: Vec<String>,
+    pub tty: bool,
+    pub started_at_unix_millis: i64,
+    pub ended_at_unix_millis: Option<i64>,
+    /// 经 shim 转发的 exec 与 attach 会话拿不到退出码。
+    pub exit_code: Option<i32>,
+    pub bytes_received: u64,
+    pub bytes_sent: u64,
+    pub transcript_path: Option<String>,
+    pub transcript_truncated: bool,
+}
+
+impl StreamingAuditRecord {
+    pub fn ended(&self) -> bool {
+        self.ended_at_unix_millis.is_some()
+    }
+
+    /// 内部事件类型，例如 `exec.session_started` / `attach.session_ended`。
+    pub fn event_kind(&self) -> String {
+        let phase = if self.ended() {
+            "session_ended"
+        } else {
+            "session_started"
+        };
+        format!("{}.{}", self.kind, phase)
+    }
+}
+
+pub type StreamingAuditSink = dyn Fn(&StreamingAuditRecord) + Send + Sync;
+
+/// 会话审计器；未安装 sink 时审计记录只写调试日志。
+#[derive(Clone)]
+pub struct StreamingAuditor {
+    config: StreamingAuditConfig,
+    sink: Arc<Mutex<Option<Arc<StreamingAuditSink>>>>,
+}
+
+impl std::fmt::Debug for StreamingAuditor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamingAuditor")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl StreamingAuditor {
+    pub fn new(config: StreamingAuditConfig) -> Self {
+        Self {
+            config,
+            sink: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub fn config(&self) -> &StreamingAuditConfig {
+        &self.config
+    }
+
+    pub fn set_sink(&self, sink: impl Fn(&StreamingAuditRecord) + Send + Sync + 'static) {
+        if let Ok(mut current) = self.sink.lock() {
+            *current = Some(Arc::new(sink));
+        }
+    }
+
+    /// 会话转录文件路径；会话 ID 只允许字母和数字，避免拼出目录之外的路径。
+    pub fn transcript_path(&self, session_id: &str) -> Option<PathBuf> {
+        if session_id.is_empty() || !session_id.chars().all(|ch| ch.is_ascii_alphanumeric()) {
+            return None;
+        }
+        Some(
+            Path::new(self.config.transcript_dir.trim())
+                .join(format!("{session_id}.{TRANSCRIPT_EXTENSION}")),
+        )
+    }
+
+    fn emit(&self, record: &StreamingAuditRecord) {
+        let sink = self.sink.lock().ok().and_then(|sink| sink.clone());
+        match sink {
+            Some(sink) => sink(record),
+            None => log::debug!(
+                "Streaming audit record without sink: {} {} session {}",
+                record.kind,
+                record.container_id,
+                record.session_id
+            ),
+        }
+    }
+
+    /// 记录会话开始；返回的 [`AuditedSession`] 在会话结束（含被强制断开）时产出结束记录。
+    pub(crate) fn begin(
+        &self,
+        mut record: StreamingAuditRecord,
+        counters: Arc<SessionCounters>,
+    ) -> (AuditedSession, Option<TranscriptTap>) {
+        let transcript = if self.config.record_transcripts {
+            self.open_transcript(&record)
+        } else {
+            None
+        };
+        record.transcript_path = transcript
+            .as_ref()
+            .map(|(path, _)| path.display().to_string());
+        self.emit(&record);
+
+        let (transcript, tap) = match transcript {
+            Some((_, tx)) => (Some(tx.clone()), Some(TranscriptTap { tx })),
+            None => (None, None),
+        };
+        (
+            AuditedSession {
+                auditor: self.clone(),
+                record: Some(record),
+                counters,
+                transcript,
+            },
+            tap,
+        )
+    }
+
+    fn open_transcript(
+        &self,
+        record: &StreamingAuditRecord,
+    ) -> Option<(PathBuf, mpsc::Sender<TapChunk>)> {
+        let path = self.transcript_path(&record.session_id)?;
+        let writer = match AsciicastWriter::create(&path, record, self.config.max_transcript_bytes)
+        {
+            Ok(writer) => writer,
+            Err(err) => {
+                log::warn!(
+                    "Failed to open streaming transcript {}: {}",
+                    path.display(),
+                    err
+                );
+                return None;
+            }
+        };
+        let decoder = TranscriptDecoder::for_protocol(&record.protocol);
+        let (tx, rx) = mpsc::channel();
+        let auditor = self.clone();
+        let spawned = std::thread::Builder::new()
+            .name("crius-stream-audit".to_string())
+            .spawn(move || run_transcript(auditor, decoder, writer, rx));
+        if let Err(err) = spawned {
+            log::warn!("Failed to start streaming transcript writer: {}", err);
+            return None;
+        }
+        Some((path, tx))
+    }
+}
+
+/// 会话审计句柄，随会话任务一起释放。
+pub(crate) struct AuditedSession {
+    auditor: StreamingAuditor,
+    record: Option<StreamingAuditRecord>,
+    counters: Arc<SessionCounters>,
+    transcript: Option<mpsc::Sender<TapChunk>>,
+}
+
+impl Drop for AuditedSession {
+    fn drop(&mut self) {
+        let Some(mut record) = self.record.take() else {
+            return;
+        };
+        record.ended_at_unix_millis = Some(chrono::Utc::now().timestamp_millis());
+        record.exit_code = self.counters.exit_code();
+        record.bytes_received = self.counters.bytes_received();
+        record.bytes_sent = self.counters.bytes_sent();
+        // 有转录时由写入线程落盘完成后再产出结束记录，以便带上截断标记。
+        if let Some(transcript) = self.transcript.take() {
+            match transcript.send(TapChunk::Close(Box::new(record))) {
+                Ok(()) => return,
+                Err(mpsc::SendError(chunk)) => {
+                    if let TapChunk::Close(record) = chunk {
+                        self.auditor.emit(&record);
+                    }
+                    return;
+                }
+            }
+        }
+        self.auditor.emit(&record);
+    }
+}
+
+enum TapChunk {
+    Input(Vec<u8>),
+    Output(Vec<u8>),
+    Close(Box<StreamingAuditRecord>),
+}
+
+/// 连接读写的旁路，把原始字节交给转录线程。
+#[derive(Clone)]
+pub(crate) struct TranscriptTap {
+    tx: mpsc::Sender<TapChunk>,
+}
+
+impl TranscriptTap {
+    pub(crate) fn input(&self, data: &[u8]) {
+        if !data.is_empty() {
+            let _ = self.tx.send(TapChunk::Input(data.to_vec()));
+        }
+    }
+
+    pub(crate) fn output(&self, data: &[u8]) {
+        if !data.is_empty() {
+            let _ = self.tx.send(TapChunk::Output(data.to_vec()));
+        }
+    }
+}
+
+fn run_transcript(
+    auditor: StreamingAuditor,
+    mut decoder: TranscriptDecoder,
+    mut writer: AsciicastWriter,
+    rx: mpsc::Receiver<TapChunk>,
+) {
+    let mut closed = None;
+    for chunk in rx {
+        let events = match chunk {
+            TapChunk::Input(data) => decoder.input(&data),
+            TapChunk::Output(data) => decoder.output(&data),
+            TapChunk::Close(record) => {
+                closed = Some(record);
+                break;
+            }
+        };
+        for event in events {
+            if let Err(err) = writer.write_event(event) {
+                log::warn!("Failed to write streaming transcript: {}", err);
+            }
+        }
+    }
+    if let Err(err) = writer.finish() {
+        log::warn!("Failed to flush streaming transcript: {}", err);
+    }
+    if let Some(mut record) = closed {
+        record.transcript_truncated = writer.truncated;
+        auditor.emit(&record);
+    }
+}
+
+/// 从连接字节流中还原出的终端事件。
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TranscriptEvent {
+    Input(Vec<u8>),
+    Output(Vec<u8>),
+    Resize { width: u16, height: u16 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TranscriptStream {
+    Input,
+    Output,
+    Resize,
+    Ignored,
+}
+
+/// 被动解析会话协议帧，不影响会话本身。解析失败的方向停止转录。
+enum TranscriptDecoder {
+    Spdy {
+        input: Vec<u8>,
+        output: Vec<u8>,
+        decompressor: Box<spdy::HeaderDecompressor>,
+        streams: HashMap<u32, TranscriptStream>,
+        broken: bool,
+    },
+    Websocket {
+        input: WebsocketFrameBuffer,
+        output: WebsocketFrameBuffer,
+    },
+}
+
+impl TranscriptDecoder {
+    fn for_protocol(protocol: &str) -> Self {
+        if protocol.starts_with("websocket/") {
+            Self::Websocket {
+                input: WebsocketFrameBuffer::default(),
+                output: WebsocketFrameBuffer::default(),
+            }
+        } else {
+            Self::Spdy {
+                input: Vec::new(),
+                output: Vec::new(),
+                decompressor: Box::new(spdy::HeaderDecompressor::new()),
+                streams: HashMap::new(),
+                broken: false,
+            }
+        }
+    }
+
+    fn input(&mut self, data: &[u8]) -> Vec<TranscriptEvent> {
+        match self {
+            Self::Spdy {
+                input,
+                decompressor,
+                streams,
+                broken,
+                ..
+            } => {
+                if *broken {
+                    return Vec::new();
+                }
+                input.extend_from_slice(data);
+                let mut events = Vec::new();
+                loop {
+                    match take_spdy_frame(input) {
+                        Ok(Some(spdy::Frame::SynStream(frame))) => {
+                            match spdy::decode_header_block(&frame.header_block, decompressor) {
+                                Ok(headers) => {
+                                    let stream = match spdy::header_value(&headers, "streamtype") {
+                                        Some("stdin") => TranscriptStream::Input,
+                                        Some("stdout") | Some("stderr") => TranscriptStream::Output,
+                                        Some("resize") => TranscriptStream::Resize,
+                                        _ => TranscriptStream::Ignored,
+                                    };
+                                    streams.insert(frame.stream_id, stream);
+                                }
+                                Err(_) => {
+                                    *broken = true;
+                                    break;
+                                }
+                            }
+                        }
+                        Ok(Some(spdy::Frame::Data(frame))) => {
+                            if let Some(event) = spdy_data_event(streams, frame) {
+                                events.push(event);
+                            }
+                        }
+                        Ok(Some(_)) => {}
+                        Ok(None) => break,
+                        Err(_) => {
+                            *broken = true;
+                            break;
+                        }
+                    }
+                }
+                events
+            }
+            Self::Websocket { input, .. } => input.push(data),
+        }
+    }
+
+    fn output(&mut self, data: &[u8]) -> Vec<TranscriptEvent> {
+        match self {
+            Self::Spdy {
+                output,
+                streams,
+                broken,
+                ..
+            } => {
+                if *broken {
+                    return Vec::new();
+                }
+                output.extend_from_slice(data);
+                let mut events = Vec::new();
+                loop {
+                    match take_spdy_frame(output) {
+                        Ok(Some(spdy::Frame::Data(frame))) => {
+                            if let Some(event) = spdy_data_event(streams, frame) {
+                                events.push(event);
+                            }
+                        }
+                        Ok(Some(_)) => {}
+                        Ok(None) => break,
+                        Err(_) => {
+                            *broken = true;
+                            break;
+                        }
+                    }
+                }
+                events
+            }
+            Self::Websocket { output, .. } => output.push(data),
+        }
+    }
+}
+
+/// 缓冲区里有完整 SPDY 帧时取出一帧。
+fn take_spdy_frame(buffer: &mut Vec<u8>) -> io::Result<Option<spdy::Frame>> {
+    if buffer.len() < 8 {
+        return Ok(None);
+    }
+    let length = u32::from_be_bytes([0, buffer[5], buffer[6], buffer[7]]) as usize;
+    if buffer.len() < 8 + length {
+        return Ok(None);
+    }
+    let frame_bytes = buffer.drain(..8 + length).collect::<Vec<_>>();
+    spdy::read_frame(&mut frame_bytes.as_slice()).map(Some)
+}
+
+fn spdy_data_event(
+    streams: &HashMap<u32, TranscriptStream>,
+    frame: spdy::DataFrame,
+) -> Option<TranscriptEvent> {
+    if frame.data.is_empty() {
+        return None;
+    }
+    match streams.get(&frame.stream_id)? {
+        TranscriptStream::Input => Some(TranscriptEvent::Input(frame.data)),
+        TranscriptStream::Output => Some(TranscriptEvent::Output(frame.data)),
+        TranscriptStream::Resize => resize_event(&frame.data),
+        TranscriptStream::Ignored => None,
+    }
+}
+
+fn resize_event(data: &[u8]) -> Option<TranscriptEvent> {
+    #[derive(Deserialize)]
+    struct TerminalSize {
+        #[serde(rename = "Width")]
+        width: u16,
+        #[serde(rename = "Height")]
+        height: u16,
+    }
+    let size = serde_json::from_slice::<TerminalSize>(data).ok()?;
+    Some(TranscriptEvent::Resize {
+        width: size.width,
+        height: size.height,
+    })
+}
+
+/// 单个方向的 websocket 帧缓冲。
+#[derive(Default)]
+struct WebsocketFrameBuffer {
+    pending: Vec<u8>,
+    /// 分片消息沿用首帧的 channel。
+    channel: Option<u8>,
+    broken: bool,
+}
+
+impl WebsocketFrameBuffer {
+    fn push(&mut self, data: &[u8]) -> Vec<TranscriptEvent> {
+        if self.broken {
+            return Vec::new();
+        }
+        self.pending.extend_from_slice(data);
+        let mut events = Vec::new();
+        loop {
+            match self.take_frame() {
+                Ok(Some((opcode, payload))) => {
+                    if let Some(event) = self.channel_event(opcode, payload) {
+                        events.push(event);
+                    }
+                }
+                Ok(None) => break,
+                Err(()) => {
+                    self.broken = true;
+                    self.pending.clear();
+                    break;
+                }
+            }
+        }
+        events
+    }
+
+    fn take_frame(&mut self) -> Result<Option<(u8, Vec<u8>)>, ()> {
+        let buffer = &self.pending;
+        if buffer.len() < 2 {
+            return Ok(None);
+        }
+        let opcode = buffer[0] & 0x0f;
+        let masked = buffer[1] & 0x80 != 0;
+        let mut offset = 2usize;
+        let length = match buffer[1] & 0x7f {
+            126 => {
+                if buffer.len() < offset + 2 {
+                    return Ok(None);
+                }
+                let length = u16::from_be_bytes([buffer[2], buffer[3]]) as u64;
+                offset += 2;
+                length
+            }
+            127 => {
+                if buffer.len() < offset + 8 {
+                    return Ok(None);
+                }
+                let mut bytes = [0u8; 8];
+                bytes.copy_from_slice(&buffer[2..10]);
+                offset += 8;
+                u64::from_be_bytes(bytes)
+            }
+            length => u64::from(length),
+        };
+        if length > MAX_DECODED_FRAME_BYTES {
+            return Err(());
+        }
+        let mask = if masked {
+            if buffer.len() < offset + 4 {
+                return Ok(None);
+            }
+            let mut mask = [0u8; 4];
+            mask.copy_from_slice(&buffer[offset..offset + 4]);
+            offset += 4;
+            Some(mask)
+        } else {
+            None
+        };
+        let end = offset + length as usize;
+        if buffer.len() < end {
+            return Ok(None);
+        }
+        let mut payload = buffer[offset..end].to_vec();
+        if let Some(mask) = mask {
+            for (index, byte) in payload.iter_mut().enumerate() {
+                *byte ^= mask[index % 4];
+            }
+        }
+        self.pending.drain(..end);
+        Ok(Some((opcode, payload)))
+    }
+
+    fn channel_event(&mut self, opcode: u8, payload: Vec<u8>) -> Option<TranscriptEvent> {
+        let (channel, data) = match opcode {
+            0x2 => {
+                let (&channel, data) = payload.split_first()?;
+                self.channel = Some(channel);
+                (channel, data.to_vec())
+            }
+            0x0 => (self.channel?, payload),
+            _ => return None,
+        };
+        if data.is_empty() {
+            return None;
+        }
+        match channel {
+            WS_CHANNEL_STDIN => Some(TranscriptEvent::Input(data)),
+            WS_CHANNEL_STDOUT | WS_CHANNEL_STDERR => Some(TranscriptEvent::Output(data)),
+            WS_CHANNEL_RESIZE => resize_event(&data),
+            _ => None,
+        }
+    }
+}
+
+/// asciicast v2 写入器。
+struct AsciicastWriter {
+    file: BufWriter<File>,
+    started: Instant,
+    written: u64,
+    max_bytes: u64,
+    input_pending: Vec<u8>,
+    output_pending: Vec<u8>,
+    truncated: bool,
+}
+
+impl AsciicastWriter {
+    fn create(path: &Path, record: &StreamingAuditRecord, max_bytes: u64) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::DirBuilder::new()
+                .recursive(true)
+                .mode(0o700)
+                .create(parent)?;
+        }
+        let file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(path)?;
+        let mut writer = Self {
+            file: BufWriter::new(file),
+            started: Instant::now(),
+            written: 0,
+            max_bytes,
+            input_pending: Vec::new(),
+            output_pending: Vec::new(),
+            truncated: false,
+        };
+        let header = serde_json::json!({
+            "version": 2,
+            "width": TRANSCRIPT_DEFAULT_WIDTH,
+            "height": TRANSCRIPT_DEFAULT_HEIGHT,
+            "timestamp": record.started_at_unix_millis / 1000,
+            "title": format!("{} {}", record.kind, record.container_id),
+            "command": record.command.join(" "),
+            "env": {"TERM": "xterm"},
+        });
+        writer.write_line(&header.to_string())?;
+        Ok(writer)
+    }
+
+    fn write_event(&mut self, event: TranscriptEvent) -> io::Result<()> {
+        if self.truncated {
+            return Ok(());
+        }
+        let (code, data) = match event {
+            TranscriptEvent::Input(data) => ("i", take_utf8(&mut self.input_pending, &data)),
+            TranscriptEvent::Output(data) => ("o", take_utf8(&mut self.output_pending, &data)),
+            TranscriptEvent::Resize { width, height } => ("r", format!("{width}x{height}")),
+        };
+        if data.is_empty() {
+            return Ok(());
+        }
+        let line = self.event_line(code, &data);
+        if self.written + line.len() as u64 + 1 > self.max_bytes {
+            self.truncated = true;
+            let marker = self.event_line("m", TRANSCRIPT_TRUNCATED_MARKER);
+            return self.write_line(&marker);
+        }
+        self.write_line(&line)
+    }
+
+    fn event_line(&self, code: &str, data: &str) -> String {
+        serde_json::json!([self.started.elapsed().as_secs_f64(), code, data]).to_string()
+    }
+
+    fn write_line(&mut self, line: &str) -> io::Result<()> {
+        self.file.write_all(line.as_bytes())?;
+        self.file.write_all(b"\n")?;
+        self.written += line.len() as u64 + 1;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// 解码 UTF-8，末尾不完整的字符留到下一段；非法字节替换为 U+FFFD。
+fn take_utf8(pending: &mut Vec<u8>, data: &[u8]) -> String {
+    pending.extend_from_slice(data);
+    let mut decoded = String::new();
+    loop {
+        match std::str::from_utf8(pending) {
+            Ok(text) => {
+                decoded.push_str(text);
+                pending.clear();
+                return decoded;
+            }
+            Err(err) => {
+                let valid = err.valid_up_to();
+                decoded.push_str(std::str::from_utf8(&pending[..valid]).unwrap_or_default());
+                match err.error_len() {
+                    Some(invalid) => {
+                        decoded.push(char::REPLACEMENT_CHARACTER);
+                        pending.drain(..valid + invalid);
+                    }
+                    None => {
+                        pending.drain(..valid);
+                        return decoded;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn syn_stream(writer: &mut spdy::SpdyWriter<Vec<u8>>, stream_id: u32, stream_type: &str) {
+        writer
+            .write_syn_stream(
+                stream_id,
+                0,
+                &[("streamtype".to_string(), stream_type.to_string())],
+                false,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn spdy_decoder_maps_streams_to_input_output_and_resize() {
+        let mut client = spdy::SpdyWriter::new(Vec::new());
+        syn_stream(&mut client, 1, "stdin");
+        syn_stream(&mut client, 3, "stdout");
+        syn_stream(&mut client, 5, "resize");
+        client.write_data(1, b"ls\n", false).unwrap();
+        client
+            .write_data(5, br#"{"Width":120,"Height":40}"#, false)
+            .unwrap();
+        let input = client.into_inner();
+
+        let mut decoder = TranscriptDecoder::for_protocol("spdy/v4.channel.k8s.io");
+        // 分两段送入，覆盖帧跨读边界的情况。
+        let (first, second) = input.split_at(7);
+        assert!(decoder.input(first).is_empty());
+        assert_eq!(
+            decoder.input(second),
+            vec![
+                TranscriptEvent::Input(b"ls\n".to_vec()),
+                TranscriptEvent::Resize {
+                    width: 120,
+                    height: 40
+                },
+            ]
+        );
+
+        let mut server = spdy::SpdyWriter::new(Vec::new());
+        server.write_data(3, b"bin\n", false).unwrap();
+        assert_eq!(
+            decoder.output(&server.into_inner()),
+            vec![TranscriptEvent::Output(b"bin\n".to_vec())]
+        );
+    }
+
+    #[test]
+    fn websocket_decoder_unmasks_client_frames_and_follows_continuations() {
+        let mask = [1u8, 2, 3, 4];
+        let payload = [WS_CHANNEL_STDIN, b'i', b'd'];
+        let mut frame = vec![0x82, 0x80 | payload.len() as u8];
+        frame.extend_from_slice(&mask);
+        frame.extend(
+            payload
+                .iter()
+                .enumerate()
+                .map(|(index, byte)| byte ^ mask[index % 4]),
+        );
+
+        let mut decoder = TranscriptDecoder::for_protocol("websocket/v5.channel.k8s.io");
+        assert_eq!(
+            decoder.input(&frame),
+            vec![TranscriptEvent::Input(b"id".to_vec())]
+        );
+
+        let mut output = vec![0x02, 3, WS_CHANNEL_STDOUT, b'u', b'i'];
+        output.extend_from_slice(&[0x80, 2, b'd', b'=']);
+        assert_eq!(
+            decoder.output(&output),
+            vec![
+                TranscriptEvent::Output(b"ui".to_vec()),
+                TranscriptEvent::Output(b"d=".to_vec()),
+            ]
+        );
+    }
+
+    #[test]
+    fn asciicast_writer_carries_partial_utf8_and_truncates_at_cap() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("audit/session.cast");
+        let record = StreamingAuditRecord {
+            kind: "exec".to_string(),
+            container_id: "c1".to_string(),
+            command: vec!["sh".to_string()],
+            ..Default::default()
+        };
+        let mut writer = AsciicastWriter::create(&path, &record, 220).unwrap();
+        let snowman = "☃".as_bytes();
+        writer
+            .write_event(TranscriptEvent::Output(snowman[..1].to_vec()))
+            .unwrap();
+        writer
+            .write_event(TranscriptEvent::Output(snowman[1..].to_vec()))
+            .unwrap();
+        writer
+            .write_event(TranscriptEvent::Output(vec![b'x'; 200]))
+            .unwrap();
+        writer
+            .write_event(TranscriptEvent::Output(b"dropped".to_vec()))
+            .unwrap();
+        writer.finish().unwrap();
+        assert!(writer.truncated);
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines = contents.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+        let header: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(header["version"], 2);
+        assert_eq!(header["title"], "exec c1");
+        let event: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(event[1], "o");
+        assert_eq!(event[2], "☃");
+        let marker: serde_json::Value = serde_json::from_str(lines[2]).unwrap();
+        assert_eq!(marker[1], "m");
+        assert_eq!(marker[2], TRANSCRIPT_TRUNCATED_MARKER);
+    }
+}
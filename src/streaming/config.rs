@@ -17,6 +17,8 @@ const DEFAULT_STREAMING_REQUEST_TTL: Duration = Duration::from_secs(30);
 const DEFAULT_PORT_FORWARD_STREAM_CREATION_TIMEOUT: Duration = Duration::from_secs(30);
 const DEFAULT_PORT_FORWARD_STREAM_IDLE_TIMEOUT: Duration = Duration::from_secs(4 * 60 * 60);
 const DEFAULT_TLS_MIN_VERSION: &str = "VersionTLS12";
+const DEFAULT_AUDIT_TRANSCRIPT_DIR: &str = "/var/log/crius/streaming-audit";
+const DEFAULT_AUDIT_MAX_TRANSCRIPT_BYTES: u64 = 16 * 1024 * 1024;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(default)]
@@ -44,6 +46,72 @@ pub struct StreamingConfig {
         serialize_with = "serialize_duration"
     )]
     pub port_forward_idle_timeout: Duration,
+    /// 节点上同时存在的 exec/attach/port-forward 会话上限，0 表示不限制。
+    pub max_sessions: usize,
+    /// 单个容器（port-forward 按 Pod 沙箱）的并发会话上限，0 表示不限制。
+    pub max_sessions_per_container: usize,
+    pub audit: StreamingAuditConfig,
+}
+
+/// exec/attach 会话审计配置。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct StreamingAuditConfig {
+    /// 把会话元数据写入内部事件账本。
+    pub enabled: bool,
+    /// 只审计这些 runtime handler 的会话；与 `namespaces` 同时为空时审计全部会话。
+    pub runtime_handlers: Vec<String>,
+    /// 只审计这些 Pod namespace 的会话。
+    pub namespaces: Vec<String>,
+    /// 额外记录 asciicast v2 格式的输入输出转录。
+    pub record_transcripts: bool,
+    pub transcript_dir: String,
+    /// 单个转录文件的大小上限，超过后截断。
+    pub max_transcript_bytes: u64,
+}
+
+impl Default for StreamingAuditConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            runtime_handlers: Vec::new(),
+            namespaces: Vec::new(),
+            record_transcripts: false,
+            transcript_dir: DEFAULT_AUDIT_TRANSCRIPT_DIR.to_string(),
+            max_transcript_bytes: DEFAULT_AUDIT_MAX_TRANSCRIPT_BYTES,
+        }
+    }
+}
+
+impl StreamingAuditConfig {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if self.record_transcripts {
+            if !Path::new(self.transcript_dir.trim()).is_absolute() {
+                anyhow::bail!("api.streaming.audit.transcript_dir must be an absolute path");
+            }
+            if self.max_transcript_bytes == 0 {
+                anyhow::bail!("api.streaming.audit.max_transcript_bytes must be greater than zero");
+            }
+        }
+        Ok(())
+    }
+
+    /// 判断某个 runtime handler / namespace 下的会话是否需要审计。
+    pub fn applies_to(&self, runtime_handler: &str, namespace: &str) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        if self.runtime_handlers.is_empty() && self.namespaces.is_empty() {
+            return true;
+        }
+        self.runtime_handlers
+            .iter()
+            .any(|handler| handler == runtime_handler)
+            || self.namespaces.iter().any(|value| value == namespace)
+    }
 }
 
 impl Default for StreamingConfig {
@@ -60,6 +128,9 @@ impl Default for StreamingConfig {
             request_token_ttl: DEFAULT_STREAMING_REQUEST_TTL,
             port_forward_stream_creation_timeout: DEFAULT_PORT_FORWARD_STREAM_CREATION_TIMEOUT,
             port_forward_idle_timeout: DEFAULT_PORT_FORWARD_STREAM_IDLE_TIMEOUT,
+            max_sessions: 0,
+            max_sessions_per_container: 0,
+            audit: StreamingAuditConfig::default(),
         }
     }
 }
@@ -99,9 +170,22 @@ impl StreamingConfig {
                 "api.streaming.port_forward_stream_creation_timeout must be greater than zero"
             );
         }
+        if self.max_sessions != 0 && self.max_sessions_per_container > self.max_sessions {
+            anyhow::bail!(
+                "api.streaming.max_sessions_per_container must not exceed api.streaming.max_sessions"
+            );
+        }
+        self.audit.validate()?;
         Ok(())
     }
 
+    pub(super) fn session_limits(&self) -> super::StreamingSessionLimits {
+        super::StreamingSessionLimits {
+            max_sessions: self.max_sessions,
+            max_sessions_per_container: self.max_sessions_per_container,
+        }
+    }
+
     pub(super) fn bind_target(&self) -> (&str, u16) {
         (self.address.as_str(), self.port)
     }
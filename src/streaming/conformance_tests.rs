@@ -0,0 +1,347 @@
+//! websocket streaming 协议的 golden-frame 一致性测试。
+//!
+//! 每个用例按 kubectl（client-go 的 gorilla websocket）在线路上的帧格式记录一段会话：客户端帧
+//! 使用固定掩码 `37 fa 21 3d`，服务端帧不带掩码。用例在进程内启动 `StreamingServer`，通过真实
+//! TCP 连接完成升级后逐字节比对服务端写出的帧。
+
+use super::*;
+
+const CLIENT_WEBSOCKET_CLOSE: &str = "88 80 37 fa 21 3d";
+const SERVER_WEBSOCKET_CLOSE: &str = "88 00";
+const FRAME_TIMEOUT: Duration = Duration::from_secs(5);
+
+enum Step {
+    Send(String),
+    Expect(String),
+}
+
+fn send(hex: &str) -> Step {
+    Step::Send(hex.to_string())
+}
+
+fn expect(hex: &str) -> Step {
+    Step::Expect(hex.to_string())
+}
+
+fn hex_bytes(hex: &str) -> Vec<u8> {
+    hex.split_whitespace()
+        .map(|byte| u8::from_str_radix(byte, 16).expect("golden frame should be hex"))
+        .collect()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 负载较长（如 `metav1.Status`）的服务端二进制帧，按 RFC 6455 编码长度。
+fn server_channel_frame(channel: u8, payload: &[u8]) -> String {
+    let length = payload.len() + 1;
+    let mut frame = vec![0x82];
+    if length < 126 {
+        frame.push(length as u8);
+    } else {
+        frame.push(126);
+        frame.extend_from_slice(&(length as u16).to_be_bytes());
+    }
+    frame.push(channel);
+    frame.extend_from_slice(payload);
+    to_hex(&frame)
+}
+
+async fn start_server() -> StreamingServer {
+    StreamingServer::start_with_config(
+        StreamingConfig {
+            address: "127.0.0.1".to_string(),
+            port: 0,
+            ..StreamingConfig::default()
+        },
+        PathBuf::from("/bin/false"),
+    )
+    .await
+    .expect("streaming server should start")
+}
+
+/// 按 kubectl 的请求头完成 websocket 升级，返回升级后的连接。
+async fn websocket_upgrade(url: &str, protocol: &str) -> TcpStream {
+    let (authority, path) = url
+        .strip_prefix("http://")
+        .and_then(|rest| rest.split_once('/'))
+        .expect("streaming url should be http");
+    let mut stream = TcpStream::connect(authority)
+        .await
+        .expect("streaming server should accept connections");
+    let request = format!(
+        "GET /{path} HTTP/1.1\r\nHost: {authority}\r\nConnection: Upgrade\r\nUpgrade: websocket\r\n\
+         Sec-WebSocket-Version: 13\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\
+         Sec-WebSocket-Protocol: {protocol}\r\n\r\n"
+    );
+    stream
+        .write_all(request.as_bytes())
+        .await
+        .expect("upgrade request should be written");
+
+    let mut head = Vec::new();
+    while !head.ends_with(b"\r\n\r\n") {
+        let byte = tokio::time::timeout(FRAME_TIMEOUT, stream.read_u8())
+            .await
+            .expect("upgrade response should arrive")
+            .expect("upgrade response should be readable");
+        head.push(byte);
+    }
+    let head = String::from_utf8_lossy(&head).to_ascii_lowercase();
+    assert!(head.starts_with("http/1.1 101"), "{head}");
+    assert!(head.contains("sec-websocket-accept: s3pplmbitxaq9kygzzhzrbk+xoo="));
+    assert!(head.contains(&format!("sec-websocket-protocol: {protocol}")));
+    stream
+}
+
+async fn replay(stream: &mut TcpStream, steps: Vec<Step>) {
+    for step in steps {
+        match step {
+            Step::Send(hex) => stream
+                .write_all(&hex_bytes(&hex))
+                .await
+                .expect("client frame should be written"),
+            Step::Expect(hex) => {
+                let mut actual = vec![0u8; hex_bytes(&hex).len()];
+                tokio::time::timeout(FRAME_TIMEOUT, stream.read_exact(&mut actual))
+                    .await
+                    .expect("server frame should arrive")
+                    .expect("server frame should be readable");
+                assert_eq!(to_hex(&actual), hex);
+            }
+        }
+    }
+}
+
+async fn assert_connection_closed(stream: &mut TcpStream) {
+    let mut rest = Vec::new();
+    tokio::time::timeout(FRAME_TIMEOUT, stream.read_to_end(&mut rest))
+        .await
+        .expect("server should close the connection")
+        .expect("connection should close cleanly");
+    assert!(
+        rest.is_empty(),
+        "unexpected trailing bytes {}",
+        to_hex(&rest)
+    );
+}
+
+/// 把 `runtime exec <id> <cmd...>` 直接执行为 `<cmd...>` 的假 runtime。
+fn fake_exec_runtime(dir: &Path) -> PathBuf {
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = dir.join("runtime");
+    std::fs::write(&path, "#!/bin/sh\nshift 2\nexec \"$@\"\n").expect("runtime should be written");
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))
+        .expect("runtime should be executable");
+    path
+}
+
+async fn exec_url(server: &StreamingServer, runtime_path: PathBuf, req: ExecRequest) -> String {
+    server
+        .get_exec(
+            &req,
+            ExecStreamOptions {
+                runtime_path,
+                runtime_config_path: PathBuf::new(),
+                exec_cpu_affinity: None,
+                exec_process: None,
+                exec_io_socket_path: None,
+                exec_resize_socket_path: None,
+                transports: StreamTransports::default(),
+                audit: None,
+            },
+        )
+        .await
+        .expect("exec url should be issued")
+        .url
+}
+
+#[tokio::test]
+async fn exec_v5_close_channel_ends_stdin_and_reports_success_status() {
+    let dir = tempfile::tempdir().expect("tempdir should be created");
+    let server = start_server().await;
+    let url = exec_url(
+        &server,
+        fake_exec_runtime(dir.path()),
+        ExecRequest {
+            container_id: "ctr".to_string(),
+            cmd: vec!["cat".to_string()],
+            stdin: true,
+            stdout: true,
+            ..Default::default()
+        },
+    )
+    .await;
+    let mut stream = websocket_upgrade(&url, "v5.channel.k8s.io").await;
+
+    replay(
+        &mut stream,
+        vec![
+            // stdin: "hello\n"
+            send("82 87 37 fa 21 3d 37 92 44 51 5b 95 2b"),
+            // stdout: "hello\n"
+            expect("82 07 01 68 65 6c 6c 6f 0a"),
+            // close 通道：关闭 stdin，cat 读到 EOF 后退出。
+            send("82 82 37 fa 21 3d c8 fa"),
+            expect(&server_channel_frame(
+                WS_CHANNEL_ERROR,
+                br#"{"metadata":{},"status":"Success"}"#,
+            )),
+            expect(SERVER_WEBSOCKET_CLOSE),
+        ],
+    )
+    .await;
+    assert_connection_closed(&mut stream).await;
+}
+
+#[tokio::test]
+async fn exec_v4_reports_non_zero_exit_as_status_json() {
+    let dir = tempfile::tempdir().expect("tempdir should be created");
+    let server = start_server().await;
+    let url = exec_url(
+        &server,
+        fake_exec_runtime(dir.path()),
+        ExecRequest {
+            container_id: "ctr".to_string(),
+            cmd: vec!["sh".to_string(), "-c".to_string(), "exit 3".to_string()],
+            stdout: true,
+            ..Default::default()
+        },
+    )
+    .await;
+    let mut stream = websocket_upgrade(&url, "v4.channel.k8s.io").await;
+
+    replay(
+        &mut stream,
+        vec![
+            expect(&server_channel_frame(
+                WS_CHANNEL_ERROR,
+                concat!(
+                    r#"{"metadata":{},"status":"Failure","#,
+                    r#""message":"command terminated with non-zero exit code: 3","#,
+                    r#""reason":"NonZeroExitCode","#,
+                    r#""details":{"causes":[{"reason":"ExitCode","message":"3"}]}}"#,
+                )
+                .as_bytes(),
+            )),
+            expect(SERVER_WEBSOCKET_CLOSE),
+        ],
+    )
+    .await;
+    assert_connection_closed(&mut stream).await;
+}
+
+/// 回显收到的数据，读到 EOF 后通知测试并关闭连接。
+async fn spawn_echo_server() -> (u16, tokio::sync::oneshot::Receiver<()>) {
+    let listener = TokioTcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("echo server should bind");
+    let port = listener.local_addr().expect("echo address").port();
+    let (eof_tx, eof_rx) = tokio::sync::oneshot::channel();
+    tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.expect("echo should accept");
+        let mut buffer = [0u8; 1024];
+        loop {
+            match socket.read(&mut buffer).await {
+                Ok(0) | Err(_) => break,
+                Ok(n) => {
+                    if socket.write_all(&buffer[..n]).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+        let _ = eof_tx.send(());
+    });
+    (port, eof_rx)
+}
+
+async fn port_forward_url(server: &StreamingServer, port: u16) -> String {
+    server
+        .get_port_forward(
+            &PortForwardRequest {
+                pod_sandbox_id: "pod".to_string(),
+                port: vec![i32::from(port)],
+            },
+            PathBuf::from("/proc/self/ns/net"),
+            StreamTransports::default(),
+        )
+        .await
+        .expect("port-forward url should be issued")
+        .url
+}
+
+/// 第一个数据帧触发连接时，服务端先在数据与 error 通道上各回写一次端口号（小端序）。
+fn port_forward_preamble(port: u16) -> Vec<Step> {
+    let port = to_hex(&port.to_le_bytes());
+    vec![
+        expect(&format!("82 03 00 {port}")),
+        expect(&format!("82 03 01 {port}")),
+    ]
+}
+
+#[tokio::test]
+async fn port_forward_v5_close_channel_half_closes_target_connection() {
+    let (port, mut target_eof) = spawn_echo_server().await;
+    let server = start_server().await;
+    let url = port_forward_url(&server, port).await;
+    let mut stream = websocket_upgrade(&url, "v5.channel.k8s.io").await;
+
+    let mut steps = vec![
+        // 数据通道 0: "ping"
+        send("82 85 37 fa 21 3d 37 8a 48 53 50"),
+    ];
+    steps.extend(port_forward_preamble(port));
+    steps.extend([
+        expect("82 05 00 70 69 6e 67"),
+        // close 通道：半关闭数据通道 0。
+        send("82 82 37 fa 21 3d c8 fa"),
+    ]);
+    replay(&mut stream, steps).await;
+    tokio::time::timeout(FRAME_TIMEOUT, &mut target_eof)
+        .await
+        .expect("target should observe EOF after close signal")
+        .expect("echo server should report EOF");
+
+    replay(
+        &mut stream,
+        vec![send(CLIENT_WEBSOCKET_CLOSE), expect(SERVER_WEBSOCKET_CLOSE)],
+    )
+    .await;
+    assert_connection_closed(&mut stream).await;
+}
+
+#[tokio::test]
+async fn port_forward_v4_ignores_close_channel() {
+    let (port, mut target_eof) = spawn_echo_server().await;
+    let server = start_server().await;
+    let url = port_forward_url(&server, port).await;
+    let mut stream = websocket_upgrade(&url, "v4.channel.k8s.io").await;
+
+    let mut steps = vec![send("82 85 37 fa 21 3d 37 8a 48 53 50")];
+    steps.extend(port_forward_preamble(port));
+    steps.extend([
+        expect("82 05 00 70 69 6e 67"),
+        send("82 82 37 fa 21 3d c8 fa"),
+    ]);
+    replay(&mut stream, steps).await;
+    assert!(
+        tokio::time::timeout(Duration::from_millis(300), &mut target_eof)
+            .await
+            .is_err(),
+        "v4 has no close channel, so the target must stay open"
+    );
+
+    replay(
+        &mut stream,
+        vec![send(CLIENT_WEBSOCKET_CLOSE), expect(SERVER_WEBSOCKET_CLOSE)],
+    )
+    .await;
+    assert_connection_closed(&mut stream).await;
+}
@@ -1,18 +1,20 @@
+mod audit;
 mod config;
+mod sessions;
 pub mod spdy;
 
 use std::collections::{HashMap, HashSet};
 use std::convert::Infallible;
 use std::fs::File;
 use std::io::{Read, Write};
-use std::net::{TcpListener, TcpStream as StdTcpStream, ToSocketAddrs};
+use std::net::{SocketAddr, TcpListener, TcpStream as StdTcpStream, ToSocketAddrs};
 use std::os::unix::fs::MetadataExt;
 use std::os::unix::io::{AsRawFd, FromRawFd};
 use std::path::Path;
 use std::path::PathBuf;
 use std::process::Stdio;
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime};
 
 use base64::Engine;
 use hyper::body::Body;
@@ -39,6 +41,7 @@ use crate::proto::runtime::v1::{
 
 const PORT_FORWARD_PROTOCOL_V1: &str = "portforward.k8s.io";
 const PORT_FORWARD_WS_PROTOCOL_V4_BINARY: &str = "v4.channel.k8s.io";
+const PORT_FORWARD_WS_PROTOCOL_V5: &str = "v5.channel.k8s.io";
 const PORT_FORWARD_WS_PROTOCOL_V4_BASE64: &str = "v4.base64.channel.k8s.io";
 const WS_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
 const WS_CHANNEL_STDIN: u8 = 0;
@@ -46,10 +49,19 @@ const WS_CHANNEL_STDOUT: u8 = 1;
 const WS_CHANNEL_STDERR: u8 = 2;
 const WS_CHANNEL_ERROR: u8 = 3;
 const WS_CHANNEL_RESIZE: u8 = 4;
+/// v5 协议的 close 通道：负载首字节为要半关闭的通道号。
+const WS_CHANNEL_CLOSE: u8 = 255;
+const REMOTECOMMAND_WS_PROTOCOL_V4: &str = "v4.channel.k8s.io";
+const REMOTECOMMAND_WS_PROTOCOL_V5: &str = "v5.channel.k8s.io";
 
+pub use audit::{StreamingAuditRecord, StreamingAuditTarget, StreamingAuditor};
 use config::load_streaming_tls_config;
-pub use config::StreamingConfig;
 pub(crate) use config::{deserialize_duration, parse_duration, serialize_duration};
+pub use config::{StreamingAuditConfig, StreamingConfig};
+use sessions::{SessionIo, SessionUpgrade};
+pub use sessions::{
+    StreamingSessionInfo, StreamingSessionKind, StreamingSessionLimits, StreamingSessionRegistry,
+};
 
 type AttachStreamCloser = dyn Fn(&str, &str) -> anyhow::Result<()> + Send + Sync;
 
@@ -71,7 +83,7 @@ struct CachedStreamingRequest {
 struct PortForwardRequestContext {
     req: PortForwardRequest,
     netns_path: PathBuf,
-    websocket_enabled: bool,
+    transports: StreamTransports,
 }
 
 #[derive(Debug)]
@@ -80,14 +92,16 @@ struct AttachRequestContext {
     attach_io_socket_path: Option<PathBuf>,
     attach_resize_socket_path: Option<PathBuf>,
     attach_stream_close: Option<AttachStreamClose>,
-    websocket_enabled: bool,
+    transports: StreamTransports,
+    audit: Option<StreamingAuditTarget>,
 }
 
 #[derive(Debug, Clone)]
 struct AttachLogRequestContext {
     req: AttachRequest,
     log_path: PathBuf,
-    websocket_enabled: bool,
+    transports: StreamTransports,
+    audit: Option<StreamingAuditTarget>,
 }
 
 #[derive(Debug, Clone)]
@@ -96,9 +110,11 @@ struct ExecRequestContext {
     runtime_path: PathBuf,
     runtime_config_path: PathBuf,
     exec_cpu_affinity: Option<usize>,
+    exec_process: Option<Box<crate::oci::spec::Process>>,
     exec_io_socket_path: Option<PathBuf>,
     exec_resize_socket_path: Option<PathBuf>,
-    websocket_enabled: bool,
+    transports: StreamTransports,
+    audit: Option<StreamingAuditTarget>,
 }
 
 pub(crate) struct AttachStreamClose {
@@ -142,14 +158,34 @@ impl AttachStreamClose {
     }
 }
 
+/// runtime handler 允许的 streaming 传输协议。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamTransports {
+    pub websocket: bool,
+    pub spdy: bool,
+}
+
+impl Default for StreamTransports {
+    fn default() -> Self {
+        Self {
+            websocket: true,
+            spdy: true,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ExecStreamOptions {
     pub runtime_path: PathBuf,
     pub runtime_config_path: PathBuf,
     pub exec_cpu_affinity: Option<usize>,
+    /// crius 扩展 exec 覆盖后的 process spec，直接调用 runtime 时通过 `--process` 传入。
+    pub exec_process: Option<crate::oci::spec::Process>,
     pub exec_io_socket_path: Option<PathBuf>,
     pub exec_resize_socket_path: Option<PathBuf>,
-    pub websocket_enabled: bool,
+    pub transports: StreamTransports,
+    /// 需要审计时由 CRI 层填充会话目标。
+    pub audit: Option<StreamingAuditTarget>,
 }
 
 #[derive(Debug, Clone)]
@@ -158,6 +194,7 @@ struct ExecServeContext {
     runtime_path: PathBuf,
     runtime_config_path: PathBuf,
     exec_cpu_affinity: Option<usize>,
+    exec_process: Option<crate::oci::spec::Process>,
     exec_io_socket_path: Option<PathBuf>,
     exec_resize_socket_path: Option<PathBuf>,
     protocol: &'static str,
@@ -168,6 +205,8 @@ pub struct StreamingServer {
     cache: Arc<Mutex<HashMap<String, CachedStreamingRequest>>>,
     base_url: String,
     config: StreamingConfig,
+    sessions: StreamingSessionRegistry,
+    auditor: StreamingAuditor,
 }
 
 impl StreamingServer {
@@ -184,6 +223,8 @@ impl StreamingServer {
         Self {
             cache: Arc::new(Mutex::new(HashMap::new())),
             base_url: base_url.into(),
+            sessions: StreamingSessionRegistry::new(config.session_limits()),
+            auditor: StreamingAuditor::new(config.audit.clone()),
             config,
         }
     }
@@ -194,7 +235,29 @@ impl StreamingServer {
         runtime_path: PathBuf,
         req: Request<Body>,
     ) -> Response<Body> {
-        handle_request(self.cache.clone(), self.config.clone(), runtime_path, req).await
+        self.handle_request_from_for_test(runtime_path, None, req)
+            .await
+    }
+
+    #[cfg(test)]
+    pub(crate) async fn handle_request_from_for_test(
+        &self,
+        runtime_path: PathBuf,
+        client_address: Option<SocketAddr>,
+        req: Request<Body>,
+    ) -> Response<Body> {
+        handle_request(
+            self.cache.clone(),
+            self.config.clone(),
+            runtime_path,
+            SessionContext {
+                registry: self.sessions.clone(),
+                auditor: self.auditor.clone(),
+                client_address,
+            },
+            req,
+        )
+        .await
     }
 
     pub async fn start(bind_addr: &str, runtime_path: PathBuf) -> anyhow::Result<Self> {
@@ -217,6 +280,8 @@ impl StreamingServer {
         listener.set_nonblocking(true)?;
         let local_addr = listener.local_addr()?;
         let cache = Arc::new(Mutex::new(HashMap::new()));
+        let sessions = StreamingSessionRegistry::new(config.session_limits());
+        let auditor = StreamingAuditor::new(config.audit.clone());
         if config.enable_tls {
             let tls_config = Arc::new(load_streaming_tls_config(&config)?);
             let tls_listener = TokioTcpListener::from_std(listener)?;
@@ -224,9 +289,11 @@ impl StreamingServer {
             let service_cache = cache.clone();
             let service_runtime_path = runtime_path.clone();
             let service_config = config.clone();
+            let service_sessions = sessions.clone();
+            let service_auditor = auditor.clone();
             tokio::spawn(async move {
                 loop {
-                    let (stream, _) = match tls_listener.accept().await {
+                    let (stream, peer) = match tls_listener.accept().await {
                         Ok(accepted) => accepted,
                         Err(err) => {
                             log::error!("Streaming TLS listener accept failed: {}", err);
@@ -237,6 +304,11 @@ impl StreamingServer {
                     let cache = service_cache.clone();
                     let runtime_path = service_runtime_path.clone();
                     let config = service_config.clone();
+                    let session_ctx = SessionContext {
+                        registry: service_sessions.clone(),
+                        auditor: service_auditor.clone(),
+                        client_address: Some(peer),
+                    };
                     tokio::spawn(async move {
                         let tls_stream = match acceptor.accept(stream).await {
                             Ok(stream) => stream,
@@ -249,9 +321,11 @@ impl StreamingServer {
                             let cache = cache.clone();
                             let runtime_path = runtime_path.clone();
                             let config = config.clone();
+                            let session_ctx = session_ctx.clone();
                             async move {
                                 Ok::<_, Infallible>(
-                                    handle_request(cache, config, runtime_path, req).await,
+                                    handle_request(cache, config, runtime_path, session_ctx, req)
+                                        .await,
                                 )
                             }
                         });
@@ -269,19 +343,27 @@ impl StreamingServer {
             let service_cache = cache.clone();
             let service_runtime_path = runtime_path.clone();
             let service_config = config.clone();
+            let service_sessions = sessions.clone();
+            let service_auditor = auditor.clone();
 
-            let make_service = make_service_fn(move |_| {
+            let make_service = make_service_fn(move |conn: &hyper::server::conn::AddrStream| {
                 let cache = service_cache.clone();
                 let runtime_path = service_runtime_path.clone();
                 let config = service_config.clone();
+                let session_ctx = SessionContext {
+                    registry: service_sessions.clone(),
+                    auditor: service_auditor.clone(),
+                    client_address: Some(conn.remote_addr()),
+                };
                 async move {
                     Ok::<_, Infallible>(service_fn(move |req| {
                         let cache = cache.clone();
                         let runtime_path = runtime_path.clone();
                         let config = config.clone();
+                        let session_ctx = session_ctx.clone();
                         async move {
                             Ok::<_, Infallible>(
-                                handle_request(cache, config, runtime_path, req).await,
+                                handle_request(cache, config, runtime_path, session_ctx, req).await,
                             )
                         }
                     }))
@@ -300,6 +382,8 @@ impl StreamingServer {
             cache,
             base_url: config.base_url(local_addr),
             config,
+            sessions,
+            auditor,
         })
     }
 
@@ -307,21 +391,36 @@ impl StreamingServer {
         &self.base_url
     }
 
+    /// 已升级的流式会话登记表。
+    pub fn sessions(&self) -> &StreamingSessionRegistry {
+        &self.sessions
+    }
+
+    /// exec/attach 会话审计器。
+    pub fn auditor(&self) -> &StreamingAuditor {
+        &self.auditor
+    }
+
     pub async fn get_exec(
         &self,
         req: &ExecRequest,
         options: ExecStreamOptions,
     ) -> Result<ExecResponse, tonic::Status> {
         Self::validate_exec_request(req)?;
+        self.sessions
+            .check_capacity(&req.container_id)
+            .map_err(tonic::Status::resource_exhausted)?;
         let token = self
             .insert_request(StreamingRequest::Exec(ExecRequestContext {
                 req: req.clone(),
                 runtime_path: options.runtime_path,
                 runtime_config_path: options.runtime_config_path,
                 exec_cpu_affinity: options.exec_cpu_affinity,
+                exec_process: options.exec_process.map(Box::new),
                 exec_io_socket_path: options.exec_io_socket_path,
                 exec_resize_socket_path: options.exec_resize_socket_path,
-                websocket_enabled: options.websocket_enabled,
+                transports: options.transports,
+                audit: options.audit,
             }))
             .await;
         Ok(ExecResponse {
@@ -334,14 +433,15 @@ impl StreamingServer {
         req: &AttachRequest,
         attach_io_socket_path: Option<PathBuf>,
         attach_resize_socket_path: Option<PathBuf>,
-        websocket_enabled: bool,
+        transports: StreamTransports,
     ) -> Result<AttachResponse, tonic::Status> {
         self.get_attach_with_close(
             req,
             attach_io_socket_path,
             attach_resize_socket_path,
             None,
-            websocket_enabled,
+            transports,
+            None,
         )
         .await
     }
@@ -352,16 +452,21 @@ impl StreamingServer {
         attach_io_socket_path: Option<PathBuf>,
         attach_resize_socket_path: Option<PathBuf>,
         attach_stream_close: Option<AttachStreamClose>,
-        websocket_enabled: bool,
+        transports: StreamTransports,
+        audit: Option<StreamingAuditTarget>,
     ) -> Result<AttachResponse, tonic::Status> {
         Self::validate_attach_request(req)?;
+        self.sessions
+            .check_capacity(&req.container_id)
+            .map_err(tonic::Status::resource_exhausted)?;
         let token = self
             .insert_request(StreamingRequest::Attach(AttachRequestContext {
                 req: req.clone(),
                 attach_io_socket_path,
                 attach_resize_socket_path,
                 attach_stream_close,
-                websocket_enabled,
+                transports,
+                audit,
             }))
             .await;
         Ok(AttachResponse {
@@ -373,14 +478,19 @@ impl StreamingServer {
         &self,
         req: &AttachRequest,
         log_path: PathBuf,
-        websocket_enabled: bool,
+        transports: StreamTransports,
+        audit: Option<StreamingAuditTarget>,
     ) -> Result<AttachResponse, tonic::Status> {
         Self::validate_attach_request(req)?;
+        self.sessions
+            .check_capacity(&req.container_id)
+            .map_err(tonic::Status::resource_exhausted)?;
         let token = self
             .insert_request(StreamingRequest::AttachLog(AttachLogRequestContext {
                 req: req.clone(),
                 log_path,
-                websocket_enabled,
+                transports,
+                audit,
             }))
             .await;
         Ok(AttachResponse {
@@ -392,14 +502,17 @@ impl StreamingServer {
         &self,
         req: &PortForwardRequest,
         netns_path: PathBuf,
-        websocket_enabled: bool,
+        transports: StreamTransports,
     ) -> Result<PortForwardResponse, tonic::Status> {
         Self::validate_port_forward_request(req)?;
+        self.sessions
+            .check_capacity(&req.pod_sandbox_id)
+            .map_err(tonic::Status::resource_exhausted)?;
         let token = self
             .insert_request(StreamingRequest::PortForward(PortForwardRequestContext {
                 req: req.clone(),
                 netns_path,
-                websocket_enabled,
+                transports,
             }))
             .await;
         Ok(PortForwardResponse {
@@ -510,6 +623,7 @@ async fn handle_request(
     cache: Arc<Mutex<HashMap<String, CachedStreamingRequest>>>,
     config: StreamingConfig,
     _runtime_path: PathBuf,
+    session_ctx: SessionContext,
     req: Request<Body>,
 ) -> Response<Body> {
     if req.method() != Method::GET && req.method() != Method::POST {
@@ -523,14 +637,14 @@ async fn handle_request(
         _ => return response(StatusCode::NOT_FOUND, "streaming token not found"),
     };
     let token = match parts.next() {
-        Some(value) if !value.is_empty() => value,
+        Some(value) if !value.is_empty() => value.to_string(),
         _ => return response(StatusCode::NOT_FOUND, "streaming token not found"),
     };
 
     let request = {
         let mut cache = cache.lock().await;
         StreamingServer::prune_expired_requests_locked(&mut cache, config.request_token_ttl);
-        cache.remove(token)
+        cache.remove(&token)
     };
 
     match (action, request.map(|entry| entry.request)) {
@@ -540,12 +654,16 @@ async fn handle_request(
                 runtime_path,
                 runtime_config_path,
                 exec_cpu_affinity,
+                exec_process,
                 exec_io_socket_path,
                 exec_resize_socket_path,
-                websocket_enabled,
+                transports,
+                audit,
             } = exec_ctx;
+            let exec_process = exec_process.map(|process| *process);
+            let container_id = exec_req.container_id.clone();
             if is_websocket_upgrade_request(&req) {
-                if !websocket_enabled {
+                if !transports.websocket {
                     return response(
                         StatusCode::BAD_REQUEST,
                         "websocket streaming is disabled for this runtime handler",
@@ -559,25 +677,34 @@ async fn handle_request(
                 };
 
                 let response = websocket_switching_response(&req, protocol);
-                let on_upgrade = hyper::upgrade::on(req);
-                tokio::spawn(async move {
-                    if let Err(e) = serve_exec_websocket(
-                        on_upgrade,
-                        ExecServeContext {
-                            req: exec_req,
-                            runtime_path,
-                            runtime_config_path,
-                            exec_cpu_affinity,
-                            exec_io_socket_path,
-                            exec_resize_socket_path,
-                            protocol,
-                        },
-                    )
-                    .await
-                    {
-                        log::error!("Exec websocket session failed: {}", e);
-                    }
-                });
+                if let Some(rejected) = session_ctx.spawn(
+                    &container_id,
+                    StreamingSessionKind::Exec,
+                    &format!("websocket/{}", protocol),
+                    req,
+                    audit.map(|target| (token.as_str(), target)),
+                    move |on_upgrade| async move {
+                        if let Err(e) = serve_exec_websocket(
+                            on_upgrade,
+                            ExecServeContext {
+                                req: exec_req,
+                                runtime_path,
+                                runtime_config_path,
+                                exec_cpu_affinity,
+                                exec_process,
+                                exec_io_socket_path,
+                                exec_resize_socket_path,
+                                protocol,
+                            },
+                        )
+                        .await
+                        {
+                            log::error!("Exec websocket session failed: {}", e);
+                        }
+                    },
+                ) {
+                    return rejected;
+                }
 
                 return response;
             }
@@ -587,6 +714,12 @@ async fn handle_request(
                     "exec requires SPDY or websocket upgrade headers",
                 );
             }
+            if !transports.spdy {
+                return response(
+                    StatusCode::BAD_REQUEST,
+                    "SPDY streaming is disabled for this runtime handler",
+                );
+            }
 
             let Some(protocol) = negotiate_remotecommand_protocol(&req) else {
                 return response(
@@ -595,33 +728,36 @@ async fn handle_request(
                 );
             };
 
-            let on_upgrade = hyper::upgrade::on(req);
-            tokio::spawn(async move {
-                if let Err(e) = serve_exec_spdy(
-                    on_upgrade,
-                    ExecServeContext {
-                        req: exec_req,
-                        runtime_path,
-                        runtime_config_path,
-                        exec_cpu_affinity,
-                        exec_io_socket_path,
-                        exec_resize_socket_path,
-                        protocol,
-                    },
-                )
-                .await
-                {
-                    log::error!("Exec SPDY session failed: {}", e);
-                }
-            });
+            if let Some(rejected) = session_ctx.spawn(
+                &container_id,
+                StreamingSessionKind::Exec,
+                &format!("spdy/{}", protocol),
+                req,
+                audit.map(|target| (token.as_str(), target)),
+                move |on_upgrade| async move {
+                    if let Err(e) = serve_exec_spdy(
+                        on_upgrade,
+                        ExecServeContext {
+                            req: exec_req,
+                            runtime_path,
+                            runtime_config_path,
+                            exec_cpu_affinity,
+                            exec_process,
+                            exec_io_socket_path,
+                            exec_resize_socket_path,
+                            protocol,
+                        },
+                    )
+                    .await
+                    {
+                        log::error!("Exec SPDY session failed: {}", e);
+                    }
+                },
+            ) {
+                return rejected;
+            }
 
-            Response::builder()
-                .status(StatusCode::SWITCHING_PROTOCOLS)
-                .header(CONNECTION, "Upgrade")
-                .header(UPGRADE, spdy::SPDY_31)
-                .header("X-Stream-Protocol-Version", protocol)
-                .body(Body::empty())
-                .unwrap_or_else(|_| Response::new(Body::empty()))
+            spdy_switching_response(protocol)
         }
         ("attach", Some(StreamingRequest::Attach(attach_ctx))) => {
             let AttachRequestContext {
@@ -629,10 +765,16 @@ async fn handle_request(
                 attach_io_socket_path,
                 attach_resize_socket_path,
                 attach_stream_close,
-                websocket_enabled,
+                transports,
+                audit,
             } = attach_ctx;
+            let container_id = attach_req.container_id.clone();
+            let scrollback_since = match attach_scrollback_since(&req) {
+                Ok(since) => since,
+                Err(message) => return response(StatusCode::BAD_REQUEST, &message),
+            };
             if is_websocket_upgrade_request(&req) {
-                if !websocket_enabled {
+                if !transports.websocket {
                     return response(
                         StatusCode::BAD_REQUEST,
                         "websocket streaming is disabled for this runtime handler",
@@ -646,21 +788,30 @@ async fn handle_request(
                 };
 
                 let response = websocket_switching_response(&req, protocol);
-                let on_upgrade = hyper::upgrade::on(req);
-                tokio::spawn(async move {
-                    if let Err(e) = serve_attach_websocket(
-                        on_upgrade,
-                        attach_req,
-                        attach_io_socket_path,
-                        attach_resize_socket_path,
-                        attach_stream_close,
-                        protocol,
-                    )
-                    .await
-                    {
-                        log::error!("Attach websocket session failed: {}", e);
-                    }
-                });
+                if let Some(rejected) = session_ctx.spawn(
+                    &container_id,
+                    StreamingSessionKind::Attach,
+                    &format!("websocket/{}", protocol),
+                    req,
+                    audit.map(|target| (token.as_str(), target)),
+                    move |on_upgrade| async move {
+                        if let Err(e) = serve_attach_websocket(
+                            on_upgrade,
+                            attach_req,
+                            attach_io_socket_path,
+                            attach_resize_socket_path,
+                            attach_stream_close,
+                            scrollback_since,
+                            protocol,
+                        )
+                        .await
+                        {
+                            log::error!("Attach websocket session failed: {}", e);
+                        }
+                    },
+                ) {
+                    return rejected;
+                }
 
                 return response;
             }
@@ -670,6 +821,12 @@ async fn handle_request(
                     "attach requires SPDY or websocket upgrade headers",
                 );
             }
+            if !transports.spdy {
+                return response(
+                    StatusCode::BAD_REQUEST,
+                    "SPDY streaming is disabled for this runtime handler",
+                );
+            }
 
             let Some(protocol) = negotiate_remotecommand_protocol(&req) else {
                 return response(
@@ -678,34 +835,39 @@ async fn handle_request(
                 );
             };
 
-            let on_upgrade = hyper::upgrade::on(req);
-            tokio::spawn(async move {
-                if let Err(e) = serve_attach_spdy(
-                    on_upgrade,
-                    attach_req,
-                    attach_io_socket_path,
-                    attach_resize_socket_path,
-                    attach_stream_close,
-                    protocol,
-                )
-                .await
-                {
-                    log::error!("Attach SPDY session failed: {}", e);
-                }
-            });
+            if let Some(rejected) = session_ctx.spawn(
+                &container_id,
+                StreamingSessionKind::Attach,
+                &format!("spdy/{}", protocol),
+                req,
+                audit.map(|target| (token.as_str(), target)),
+                move |on_upgrade| async move {
+                    if let Err(e) = serve_attach_spdy(
+                        on_upgrade,
+                        attach_req,
+                        attach_io_socket_path,
+                        attach_resize_socket_path,
+                        attach_stream_close,
+                        scrollback_since,
+                        protocol,
+                    )
+                    .await
+                    {
+                        log::error!("Attach SPDY session failed: {}", e);
+                    }
+                },
+            ) {
+                return rejected;
+            }
 
-            Response::builder()
-                .status(StatusCode::SWITCHING_PROTOCOLS)
-                .header(CONNECTION, "Upgrade")
-                .header(UPGRADE, spdy::SPDY_31)
-                .header("X-Stream-Protocol-Version", protocol)
-                .body(Body::empty())
-                .unwrap_or_else(|_| Response::new(Body::empty()))
+            spdy_switching_response(protocol)
         }
         ("attach", Some(StreamingRequest::AttachLog(attach_log_ctx))) => {
-            let websocket_enabled = attach_log_ctx.websocket_enabled;
+            let transports = attach_log_ctx.transports;
+            let container_id = attach_log_ctx.req.container_id.clone();
+            let audit = attach_log_ctx.audit.clone();
             if is_websocket_upgrade_request(&req) {
-                if !websocket_enabled {
+                if !transports.websocket {
                     return response(
                         StatusCode::BAD_REQUEST,
                         "websocket streaming is disabled for this runtime handler",
@@ -719,14 +881,22 @@ async fn handle_request(
                 };
 
                 let response = websocket_switching_response(&req, protocol);
-                let on_upgrade = hyper::upgrade::on(req);
-                tokio::spawn(async move {
-                    if let Err(e) =
-                        serve_attach_log_websocket(on_upgrade, attach_log_ctx, protocol).await
-                    {
-                        log::error!("Attach log websocket session failed: {}", e);
-                    }
-                });
+                if let Some(rejected) = session_ctx.spawn(
+                    &container_id,
+                    StreamingSessionKind::Attach,
+                    &format!("websocket/{}", protocol),
+                    req,
+                    audit.map(|target| (token.as_str(), target)),
+                    move |on_upgrade| async move {
+                        if let Err(e) =
+                            serve_attach_log_websocket(on_upgrade, attach_log_ctx, protocol).await
+                        {
+                            log::error!("Attach log websocket session failed: {}", e);
+                        }
+                    },
+                ) {
+                    return rejected;
+                }
 
                 return response;
             }
@@ -736,6 +906,12 @@ async fn handle_request(
                     "attach requires SPDY or websocket upgrade headers",
                 );
             }
+            if !transports.spdy {
+                return response(
+                    StatusCode::BAD_REQUEST,
+                    "SPDY streaming is disabled for this runtime handler",
+                );
+            }
 
             let Some(protocol) = negotiate_remotecommand_protocol(&req) else {
                 return response(
@@ -744,25 +920,30 @@ async fn handle_request(
                 );
             };
 
-            let on_upgrade = hyper::upgrade::on(req);
-            tokio::spawn(async move {
-                if let Err(e) = serve_attach_log_spdy(on_upgrade, attach_log_ctx, protocol).await {
-                    log::error!("Attach log fallback session failed: {}", e);
-                }
-            });
+            if let Some(rejected) = session_ctx.spawn(
+                &container_id,
+                StreamingSessionKind::Attach,
+                &format!("spdy/{}", protocol),
+                req,
+                audit.map(|target| (token.as_str(), target)),
+                move |on_upgrade| async move {
+                    if let Err(e) =
+                        serve_attach_log_spdy(on_upgrade, attach_log_ctx, protocol).await
+                    {
+                        log::error!("Attach log fallback session failed: {}", e);
+                    }
+                },
+            ) {
+                return rejected;
+            }
 
-            Response::builder()
-                .status(StatusCode::SWITCHING_PROTOCOLS)
-                .header(CONNECTION, "Upgrade")
-                .header(UPGRADE, spdy::SPDY_31)
-                .header("X-Stream-Protocol-Version", protocol)
-                .body(Body::empty())
-                .unwrap_or_else(|_| Response::new(Body::empty()))
+            spdy_switching_response(protocol)
         }
         ("portforward", Some(StreamingRequest::PortForward(port_forward_ctx))) => {
-            let websocket_enabled = port_forward_ctx.websocket_enabled;
+            let transports = port_forward_ctx.transports;
+            let pod_sandbox_id = port_forward_ctx.req.pod_sandbox_id.clone();
             if is_websocket_upgrade_request(&req) {
-                if !websocket_enabled {
+                if !transports.websocket {
                     return response(
                         StatusCode::BAD_REQUEST,
                         "websocket streaming is disabled for this runtime handler",
@@ -776,16 +957,28 @@ async fn handle_request(
                 };
 
                 let response = websocket_switching_response(&req, protocol);
-                let on_upgrade = hyper::upgrade::on(req);
                 let config = config.clone();
-                tokio::spawn(async move {
-                    if let Err(e) =
-                        serve_portforward_websocket(on_upgrade, port_forward_ctx, protocol, config)
-                            .await
-                    {
-                        log::error!("Port-forward websocket session failed: {}", e);
-                    }
-                });
+                if let Some(rejected) = session_ctx.spawn(
+                    &pod_sandbox_id,
+                    StreamingSessionKind::PortForward,
+                    &format!("websocket/{}", protocol),
+                    req,
+                    None,
+                    move |on_upgrade| async move {
+                        if let Err(e) = serve_portforward_websocket(
+                            on_upgrade,
+                            port_forward_ctx,
+                            protocol,
+                            config,
+                        )
+                        .await
+                        {
+                            log::error!("Port-forward websocket session failed: {}", e);
+                        }
+                    },
+                ) {
+                    return rejected;
+                }
 
                 return response;
             }
@@ -795,6 +988,12 @@ async fn handle_request(
                     "portforward requires SPDY or websocket upgrade headers",
                 );
             }
+            if !transports.spdy {
+                return response(
+                    StatusCode::BAD_REQUEST,
+                    "SPDY streaming is disabled for this runtime handler",
+                );
+            }
 
             let Some(protocol) = negotiate_portforward_protocol(&req) else {
                 return response(
@@ -803,21 +1002,25 @@ async fn handle_request(
                 );
             };
 
-            let on_upgrade = hyper::upgrade::on(req);
             let config = config.clone();
-            tokio::spawn(async move {
-                if let Err(e) = serve_portforward_spdy(on_upgrade, port_forward_ctx, config).await {
-                    log::error!("Port-forward SPDY session failed: {}", e);
-                }
-            });
+            if let Some(rejected) = session_ctx.spawn(
+                &pod_sandbox_id,
+                StreamingSessionKind::PortForward,
+                &format!("spdy/{}", protocol),
+                req,
+                None,
+                move |on_upgrade| async move {
+                    if let Err(e) =
+                        serve_portforward_spdy(on_upgrade, port_forward_ctx, config).await
+                    {
+                        log::error!("Port-forward SPDY session failed: {}", e);
+                    }
+                },
+            ) {
+                return rejected;
+            }
 
-            Response::builder()
-                .status(StatusCode::SWITCHING_PROTOCOLS)
-                .header(CONNECTION, "Upgrade")
-                .header(UPGRADE, spdy::SPDY_31)
-                .header("X-Stream-Protocol-Version", protocol)
-                .body(Body::empty())
-                .unwrap_or_else(|_| Response::new(Body::empty()))
+            spdy_switching_response(protocol)
         }
         ("exec", Some(_)) | ("attach", Some(_)) | ("portforward", Some(_)) => {
             response(StatusCode::BAD_REQUEST, "streaming token kind mismatch")
@@ -826,6 +1029,87 @@ async fn handle_request(
     }
 }
 
+/// 单个连接上的会话登记上下文。
+#[derive(Clone)]
+struct SessionContext {
+    registry: StreamingSessionRegistry,
+    auditor: StreamingAuditor,
+    client_address: Option<SocketAddr>,
+}
+
+impl SessionContext {
+    /// 登记会话并在后台运行；超出并发上限时返回 429 响应，不再升级连接。
+    /// `audit` 为签发会话的 token 与审计目标。
+    fn spawn<F, Fut>(
+        &self,
+        container_id: &str,
+        kind: StreamingSessionKind,
+        protocol: &str,
+        req: Request<Body>,
+        audit: Option<(&str, StreamingAuditTarget)>,
+        serve: F,
+    ) -> Option<Response<Body>>
+    where
+        F: FnOnce(SessionUpgrade) -> Fut,
+        Fut: std::future::Future<Output = ()> + Send + 'static,
+    {
+        let session = match self
+            .registry
+            .open(container_id, kind, protocol, self.client_address)
+        {
+            Ok(session) => session,
+            Err(message) => return Some(response(StatusCode::TOO_MANY_REQUESTS, &message)),
+        };
+        let session_id = session.id().to_string();
+        let (audited, transcript) = match audit {
+            Some((token, target)) => {
+                let record = StreamingAuditRecord {
+                    session_id: session_id.clone(),
+                    token: token.to_string(),
+                    kind: kind.as_str().to_string(),
+                    container_id: container_id.to_string(),
+                    pod_sandbox_id: target.pod_sandbox_id,
+                    pod_namespace: target.pod_namespace,
+                    pod_name: target.pod_name,
+                    runtime_handler: target.runtime_handler,
+                    client_address: self.client_address.map(|address| address.to_string()),
+                    protocol: protocol.to_string(),
+                    command: target.command,
+                    tty: target.tty,
+                    started_at_unix_millis: chrono::Utc::now().timestamp_millis(),
+                    ..Default::default()
+                };
+                let (audited, transcript) = self.auditor.begin(record, session.counters());
+                (Some(audited), transcript)
+            }
+            None => (None, None),
+        };
+        let serve = serve(SessionUpgrade::new(
+            hyper::upgrade::on(req),
+            &session,
+            transcript,
+        ));
+        let task = tokio::spawn(async move {
+            let _session = session;
+            let _audited = audited;
+            serve.await;
+        });
+        self.registry
+            .set_abort_handle(&session_id, task.abort_handle());
+        None
+    }
+}
+
+fn spdy_switching_response(protocol: &str) -> Response<Body> {
+    Response::builder()
+        .status(StatusCode::SWITCHING_PROTOCOLS)
+        .header(CONNECTION, "Upgrade")
+        .header(UPGRADE, spdy::SPDY_31)
+        .header("X-Stream-Protocol-Version", protocol)
+        .body(Body::empty())
+        .unwrap_or_else(|_| Response::new(Body::empty()))
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum AttachStreamRole {
     Error,
@@ -918,6 +1202,7 @@ enum PortForwardWebsocketProtocol {
     Legacy,
     V4Binary,
     V4Base64,
+    V5Binary,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -1057,6 +1342,28 @@ fn expected_attach_roles(req: &AttachRequest) -> Vec<AttachStreamRole> {
     roles
 }
 
+/// 解析 attach URL 上的 `sinceSeconds`：只回放最近这段时间的 shim 缓冲输出，缺省时全部回放。
+fn attach_scrollback_since(req: &Request<Body>) -> Result<Option<SystemTime>, String> {
+    let Some(query) = req.uri().query() else {
+        return Ok(None);
+    };
+    let Some(raw) = query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find_map(|(key, value)| (key == "sinceSeconds").then_some(value))
+    else {
+        return Ok(None);
+    };
+    let seconds = raw
+        .parse::<u64>()
+        .map_err(|_| format!("invalid sinceSeconds {:?}", raw))?;
+    Ok(Some(
+        SystemTime::now()
+            .checked_sub(Duration::from_secs(seconds))
+            .unwrap_or(SystemTime::UNIX_EPOCH),
+    ))
+}
+
 fn expected_exec_roles(req: &ExecRequest) -> Vec<ExecStreamRole> {
     let mut roles = vec![ExecStreamRole::Error];
     if req.stdin {
@@ -1110,7 +1417,7 @@ fn apply_terminal_resize(tty: &File, width: u16, height: u16) -> anyhow::Result<
 }
 
 async fn write_error_stream(
-    writer: &Arc<Mutex<spdy::AsyncSpdyWriter<tokio::io::WriteHalf<hyper::upgrade::Upgraded>>>>,
+    writer: &Arc<Mutex<spdy::AsyncSpdyWriter<tokio::io::WriteHalf<SessionIo>>>>,
     error_stream: Option<spdy::StreamId>,
     message: &str,
 ) -> anyhow::Result<()> {
@@ -1236,8 +1543,8 @@ fn negotiate_remotecommand_websocket_protocol(req: &Request<Body>) -> Option<&'s
         .collect();
 
     [
-        "v5.channel.k8s.io",
-        "v4.channel.k8s.io",
+        REMOTECOMMAND_WS_PROTOCOL_V5,
+        REMOTECOMMAND_WS_PROTOCOL_V4,
         "v3.channel.k8s.io",
         "v2.channel.k8s.io",
         "channel.k8s.io",
@@ -1348,7 +1655,9 @@ async fn write_portforward_websocket_channel_frame<W: AsyncWriteExt + Unpin>(
     payload: &[u8],
 ) -> anyhow::Result<()> {
     let result = match protocol {
-        PortForwardWebsocketProtocol::Legacy | PortForwardWebsocketProtocol::V4Binary => {
+        PortForwardWebsocketProtocol::Legacy
+        | PortForwardWebsocketProtocol::V4Binary
+        | PortForwardWebsocketProtocol::V5Binary => {
             write_websocket_channel_frame(writer, channel, payload).await
         }
         PortForwardWebsocketProtocol::V4Base64 => {
@@ -1377,7 +1686,9 @@ fn decode_portforward_websocket_frame(
     }
 
     match protocol {
-        PortForwardWebsocketProtocol::Legacy | PortForwardWebsocketProtocol::V4Binary => {
+        PortForwardWebsocketProtocol::Legacy
+        | PortForwardWebsocketProtocol::V4Binary
+        | PortForwardWebsocketProtocol::V5Binary => {
             Ok(Some((frame.payload[0], frame.payload[1..].to_vec())))
         }
         PortForwardWebsocketProtocol::V4Base64 => {
@@ -1461,6 +1772,7 @@ fn negotiate_portforward_websocket_protocol(req: &Request<Body>) -> Option<&'sta
         .collect();
 
     [
+        PORT_FORWARD_WS_PROTOCOL_V5,
         PORT_FORWARD_WS_PROTOCOL_V4_BINARY,
         PORT_FORWARD_WS_PROTOCOL_V4_BASE64,
         PORT_FORWARD_PROTOCOL_V1,
@@ -1471,6 +1783,7 @@ fn negotiate_portforward_websocket_protocol(req: &Request<Body>) -> Option<&'sta
 
 fn portforward_websocket_protocol(protocol: &str) -> PortForwardWebsocketProtocol {
     match protocol {
+        PORT_FORWARD_WS_PROTOCOL_V5 => PortForwardWebsocketProtocol::V5Binary,
         PORT_FORWARD_WS_PROTOCOL_V4_BINARY => PortForwardWebsocketProtocol::V4Binary,
         PORT_FORWARD_WS_PROTOCOL_V4_BASE64 => PortForwardWebsocketProtocol::V4Base64,
         _ => PortForwardWebsocketProtocol::Legacy,
@@ -1481,6 +1794,32 @@ fn exec_exit_error_message(exit_code: i32) -> String {
     format!("command terminated with non-zero exit code: {}", exit_code)
 }
 
+/// exec 结束时写入 error 通道的内容。v4 起 kubectl 按 `metav1.Status` JSON 解析该通道，
+/// 更早的协议只在失败时写入纯文本。`failed_exit_code` 为 `None` 表示命令成功。
+fn exec_exit_status_payload(protocol: &str, failed_exit_code: Option<i32>) -> Option<Vec<u8>> {
+    if protocol != REMOTECOMMAND_WS_PROTOCOL_V4 && protocol != REMOTECOMMAND_WS_PROTOCOL_V5 {
+        return failed_exit_code.map(|code| exec_exit_error_message(code).into_bytes());
+    }
+    let status = match failed_exit_code {
+        None => serde_json::json!({"metadata": {}, "status": "Success"}),
+        Some(code) => serde_json::json!({
+            "metadata": {},
+            "status": "Failure",
+            "message": exec_exit_error_message(code),
+            "reason": "NonZeroExitCode",
+            "details": {"causes": [{"reason": "ExitCode", "message": code.to_string()}]},
+        }),
+    };
+    serde_json::to_vec(&status).ok()
+}
+
+/// v5 客户端通过 close 通道半关闭 stdin。
+fn is_websocket_stdin_close(protocol: &str, channel: u8, payload: &[u8]) -> bool {
+    protocol == REMOTECOMMAND_WS_PROTOCOL_V5
+        && channel == WS_CHANNEL_CLOSE
+        && payload.first() == Some(&WS_CHANNEL_STDIN)
+}
+
 fn portforward_request_id(
     stream_id: spdy::StreamId,
     stream_type: &str,
@@ -1780,7 +2119,13 @@ fn connect_to_port_in_netns_blocking(
 ) -> anyhow::Result<StdTcpStream> {
     with_netns_path(&netns_path, || {
         let mut last_error = None;
-        for address in ("localhost", port).to_socket_addrs()? {
+        // 不依赖宿主 /etc/hosts 的 localhost 解析：依次尝试 IPv4 与 IPv6 loopback，
+        // 只监听 [::1] 的 IPv6-only Pod 也能转发。
+        let loopbacks = [
+            std::net::IpAddr::V4(std::net::Ipv4Addr::LOCALHOST),
+            std::net::IpAddr::V6(std::net::Ipv6Addr::LOCALHOST),
+        ];
+        for address in loopbacks.map(|ip| std::net::SocketAddr::new(ip, port)) {
             match StdTcpStream::connect(address) {
                 Ok(stream) => {
                     stream.set_nonblocking(true)?;
@@ -1811,7 +2156,7 @@ async fn connect_to_port_in_netns(netns_path: PathBuf, port: u16) -> anyhow::Res
 }
 
 async fn write_portforward_error(
-    writer: &Arc<Mutex<spdy::AsyncSpdyWriter<tokio::io::WriteHalf<hyper::upgrade::Upgraded>>>>,
+    writer: &Arc<Mutex<spdy::AsyncSpdyWriter<tokio::io::WriteHalf<SessionIo>>>>,
     activity: &StreamActivity,
     data_stream: spdy::StreamId,
     error_stream: spdy::StreamId,
@@ -1830,7 +2175,7 @@ async fn write_portforward_error(
 }
 
 async fn write_portforward_stream_error(
-    writer: &Arc<Mutex<spdy::AsyncSpdyWriter<tokio::io::WriteHalf<hyper::upgrade::Upgraded>>>>,
+    writer: &Arc<Mutex<spdy::AsyncSpdyWriter<tokio::io::WriteHalf<SessionIo>>>>,
     activity: &StreamActivity,
     stream_id: spdy::StreamId,
     message: &str,
@@ -1847,7 +2192,7 @@ async fn write_portforward_stream_error(
 }
 
 async fn write_portforward_pair_setup_error(
-    writer: &Arc<Mutex<spdy::AsyncSpdyWriter<tokio::io::WriteHalf<hyper::upgrade::Upgraded>>>>,
+    writer: &Arc<Mutex<spdy::AsyncSpdyWriter<tokio::io::WriteHalf<SessionIo>>>>,
     activity: &StreamActivity,
     pair: PortForwardPair,
     extra_stream: Option<spdy::StreamId>,
@@ -1878,7 +2223,7 @@ async fn write_portforward_pair_setup_error(
 }
 
 async fn serve_portforward_pair(
-    writer: Arc<Mutex<spdy::AsyncSpdyWriter<tokio::io::WriteHalf<hyper::upgrade::Upgraded>>>>,
+    writer: Arc<Mutex<spdy::AsyncSpdyWriter<tokio::io::WriteHalf<SessionIo>>>>,
     activity: StreamActivity,
     netns_path: PathBuf,
     port: u16,
@@ -1965,7 +2310,7 @@ async fn serve_portforward_pair(
 }
 
 async fn serve_exec_spdy(
-    on_upgrade: hyper::upgrade::OnUpgrade,
+    on_upgrade: SessionUpgrade,
     context: ExecServeContext,
 ) -> anyhow::Result<()> {
     let ExecServeContext {
@@ -1973,10 +2318,12 @@ async fn serve_exec_spdy(
         runtime_path,
         runtime_config_path,
         exec_cpu_affinity,
+        exec_process,
         exec_io_socket_path,
         exec_resize_socket_path,
         protocol,
     } = context;
+    let session_exit_code = on_upgrade.exit_code();
 
     if let Some(exec_io_socket_path) = exec_io_socket_path {
         return serve_exec_spdy_via_shim(
@@ -1989,7 +2336,7 @@ async fn serve_exec_spdy(
         .await;
     }
 
-    let upgraded = on_upgrade.await?;
+    let upgraded = on_upgrade.upgrade().await?;
     let (read_half, write_half) = tokio::io::split(upgraded);
     let writer = Arc::new(Mutex::new(spdy::AsyncSpdyWriter::new(write_half)));
     let mut reader = read_half;
@@ -2078,6 +2425,13 @@ async fn serve_exec_spdy(
     if req.tty {
         command.arg("-t");
     }
+    let process_file = exec_process
+        .as_ref()
+        .map(crate::runtime::RuncRuntime::write_exec_process_spec)
+        .transpose()?;
+    if let Some(file) = process_file.as_ref() {
+        command.arg("--process").arg(file.path());
+    }
     command.arg(&req.container_id);
     for arg in &req.cmd {
         command.arg(arg);
@@ -2401,6 +2755,9 @@ async fn serve_exec_spdy(
     });
 
     let status = child.wait().await?;
+    if let Some(code) = status.code() {
+        session_exit_code.record(code);
+    }
 
     if let Some(tx) = console_stdin_tx.as_ref() {
         let _ = tx.send(None).await;
@@ -2462,7 +2819,7 @@ async fn serve_exec_spdy(
 }
 
 async fn serve_exec_websocket(
-    on_upgrade: hyper::upgrade::OnUpgrade,
+    on_upgrade: SessionUpgrade,
     context: ExecServeContext,
 ) -> anyhow::Result<()> {
     let ExecServeContext {
@@ -2470,10 +2827,12 @@ async fn serve_exec_websocket(
         runtime_path,
         runtime_config_path,
         exec_cpu_affinity,
+        exec_process,
         exec_io_socket_path,
         exec_resize_socket_path,
         protocol,
     } = context;
+    let session_exit_code = on_upgrade.exit_code();
 
     if let Some(exec_io_socket_path) = exec_io_socket_path {
         return serve_exec_websocket_via_shim(
@@ -2486,7 +2845,7 @@ async fn serve_exec_websocket(
         .await;
     }
 
-    let upgraded = on_upgrade.await?;
+    let upgraded = on_upgrade.upgrade().await?;
     let (mut reader, writer) = tokio::io::split(upgraded);
     let writer = Arc::new(Mutex::new(writer));
 
@@ -2498,6 +2857,13 @@ async fn serve_exec_websocket(
     if req.tty {
         command.arg("-t");
     }
+    let process_file = exec_process
+        .as_ref()
+        .map(crate::runtime::RuncRuntime::write_exec_process_spec)
+        .transpose()?;
+    if let Some(file) = process_file.as_ref() {
+        command.arg("--process").arg(file.path());
+    }
     command.arg(&req.container_id);
     for arg in &req.cmd {
         command.arg(arg);
@@ -2698,6 +3064,15 @@ async fn serve_exec_websocket(
                     let channel = frame.payload[0];
                     let payload = &frame.payload[1..];
                     match channel {
+                        _ if is_websocket_stdin_close(protocol, channel, payload) => {
+                            if req.tty {
+                                if let Some(tx) = console_stdin_tx_for_input.as_ref() {
+                                    let _ = tx.send(None).await;
+                                }
+                            } else if let Some(mut stdin) = child_stdin.take() {
+                                let _ = stdin.shutdown().await;
+                            }
+                        }
                         WS_CHANNEL_RESIZE if req.tty => {
                             if let Some(tty) = tty_resize_for_input.as_ref() {
                                 match parse_terminal_size(payload).and_then(|(width, height)| {
@@ -2741,6 +3116,9 @@ async fn serve_exec_websocket(
     });
 
     let status = child.wait().await?;
+    if let Some(code) = status.code() {
+        session_exit_code.record(code);
+    }
 
     if let Some(tx) = console_stdin_tx.as_ref() {
         let _ = tx.send(None).await;
@@ -2757,25 +3135,20 @@ async fn serve_exec_websocket(
         task.await??;
     }
 
-    if !status.success() {
-        let exit_code = status.code().unwrap_or_default();
-        write_websocket_channel_frame(
-            &writer,
-            WS_CHANNEL_ERROR,
-            exec_exit_error_message(exit_code).as_bytes(),
-        )
-        .await?;
+    let failed_exit_code = (!status.success()).then(|| status.code().unwrap_or_default());
+    if let Some(payload) = exec_exit_status_payload(protocol, failed_exit_code) {
+        write_websocket_channel_frame(&writer, WS_CHANNEL_ERROR, &payload).await?;
     }
     write_websocket_frame(&mut *writer.lock().await, 0x8, &[]).await?;
     Ok(())
 }
 
 async fn serve_portforward_spdy(
-    on_upgrade: hyper::upgrade::OnUpgrade,
+    on_upgrade: SessionUpgrade,
     ctx: PortForwardRequestContext,
     config: StreamingConfig,
 ) -> anyhow::Result<()> {
-    let upgraded = on_upgrade.await?;
+    let upgraded = on_upgrade.upgrade().await?;
     let (read_half, write_half) = tokio::io::split(upgraded);
     let writer = Arc::new(Mutex::new(spdy::AsyncSpdyWriter::new(write_half)));
     let mut reader = read_half;
@@ -3009,15 +3382,16 @@ async fn serve_portforward_spdy(
 }
 
 async fn serve_attach_spdy(
-    on_upgrade: hyper::upgrade::OnUpgrade,
+    on_upgrade: SessionUpgrade,
     req: AttachRequest,
     attach_io_socket_path: Option<PathBuf>,
     attach_resize_socket_path: Option<PathBuf>,
     attach_stream_close: Option<AttachStreamClose>,
+    scrollback_since: Option<SystemTime>,
     _protocol: &'static str,
 ) -> anyhow::Result<()> {
     let _attach_stream_close = attach_stream_close;
-    let upgraded = on_upgrade.await?;
+    let upgraded = on_upgrade.upgrade().await?;
     let (read_half, write_half) = tokio::io::split(upgraded);
     let writer = Arc::new(Mutex::new(spdy::AsyncSpdyWriter::new(write_half)));
     let mut reader = read_half;
@@ -3151,7 +3525,7 @@ async fn serve_attach_spdy(
     let stdout_stream_for_output = stdout_stream;
     let stderr_stream_for_output = stderr_stream;
     tokio::spawn(async move {
-        let mut decoder = AttachOutputDecoder::default();
+        let mut decoder = AttachOutputDecoder::with_scrollback_since(scrollback_since);
         let mut buffer = [0u8; 8192];
         loop {
             match shim_read.read(&mut buffer).await {
@@ -3312,12 +3686,12 @@ async fn serve_attach_spdy(
 }
 
 async fn serve_portforward_websocket(
-    on_upgrade: hyper::upgrade::OnUpgrade,
+    on_upgrade: SessionUpgrade,
     ctx: PortForwardRequestContext,
     protocol: &'static str,
     config: StreamingConfig,
 ) -> anyhow::Result<()> {
-    let upgraded = on_upgrade.await?;
+    let upgraded = on_upgrade.upgrade().await?;
     let (mut reader, writer) = tokio::io::split(upgraded);
     let writer = Arc::new(Mutex::new(writer));
 
@@ -3359,6 +3733,17 @@ async fn serve_portforward_websocket(
                 else {
                     continue;
                 };
+                if channel == WS_CHANNEL_CLOSE && protocol == PortForwardWebsocketProtocol::V5Binary
+                {
+                    // 半关闭对应端口的上行方向，下行数据继续返回直到目标端关闭连接。
+                    if let Some(tx) = payload
+                        .first()
+                        .and_then(|data_channel| data_channels.get(data_channel))
+                    {
+                        let _ = tx.send(None).await;
+                    }
+                    continue;
+                }
                 if channel % 2 != 0 {
                     continue;
                 }
@@ -3488,13 +3873,13 @@ async fn serve_portforward_websocket(
 }
 
 async fn serve_exec_spdy_via_shim(
-    on_upgrade: hyper::upgrade::OnUpgrade,
+    on_upgrade: SessionUpgrade,
     req: ExecRequest,
     exec_io_socket_path: PathBuf,
     exec_resize_socket_path: Option<PathBuf>,
     _protocol: &'static str,
 ) -> anyhow::Result<()> {
-    let upgraded = on_upgrade.await?;
+    let upgraded = on_upgrade.upgrade().await?;
     let (read_half, write_half) = tokio::io::split(upgraded);
     let writer = Arc::new(Mutex::new(spdy::AsyncSpdyWriter::new(write_half)));
     let mut reader = read_half;
@@ -3781,13 +4166,13 @@ async fn serve_exec_spdy_via_shim(
 }
 
 async fn serve_exec_websocket_via_shim(
-    on_upgrade: hyper::upgrade::OnUpgrade,
+    on_upgrade: SessionUpgrade,
     req: ExecRequest,
     exec_io_socket_path: PathBuf,
     exec_resize_socket_path: Option<PathBuf>,
-    _protocol: &'static str,
+    protocol: &'static str,
 ) -> anyhow::Result<()> {
-    let upgraded = on_upgrade.await?;
+    let upgraded = on_upgrade.upgrade().await?;
     let (mut reader, writer) = tokio::io::split(upgraded);
     let writer = Arc::new(Mutex::new(writer));
 
@@ -3892,6 +4277,9 @@ async fn serve_exec_websocket_via_shim(
                 let channel = frame.payload[0];
                 let payload = &frame.payload[1..];
                 match channel {
+                    _ if is_websocket_stdin_close(protocol, channel, payload) => {
+                        let _ = shim_write.lock().await.shutdown().await;
+                    }
                     WS_CHANNEL_STDIN => {
                         if !payload.is_empty() {
                             shim_write.lock().await.write_all(payload).await?;
@@ -3923,11 +4311,11 @@ async fn serve_exec_websocket_via_shim(
 }
 
 async fn serve_attach_log_spdy(
-    on_upgrade: hyper::upgrade::OnUpgrade,
+    on_upgrade: SessionUpgrade,
     ctx: AttachLogRequestContext,
     _protocol: &'static str,
 ) -> anyhow::Result<()> {
-    let upgraded = on_upgrade.await?;
+    let upgraded = on_upgrade.upgrade().await?;
     let (read_half, write_half) = tokio::io::split(upgraded);
     let writer = Arc::new(Mutex::new(spdy::AsyncSpdyWriter::new(write_half)));
     let mut reader = read_half;
@@ -4093,15 +4481,16 @@ async fn serve_attach_log_spdy(
 }
 
 async fn serve_attach_websocket(
-    on_upgrade: hyper::upgrade::OnUpgrade,
+    on_upgrade: SessionUpgrade,
     req: AttachRequest,
     attach_io_socket_path: Option<PathBuf>,
     attach_resize_socket_path: Option<PathBuf>,
     attach_stream_close: Option<AttachStreamClose>,
-    _protocol: &'static str,
+    scrollback_since: Option<SystemTime>,
+    protocol: &'static str,
 ) -> anyhow::Result<()> {
     let _attach_stream_close = attach_stream_close;
-    let upgraded = on_upgrade.await?;
+    let upgraded = on_upgrade.upgrade().await?;
     let (mut reader, writer) = tokio::io::split(upgraded);
     let writer = Arc::new(Mutex::new(writer));
 
@@ -4134,7 +4523,7 @@ async fn serve_attach_websocket(
     let tty = req.tty;
     let (output_done_tx, mut output_done_rx) = tokio::sync::oneshot::channel::<()>();
     tokio::spawn(async move {
-        let mut decoder = AttachOutputDecoder::default();
+        let mut decoder = AttachOutputDecoder::with_scrollback_since(scrollback_since);
         let mut buffer = [0u8; 8192];
         loop {
             match shim_read.read(&mut buffer).await {
@@ -4216,6 +4605,9 @@ async fn serve_attach_websocket(
                 let channel = frame.payload[0];
                 let payload = &frame.payload[1..];
                 match channel {
+                    _ if req.stdin && is_websocket_stdin_close(protocol, channel, payload) => {
+                        let _ = shim_write.lock().await.shutdown().await;
+                    }
                     WS_CHANNEL_STDIN if req.stdin => {
                         let mut shim_write = shim_write.lock().await;
                         if !payload.is_empty() {
@@ -4252,11 +4644,11 @@ async fn serve_attach_websocket(
 }
 
 async fn serve_attach_log_websocket(
-    on_upgrade: hyper::upgrade::OnUpgrade,
+    on_upgrade: SessionUpgrade,
     ctx: AttachLogRequestContext,
     _protocol: &'static str,
 ) -> anyhow::Result<()> {
-    let upgraded = on_upgrade.await?;
+    let upgraded = on_upgrade.upgrade().await?;
     let (mut reader, writer) = tokio::io::split(upgraded);
     let writer = Arc::new(Mutex::new(writer));
 
@@ -4336,3 +4728,6 @@ fn response(status: StatusCode, body: &str) -> Response<Body> {
 
 #[cfg(test)]
 mod tests;
+
+#[cfg(test)]
+mod conformance_tests;
@@ -0,0 +1,488 @@
+//! 流式会话登记
+//!
+//! token 只覆盖 `GetExec`/`GetAttach`/`GetPortForward` 到连接升级之间的那一段；
+//! 升级之后的 exec/attach/port-forward 会话由这里登记：
+//! - 记录容器、类型、开始时间、客户端地址、协议与双向字节数
+//! - 支持按会话 ID 强制断开（中止会话任务，连接随之关闭）
+//! - 按节点与按容器限制并发会话数，超限时直接拒绝而不是耗尽 fd
+
+use std::collections::HashMap;
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+use super::audit::TranscriptTap;
+
+/// 会话类型。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamingSessionKind {
+    Exec,
+    Attach,
+    PortForward,
+}
+
+impl StreamingSessionKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Exec => "exec",
+            Self::Attach => "attach",
+            Self::PortForward => "portforward",
+        }
+    }
+}
+
+/// 单个活动会话的快照。port-forward 会话的 `container_id` 为 Pod 沙箱 ID。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamingSessionInfo {
+    pub id: String,
+    pub container_id: String,
+    pub kind: String,
+    pub protocol: String,
+    pub client_address: Option<String>,
+    pub started_at_unix_millis: i64,
+    /// 从客户端读到的字节数。
+    pub bytes_received: u64,
+    /// 写给客户端的字节数。
+    pub bytes_sent: u64,
+}
+
+/// 并发会话上限；0 表示不限制。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StreamingSessionLimits {
+    pub max_sessions: usize,
+    pub max_sessions_per_container: usize,
+}
+
+const EXIT_CODE_UNKNOWN: i64 = i64::MIN;
+
+#[derive(Debug)]
+pub(crate) struct SessionCounters {
+    received: AtomicU64,
+    sent: AtomicU64,
+    exit_code: AtomicI64,
+}
+
+impl Default for SessionCounters {
+    fn default() -> Self {
+        Self {
+            received: AtomicU64::new(0),
+            sent: AtomicU64::new(0),
+            exit_code: AtomicI64::new(EXIT_CODE_UNKNOWN),
+        }
+    }
+}
+
+impl SessionCounters {
+    pub(crate) fn bytes_received(&self) -> u64 {
+        self.received.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn bytes_sent(&self) -> u64 {
+        self.sent.load(Ordering::Relaxed)
+    }
+
+    /// 会话内进程的退出码；经 shim 转发的 exec 会话拿不到退出码。
+    pub(crate) fn exit_code(&self) -> Option<i32> {
+        match self.exit_code.load(Ordering::Relaxed) {
+            EXIT_CODE_UNKNOWN => None,
+            code => Some(code as i32),
+        }
+    }
+}
+
+/// 由会话实现回填退出码。
+#[derive(Debug, Clone)]
+pub(crate) struct SessionExitCode(Arc<SessionCounters>);
+
+impl SessionExitCode {
+    pub(crate) fn record(&self, exit_code: i32) {
+        self.0
+            .exit_code
+            .store(i64::from(exit_code), Ordering::Relaxed);
+    }
+}
+
+#[derive(Debug)]
+struct SessionEntry {
+    container_id: String,
+    kind: StreamingSessionKind,
+    protocol: String,
+    client_address: Option<SocketAddr>,
+    started_at_unix_millis: i64,
+    counters: Arc<SessionCounters>,
+    abort: Option<tokio::task::AbortHandle>,
+}
+
+/// 流式会话登记表句柄；克隆后共享同一份状态。
+#[derive(Debug, Clone, Default)]
+pub struct StreamingSessionRegistry {
+    sessions: Arc<Mutex<HashMap<String, SessionEntry>>>,
+    limits: StreamingSessionLimits,
+}
+
+/// 会话登记凭据；随会话任务一起释放时自动注销。
+#[derive(Debug)]
+pub(crate) struct StreamingSessionGuard {
+    registry: StreamingSessionRegistry,
+    id: String,
+    counters: Arc<SessionCounters>,
+}
+
+impl StreamingSessionGuard {
+    pub(crate) fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub(crate) fn counters(&self) -> Arc<SessionCounters> {
+        self.counters.clone()
+    }
+}
+
+impl Drop for StreamingSessionGuard {
+    fn drop(&mut self) {
+        if let Ok(mut sessions) = self.registry.sessions.lock() {
+            sessions.remove(&self.id);
+        }
+    }
+}
+
+impl StreamingSessionRegistry {
+    pub fn new(limits: StreamingSessionLimits) -> Self {
+        Self {
+            sessions: Arc::new(Mutex::new(HashMap::new())),
+            limits,
+        }
+    }
+
+    pub fn limits(&self) -> StreamingSessionLimits {
+        self.limits
+    }
+
+    /// 检查是否还能为指定容器建立新会话；超限时返回可直接展示给用户的原因。
+    pub fn check_capacity(&self, container_id: &str) -> Result<(), String> {
+        let sessions = self
+            .sessions
+            .lock()
+            .map_err(|_| "streaming session registry lock poisoned".to_string())?;
+        self.check_capacity_locked(&sessions, container_id)
+    }
+
+    fn check_capacity_locked(
+        &self,
+        sessions: &HashMap<String, SessionEntry>,
+        container_id: &str,
+    ) -> Result<(), String> {
+        if self.limits.max_sessions != 0 && sessions.len() >= self.limits.max_sessions {
+            return Err(format!(
+                "streaming session limit reached: {} active sessions on this node (api.streaming.max_sessions = {})",
+                sessions.len(),
+                self.limits.max_sessions
+            ));
+        }
+        if self.limits.max_sessions_per_container != 0 {
+            let active = sessions
+                .values()
+                .filter(|entry| entry.container_id == container_id)
+                .count();
+            if active >= self.limits.max_sessions_per_container {
+                return Err(format!(
+                    "streaming session limit reached for {}: {} active sessions (api.streaming.max_sessions_per_container = {})",
+                    container_id, active, self.limits.max_sessions_per_container
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// 登记一个刚完成协议协商的会话；检查与登记在同一把锁内完成。
+    pub(crate) fn open(
+        &self,
+        container_id: &str,
+        kind: StreamingSessionKind,
+        protocol: &str,
+        client_address: Option<SocketAddr>,
+    ) -> Result<StreamingSessionGuard, String> {
+        let mut sessions = self
+            .sessions
+            .lock()
+            .map_err(|_| "streaming session registry lock poisoned".to_string())?;
+        self.check_capacity_locked(&sessions, container_id)?;
+        let id = uuid::Uuid::new_v4().to_simple().to_string();
+        let counters = Arc::new(SessionCounters::default());
+        sessions.insert(
+            id.clone(),
+            SessionEntry {
+                container_id: container_id.to_string(),
+                kind,
+                protocol: protocol.to_string(),
+                client_address,
+                started_at_unix_millis: chrono::Utc::now().timestamp_millis(),
+                counters: counters.clone(),
+                abort: None,
+            },
+        );
+        Ok(StreamingSessionGuard {
+            registry: self.clone(),
+            id,
+            counters,
+        })
+    }
+
+    /// 关联会话任务，供 [`Self::kill`] 中止。
+    pub(crate) fn set_abort_handle(&self, id: &str, abort: tokio::task::AbortHandle) {
+        if let Ok(mut sessions) = self.sessions.lock() {
+            match sessions.get_mut(id) {
+                Some(entry) => entry.abort = Some(abort),
+                // 会话在登记任务句柄之前就已结束。
+                None => drop(abort),
+            }
+        }
+    }
+
+    /// 列出活动会话，按开始时间排序；`container_id` 为空时列出全部。
+    pub fn list(&self, container_id: Option<&str>) -> Vec<StreamingSessionInfo> {
+        let Ok(sessions) = self.sessions.lock() else {
+            return Vec::new();
+        };
+        let mut infos: Vec<_> = sessions
+            .iter()
+            .filter(|(_, entry)| container_id.is_none_or(|id| entry.container_id == id))
+            .map(|(id, entry)| StreamingSessionInfo {
+                id: id.clone(),
+                container_id: entry.container_id.clone(),
+                kind: entry.kind.as_str().to_string(),
+                protocol: entry.protocol.clone(),
+                client_address: entry.client_address.map(|addr| addr.to_string()),
+                started_at_unix_millis: entry.started_at_unix_millis,
+                bytes_received: entry.counters.bytes_received(),
+                bytes_sent: entry.counters.bytes_sent(),
+            })
+            .collect();
+        infos.sort_by(|left, right| {
+            (left.started_at_unix_millis, &left.id).cmp(&(right.started_at_unix_millis, &right.id))
+        });
+        infos
+    }
+
+    /// 强制断开会话；会话不存在时返回 false。
+    pub fn kill(&self, id: &str) -> bool {
+        let Ok(mut sessions) = self.sessions.lock() else {
+            return false;
+        };
+        let Some(entry) = sessions.remove(id) else {
+            return false;
+        };
+        if let Some(abort) = entry.abort {
+            abort.abort();
+        }
+        log::info!(
+            "Killed {} streaming session {} for {}",
+            entry.kind.as_str(),
+            id,
+            entry.container_id
+        );
+        true
+    }
+}
+
+/// 带会话计数的连接升级；升级完成后得到 [`SessionIo`]。
+pub(crate) struct SessionUpgrade {
+    on_upgrade: hyper::upgrade::OnUpgrade,
+    counters: Arc<SessionCounters>,
+    transcript: Option<TranscriptTap>,
+}
+
+impl SessionUpgrade {
+    pub(crate) fn new(
+        on_upgrade: hyper::upgrade::OnUpgrade,
+        session: &StreamingSessionGuard,
+        transcript: Option<TranscriptTap>,
+    ) -> Self {
+        Self {
+            on_upgrade,
+            counters: session.counters.clone(),
+            transcript,
+        }
+    }
+
+    pub(crate) fn exit_code(&self) -> SessionExitCode {
+        SessionExitCode(self.counters.clone())
+    }
+
+    pub(crate) async fn upgrade(self) -> hyper::Result<SessionIo> {
+        Ok(SessionIo {
+            inner: self.on_upgrade.await?,
+            counters: self.counters,
+            transcript: self.transcript,
+        })
+    }
+}
+
+/// 升级后的连接，读写时累加会话字节数；开启转录时把原始字节交给 [`TranscriptTap`]。
+pub(crate) struct SessionIo {
+    inner: hyper::upgrade::Upgraded,
+    counters: Arc<SessionCounters>,
+    transcript: Option<TranscriptTap>,
+}
+
+impl AsyncRead for SessionIo {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let before = buf.filled().len();
+        let result = Pin::new(&mut self.inner).poll_read(cx, buf);
+        if let Poll::Ready(Ok(())) = result {
+            let read = &buf.filled()[before..];
+            self.counters
+                .received
+                .fetch_add(read.len() as u64, Ordering::Relaxed);
+            if let Some(transcript) = self.transcript.as_ref() {
+                transcript.input(read);
+            }
+        }
+        result
+    }
+}
+
+impl AsyncWrite for SessionIo {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let result = Pin::new(&mut self.inner).poll_write(cx, buf);
+        if let Poll::Ready(Ok(written)) = result {
+            self.counters
+                .sent
+                .fetch_add(written as u64, Ordering::Relaxed);
+            if let Some(transcript) = self.transcript.as_ref() {
+                transcript.output(&buf[..written]);
+            }
+        }
+        result
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        let result = Pin::new(&mut self.inner).poll_write_vectored(cx, bufs);
+        if let Poll::Ready(Ok(written)) = result {
+            self.counters
+                .sent
+                .fetch_add(written as u64, Ordering::Relaxed);
+            if let Some(transcript) = self.transcript.as_ref() {
+                let mut remaining = written;
+                for buf in bufs {
+                    if remaining == 0 {
+                        break;
+                    }
+                    let take = remaining.min(buf.len());
+                    transcript.output(&buf[..take]);
+                    remaining -= take;
+                }
+            }
+        }
+        result
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_enforces_node_and_per_container_limits() {
+        let registry = StreamingSessionRegistry::new(StreamingSessionLimits {
+            max_sessions: 3,
+            max_sessions_per_container: 2,
+        });
+
+        let first = registry
+            .open("c1", StreamingSessionKind::Exec, "v5.channel.k8s.io", None)
+            .unwrap();
+        let _second = registry
+            .open(
+                "c1",
+                StreamingSessionKind::Attach,
+                "v4.channel.k8s.io",
+                None,
+            )
+            .unwrap();
+        let err = registry
+            .open("c1", StreamingSessionKind::Exec, "v4.channel.k8s.io", None)
+            .unwrap_err();
+        assert!(err.contains("max_sessions_per_container = 2"));
+        assert!(registry.check_capacity("c1").is_err());
+
+        let _third = registry
+            .open(
+                "c2",
+                StreamingSessionKind::PortForward,
+                "portforward.k8s.io",
+                None,
+            )
+            .unwrap();
+        let err = registry.check_capacity("c3").unwrap_err();
+        assert!(err.contains("api.streaming.max_sessions = 3"));
+
+        drop(first);
+        assert!(registry.check_capacity("c1").is_ok());
+        assert_eq!(registry.list(Some("c1")).len(), 1);
+        assert_eq!(registry.list(None).len(), 2);
+    }
+
+    #[tokio::test]
+    async fn kill_aborts_session_task_and_unregisters_it() {
+        let registry = StreamingSessionRegistry::default();
+        let session = registry
+            .open(
+                "c1",
+                StreamingSessionKind::Exec,
+                "v4.channel.k8s.io",
+                Some("10.0.0.5:43210".parse().unwrap()),
+            )
+            .unwrap();
+        let id = session.id().to_string();
+        let task = tokio::spawn(async move {
+            let _session = session;
+            std::future::pending::<()>().await;
+        });
+        registry.set_abort_handle(&id, task.abort_handle());
+
+        let sessions = registry.list(None);
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].kind, "exec");
+        assert_eq!(
+            sessions[0].client_address.as_deref(),
+            Some("10.0.0.5:43210")
+        );
+
+        assert!(registry.kill(&id));
+        assert!(task.await.unwrap_err().is_cancelled());
+        assert!(registry.list(None).is_empty());
+        assert!(!registry.kill(&id));
+    }
+}
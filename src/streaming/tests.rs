@@ -83,9 +83,11 @@ async fn test_exec_url_generation() {
                 runtime_path: PathBuf::from("/bin/false"),
                 runtime_config_path: PathBuf::new(),
                 exec_cpu_affinity: None,
+                exec_process: None,
                 exec_io_socket_path: None,
                 exec_resize_socket_path: None,
-                websocket_enabled: true,
+                transports: StreamTransports::default(),
+                audit: None,
             },
         )
         .await
@@ -141,7 +143,10 @@ async fn test_attach_url_generation() {
         tty: false,
     };
 
-    let response = server.get_attach(&req, None, None, true).await.unwrap();
+    let response = server
+        .get_attach(&req, None, None, StreamTransports::default())
+        .await
+        .unwrap();
     assert!(response.url.contains("/attach/"));
 }
 
@@ -161,9 +166,11 @@ async fn test_exec_transport_explicitly_rejects_non_spdy_requests() {
             runtime_path: PathBuf::from("/bin/false"),
             runtime_config_path: PathBuf::new(),
             exec_cpu_affinity: None,
+            exec_process: None,
             exec_io_socket_path: None,
             exec_resize_socket_path: None,
-            websocket_enabled: true,
+            transports: StreamTransports::default(),
+            audit: None,
         }))
         .await;
     let request = Request::builder()
@@ -197,9 +204,11 @@ async fn test_exec_transport_accepts_websocket_upgrade_requests() {
             runtime_path: PathBuf::from("/bin/false"),
             runtime_config_path: PathBuf::new(),
             exec_cpu_affinity: None,
+            exec_process: None,
             exec_io_socket_path: None,
             exec_resize_socket_path: None,
-            websocket_enabled: true,
+            transports: StreamTransports::default(),
+            audit: None,
         }))
         .await;
     let request = Request::builder()
@@ -249,9 +258,14 @@ async fn test_exec_transport_rejects_websocket_when_disabled_for_token() {
             runtime_path: PathBuf::from("/bin/false"),
             runtime_config_path: PathBuf::new(),
             exec_cpu_affinity: None,
+            exec_process: None,
             exec_io_socket_path: None,
             exec_resize_socket_path: None,
-            websocket_enabled: false,
+            transports: StreamTransports {
+                websocket: false,
+                spdy: true,
+            },
+            audit: None,
         }))
         .await;
     let request = Request::builder()
@@ -313,7 +327,8 @@ async fn test_attach_transport_explicitly_rejects_non_spdy_requests() {
             attach_io_socket_path: None,
             attach_resize_socket_path: None,
             attach_stream_close: None,
-            websocket_enabled: true,
+            transports: StreamTransports::default(),
+            audit: None,
         }))
         .await;
     let request = Request::builder()
@@ -331,6 +346,59 @@ async fn test_attach_transport_explicitly_rejects_non_spdy_requests() {
         .contains("SPDY or websocket upgrade headers"));
 }
 
+#[tokio::test]
+async fn test_attach_transport_rejects_invalid_scrollback_since() {
+    let server = StreamingServer::for_test("http://127.0.0.1:12345");
+    let token = server
+        .insert_request(StreamingRequest::Attach(AttachRequestContext {
+            req: AttachRequest {
+                container_id: "abc".to_string(),
+                stdin: false,
+                stdout: true,
+                stderr: true,
+                tty: false,
+            },
+            attach_io_socket_path: None,
+            attach_resize_socket_path: None,
+            attach_stream_close: None,
+            transports: StreamTransports::default(),
+            audit: None,
+        }))
+        .await;
+    let request = Request::builder()
+        .method(Method::GET)
+        .uri(format!("/attach/{}?sinceSeconds=-5", token))
+        .body(Body::empty())
+        .unwrap();
+
+    let response = server
+        .handle_request_for_test(PathBuf::from("/bin/false"), request)
+        .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert!(response_body_string(response)
+        .await
+        .contains("invalid sinceSeconds"));
+}
+
+#[test]
+fn test_attach_scrollback_since_reads_query_parameter() {
+    let request = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+
+    assert_eq!(
+        attach_scrollback_since(&request("/attach/t")).unwrap(),
+        None
+    );
+    assert_eq!(
+        attach_scrollback_since(&request("/attach/t?foo=bar")).unwrap(),
+        None
+    );
+    let since = attach_scrollback_since(&request("/attach/t?foo=bar&sinceSeconds=30"))
+        .unwrap()
+        .unwrap();
+    let age = SystemTime::now().duration_since(since).unwrap();
+    assert!(age >= Duration::from_secs(30) && age < Duration::from_secs(40));
+}
+
 #[tokio::test]
 async fn test_attach_log_url_generation() {
     let server = StreamingServer::for_test("http://127.0.0.1:12345");
@@ -343,7 +411,12 @@ async fn test_attach_log_url_generation() {
     };
 
     let response = server
-        .get_attach_log(&req, PathBuf::from("/var/log/pods/abc.log"), true)
+        .get_attach_log(
+            &req,
+            PathBuf::from("/var/log/pods/abc.log"),
+            StreamTransports::default(),
+            None,
+        )
         .await
         .unwrap();
     assert!(response.url.contains("/attach/"));
@@ -365,7 +438,8 @@ async fn test_attach_transport_accepts_websocket_upgrade_requests() {
             attach_io_socket_path: None,
             attach_resize_socket_path: None,
             attach_stream_close: None,
-            websocket_enabled: true,
+            transports: StreamTransports::default(),
+            audit: None,
         }))
         .await;
     let request = Request::builder()
@@ -414,7 +488,11 @@ async fn test_attach_transport_rejects_websocket_when_disabled_for_token() {
             attach_io_socket_path: None,
             attach_resize_socket_path: None,
             attach_stream_close: None,
-            websocket_enabled: false,
+            transports: StreamTransports {
+                websocket: false,
+                spdy: true,
+            },
+            audit: None,
         }))
         .await;
     let request = Request::builder()
@@ -476,7 +554,8 @@ async fn test_attach_log_transport_accepts_websocket_upgrade_requests() {
                 tty: false,
             },
             log_path: PathBuf::from("/var/log/pods/abc.log"),
-            websocket_enabled: true,
+            transports: StreamTransports::default(),
+            audit: None,
         }))
         .await;
     let request = Request::builder()
@@ -519,7 +598,11 @@ async fn test_portforward_url_generation() {
     };
 
     let response = server
-        .get_port_forward(&req, PathBuf::from("/proc/self/ns/net"), true)
+        .get_port_forward(
+            &req,
+            PathBuf::from("/proc/self/ns/net"),
+            StreamTransports::default(),
+        )
         .await
         .unwrap();
     assert!(response.url.contains("/portforward/"));
@@ -536,7 +619,7 @@ async fn test_portforward_transport_explicitly_rejects_non_spdy_requests() {
                 port: vec![8080],
             },
             netns_path: PathBuf::from("/proc/thread-self/ns/net"),
-            websocket_enabled: true,
+            transports: StreamTransports::default(),
         }))
         .await;
     let request = Request::builder()
@@ -564,7 +647,7 @@ async fn test_portforward_transport_accepts_websocket_upgrade_requests() {
                 port: vec![8080],
             },
             netns_path: PathBuf::from("/proc/thread-self/ns/net"),
-            websocket_enabled: true,
+            transports: StreamTransports::default(),
         }))
         .await;
     let request = Request::builder()
@@ -608,7 +691,10 @@ async fn test_portforward_transport_rejects_websocket_when_disabled_for_token()
                 port: vec![8080],
             },
             netns_path: PathBuf::from("/proc/thread-self/ns/net"),
-            websocket_enabled: false,
+            transports: StreamTransports {
+                websocket: false,
+                spdy: true,
+            },
         }))
         .await;
     let request = Request::builder()
@@ -631,6 +717,40 @@ async fn test_portforward_transport_rejects_websocket_when_disabled_for_token()
         .contains("websocket streaming is disabled"));
 }
 
+#[tokio::test]
+async fn test_portforward_transport_rejects_spdy_when_disabled_for_token() {
+    let server = StreamingServer::for_test("http://127.0.0.1:12345");
+    let token = server
+        .insert_request(StreamingRequest::PortForward(PortForwardRequestContext {
+            req: PortForwardRequest {
+                pod_sandbox_id: "pod-1".to_string(),
+                port: vec![8080],
+            },
+            netns_path: PathBuf::from("/proc/thread-self/ns/net"),
+            transports: StreamTransports {
+                websocket: true,
+                spdy: false,
+            },
+        }))
+        .await;
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri(format!("/portforward/{}", token))
+        .header(CONNECTION, "Upgrade")
+        .header(UPGRADE, spdy::SPDY_31)
+        .header("X-Stream-Protocol-Version", PORT_FORWARD_PROTOCOL_V1)
+        .body(Body::empty())
+        .unwrap();
+
+    let response = server
+        .handle_request_for_test(PathBuf::from("/bin/false"), request)
+        .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert!(response_body_string(response)
+        .await
+        .contains("SPDY streaming is disabled"));
+}
+
 #[tokio::test]
 async fn test_portforward_transport_requires_supported_protocol_version() {
     let server = StreamingServer::for_test("http://127.0.0.1:12345");
@@ -641,7 +761,7 @@ async fn test_portforward_transport_requires_supported_protocol_version() {
                 port: vec![8080],
             },
             netns_path: PathBuf::from("/proc/thread-self/ns/net"),
-            websocket_enabled: true,
+            transports: StreamTransports::default(),
         }))
         .await;
     let request = Request::builder()
@@ -661,6 +781,69 @@ async fn test_portforward_transport_requires_supported_protocol_version() {
         .contains("no supported port-forward protocol was requested"));
 }
 
+#[tokio::test]
+async fn test_streaming_session_limit_rejects_new_tokens_and_upgrades() {
+    let server = StreamingServer::for_test_with_config(
+        "http://127.0.0.1:12345",
+        StreamingConfig {
+            max_sessions_per_container: 1,
+            ..Default::default()
+        },
+    );
+    let port_forward_req = PortForwardRequest {
+        pod_sandbox_id: "pod-1".to_string(),
+        port: vec![8080],
+    };
+    let token = server
+        .insert_request(StreamingRequest::PortForward(PortForwardRequestContext {
+            req: port_forward_req.clone(),
+            netns_path: PathBuf::from("/proc/thread-self/ns/net"),
+            transports: StreamTransports::default(),
+        }))
+        .await;
+    let _active = server
+        .sessions()
+        .open(
+            "pod-1",
+            StreamingSessionKind::PortForward,
+            "spdy/portforward.k8s.io",
+            None,
+        )
+        .unwrap();
+
+    let err = server
+        .get_port_forward(
+            &port_forward_req,
+            PathBuf::from("/proc/thread-self/ns/net"),
+            StreamTransports::default(),
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::ResourceExhausted);
+    assert!(err.message().contains("max_sessions_per_container = 1"));
+
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri(format!("/portforward/{}", token))
+        .header(CONNECTION, "Upgrade")
+        .header(UPGRADE, spdy::SPDY_31)
+        .header("X-Stream-Protocol-Version", PORT_FORWARD_PROTOCOL_V1)
+        .body(Body::empty())
+        .unwrap();
+    let response = server
+        .handle_request_from_for_test(
+            PathBuf::from("/bin/false"),
+            Some("10.0.0.5:43210".parse().unwrap()),
+            request,
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert!(response_body_string(response)
+        .await
+        .contains("streaming session limit reached for pod-1"));
+    assert_eq!(server.sessions().list(Some("pod-1")).len(), 1);
+}
+
 #[tokio::test]
 async fn test_portforward_route_rejects_token_kind_mismatch() {
     let server = StreamingServer::for_test("http://127.0.0.1:12345");
@@ -677,9 +860,11 @@ async fn test_portforward_route_rejects_token_kind_mismatch() {
             runtime_path: PathBuf::from("/bin/false"),
             runtime_config_path: PathBuf::new(),
             exec_cpu_affinity: None,
+            exec_process: None,
             exec_io_socket_path: None,
             exec_resize_socket_path: None,
-            websocket_enabled: true,
+            transports: StreamTransports::default(),
+            audit: None,
         }))
         .await;
     let request = Request::builder()
@@ -720,9 +905,11 @@ async fn test_expired_streaming_token_is_rejected() {
                 runtime_path: PathBuf::from("/bin/false"),
                 runtime_config_path: PathBuf::new(),
                 exec_cpu_affinity: None,
+                exec_process: None,
                 exec_io_socket_path: None,
                 exec_resize_socket_path: None,
-                websocket_enabled: true,
+                transports: StreamTransports::default(),
+                audit: None,
             }),
             Duration::from_secs(6),
         )
@@ -1196,6 +1383,28 @@ fn test_negotiate_portforward_protocol_accepts_v1() {
     );
 }
 
+#[test]
+fn test_negotiate_portforward_websocket_protocol_prefers_v5() {
+    let request = Request::builder()
+        .header(
+            SEC_WEBSOCKET_PROTOCOL,
+            format!(
+                "{}, {}",
+                PORT_FORWARD_WS_PROTOCOL_V4_BINARY, PORT_FORWARD_WS_PROTOCOL_V5
+            ),
+        )
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(
+        negotiate_portforward_websocket_protocol(&request),
+        Some(PORT_FORWARD_WS_PROTOCOL_V5)
+    );
+    assert_eq!(
+        portforward_websocket_protocol(PORT_FORWARD_WS_PROTOCOL_V5),
+        PortForwardWebsocketProtocol::V5Binary
+    );
+}
+
 #[test]
 fn test_negotiate_portforward_websocket_protocol_prefers_v4() {
     let request = Request::builder()
@@ -1223,6 +1432,22 @@ fn test_negotiate_portforward_websocket_protocol_prefers_v4() {
     );
 }
 
+#[test]
+fn test_exec_exit_status_payload_uses_plain_text_before_v4() {
+    assert_eq!(exec_exit_status_payload("v3.channel.k8s.io", None), None);
+    assert_eq!(
+        exec_exit_status_payload("v3.channel.k8s.io", Some(2)),
+        Some(b"command terminated with non-zero exit code: 2".to_vec())
+    );
+    let status: serde_json::Value = serde_json::from_slice(
+        &exec_exit_status_payload(REMOTECOMMAND_WS_PROTOCOL_V5, Some(2))
+            .expect("v5 should report a status"),
+    )
+    .expect("status should be json");
+    assert_eq!(status["reason"], "NonZeroExitCode");
+    assert_eq!(status["details"]["causes"][0]["message"], "2");
+}
+
 #[test]
 fn test_exec_exit_error_message_is_stable() {
     assert_eq!(
@@ -1366,3 +1591,41 @@ fn test_with_netns_path_allows_current_namespace() {
     let result = with_netns_path(Path::new("/proc/thread-self/ns/net"), || Ok(42)).unwrap();
     assert_eq!(result, 42);
 }
+
+#[tokio::test]
+async fn test_port_forward_connects_to_ipv6_only_loopback_in_throwaway_netns() {
+    let can_unshare = std::process::Command::new("unshare")
+        .args(["-n", "true"])
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false);
+    if !can_unshare {
+        eprintln!("skipping IPv6 port-forward test because CLONE_NEWNET is unavailable");
+        return;
+    }
+
+    let temp = tempdir().unwrap();
+    let manager = crate::network::NamespaceManager::new(temp.path().to_path_buf());
+    let netns_path = manager.create("ipv6-only").await.expect("create netns");
+    crate::network::netlink::set_loopback_up(netns_path.clone())
+        .await
+        .expect("bring up loopback");
+
+    let listener = with_netns_path(&netns_path, || {
+        Ok(TcpListener::bind((std::net::Ipv6Addr::LOCALHOST, 0))?)
+    })
+    .expect("bind [::1] inside netns");
+    let port = listener.local_addr().unwrap().port();
+
+    let stream = connect_to_port_in_netns_blocking(netns_path.clone(), port)
+        .expect("port-forward should fall back to the IPv6 loopback");
+    assert!(stream.peer_addr().unwrap().is_ipv6());
+    listener.accept().expect("accept forwarded connection");
+
+    drop(stream);
+    drop(listener);
+    manager
+        .remove(netns_path.to_str().unwrap())
+        .await
+        .expect("remove netns");
+}
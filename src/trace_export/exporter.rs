@@ -0,0 +1,307 @@
+//! 批量 span 处理器与 OTLP/HTTP 导出
+//!
+//! span 结束时经有界队列交给后台任务；队列满时直接丢弃并计数，不阻塞业务路径。
+//! 后台任务凑满一批或到达导出周期时 POST 到 collector，失败只打印到 stderr，
+//! 避免导出错误再次进入 tracing 形成回环。
+
+use super::otlp::{encode_json, encode_protobuf, ExportResource, SpanRecord};
+use crate::config::TracingProtocol;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// span 产生方持有的发送端。
+#[derive(Clone)]
+pub(crate) struct SpanQueue {
+    sender: mpsc::Sender<SpanRecord>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl SpanQueue {
+    #[cfg(test)]
+    pub(crate) fn for_test(capacity: usize) -> (Self, mpsc::Receiver<SpanRecord>) {
+        let (sender, receiver) = mpsc::channel(capacity);
+        (
+            Self {
+                sender,
+                dropped: Arc::new(AtomicU64::new(0)),
+            },
+            receiver,
+        )
+    }
+
+    pub(crate) fn push(&self, span: SpanRecord) {
+        if self.sender.try_send(span).is_err() {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct BatchOptions {
+    pub max_queue_size: usize,
+    pub max_export_batch_size: usize,
+    pub export_interval: Duration,
+}
+
+pub(crate) struct OtlpHttpExporter {
+    client: reqwest::Client,
+    endpoint: String,
+    protocol: TracingProtocol,
+    resource: ExportResource,
+}
+
+impl OtlpHttpExporter {
+    pub(crate) fn new(
+        endpoint: &str,
+        protocol: TracingProtocol,
+        timeout: Duration,
+        resource: ExportResource,
+    ) -> anyhow::Result<Self> {
+        let client = reqwest::Client::builder().timeout(timeout).build()?;
+        Ok(Self {
+            client,
+            endpoint: traces_endpoint(endpoint)?,
+            protocol,
+            resource,
+        })
+    }
+
+    pub(crate) async fn export(&self, spans: &[SpanRecord]) -> anyhow::Result<()> {
+        let request = match self.protocol {
+            TracingProtocol::HttpProtobuf => self
+                .client
+                .post(&self.endpoint)
+                .header("content-type", "application/x-protobuf")
+                .body(encode_protobuf(&self.resource, spans)),
+            TracingProtocol::HttpJson => self
+                .client
+                .post(&self.endpoint)
+                .header("content-type", "application/json")
+                .body(encode_json(&self.resource, spans)),
+        };
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            anyhow::bail!("collector returned HTTP {}", response.status());
+        }
+        Ok(())
+    }
+}
+
+/// 只给出 collector 根地址时补上 OTLP/HTTP 的 `/v1/traces` 路径，已有路径则原样使用。
+pub(crate) fn traces_endpoint(endpoint: &str) -> anyhow::Result<String> {
+    let mut url = reqwest::Url::parse(endpoint.trim())?;
+    if !matches!(url.scheme(), "http" | "https") {
+        anyhow::bail!("tracing.endpoint must start with http:// or https://");
+    }
+    if url.path().is_empty() || url.path() == "/" {
+        url.set_path("/v1/traces");
+    }
+    Ok(url.to_string())
+}
+
+/// 启动后台批处理任务，返回写入端。
+pub(crate) fn spawn_batch_processor(
+    exporter: OtlpHttpExporter,
+    options: BatchOptions,
+) -> SpanQueue {
+    let (sender, receiver) = mpsc::channel(options.max_queue_size.max(1));
+    let dropped = Arc::new(AtomicU64::new(0));
+    tokio::spawn(run_batch_processor(
+        exporter,
+        options,
+        receiver,
+        dropped.clone(),
+    ));
+    SpanQueue { sender, dropped }
+}
+
+async fn run_batch_processor(
+    exporter: OtlpHttpExporter,
+    options: BatchOptions,
+    mut receiver: mpsc::Receiver<SpanRecord>,
+    dropped: Arc<AtomicU64>,
+) {
+    let batch_size = options.max_export_batch_size.max(1);
+    let mut batch = Vec::with_capacity(batch_size);
+    let mut ticker = tokio::time::interval(options.export_interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        let closed = tokio::select! {
+            span = receiver.recv() => match span {
+                Some(span) => {
+                    batch.push(span);
+                    if batch.len() < batch_size {
+                        continue;
+                    }
+                    false
+                }
+                None => true,
+            },
+            _ = ticker.tick() => false,
+        };
+        let dropped_spans = dropped.swap(0, Ordering::Relaxed);
+        if dropped_spans > 0 {
+            eprintln!("trace export dropped {dropped_spans} spans: queue full");
+        }
+        if !batch.is_empty() {
+            if let Err(err) = exporter.export(&batch).await {
+                eprintln!("trace export failed: {err:#}");
+            }
+            batch.clear();
+        }
+        if closed {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace_export::otlp::{ExportTraceServiceRequest, SpanKind};
+    use hyper::service::{make_service_fn, service_fn};
+    use prost::Message;
+
+    fn span(name: &str) -> SpanRecord {
+        SpanRecord {
+            trace_id: [1; 16],
+            span_id: [2; 8],
+            parent_span_id: None,
+            name: name.to_string(),
+            kind: SpanKind::Internal,
+            start_time_unix_nano: 1,
+            end_time_unix_nano: 2,
+            attributes: Vec::new(),
+            events: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// 本地 HTTP collector 替身，把收到的 (路径, content-type, body) 转发给测试。
+    async fn start_collector() -> (String, mpsc::UnboundedReceiver<(String, String, Vec<u8>)>) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let make_service = make_service_fn(move |_| {
+            let sender = sender.clone();
+            async move {
+                Ok::<_, hyper::Error>(service_fn(move |request: hyper::Request<hyper::Body>| {
+                    let sender = sender.clone();
+                    async move {
+                        let path = request.uri().path().to_string();
+                        let content_type = request
+                            .headers()
+                            .get("content-type")
+                            .and_then(|value| value.to_str().ok())
+                            .unwrap_or_default()
+                            .to_string();
+                        let body = hyper::body::to_bytes(request.into_body()).await?;
+                        let _ = sender.send((path, content_type, body.to_vec()));
+                        Ok::<_, hyper::Error>(hyper::Response::new(hyper::Body::empty()))
+                    }
+                }))
+            }
+        });
+        let server = hyper::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(make_service);
+        let endpoint = format!("http://{}", server.local_addr());
+        tokio::spawn(server);
+        (endpoint, receiver)
+    }
+
+    fn resource() -> ExportResource {
+        ExportResource {
+            service_name: "crius-test".to_string(),
+            service_version: "0.0.0".to_string(),
+        }
+    }
+
+    #[test]
+    fn traces_endpoint_appends_default_signal_path() {
+        assert_eq!(
+            traces_endpoint("http://collector:4318").unwrap(),
+            "http://collector:4318/v1/traces"
+        );
+        assert_eq!(
+            traces_endpoint("https://collector/otlp/v1/traces").unwrap(),
+            "https://collector/otlp/v1/traces"
+        );
+        assert!(traces_endpoint("grpc://collector:4317").is_err());
+    }
+
+    #[tokio::test]
+    async fn batch_processor_flushes_full_batches_as_protobuf() {
+        let (endpoint, mut received) = start_collector().await;
+        let exporter = OtlpHttpExporter::new(
+            &endpoint,
+            TracingProtocol::HttpProtobuf,
+            Duration::from_secs(5),
+            resource(),
+        )
+        .unwrap();
+        let queue = spawn_batch_processor(
+            exporter,
+            BatchOptions {
+                max_queue_size: 16,
+                max_export_batch_size: 2,
+                export_interval: Duration::from_secs(3600),
+            },
+        );
+        queue.push(span("first"));
+        queue.push(span("second"));
+
+        let (path, content_type, body) =
+            tokio::time::timeout(Duration::from_secs(5), received.recv())
+                .await
+                .unwrap()
+                .unwrap();
+        assert_eq!(path, "/v1/traces");
+        assert_eq!(content_type, "application/x-protobuf");
+        let request = ExportTraceServiceRequest::decode(body.as_slice()).unwrap();
+        let names = request.resource_spans[0].scope_spans[0]
+            .spans
+            .iter()
+            .map(|span| span.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["first", "second"]);
+    }
+
+    #[tokio::test]
+    async fn batch_processor_flushes_partial_batches_on_interval_as_json() {
+        let (endpoint, mut received) = start_collector().await;
+        let exporter = OtlpHttpExporter::new(
+            &endpoint,
+            TracingProtocol::HttpJson,
+            Duration::from_secs(5),
+            resource(),
+        )
+        .unwrap();
+        let queue = spawn_batch_processor(
+            exporter,
+            BatchOptions {
+                max_queue_size: 16,
+                max_export_batch_size: 512,
+                export_interval: Duration::from_millis(50),
+            },
+        );
+        queue.push(span("lonely"));
+
+        let (_, content_type, body) = tokio::time::timeout(Duration::from_secs(5), received.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(content_type, "application/json");
+        let payload: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            payload["resourceSpans"][0]["scopeSpans"][0]["spans"][0]["name"],
+            "lonely"
+        );
+    }
+
+    #[tokio::test]
+    async fn span_queue_counts_drops_when_full() {
+        let (queue, _receiver) = SpanQueue::for_test(1);
+        queue.push(span("kept"));
+        queue.push(span("dropped"));
+        assert_eq!(queue.dropped.load(Ordering::Relaxed), 1);
+    }
+}
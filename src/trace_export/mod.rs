@@ -0,0 +1,566 @@
+//! tracing span 的 OpenTelemetry 导出
+//!
+//! `TraceExportLayer` 为 tracing span 分配 trace/span ID、维护父子关系，span 结束后经批处理器
+//! 以 OTLP/HTTP（protobuf 或 JSON）导出；span 内的 tracing 事件作为 span event 一并上报。
+//! 入站 CRI gRPC 请求由 [`GrpcTraceLayer`] 读取 W3C `traceparent` 作为远端父 span，
+//! 下游的 NRI 插件调用与 shim RPC 通过 [`current_traceparent`] 继续传播上下文。
+//!
+//! 以下 span 字段有特殊含义，不作为普通属性导出：
+//! `otel.name` 覆盖 span 名称，`otel.kind` 取 `server`/`client`/`internal`，
+//! `otel.status_message` 把 span 标记为错误，`traceparent` 指定远端父 span。
+
+mod exporter;
+mod otlp;
+mod propagation;
+
+use std::pin::Pin;
+use std::task::{Context as TaskContext, Poll};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use exporter::{spawn_batch_processor, BatchOptions, OtlpHttpExporter, SpanQueue};
+use otlp::{AttributeValue, ExportResource, SpanEventRecord, SpanKind, SpanRecord};
+use tonic::codegen::http;
+use tower::{Layer as TowerLayer, Service};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Instrument, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+pub use propagation::{enter_remote_context, RemoteContextGuard, TraceContext};
+
+/// W3C Trace Context 头名称，同时用作 ttrpc metadata 与 shim RPC 的键。
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+
+const MAX_EVENTS_PER_SPAN: usize = 128;
+
+#[derive(Clone)]
+pub struct TraceExportLayer {
+    queue: SpanQueue,
+    sampling_rate_per_million: u32,
+}
+
+pub fn build_layer(
+    config: &crate::config::TracingConfig,
+) -> anyhow::Result<Option<TraceExportLayer>> {
+    if !config.enable {
+        return Ok(None);
+    }
+    let endpoint = config.endpoint.trim();
+    if endpoint.is_empty() {
+        anyhow::bail!("tracing.endpoint must not be empty when tracing.enable is true");
+    }
+
+    let exporter = OtlpHttpExporter::new(
+        endpoint,
+        config.protocol,
+        Duration::from_millis(config.export_timeout_ms),
+        ExportResource {
+            service_name: config.service_name.clone(),
+            service_version: env!("CARGO_PKG_VERSION").to_string(),
+        },
+    )
+    .map_err(|err| anyhow::anyhow!("invalid tracing.endpoint {endpoint}: {err}"))?;
+    let queue = spawn_batch_processor(
+        exporter,
+        BatchOptions {
+            max_queue_size: config.max_queue_size,
+            max_export_batch_size: config.max_export_batch_size,
+            export_interval: Duration::from_millis(config.export_interval_ms),
+        },
+    );
+
+    Ok(Some(TraceExportLayer {
+        queue,
+        sampling_rate_per_million: config.sampling_rate_per_million,
+    }))
+}
+
+/// 生命周期阶段 span，导出名为 `<scope>.<phase>`，`object_id` 为空时不记录。
+pub fn phase_span(scope: &str, phase: &str, object_id: &str) -> tracing::Span {
+    tracing::info_span!(
+        target: "crius::trace",
+        "phase",
+        otel.name = %format_args!("{scope}.{phase}"),
+        crius.id = object_id,
+    )
+}
+
+/// 当前 span 的 trace 上下文；没有导出层时退回本线程上的远端上下文。
+pub fn current_trace_context() -> Option<TraceContext> {
+    tracing::Span::current()
+        .with_subscriber(|(id, dispatch)| {
+            let registry = dispatch.downcast_ref::<tracing_subscriber::Registry>()?;
+            let span = registry.span(id)?;
+            let extensions = span.extensions();
+            extensions.get::<SpanState>().map(|state| state.context)
+        })
+        .flatten()
+        .or_else(propagation::remote_context)
+}
+
+/// 当前 span 的 `traceparent`；没有导出层时退回本线程上的远端上下文。
+pub fn current_traceparent() -> Option<String> {
+    current_trace_context().map(|context| context.to_traceparent())
+}
+
+struct SpanState {
+    context: TraceContext,
+    parent_span_id: Option<[u8; 8]>,
+    name: String,
+    kind: SpanKind,
+    start_time_unix_nano: u64,
+    attributes: Vec<(String, AttributeValue)>,
+    events: Vec<SpanEventRecord>,
+    error: Option<String>,
+}
+
+impl SpanState {
+    fn apply(&mut self, fields: Vec<(String, AttributeValue)>) {
+        for (key, value) in fields {
+            match (key.as_str(), value) {
+                ("otel.name", AttributeValue::String(name)) => self.name = name,
+                ("otel.kind", AttributeValue::String(kind)) => {
+                    if let Some(kind) = SpanKind::parse(&kind) {
+                        self.kind = kind;
+                    }
+                }
+                ("otel.status_message", AttributeValue::String(message)) => {
+                    self.error = Some(message)
+                }
+                (TRACEPARENT_HEADER, _) => {}
+                (_, value) => {
+                    match self
+                        .attributes
+                        .iter_mut()
+                        .find(|(existing, _)| *existing == key)
+                    {
+                        Some((_, existing)) => *existing = value,
+                        None => self.attributes.push((key, value)),
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn now_unix_nano() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos() as u64)
+        .unwrap_or_default()
+}
+
+fn remote_parent(fields: &[(String, AttributeValue)]) -> Option<TraceContext> {
+    fields.iter().find_map(|(key, value)| match value {
+        AttributeValue::String(value) if key == TRACEPARENT_HEADER => TraceContext::parse(value),
+        _ => None,
+    })
+}
+
+impl<S> Layer<S> for TraceExportLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut visitor = FieldVisitor::default();
+        attrs.record(&mut visitor);
+
+        // 父 span 优先级：显式 traceparent > 本进程内父 span > 线程上的远端上下文。
+        let parent = remote_parent(&visitor.fields)
+            .or_else(|| {
+                span.parent().and_then(|parent| {
+                    parent
+                        .extensions()
+                        .get::<SpanState>()
+                        .map(|state| state.context)
+                })
+            })
+            .or_else(propagation::remote_context);
+        let context = TraceContext {
+            trace_id: parent
+                .map(|parent| parent.trace_id)
+                .unwrap_or_else(rand::random),
+            span_id: rand::random(),
+            sampled: parent
+                .map(|parent| parent.sampled)
+                .unwrap_or_else(|| should_sample(self.sampling_rate_per_million)),
+        };
+        let mut state = SpanState {
+            context,
+            parent_span_id: parent.map(|parent| parent.span_id),
+            name: attrs.metadata().name().to_string(),
+            kind: SpanKind::Internal,
+            start_time_unix_nano: now_unix_nano(),
+            attributes: Vec::new(),
+            events: Vec::new(),
+            error: None,
+        };
+        state.apply(visitor.fields);
+        span.extensions_mut().insert(state);
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut visitor = FieldVisitor::default();
+        values.record(&mut visitor);
+        let mut extensions = span.extensions_mut();
+        if let Some(state) = extensions.get_mut::<SpanState>() {
+            state.apply(visitor.fields);
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.event_span(event) else {
+            return;
+        };
+        let mut extensions = span.extensions_mut();
+        let Some(state) = extensions.get_mut::<SpanState>() else {
+            return;
+        };
+        if !state.context.sampled || state.events.len() >= MAX_EVENTS_PER_SPAN {
+            return;
+        }
+
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        let mut name = event.metadata().name().to_string();
+        let mut attributes = vec![(
+            "level".to_string(),
+            AttributeValue::String(event.metadata().level().as_str().to_string()),
+        )];
+        for (key, value) in visitor.fields {
+            match (key.as_str(), value) {
+                ("message", AttributeValue::String(message)) => name = message,
+                (_, value) => attributes.push((key, value)),
+            }
+        }
+        if *event.metadata().level() == Level::ERROR && state.error.is_none() {
+            state.error = Some(name.clone());
+        }
+        state.events.push(SpanEventRecord {
+            time_unix_nano: now_unix_nano(),
+            name,
+            attributes,
+        });
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let Some(state) = span.extensions_mut().remove::<SpanState>() else {
+            return;
+        };
+        if !state.context.sampled {
+            return;
+        }
+        self.queue.push(SpanRecord {
+            trace_id: state.context.trace_id,
+            span_id: state.context.span_id,
+            parent_span_id: state.parent_span_id,
+            name: state.name,
+            kind: state.kind,
+            start_time_unix_nano: state.start_time_unix_nano,
+            end_time_unix_nano: now_unix_nano(),
+            attributes: state.attributes,
+            events: state.events,
+            error: state.error,
+        });
+    }
+}
+
+fn should_sample(sampling_rate_per_million: u32) -> bool {
+    if sampling_rate_per_million == 0 {
+        return false;
+    }
+    if sampling_rate_per_million >= 1_000_000 {
+        return true;
+    }
+    let threshold = sampling_rate_per_million as u64;
+    let sample = rand::random::<u64>() % 1_000_000;
+    sample < threshold
+}
+
+#[derive(Default)]
+struct FieldVisitor {
+    fields: Vec<(String, AttributeValue)>,
+}
+
+impl FieldVisitor {
+    fn push(&mut self, field: &Field, value: AttributeValue) {
+        self.fields.push((field.name().to_string(), value));
+    }
+}
+
+impl Visit for FieldVisitor {
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.push(field, AttributeValue::Bool(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.push(field, AttributeValue::Int(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.push(
+            field,
+            i64::try_from(value)
+                .map(AttributeValue::Int)
+                .unwrap_or_else(|_| AttributeValue::String(value.to_string())),
+        );
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.push(field, AttributeValue::Double(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if !value.is_empty() {
+            self.push(field, AttributeValue::String(value.to_string()));
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        let value = format!("{value:?}");
+        if !value.is_empty() {
+            self.push(field, AttributeValue::String(value));
+        }
+    }
+}
+
+/// 为每个入站 gRPC 请求创建 server span，并以请求头中的 `traceparent` 作为远端父 span。
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GrpcTraceLayer;
+
+impl<S> TowerLayer<S> for GrpcTraceLayer {
+    type Service = GrpcTraceService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        GrpcTraceService { inner }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GrpcTraceService<S> {
+    inner: S,
+}
+
+impl<S, ReqBody, ResBody> Service<http::Request<ReqBody>> for GrpcTraceService<S>
+where
+    S: Service<http::Request<ReqBody>, Response = http::Response<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future =
+        Pin<Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<ReqBody>) -> Self::Future {
+        let span = grpc_request_span(&request);
+        let future = span.in_scope(|| self.inner.call(request));
+        let recorder = span.clone();
+        Box::pin(
+            async move {
+                let result = future.await;
+                let code = match &result {
+                    Ok(response) => response
+                        .headers()
+                        .get("grpc-status")
+                        .and_then(|value| value.to_str().ok())
+                        .and_then(|value| value.parse::<i64>().ok())
+                        .unwrap_or(0),
+                    Err(_) => tonic::Code::Internal as i64,
+                };
+                recorder.record("rpc.grpc.status_code", code);
+                if code != 0 {
+                    recorder.record(
+                        "otel.status_message",
+                        format!("{:?}", tonic::Code::from(code as i32)).as_str(),
+                    );
+                }
+                result
+            }
+            .instrument(span),
+        )
+    }
+}
+
+fn grpc_request_span<B>(request: &http::Request<B>) -> tracing::Span {
+    let path = request.uri().path().trim_start_matches('/');
+    let (service, method) = path.split_once('/').unwrap_or((path, ""));
+    let traceparent = request
+        .headers()
+        .get(TRACEPARENT_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    tracing::info_span!(
+        target: "crius::trace",
+        "grpc.request",
+        otel.name = path,
+        otel.kind = "server",
+        rpc.system = "grpc",
+        rpc.service = service,
+        rpc.method = method,
+        traceparent = traceparent,
+        rpc.grpc.status_code = tracing::field::Empty,
+        otel.status_message = tracing::field::Empty,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::mpsc;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    const REMOTE: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+    fn capture_layer(
+        sampling_rate_per_million: u32,
+    ) -> (TraceExportLayer, mpsc::Receiver<SpanRecord>) {
+        let (queue, receiver) = SpanQueue::for_test(64);
+        (
+            TraceExportLayer {
+                queue,
+                sampling_rate_per_million,
+            },
+            receiver,
+        )
+    }
+
+    fn drain(receiver: &mut mpsc::Receiver<SpanRecord>) -> Vec<SpanRecord> {
+        let mut spans = Vec::new();
+        while let Ok(span) = receiver.try_recv() {
+            spans.push(span);
+        }
+        spans
+    }
+
+    #[test]
+    fn sampler_bounds_are_stable() {
+        assert!(!should_sample(0));
+        assert!(should_sample(1_000_000));
+    }
+
+    #[test]
+    fn spans_inherit_remote_parent_and_nest() {
+        let (layer, mut receiver) = capture_layer(0);
+        let subscriber = tracing_subscriber::registry().with(layer);
+        let mut propagated = None;
+        tracing::subscriber::with_default(subscriber, || {
+            let root = tracing::info_span!(
+                "grpc.request",
+                otel.name = "runtime.v1.RuntimeService/RunPodSandbox",
+                otel.kind = "server",
+                traceparent = REMOTE,
+            );
+            let _root = root.enter();
+            let phase = phase_span("sandbox", "createPodSandbox", "pod-1");
+            let _phase = phase.enter();
+            tracing::error!(attempt = 1, "network setup failed");
+            propagated = current_traceparent();
+        });
+
+        let spans = drain(&mut receiver);
+        assert_eq!(spans.len(), 2);
+        let (phase, root) = (&spans[0], &spans[1]);
+        let remote = TraceContext::parse(REMOTE).unwrap();
+        assert_eq!(root.name, "runtime.v1.RuntimeService/RunPodSandbox");
+        assert_eq!(root.kind, SpanKind::Server);
+        assert_eq!(root.trace_id, remote.trace_id);
+        assert_eq!(root.parent_span_id, Some(remote.span_id));
+        assert!(root
+            .attributes
+            .iter()
+            .all(|(key, _)| key != TRACEPARENT_HEADER));
+
+        assert_eq!(phase.name, "sandbox.createPodSandbox");
+        assert_eq!(phase.trace_id, remote.trace_id);
+        assert_eq!(phase.parent_span_id, Some(root.span_id));
+        assert_eq!(phase.error.as_deref(), Some("network setup failed"));
+        assert_eq!(phase.events[0].name, "network setup failed");
+        assert!(phase.attributes.contains(&(
+            "crius.id".to_string(),
+            AttributeValue::String("pod-1".to_string())
+        )));
+
+        let propagated = TraceContext::parse(&propagated.unwrap()).unwrap();
+        assert_eq!(propagated.trace_id, remote.trace_id);
+        assert_eq!(propagated.span_id, phase.span_id);
+    }
+
+    #[test]
+    fn unsampled_roots_are_not_exported_but_still_propagate() {
+        let (layer, mut receiver) = capture_layer(0);
+        let subscriber = tracing_subscriber::registry().with(layer);
+        let mut propagated = None;
+        tracing::subscriber::with_default(subscriber, || {
+            let span = phase_span("image", "pull", "busybox");
+            let _entered = span.enter();
+            propagated = current_traceparent();
+        });
+
+        assert!(drain(&mut receiver).is_empty());
+        assert!(!TraceContext::parse(&propagated.unwrap()).unwrap().sampled);
+    }
+
+    #[test]
+    fn current_traceparent_falls_back_to_thread_remote_context() {
+        assert!(current_traceparent().is_none());
+        let _guard = enter_remote_context(Some(REMOTE));
+        assert_eq!(current_traceparent().as_deref(), Some(REMOTE));
+    }
+
+    #[tokio::test]
+    async fn grpc_layer_extracts_traceparent_and_records_status() {
+        let (layer, mut receiver) = capture_layer(0);
+        let _default = tracing::subscriber::set_default(tracing_subscriber::registry().with(layer));
+        let mut service = GrpcTraceLayer.layer(tower::service_fn(
+            |_request: http::Request<()>| async move {
+                let child_parent = current_traceparent();
+                Ok::<_, std::convert::Infallible>(
+                    http::Response::builder()
+                        .header("grpc-status", "5")
+                        .header("x-child-parent", child_parent.unwrap_or_default())
+                        .body(())
+                        .unwrap(),
+                )
+            },
+        ));
+        let request = http::Request::builder()
+            .uri("/runtime.v1.RuntimeService/ContainerStatus")
+            .header(TRACEPARENT_HEADER, REMOTE)
+            .body(())
+            .unwrap();
+
+        let response = service.call(request).await.unwrap();
+
+        let spans = drain(&mut receiver);
+        assert_eq!(spans.len(), 1);
+        let span = &spans[0];
+        assert_eq!(span.name, "runtime.v1.RuntimeService/ContainerStatus");
+        assert_eq!(
+            span.parent_span_id,
+            Some([0x00, 0xf0, 0x67, 0xaa, 0x0b, 0xa9, 0x02, 0xb7])
+        );
+        assert_eq!(span.error.as_deref(), Some("NotFound"));
+        assert!(span
+            .attributes
+            .contains(&("rpc.grpc.status_code".to_string(), AttributeValue::Int(5))));
+        let child_parent = response.headers()["x-child-parent"].to_str().unwrap();
+        assert_eq!(
+            TraceContext::parse(child_parent).unwrap().span_id,
+            span.span_id
+        );
+    }
+}
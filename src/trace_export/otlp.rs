@@ -0,0 +1,386 @@
+//! OTLP trace 负载编码
+//!
+//! 只定义导出 span 所需的 `opentelemetry.proto.collector.trace.v1` 消息子集，字段号与上游
+//! proto 保持一致；JSON 编码遵循 OTLP/JSON 映射（ID 为十六进制，64 位整数为字符串）。
+
+use super::propagation::encode_hex;
+use serde_json::{json, Value};
+
+/// 已结束、待导出的 span。
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SpanRecord {
+    pub trace_id: [u8; 16],
+    pub span_id: [u8; 8],
+    pub parent_span_id: Option<[u8; 8]>,
+    pub name: String,
+    pub kind: SpanKind,
+    pub start_time_unix_nano: u64,
+    pub end_time_unix_nano: u64,
+    pub attributes: Vec<(String, AttributeValue)>,
+    pub events: Vec<SpanEventRecord>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SpanEventRecord {
+    pub time_unix_nano: u64,
+    pub name: String,
+    pub attributes: Vec<(String, AttributeValue)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum AttributeValue {
+    String(String),
+    Bool(bool),
+    Int(i64),
+    Double(f64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SpanKind {
+    Internal = 1,
+    Server = 2,
+    Client = 3,
+}
+
+impl SpanKind {
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        match value {
+            "internal" => Some(Self::Internal),
+            "server" => Some(Self::Server),
+            "client" => Some(Self::Client),
+            _ => None,
+        }
+    }
+}
+
+/// 导出负载的 resource 与 instrumentation scope 描述。
+#[derive(Debug, Clone)]
+pub(crate) struct ExportResource {
+    pub service_name: String,
+    pub service_version: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct ExportTraceServiceRequest {
+    #[prost(message, repeated, tag = "1")]
+    pub resource_spans: Vec<ResourceSpans>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct ResourceSpans {
+    #[prost(message, optional, tag = "1")]
+    pub resource: Option<Resource>,
+    #[prost(message, repeated, tag = "2")]
+    pub scope_spans: Vec<ScopeSpans>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct Resource {
+    #[prost(message, repeated, tag = "1")]
+    pub attributes: Vec<KeyValue>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct ScopeSpans {
+    #[prost(message, optional, tag = "1")]
+    pub scope: Option<InstrumentationScope>,
+    #[prost(message, repeated, tag = "2")]
+    pub spans: Vec<Span>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct InstrumentationScope {
+    #[prost(string, tag = "1")]
+    pub name: String,
+    #[prost(string, tag = "2")]
+    pub version: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct Span {
+    #[prost(bytes = "vec", tag = "1")]
+    pub trace_id: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub span_id: Vec<u8>,
+    #[prost(bytes = "vec", tag = "4")]
+    pub parent_span_id: Vec<u8>,
+    #[prost(string, tag = "5")]
+    pub name: String,
+    #[prost(int32, tag = "6")]
+    pub kind: i32,
+    #[prost(fixed64, tag = "7")]
+    pub start_time_unix_nano: u64,
+    #[prost(fixed64, tag = "8")]
+    pub end_time_unix_nano: u64,
+    #[prost(message, repeated, tag = "9")]
+    pub attributes: Vec<KeyValue>,
+    #[prost(message, repeated, tag = "11")]
+    pub events: Vec<SpanEvent>,
+    #[prost(message, optional, tag = "15")]
+    pub status: Option<Status>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct SpanEvent {
+    #[prost(fixed64, tag = "1")]
+    pub time_unix_nano: u64,
+    #[prost(string, tag = "2")]
+    pub name: String,
+    #[prost(message, repeated, tag = "3")]
+    pub attributes: Vec<KeyValue>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct Status {
+    #[prost(string, tag = "2")]
+    pub message: String,
+    #[prost(int32, tag = "3")]
+    pub code: i32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct KeyValue {
+    #[prost(string, tag = "1")]
+    pub key: String,
+    #[prost(message, optional, tag = "2")]
+    pub value: Option<AnyValue>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct AnyValue {
+    #[prost(oneof = "any_value::Value", tags = "1, 2, 3, 4")]
+    pub value: Option<any_value::Value>,
+}
+
+pub(crate) mod any_value {
+    // 变体名与 OTLP proto 中的 oneof 字段名保持一致。
+    #[allow(clippy::enum_variant_names)]
+    #[derive(Clone, PartialEq, prost::Oneof)]
+    pub(crate) enum Value {
+        #[prost(string, tag = "1")]
+        StringValue(String),
+        #[prost(bool, tag = "2")]
+        BoolValue(bool),
+        #[prost(int64, tag = "3")]
+        IntValue(i64),
+        #[prost(double, tag = "4")]
+        DoubleValue(f64),
+    }
+}
+
+const STATUS_CODE_ERROR: i32 = 2;
+
+fn key_values(attributes: &[(String, AttributeValue)]) -> Vec<KeyValue> {
+    attributes
+        .iter()
+        .map(|(key, value)| KeyValue {
+            key: key.clone(),
+            value: Some(AnyValue {
+                value: Some(match value {
+                    AttributeValue::String(value) => any_value::Value::StringValue(value.clone()),
+                    AttributeValue::Bool(value) => any_value::Value::BoolValue(*value),
+                    AttributeValue::Int(value) => any_value::Value::IntValue(*value),
+                    AttributeValue::Double(value) => any_value::Value::DoubleValue(*value),
+                }),
+            }),
+        })
+        .collect()
+}
+
+fn resource_attributes(resource: &ExportResource) -> Vec<(String, AttributeValue)> {
+    vec![
+        (
+            "service.name".to_string(),
+            AttributeValue::String(resource.service_name.clone()),
+        ),
+        (
+            "service.version".to_string(),
+            AttributeValue::String(resource.service_version.clone()),
+        ),
+    ]
+}
+
+pub(crate) fn export_request(
+    resource: &ExportResource,
+    spans: &[SpanRecord],
+) -> ExportTraceServiceRequest {
+    let spans = spans
+        .iter()
+        .map(|span| Span {
+            trace_id: span.trace_id.to_vec(),
+            span_id: span.span_id.to_vec(),
+            parent_span_id: span
+                .parent_span_id
+                .map(|id| id.to_vec())
+                .unwrap_or_default(),
+            name: span.name.clone(),
+            kind: span.kind as i32,
+            start_time_unix_nano: span.start_time_unix_nano,
+            end_time_unix_nano: span.end_time_unix_nano,
+            attributes: key_values(&span.attributes),
+            events: span
+                .events
+                .iter()
+                .map(|event| SpanEvent {
+                    time_unix_nano: event.time_unix_nano,
+                    name: event.name.clone(),
+                    attributes: key_values(&event.attributes),
+                })
+                .collect(),
+            status: span.error.as_ref().map(|message| Status {
+                message: message.clone(),
+                code: STATUS_CODE_ERROR,
+            }),
+        })
+        .collect();
+    ExportTraceServiceRequest {
+        resource_spans: vec![ResourceSpans {
+            resource: Some(Resource {
+                attributes: key_values(&resource_attributes(resource)),
+            }),
+            scope_spans: vec![ScopeSpans {
+                scope: Some(InstrumentationScope {
+                    name: "crius".to_string(),
+                    version: resource.service_version.clone(),
+                }),
+                spans,
+            }],
+        }],
+    }
+}
+
+pub(crate) fn encode_protobuf(resource: &ExportResource, spans: &[SpanRecord]) -> Vec<u8> {
+    prost::Message::encode_to_vec(&export_request(resource, spans))
+}
+
+fn json_attributes(attributes: &[(String, AttributeValue)]) -> Value {
+    Value::Array(
+        attributes
+            .iter()
+            .map(|(key, value)| {
+                let value = match value {
+                    AttributeValue::String(value) => json!({ "stringValue": value }),
+                    AttributeValue::Bool(value) => json!({ "boolValue": value }),
+                    AttributeValue::Int(value) => json!({ "intValue": value.to_string() }),
+                    AttributeValue::Double(value) => json!({ "doubleValue": value }),
+                };
+                json!({ "key": key, "value": value })
+            })
+            .collect(),
+    )
+}
+
+pub(crate) fn encode_json(resource: &ExportResource, spans: &[SpanRecord]) -> String {
+    let spans = spans
+        .iter()
+        .map(|span| {
+            let mut value = json!({
+                "traceId": encode_hex(&span.trace_id),
+                "spanId": encode_hex(&span.span_id),
+                "name": span.name,
+                "kind": span.kind as i32,
+                "startTimeUnixNano": span.start_time_unix_nano.to_string(),
+                "endTimeUnixNano": span.end_time_unix_nano.to_string(),
+                "attributes": json_attributes(&span.attributes),
+                "events": span.events.iter().map(|event| json!({
+                    "timeUnixNano": event.time_unix_nano.to_string(),
+                    "name": event.name,
+                    "attributes": json_attributes(&event.attributes),
+                })).collect::<Vec<_>>(),
+            });
+            if let Some(parent) = span.parent_span_id {
+                value["parentSpanId"] = json!(encode_hex(&parent));
+            }
+            if let Some(message) = &span.error {
+                value["status"] = json!({ "code": STATUS_CODE_ERROR, "message": message });
+            }
+            value
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "resourceSpans": [{
+            "resource": { "attributes": json_attributes(&resource_attributes(resource)) },
+            "scopeSpans": [{
+                "scope": { "name": "crius", "version": resource.service_version },
+                "spans": spans,
+            }],
+        }],
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost::Message;
+
+    fn sample_span() -> SpanRecord {
+        SpanRecord {
+            trace_id: [0x11; 16],
+            span_id: [0x22; 8],
+            parent_span_id: Some([0x33; 8]),
+            name: "cni.ADD".to_string(),
+            kind: SpanKind::Internal,
+            start_time_unix_nano: 1_000,
+            end_time_unix_nano: 2_500,
+            attributes: vec![
+                (
+                    "crius.id".to_string(),
+                    AttributeValue::String("pod-1".to_string()),
+                ),
+                ("attempt".to_string(), AttributeValue::Int(2)),
+            ],
+            events: vec![SpanEventRecord {
+                time_unix_nano: 1_500,
+                name: "plugin chain finished".to_string(),
+                attributes: Vec::new(),
+            }],
+            error: Some("plugin failed".to_string()),
+        }
+    }
+
+    fn resource() -> ExportResource {
+        ExportResource {
+            service_name: "crius".to_string(),
+            service_version: "0.1.0".to_string(),
+        }
+    }
+
+    #[test]
+    fn protobuf_payload_decodes_as_export_trace_service_request() {
+        let payload = encode_protobuf(&resource(), &[sample_span()]);
+        let decoded = ExportTraceServiceRequest::decode(payload.as_slice()).unwrap();
+        let span = &decoded.resource_spans[0].scope_spans[0].spans[0];
+        assert_eq!(span.trace_id, vec![0x11; 16]);
+        assert_eq!(span.parent_span_id, vec![0x33; 8]);
+        assert_eq!(span.end_time_unix_nano, 2_500);
+        assert_eq!(span.status.as_ref().unwrap().code, STATUS_CODE_ERROR);
+        assert_eq!(
+            span.attributes[1].value.as_ref().unwrap().value,
+            Some(any_value::Value::IntValue(2))
+        );
+        assert_eq!(
+            decoded.resource_spans[0]
+                .resource
+                .as_ref()
+                .unwrap()
+                .attributes[0]
+                .key,
+            "service.name"
+        );
+    }
+
+    #[test]
+    fn json_payload_uses_hex_ids_and_string_integers() {
+        let payload: Value =
+            serde_json::from_str(&encode_json(&resource(), &[sample_span()])).unwrap();
+        let span = &payload["resourceSpans"][0]["scopeSpans"][0]["spans"][0];
+        assert_eq!(span["traceId"], "11111111111111111111111111111111");
+        assert_eq!(span["parentSpanId"], "3333333333333333");
+        assert_eq!(span["startTimeUnixNano"], "1000");
+        assert_eq!(span["attributes"][1]["value"]["intValue"], "2");
+        assert_eq!(span["status"]["code"], 2);
+        assert_eq!(span["events"][0]["timeUnixNano"], "1500");
+    }
+}
@@ -0,0 +1,151 @@
+//! W3C Trace Context（`traceparent`）解析与跨进程传播。
+
+use std::cell::RefCell;
+
+/// `traceparent` 头中携带的 span 上下文。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceContext {
+    pub trace_id: [u8; 16],
+    pub span_id: [u8; 8],
+    pub sampled: bool,
+}
+
+impl TraceContext {
+    /// 解析 `00-<trace-id>-<span-id>-<flags>`；版本 `ff`、全零 ID 或格式错误时返回 `None`。
+    /// 未知的更高版本按规范只读取前四段。
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut parts = value.trim().split('-');
+        let version = parts.next()?;
+        let trace_id = parts.next()?;
+        let span_id = parts.next()?;
+        let flags = parts.next()?;
+        if version.len() != 2 || version.eq_ignore_ascii_case("ff") {
+            return None;
+        }
+        let version = u8::from_str_radix(version, 16).ok()?;
+        if version == 0 && parts.next().is_some() {
+            return None;
+        }
+        if flags.len() != 2 {
+            return None;
+        }
+        let flags = u8::from_str_radix(flags, 16).ok()?;
+        let trace_id = decode_hex::<16>(trace_id)?;
+        let span_id = decode_hex::<8>(span_id)?;
+        if trace_id == [0; 16] || span_id == [0; 8] {
+            return None;
+        }
+        Some(Self {
+            trace_id,
+            span_id,
+            sampled: flags & 0x01 == 0x01,
+        })
+    }
+
+    pub fn to_traceparent(&self) -> String {
+        format!(
+            "00-{}-{}-{}",
+            encode_hex(&self.trace_id),
+            encode_hex(&self.span_id),
+            if self.sampled { "01" } else { "00" }
+        )
+    }
+
+    /// 32 位小写十六进制 trace ID，与 OTLP 导出及 metrics exemplar 中的取值一致。
+    pub fn trace_id_hex(&self) -> String {
+        encode_hex(&self.trace_id)
+    }
+}
+
+pub(crate) fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn decode_hex<const N: usize>(value: &str) -> Option<[u8; N]> {
+    if value.len() != N * 2 || !value.is_ascii() {
+        return None;
+    }
+    let mut bytes = [0u8; N];
+    for (index, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&value[index * 2..index * 2 + 2], 16).ok()?;
+    }
+    Some(bytes)
+}
+
+thread_local! {
+    static REMOTE_CONTEXT: RefCell<Option<TraceContext>> = const { RefCell::new(None) };
+}
+
+/// 在当前线程上设置远端上下文，guard 释放时恢复原值。
+/// 供没有安装 tracing 导出层的进程（如 `crius-shim`）处理单个 RPC 时使用。
+pub fn enter_remote_context(traceparent: Option<&str>) -> RemoteContextGuard {
+    let context = traceparent.and_then(TraceContext::parse);
+    let previous = REMOTE_CONTEXT.with(|slot| slot.replace(context));
+    RemoteContextGuard { previous }
+}
+
+pub struct RemoteContextGuard {
+    previous: Option<TraceContext>,
+}
+
+impl Drop for RemoteContextGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        REMOTE_CONTEXT.with(|slot| *slot.borrow_mut() = previous);
+    }
+}
+
+pub(super) fn remote_context() -> Option<TraceContext> {
+    REMOTE_CONTEXT.with(|slot| *slot.borrow())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+    #[test]
+    fn traceparent_round_trips() {
+        let context = TraceContext::parse(SAMPLE).unwrap();
+        assert!(context.sampled);
+        assert_eq!(
+            context.span_id,
+            [0x00, 0xf0, 0x67, 0xaa, 0x0b, 0xa9, 0x02, 0xb7]
+        );
+        assert_eq!(context.to_traceparent(), SAMPLE);
+    }
+
+    #[test]
+    fn traceparent_rejects_invalid_values() {
+        for value in [
+            "",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e473-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902bz-01",
+        ] {
+            assert!(TraceContext::parse(value).is_none(), "{value}");
+        }
+        assert!(TraceContext::parse(
+            "01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-00-future"
+        )
+        .is_some_and(|context| !context.sampled));
+    }
+
+    #[test]
+    fn remote_context_guard_restores_previous_value() {
+        assert!(remote_context().is_none());
+        {
+            let _outer = enter_remote_context(Some(SAMPLE));
+            {
+                let _inner = enter_remote_context(None);
+                assert!(remote_context().is_none());
+            }
+            assert_eq!(remote_context(), TraceContext::parse(SAMPLE));
+        }
+        assert!(remote_context().is_none());
+    }
+}
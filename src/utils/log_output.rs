@@ -0,0 +1,145 @@
+//! 日志外发的共用部分
+//!
+//! shim 的 `syslog`/`json-file` 日志驱动和守护进程的事件 sink 共用这里的 syslog 地址解析、
+//! RFC 5424 头部字段规整、RFC 6587 分帧以及 `<path>.N` 按大小轮转。
+
+use std::path::{Path, PathBuf};
+
+/// RFC 5424 APP-NAME 的最大长度。
+pub const SYSLOG_APP_NAME_MAX_LEN: usize = 48;
+/// RFC 5424 MSGID 的最大长度。
+pub const SYSLOG_MSG_ID_MAX_LEN: usize = 32;
+
+/// syslog 端点：`unix://`（或绝对路径）、`udp://host:port`、`tcp://host:port`。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyslogAddress {
+    Unix(PathBuf),
+    Udp(String),
+    Tcp(String),
+}
+
+impl SyslogAddress {
+    pub fn parse(raw: &str) -> anyhow::Result<Self> {
+        let raw = raw.trim();
+        if let Some(path) = raw.strip_prefix("unix://") {
+            return Ok(Self::Unix(PathBuf::from(path)));
+        }
+        if let Some(host) = raw.strip_prefix("udp://") {
+            return Ok(Self::Udp(host.to_string()));
+        }
+        if let Some(host) = raw.strip_prefix("tcp://") {
+            return Ok(Self::Tcp(host.to_string()));
+        }
+        if raw.starts_with('/') {
+            return Ok(Self::Unix(PathBuf::from(raw)));
+        }
+        anyhow::bail!(
+            "invalid syslog address {:?}: expected unix://, udp:// or tcp://",
+            raw
+        )
+    }
+}
+
+/// 本机主机名，取不到时为 RFC 5424 的 NILVALUE。
+pub fn syslog_hostname() -> String {
+    nix::unistd::gethostname()
+        .ok()
+        .and_then(|name| name.into_string().ok())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// 把任意字符串规整为 syslog 头部字段：只保留可见 ASCII 并截断，空值用 NILVALUE。
+pub fn syslog_field(value: &str, max_len: usize) -> String {
+    let field = value
+        .chars()
+        .filter(|c| c.is_ascii_graphic())
+        .take(max_len)
+        .collect::<String>();
+    if field.is_empty() {
+        "-".to_string()
+    } else {
+        field
+    }
+}
+
+/// RFC 6587 octet-counting 分帧，用于 TCP 传输。
+pub fn syslog_octet_frame(message: &[u8]) -> Vec<u8> {
+    let mut framed = format!("{} ", message.len()).into_bytes();
+    framed.extend_from_slice(message);
+    framed
+}
+
+/// 第 `index` 个轮转文件 `<path>.<index>`。
+pub fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut rotated = path.as_os_str().to_owned();
+    rotated.push(format!(".{}", index));
+    PathBuf::from(rotated)
+}
+
+/// 把 `<path>.N` 依次后移、当前文件改名为 `<path>.1`，最多保留 `max_files` 个文件（含当前文件）；
+/// `max_files <= 1` 时直接删除当前文件。调用方负责先关闭并在之后重新打开 `path`。
+pub fn rotate_numbered(path: &Path, max_files: usize) -> std::io::Result<()> {
+    if max_files > 1 {
+        for index in (1..max_files - 1).rev() {
+            let from = rotated_path(path, index);
+            if from.exists() {
+                std::fs::rename(&from, rotated_path(path, index + 1))?;
+            }
+        }
+        std::fs::rename(path, rotated_path(path, 1))
+    } else {
+        std::fs::remove_file(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_syslog_addresses_and_normalizes_fields() {
+        assert_eq!(
+            SyslogAddress::parse(" unix:///dev/log ").unwrap(),
+            SyslogAddress::Unix(PathBuf::from("/dev/log"))
+        );
+        assert_eq!(
+            SyslogAddress::parse("/run/syslog.sock").unwrap(),
+            SyslogAddress::Unix(PathBuf::from("/run/syslog.sock"))
+        );
+        assert_eq!(
+            SyslogAddress::parse("tcp://10.0.0.1:601").unwrap(),
+            SyslogAddress::Tcp("10.0.0.1:601".to_string())
+        );
+        assert!(SyslogAddress::parse("10.0.0.1:514").is_err());
+
+        assert_eq!(syslog_field("my app\n", SYSLOG_APP_NAME_MAX_LEN), "myapp");
+        assert_eq!(syslog_field(" \t", SYSLOG_MSG_ID_MAX_LEN), "-");
+        assert_eq!(syslog_field("abcdef", 3), "abc");
+        assert_eq!(syslog_octet_frame(b"<14>1 -"), b"7 <14>1 -");
+    }
+
+    #[test]
+    fn rotate_numbered_shifts_and_caps_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.log");
+        for round in 1..=3 {
+            std::fs::write(&path, format!("{round}")).unwrap();
+            rotate_numbered(&path, 3).unwrap();
+        }
+        assert!(!path.exists());
+        assert_eq!(
+            std::fs::read_to_string(rotated_path(&path, 1)).unwrap(),
+            "3"
+        );
+        assert_eq!(
+            std::fs::read_to_string(rotated_path(&path, 2)).unwrap(),
+            "2"
+        );
+        assert!(!rotated_path(&path, 3).exists());
+
+        std::fs::write(&path, "4").unwrap();
+        rotate_numbered(&path, 1).unwrap();
+        assert!(!path.exists());
+    }
+}
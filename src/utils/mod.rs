@@ -1,3 +1,5 @@
+pub mod log_output;
+
 use std::path::{Path, PathBuf};
 
 use crate::prelude::*;
@@ -261,6 +261,7 @@ impl TaskController for FakeRuntimeBackend {
         _container_id: &str,
         _command: &[String],
         _tty: bool,
+        _overrides: &crius::security::spec_patch::ExecOverrides,
     ) -> Result<i32> {
         Ok(0)
     }
@@ -512,8 +513,15 @@ impl TaskController for DirectTaskRuntimeBackend {
         self.inner.reopen_container_log(container_id)
     }
 
-    fn exec_in_container(&self, container_id: &str, command: &[String], tty: bool) -> Result<i32> {
-        self.inner.exec_in_container(container_id, command, tty)
+    fn exec_in_container(
+        &self,
+        container_id: &str,
+        command: &[String],
+        tty: bool,
+        overrides: &crius::security::spec_patch::ExecOverrides,
+    ) -> Result<i32> {
+        self.inner
+            .exec_in_container(container_id, command, tty, overrides)
     }
 
     fn update_container_resources(
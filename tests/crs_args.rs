@@ -173,6 +173,39 @@ fn parses_logs_options() {
     assert!(logs.timestamps);
 }
 
+#[test]
+fn parses_logs_previous_since_time_and_limit_bytes() {
+    let args = Args::try_parse_from([
+        "crs",
+        "logs",
+        "-p",
+        "--since-time",
+        "2026-06-03T12:00:00Z",
+        "--limit-bytes",
+        "4096",
+        "ctr1",
+    ])
+    .expect("logs options should parse");
+    let Command::Logs(logs) = args.command else {
+        panic!("expected logs command");
+    };
+    assert!(logs.previous);
+    assert_eq!(logs.since_time.as_deref(), Some("2026-06-03T12:00:00Z"));
+    assert_eq!(logs.limit_bytes, Some(4096));
+
+    let error = Args::try_parse_from([
+        "crs",
+        "logs",
+        "--since",
+        "5m",
+        "--since-time",
+        "2026-06-03T12:00:00Z",
+        "ctr1",
+    ])
+    .expect_err("--since and --since-time should conflict");
+    assert_eq!(error.kind(), clap::error::ErrorKind::ArgumentConflict);
+}
+
 #[test]
 fn parses_logs_short_options() {
     let args = Args::try_parse_from(["crs", "logs", "-f", "-n", "10", "-t", "ctr1"])
@@ -388,6 +421,17 @@ fn parses_container_command_arguments() {
     };
     assert!(exec.stream.stdin);
     assert!(exec.stream.tty);
+
+    let args = Args::try_parse_from(["crs", "container", "attach", "--since", "30s", "ctr1"])
+        .expect("container attach scrollback option should parse");
+    let Command::Container(container) = args.command else {
+        panic!("expected container command");
+    };
+    let ContainerCommand::Attach { id, since, .. } = container.command else {
+        panic!("expected container attach command");
+    };
+    assert_eq!(id, "ctr1");
+    assert_eq!(since, Some(Duration::from_secs(30)));
 }
 
 #[test]
@@ -928,6 +972,7 @@ fn parses_every_public_command_minimal_arguments() {
         &["crs", "container", "update", "ctr"],
         &["crs", "container", "reopen-log", "ctr"],
         &["crs", "container", "logs", "ctr"],
+        &["crs", "container", "cp", "ctr:/etc/hosts", "hosts"],
         &["crs", "run", "busybox"],
         &["crs", "events"],
         &["crs", "stats"],
@@ -945,6 +990,26 @@ fn parses_every_public_command_minimal_arguments() {
         &["crs", "debug", "security"],
         &["crs", "debug", "cgroups"],
         &["crs", "debug", "streaming"],
+        &[
+            "crs",
+            "debug",
+            "streaming",
+            "sessions",
+            "--container",
+            "ctr",
+        ],
+        &["crs", "streaming", "kill", "session-id"],
+        &["crs", "streaming", "recordings", "--container", "ctr"],
+        &[
+            "crs",
+            "streaming",
+            "replay",
+            "session-id",
+            "--speed",
+            "2",
+            "--max-idle",
+            "0.5",
+        ],
         &["crs", "debug", "metrics"],
         &["crs", "debug", "tracing"],
         &["crs", "debug", "rootless"],
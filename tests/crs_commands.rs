@@ -11,17 +11,27 @@ use std::{
 use crius::proto::{
     diagnostics::v1::{
         diagnostics_service_server::{DiagnosticsService, DiagnosticsServiceServer},
-        ContainerLogChunk, ContainerLogRequest, ContentGcCandidate, ContentGcRequest,
-        ContentGcResponse, EffectiveConfigRequest, EffectiveConfigResponse, ImageTransferInfo,
-        ImageTransfersRequest, ImageTransfersResponse, NriStatusRequest, NriStatusResponse,
-        RecoveryAction, RecoveryCheckRequest, RecoveryCheckResponse, RecoveryStatusRequest,
-        RecoveryStatusResponse, RuntimeHandlerInfo, RuntimeHandlersRequest,
-        RuntimeHandlersResponse, SecurityStatusRequest, SecurityStatusResponse, ServerInfoRequest,
-        ServerInfoResponse, ShimStatusRequest, ShimStatusResponse,
+        CniPluginUsage, ContainerLogChunk, ContainerLogRequest, ContentGcCandidate,
+        ContentGcRequest, ContentGcResponse, EffectiveConfigRequest, EffectiveConfigResponse,
+        EventRecord, EventSinkInfo, EventSinkStatusRequest, EventSinkStatusResponse,
+        ImageTransferInfo, ImageTransfersRequest, ImageTransfersResponse,
+        KillStreamingSessionRequest, KillStreamingSessionResponse, NriStatusRequest,
+        NriStatusResponse, RecoveryAction, RecoveryCheckRequest, RecoveryCheckResponse,
+        RecoveryStatusRequest, RecoveryStatusResponse, RuntimeComponentUsage,
+        RuntimeFootprintRequest, RuntimeFootprintResponse, RuntimeHandlerInfo,
+        RuntimeHandlersRequest, RuntimeHandlersResponse, SecurityStatusRequest,
+        SecurityStatusResponse, ServerInfoRequest, ServerInfoResponse, ShimStatusRequest,
+        ShimStatusResponse, StreamingAuditSession, StreamingAuditSessionsRequest,
+        StreamingAuditSessionsResponse, StreamingAuditTranscriptChunk,
+        StreamingAuditTranscriptRequest, StreamingSession, StreamingSessionsRequest,
+        StreamingSessionsResponse, WatchEventsRequest,
     },
     local::v1::{
         local_service_server::{LocalService, LocalServiceServer},
-        CreateLocalContainerRequest, CreateLocalContainerResponse,
+        CopyArchiveChunk, CopyFromContainerRequest, CopyToContainerRequest,
+        CopyToContainerResponse, CreateDebugContainerRequest, CreateDebugContainerResponse,
+        CreateLocalContainerRequest, CreateLocalContainerResponse, LocalExecRequest,
+        LocalExecSyncRequest,
     },
     runtime::v1::{
         image_service_server::{ImageService, ImageServiceServer},
@@ -116,6 +126,8 @@ struct MockState {
     last_update_pod_resources: Arc<Mutex<Option<UpdatePodSandboxResourcesRequest>>>,
     last_create_container: Arc<Mutex<Option<CreateContainerRequest>>>,
     last_create_local_container: Arc<Mutex<Option<CreateLocalContainerRequest>>>,
+    last_local_exec_sync: Arc<Mutex<Option<LocalExecSyncRequest>>>,
+    last_debug_container: Arc<Mutex<Option<CreateDebugContainerRequest>>>,
     last_start_container: Arc<Mutex<Option<StartContainerRequest>>>,
     last_stop_container: Arc<Mutex<Option<StopContainerRequest>>>,
     last_remove_container: Arc<Mutex<Option<RemoveContainerRequest>>>,
@@ -140,8 +152,14 @@ struct MockState {
     last_effective_config: Arc<Mutex<Option<EffectiveConfigRequest>>>,
     last_image_transfers: Arc<Mutex<Option<ImageTransfersRequest>>>,
     last_container_log: Arc<Mutex<Option<ContainerLogRequest>>>,
+    last_watch_events: Arc<Mutex<Option<WatchEventsRequest>>>,
     last_recovery_check: Arc<Mutex<Option<RecoveryCheckRequest>>>,
     last_content_gc: Arc<Mutex<Option<ContentGcRequest>>>,
+    last_streaming_sessions: Arc<Mutex<Option<StreamingSessionsRequest>>>,
+    killed_streaming_sessions: Arc<Mutex<Vec<String>>>,
+    last_streaming_recordings: Arc<Mutex<Option<StreamingAuditSessionsRequest>>>,
+    container_copy_root: Arc<Mutex<Option<std::path::PathBuf>>>,
+    last_copy_to_container: Arc<Mutex<Option<(String, String)>>>,
     fail_cri_cni_network: Arc<Mutex<bool>>,
     fail_run_streaming_when_stopped: Arc<Mutex<bool>>,
     short_run_status_polls: Arc<AtomicUsize>,
@@ -203,6 +221,8 @@ impl Default for MockState {
             last_update_pod_resources: Arc::default(),
             last_create_container: Arc::default(),
             last_create_local_container: Arc::default(),
+            last_local_exec_sync: Arc::default(),
+            last_debug_container: Arc::default(),
             last_start_container: Arc::default(),
             last_stop_container: Arc::default(),
             last_remove_container: Arc::default(),
@@ -227,8 +247,14 @@ impl Default for MockState {
             last_effective_config: Arc::default(),
             last_image_transfers: Arc::default(),
             last_container_log: Arc::default(),
+            last_watch_events: Arc::default(),
             last_recovery_check: Arc::default(),
             last_content_gc: Arc::default(),
+            last_streaming_sessions: Arc::default(),
+            killed_streaming_sessions: Arc::default(),
+            last_streaming_recordings: Arc::default(),
+            container_copy_root: Arc::default(),
+            last_copy_to_container: Arc::default(),
             fail_cri_cni_network: Arc::default(),
             fail_run_streaming_when_stopped: Arc::default(),
             short_run_status_polls: Arc::default(),
@@ -1053,7 +1079,11 @@ impl RuntimeService for MockRuntimeService {
                                 "watcherStatus": "running",
                                 "lastReloadAtUnixMillis": 1234,
                                 "lastReloadError": null,
-                                "lastCniWatchError": "template pending"
+                                "lastCniWatchError": "template pending",
+                                "cniLastKnownGood": {
+                                    "generation": 3,
+                                    "loadedNetworks": ["crius-bridge", "crius-macvlan"]
+                                }
                             },
                             "internalServices": {
                                 "introspection": {
@@ -1316,6 +1346,117 @@ impl LocalService for MockLocalService {
             container_id: format!("ctr-local-{image}"),
         }))
     }
+
+    async fn exec(
+        &self,
+        _request: Request<LocalExecRequest>,
+    ) -> Result<Response<ExecResponse>, Status> {
+        Err(Status::unimplemented("local exec is not mocked"))
+    }
+
+    async fn exec_sync(
+        &self,
+        request: Request<LocalExecSyncRequest>,
+    ) -> Result<Response<ExecSyncResponse>, Status> {
+        *self
+            .state
+            .last_local_exec_sync
+            .lock()
+            .expect("last local exec-sync lock") = Some(request.into_inner());
+        Ok(Response::new(ExecSyncResponse {
+            stdout: b"local-stdout".to_vec(),
+            stderr: Vec::new(),
+            exit_code: 0,
+        }))
+    }
+
+    async fn create_debug_container(
+        &self,
+        request: Request<CreateDebugContainerRequest>,
+    ) -> Result<Response<CreateDebugContainerResponse>, Status> {
+        let request = request.into_inner();
+        let target = request.target_container_id.clone();
+        *self
+            .state
+            .last_debug_container
+            .lock()
+            .expect("last debug container lock") = Some(request);
+        if target == "missing" {
+            return Err(Status::not_found("container not found"));
+        }
+        Err(Status::failed_precondition(format!(
+            "container {target} has no running process; debug containers join the namespaces of a running container"
+        )))
+    }
+
+    type CopyFromContainerStream = ReceiverStream<Result<CopyArchiveChunk, Status>>;
+
+    async fn copy_from_container(
+        &self,
+        request: Request<CopyFromContainerRequest>,
+    ) -> Result<Response<Self::CopyFromContainerStream>, Status> {
+        let request = request.into_inner();
+        let root = self.mock_copy_root(&request.container_id)?;
+        let mut data = Vec::new();
+        root.archive(&request.path, &mut data)
+            .map_err(|err| Status::not_found(err.to_string()))?;
+        let (tx, rx) = tokio::sync::mpsc::channel(4);
+        for chunk in data.chunks(1024) {
+            tx.send(Ok(CopyArchiveChunk {
+                data: chunk.to_vec(),
+            }))
+            .await
+            .expect("copy chunk should be queued");
+        }
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    async fn copy_to_container(
+        &self,
+        request: Request<tonic::Streaming<CopyToContainerRequest>>,
+    ) -> Result<Response<CopyToContainerResponse>, Status> {
+        let mut stream = request.into_inner();
+        let first = stream
+            .message()
+            .await?
+            .ok_or_else(|| Status::invalid_argument("empty copy stream"))?;
+        let root = self.mock_copy_root(&first.container_id)?;
+        let mut data = first.data;
+        while let Some(message) = stream.message().await? {
+            data.extend_from_slice(&message.data);
+        }
+        let summary = root
+            .extract(&first.path, data.as_slice())
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
+        *self
+            .state
+            .last_copy_to_container
+            .lock()
+            .expect("last copy lock") = Some((first.container_id, first.path));
+        Ok(Response::new(CopyToContainerResponse {
+            entries: summary.entries,
+            bytes: summary.bytes,
+        }))
+    }
+}
+
+impl MockLocalService {
+    #[allow(clippy::result_large_err)]
+    fn mock_copy_root(
+        &self,
+        container_id: &str,
+    ) -> Result<crius::runtime::container_copy::CopyRoot, Status> {
+        let root = self
+            .state
+            .container_copy_root
+            .lock()
+            .expect("copy root lock")
+            .clone()
+            .filter(|_| container_id == "ctr1")
+            .ok_or_else(|| Status::not_found(format!("container {container_id} not found")))?;
+        crius::runtime::container_copy::CopyRoot::for_directory(&root)
+            .map_err(|err| Status::internal(err.to_string()))
+    }
 }
 
 #[tonic::async_trait]
@@ -1676,6 +1817,8 @@ impl DiagnosticsService for MockDiagnosticsService {
             rootless_enabled: false,
             devices_policy_json: r#"{"defaultAction":"deny"}"#.into(),
             warnings: vec![],
+            rootless_network_mode: "slirp4netns".into(),
+            rootless_port_forwards_json: r#"[{"podId":"pod1","helper":"slirp4netns","protocol":"tcp","hostIp":"0.0.0.0","hostPort":8080,"containerPort":80,"forwardId":1}]"#.into(),
         }))
     }
 
@@ -1777,6 +1920,241 @@ impl DiagnosticsService for MockDiagnosticsService {
         });
         Ok(Response::new(ReceiverStream::new(rx)))
     }
+
+    async fn streaming_sessions(
+        &self,
+        request: Request<StreamingSessionsRequest>,
+    ) -> Result<Response<StreamingSessionsResponse>, Status> {
+        let request = request.into_inner();
+        *self
+            .state
+            .last_streaming_sessions
+            .lock()
+            .expect("last streaming sessions lock") = Some(request.clone());
+        let sessions = [mock_streaming_session("sess-exec", "ctr1", "exec")]
+            .into_iter()
+            .filter(|session| {
+                request.container_id.is_empty() || session.container_id == request.container_id
+            })
+            .collect();
+        Ok(Response::new(StreamingSessionsResponse {
+            sessions,
+            max_sessions: 64,
+            max_sessions_per_container: 8,
+            warnings: vec![],
+        }))
+    }
+
+    async fn kill_streaming_session(
+        &self,
+        request: Request<KillStreamingSessionRequest>,
+    ) -> Result<Response<KillStreamingSessionResponse>, Status> {
+        let session_id = request.into_inner().session_id;
+        if session_id != "sess-exec" {
+            return Err(Status::not_found(format!(
+                "streaming session {session_id} not found"
+            )));
+        }
+        self.state
+            .killed_streaming_sessions
+            .lock()
+            .expect("killed streaming sessions lock")
+            .push(session_id);
+        Ok(Response::new(KillStreamingSessionResponse {
+            session: Some(mock_streaming_session("sess-exec", "ctr1", "exec")),
+        }))
+    }
+
+    async fn streaming_audit_sessions(
+        &self,
+        request: Request<StreamingAuditSessionsRequest>,
+    ) -> Result<Response<StreamingAuditSessionsResponse>, Status> {
+        let request = request.into_inner();
+        *self
+            .state
+            .last_streaming_recordings
+            .lock()
+            .expect("last streaming recordings lock") = Some(request);
+        Ok(Response::new(StreamingAuditSessionsResponse {
+            sessions: vec![StreamingAuditSession {
+                session_id: "sess1".into(),
+                token: "tok1".into(),
+                kind: "exec".into(),
+                container_id: "ctr1".into(),
+                pod_sandbox_id: "pod1".into(),
+                pod_namespace: "default".into(),
+                pod_name: "web".into(),
+                runtime_handler: "runc".into(),
+                client_address: "10.0.0.9:51000".into(),
+                protocol: "websocket/v5.channel.k8s.io".into(),
+                command: vec!["sh".into(), "-c".into(), "id".into()],
+                tty: true,
+                started_at_unix_millis: 1_700_000_000_000,
+                ended_at_unix_millis: 1_700_000_003_000,
+                exit_code_known: true,
+                exit_code: 0,
+                bytes_received: 12,
+                bytes_sent: 48,
+                transcript_path: "/var/log/crius/streaming-audit/sess1.cast".into(),
+                transcript_truncated: false,
+            }],
+            audit_enabled: true,
+            record_transcripts: true,
+            warnings: vec![],
+        }))
+    }
+
+    type StreamingAuditTranscriptStream =
+        ReceiverStream<Result<StreamingAuditTranscriptChunk, Status>>;
+
+    async fn streaming_audit_transcript(
+        &self,
+        request: Request<StreamingAuditTranscriptRequest>,
+    ) -> Result<Response<Self::StreamingAuditTranscriptStream>, Status> {
+        let session_id = request.into_inner().session_id;
+        if session_id != "sess1" {
+            return Err(Status::not_found(format!(
+                "no transcript recorded for streaming session {session_id}"
+            )));
+        }
+        let (tx, rx) = tokio::sync::mpsc::channel(2);
+        tokio::spawn(async move {
+            let _ = tx
+                .send(Ok(StreamingAuditTranscriptChunk {
+                    data: b"{\"version\":2,\"width\":80,\"height\":24}\n[0.0,\"i\",\"id\\r\"]\n"
+                        .to_vec(),
+                }))
+                .await;
+            let _ = tx
+                .send(Ok(StreamingAuditTranscriptChunk {
+                    data: b"[0.01,\"o\",\"uid=0(root)\\r\\n\"]\n".to_vec(),
+                }))
+                .await;
+        });
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+    type WatchEventsStream = ReceiverStream<Result<EventRecord, Status>>;
+
+    async fn watch_events(
+        &self,
+        request: Request<WatchEventsRequest>,
+    ) -> Result<Response<Self::WatchEventsStream>, Status> {
+        *self
+            .state
+            .last_watch_events
+            .lock()
+            .expect("last watch events lock") = Some(request.into_inner());
+        let (tx, rx) = tokio::sync::mpsc::channel(4);
+        tokio::spawn(async move {
+            for (seq, kind, severity) in [
+                (5, "cri.container_started", "info"),
+                (6, "container.oom", "warning"),
+            ] {
+                let _ = tx
+                    .send(Ok(EventRecord {
+                        seq,
+                        timestamp_unix_nanos: 1_700_000_000_000_000_000 + seq,
+                        source: "internal".into(),
+                        kind: kind.into(),
+                        subject_kind: "container".into(),
+                        subject_id: "ctr-log-a".into(),
+                        pod_id: "pod-log-b".into(),
+                        namespace: "default".into(),
+                        severity: severity.into(),
+                        details_json: r#"{"reason":"OOMKilled"}"#.into(),
+                    }))
+                    .await;
+            }
+        });
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    async fn event_sink_status(
+        &self,
+        _request: Request<EventSinkStatusRequest>,
+    ) -> Result<Response<EventSinkStatusResponse>, Status> {
+        Ok(Response::new(EventSinkStatusResponse {
+            sinks: vec![
+                EventSinkInfo {
+                    name: "siem".into(),
+                    sink_type: "webhook".into(),
+                    target: "https://siem.example.com/ingest".into(),
+                    healthy: true,
+                    delivered: 12,
+                    last_delivered_unix_nanos: 1_700_000_000_000_000_000,
+                    ..Default::default()
+                },
+                EventSinkInfo {
+                    name: "remote-syslog".into(),
+                    sink_type: "syslog".into(),
+                    target: "tcp://syslog.example.com:601".into(),
+                    healthy: false,
+                    queued: 3,
+                    failed: 2,
+                    dropped: 1,
+                    last_error: "syslog send failed: connection refused".into(),
+                    last_failed_unix_nanos: 1_700_000_060_000_000_000,
+                    ..Default::default()
+                },
+            ],
+            warnings: Vec::new(),
+        }))
+    }
+
+    async fn runtime_footprint(
+        &self,
+        _request: Request<RuntimeFootprintRequest>,
+    ) -> Result<Response<RuntimeFootprintResponse>, Status> {
+        Ok(Response::new(RuntimeFootprintResponse {
+            components: vec![
+                RuntimeComponentUsage {
+                    component: "daemon".into(),
+                    processes: 1,
+                    cpu_seconds: 12.5,
+                    memory_bytes: 96 << 20,
+                    open_fds: 40,
+                    threads: 16,
+                },
+                RuntimeComponentUsage {
+                    component: "shim".into(),
+                    processes: 2,
+                    cpu_seconds: 0.75,
+                    memory_bytes: 8 << 20,
+                    open_fds: 24,
+                    threads: 4,
+                },
+                RuntimeComponentUsage {
+                    component: "pull_cgroup".into(),
+                    processes: 1,
+                    cpu_seconds: 3.0,
+                    memory_bytes: 256 << 20,
+                    ..Default::default()
+                },
+            ],
+            cni_plugins: vec![CniPluginUsage {
+                plugin: "bridge".into(),
+                invocations: 4,
+                failures: 1,
+                duration_seconds: 0.5,
+                in_flight: 0,
+            }],
+            warnings: vec!["1 shim process(es) recorded in the shim ledger are not running".into()],
+            collected_at_unix_nanos: 1_700_000_000_000_000_000,
+        }))
+    }
+}
+
+fn mock_streaming_session(id: &str, container_id: &str, kind: &str) -> StreamingSession {
+    StreamingSession {
+        id: id.into(),
+        container_id: container_id.into(),
+        kind: kind.into(),
+        protocol: "websocket/v5.channel.k8s.io".into(),
+        client_address: "10.0.0.9:51000".into(),
+        started_at_unix_millis: 1_700_000_000_000,
+        bytes_received: 128,
+        bytes_sent: 4096,
+    }
 }
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
@@ -1978,6 +2356,11 @@ async fn config_commands_use_diagnostics_effective_config_and_redact_sensitive_f
     assert_eq!(value["kind"], "ConfigReloadStatus");
     assert_eq!(value["items"][0]["watcher"], "running");
     assert_eq!(value["items"][0]["cniWatcher"], "template pending");
+    assert_eq!(
+        value["items"][0]["cniActive"],
+        "gen 3: crius-bridge,crius-macvlan"
+    );
+    assert_eq!(value["summary"]["cniLastKnownGood"]["generation"], 3);
     assert_eq!(config_requests.load(Ordering::SeqCst), 2);
 }
 
@@ -2280,6 +2663,175 @@ async fn gc_run_execute_returns_failure_for_item_errors() {
     assert!(request.execute);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn streaming_sessions_are_listed_and_killed_through_diagnostics() {
+    let state = MockState::default();
+    let last_sessions = Arc::clone(&state.last_streaming_sessions);
+    let killed = Arc::clone(&state.killed_streaming_sessions);
+    let endpoint = spawn_mock_services(state).await;
+
+    let output = run_crs(
+        endpoint,
+        [
+            "--output",
+            "json",
+            "debug",
+            "streaming",
+            "sessions",
+            "--container",
+            "ctr1",
+        ],
+    );
+    assert_success(&output);
+    let value = stdout_json(&output);
+    assert_eq!(value["kind"], "StreamingSessions");
+    assert_eq!(value["summary"]["count"], 1);
+    assert_eq!(value["summary"]["maxSessionsPerContainer"], 8);
+    assert_eq!(value["items"][0]["id"], "sess-exec");
+    assert_eq!(value["items"][0]["protocol"], "websocket/v5.channel.k8s.io");
+    assert_eq!(value["items"][0]["bytesSent"], 4096);
+    let request = last_sessions
+        .lock()
+        .expect("last streaming sessions lock")
+        .clone()
+        .expect("streaming sessions request");
+    assert_eq!(request.container_id, "ctr1");
+
+    let output = run_crs(
+        endpoint,
+        ["--output", "json", "streaming", "kill", "sess-exec"],
+    );
+    assert_success(&output);
+    let value = stdout_json(&output);
+    assert_eq!(value["kind"], "StreamingSessionKill");
+    assert_eq!(value["items"][0]["containerId"], "ctr1");
+    assert_eq!(
+        *killed.lock().expect("killed streaming sessions lock"),
+        vec!["sess-exec".to_string()]
+    );
+
+    let output = run_crs(endpoint, ["streaming", "kill", "sess-missing"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("sess-missing"));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn streaming_recordings_are_listed_and_replayed_through_diagnostics() {
+    let state = MockState::default();
+    let last_recordings = Arc::clone(&state.last_streaming_recordings);
+    let endpoint = spawn_mock_services(state).await;
+
+    let output = run_crs(
+        endpoint,
+        [
+            "--output",
+            "json",
+            "streaming",
+            "recordings",
+            "--container",
+            "ctr1",
+        ],
+    );
+    assert_success(&output);
+    let value = stdout_json(&output);
+    assert_eq!(value["kind"], "StreamingRecordings");
+    assert_eq!(value["summary"]["count"], 1);
+    assert_eq!(value["summary"]["recordTranscripts"], true);
+    assert_eq!(value["items"][0]["token"], "tok1");
+    assert_eq!(value["items"][0]["podName"], "web");
+    assert_eq!(value["items"][0]["exitCode"], 0);
+    assert_eq!(value["items"][0]["command"][2], "id");
+    let request = last_recordings
+        .lock()
+        .expect("last streaming recordings lock")
+        .clone()
+        .expect("streaming recordings request");
+    assert_eq!(request.container_id, "ctr1");
+
+    let output = run_crs(endpoint, ["streaming", "replay", "sess1", "--raw"]);
+    assert_success(&output);
+    let raw = String::from_utf8_lossy(&output.stdout);
+    assert!(raw.starts_with("{\"version\":2"));
+    assert_eq!(raw.lines().count(), 3);
+
+    let output = run_crs(endpoint, ["streaming", "replay", "sess1", "--speed", "100"]);
+    assert_success(&output);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "uid=0(root)\r\n");
+
+    let output = run_crs(endpoint, ["streaming", "replay", "sess-missing"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("sess-missing"));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn container_cp_streams_archives_in_both_directions() {
+    let container_root = tempfile::tempdir().expect("tempdir should be created");
+    std::fs::create_dir_all(container_root.path().join("etc/app")).unwrap();
+    std::fs::write(
+        container_root.path().join("etc/app/config.yaml"),
+        "debug: true\n",
+    )
+    .unwrap();
+    let state = MockState::default();
+    *state.container_copy_root.lock().unwrap() = Some(container_root.path().to_path_buf());
+    let last_copy = Arc::clone(&state.last_copy_to_container);
+    let endpoint = spawn_mock_services(state).await;
+
+    let local = tempfile::tempdir().expect("tempdir should be created");
+    let copied = local.path().join("app-copy");
+    let output = run_crs(
+        endpoint,
+        [
+            "--output",
+            "json",
+            "container",
+            "cp",
+            "ctr1:/etc/app",
+            copied.to_str().unwrap(),
+        ],
+    );
+    assert_success(&output);
+    let value = stdout_json(&output);
+    assert_eq!(value["kind"], "ContainerCopy");
+    assert_eq!(value["summary"]["entries"], 2);
+    assert_eq!(
+        std::fs::read_to_string(copied.join("config.yaml")).unwrap(),
+        "debug: true\n"
+    );
+
+    std::fs::write(copied.join("extra.txt"), "hello").unwrap();
+    let output = run_crs(
+        endpoint,
+        [
+            "container",
+            "cp",
+            copied.join("extra.txt").to_str().unwrap(),
+            "ctr1:/etc/app",
+        ],
+    );
+    assert_success(&output);
+    assert_eq!(
+        std::fs::read_to_string(container_root.path().join("etc/app/extra.txt")).unwrap(),
+        "hello"
+    );
+    assert_eq!(
+        last_copy.lock().unwrap().clone(),
+        Some(("ctr1".to_string(), "/etc/app".to_string()))
+    );
+
+    let output = run_crs(endpoint, ["container", "cp", "ctr1:/etc/app", "-"]);
+    assert_success(&output);
+    assert!(output.stdout.len() % 512 == 0 && output.stdout.len() >= 4 * 512);
+
+    let output = run_crs(endpoint, ["container", "cp", "ctr2:/etc", "out"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("ctr2"));
+
+    let output = run_crs(endpoint, ["container", "cp", "a", "b"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("CONTAINER:PATH"));
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn debug_commands_emit_stable_json_kinds() {
     let endpoint = spawn_mock_services(MockState::default()).await;
@@ -2374,6 +2926,44 @@ async fn debug_security_preserves_raw_devices_policy_on_parse_failure() {
     assert_eq!(value["summary"]["devicesPolicy"]["defaultAction"], "deny");
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn debug_rootless_reports_active_port_forwards() {
+    let endpoint = spawn_mock_services(MockState::default()).await;
+    let output = run_crs(endpoint, ["--output", "json", "debug", "rootless"]);
+
+    assert_success(&output);
+    let value = stdout_json(&output);
+    assert_eq!(value["kind"], "DebugRootless");
+    assert_eq!(value["summary"]["networkMode"], "slirp4netns");
+    let forward = &value["summary"]["portForwards"][0];
+    assert_eq!(forward["podId"], "pod1");
+    assert_eq!(forward["hostPort"], 8080);
+    assert_eq!(forward["containerPort"], 80);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn debug_metrics_reports_event_sink_health() {
+    let endpoint = spawn_mock_services(MockState::default()).await;
+    let output = run_crs(endpoint, ["--output", "json", "debug", "metrics"]);
+
+    assert_success(&output);
+    let value = stdout_json(&output);
+    assert_eq!(value["items"][0]["status"], "warning");
+    let sinks = &value["summary"]["eventSinks"];
+    assert_eq!(sinks[0]["name"], "siem");
+    assert_eq!(sinks[0]["healthy"], true);
+    assert_eq!(sinks[0]["lastDeliveredAt"], "2023-11-14T22:13:20Z");
+    assert_eq!(sinks[1]["type"], "syslog");
+    assert_eq!(sinks[1]["dropped"], 1);
+    assert!(sinks[1]["lastDeliveredAt"].is_null());
+    assert!(value["warnings"]
+        .as_array()
+        .expect("warnings should be an array")
+        .iter()
+        .any(|warning| warning
+            == "event sink remote-syslog is failing: syslog send failed: connection refused"));
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn diagnostics_unavailable_errors_and_fallbacks_are_stable() {
     let endpoint = spawn_mock_cri_services(MockState::default()).await;
@@ -3580,6 +4170,56 @@ async fn container_exec_uses_exec_sync_for_non_interactive_commands() {
     assert_eq!(request.cmd, vec!["echo".to_string(), "ok".to_string()]);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn exec_with_process_overrides_uses_local_exec_sync() {
+    let state = MockState::default();
+    let exec_sync_requests = Arc::clone(&state.exec_sync_requests);
+    let last_local_exec_sync = Arc::clone(&state.last_local_exec_sync);
+    let endpoint = spawn_mock_services(state).await;
+
+    let output = run_crs(
+        endpoint,
+        [
+            "exec",
+            "--env",
+            "MODE=debug",
+            "-w",
+            "/srv",
+            "--user",
+            "1000",
+            "--cap-add",
+            "NET_ADMIN",
+            "ctr1",
+            "--",
+            "id",
+        ],
+    );
+
+    assert_success(&output);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "local-stdout");
+    assert_eq!(exec_sync_requests.load(Ordering::SeqCst), 0);
+    let request = last_local_exec_sync
+        .lock()
+        .expect("last local exec sync lock")
+        .clone()
+        .expect("local exec sync request");
+    let exec = request.request.expect("exec sync request");
+    assert_eq!(exec.container_id, "ctr1");
+    assert_eq!(exec.cmd, vec!["id".to_string()]);
+    let options = request.options.expect("exec options");
+    assert_eq!(options.env, vec!["MODE=debug".to_string()]);
+    assert_eq!(options.workdir, "/srv");
+    let user = options.user.expect("exec user");
+    assert_eq!(user.uid, 1000);
+    assert!(user.gid.is_none());
+    assert!(!options.privileged);
+    assert_eq!(options.add_capabilities, vec!["NET_ADMIN".to_string()]);
+
+    let output = run_crs(endpoint, ["exec", "--user", "nobody", "ctr1", "--", "id"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("numeric UID"));
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn container_exec_with_tty_calls_runtime_exec_and_streams_websocket() {
     let state = MockState::default();
@@ -3660,6 +4300,42 @@ async fn container_logs_streams_text_without_json_envelope() {
     assert!(request.timestamps);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn container_logs_forwards_previous_since_time_and_limit_bytes() {
+    let state = MockState::default();
+    let last_log = Arc::clone(&state.last_container_log);
+    let endpoint = spawn_mock_services(state).await;
+
+    let output = run_crs(
+        endpoint,
+        [
+            "--output",
+            "text",
+            "logs",
+            "--previous",
+            "--since-time",
+            "2026-06-03T12:00:00Z",
+            "--limit-bytes",
+            "4096",
+            "ctr1",
+        ],
+    );
+
+    assert_success(&output);
+    let request = last_log
+        .lock()
+        .expect("last container log lock")
+        .clone()
+        .expect("container log request");
+    assert!(request.previous);
+    assert_eq!(request.limit_bytes, 4096);
+    assert_eq!(request.since_unix_nanos, 1_780_488_000_000_000_000);
+
+    let output = run_crs(endpoint, ["logs", "--since-time", "5m", "ctr1"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("expected RFC3339 timestamp"));
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn container_logs_json_outputs_parseable_ndjson() {
     let endpoint = spawn_mock_services(MockState::default()).await;
@@ -4188,6 +4864,84 @@ async fn events_json_outputs_parseable_ndjson_items() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn events_since_seq_replays_event_log_and_warns_about_evicted_range() {
+    let state = MockState::default();
+    let event_requests = Arc::clone(&state.container_events_requests);
+    let last_watch = Arc::clone(&state.last_watch_events);
+    let endpoint = spawn_mock_services(state).await;
+
+    let output = run_crs(endpoint, ["events", "--since-seq", "2"]);
+
+    assert_success(&output);
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    let stderr = String::from_utf8(output.stderr).expect("stderr should be utf8");
+    assert_eq!(stdout.matches("SEQ").count(), 1);
+    assert!(stdout.contains("container/ctr-log-a"));
+    assert!(stdout.contains("container.oom"));
+    assert!(
+        stderr.contains("events 3..4 are no longer retained"),
+        "{stderr}"
+    );
+    assert_eq!(event_requests.load(Ordering::SeqCst), 0);
+    let request = last_watch
+        .lock()
+        .expect("last watch events lock")
+        .clone()
+        .expect("watch events request");
+    assert_eq!(request.since_seq, 2);
+    assert!(!request.follow);
+    assert!(!request.from_latest);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn events_follow_filters_are_forwarded_and_json_keeps_sequence() {
+    let state = MockState::default();
+    let last_watch = Arc::clone(&state.last_watch_events);
+    let endpoint = spawn_mock_services(state).await;
+
+    let output = run_crs(
+        endpoint,
+        [
+            "--output",
+            "json",
+            "events",
+            "--follow",
+            "--filter",
+            "kind=container.*",
+            "--filter",
+            "subject=container/ctr-log-a",
+            "--filter",
+            "namespace=default",
+            "--filter",
+            "severity=warning",
+        ],
+    );
+
+    assert_success(&output);
+    let stdout = String::from_utf8(output.stdout).expect("stdout should be utf8");
+    let seqs = stdout
+        .lines()
+        .map(|line| {
+            let value: serde_json::Value = serde_json::from_str(line).expect("event JSON line");
+            value["seq"].as_i64().expect("event seq")
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(seqs, vec![5, 6]);
+    let request = last_watch
+        .lock()
+        .expect("last watch events lock")
+        .clone()
+        .expect("watch events request");
+    assert!(request.follow);
+    assert!(request.from_latest);
+    assert_eq!(request.kinds, vec!["container.*"]);
+    assert_eq!(request.subject_kind, "container");
+    assert_eq!(request.subject_id, "ctr-log-a");
+    assert_eq!(request.namespace, "default");
+    assert_eq!(request.min_severity, "warning");
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn events_sigint_returns_interrupted_exit_code() {
     let state = MockState::default();
@@ -4232,6 +4986,33 @@ async fn top_level_stats_empty_list_returns_successful_summary() {
     assert_eq!(requests.load(Ordering::SeqCst), 1);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn top_level_stats_runtime_reports_component_footprint() {
+    let state = MockState::default();
+    let requests = Arc::clone(&state.list_container_stats_requests);
+    let endpoint = spawn_mock_services(state).await;
+
+    let output = run_crs(endpoint, ["--output", "json", "stats", "--runtime"]);
+
+    assert_success(&output);
+    let value = stdout_json(&output);
+    assert_eq!(value["kind"], "RuntimeFootprint");
+    let items = value["items"].as_array().expect("items");
+    assert_eq!(items.len(), 3);
+    assert_eq!(items[0]["component"], "daemon");
+    assert_eq!(items[0]["openFds"], 40);
+    assert_eq!(items[1]["processes"], 2);
+    assert_eq!(value["summary"]["processes"], 3);
+    assert_eq!(value["summary"]["memoryBytes"], (96u64 + 8) << 20);
+    assert_eq!(value["summary"]["cniPlugins"][0]["plugin"], "bridge");
+    assert_eq!(value["summary"]["cniPlugins"][0]["failures"], 1);
+    assert_eq!(
+        value["warnings"][0],
+        "1 shim process(es) recorded in the shim ledger are not running"
+    );
+    assert_eq!(requests.load(Ordering::SeqCst), 0);
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn container_stats_single_and_list_paths_share_view_shape() {
     let state = MockState::default();
@@ -4855,3 +5636,57 @@ async fn spawn_mock_websocket() -> SocketAddr {
 
     endpoint
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn debug_container_requests_ephemeral_container_next_to_target() {
+    let state = MockState::default();
+    let last_debug_container = Arc::clone(&state.last_debug_container);
+    let endpoint = spawn_mock_services(state).await;
+
+    let output = run_crs(
+        endpoint,
+        [
+            "debug",
+            "container",
+            "ctr1",
+            "--image",
+            "busybox",
+            "--target-rootfs",
+            "--cap-add",
+            "SYS_PTRACE",
+            "-t",
+            "--",
+            "sh",
+        ],
+    );
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("has no running process"));
+    let request = last_debug_container
+        .lock()
+        .expect("last debug container lock")
+        .clone()
+        .expect("debug container request");
+    assert_eq!(request.target_container_id, "ctr1");
+    assert!(request.mount_target_rootfs);
+    let config = request.config.expect("debug container config");
+    assert_eq!(config.image.expect("image").image, "busybox");
+    assert!(config.stdin);
+    assert!(config.tty);
+    let capabilities = config
+        .linux
+        .and_then(|linux| linux.security_context)
+        .and_then(|security| security.capabilities)
+        .expect("capabilities");
+    assert_eq!(
+        capabilities.add_capabilities,
+        vec!["SYS_PTRACE".to_string()]
+    );
+
+    let output = run_crs(
+        endpoint,
+        ["debug", "container", "missing", "--image", "busybox"],
+    );
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("container not found"));
+}
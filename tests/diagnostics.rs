@@ -1,13 +1,18 @@
 use crius::proto::diagnostics::v1::{
     diagnostics_service_client::DiagnosticsServiceClient,
     diagnostics_service_server::{DiagnosticsService, DiagnosticsServiceServer},
-    ContainerLogChunk, ContainerLogRequest, ContentGcCandidate, ContentGcRequest,
-    ContentGcResponse, EffectiveConfigRequest, EffectiveConfigResponse, ImageTransferInfo,
-    ImageTransfersRequest, ImageTransfersResponse, NriStatusRequest, NriStatusResponse,
-    RecoveryAction, RecoveryCheckRequest, RecoveryCheckResponse, RecoveryStatusRequest,
-    RecoveryStatusResponse, RuntimeHandlerInfo, RuntimeHandlersRequest, RuntimeHandlersResponse,
-    SecurityStatusRequest, SecurityStatusResponse, ServerInfoRequest, ServerInfoResponse, ShimInfo,
-    ShimStatusRequest, ShimStatusResponse,
+    CniPluginUsage, ContainerLogChunk, ContainerLogRequest, ContentGcCandidate, ContentGcRequest,
+    ContentGcResponse, EffectiveConfigRequest, EffectiveConfigResponse, EventRecord, EventSinkInfo,
+    EventSinkStatusRequest, EventSinkStatusResponse, ImageTransferInfo, ImageTransfersRequest,
+    ImageTransfersResponse, KillStreamingSessionRequest, KillStreamingSessionResponse,
+    NriStatusRequest, NriStatusResponse, RecoveryAction, RecoveryCheckRequest,
+    RecoveryCheckResponse, RecoveryStatusRequest, RecoveryStatusResponse, RuntimeComponentUsage,
+    RuntimeFootprintRequest, RuntimeFootprintResponse, RuntimeHandlerInfo, RuntimeHandlersRequest,
+    RuntimeHandlersResponse, SecurityStatusRequest, SecurityStatusResponse, ServerInfoRequest,
+    ServerInfoResponse, ShimInfo, ShimStatusRequest, ShimStatusResponse,
+    StreamingAuditSessionsRequest, StreamingAuditSessionsResponse, StreamingAuditTranscriptChunk,
+    StreamingAuditTranscriptRequest, StreamingSession, StreamingSessionsRequest,
+    StreamingSessionsResponse, WatchEventsRequest,
 };
 use tokio_stream::wrappers::TcpListenerStream;
 use tokio_stream::{wrappers::ReceiverStream, StreamExt};
@@ -140,6 +145,8 @@ impl DiagnosticsService for SmokeDiagnostics {
             rootless_enabled: false,
             devices_policy_json: "{}".into(),
             warnings: Vec::new(),
+            rootless_network_mode: String::new(),
+            rootless_port_forwards_json: "[]".into(),
         }))
     }
 
@@ -197,6 +204,112 @@ impl DiagnosticsService for SmokeDiagnostics {
         .expect("receiver should be alive");
         Ok(tonic::Response::new(ReceiverStream::new(rx)))
     }
+
+    async fn streaming_sessions(
+        &self,
+        _request: tonic::Request<StreamingSessionsRequest>,
+    ) -> Result<tonic::Response<StreamingSessionsResponse>, tonic::Status> {
+        Ok(tonic::Response::new(StreamingSessionsResponse {
+            sessions: vec![StreamingSession {
+                id: "session".into(),
+                container_id: "container".into(),
+                kind: "exec".into(),
+                protocol: "spdy/v4.channel.k8s.io".into(),
+                client_address: "127.0.0.1:40000".into(),
+                started_at_unix_millis: 1,
+                bytes_received: 0,
+                bytes_sent: 0,
+            }],
+            max_sessions: 0,
+            max_sessions_per_container: 0,
+            warnings: Vec::new(),
+        }))
+    }
+
+    async fn kill_streaming_session(
+        &self,
+        request: tonic::Request<KillStreamingSessionRequest>,
+    ) -> Result<tonic::Response<KillStreamingSessionResponse>, tonic::Status> {
+        Err(tonic::Status::not_found(format!(
+            "streaming session {} not found",
+            request.into_inner().session_id
+        )))
+    }
+
+    async fn streaming_audit_sessions(
+        &self,
+        _request: tonic::Request<StreamingAuditSessionsRequest>,
+    ) -> Result<tonic::Response<StreamingAuditSessionsResponse>, tonic::Status> {
+        Ok(tonic::Response::new(
+            StreamingAuditSessionsResponse::default(),
+        ))
+    }
+
+    type StreamingAuditTranscriptStream =
+        ReceiverStream<Result<StreamingAuditTranscriptChunk, tonic::Status>>;
+
+    async fn streaming_audit_transcript(
+        &self,
+        request: tonic::Request<StreamingAuditTranscriptRequest>,
+    ) -> Result<tonic::Response<Self::StreamingAuditTranscriptStream>, tonic::Status> {
+        Err(tonic::Status::not_found(format!(
+            "no transcript recorded for streaming session {}",
+            request.into_inner().session_id
+        )))
+    }
+
+    type WatchEventsStream = ReceiverStream<Result<EventRecord, tonic::Status>>;
+
+    async fn watch_events(
+        &self,
+        request: tonic::Request<WatchEventsRequest>,
+    ) -> Result<tonic::Response<Self::WatchEventsStream>, tonic::Status> {
+        let (tx, rx) = tokio::sync::mpsc::channel(1);
+        tx.send(Ok(EventRecord {
+            seq: request.into_inner().since_seq + 1,
+            kind: "container.oom".into(),
+            ..Default::default()
+        }))
+        .await
+        .expect("event should be queued");
+        Ok(tonic::Response::new(ReceiverStream::new(rx)))
+    }
+
+    async fn event_sink_status(
+        &self,
+        _request: tonic::Request<EventSinkStatusRequest>,
+    ) -> Result<tonic::Response<EventSinkStatusResponse>, tonic::Status> {
+        Ok(tonic::Response::new(EventSinkStatusResponse {
+            sinks: vec![EventSinkInfo {
+                name: "siem".into(),
+                sink_type: "webhook".into(),
+                healthy: true,
+                delivered: 3,
+                ..Default::default()
+            }],
+            warnings: Vec::new(),
+        }))
+    }
+
+    async fn runtime_footprint(
+        &self,
+        _request: tonic::Request<RuntimeFootprintRequest>,
+    ) -> Result<tonic::Response<RuntimeFootprintResponse>, tonic::Status> {
+        Ok(tonic::Response::new(RuntimeFootprintResponse {
+            components: vec![RuntimeComponentUsage {
+                component: "daemon".into(),
+                processes: 1,
+                memory_bytes: 64 << 20,
+                ..Default::default()
+            }],
+            cni_plugins: vec![CniPluginUsage {
+                plugin: "bridge".into(),
+                invocations: 2,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }))
+    }
 }
 
 #[tokio::test]
@@ -330,4 +443,37 @@ async fn diagnostics_client_round_trips_every_rpc() {
         .expect("log chunk should be present")
         .expect("log chunk should be ok");
     assert_eq!(chunk.data, b"hello\n");
+
+    let mut events = client
+        .watch_events(WatchEventsRequest {
+            since_seq: 4,
+            ..Default::default()
+        })
+        .await
+        .expect("watch events should succeed")
+        .into_inner();
+    let event = events
+        .next()
+        .await
+        .expect("event record should be present")
+        .expect("event record should be ok");
+    assert_eq!(event.seq, 5);
+    assert_eq!(event.kind, "container.oom");
+
+    let sinks = client
+        .event_sink_status(EventSinkStatusRequest {})
+        .await
+        .expect("event sink status should succeed")
+        .into_inner();
+    assert_eq!(sinks.sinks[0].name, "siem");
+    assert_eq!(sinks.sinks[0].delivered, 3);
+
+    let footprint = client
+        .runtime_footprint(RuntimeFootprintRequest {})
+        .await
+        .expect("runtime footprint should succeed")
+        .into_inner();
+    assert_eq!(footprint.components[0].component, "daemon");
+    assert_eq!(footprint.components[0].memory_bytes, 64 << 20);
+    assert_eq!(footprint.cni_plugins[0].invocations, 2);
 }
@@ -48,12 +48,14 @@ mod rootless_lifecycle {
             monitor_cgroup: String::new(),
             monitor_env: Vec::new(),
             stream_websockets: false,
+            stream_spdy: true,
             allowed_annotations: Vec::new(),
             default_annotations: HashMap::new(),
             privileged_without_host_devices: false,
             privileged_without_host_devices_all_devices_allowed: false,
             container_create_timeout: 30,
             snapshotter: "internal-overlay-untar".to_string(),
+            log_drivers: Vec::new(),
         }
     }
 
@@ -107,12 +109,14 @@ mod rootless_lifecycle {
                 monitor_env: Vec::new(),
                 debug: false,
                 log_to_journald: false,
+                log_drivers: Vec::new(),
                 no_sync_log: false,
                 no_pivot: false,
                 no_new_keyring: false,
                 systemd_cgroup: false,
                 runtime_path,
                 max_container_log_line_size: 4096,
+                attach_scrollback_bytes: 64 * 1024,
                 state_db_path: root_dir.join("crius.db"),
             },
             ..RuntimeConfig::default()
@@ -596,12 +600,14 @@ mod reload_watcher {
             monitor_cgroup: String::new(),
             monitor_env: Vec::new(),
             stream_websockets: false,
+            stream_spdy: true,
             allowed_annotations: Vec::new(),
             default_annotations: HashMap::new(),
             privileged_without_host_devices: false,
             privileged_without_host_devices_all_devices_allowed: false,
             container_create_timeout: 30,
             snapshotter: "internal-overlay-untar".to_string(),
+            log_drivers: Vec::new(),
         }
     }
 
@@ -638,12 +644,14 @@ mod reload_watcher {
                 monitor_env: Vec::new(),
                 debug: false,
                 log_to_journald: false,
+                log_drivers: Vec::new(),
                 no_sync_log: false,
                 no_pivot: false,
                 no_new_keyring: false,
                 systemd_cgroup: false,
                 runtime_path,
                 max_container_log_line_size: 4096,
+                attach_scrollback_bytes: 64 * 1024,
                 state_db_path: root_dir.join("crius.db"),
             },
             ..RuntimeConfig::default()
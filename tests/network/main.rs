@@ -181,3 +181,64 @@ fn gated_real_cni_add_del_reload_has_fixed_entrypoint() {
         "real CNI e2e command failed with {status}"
     );
 }
+
+fn command_available(command: &str) -> bool {
+    std::process::Command::new(command)
+        .arg("--version")
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+#[test]
+fn dual_stack_host_port_rules_land_in_both_families_in_throwaway_netns() {
+    use crius::network::{PortMapping, PortMappingBackend, PortMappingManager, Protocol};
+
+    if !command_available("iptables") || !command_available("ip6tables") {
+        eprintln!("skipping dual-stack hostPort test; iptables/ip6tables not installed");
+        return;
+    }
+
+    // 在独立线程里 unshare 出一次性 netns，规则随线程退出一起销毁。
+    let result = std::thread::spawn(|| {
+        if nix::sched::unshare(nix::sched::CloneFlags::CLONE_NEWNET).is_err() {
+            return None;
+        }
+        let manager = PortMappingManager::new(PortMappingBackend::Iptables).unwrap();
+        for container_ip in ["10.88.0.2", "fd00::2"] {
+            manager
+                .add_port_mapping(&PortMapping {
+                    protocol: Protocol::Tcp,
+                    container_port: 80,
+                    host_port: 8080,
+                    host_ip: None,
+                    container_ip: container_ip.parse().unwrap(),
+                })
+                .unwrap();
+        }
+        let list = |command: &str| {
+            let output = std::process::Command::new(command)
+                .args(["-t", "nat", "-S", "PREROUTING"])
+                .output()
+                .unwrap();
+            String::from_utf8_lossy(&output.stdout).into_owned()
+        };
+        Some((list("iptables"), list("ip6tables")))
+    })
+    .join()
+    .unwrap();
+
+    let Some((v4_rules, v6_rules)) = result else {
+        eprintln!("skipping dual-stack hostPort test because CLONE_NEWNET is unavailable");
+        return;
+    };
+    assert!(
+        v4_rules.contains("--to-destination 10.88.0.2:80"),
+        "{v4_rules}"
+    );
+    assert!(
+        v6_rules.contains("--to-destination [fd00::2]:80"),
+        "{v6_rules}"
+    );
+    assert!(!v4_rules.contains("fd00::2"), "{v4_rules}");
+}
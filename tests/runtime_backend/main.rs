@@ -417,7 +417,12 @@ fn wasm_direct_backend_rejects_unsupported_features_explicitly() {
     backend.start_container("wasm-1").unwrap();
 
     assert!(backend
-        .exec_in_container("wasm-1", &["sh".to_string()], false)
+        .exec_in_container(
+            "wasm-1",
+            &["sh".to_string()],
+            false,
+            &crius::security::spec_patch::ExecOverrides::default(),
+        )
         .unwrap_err()
         .to_string()
         .contains("does not support task operation exec_in_container"));